@@ -0,0 +1,18 @@
+use std::process::Command;
+
+fn main() {
+    // NOTE: The commit hash is shown in the client so players can include it in
+    // bug reports. Builds from a source archive don't have one.
+    let commit_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned());
+
+    println!("cargo:rustc-env=KORANGAR_COMMIT_HASH={commit_hash}");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
@@ -75,7 +75,7 @@ pub fn derive_toggle(token_stream: InterfaceTokenStream) -> InterfaceTokenStream
     }
 }
 
-#[proc_macro_derive(PrototypeElement, attributes(name, hidden_element))]
+#[proc_macro_derive(PrototypeElement, attributes(name, hidden_element, mutable))]
 pub fn derive_prototype_element(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
     let DeriveInput {
         ident,
@@ -92,7 +92,7 @@ pub fn derive_prototype_element(token_stream: InterfaceTokenStream) -> Interface
     }
 }
 
-#[proc_macro_derive(PrototypeWindow, attributes(name, hidden_element, window_title, window_class))]
+#[proc_macro_derive(PrototypeWindow, attributes(name, hidden_element, mutable, window_title, window_class))]
 pub fn derive_prototype_window(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
     let DeriveInput {
         ident,
@@ -1,8 +1,8 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Attribute, DataStruct, Field, LitStr};
+use syn::{Attribute, DataStruct, Field, LitStr, Meta};
 
-use crate::utils::get_unique_attribute;
+use crate::utils::{get_unique_attribute, MutableRange};
 
 pub fn prototype_element_helper(
     data_struct: DataStruct,
@@ -43,7 +43,44 @@ pub fn prototype_element_helper(
             .map(|name: LitStr| name.value())
             .unwrap_or_else(|| str::replace(&field_variable.to_string(), "_", " "));
 
-        initializers.push(quote!(crate::interface::PrototypeElement::to_element(&self.#field_identifier, #display_name.to_string())));
+        let element = quote!(crate::interface::PrototypeElement::to_element(&self.#field_identifier, #display_name.to_string()));
+
+        // NOTE: Mutable fields have to be a `TrackedState`, since the elements
+        // write the edited value back through it. They are only editable in
+        // debug builds.
+        let initializer = match get_unique_attribute(&mut field.attrs, "mutable") {
+            Some(attribute) => {
+                let mutable_element = match attribute.meta {
+                    Meta::Path(..) => quote! {
+                        crate::interface::PrototypeMutableElement::to_mutable_element(&self.#field_identifier, #display_name.to_string(), None)
+                    },
+                    _ => {
+                        let MutableRange { minimum, maximum } = attribute.parse_args().expect("failed to parse mutable range");
+
+                        quote! {
+                            crate::interface::PrototypeMutableRangeElement::to_mutable_range_element(
+                                &self.#field_identifier,
+                                #display_name.to_string(),
+                                #minimum,
+                                #maximum,
+                                None,
+                            )
+                        }
+                    }
+                };
+
+                quote!({
+                    #[cfg(feature = "debug")]
+                    let element = #mutable_element;
+                    #[cfg(not(feature = "debug"))]
+                    let element = #element;
+                    element
+                })
+            }
+            None => element,
+        };
+
+        initializers.push(initializer);
     }
 
     (initializers, is_unnamed, window_title, window_class)
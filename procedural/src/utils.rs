@@ -1,6 +1,6 @@
 use proc_macro2::Punct;
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, Error, LitInt};
+use syn::{Attribute, Error, Expr, LitInt, Token};
 
 #[derive(Clone)]
 pub struct PacketSignature {
@@ -31,6 +31,21 @@ impl Parse for Version {
     }
 }
 
+#[derive(Clone)]
+pub struct MutableRange {
+    pub minimum: Expr,
+    pub maximum: Expr,
+}
+
+impl Parse for MutableRange {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let minimum = input.parse().expect("mutable range must have a minimum");
+        input.parse::<Token![,]>().expect("minimum and maximum must be seperated by commas");
+        let maximum = input.parse().expect("mutable range must have a maximum");
+        Ok(MutableRange { minimum, maximum })
+    }
+}
+
 pub fn get_unique_attribute(attributes: &mut Vec<Attribute>, name: &str) -> Option<Attribute> {
     let mut matching_attributes = attributes.extract_if(|attribute| attribute.path().segments[0].ident == name);
     let return_attribute = matching_attributes.next();
@@ -70,6 +70,19 @@ impl<T: ToBytes, const SIZE: usize> ToBytes for [T; SIZE] {
     }
 }
 
+impl<T: ToBytes> ToBytes for Vec<T> {
+    fn to_bytes(&self) -> ConversionResult<Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        for item in self.iter() {
+            let item = item.to_bytes().trace::<Self>()?;
+            bytes.extend(item);
+        }
+
+        Ok(bytes)
+    }
+}
+
 impl ToBytes for String {
     fn to_bytes(&self) -> ConversionResult<Vec<u8>> {
         Ok(self.bytes().chain(std::iter::once(0)).collect())
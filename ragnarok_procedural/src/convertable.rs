@@ -13,7 +13,7 @@ fn derive_for_struct(
     implement_from: bool,
     implement_to: bool,
 ) -> InterfaceTokenStream {
-    let (from_bytes_implementations, implemented_fields, to_bytes_implementations, delimiter) = byte_convertable_helper(data_struct);
+    let (from_bytes_implementations, implemented_fields, to_bytes_implementations, delimiter) = byte_convertable_helper(data_struct, false);
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
     let instanciate = match delimiter {
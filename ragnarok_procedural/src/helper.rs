@@ -1,6 +1,6 @@
 use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
 use quote::{format_ident, quote};
-use syn::{DataStruct, Field};
+use syn::{DataStruct, Field, LitInt};
 
 use crate::utils::*;
 
@@ -31,7 +31,14 @@ fn remove_self_from_stream(token_stream: TokenStream) -> TokenStream {
     new_stream
 }
 
-pub fn byte_convertable_helper(data_struct: DataStruct) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>, Delimiter) {
+/// Generate the code for reading and writing the fields of a struct. Fields
+/// restricted to a packet version can only be written if `has_packet_version`
+/// is set, in which case the packet version is expected to be in scope as
+/// `packet_version`.
+pub fn byte_convertable_helper(
+    data_struct: DataStruct,
+    has_packet_version: bool,
+) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>, Delimiter) {
     let mut from_bytes_implementations = vec![];
     let mut implemented_fields = vec![];
     let mut to_bytes_implementations = vec![];
@@ -68,9 +75,9 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (Vec<TokenStream>, Ve
             None => quote!(ragnarok_bytes::FromBytes::from_bytes(byte_stream)),
         };
 
-        let to_length_hint = match length_hint {
-            Some(length_hint) => quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(&self.#field_identifier, #length_hint)),
-            None => quote!(ragnarok_bytes::ToBytes::to_bytes(&self.#field_identifier)),
+        let to_length_hint = |value: TokenStream| match &length_hint {
+            Some(length_hint) => quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(#value, #length_hint)),
+            None => quote!(ragnarok_bytes::ToBytes::to_bytes(#value)),
         };
 
         let repeating: Option<TokenStream> = get_unique_attribute(&mut field.attrs, "repeating").map(|attribute| match attribute.meta {
@@ -90,12 +97,30 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (Vec<TokenStream>, Ve
             .map(|version: Version| (version.major, version.minor))
             .map(|(major, minor)| quote!(equals_or_above(#major, #minor)));
 
+        let packet_version_before = get_unique_attribute(&mut field.attrs, "packet_version_before")
+            .map(|attribute| attribute.parse_args::<LitInt>().expect("failed to parse packet version"))
+            .map(|packet_version| quote!(before(#packet_version)));
+
+        let packet_version_since = get_unique_attribute(&mut field.attrs, "packet_version_since")
+            .map(|attribute| attribute.parse_args::<LitInt>().expect("failed to parse packet version"))
+            .map(|packet_version| quote!(since(#packet_version)));
+
         assert!(
-            version_smaller.is_none() || version_equals_or_above.is_none(),
+            [
+                &version_smaller,
+                &version_equals_or_above,
+                &packet_version_before,
+                &packet_version_since
+            ]
+            .into_iter()
+            .filter(|restriction| restriction.is_some())
+            .count()
+                <= 1,
             "version restriction may only be specified once"
         );
         let version_function = version_smaller.or(version_equals_or_above);
         let version_restricted = version_function.is_some();
+        let packet_version_function = packet_version_before.or(packet_version_since);
         let is_repeating = repeating.is_some();
 
         if is_packet_length {
@@ -153,16 +178,43 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (Vec<TokenStream>, Ve
                     };
                 }
             }
-            None => quote!(let #field_variable = #from_implementation;),
+            None => match &packet_version_function {
+                Some(function) => {
+                    quote! {
+                        let is_in_packet_version = byte_stream
+                            .get_metadata::<Self, crate::network::NetworkMetadata>()?
+                            .packet_version
+                            .#function;
+                        let #field_variable = match is_in_packet_version {
+                            true => Some(#from_implementation),
+                            false => None,
+                        };
+                    }
+                }
+                None => quote!(let #field_variable = #from_implementation;),
+            },
         };
 
         // base to byte implementation
-        let to_implementation = match is_repeating || version_restricted {
-            true => quote!({
+        let to_implementation = match &packet_version_function {
+            Some(function) if has_packet_version && !is_repeating => {
+                let to_length_hint = to_length_hint(quote!(value));
+
+                quote!(match packet_version.#function {
+                    true => match &self.#field_identifier {
+                        Some(value) => ragnarok_bytes::ConversionResultExt::trace::<Self>(#to_length_hint)?,
+                        None => Err(ragnarok_bytes::ConversionError::from_message("field required by the packet version is not set"))?,
+                    },
+                    false => Vec::new(),
+                }
+                .as_slice())
+            }
+            _ if is_repeating || version_restricted || packet_version_function.is_some() => quote!({
                 panic!("implement for to_bytes as well");
                 [0u8].as_slice()
             }),
-            false => {
+            _ => {
+                let to_length_hint = to_length_hint(quote!(&self.#field_identifier));
                 quote!(ragnarok_bytes::ConversionResultExt::trace::<Self>(#to_length_hint)?.as_slice())
             }
         };
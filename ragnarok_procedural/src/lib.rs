@@ -42,7 +42,9 @@ pub fn derive_fixed_byte_size(token_stream: InterfaceTokenStream) -> InterfaceTo
         numeric_value,
         version,
         version_smaller,
-        version_equals_or_above
+        version_equals_or_above,
+        packet_version_before,
+        packet_version_since
     )
 )]
 pub fn derive_byte_convertable(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
@@ -72,7 +74,9 @@ pub fn derive_byte_convertable(token_stream: InterfaceTokenStream) -> InterfaceT
         numeric_value,
         version,
         version_smaller,
-        version_equals_or_above
+        version_equals_or_above,
+        packet_version_before,
+        packet_version_since
     )
 )]
 pub fn derive_from_bytes(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
@@ -102,7 +106,9 @@ pub fn derive_from_bytes(token_stream: InterfaceTokenStream) -> InterfaceTokenSt
         numeric_value,
         version,
         version_smaller,
-        version_equals_or_above
+        version_equals_or_above,
+        packet_version_before,
+        packet_version_since
     )
 )]
 pub fn derive_to_bytes(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
@@ -123,7 +129,16 @@ pub fn derive_to_bytes(token_stream: InterfaceTokenStream) -> InterfaceTokenStre
 
 #[proc_macro_derive(
     IncomingPacket,
-    attributes(packet_length, header, ping, length_hint, repeating, repeating_remaining)
+    attributes(
+        packet_length,
+        header,
+        ping,
+        length_hint,
+        repeating,
+        repeating_remaining,
+        packet_version_before,
+        packet_version_since
+    )
 )]
 pub fn derive_incoming_packet(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
     let DeriveInput {
@@ -143,7 +158,16 @@ pub fn derive_incoming_packet(token_stream: InterfaceTokenStream) -> InterfaceTo
 
 #[proc_macro_derive(
     OutgoingPacket,
-    attributes(packet_length, header, ping, length_hint, repeating, repeating_remaining)
+    attributes(
+        packet_length,
+        header,
+        ping,
+        length_hint,
+        repeating,
+        repeating_remaining,
+        packet_version_before,
+        packet_version_since
+    )
 )]
 pub fn derive_packet(token_stream: InterfaceTokenStream) -> InterfaceTokenStream {
     let DeriveInput {
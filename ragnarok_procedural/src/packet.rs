@@ -20,7 +20,8 @@ pub fn derive_incoming_packet_struct(
     let is_ping = get_unique_attribute(&mut attributes, "ping").is_some();
 
     let signature = packet_signature.signature;
-    let (from_bytes_implementations, implemented_fields, _to_bytes_implementations, delimiter) = byte_convertable_helper(data_struct);
+    let (from_bytes_implementations, implemented_fields, _to_bytes_implementations, delimiter) =
+        byte_convertable_helper(data_struct, false);
 
     let instanciate = match delimiter {
         proc_macro2::Delimiter::Brace => quote!(Self { #(#implemented_fields),* }),
@@ -63,7 +64,8 @@ pub fn derive_outgoing_packet_struct(
     let is_ping = get_unique_attribute(&mut attributes, "ping").is_some();
 
     let signature = packet_signature.signature;
-    let (_from_bytes_implementations, _implemented_fields, to_bytes_implementations, _delimiter) = byte_convertable_helper(data_struct);
+    let (_from_bytes_implementations, _implemented_fields, to_bytes_implementations, _delimiter) =
+        byte_convertable_helper(data_struct, true);
     let to_bytes = quote!([&#signature.to_le_bytes()[..], #(#to_bytes_implementations),*].concat());
 
     quote! {
@@ -71,8 +73,8 @@ pub fn derive_outgoing_packet_struct(
             const IS_PING: bool = #is_ping;
 
             // Temporary until serialization is always possible
-            #[allow(unreachable_code)]
-            fn to_bytes(&self) -> ragnarok_bytes::ConversionResult<Vec<u8>> {
+            #[allow(unreachable_code, unused_variables)]
+            fn to_bytes(&self, packet_version: crate::network::PacketVersion) -> ragnarok_bytes::ConversionResult<Vec<u8>> {
                 Ok(#to_bytes)
             }
         }
@@ -0,0 +1,414 @@
+mod settings;
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use cgmath::{InnerSpace, Matrix3, Matrix4, SquareMatrix, Vector3};
+use procedural::profile;
+use rodio::source::Buffered;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
+
+pub use self::settings::AudioSettings;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::Camera;
+use crate::loaders::GameFileLoader;
+
+type SoundBuffer = Buffered<Decoder<Cursor<Vec<u8>>>>;
+
+// NOTE: The emitter is always placed at a unit distance from the listener, so
+// the distance between the ears only controls how strong the panning is.
+const LEFT_EAR_POSITION: [f32; 3] = [-0.1, 0.0, 0.0];
+const RIGHT_EAR_POSITION: [f32; 3] = [0.1, 0.0, 0.0];
+const CENTER_POSITION: [f32; 3] = [0.0, 0.0, 1.0];
+
+const BACKGROUND_MUSIC_TABLE_FILE: &str = "data\\mp3nametable.txt";
+const BACKGROUND_MUSIC_FADE_DURATION: f32 = 2.0;
+
+/// Key of a sound effect that has been loaded into the [`AudioEngine`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundEffectKey(usize);
+
+/// Handle to a sound that is currently playing. Handles stay valid after the
+/// sound finished, all operations on them will simply be ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundHandle(usize);
+
+struct SpatialProperties {
+    position: Vector3<f32>,
+    range: f32,
+}
+
+struct PlayingSound {
+    sink: SpatialSink,
+    volume: f32,
+    spatial: Option<SpatialProperties>,
+}
+
+struct AmbientSound {
+    key: SoundEffectKey,
+    volume: f32,
+    position: Vector3<f32>,
+    range: f32,
+    cycle: Option<f32>,
+    timer: f32,
+    handle: Option<SoundHandle>,
+}
+
+struct BackgroundMusic {
+    track: String,
+    file_data: Arc<[u8]>,
+    sink: Sink,
+    fade: f32,
+}
+
+pub struct AudioEngine {
+    output: Option<(OutputStream, OutputStreamHandle)>,
+    sound_effects: Vec<SoundBuffer>,
+    sound_effect_paths: HashMap<String, SoundEffectKey>,
+    playing_sounds: HashMap<SoundHandle, PlayingSound>,
+    next_handle: usize,
+    listener_matrix: Matrix4<f32>,
+    ambient_sounds: Vec<AmbientSound>,
+    background_music_tracks: HashMap<String, String>,
+    background_music: Option<BackgroundMusic>,
+    fading_background_music: Vec<BackgroundMusic>,
+    sound_effect_volume: f32,
+    background_music_volume: f32,
+    mute: bool,
+}
+
+impl AudioEngine {
+    pub fn new(game_file_loader: &mut GameFileLoader) -> Self {
+        // NOTE: Not having an audio device should not prevent the client from
+        // starting, so we simply don't play any sounds in that case.
+        let output = match OutputStream::try_default() {
+            Ok(output) => Some(output),
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to open audio device: {_error:?}");
+
+                None
+            }
+        };
+
+        let background_music_tracks = Self::load_background_music_tracks(game_file_loader);
+
+        Self {
+            output,
+            sound_effects: Vec::new(),
+            sound_effect_paths: HashMap::new(),
+            playing_sounds: HashMap::new(),
+            next_handle: 0,
+            listener_matrix: Matrix4::identity(),
+            ambient_sounds: Vec::new(),
+            background_music_tracks,
+            background_music: None,
+            fading_background_music: Vec::new(),
+            sound_effect_volume: 1.0,
+            background_music_volume: 1.0,
+            mute: false,
+        }
+    }
+
+    /// Parse the table that maps every map to its background music. Each line
+    /// has the format `prontera.rsw#bgm\\08.mp3#`.
+    fn load_background_music_tracks(game_file_loader: &mut GameFileLoader) -> HashMap<String, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new("load background music table");
+
+        let Ok(file_data) = game_file_loader.get(BACKGROUND_MUSIC_TABLE_FILE) else {
+            #[cfg(feature = "debug")]
+            print_debug!("[{RED}error{NONE}] failed to load background music table");
+
+            return HashMap::new();
+        };
+
+        let background_music_tracks = String::from_utf8_lossy(&file_data)
+            .lines()
+            .filter(|line| !line.starts_with("//"))
+            .filter_map(|line| {
+                let mut fields = line.split('#');
+                let map_file = fields.next()?.trim().to_lowercase();
+                let track = fields.next()?.trim().replace("\\\\", "\\");
+                let map_name = map_file.strip_suffix(".rsw")?;
+
+                (!track.is_empty()).then(|| (map_name.to_string(), track))
+            })
+            .collect();
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        background_music_tracks
+    }
+
+    fn load_sound_effect(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<SoundEffectKey, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load sound effect from {MAGENTA}{path}{NONE}"));
+
+        let file_data = game_file_loader.get(&format!("data\\wav\\{path}"))?;
+        let decoder = Decoder::new_wav(Cursor::new(file_data)).map_err(|error| error.to_string())?;
+
+        let key = SoundEffectKey(self.sound_effects.len());
+        self.sound_effects.push(decoder.buffered());
+        self.sound_effect_paths.insert(path.to_string(), key);
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(key)
+    }
+
+    pub fn get_sound_effect(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<SoundEffectKey, String> {
+        match self.sound_effect_paths.get(path) {
+            Some(key) => Ok(*key),
+            None => self.load_sound_effect(path, game_file_loader),
+        }
+    }
+
+    fn effective_volume(&self, volume: f32) -> f32 {
+        match self.mute {
+            true => 0.0,
+            false => volume,
+        }
+    }
+
+    fn play(&mut self, key: SoundEffectKey, volume: f32, spatial: Option<SpatialProperties>) -> Option<SoundHandle> {
+        let (_, output_handle) = self.output.as_ref()?;
+        let sink = SpatialSink::try_new(output_handle, CENTER_POSITION, LEFT_EAR_POSITION, RIGHT_EAR_POSITION).ok()?;
+
+        let mut sound = PlayingSound { sink, volume, spatial };
+        sound.update(self.listener_matrix, self.effective_volume(self.sound_effect_volume));
+        sound.sink.append(self.sound_effects[key.0].clone());
+
+        let handle = SoundHandle(self.next_handle);
+        self.next_handle += 1;
+        self.playing_sounds.insert(handle, sound);
+
+        Some(handle)
+    }
+
+    /// Play a sound effect without any attenuation or panning, e.g. for the
+    /// user interface.
+    pub fn play_sound_effect(&mut self, key: SoundEffectKey, volume: f32) -> Option<SoundHandle> {
+        self.play(key, volume, None)
+    }
+
+    /// Play a sound effect at a position in the world. The sound will fade out
+    /// linearly with the distance to the listener and be inaudible outside of
+    /// `range`.
+    pub fn play_spatial_sound_effect(
+        &mut self,
+        key: SoundEffectKey,
+        volume: f32,
+        position: Vector3<f32>,
+        range: f32,
+    ) -> Option<SoundHandle> {
+        self.play(key, volume, Some(SpatialProperties { position, range }))
+    }
+
+    pub fn set_sound_position(&mut self, handle: SoundHandle, position: Vector3<f32>) {
+        if let Some(spatial) = self.playing_sounds.get_mut(&handle).and_then(|sound| sound.spatial.as_mut()) {
+            spatial.position = position;
+        }
+    }
+
+    pub fn set_sound_volume(&mut self, handle: SoundHandle, volume: f32) {
+        if let Some(sound) = self.playing_sounds.get_mut(&handle) {
+            sound.volume = volume;
+        }
+    }
+
+    pub fn is_playing(&self, handle: SoundHandle) -> bool {
+        self.playing_sounds.get(&handle).is_some_and(|sound| !sound.sink.empty())
+    }
+
+    pub fn stop_sound(&mut self, handle: SoundHandle) {
+        if let Some(sound) = self.playing_sounds.remove(&handle) {
+            sound.sink.stop();
+        }
+    }
+
+    pub fn stop_all_sounds(&mut self) {
+        self.playing_sounds.drain().for_each(|(_, sound)| sound.sink.stop());
+    }
+
+    /// Register a sound that is repeatedly played at a fixed position in the
+    /// world, e.g. a waterfall. The sound is replayed every `cycle` seconds
+    /// while the listener is in range, or as soon as it finished playing if
+    /// there is no cycle.
+    pub fn add_ambient_sound(&mut self, key: SoundEffectKey, volume: f32, position: Vector3<f32>, range: f32, cycle: Option<f32>) {
+        self.ambient_sounds.push(AmbientSound {
+            key,
+            volume,
+            position,
+            range,
+            cycle,
+            timer: 0.0,
+            handle: None,
+        });
+    }
+
+    pub fn clear_ambient_sounds(&mut self) {
+        self.ambient_sounds
+            .drain(..)
+            .filter_map(|ambient_sound| ambient_sound.handle)
+            .for_each(|handle| {
+                if let Some(sound) = self.playing_sounds.remove(&handle) {
+                    sound.sink.stop();
+                }
+            });
+    }
+
+    fn update_ambient_sounds(&mut self, delta_time: f32) {
+        let mut ambient_sounds = std::mem::take(&mut self.ambient_sounds);
+
+        for ambient_sound in &mut ambient_sounds {
+            ambient_sound.timer -= delta_time;
+
+            let relative_position = (self.listener_matrix * ambient_sound.position.extend(1.0)).truncate();
+            let in_range = relative_position.magnitude() < ambient_sound.range;
+            let playing = ambient_sound.handle.is_some_and(|handle| self.is_playing(handle));
+
+            if in_range && !playing && ambient_sound.timer <= 0.0 {
+                ambient_sound.handle = self.play_spatial_sound_effect(
+                    ambient_sound.key,
+                    ambient_sound.volume,
+                    ambient_sound.position,
+                    ambient_sound.range,
+                );
+                ambient_sound.timer = ambient_sound.cycle.unwrap_or_default();
+            }
+        }
+
+        self.ambient_sounds = ambient_sounds;
+    }
+
+    fn load_background_music(&self, track: &str, game_file_loader: &mut GameFileLoader) -> Result<BackgroundMusic, String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load background music from {MAGENTA}{track}{NONE}"));
+
+        let Some((_, output_handle)) = self.output.as_ref() else {
+            return Err("no audio device available".to_string());
+        };
+
+        let file_data: Arc<[u8]> = game_file_loader.get(track)?.into();
+        let decoder = Decoder::new_mp3(Cursor::new(file_data.clone())).map_err(|error| error.to_string())?;
+        let sink = Sink::try_new(output_handle).map_err(|error| error.to_string())?;
+
+        sink.set_volume(0.0);
+        sink.append(decoder);
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(BackgroundMusic {
+            track: track.to_string(),
+            file_data,
+            sink,
+            fade: 0.0,
+        })
+    }
+
+    /// Crossfade to the background music of the given map. If the map has no
+    /// background music, the current track will simply fade out.
+    pub fn play_background_music(&mut self, map_name: &str, game_file_loader: &mut GameFileLoader) {
+        let track = self.background_music_tracks.get(&map_name.to_lowercase()).cloned();
+
+        if self.background_music.as_ref().map(|music| &music.track) == track.as_ref() {
+            return;
+        }
+
+        if let Some(background_music) = self.background_music.take() {
+            self.fading_background_music.push(background_music);
+        }
+
+        self.background_music = track.and_then(|track| {
+            self.load_background_music(&track, game_file_loader)
+                .map_err(|_error| {
+                    #[cfg(feature = "debug")]
+                    print_debug!("[{RED}error{NONE}] failed to play background music {MAGENTA}{track}{NONE}: {_error}");
+                })
+                .ok()
+        });
+    }
+
+    pub fn set_sound_effect_volume(&mut self, volume: f32) {
+        self.sound_effect_volume = volume;
+    }
+
+    pub fn set_background_music_volume(&mut self, volume: f32) {
+        self.background_music_volume = volume;
+    }
+
+    pub fn set_mute(&mut self, mute: bool) {
+        self.mute = mute;
+    }
+
+    /// Place the listener at `position`, facing the same direction as the
+    /// camera. Ranges of sounds are relative to this position rather than the
+    /// camera, so zooming doesn't change what is audible.
+    pub fn set_listener(&mut self, position: Vector3<f32>, camera: &dyn Camera) {
+        let (view_matrix, _) = camera.view_projection_matrices();
+        let rotation_matrix = Matrix3::from_cols(view_matrix.x.truncate(), view_matrix.y.truncate(), view_matrix.z.truncate());
+        self.listener_matrix = Matrix4::from(rotation_matrix) * Matrix4::from_translation(-position);
+    }
+
+    #[profile("update audio")]
+    pub fn update(&mut self, delta_time: f32) {
+        let sound_effect_volume = self.effective_volume(self.sound_effect_volume);
+        let background_music_volume = self.effective_volume(self.background_music_volume);
+        let fade_step = delta_time / BACKGROUND_MUSIC_FADE_DURATION;
+
+        self.playing_sounds.retain(|_, sound| !sound.sink.empty());
+        self.update_ambient_sounds(delta_time);
+        self.playing_sounds
+            .values_mut()
+            .for_each(|sound| sound.update(self.listener_matrix, sound_effect_volume));
+
+        self.fading_background_music.retain_mut(|background_music| {
+            background_music.fade -= fade_step;
+            background_music
+                .sink
+                .set_volume(background_music.fade.max(0.0) * background_music_volume);
+            background_music.fade > 0.0
+        });
+
+        if let Some(background_music) = &mut self.background_music {
+            background_music.fade = (background_music.fade + fade_step).min(1.0);
+            background_music.sink.set_volume(background_music.fade * background_music_volume);
+
+            // NOTE: Loop the track by decoding it again once it finished playing.
+            if background_music.sink.empty()
+                && let Ok(decoder) = Decoder::new_mp3(Cursor::new(background_music.file_data.clone()))
+            {
+                background_music.sink.append(decoder);
+            }
+        }
+    }
+}
+
+impl PlayingSound {
+    fn update(&mut self, listener_matrix: Matrix4<f32>, master_volume: f32) {
+        let Some(spatial) = &self.spatial else {
+            self.sink.set_volume(self.volume * master_volume);
+            return;
+        };
+
+        // NOTE: Transform the emitter into the space of the listener, so that the
+        // x axis always points to the right ear.
+        let relative_position = (listener_matrix * spatial.position.extend(1.0)).truncate();
+        let distance = relative_position.magnitude();
+        let attenuation = (1.0 - distance / spatial.range).clamp(0.0, 1.0);
+
+        let emitter_position = match distance > f32::EPSILON {
+            true => (relative_position / distance).into(),
+            false => CENTER_POSITION,
+        };
+
+        self.sink.set_emitter_position(emitter_position);
+        self.sink.set_volume(self.volume * attenuation * master_volume);
+    }
+}
@@ -0,0 +1,56 @@
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct AudioSettings {
+    pub mute: bool,
+    pub background_music_volume: f32,
+    pub sound_effect_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            mute: false,
+            background_music_volume: 0.5,
+            sound_effect_volume: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load audio settings from {}filename{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading audio settings from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string("client/audio_settings.ron")
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving audio settings to {}filename{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write("client/audio_settings.ron", data).expect("unable to write file");
+    }
+}
+
+impl Drop for AudioSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
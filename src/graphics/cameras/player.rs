@@ -23,6 +23,7 @@ pub struct PlayerCamera {
     view_angle: SmoothedValue,
     zoom: SmoothedValue,
     aspect_ratio: f32,
+    shake_offset: Vector3<f32>,
 }
 
 impl PlayerCamera {
@@ -40,6 +41,7 @@ impl PlayerCamera {
             view_angle: SmoothedValue::new(FRAC_PI_2, THRESHHOLD, 15.0),
             zoom: SmoothedValue::new(DEFAULT_ZOOM, THRESHHOLD, 5.0),
             aspect_ratio: 0.0,
+            shake_offset: Vector3::new(0.0, 0.0, 0.0),
         }
     }
 
@@ -67,6 +69,13 @@ impl PlayerCamera {
         self.view_angle.move_desired(rotation * ROTATION_SPEED);
     }
 
+    /// Offset applied to the camera by the
+    /// [`ScreenEffects`](crate::graphics::ScreenEffects), without moving the
+    /// focus point itself.
+    pub fn set_shake_offset(&mut self, offset: Vector3<f32>) {
+        self.shake_offset = offset;
+    }
+
     pub fn update(&mut self, delta_time: f64) {
         self.focus_point.x.update(delta_time);
         self.focus_point.y.update(delta_time);
@@ -112,8 +121,9 @@ impl Camera for PlayerCamera {
         self.aspect_ratio = window_size.x as f32 / window_size.y as f32;
         self.projection_matrix = cgmath::perspective(Rad(0.2617), self.aspect_ratio, Self::NEAR_PLANE, Self::FAR_PLANE);
 
-        let camera_position = self.camera_position();
-        self.view_matrix = Matrix4::look_at_rh(camera_position, self.get_focus_point(), self.look_up_vector);
+        let camera_position = self.camera_position() + self.shake_offset;
+        let focus_point = self.get_focus_point() + self.shake_offset;
+        self.view_matrix = Matrix4::look_at_rh(camera_position, focus_point, self.look_up_vector);
 
         self.world_to_screen_matrix = self.projection_matrix * self.view_matrix;
         self.screen_to_world_matrix = self.world_to_screen_matrix.invert().unwrap();
@@ -15,7 +15,11 @@ pub struct ShadowCamera {
 }
 
 impl ShadowCamera {
+    // NOTE: The light only moves in small steps, so that the static shadow map
+    // can be reused for many frames.
+    const DAY_TIMER_STEP: f32 = 0.002;
     const FAR_PLANE: f32 = 500.0;
+    const FOCUS_POINT_STEP: f32 = 25.0;
     const NEAR_PLANE: f32 = -1000.0;
 
     pub fn new() -> Self {
@@ -31,11 +35,11 @@ impl ShadowCamera {
     }
 
     pub fn set_focus_point(&mut self, focus_point: Point3<f32>) {
-        self.focus_point = focus_point;
+        self.focus_point = focus_point.map(|coordinate| (coordinate / Self::FOCUS_POINT_STEP).round() * Self::FOCUS_POINT_STEP);
     }
 
     pub fn update(&mut self, day_timer: f32) {
-        self.day_timer = day_timer;
+        self.day_timer = (day_timer / Self::DAY_TIMER_STEP).floor() * Self::DAY_TIMER_STEP;
     }
 
     fn camera_position(&self) -> Point3<f32> {
@@ -39,6 +39,10 @@ impl StartCamera {
         }
     }
 
+    pub fn get_focus_point(&self) -> Point3<f32> {
+        self.focus_point
+    }
+
     pub fn set_focus_point(&mut self, focus_point: Point3<f32>) {
         self.focus_point = focus_point;
     }
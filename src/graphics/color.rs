@@ -2,6 +2,8 @@ use procedural::PrototypeElement;
 use ragnarok_procedural::ByteConvertable;
 use serde::{Deserialize, Serialize};
 
+use crate::interface::{ArrayType, ElementDisplay};
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub red: f32,
@@ -10,6 +12,30 @@ pub struct Color {
     pub alpha: f32,
 }
 
+impl ArrayType for Color {
+    type Element = f32;
+
+    const ELEMENT_COUNT: usize = 4;
+
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT] {
+        ["red", "green", "blue", "alpha"]
+    }
+
+    fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+
+    fn set_element(&mut self, index: usize, value: Self::Element) {
+        match index {
+            0 => self.red = value,
+            1 => self.green = value,
+            2 => self.blue = value,
+            3 => self.alpha = value,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
 impl Color {
     pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
         Self {
@@ -79,7 +105,6 @@ impl Color {
         (self.alpha * 255.0) as u8
     }
 
-    #[cfg(feature = "debug")]
     pub fn multiply_alpha(mut self, alpha: f32) -> Self {
         self.alpha *= alpha;
         self
@@ -135,13 +160,57 @@ impl From<ColorBGRA> for Color {
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, ByteConvertable)]
 pub struct ColorRGB {
     pub red: f32,
     pub green: f32,
     pub blue: f32,
 }
 
+impl ColorRGB {
+    pub const fn monochrome(brightness: f32) -> Self {
+        Self {
+            red: brightness,
+            green: brightness,
+            blue: brightness,
+        }
+    }
+}
+
+impl ElementDisplay for ColorRGB {
+    fn display(&self) -> String {
+        format!(
+            "^FF5555r^000000{} ^55FF55g^000000{} ^5555FFb^000000{}",
+            self.red.display(),
+            self.green.display(),
+            self.blue.display()
+        )
+    }
+}
+
+impl ArrayType for ColorRGB {
+    type Element = f32;
+
+    const ELEMENT_COUNT: usize = 3;
+
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT] {
+        ["red", "green", "blue"]
+    }
+
+    fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT] {
+        [self.red, self.green, self.blue]
+    }
+
+    fn set_element(&mut self, index: usize, value: Self::Element) {
+        match index {
+            0 => self.red = value,
+            1 => self.green = value,
+            2 => self.blue = value,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
 impl From<ColorRGB> for Color {
     fn from(color: ColorRGB) -> Self {
         Self::rgb(color.red, color.green, color.blue)
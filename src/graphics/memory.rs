@@ -195,7 +195,7 @@ impl BufferAllocator {
         I: IntoIterator<Item = T>,
         I::IntoIter: ExactSizeIterator,
     {
-        self.allocate(data, BufferUsage::VERTEX_BUFFER)
+        self.allocate(data, BufferUsage::VERTEX_BUFFER, "vertex buffer")
     }
 
     pub fn allocate_index_buffer<I>(&mut self, data: I) -> Subbuffer<[u16]>
@@ -203,10 +203,10 @@ impl BufferAllocator {
         I: IntoIterator<Item = u16>,
         I::IntoIter: ExactSizeIterator,
     {
-        self.allocate(data, BufferUsage::INDEX_BUFFER)
+        self.allocate(data, BufferUsage::INDEX_BUFFER, "index buffer")
     }
 
-    fn allocate<T, I>(&mut self, data: I, usage: BufferUsage) -> Subbuffer<[T]>
+    fn allocate<T, I>(&mut self, data: I, usage: BufferUsage, _label: &str) -> Subbuffer<[T]>
     where
         T: BufferContents,
         I: IntoIterator<Item = T>,
@@ -256,6 +256,9 @@ impl BufferAllocator {
             .copy_buffer(CopyBufferInfo::buffers(host_buffer, device_buffer.clone()))
             .unwrap();
 
+        #[cfg(feature = "debug")]
+        super::track_buffer(_label, &device_buffer);
+
         device_buffer
     }
 
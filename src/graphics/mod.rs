@@ -3,6 +3,9 @@ mod color;
 mod memory;
 mod particles;
 mod renderers;
+#[cfg(feature = "debug")]
+mod residency;
+mod screen_effects;
 mod settings;
 mod smoothed;
 mod transform;
@@ -20,6 +23,9 @@ use self::memory::{allocate_descriptor_set, MatrixAllocator};
 pub use self::memory::{BufferAllocator, MemoryAllocator};
 pub use self::particles::*;
 pub use self::renderers::*;
+#[cfg(feature = "debug")]
+pub use self::residency::*;
+pub use self::screen_effects::ScreenEffects;
 pub use self::settings::GraphicsSettings;
 pub use self::smoothed::SmoothedValue;
 pub use self::transform::Transform;
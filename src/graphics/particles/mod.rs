@@ -6,7 +6,7 @@ use procedural::profile;
 use rand::{thread_rng, Rng};
 
 use crate::graphics::*;
-use crate::interface::{ScreenClip, ScreenPosition, ScreenSize};
+use crate::interface::{ScreenClip, ScreenPosition, ScreenSize, SpeechBubbleTheme};
 use crate::loaders::{GameFileLoader, TextureLoader};
 use crate::network::{EntityId, QuestColor, QuestEffectPacket};
 use crate::world::*;
@@ -127,6 +127,172 @@ impl Particle for HealNumber {
     }
 }
 
+/// Particle spawned by the emitter of an effect source of the map.
+pub struct EffectParticle {
+    position: Vector3<f32>,
+    velocity: Vector3<f32>,
+    gravity: f32,
+    colors: [Color; 2],
+    sizes: [f32; 2],
+    texture: Option<Arc<ImageView>>,
+    age: f32,
+    lifetime: f32,
+}
+
+impl Particle for EffectParticle {
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.velocity.y += self.gravity * delta_time;
+        self.position += self.velocity * delta_time;
+
+        self.age += delta_time;
+        self.age < self.lifetime
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+    ) {
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let clip_space_position = (projection_matrix * view_matrix) * self.position.extend(1.0);
+
+        // NOTE: Particles behind the camera would be mirrored onto the screen.
+        if clip_space_position.w <= 0.0 {
+            return;
+        }
+
+        let screen_position = Vector2::new(
+            clip_space_position.x / clip_space_position.w + 1.0,
+            clip_space_position.y / clip_space_position.w + 1.0,
+        );
+        let screen_position = screen_position / 2.0;
+        let final_position = ScreenPosition {
+            left: screen_position.x * window_size.width,
+            top: screen_position.y * window_size.height,
+        };
+
+        let progress = (self.age / self.lifetime).clamp(0.0, 1.0);
+        let [start_color, end_color] = self.colors;
+        let color = Color::rgba(
+            start_color.red + (end_color.red - start_color.red) * progress,
+            start_color.green + (end_color.green - start_color.green) * progress,
+            start_color.blue + (end_color.blue - start_color.blue) * progress,
+            start_color.alpha + (end_color.alpha - start_color.alpha) * progress,
+        );
+        let size = ScreenSize::uniform(self.sizes[0] + (self.sizes[1] - self.sizes[0]) * progress);
+        let position = final_position - size / 2.0;
+
+        match &self.texture {
+            Some(texture) => renderer.render_sprite(
+                render_target,
+                texture.clone(),
+                position,
+                size,
+                ScreenClip::default(),
+                color,
+                true,
+            ),
+            None => renderer.render_rectangle(render_target, position, size, color),
+        }
+    }
+}
+
+/// Spawns particles for an effect source of the map, or for a limited time
+/// for effects like skills.
+pub struct ParticleEmitter {
+    position: Vector3<f32>,
+    description: EmitterDescription,
+    texture: Option<Arc<ImageView>>,
+    spawn_timer: f32,
+    remaining_duration: Option<f32>,
+}
+
+impl ParticleEmitter {
+    pub fn new(position: Vector3<f32>, description: EmitterDescription, texture: Option<Arc<ImageView>>) -> Self {
+        Self {
+            position,
+            description,
+            texture,
+            spawn_timer: 0.0,
+            remaining_duration: None,
+        }
+    }
+
+    /// Stop emitting after `duration` seconds.
+    pub fn with_duration(self, duration: f32) -> Self {
+        Self {
+            remaining_duration: Some(duration),
+            ..self
+        }
+    }
+
+    /// Returns `false` once the emitter should be removed.
+    fn update(&mut self, delta_time: f32) -> bool {
+        match &mut self.remaining_duration {
+            Some(remaining_duration) => {
+                *remaining_duration -= delta_time;
+                *remaining_duration > 0.0
+            }
+            None => true,
+        }
+    }
+
+    /// Number of particles that should be spawned after `delta_time` seconds
+    /// have passed.
+    fn spawn_count(&mut self, delta_time: f32) -> usize {
+        if self.description.spawn_rate <= 0.0 {
+            return 0;
+        }
+
+        let spawn_interval = self.description.spawn_rate.recip();
+        let mut spawn_count = 0;
+
+        self.spawn_timer += delta_time;
+
+        while self.spawn_timer >= spawn_interval {
+            self.spawn_timer -= spawn_interval;
+            spawn_count += 1;
+        }
+
+        spawn_count
+    }
+
+    fn spawn_particle(&self) -> EffectParticle {
+        let mut rng = thread_rng();
+        let description = &self.description;
+
+        let mut random_in = |minimum: f32, maximum: f32| match minimum < maximum {
+            true => rng.gen_range(minimum..maximum),
+            false => minimum,
+        };
+
+        let offset = Vector3::new(
+            random_in(-description.spread.x, description.spread.x),
+            random_in(-description.spread.y, description.spread.y),
+            random_in(-description.spread.z, description.spread.z),
+        );
+        let velocity = Vector3::new(
+            random_in(description.minimum_velocity.x, description.maximum_velocity.x),
+            random_in(description.minimum_velocity.y, description.maximum_velocity.y),
+            random_in(description.minimum_velocity.z, description.maximum_velocity.z),
+        );
+        let lifetime = random_in(description.lifetime.start, description.lifetime.end);
+
+        EffectParticle {
+            position: self.position + offset,
+            velocity,
+            gravity: description.gravity,
+            colors: description.colors,
+            sizes: description.sizes,
+            texture: self.texture.clone(),
+            age: 0.0,
+            lifetime,
+        }
+    }
+}
+
 pub struct QuestIcon {
     position: Vector3<f32>,
     texture: Arc<ImageView>,
@@ -158,6 +324,22 @@ impl QuestIcon {
         Self { position, texture, color }
     }
 
+    /// Marker for a position the map server pointed the player at.
+    pub fn navigation(
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        map: &Map,
+        position: Vector2<u16>,
+    ) -> Self {
+        let position = map.get_world_position(position.map(usize::from)) + Vector3::new(0.0, 5.0, 0.0);
+        let texture = texture_loader
+            .get("À¯ÀúÀÎÅÍÆäÀÌ½º\\minimap\\quest_0_1.bmp", game_file_loader)
+            .unwrap();
+        let color = Color::rgb_u8(30, 200, 200);
+
+        Self { position, texture, color }
+    }
+
     fn render(
         &self,
         render_target: &mut <DeferredRenderer as Renderer>::Target,
@@ -189,10 +371,160 @@ impl QuestIcon {
     }
 }
 
+pub struct SpeechBubble {
+    lines: Vec<String>,
+    timer: f32,
+}
+
+impl SpeechBubble {
+    pub fn new(message: &str, theme: &SpeechBubbleTheme) -> Self {
+        let character_width = theme.font_size.get() / 2.0;
+        let maximum_characters = ((theme.maximum_width.get() / character_width) as usize).max(1);
+        let lines = wrap_text(message, maximum_characters);
+        let timer = theme.display_duration.get();
+
+        Self { lines, timer }
+    }
+
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.timer -= delta_time;
+        self.timer > 0.0
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        position: Vector3<f32>,
+        theme: &SpeechBubbleTheme,
+    ) {
+        render_text_box(render_target, renderer, camera, window_size, position, &self.lines, theme);
+    }
+}
+
+/// Title of a vending shop, shown above the vending player for as long as the
+/// shop is open.
+pub struct VendingTitle {
+    title: String,
+}
+
+impl VendingTitle {
+    pub fn new(title: &str) -> Self {
+        let title = title.to_owned();
+        Self { title }
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        position: Vector3<f32>,
+        theme: &SpeechBubbleTheme,
+    ) {
+        let lines = std::slice::from_ref(&self.title);
+        render_text_box(render_target, renderer, camera, window_size, position, lines, theme);
+    }
+}
+
+fn render_text_box(
+    render_target: &mut <DeferredRenderer as Renderer>::Target,
+    renderer: &DeferredRenderer,
+    camera: &dyn Camera,
+    window_size: ScreenSize,
+    position: Vector3<f32>,
+    lines: &[String],
+    theme: &SpeechBubbleTheme,
+) {
+    let position = position + Vector3::new(0.0, 25.0, 0.0); // TODO: get height of the entity as offset
+    let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+    let clip_space_position = (projection_matrix * view_matrix) * position.extend(1.0);
+    let screen_position = Vector2::new(
+        clip_space_position.x / clip_space_position.w + 1.0,
+        clip_space_position.y / clip_space_position.w + 1.0,
+    );
+    let screen_position = screen_position / 2.0;
+    let final_position = ScreenPosition {
+        left: screen_position.x * window_size.width,
+        top: screen_position.y * window_size.height,
+    };
+
+    let font_size = theme.font_size.get();
+    let border_size = theme.border_size.get();
+    let longest_line = lines.iter().map(String::len).max().unwrap_or_default();
+    let bubble_size = ScreenSize {
+        width: longest_line as f32 * font_size / 2.0,
+        height: lines.len() as f32 * font_size,
+    } + border_size * 2.0;
+    let bubble_position = final_position
+        - ScreenSize {
+            width: bubble_size.width / 2.0,
+            height: bubble_size.height + theme.offset.get(),
+        };
+
+    renderer.render_rectangle(render_target, bubble_position, bubble_size, theme.background_color.get());
+
+    let foreground_color = theme.foreground_color.get();
+    let mut text_position = bubble_position + border_size;
+
+    for line in lines {
+        renderer.render_text(render_target, line, text_position, foreground_color, font_size);
+        text_position.top += font_size;
+    }
+}
+
+/// Split the text into lines of at most `maximum_characters` characters,
+/// breaking at whitespace where possible.
+fn wrap_text(text: &str, maximum_characters: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current_line = String::new();
+
+    for word in text.split_whitespace() {
+        let line_length = current_line.chars().count();
+
+        if line_length > 0 && line_length + 1 + word.chars().count() <= maximum_characters {
+            current_line.push(' ');
+            current_line.push_str(word);
+            continue;
+        }
+
+        if line_length > 0 {
+            lines.push(std::mem::take(&mut current_line));
+        }
+
+        // NOTE: Words that don't fit on a single line are split at the line boundary.
+        let characters: Vec<char> = word.chars().collect();
+        let mut chunks = characters.chunks(maximum_characters).peekable();
+
+        while let Some(chunk) = chunks.next() {
+            let chunk: String = chunk.iter().collect();
+
+            match chunks.peek() {
+                Some(_) => lines.push(chunk),
+                None => current_line = chunk,
+            }
+        }
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines
+}
+
 #[derive(Default)]
 pub struct ParticleHolder {
     particles: Vec<Box<dyn Particle + Send + Sync>>,
     quest_icons: HashMap<EntityId, QuestIcon>,
+    speech_bubbles: HashMap<EntityId, SpeechBubble>,
+    navigation_marker: Option<QuestIcon>,
+    vending_titles: HashMap<EntityId, VendingTitle>,
+    emitters: Vec<ParticleEmitter>,
+    emitters_paused: bool,
 }
 
 impl ParticleHolder {
@@ -217,14 +549,77 @@ impl ParticleHolder {
         self.quest_icons.remove(&entity_id);
     }
 
+    pub fn set_navigation_marker(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        map: &Map,
+        position: Vector2<u16>,
+    ) {
+        self.navigation_marker = Some(QuestIcon::navigation(game_file_loader, texture_loader, map, position));
+    }
+
+    pub fn clear_navigation_marker(&mut self) {
+        self.navigation_marker = None;
+    }
+
+    pub fn add_speech_bubble(&mut self, entity_id: EntityId, message: &str, theme: &SpeechBubbleTheme) {
+        self.speech_bubbles.insert(entity_id, SpeechBubble::new(message, theme));
+    }
+
+    pub fn add_vending_title(&mut self, entity_id: EntityId, title: &str) {
+        self.vending_titles.insert(entity_id, VendingTitle::new(title));
+    }
+
+    pub fn remove_vending_title(&mut self, entity_id: EntityId) {
+        self.vending_titles.remove(&entity_id);
+    }
+
+    pub fn has_vending_title(&self, entity_id: EntityId) -> bool {
+        self.vending_titles.contains_key(&entity_id)
+    }
+
+    pub fn get_vending_title(&self, entity_id: EntityId) -> Option<String> {
+        self.vending_titles.get(&entity_id).map(|vending_title| vending_title.title.clone())
+    }
+
+    pub fn add_emitter(&mut self, emitter: ParticleEmitter) {
+        self.emitters.push(emitter);
+    }
+
+    /// Replace the emitters of the previous map. Emitters are not removed by
+    /// [`clear`](Self::clear) since they belong to the map.
+    pub fn set_emitters(&mut self, emitters: Vec<ParticleEmitter>) {
+        self.emitters = emitters;
+    }
+
+    /// Paused emitters don't spawn new particles. Used to save work while
+    /// the window is in the background.
+    pub fn pause_emitters(&mut self, paused: bool) {
+        self.emitters_paused = paused;
+    }
+
     pub fn clear(&mut self) {
         self.particles.clear();
         self.quest_icons.clear();
+        self.speech_bubbles.clear();
+        self.navigation_marker = None;
+        self.vending_titles.clear();
     }
 
     #[profile("update particles")]
     pub fn update(&mut self, delta_time: f32) {
+        if !self.emitters_paused {
+            for emitter in &mut self.emitters {
+                for _ in 0..emitter.spawn_count(delta_time) {
+                    self.particles.push(Box::new(emitter.spawn_particle()));
+                }
+            }
+        }
+
+        self.emitters.retain_mut(|emitter| emitter.update(delta_time));
         self.particles.retain_mut(|particle| particle.update(delta_time));
+        self.speech_bubbles.retain(|_, speech_bubble| speech_bubble.update(delta_time));
     }
 
     #[profile("render particles")]
@@ -245,4 +640,91 @@ impl ParticleHolder {
             .filter_map(|entity| self.quest_icons.get(&entity.get_entity_id()))
             .for_each(|quest_icon| quest_icon.render(render_target, renderer, camera, window_size));
     }
+
+    pub fn render_navigation_marker(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+    ) {
+        if let Some(navigation_marker) = &self.navigation_marker {
+            navigation_marker.render(render_target, renderer, camera, window_size);
+        }
+    }
+
+    /// Render the speech bubbles of all entities that are within `range` tiles
+    /// of the player. If `range` is `None`, all speech bubbles are rendered.
+    #[profile("render speech bubbles")]
+    pub fn render_speech_bubbles(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        entities: &[Entity],
+        theme: &SpeechBubbleTheme,
+        range: Option<usize>,
+    ) {
+        let Some(player) = entities.first() else {
+            return;
+        };
+
+        let player_position = player.get_grid_position();
+
+        entities
+            .iter()
+            .filter(|entity| {
+                range.map_or(true, |range| {
+                    let position = entity.get_grid_position();
+                    position.x.abs_diff(player_position.x).max(position.y.abs_diff(player_position.y)) <= range
+                })
+            })
+            .filter_map(|entity| {
+                self.speech_bubbles
+                    .get(&entity.get_entity_id())
+                    .map(|speech_bubble| (entity.get_position(), speech_bubble))
+            })
+            .for_each(|(position, speech_bubble)| speech_bubble.render(render_target, renderer, camera, window_size, position, theme));
+    }
+
+    #[profile("render vending titles")]
+    pub fn render_vending_titles(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        entities: &[Entity],
+        theme: &SpeechBubbleTheme,
+    ) {
+        entities
+            .iter()
+            .filter_map(|entity| {
+                self.vending_titles
+                    .get(&entity.get_entity_id())
+                    .map(|vending_title| (entity.get_position(), vending_title))
+            })
+            .for_each(|(position, vending_title)| vending_title.render(render_target, renderer, camera, window_size, position, theme));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector3;
+
+    use super::ParticleEmitter;
+    use crate::world::EmitterDescription;
+
+    #[test]
+    fn spawn_timer_carries_over() {
+        let mut description = EmitterDescription::from_effect_type(47).unwrap();
+        description.spawn_rate = 4.0;
+
+        let mut emitter = ParticleEmitter::new(Vector3::new(0.0, 0.0, 0.0), description, None);
+
+        assert_eq!(emitter.spawn_count(0.2), 0);
+        assert_eq!(emitter.spawn_count(0.2), 1);
+        assert_eq!(emitter.spawn_count(1.0), 4);
+    }
 }
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use chrono::Local;
+use derive_new::new;
+use image::RgbaImage;
+use vulkano::buffer::{Buffer, BufferCreateInfo, BufferUsage, Subbuffer};
+use vulkano::command_buffer::{
+    AutoCommandBufferBuilder, CommandBufferUsage, CopyImageToBufferInfo, PrimaryCommandBufferAbstract, ResolveImageInfo,
+};
+use vulkano::device::Queue;
+use vulkano::format::Format;
+use vulkano::image::view::ImageView;
+use vulkano::image::{Image, ImageAspects, ImageCreateInfo, ImageUsage, SampleCount};
+use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
+use vulkano::sync::GpuFuture;
+
+use crate::debug::*;
+use crate::graphics::MemoryAllocator;
+
+const CAPTURE_DIRECTORY: &str = "client/captures";
+
+/// How the texels of a render target are mapped to the colors of the PNG
+/// file.
+#[derive(Clone, Copy)]
+pub enum CaptureEncoding {
+    Color,
+    /// Signed vectors in the range -1 to 1, like normals.
+    Vector,
+    /// Depth values are stretched to the range of the captured image, since
+    /// most of them are very close to 1.
+    Depth,
+}
+
+/// Render target that is written to its own file when capturing a frame.
+#[derive(new)]
+pub struct CaptureTarget {
+    name: &'static str,
+    image: Arc<ImageView>,
+    encoding: CaptureEncoding,
+}
+
+struct PendingCapture {
+    name: &'static str,
+    encoding: CaptureEncoding,
+    format: Format,
+    dimensions: [u32; 2],
+    buffer: Subbuffer<[u8]>,
+}
+
+/// Copy all targets to the host and save them as PNG files in a new
+/// directory. The targets must not be in use by the GPU anymore. Returns the
+/// path of the directory.
+///
+/// Multisampled color targets are resolved first. Multisampled depth targets
+/// can't be resolved by a copy, so they are skipped.
+pub fn capture_render_targets(
+    memory_allocator: &MemoryAllocator,
+    queue: Arc<Queue>,
+    targets: Vec<CaptureTarget>,
+) -> Result<String, String> {
+    let directory = format!("{CAPTURE_DIRECTORY}/{}", Local::now().format("%Y-%m-%d_%H-%M-%S"));
+    std::fs::create_dir_all(&directory).map_err(|error| format!("failed to create {directory}: {error}"))?;
+
+    let mut builder = AutoCommandBufferBuilder::primary(memory_allocator, queue.queue_family_index(), CommandBufferUsage::OneTimeSubmit)
+        .map_err(|error| error.to_string())?;
+    let mut pending_captures = Vec::new();
+
+    for CaptureTarget { name, image, encoding } in targets {
+        let image = image.image().clone();
+        let format = image.format();
+        let [width, height, _] = image.extent();
+
+        let source_image = match image.samples() {
+            SampleCount::Sample1 => image,
+            _ if format.aspects().intersects(ImageAspects::DEPTH) => {
+                print_debug!("[{YELLOW}warning{NONE}] skipping multisampled depth target {MAGENTA}{name}{NONE}");
+                continue;
+            }
+            _ => {
+                let resolved_image = Image::new(
+                    memory_allocator,
+                    ImageCreateInfo {
+                        format,
+                        extent: [width, height, 1],
+                        usage: ImageUsage::TRANSFER_DST | ImageUsage::TRANSFER_SRC,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                )
+                .map_err(|error| error.to_string())?;
+
+                builder
+                    .resolve_image(ResolveImageInfo::images(image, resolved_image.clone()))
+                    .map_err(|error| error.to_string())?;
+
+                resolved_image
+            }
+        };
+
+        let block_size = format.block_size();
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            width as u64 * height as u64 * block_size,
+        )
+        .map_err(|error| error.to_string())?;
+
+        builder
+            .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(source_image, buffer.clone()))
+            .map_err(|error| error.to_string())?;
+
+        pending_captures.push(PendingCapture {
+            name,
+            encoding,
+            format,
+            dimensions: [width, height],
+            buffer,
+        });
+    }
+
+    let command_buffer = builder.build().map_err(|error| error.to_string())?;
+    command_buffer
+        .execute(queue)
+        .map_err(|error| error.to_string())?
+        .then_signal_fence_and_flush()
+        .map_err(|error| error.to_string())?
+        .wait(None)
+        .map_err(|error| error.to_string())?;
+
+    for PendingCapture {
+        name,
+        encoding,
+        format,
+        dimensions,
+        buffer,
+    } in pending_captures
+    {
+        let data = buffer.read().map_err(|error| error.to_string())?;
+        let texels = decode_texels(format, &data).ok_or_else(|| format!("unsupported format {format:?} of target {name}"))?;
+        let pixels = encode_texels(&texels, encoding);
+
+        let path = format!("{directory}/{name}.png");
+        RgbaImage::from_raw(dimensions[0], dimensions[1], pixels)
+            .ok_or_else(|| format!("invalid size of target {name}"))?
+            .save(&path)
+            .map_err(|error| format!("failed to save {path}: {error}"))?;
+    }
+
+    print_debug!("captured render targets to {MAGENTA}{directory}{NONE}");
+
+    Ok(directory)
+}
+
+fn decode_texels(format: Format, data: &[u8]) -> Option<Vec<[f32; 4]>> {
+    let read_f32 = |bytes: &[u8]| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let read_f16 = |bytes: &[u8]| half_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]));
+
+    let texels = match format {
+        Format::R32G32B32A32_SFLOAT => data
+            .chunks_exact(16)
+            .map(|texel| [0, 4, 8, 12].map(|offset| read_f32(&texel[offset..])))
+            .collect(),
+        Format::R16G16B16A16_SFLOAT => data
+            .chunks_exact(8)
+            .map(|texel| [0, 2, 4, 6].map(|offset| read_f16(&texel[offset..])))
+            .collect(),
+        Format::R8G8B8A8_UNORM => data
+            .chunks_exact(4)
+            .map(|texel| [0, 1, 2, 3].map(|index| texel[index] as f32 / 255.0))
+            .collect(),
+        Format::D32_SFLOAT => data
+            .chunks_exact(4)
+            .map(|texel| {
+                let depth = read_f32(texel);
+                [depth, depth, depth, 1.0]
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    Some(texels)
+}
+
+fn encode_texels(texels: &[[f32; 4]], encoding: CaptureEncoding) -> Vec<u8> {
+    let to_byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    match encoding {
+        CaptureEncoding::Color => texels.iter().flat_map(|texel| texel.map(to_byte)).collect(),
+        CaptureEncoding::Vector => texels
+            .iter()
+            .flat_map(|[x, y, z, _]| [x, y, z].map(|value| to_byte(value * 0.5 + 0.5)).into_iter().chain([255]))
+            .collect(),
+        CaptureEncoding::Depth => {
+            let (minimum, maximum) = texels.iter().fold((f32::MAX, f32::MIN), |(minimum, maximum), [depth, ..]| {
+                (minimum.min(*depth), maximum.max(*depth))
+            });
+            let range = (maximum - minimum).max(f32::EPSILON);
+
+            texels
+                .iter()
+                .flat_map(|[depth, ..]| {
+                    let value = to_byte((depth - minimum) / range);
+                    [value, value, value, 255]
+                })
+                .collect()
+        }
+    }
+}
+
+/// Convert an IEEE 754 half precision float to a single precision float.
+fn half_to_f32(bits: u16) -> f32 {
+    let sign = match bits & 0x8000 {
+        0 => 1.0,
+        _ => -1.0,
+    };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f32;
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => sign * f32::INFINITY,
+        31 => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::half_to_f32;
+
+    #[test]
+    fn half_precision_floats() {
+        assert_eq!(half_to_f32(0x0000), 0.0);
+        assert_eq!(half_to_f32(0x3C00), 1.0);
+        assert_eq!(half_to_f32(0xBC00), -1.0);
+        assert_eq!(half_to_f32(0x3800), 0.5);
+        assert_eq!(half_to_f32(0x7BFF), 65504.0);
+        assert_eq!(half_to_f32(0x7C00), f32::INFINITY);
+        assert!(half_to_f32(0x7E00).is_nan());
+    }
+}
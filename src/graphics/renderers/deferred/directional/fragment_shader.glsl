@@ -8,9 +8,10 @@ layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInputMS
 layout(input_attachment_index = 1, set = 0, binding = 1) uniform subpassInputMS normal_in;
 layout(input_attachment_index = 2, set = 0, binding = 2) uniform subpassInputMS depth_in;
 
-layout (set = 0, binding = 3) uniform sampler2D shadow_map_in;
+layout (set = 0, binding = 3) uniform sampler2DShadow shadow_map_in;
+layout (set = 0, binding = 4) uniform sampler2DShadow entity_shadow_map_in;
 
-layout(set = 0, binding = 4) uniform Matrices {
+layout(set = 0, binding = 5) uniform Matrices {
     mat4 screen_to_world;
     mat4 light;
 } matrices;
@@ -38,11 +39,12 @@ vec3 calculate_sample(int sample_index) {
     vec3 light_coords = light_position.xyz / light_position.w;
     light_coords.xy = light_coords.xy * 0.5 + 0.5;
 
-    float shadow_map_depth = texture(shadow_map_in, light_coords.xy).r;
-    bool visibility = light_coords.z - bias < shadow_map_depth;
+    // the samplers compare the depth against the reference, so linear filtering gives smooth shadow edges
+    vec3 reference = vec3(light_coords.xy, light_coords.z - bias);
+    float visibility = min(texture(shadow_map_in, reference), texture(entity_shadow_map_in, reference));
 
     vec3 diffuse = subpassLoad(diffuse_in, sample_index).rgb;
-    return light_percent * constants.color * diffuse * float(visibility); 
+    return light_percent * constants.color * diffuse * visibility; 
 }
 
 void main() {
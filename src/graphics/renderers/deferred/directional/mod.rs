@@ -25,7 +25,7 @@ pub struct DirectionalLightRenderer {
     vertex_shader: EntryPoint,
     fragment_shader: EntryPoint,
     matrices_buffer: MatrixAllocator<Matrices>,
-    linear_sampler: Arc<Sampler>,
+    shadow_sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -35,7 +35,7 @@ impl DirectionalLightRenderer {
         let vertex_shader = vertex_shader::entry_point(&device);
         let fragment_shader = fragment_shader::entry_point(&device);
         let matrices_buffer = MatrixAllocator::new(&memory_allocator);
-        let linear_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let shadow_sampler = create_new_sampler(&device, SamplerType::ShadowComparison(SamplerConfiguration::default().shadow));
         let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
 
         Self {
@@ -43,11 +43,15 @@ impl DirectionalLightRenderer {
             vertex_shader,
             fragment_shader,
             matrices_buffer,
-            linear_sampler,
+            shadow_sampler,
             pipeline,
         }
     }
 
+    pub fn set_sampler_settings(&mut self, sampler_settings: SamplerSettings) {
+        self.shadow_sampler = create_new_sampler(self.memory_allocator.device(), SamplerType::ShadowComparison(sampler_settings));
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -81,6 +85,7 @@ impl DirectionalLightRenderer {
         render_target: &mut <DeferredRenderer as Renderer>::Target,
         camera: &dyn Camera,
         shadow_image: Arc<ImageView>,
+        entity_shadow_image: Arc<ImageView>,
         light_matrix: Matrix4<f32>,
         direction: Vector3<f32>,
         color: Color,
@@ -99,8 +104,9 @@ impl DirectionalLightRenderer {
             WriteDescriptorSet::image_view(0, render_target.diffuse_image.clone()),
             WriteDescriptorSet::image_view(1, render_target.normal_image.clone()),
             WriteDescriptorSet::image_view(2, render_target.depth_image.clone()),
-            WriteDescriptorSet::image_view_sampler(3, shadow_image, self.linear_sampler.clone()),
-            WriteDescriptorSet::buffer(4, buffer),
+            WriteDescriptorSet::image_view_sampler(3, shadow_image, self.shadow_sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(4, entity_shadow_image, self.shadow_sampler.clone()),
+            WriteDescriptorSet::buffer(5, buffer),
         ]);
 
         let constants = Constants {
@@ -24,7 +24,7 @@ pub struct EffectRenderer {
     memory_allocator: Arc<MemoryAllocator>,
     vertex_shader: EntryPoint,
     fragment_shader: EntryPoint,
-    linear_sampler: Arc<Sampler>,
+    sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -33,18 +33,22 @@ impl EffectRenderer {
         let device = memory_allocator.device().clone();
         let vertex_shader = vertex_shader::entry_point(&device);
         let fragment_shader = fragment_shader::entry_point(&device);
-        let linear_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let sampler = create_new_sampler(&device, SamplerType::Configured(SamplerConfiguration::default().sprite));
         let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
 
         Self {
             memory_allocator,
             vertex_shader,
             fragment_shader,
-            linear_sampler,
+            sampler,
             pipeline,
         }
     }
 
+    pub fn set_sampler_settings(&mut self, sampler_settings: SamplerSettings) {
+        self.sampler = create_new_sampler(self.memory_allocator.device(), SamplerType::Configured(sampler_settings));
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -110,7 +114,7 @@ impl EffectRenderer {
         let texture_coordinates = texture_coordinates.map(|coordinate| [coordinate.x, coordinate.y]);
 
         let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 0, [
-            WriteDescriptorSet::image_view_sampler(0, texture, self.linear_sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(0, texture, self.sampler.clone()),
         ]);
 
         // TODO: apply angle
@@ -10,24 +10,28 @@ layout(location = 1) out vec3 fragment_normal;
 
 layout(push_constant) uniform Constants {
     mat4 world;
+    vec4 color;
     vec2 texture_position;
     vec2 texture_size;
     float depth_offset;
     float curvature;
     bool mirror;
+    bool supersample;
 } constants;
 
 layout (set = 1, binding = 0) uniform sampler2D sampled_texture;
 
+#include "../../sprite.glsl"
+
 void main() {
 
-    vec4 diffuse_color = texture(sampled_texture, texture_coordinates);
+    vec4 diffuse_color = sample_sprite(sampled_texture, texture_coordinates, constants.supersample);
 
     if (diffuse_color.a != 1.0) {
         discard;
     }
 
-    fragment_color = diffuse_color;
+    fragment_color = vec4(diffuse_color.rgb * constants.color.rgb, diffuse_color.a);
     fragment_normal = normalize(normal);
 
     float curvature_offset = (0.5 - pow(curvature, 2)) * constants.curvature;
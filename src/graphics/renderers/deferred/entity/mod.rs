@@ -17,7 +17,7 @@ use vulkano::shader::EntryPoint;
 use self::vertex_shader::{Constants, Matrices};
 use super::DeferredSubrenderer;
 use crate::graphics::renderers::pipeline::PipelineBuilder;
-use crate::graphics::renderers::sampler::{create_new_sampler, SamplerType};
+use crate::graphics::renderers::sampler::create_new_sampler;
 use crate::graphics::{allocate_descriptor_set, *};
 
 pub struct EntityRenderer {
@@ -26,7 +26,8 @@ pub struct EntityRenderer {
     vertex_shader: EntryPoint,
     fragment_shader: EntryPoint,
     matrices_buffer: MatrixAllocator<Matrices>,
-    linear_sampler: Arc<Sampler>,
+    sprite_filtering: SpriteFiltering,
+    sampler: Arc<Sampler>,
 }
 
 impl EntityRenderer {
@@ -34,7 +35,8 @@ impl EntityRenderer {
         let device = memory_allocator.device().clone();
         let vertex_shader = vertex_shader::entry_point(&device);
         let fragment_shader = fragment_shader::entry_point(&device);
-        let linear_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let sprite_filtering = SpriteFiltering::Linear;
+        let sampler = create_new_sampler(&device, sprite_filtering.sampler_type());
         let matrices_buffer = MatrixAllocator::new(&memory_allocator);
         let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
 
@@ -44,10 +46,17 @@ impl EntityRenderer {
             vertex_shader,
             fragment_shader,
             matrices_buffer,
-            linear_sampler,
+            sprite_filtering,
+            sampler,
         }
     }
 
+    pub fn set_sprite_filtering(&mut self, sprite_filtering: SpriteFiltering) {
+        let device = self.memory_allocator.device();
+        self.sampler = create_new_sampler(device, sprite_filtering.sampler_type());
+        self.sprite_filtering = sprite_filtering;
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -100,6 +109,7 @@ impl EntityRenderer {
         cell_count: Vector2<usize>,
         cell_position: Vector2<usize>,
         mirror: bool,
+        color: Color,
     ) {
         if render_target.bind_subrenderer(DeferredSubrenderer::Entity) {
             self.bind_pipeline(render_target, camera);
@@ -117,16 +127,18 @@ impl EntityRenderer {
         let (depth_offset, curvature) = camera.calculate_depth_offset_and_curvature(&world_matrix);
 
         let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 1, [
-            WriteDescriptorSet::image_view_sampler(0, texture, self.linear_sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(0, texture, self.sampler.clone()),
         ]);
 
         let constants = Constants {
             world: world_matrix.into(),
+            color: color.into(),
             texture_position: texture_position.into(),
             texture_size: texture_size.into(),
             depth_offset,
             curvature,
             mirror: mirror as u32,
+            supersample: self.sprite_filtering.supersample() as u32,
         };
 
         render_target
@@ -12,11 +12,13 @@ layout(set = 0, binding = 0) uniform Matrices {
 
 layout(push_constant) uniform Constants {
     mat4 world;
+    vec4 color;
     vec2 texture_position;
     vec2 texture_size;
     float depth_offset;
     float curvature;
     bool mirror;
+    bool supersample;
 } constants;
 
 struct Vertex {
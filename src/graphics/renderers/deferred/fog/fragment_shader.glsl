@@ -0,0 +1,40 @@
+#version 450
+
+layout(location = 0) in vec2 position;
+
+layout(location = 0) out vec4 fragment_color;
+
+layout(input_attachment_index = 3, set = 0, binding = 0) uniform subpassInputMS depth_in;
+
+layout(push_constant) uniform Constants {
+    mat4 screen_to_world_matrix;
+    vec4 color;
+    float near;
+    float far;
+} constants;
+
+vec3 world_position(float depth) {
+    vec4 position_world_space = constants.screen_to_world_matrix * vec4(position, depth, 1.0);
+    return position_world_space.xyz / position_world_space.w;
+}
+
+float calculate_sample(int sample_index) {
+
+    float depth = subpassLoad(depth_in, sample_index).x;
+
+    // The distance is measured from the near plane rather than the camera, which
+    // is close enough for fog.
+    float view_distance = length(world_position(depth) - world_position(0.0));
+
+    return smoothstep(constants.near, constants.far, view_distance);
+}
+
+void main() {
+
+    float fog = 0.0;
+
+    for (int i = 0; i < 4; i++)
+        fog += calculate_sample(i);
+
+    fragment_color = vec4(constants.color.rgb, fog / 4.0 * constants.color.a);
+}
@@ -28,7 +28,8 @@ pub struct GeometryRenderer {
     fragment_shader: EntryPoint,
     matrices_buffer: MatrixAllocator<Matrices>,
     nearest_sampler: Arc<Sampler>,
-    linear_sampler: Arc<Sampler>,
+    ground_sampler: Arc<Sampler>,
+    model_sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -39,7 +40,9 @@ impl GeometryRenderer {
         let fragment_shader = fragment_shader::entry_point(&device);
         let matrices_buffer = MatrixAllocator::new(&memory_allocator);
         let nearest_sampler = create_new_sampler(&device, SamplerType::Nearest);
-        let linear_sampler = create_new_sampler(&device, SamplerType::LinearAnisotropic(4.0));
+        let sampler_configuration = SamplerConfiguration::default();
+        let ground_sampler = create_new_sampler(&device, SamplerType::Configured(sampler_configuration.ground));
+        let model_sampler = create_new_sampler(&device, SamplerType::Configured(sampler_configuration.model));
         let pipeline = Self::create_pipeline(
             device,
             subpass,
@@ -56,11 +59,18 @@ impl GeometryRenderer {
             fragment_shader,
             matrices_buffer,
             nearest_sampler,
-            linear_sampler,
+            ground_sampler,
+            model_sampler,
             pipeline,
         }
     }
 
+    pub fn set_sampler_configuration(&mut self, sampler_configuration: &SamplerConfiguration) {
+        let device = self.memory_allocator.device();
+        self.ground_sampler = create_new_sampler(device, SamplerType::Configured(sampler_configuration.ground));
+        self.model_sampler = create_new_sampler(device, SamplerType::Configured(sampler_configuration.model));
+    }
+
     #[profile]
     pub fn recreate_pipeline(
         &mut self,
@@ -144,6 +154,7 @@ impl GeometryRenderer {
         camera: &dyn Camera,
         vertex_buffer: Subbuffer<[ModelVertex]>,
         textures: &[Arc<ImageView>],
+        sampler_category: SamplerCategory,
         world_matrix: Matrix4<f32>,
         time: f32,
     ) {
@@ -168,9 +179,14 @@ impl GeometryRenderer {
             textures.push(textures[0].clone());
         }
 
+        let sampler = match sampler_category {
+            SamplerCategory::Ground => self.ground_sampler.clone(),
+            _ => self.model_sampler.clone(),
+        };
+
         let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 1, [
             WriteDescriptorSet::sampler(0, self.nearest_sampler.clone()),
-            WriteDescriptorSet::sampler(1, self.linear_sampler.clone()),
+            WriteDescriptorSet::sampler(1, sampler),
             WriteDescriptorSet::image_view_array(2, 0, textures),
         ]);
 
@@ -6,12 +6,14 @@ mod buffer;
 mod directional;
 mod effect;
 mod entity;
+mod fog;
 mod geometry;
 mod indicator;
 mod overlay;
 mod point;
 mod rectangle;
 mod sprite;
+mod transparent;
 mod water;
 mod water_light;
 
@@ -36,12 +38,15 @@ use self::buffer::BufferRenderer;
 use self::directional::DirectionalLightRenderer;
 use self::effect::EffectRenderer;
 use self::entity::EntityRenderer;
+use self::fog::FogRenderer;
 use self::geometry::GeometryRenderer;
 use self::indicator::IndicatorRenderer;
 use self::overlay::OverlayRenderer;
 use self::point::PointLightRenderer;
 use self::rectangle::RectangleRenderer;
 use self::sprite::SpriteRenderer;
+pub use self::transparent::TransparentLighting;
+use self::transparent::TransparentRenderer;
 use self::water::WaterRenderer;
 use self::water_light::WaterLightRenderer;
 use super::SubpassAttachments;
@@ -50,7 +55,7 @@ use crate::graphics::{
     SpriteRenderer as SpriteRendererTrait, *,
 };
 use crate::interface::{ScreenClip, ScreenPosition, ScreenSize};
-use crate::loaders::{GameFileLoader, TextureLoader};
+use crate::loaders::{FogSettings, GameFileLoader, TextureLoader};
 use crate::network::EntityId;
 #[cfg(feature = "debug")]
 use crate::world::{BoundingBox, MarkerIdentifier};
@@ -64,6 +69,7 @@ pub enum DeferredSubrenderer {
     DirectionalLight,
     PointLight,
     WaterLight,
+    Fog,
     Indicator,
     #[cfg(feature = "debug")]
     BoundingBox,
@@ -73,6 +79,7 @@ pub enum DeferredSubrenderer {
     Rectangle,
     Sprite,
     Effect,
+    TransparentGeometry,
 }
 
 pub struct DeferredRenderer {
@@ -87,10 +94,12 @@ pub struct DeferredRenderer {
     directional_light_renderer: DirectionalLightRenderer,
     point_light_renderer: PointLightRenderer,
     water_light_renderer: WaterLightRenderer,
+    fog_renderer: FogRenderer,
     overlay_renderer: OverlayRenderer,
     rectangle_renderer: RectangleRenderer,
     sprite_renderer: SpriteRenderer,
     effect_renderer: EffectRenderer,
+    transparent_renderer: TransparentRenderer,
     #[cfg(feature = "debug")]
     buffer_renderer: BufferRenderer,
     #[cfg(feature = "debug")]
@@ -185,6 +194,7 @@ impl DeferredRenderer {
             DirectionalLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         let point_light_renderer = PointLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         let water_light_renderer = WaterLightRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
+        let fog_renderer = FogRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         let overlay_renderer = OverlayRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         let rectangle_renderer = RectangleRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         let sprite_renderer = SpriteRenderer::new(
@@ -197,6 +207,7 @@ impl DeferredRenderer {
             texture_loader,
         );
         let effect_renderer = EffectRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
+        let transparent_renderer = TransparentRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         #[cfg(feature = "debug")]
         let buffer_renderer = BufferRenderer::new(memory_allocator.clone(), lighting_subpass.clone(), viewport.clone());
         #[cfg(feature = "debug")]
@@ -228,10 +239,12 @@ impl DeferredRenderer {
             directional_light_renderer,
             point_light_renderer,
             water_light_renderer,
+            fog_renderer,
             overlay_renderer,
             rectangle_renderer,
             sprite_renderer,
             effect_renderer,
+            transparent_renderer,
             #[cfg(feature = "debug")]
             buffer_renderer,
             #[cfg(feature = "debug")]
@@ -271,6 +284,8 @@ impl DeferredRenderer {
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
         self.water_light_renderer
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+        self.fog_renderer
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
         self.overlay_renderer
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
         self.rectangle_renderer
@@ -279,6 +294,8 @@ impl DeferredRenderer {
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
         self.effect_renderer
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
+        self.transparent_renderer
+            .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
         #[cfg(feature = "debug")]
         self.buffer_renderer
             .recreate_pipeline(device.clone(), lighting_subpass.clone(), viewport.clone());
@@ -287,6 +304,18 @@ impl DeferredRenderer {
         self.dimensions = dimensions;
     }
 
+    pub fn set_sprite_filtering(&mut self, sprite_filtering: SpriteFiltering) {
+        self.entity_renderer.set_sprite_filtering(sprite_filtering);
+    }
+
+    pub fn set_sampler_configuration(&mut self, sampler_configuration: &SamplerConfiguration) {
+        self.geometry_renderer.set_sampler_configuration(sampler_configuration);
+        self.transparent_renderer.set_sampler_settings(sampler_configuration.model);
+        self.sprite_renderer.set_sampler_settings(sampler_configuration.sprite);
+        self.effect_renderer.set_sampler_settings(sampler_configuration.sprite);
+        self.directional_light_renderer.set_sampler_settings(sampler_configuration.shadow);
+    }
+
     #[profile("create deferred render target")]
     pub fn create_render_target(&self, swapchain_image: Arc<Image>) -> <Self as Renderer>::Target {
         <Self as Renderer>::Target::new(
@@ -317,13 +346,22 @@ impl DeferredRenderer {
         render_target: &mut <Self as Renderer>::Target,
         camera: &dyn Camera,
         light_image: Arc<ImageView>,
+        entity_light_image: Arc<ImageView>,
         light_matrix: Matrix4<f32>,
         direction: Vector3<f32>,
         color: Color,
         intensity: f32,
     ) {
-        self.directional_light_renderer
-            .render(render_target, camera, light_image, light_matrix, direction, color, intensity);
+        self.directional_light_renderer.render(
+            render_target,
+            camera,
+            light_image,
+            entity_light_image,
+            light_matrix,
+            direction,
+            color,
+            intensity,
+        );
     }
 
     pub fn point_light(
@@ -341,6 +379,35 @@ impl DeferredRenderer {
         self.water_light_renderer.render(render_target, camera, water_level);
     }
 
+    pub fn fog(&self, render_target: &mut <Self as Renderer>::Target, camera: &dyn Camera, fog_settings: &FogSettings) {
+        self.fog_renderer.render(render_target, camera, fog_settings);
+    }
+
+    /// Render geometry that is blended over the lit scene. Needs to be called
+    /// after the lighting, in back to front order.
+    pub fn render_transparent_geometry(
+        &self,
+        render_target: &mut <Self as Renderer>::Target,
+        camera: &dyn Camera,
+        vertex_buffer: Subbuffer<[ModelVertex]>,
+        textures: &[Arc<ImageView>],
+        world_matrix: Matrix4<f32>,
+        alpha: f32,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        self.transparent_renderer.render(
+            render_target,
+            camera,
+            vertex_buffer,
+            textures,
+            world_matrix,
+            alpha,
+            lighting,
+            time,
+        );
+    }
+
     pub fn overlay_interface(&self, render_target: &mut <Self as Renderer>::Target, interface_image: Arc<ImageView>) {
         self.overlay_renderer.render(render_target, interface_image);
     }
@@ -475,6 +542,7 @@ impl DeferredRenderer {
             camera,
             vertex_buffer,
             &self.tile_textures,
+            SamplerCategory::Ground,
             Matrix4::identity(),
             0.0,
         );
@@ -517,13 +585,21 @@ impl GeometryRendererTrait for DeferredRenderer {
         camera: &dyn Camera,
         vertex_buffer: Subbuffer<[ModelVertex]>,
         textures: &[Arc<ImageView>],
+        sampler_category: SamplerCategory,
         world_matrix: Matrix4<f32>,
         time: f32,
     ) where
         Self: Renderer,
     {
-        self.geometry_renderer
-            .render(render_target, camera, vertex_buffer, textures, world_matrix, time);
+        self.geometry_renderer.render(
+            render_target,
+            camera,
+            vertex_buffer,
+            textures,
+            sampler_category,
+            world_matrix,
+            time,
+        );
     }
 }
 
@@ -540,6 +616,7 @@ impl EntityRendererTrait for DeferredRenderer {
         cell_position: Vector2<usize>,
         mirror: bool,
         _entity_id: EntityId,
+        color: Color,
     ) where
         Self: Renderer,
     {
@@ -553,6 +630,7 @@ impl EntityRendererTrait for DeferredRenderer {
             cell_count,
             cell_position,
             mirror,
+            color,
         );
     }
 }
@@ -39,7 +39,7 @@ pub struct SpriteRenderer {
     #[cfg(feature = "debug")]
     entity_marker_texture: Arc<ImageView>,
     nearest_sampler: Arc<Sampler>,
-    linear_sampler: Arc<Sampler>,
+    smooth_sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -67,7 +67,7 @@ impl SpriteRenderer {
         let entity_marker_texture = texture_loader.get("entity.png", game_file_loader).unwrap();
 
         let nearest_sampler = create_new_sampler(&device, SamplerType::Nearest);
-        let linear_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let smooth_sampler = create_new_sampler(&device, SamplerType::Configured(SamplerConfiguration::default().sprite));
         let pipeline = Self::create_pipeline(device.clone(), subpass, viewport, &vertex_shader, &fragment_shader);
 
         Self {
@@ -85,11 +85,15 @@ impl SpriteRenderer {
             #[cfg(feature = "debug")]
             entity_marker_texture,
             nearest_sampler,
-            linear_sampler,
+            smooth_sampler,
             pipeline,
         }
     }
 
+    pub fn set_sampler_settings(&mut self, sampler_settings: SamplerSettings) {
+        self.smooth_sampler = create_new_sampler(self.memory_allocator.device(), SamplerType::Configured(sampler_settings));
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -133,7 +137,7 @@ impl SpriteRenderer {
         }
 
         let sampler = match smooth {
-            true => self.linear_sampler.clone(),
+            true => self.smooth_sampler.clone(),
             false => self.nearest_sampler.clone(),
         };
 
@@ -0,0 +1,53 @@
+#version 450
+
+const int TEXTURE_COUNT = 30;
+
+layout(location = 0) in vec3 normal;
+layout(location = 1) in vec2 texture_coordinates;
+layout(location = 2) flat in int texture_index;
+
+layout(location = 0) out vec4 fragment_color;
+
+layout(input_attachment_index = 3, set = 0, binding = 0) uniform subpassInputMS depth_in;
+
+layout(set = 0, binding = 2) uniform Lighting {
+    vec4 ambient_color;
+    vec4 light_direction;
+    vec4 light_color;
+} lighting;
+
+layout (set = 1, binding = 0) uniform sampler linear_sampler;
+layout (set = 1, binding = 1) uniform texture2D textures[TEXTURE_COUNT];
+
+layout(push_constant) uniform Constants {
+    mat4 world;
+    float alpha;
+} constants;
+
+void main() {
+
+    // the lighting subpass has no depth attachment, so we test against the depth of the opaque geometry
+    // by hand. counting the covered samples keeps the edges smooth
+    float coverage = 0.0;
+
+    for (int i = 0; i < 4; i++)
+        if (gl_FragCoord.z < subpassLoad(depth_in, i).x)
+            coverage += 0.25;
+
+    if (coverage == 0.0) {
+        discard;
+    }
+
+    vec4 diffuse_color;
+
+    for (int index = 0; index < TEXTURE_COUNT; ++index)
+        if (texture_index == index) {
+            diffuse_color = texture(sampler2D(textures[index], linear_sampler), texture_coordinates);
+        }
+
+    float light_percent = clamp(dot(normalize(-lighting.light_direction.xyz), normalize(normal)), 0.0, 1.0);
+    vec3 light = lighting.ambient_color.rgb + lighting.light_color.rgb * light_percent;
+
+    fragment_color.rgb = diffuse_color.rgb * light;
+    fragment_color.a = diffuse_color.a * constants.alpha * coverage;
+}
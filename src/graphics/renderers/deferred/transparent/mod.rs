@@ -0,0 +1,183 @@
+vertex_shader!("src/graphics/renderers/deferred/transparent/vertex_shader.glsl");
+fragment_shader!("src/graphics/renderers/deferred/transparent/fragment_shader.glsl");
+
+use std::sync::Arc;
+
+use cgmath::{Matrix4, Vector3};
+use procedural::profile;
+use vulkano::descriptor_set::WriteDescriptorSet;
+use vulkano::device::{Device, DeviceOwned};
+use vulkano::image::sampler::Sampler;
+use vulkano::pipeline::graphics::rasterization::{CullMode, RasterizationState};
+use vulkano::pipeline::graphics::viewport::Viewport;
+use vulkano::pipeline::{GraphicsPipeline, PipelineBindPoint, StateMode};
+use vulkano::render_pass::Subpass;
+use vulkano::shader::EntryPoint;
+
+use self::fragment_shader::Lighting;
+use self::vertex_shader::{Constants, Matrices};
+use super::DeferredSubrenderer;
+use crate::graphics::renderers::pipeline::PipelineBuilder;
+use crate::graphics::renderers::sampler::{create_new_sampler, SamplerType};
+use crate::graphics::*;
+
+/// Light of the scene at the time of the frame. Transparent geometry is not
+/// part of the G-buffer, so it is lit directly when it is rendered.
+#[derive(Clone, Copy)]
+pub struct TransparentLighting {
+    pub ambient_color: Color,
+    pub light_direction: Vector3<f32>,
+    pub light_color: Color,
+}
+
+pub struct TransparentRenderer {
+    memory_allocator: Arc<MemoryAllocator>,
+    vertex_shader: EntryPoint,
+    fragment_shader: EntryPoint,
+    matrices_buffer: MatrixAllocator<Matrices>,
+    lighting_buffer: MatrixAllocator<Lighting>,
+    model_sampler: Arc<Sampler>,
+    pipeline: Arc<GraphicsPipeline>,
+}
+
+impl TransparentRenderer {
+    pub fn new(memory_allocator: Arc<MemoryAllocator>, subpass: Subpass, viewport: Viewport) -> Self {
+        let device = memory_allocator.device().clone();
+        let vertex_shader = vertex_shader::entry_point(&device);
+        let fragment_shader = fragment_shader::entry_point(&device);
+        let matrices_buffer = MatrixAllocator::new(&memory_allocator);
+        let lighting_buffer = MatrixAllocator::new(&memory_allocator);
+        let model_sampler = create_new_sampler(&device, SamplerType::Configured(SamplerConfiguration::default().model));
+        let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
+
+        Self {
+            memory_allocator,
+            vertex_shader,
+            fragment_shader,
+            matrices_buffer,
+            lighting_buffer,
+            model_sampler,
+            pipeline,
+        }
+    }
+
+    pub fn set_sampler_settings(&mut self, sampler_settings: SamplerSettings) {
+        self.model_sampler = create_new_sampler(self.memory_allocator.device(), SamplerType::Configured(sampler_settings));
+    }
+
+    #[profile]
+    pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
+        self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
+    }
+
+    fn create_pipeline(
+        device: Arc<Device>,
+        subpass: Subpass,
+        viewport: Viewport,
+        vertex_shader: &EntryPoint,
+        fragment_shader: &EntryPoint,
+    ) -> Arc<GraphicsPipeline> {
+        let rasterization_state = RasterizationState {
+            cull_mode: StateMode::Fixed(CullMode::Back),
+            ..Default::default()
+        };
+
+        PipelineBuilder::<_, { DeferredRenderer::lighting_subpass() }>::new([vertex_shader, fragment_shader])
+            .vertex_input_state::<ModelVertex>(vertex_shader)
+            .fixed_viewport(viewport)
+            .rasterization_state(rasterization_state)
+            .blend_alpha()
+            .build(device, subpass)
+    }
+
+    #[profile]
+    fn bind_pipeline(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        camera: &dyn Camera,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let matrices_buffer = self.matrices_buffer.allocate(Matrices {
+            view_projection: (projection_matrix * view_matrix).into(),
+            time,
+        });
+        let lighting_buffer = self.lighting_buffer.allocate(Lighting {
+            ambient_color: lighting.ambient_color.into(),
+            light_direction: lighting.light_direction.extend(0.0).into(),
+            light_color: lighting.light_color.into(),
+        });
+
+        let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 0, [
+            WriteDescriptorSet::image_view(0, render_target.depth_image.clone()),
+            WriteDescriptorSet::buffer(1, matrices_buffer),
+            WriteDescriptorSet::buffer(2, lighting_buffer),
+        ]);
+
+        render_target
+            .state
+            .get_builder()
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .unwrap()
+            .bind_descriptor_sets(PipelineBindPoint::Graphics, layout, set_id, set)
+            .unwrap();
+    }
+
+    #[profile("render transparent geometry")]
+    pub fn render(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        camera: &dyn Camera,
+        vertex_buffer: Subbuffer<[ModelVertex]>,
+        textures: &[Arc<ImageView>],
+        world_matrix: Matrix4<f32>,
+        alpha: f32,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        if render_target.bind_subrenderer(DeferredSubrenderer::TransparentGeometry) {
+            self.bind_pipeline(render_target, camera, lighting, time);
+        }
+
+        if textures.is_empty() {
+            return;
+        }
+
+        const TEXTURE_COUNT: usize = 30;
+
+        let texture_count = textures.len();
+        let mut textures: Vec<Arc<ImageView>> = textures
+            .iter()
+            .take(TEXTURE_COUNT.min(texture_count))
+            .map(|texture| texture.clone() as _)
+            .collect();
+
+        for _ in 0..TEXTURE_COUNT.saturating_sub(texture_count) {
+            textures.push(textures[0].clone());
+        }
+
+        let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 1, [
+            WriteDescriptorSet::sampler(0, self.model_sampler.clone()),
+            WriteDescriptorSet::image_view_array(1, 0, textures),
+        ]);
+
+        let vertex_count = vertex_buffer.size() as usize / std::mem::size_of::<ModelVertex>();
+        let constants = Constants {
+            world: world_matrix.into(),
+            alpha,
+        };
+
+        render_target
+            .state
+            .get_builder()
+            .bind_descriptor_sets(PipelineBindPoint::Graphics, layout.clone(), set_id, set)
+            .unwrap()
+            .push_constants(layout, 0, constants)
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap()
+            .draw(vertex_count as u32, 1, 0, 0)
+            .unwrap();
+    }
+}
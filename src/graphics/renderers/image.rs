@@ -25,9 +25,13 @@ pub(super) struct AttachmentImageFactory<'a> {
 impl<'a> AttachmentImageFactory<'a> {
     pub(super) fn new_image(&self, format: Format, attachment_image_type: AttachmentImageType) -> Arc<ImageView> {
         let usage = match attachment_image_type {
-            AttachmentImageType::InputColor => ImageUsage::SAMPLED | ImageUsage::COLOR_ATTACHMENT | ImageUsage::INPUT_ATTACHMENT,
+            AttachmentImageType::InputColor => {
+                ImageUsage::SAMPLED | ImageUsage::COLOR_ATTACHMENT | ImageUsage::INPUT_ATTACHMENT | ImageUsage::TRANSFER_SRC
+            }
             AttachmentImageType::CopyColor => ImageUsage::SAMPLED | ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
-            AttachmentImageType::InputDepth => ImageUsage::SAMPLED | ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::INPUT_ATTACHMENT,
+            AttachmentImageType::InputDepth => {
+                ImageUsage::SAMPLED | ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::INPUT_ATTACHMENT | ImageUsage::TRANSFER_SRC
+            }
             AttachmentImageType::Depth => ImageUsage::DEPTH_STENCIL_ATTACHMENT,
         };
 
@@ -0,0 +1,24 @@
+// Clips at least this wide are treated as unbounded, since the center of the
+// clip can't be represented precisely for them.
+const float UNBOUNDED_CLIP = 1.0e38;
+
+float clip_coverage(vec2 fragment_coordinates, vec4 screen_clip, vec4 clip_corner_radius) {
+    if (screen_clip.z >= UNBOUNDED_CLIP && screen_clip.w >= UNBOUNDED_CLIP) {
+        return 1.0;
+    }
+
+    vec2 half_size = (screen_clip.zw - screen_clip.xy) / 2.0;
+    vec2 relative_position = fragment_coordinates - (screen_clip.xy + half_size);
+
+    // Corner radii are in the same units as the ones of rectangles, which are two units per pixel.
+    float corner_radius = relative_position.x < 0.0
+        ? (relative_position.y < 0.0 ? clip_corner_radius.x : clip_corner_radius.w)
+        : (relative_position.y < 0.0 ? clip_corner_radius.y : clip_corner_radius.z);
+    corner_radius = min(corner_radius / 2.0, min(half_size.x, half_size.y));
+
+    // Signed distance to the rounded clip rectangle, so that the edges can be smoothed.
+    vec2 corner_distance = abs(relative_position) - half_size + corner_radius;
+    float distance = min(max(corner_distance.x, corner_distance.y), 0.0) + length(max(corner_distance, 0.0)) - corner_radius;
+
+    return clamp(0.5 - distance, 0.0, 1.0);
+}
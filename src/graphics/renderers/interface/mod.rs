@@ -2,10 +2,11 @@ mod rectangle;
 mod sprite;
 mod text;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 
+use bumpalo::Bump;
 use cgmath::Vector2;
 use procedural::profile;
 use vulkano::device::{DeviceOwned, Queue};
@@ -19,7 +20,9 @@ use self::rectangle::RectangleRenderer;
 use self::sprite::SpriteRenderer;
 use self::text::TextRenderer;
 use super::{IntoFormat, SubpassAttachments};
-use crate::graphics::{Color, MemoryAllocator, Renderer, SingleRenderTarget, SpriteRenderer as SpriteRendererTrait};
+use crate::graphics::{
+    Color, MemoryAllocator, Renderer, SamplerConfiguration, SingleRenderTarget, SpriteFiltering, SpriteRenderer as SpriteRendererTrait,
+};
 use crate::interface::{CornerRadius, ScreenClip, ScreenPosition, ScreenSize};
 use crate::loaders::{FontLoader, GameFileLoader, TextureLoader};
 
@@ -43,6 +46,10 @@ pub struct InterfaceRenderer {
     expanded_arrow_texture: Arc<ImageView>,
     collapsed_arrow_texture: Arc<ImageView>,
     dimensions: [u32; 2],
+    frame_arena: Bump,
+    /// Multiplied with the alpha of everything that is rendered, so a window
+    /// can be faded as a whole.
+    opacity: Cell<f32>,
 }
 
 impl InterfaceRenderer {
@@ -100,11 +107,36 @@ impl InterfaceRenderer {
             expanded_arrow_texture,
             collapsed_arrow_texture,
             dimensions,
+            frame_arena: Bump::new(),
+            opacity: Cell::new(1.0),
         }
     }
 
     pub fn get_text_dimensions(&self, text: &str, font_size: f32, available_width: f32) -> Vector2<f32> {
-        self.font_loader.borrow().get_text_dimensions(text, font_size, available_width)
+        self.font_loader
+            .borrow_mut()
+            .get_text_dimensions(text, font_size, available_width, &self.frame_arena)
+    }
+
+    /// Bump allocator for anything that only needs to live while the interface
+    /// is resolved and rendered. This avoids going through the global
+    /// allocator for the many small allocations made every frame.
+    pub fn get_frame_arena(&self) -> &Bump {
+        &self.frame_arena
+    }
+
+    /// Free all allocations of the current frame. Must only be called after
+    /// the frame has been presented.
+    pub fn reset_frame_arena(&mut self) {
+        self.frame_arena.reset();
+    }
+
+    pub fn set_sprite_filtering(&mut self, sprite_filtering: SpriteFiltering) {
+        self.sprite_renderer.set_sprite_filtering(sprite_filtering);
+    }
+
+    pub fn set_sampler_configuration(&mut self, sampler_configuration: &SamplerConfiguration) {
+        self.text_renderer.set_sampler_settings(sampler_configuration.interface);
     }
 
     #[profile("re-create interface pipeline")]
@@ -128,11 +160,20 @@ impl InterfaceRenderer {
             self.render_pass.clone(),
             self.dimensions,
             SampleCount::Sample4,
-            ImageUsage::SAMPLED | ImageUsage::TRANSFER_DST | ImageUsage::COLOR_ATTACHMENT | ImageUsage::INPUT_ATTACHMENT,
+            ImageUsage::SAMPLED
+                | ImageUsage::TRANSFER_DST
+                | ImageUsage::TRANSFER_SRC
+                | ImageUsage::COLOR_ATTACHMENT
+                | ImageUsage::INPUT_ATTACHMENT,
             ClearColorValue::Float([0.0, 0.0, 0.0, 0.0]),
         )
     }
 
+    /// Set the opacity of everything rendered until it is set again.
+    pub fn set_opacity(&self, opacity: f32) {
+        self.opacity.set(opacity);
+    }
+
     fn get_window_size(&self) -> ScreenSize {
         ScreenSize {
             width: self.dimensions[0] as f32,
@@ -156,7 +197,7 @@ impl InterfaceRenderer {
             size,
             screen_clip,
             corner_radius,
-            color,
+            color.multiply_alpha(self.opacity.get()),
         );
     }
 
@@ -209,8 +250,9 @@ impl InterfaceRenderer {
             self.get_window_size(),
             position,
             screen_clip,
-            color,
+            color.multiply_alpha(self.opacity.get()),
             font_size,
+            &self.frame_arena,
         )
     }
 }
@@ -247,7 +289,7 @@ impl SpriteRendererTrait for InterfaceRenderer {
             position,
             size,
             screen_clip,
-            color,
+            color.multiply_alpha(self.opacity.get()),
             smooth,
         );
     }
@@ -8,11 +8,14 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec4 corner_radius;
     vec4 color;
     float aspect_ratio;
 } constants;
 
+#include "../clip.glsl"
+
 void main() {
 
     vec2 coords = fragment_position * constants.screen_size;
@@ -45,9 +48,11 @@ void main() {
         discard;
     }
 
-    if (gl_FragCoord.x < constants.screen_clip.x || gl_FragCoord.y < constants.screen_clip.y || gl_FragCoord.x > constants.screen_clip.z || gl_FragCoord.y > constants.screen_clip.w) {
+    float coverage = clip_coverage(gl_FragCoord.xy, constants.screen_clip, constants.clip_corner_radius);
+
+    if (coverage <= 0.0) {
         discard;
     }
 
-    fragment_color = constants.color;
+    fragment_color = vec4(constants.color.rgb, constants.color.a * coverage);
 }
@@ -93,6 +93,7 @@ impl RectangleRenderer {
             screen_position: screen_position.into(),
             screen_size: screen_size.into(),
             screen_clip: screen_clip.into(),
+            clip_corner_radius: screen_clip.corner_radius.into(),
             corner_radius: corner_radius.into(),
             color: color.into(),
             aspect_ratio: window_size.height / window_size.width,
@@ -6,6 +6,7 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec4 corner_radius;
     vec4 color;
     float aspect_ratio;
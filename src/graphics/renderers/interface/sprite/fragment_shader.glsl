@@ -10,14 +10,23 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec4 color;
+    bool supersample;
 } constants;
 
+#include "../../sprite.glsl"
+
+#include "../clip.glsl"
+
 void main() {
 
-    if (gl_FragCoord.x < constants.screen_clip.x || gl_FragCoord.y < constants.screen_clip.y || gl_FragCoord.x > constants.screen_clip.z || gl_FragCoord.y > constants.screen_clip.w) {
+    float coverage = clip_coverage(gl_FragCoord.xy, constants.screen_clip, constants.clip_corner_radius);
+
+    if (coverage <= 0.0) {
         discard;
     }
 
-    fragment_color = texture(sprite_texture, texture_coordinates) * constants.color;
+    fragment_color = sample_sprite(sprite_texture, texture_coordinates, constants.supersample) * constants.color;
+    fragment_color.a *= coverage;
 }
@@ -25,7 +25,8 @@ pub struct SpriteRenderer {
     vertex_shader: EntryPoint,
     fragment_shader: EntryPoint,
     nearest_sampler: Arc<Sampler>,
-    linear_sampler: Arc<Sampler>,
+    sprite_filtering: SpriteFiltering,
+    smooth_sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -35,7 +36,8 @@ impl SpriteRenderer {
         let vertex_shader = vertex_shader::entry_point(&device);
         let fragment_shader = fragment_shader::entry_point(&device);
         let nearest_sampler = create_new_sampler(&device, SamplerType::Nearest);
-        let linear_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let sprite_filtering = SpriteFiltering::Linear;
+        let smooth_sampler = create_new_sampler(&device, sprite_filtering.sampler_type());
         let pipeline = Self::create_pipeline(device, subpass, viewport, &vertex_shader, &fragment_shader);
 
         Self {
@@ -44,10 +46,19 @@ impl SpriteRenderer {
             vertex_shader,
             fragment_shader,
             nearest_sampler,
-            linear_sampler,
+            sprite_filtering,
+            smooth_sampler,
         }
     }
 
+    /// Set the filtering of sprites that are rendered smooth. Sprites that
+    /// are not smooth always use nearest filtering.
+    pub fn set_sprite_filtering(&mut self, sprite_filtering: SpriteFiltering) {
+        let device = self.memory_allocator.device();
+        self.smooth_sampler = create_new_sampler(device, sprite_filtering.sampler_type());
+        self.sprite_filtering = sprite_filtering;
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -96,9 +107,9 @@ impl SpriteRenderer {
         let screen_position = screen_position / half_screen;
         let screen_size = screen_size / half_screen;
 
-        let sampler = match smooth {
-            true => self.linear_sampler.clone(),
-            false => self.nearest_sampler.clone(),
+        let (sampler, supersample) = match smooth {
+            true => (self.smooth_sampler.clone(), self.sprite_filtering.supersample()),
+            false => (self.nearest_sampler.clone(), false),
         };
 
         let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 0, [
@@ -109,7 +120,9 @@ impl SpriteRenderer {
             screen_position: screen_position.into(),
             screen_size: screen_size.into(),
             screen_clip: screen_clip.into(),
+            clip_corner_radius: screen_clip.corner_radius.into(),
             color: color.into(),
+            supersample: supersample as u32,
         };
 
         render_target
@@ -6,7 +6,9 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec4 color;
+    bool supersample;
 } constants;
 
 const vec2 data[6] = vec2[]
@@ -10,16 +10,28 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec2 texture_position;
     vec2 texture_size;
     vec4 color;
 } constants;
 
+#include "../clip.glsl"
+
 void main() {
 
-    if (gl_FragCoord.x < constants.screen_clip.x || gl_FragCoord.y < constants.screen_clip.y || gl_FragCoord.x > constants.screen_clip.z || gl_FragCoord.y > constants.screen_clip.w) {
+    float coverage = clip_coverage(gl_FragCoord.xy, constants.screen_clip, constants.clip_corner_radius);
+
+    if (coverage <= 0.0) {
         discard;
     }
 
-    fragment_color = vec4(constants.color.rgb, texture(sprite_texture, texture_coordinates).r * constants.color.a);
+    // Glyphs are stored as signed distance fields with the outline at 0.5. Scaling
+    // the distance by its screen space derivative gives an edge that is about one
+    // pixel wide, no matter how big the text is.
+    float distance = texture(sprite_texture, texture_coordinates).r;
+    float edge_width = max(fwidth(distance), 0.0001);
+    float glyph_coverage = clamp((distance - 0.5) / edge_width + 0.5, 0.0, 1.0);
+
+    fragment_color = vec4(constants.color.rgb, glyph_coverage * constants.color.a * coverage);
 }
@@ -5,6 +5,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use bumpalo::Bump;
 use procedural::profile;
 use vulkano::descriptor_set::WriteDescriptorSet;
 use vulkano::device::{Device, DeviceOwned};
@@ -28,7 +29,7 @@ pub struct TextRenderer {
     font_loader: Rc<RefCell<FontLoader>>,
     vertex_shader: EntryPoint,
     fragment_shader: EntryPoint,
-    nearest_sampler: Arc<Sampler>,
+    sampler: Arc<Sampler>,
     pipeline: Arc<GraphicsPipeline>,
 }
 
@@ -37,7 +38,7 @@ impl TextRenderer {
         let device = memory_allocator.device().clone();
         let vertex_shader = vertex_shader::entry_point(&device);
         let fragment_shader = fragment_shader::entry_point(&device);
-        let nearest_sampler = create_new_sampler(&device, SamplerType::Linear);
+        let sampler = create_new_sampler(&device, SamplerType::Configured(SamplerConfiguration::default().interface));
         let pipeline = Self::create_pipeline(device.clone(), subpass, viewport, &vertex_shader, &fragment_shader);
 
         Self {
@@ -46,10 +47,14 @@ impl TextRenderer {
             pipeline,
             vertex_shader,
             fragment_shader,
-            nearest_sampler,
+            sampler,
         }
     }
 
+    pub fn set_sampler_settings(&mut self, sampler_settings: SamplerSettings) {
+        self.sampler = create_new_sampler(self.memory_allocator.device(), SamplerType::Configured(sampler_settings));
+    }
+
     #[profile]
     pub fn recreate_pipeline(&mut self, device: Arc<Device>, subpass: Subpass, viewport: Viewport) {
         self.pipeline = Self::create_pipeline(device, subpass, viewport, &self.vertex_shader, &self.fragment_shader);
@@ -88,6 +93,7 @@ impl TextRenderer {
         screen_clip: ScreenClip,
         color: Color,
         font_size: f32,
+        frame_arena: &Bump,
     ) -> f32 {
         if render_target.bind_subrenderer(InterfaceSubrenderer::Text) {
             self.bind_pipeline(render_target);
@@ -95,11 +101,11 @@ impl TextRenderer {
 
         let mut font_loader = self.font_loader.borrow_mut();
         let texture = font_loader.get_font_atlas();
-        let (character_layout, height) = font_loader.get(text, color, font_size, screen_clip.right - screen_position.left);
+        let (character_layout, height) = font_loader.get(text, color, font_size, screen_clip.right - screen_position.left, frame_arena);
         let half_screen = window_size / 2.0;
 
         let (layout, set, set_id) = allocate_descriptor_set(&self.pipeline, &self.memory_allocator, 0, [
-            WriteDescriptorSet::image_view_sampler(0, texture, self.nearest_sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(0, texture, self.sampler.clone()),
         ]);
 
         render_target
@@ -110,13 +116,13 @@ impl TextRenderer {
 
         character_layout.iter().for_each(|(texture_coordinates, position, color)| {
             let screen_position = ScreenPosition {
-                left: screen_position.left + position.min.x as f32,
-                top: screen_position.top + position.min.y as f32,
+                left: screen_position.left + position.min.x,
+                top: screen_position.top + position.min.y,
             } / half_screen;
 
             let screen_size = ScreenSize {
-                width: position.width() as f32,
-                height: position.height() as f32,
+                width: position.width(),
+                height: position.height(),
             } / half_screen;
 
             let texture_position = texture_coordinates.min;
@@ -126,6 +132,7 @@ impl TextRenderer {
                 screen_position: screen_position.into(),
                 screen_size: screen_size.into(),
                 screen_clip: screen_clip.into(),
+                clip_corner_radius: screen_clip.corner_radius.into(),
                 texture_position: [texture_position.x, texture_position.y],
                 texture_size: [texture_size.x, texture_size.y],
                 color: (*color).into(),
@@ -6,6 +6,7 @@ layout(push_constant) uniform Constants {
     vec2 screen_position;
     vec2 screen_size;
     vec4 screen_clip;
+    vec4 clip_corner_radius;
     vec2 texture_position;
     vec2 texture_size;
     vec4 color;
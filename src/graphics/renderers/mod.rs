@@ -28,6 +28,8 @@ macro_rules! fragment_shader {
     };
 }
 
+#[cfg(feature = "debug")]
+mod capture;
 mod deferred;
 mod image;
 mod interface;
@@ -62,12 +64,15 @@ use vulkano::sync::future::{FenceSignalFuture, SemaphoreSignalFuture};
 use vulkano::sync::GpuFuture;
 use vulkano::Validated;
 
-pub use self::deferred::DeferredRenderer;
+#[cfg(feature = "debug")]
+pub use self::capture::{capture_render_targets, CaptureEncoding, CaptureTarget};
 use self::deferred::DeferredSubrenderer;
+pub use self::deferred::{DeferredRenderer, TransparentLighting};
 use self::image::{AttachmentImageFactory, AttachmentImageType};
 pub use self::interface::InterfaceRenderer;
 use self::picker::PickerSubrenderer;
 pub use self::picker::{PickerRenderer, PickerTarget};
+pub use self::sampler::{SamplerCategory, SamplerConfiguration, SamplerSettings, SpriteFiltering, TextureAddressMode, TextureFiltering};
 #[cfg(feature = "debug")]
 pub use self::settings::RenderSettings;
 pub use self::shadow::{ShadowDetail, ShadowRenderer};
@@ -99,6 +104,15 @@ pub const WATER_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
     dst_alpha_blend_factor: BlendFactor::One,
 };
 
+pub const FOG_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
+    color_blend_op: BlendOp::Add,
+    src_color_blend_factor: BlendFactor::SrcAlpha,
+    dst_color_blend_factor: BlendFactor::OneMinusSrcAlpha,
+    alpha_blend_op: BlendOp::Max,
+    src_alpha_blend_factor: BlendFactor::One,
+    dst_alpha_blend_factor: BlendFactor::One,
+};
+
 pub const INTERFACE_ATTACHMENT_BLEND: AttachmentBlend = AttachmentBlend {
     color_blend_op: BlendOp::Add,
     src_color_blend_factor: BlendFactor::SrcAlpha,
@@ -136,6 +150,7 @@ pub trait GeometryRenderer {
         camera: &dyn Camera,
         vertex_buffer: Subbuffer<[ModelVertex]>,
         textures: &[Arc<ImageView>],
+        sampler_category: SamplerCategory,
         world_matrix: Matrix4<f32>,
         time: f32,
     ) where
@@ -155,6 +170,7 @@ pub trait EntityRenderer {
         cell_position: Vector2<usize>,
         mirror: bool,
         entity_id: EntityId,
+        color: Color,
     ) where
         Self: Renderer;
 }
@@ -342,6 +358,17 @@ impl DeferredRenderTarget {
         self.state = RenderTargetState::Rendering(builder);
     }
 
+    /// The channels of the G-buffer, for capturing a frame.
+    #[cfg(feature = "debug")]
+    pub fn capture_targets(&self) -> Vec<CaptureTarget> {
+        vec![
+            CaptureTarget::new("diffuse", self.diffuse_image.clone(), CaptureEncoding::Color),
+            CaptureTarget::new("normal", self.normal_image.clone(), CaptureEncoding::Vector),
+            CaptureTarget::new("water", self.water_image.clone(), CaptureEncoding::Color),
+            CaptureTarget::new("depth", self.depth_image.clone(), CaptureEncoding::Depth),
+        ]
+    }
+
     pub fn bind_subrenderer(&mut self, subrenderer: DeferredSubrenderer) -> bool {
         let already_bound = self.bound_subrenderer.contains(&subrenderer);
         self.bound_subrenderer = Some(subrenderer);
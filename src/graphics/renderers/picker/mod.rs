@@ -148,6 +148,7 @@ impl GeometryRendererTrait for PickerRenderer {
         camera: &dyn Camera,
         vertex_buffer: Subbuffer<[ModelVertex]>,
         textures: &[Arc<ImageView>],
+        _sampler_category: SamplerCategory,
         world_matrix: Matrix4<f32>,
         _time: f32,
     ) where
@@ -171,6 +172,7 @@ impl EntityRendererTrait for PickerRenderer {
         cell_position: Vector2<usize>,
         mirror: bool,
         entity_id: EntityId,
+        _color: Color,
     ) where
         Self: Renderer,
     {
@@ -1,12 +1,161 @@
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use vulkano::device::Device;
 use vulkano::image::sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo};
+use vulkano::pipeline::graphics::depth_stencil::CompareOp;
+
+/// How sprites are filtered when they are drawn at a size different from
+/// their texture.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SpriteFiltering {
+    /// Crisp pixels, but aliasing when zoomed out.
+    Nearest,
+    /// Smooth, but blurry when zoomed in.
+    Linear,
+    /// Linear filtering with multiple samples per pixel, which reduces
+    /// aliasing when sprites are drawn smaller than their texture.
+    Supersampled,
+}
+
+impl SpriteFiltering {
+    pub(super) fn sampler_type(self) -> SamplerType {
+        match self {
+            SpriteFiltering::Nearest => SamplerType::Nearest,
+            SpriteFiltering::Linear | SpriteFiltering::Supersampled => SamplerType::Linear,
+        }
+    }
+
+    pub(super) fn supersample(self) -> bool {
+        matches!(self, SpriteFiltering::Supersampled)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TextureFiltering {
+    Nearest,
+    Linear,
+}
+
+/// What happens when a texture is sampled outside of its bounds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TextureAddressMode {
+    ClampToEdge,
+    Repeat,
+    MirroredRepeat,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SamplerSettings {
+    pub filtering: TextureFiltering,
+    pub address_mode: TextureAddressMode,
+    /// Maximum number of anisotropic samples. Limited by what the device
+    /// supports.
+    pub anisotropy: Option<usize>,
+}
+
+impl SamplerSettings {
+    const fn new(filtering: TextureFiltering, anisotropy: Option<usize>) -> Self {
+        Self {
+            filtering,
+            address_mode: TextureAddressMode::ClampToEdge,
+            anisotropy,
+        }
+    }
+
+    fn create_info(self, device: &Arc<Device>) -> SamplerCreateInfo {
+        let filter = match self.filtering {
+            TextureFiltering::Nearest => Filter::Nearest,
+            TextureFiltering::Linear => Filter::Linear,
+        };
+
+        let address_mode = match self.address_mode {
+            TextureAddressMode::ClampToEdge => SamplerAddressMode::ClampToEdge,
+            TextureAddressMode::Repeat => SamplerAddressMode::Repeat,
+            TextureAddressMode::MirroredRepeat => SamplerAddressMode::MirroredRepeat,
+        };
+
+        let maximum_anisotropy = device.physical_device().properties().max_sampler_anisotropy;
+        let anisotropy = self
+            .anisotropy
+            .map(|anisotropy| (anisotropy as f32).min(maximum_anisotropy))
+            .filter(|anisotropy| *anisotropy > 1.0);
+
+        SamplerCreateInfo {
+            mag_filter: filter,
+            min_filter: filter,
+            anisotropy,
+            address_mode: [address_mode; 3],
+            ..Default::default()
+        }
+    }
+}
+
+/// Groups of textures that can be sampled differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplerCategory {
+    Ground,
+    Model,
+    /// Sprites and effects in the world. Entities use the sprite filtering
+    /// instead.
+    Sprite,
+    /// Text of the interface.
+    Interface,
+    /// Sampling of the shadow maps. Linear filtering smooths the edges of
+    /// shadows.
+    Shadow,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SamplerConfiguration {
+    pub ground: SamplerSettings,
+    pub model: SamplerSettings,
+    pub sprite: SamplerSettings,
+    pub interface: SamplerSettings,
+    pub shadow: SamplerSettings,
+}
+
+impl Default for SamplerConfiguration {
+    fn default() -> Self {
+        Self {
+            ground: SamplerSettings::new(TextureFiltering::Linear, Some(4)),
+            model: SamplerSettings::new(TextureFiltering::Linear, Some(4)),
+            sprite: SamplerSettings::new(TextureFiltering::Linear, None),
+            interface: SamplerSettings::new(TextureFiltering::Linear, None),
+            shadow: SamplerSettings::new(TextureFiltering::Linear, None),
+        }
+    }
+}
+
+impl SamplerConfiguration {
+    pub fn get(&self, category: SamplerCategory) -> SamplerSettings {
+        match category {
+            SamplerCategory::Ground => self.ground,
+            SamplerCategory::Model => self.model,
+            SamplerCategory::Sprite => self.sprite,
+            SamplerCategory::Interface => self.interface,
+            SamplerCategory::Shadow => self.shadow,
+        }
+    }
+
+    pub fn get_mut(&mut self, category: SamplerCategory) -> &mut SamplerSettings {
+        match category {
+            SamplerCategory::Ground => &mut self.ground,
+            SamplerCategory::Model => &mut self.model,
+            SamplerCategory::Sprite => &mut self.sprite,
+            SamplerCategory::Interface => &mut self.interface,
+            SamplerCategory::Shadow => &mut self.shadow,
+        }
+    }
+}
 
 pub(super) enum SamplerType {
     Linear,
-    LinearAnisotropic(f32),
     Nearest,
+    Configured(SamplerSettings),
+    /// Sampler that compares the depth of a shadow map against a reference
+    /// value instead of returning the depth.
+    ShadowComparison(SamplerSettings),
 }
 
 pub(super) fn create_new_sampler(device: &Arc<Device>, sampler_type: SamplerType) -> Arc<Sampler> {
@@ -18,14 +167,6 @@ pub(super) fn create_new_sampler(device: &Arc<Device>, sampler_type: SamplerType
             ..Default::default()
         })
         .unwrap(),
-        SamplerType::LinearAnisotropic(anisotropy) => Sampler::new(device.clone(), SamplerCreateInfo {
-            mag_filter: Filter::Linear,
-            min_filter: Filter::Linear,
-            anisotropy: Some(anisotropy),
-            address_mode: [SamplerAddressMode::ClampToEdge; 3],
-            ..Default::default()
-        })
-        .unwrap(),
         SamplerType::Nearest => Sampler::new(device.clone(), SamplerCreateInfo {
             mag_filter: Filter::Nearest,
             min_filter: Filter::Nearest,
@@ -33,5 +174,11 @@ pub(super) fn create_new_sampler(device: &Arc<Device>, sampler_type: SamplerType
             ..Default::default()
         })
         .unwrap(),
+        SamplerType::Configured(settings) => Sampler::new(device.clone(), settings.create_info(device)).unwrap(),
+        SamplerType::ShadowComparison(settings) => Sampler::new(device.clone(), SamplerCreateInfo {
+            compare: Some(CompareOp::Less),
+            ..settings.create_info(device)
+        })
+        .unwrap(),
     }
 }
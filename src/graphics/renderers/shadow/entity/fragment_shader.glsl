@@ -6,6 +6,10 @@ layout(location = 2) in float curvature;
 
 layout (set = 1, binding = 0) uniform sampler2D sampled_texture;
 
+// RGBA sprites can have partially transparent edges, so we only cut off the mostly transparent texels
+// to keep the silhouette of the shadow the same size as the sprite.
+const float ALPHA_CUTOFF = 0.5;
+
 layout(push_constant) uniform Constants {
     mat4 world;
     vec2 texture_position;
@@ -18,7 +22,7 @@ layout(push_constant) uniform Constants {
 void main() {
     vec4 diffuse_color = texture(sampled_texture, texture_coordinates);
 
-    if (diffuse_color.a != 1.0) {
+    if (diffuse_color.a < ALPHA_CUTOFF) {
         discard;
     }
 
@@ -40,6 +40,38 @@ impl ShadowDetail {
     }
 }
 
+/// Keeps track of the light matrix every directional shadow target was last
+/// rendered with. Since the static map geometry doesn't move, its shadow map
+/// only needs to be re-rendered when the light changes. Entities and animated
+/// objects are rendered into a separate shadow map every frame.
+pub struct ShadowCache {
+    light_matrices: Vec<Option<Matrix4<f32>>>,
+}
+
+impl ShadowCache {
+    pub fn new(target_count: usize) -> Self {
+        Self {
+            light_matrices: vec![None; target_count],
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.light_matrices.iter_mut().for_each(|light_matrix| *light_matrix = None);
+    }
+
+    /// Returns `true` if the static shadow map of the target has been rendered
+    /// since the last invalidation.
+    pub fn is_cached(&self, image_number: usize) -> bool {
+        self.light_matrices[image_number].is_some()
+    }
+
+    /// Returns `true` if the static shadow map of the target needs to be
+    /// re-rendered.
+    pub fn update(&mut self, image_number: usize, light_matrix: Matrix4<f32>) -> bool {
+        self.light_matrices[image_number].replace(light_matrix) != Some(light_matrix)
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum ShadowSubrenderer {
     Geometry,
@@ -114,7 +146,7 @@ impl ShadowRenderer {
             self.render_pass.clone(),
             [size; 2],
             SampleCount::Sample1,
-            ImageUsage::SAMPLED | ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+            ImageUsage::SAMPLED | ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::TRANSFER_SRC,
             ClearValue::Depth(1.0),
         )
     }
@@ -139,6 +171,7 @@ impl GeometryRendererTrait for ShadowRenderer {
         camera: &dyn Camera,
         vertex_buffer: Subbuffer<[ModelVertex]>,
         textures: &[Arc<ImageView>],
+        _sampler_category: SamplerCategory,
         world_matrix: Matrix4<f32>,
         time: f32,
     ) where
@@ -162,6 +195,7 @@ impl EntityRendererTrait for ShadowRenderer {
         cell_position: Vector2<usize>,
         mirror: bool,
         _entity_id: EntityId,
+        _color: Color,
     ) where
         Self: Renderer,
     {
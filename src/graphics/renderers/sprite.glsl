@@ -0,0 +1,15 @@
+// Average four samples on a rotated grid inside of the pixel, so sprites that
+// are drawn smaller than their texture don't skip texels.
+vec4 sample_sprite(sampler2D sprite, vec2 coordinates, bool supersample) {
+    if (!supersample) {
+        return texture(sprite, coordinates);
+    }
+
+    vec2 x_step = dFdx(coordinates);
+    vec2 y_step = dFdy(coordinates);
+
+    return (texture(sprite, coordinates + x_step * 0.125 + y_step * 0.375)
+        + texture(sprite, coordinates - x_step * 0.375 + y_step * 0.125)
+        + texture(sprite, coordinates + x_step * 0.375 - y_step * 0.125)
+        + texture(sprite, coordinates - x_step * 0.125 - y_step * 0.375)) / 4.0;
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use lazy_static::lazy_static;
+use vulkano::buffer::{Buffer, Subbuffer};
+use vulkano::image::view::ImageView;
+use vulkano::DeviceSize;
+
+use crate::debug::*;
+
+/// Tag of resources that are not created while loading a map.
+const GLOBAL_TAG: &str = "global";
+/// Maximum number of resource labels that are printed per report.
+const MAXIMUM_LISTED_RESOURCES: usize = 10;
+
+enum TrackedHandle {
+    Texture(Weak<ImageView>),
+    Buffer(Weak<Buffer>),
+}
+
+impl TrackedHandle {
+    fn is_alive(&self) -> bool {
+        match self {
+            TrackedHandle::Texture(texture) => texture.strong_count() > 0,
+            TrackedHandle::Buffer(buffer) => buffer.strong_count() > 0,
+        }
+    }
+
+    fn is_texture(&self) -> bool {
+        matches!(self, TrackedHandle::Texture(_))
+    }
+}
+
+struct TrackedResource {
+    tag: String,
+    /// Owned by one of the loader caches, which never evict. These are
+    /// expected to outlive the map they were loaded for.
+    cached: bool,
+    label: String,
+    size: DeviceSize,
+    handle: TrackedHandle,
+}
+
+#[derive(Default)]
+struct ResidencyStatistics {
+    texture_count: usize,
+    texture_bytes: DeviceSize,
+    buffer_count: usize,
+    buffer_bytes: DeviceSize,
+}
+
+impl ResidencyStatistics {
+    fn from_resources<'a>(resources: impl IntoIterator<Item = &'a TrackedResource>) -> Self {
+        let mut statistics = Self::default();
+        resources.into_iter().for_each(|resource| statistics.add(resource));
+        statistics
+    }
+
+    fn add(&mut self, resource: &TrackedResource) {
+        match resource.handle.is_texture() {
+            true => {
+                self.texture_count += 1;
+                self.texture_bytes += resource.size;
+            }
+            false => {
+                self.buffer_count += 1;
+                self.buffer_bytes += resource.size;
+            }
+        }
+    }
+}
+
+/// Remembers every texture and buffer together with the map that was being
+/// loaded when it was created. Resources are only referenced weakly, so
+/// tracking them doesn't keep them alive.
+struct ResidencyTracker {
+    resources: HashMap<usize, TrackedResource>,
+    tag_stack: Vec<String>,
+    cache_depth: usize,
+    /// Maps that were evicted from the map cache since the last report.
+    evicted_maps: Vec<String>,
+}
+
+impl ResidencyTracker {
+    fn current_tag(&self) -> String {
+        self.tag_stack.last().cloned().unwrap_or_else(|| GLOBAL_TAG.to_owned())
+    }
+
+    fn insert(&mut self, key: usize, label: &str, size: DeviceSize, handle: TrackedHandle) {
+        let resource = TrackedResource {
+            tag: self.current_tag(),
+            cached: self.cache_depth > 0,
+            label: label.to_owned(),
+            size,
+            handle,
+        };
+
+        self.resources.insert(key, resource);
+    }
+
+    fn prune(&mut self) {
+        self.resources.retain(|_, resource| resource.handle.is_alive());
+    }
+}
+
+lazy_static! {
+    static ref TRACKER: Mutex<ResidencyTracker> = Mutex::new(ResidencyTracker {
+        resources: HashMap::new(),
+        tag_stack: Vec::new(),
+        cache_depth: 0,
+        evicted_maps: Vec::new(),
+    });
+}
+
+/// Tags all resources created while this guard is alive with the given map.
+pub struct ResidencyScope;
+
+impl ResidencyScope {
+    pub fn new(tag: &str) -> Self {
+        TRACKER.lock().unwrap().tag_stack.push(tag.to_owned());
+        Self
+    }
+}
+
+impl Drop for ResidencyScope {
+    fn drop(&mut self) {
+        TRACKER.lock().unwrap().tag_stack.pop();
+    }
+}
+
+/// Marks all resources created while this guard is alive as owned by a
+/// loader cache.
+pub struct CacheResidencyScope;
+
+impl CacheResidencyScope {
+    pub fn enter() -> Self {
+        TRACKER.lock().unwrap().cache_depth += 1;
+        Self
+    }
+}
+
+impl Drop for CacheResidencyScope {
+    fn drop(&mut self) {
+        TRACKER.lock().unwrap().cache_depth -= 1;
+    }
+}
+
+pub fn track_texture(label: &str, texture: &Arc<ImageView>) {
+    let image = texture.image();
+    let [width, height, depth] = image.extent();
+    let size = width as DeviceSize * height as DeviceSize * depth as DeviceSize * image.format().block_size();
+
+    TRACKER.lock().unwrap().insert(
+        Arc::as_ptr(texture) as usize,
+        label,
+        size,
+        TrackedHandle::Texture(Arc::downgrade(texture)),
+    );
+}
+
+pub fn track_buffer<T: ?Sized>(label: &str, buffer: &Subbuffer<T>) {
+    let buffer = buffer.buffer();
+
+    TRACKER.lock().unwrap().insert(
+        Arc::as_ptr(buffer) as usize,
+        label,
+        buffer.size(),
+        TrackedHandle::Buffer(Arc::downgrade(buffer)),
+    );
+}
+
+fn format_bytes(bytes: DeviceSize) -> String {
+    format!("{:.2} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Remember that a map was evicted from the map cache, so its resources are
+/// expected to be freed by the next report.
+pub fn mark_map_evicted(map_name: &str) {
+    TRACKER.lock().unwrap().evicted_maps.push(map_name.to_owned());
+}
+
+/// Report all resources of maps evicted from the map cache that are still
+/// alive after switching to a new map. Resources of maps that are still
+/// cached and resources owned by the loader caches are not reported, since the
+/// caches keep them alive on purpose.
+pub fn report_residency_after_map_change(map_name: &str) {
+    let mut tracker = TRACKER.lock().unwrap();
+    tracker.prune();
+
+    let evicted_maps = std::mem::take(&mut tracker.evicted_maps);
+
+    let total = ResidencyStatistics::from_resources(tracker.resources.values());
+    let cached = ResidencyStatistics::from_resources(tracker.resources.values().filter(|resource| resource.cached));
+
+    print_debug!(
+        "resident resources after loading {MAGENTA}{map_name}{NONE}: {MAGENTA}{}{NONE} textures ({}), {MAGENTA}{}{NONE} buffers ({}), of \
+         which {MAGENTA}{}{NONE} textures ({}) and {MAGENTA}{}{NONE} buffers ({}) are cached",
+        total.texture_count,
+        format_bytes(total.texture_bytes),
+        total.buffer_count,
+        format_bytes(total.buffer_bytes),
+        cached.texture_count,
+        format_bytes(cached.texture_bytes),
+        cached.buffer_count,
+        format_bytes(cached.buffer_bytes)
+    );
+
+    for evicted_map in evicted_maps.iter().filter(|evicted_map| *evicted_map != map_name) {
+        let mut remaining: Vec<&TrackedResource> = tracker
+            .resources
+            .values()
+            .filter(|resource| resource.tag == *evicted_map && !resource.cached)
+            .collect();
+
+        if remaining.is_empty() {
+            continue;
+        }
+
+        let statistics = ResidencyStatistics::from_resources(remaining.iter().copied());
+
+        print_debug!(
+            "[{YELLOW}warning{NONE}] {MAGENTA}{}{NONE} textures ({}) and {MAGENTA}{}{NONE} buffers ({}) of evicted map \
+             {MAGENTA}{evicted_map}{NONE} are still alive",
+            statistics.texture_count,
+            format_bytes(statistics.texture_bytes),
+            statistics.buffer_count,
+            format_bytes(statistics.buffer_bytes)
+        );
+
+        remaining.sort_by(|first, second| second.size.cmp(&first.size));
+
+        for resource in remaining.iter().take(MAXIMUM_LISTED_RESOURCES) {
+            print_debug!("{} ({})", resource.label, format_bytes(resource.size));
+        }
+    }
+}
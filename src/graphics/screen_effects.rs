@@ -0,0 +1,86 @@
+use cgmath::Vector3;
+
+/// Effects that make heavy hits feel impactful. The camera shakes for a
+/// moment and the world freezes briefly (hit-stop).
+#[derive(Default)]
+pub struct ScreenEffects {
+    /// Strength of the camera shake between 0 and 1. It decays over time and
+    /// the offset grows quadratically with it, so small hits barely register.
+    trauma: f32,
+    elapsed: f32,
+    /// Remaining time in seconds for which the world is frozen.
+    hit_stop: f32,
+}
+
+impl ScreenEffects {
+    const MAXIMUM_SHAKE_OFFSET: f32 = 2.5;
+    const TRAUMA_DECAY: f32 = 1.8;
+
+    pub fn add_shake(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+
+    pub fn add_hit_stop(&mut self, duration: f32) {
+        self.hit_stop = self.hit_stop.max(duration);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advance the effects and return the time that passed for the world,
+    /// which is zero while a hit-stop is active.
+    pub fn update(&mut self, delta_time: f64) -> f64 {
+        let delta_seconds = delta_time as f32;
+
+        self.elapsed += delta_seconds;
+        self.trauma = (self.trauma - Self::TRAUMA_DECAY * delta_seconds).max(0.0);
+
+        match self.hit_stop > 0.0 {
+            true => {
+                self.hit_stop -= delta_seconds;
+                0.0
+            }
+            false => delta_time,
+        }
+    }
+
+    /// Offset of the camera. The intensity is set by the player and is zero if
+    /// camera shake is turned off.
+    pub fn camera_offset(&self, intensity: f32) -> Vector3<f32> {
+        let strength = self.trauma * self.trauma * intensity * Self::MAXIMUM_SHAKE_OFFSET;
+
+        if strength == 0.0 {
+            return Vector3::new(0.0, 0.0, 0.0);
+        }
+
+        // NOTE: Sines with unrelated frequencies are cheap and look random enough
+        // for the short duration of a shake.
+        let time = self.elapsed * 40.0;
+        Vector3::new(
+            (time * 1.1).sin() * strength,
+            (time * 1.7 + 1.3).sin() * strength * 0.5,
+            (time * 1.3 + 2.9).sin() * strength,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ScreenEffects;
+
+    #[test]
+    fn hit_stop_freezes_the_world() {
+        let mut screen_effects = ScreenEffects::default();
+        screen_effects.add_shake(0.5);
+        screen_effects.add_hit_stop(0.1);
+
+        assert_eq!(screen_effects.update(0.05), 0.0);
+        assert_eq!(screen_effects.update(0.06), 0.0);
+        assert_eq!(screen_effects.update(0.05), 0.05);
+
+        // The shake has decayed completely after the same time.
+        screen_effects.update(0.5);
+        assert_eq!(screen_effects.camera_offset(1.0), cgmath::Vector3::new(0.0, 0.0, 0.0));
+    }
+}
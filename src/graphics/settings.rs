@@ -2,17 +2,67 @@ use procedural::toggle;
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 
-use super::ShadowDetail;
+use super::{SamplerConfiguration, ShadowDetail, SpriteFiltering};
 #[cfg(feature = "debug")]
 use crate::debug::*;
+use crate::world::{CastPreviewMode, TargetPriority};
 
 #[derive(Serialize, Deserialize, toggle)]
 pub struct GraphicsSettings {
-    #[toggle]
     pub frame_limit: bool,
     #[toggle]
     pub show_interface: bool,
+    pub show_speech_bubbles: bool,
+    pub show_path_preview: bool,
+    pub speech_bubble_range: Option<usize>,
     pub shadow_detail: ShadowDetail,
+    /// Maximum distance in tiles at which other entities are rendered.
+    #[serde(default)]
+    pub entity_render_distance: Option<usize>,
+    /// Maximum number of entities rendered at the same time, including the
+    /// player.
+    #[serde(default)]
+    pub maximum_rendered_entities: Option<usize>,
+    /// Load the maps connected to the current one while the client is idle.
+    #[serde(default)]
+    pub preload_adjacent_maps: bool,
+    #[serde(default = "default_sprite_filtering")]
+    pub sprite_filtering: SpriteFiltering,
+    /// Filtering, address mode and anisotropy of each category of textures.
+    #[serde(default)]
+    pub sampler_configuration: SamplerConfiguration,
+    /// Intensity of the camera shake on heavy hits. Zero turns it off.
+    #[serde(default = "default_camera_shake")]
+    pub camera_shake: f32,
+    /// Briefly freeze the world on heavy hits.
+    #[serde(default = "default_hit_stop")]
+    pub hit_stop: bool,
+    /// Which skill casts of other entities are shown on the ground.
+    #[serde(default)]
+    pub cast_previews: CastPreviewMode,
+    /// Which of the targets on the same tile is picked when clicking it.
+    #[serde(default)]
+    pub target_priority: TargetPriority,
+    /// Mark positions that the map server points the player at, usually quest
+    /// targets.
+    #[serde(default = "default_show_quest_markers")]
+    pub show_quest_markers: bool,
+}
+
+fn default_sprite_filtering() -> SpriteFiltering {
+    SpriteFiltering::Linear
+}
+
+fn default_camera_shake() -> f32 {
+    0.5
+}
+
+fn default_hit_stop() -> bool {
+    true
+}
+
+fn default_show_quest_markers() -> bool {
+    true
 }
 
 impl Default for GraphicsSettings {
@@ -20,7 +70,20 @@ impl Default for GraphicsSettings {
         Self {
             frame_limit: true,
             show_interface: true,
+            show_speech_bubbles: true,
+            show_path_preview: false,
+            speech_bubble_range: None,
             shadow_detail: ShadowDetail::Medium,
+            entity_render_distance: None,
+            maximum_rendered_entities: None,
+            preload_adjacent_maps: false,
+            sprite_filtering: default_sprite_filtering(),
+            sampler_configuration: SamplerConfiguration::default(),
+            camera_shake: default_camera_shake(),
+            hit_stop: default_hit_stop(),
+            cast_previews: CastPreviewMode::default(),
+            target_priority: TargetPriority::default(),
+            show_quest_markers: default_show_quest_markers(),
         }
     }
 }
@@ -3,7 +3,10 @@ use cgmath::Vector2;
 use super::HotbarSlot;
 use crate::interface::{ItemMove, SkillMove, ThemeKind};
 use crate::loaders::ServiceId;
-use crate::network::{AccountId, CharacterId, CharacterServerInformation, EntityId};
+use crate::network::{
+    AccountId, CharacterCreationData, CharacterId, CharacterServerInformation, EntityId, GuildPosition, ItemIndex, MailId, Sex,
+    ShopDealType, StatType,
+};
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
@@ -20,15 +23,49 @@ pub enum UserEvent {
     Exit,
     CameraZoom(f32),
     CameraRotate(f32),
-    ToggleFrameLimit,
     ToggleShowInterface,
     OpenMenuWindow,
     OpenInventoryWindow,
     OpenEquipmentWindow,
+    OpenCartWindow,
+    OpenQuestWindow,
+    OpenAchievementWindow,
+    OpenBankWindow,
     OpenSkillTreeWindow,
+    OpenStatsWindow,
+    OpenGeneralSettingsWindow,
     OpenGraphicsSettingsWindow,
     OpenAudioSettingsWindow,
+    OpenWindowOptions(String),
+    SetWindowOpacity {
+        window_class: String,
+        opacity: f32,
+    },
+    SetWindowLocked {
+        window_class: String,
+        locked: bool,
+    },
+    OpenLayoutProfilesWindow,
+    OpenThemeEditorWindow,
+    CopyToClipboard(String),
+    RequestConfirmation {
+        text: String,
+        event: Box<UserEvent>,
+    },
+    ResolveConfirmation(bool),
+    SaveLayoutProfile(String),
+    LoadLayoutProfile(String),
+    DeleteLayoutProfile(String),
+    ExportLayoutProfile(String),
+    ImportLayoutProfile(String),
     OpenFriendsWindow,
+    OpenGuildWindow,
+    OpenSessionStatisticsWindow,
+    ResetSessionStatistics,
+    ExportSessionStatistics,
+    OpenEntitySearchWindow,
+    ClearEntitySearch,
+    SetLanguage(String),
     SetThemeFile {
         theme_file: String,
         theme_kind: ThemeKind,
@@ -40,23 +77,41 @@ pub enum UserEvent {
         theme_kind: ThemeKind,
     },
     SelectCharacter(usize),
+    PreviewCharacter(usize),
     OpenCharacterCreationWindow(usize),
-    CreateCharacter(usize, String),
-    DeleteCharacter(CharacterId),
+    UpdateCharacterAppearance {
+        sex: Sex,
+        hair_style: u16,
+        hair_color: u16,
+    },
+    CreateCharacter(usize, CharacterCreationData),
+    RequestCharacterDeletion(CharacterId),
+    ReserveCharacterDeletion(CharacterId),
+    CancelCharacterDeletion(CharacterId),
+    /// Delete the character, confirmed by the e-mail address of the account or
+    /// the birthdate of the account owner.
+    DeleteCharacter(CharacterId, String),
+    CloseCharacterDeletionWindow,
     RequestSwitchCharacterSlot(usize),
     CancelSwitchCharacterSlot,
     SwitchCharacterSlot(usize),
     RequestPlayerMove(Vector2<usize>),
     RequestPlayerInteract(EntityId),
+    RequestPickUpItem(EntityId),
+    CycleTarget,
     RequestWarpToMap(String, Vector2<usize>),
     SendMessage(String),
+    SendPartyPing(Vector2<usize>),
     NextDialog(EntityId),
     CloseDialog(EntityId),
     ChooseDialogOption(EntityId, i8),
     MoveItem(ItemMove),
+    /// Sent once the player confirmed to give away a favorite item.
+    MoveFavoriteItem(ItemMove),
     MoveSkill(SkillMove),
-    CastSkill(HotbarSlot),
-    StopSkill(HotbarSlot),
+    UseHotbarSlot(HotbarSlot),
+    ReleaseHotbarSlot(HotbarSlot),
+    ClearHotbarSlot(HotbarSlot),
     AddFriend(String),
     RemoveFriend {
         account_id: AccountId,
@@ -70,6 +125,78 @@ pub enum UserEvent {
         account_id: AccountId,
         character_id: CharacterId,
     },
+    OpenEntityContextMenu(EntityId),
+    OpenWhisperWindow(String),
+    SendWhisper {
+        name: String,
+        message: String,
+    },
+    RequestTrade(EntityId),
+    AcceptTradeRequest,
+    RejectTradeRequest,
+    OfferTradeZeny(u32),
+    LockTrade,
+    ConfirmTrade,
+    CancelTrade,
+    SelectShopDealType {
+        npc_id: EntityId,
+        deal_type: ShopDealType,
+    },
+    SetShopItemAmount {
+        position: usize,
+        amount: u16,
+    },
+    CompleteShopDeal,
+    CloseShop,
+    OpenMailbox,
+    LoadMoreMail,
+    ReadMail(MailId),
+    CloseMail,
+    TakeMailZeny,
+    TakeMailItems,
+    DeleteMail,
+    OpenMailCompose,
+    CancelMailCompose,
+    SendMail {
+        receiver_name: String,
+        title: String,
+        text: String,
+        zeny: u64,
+    },
+    OpenVendingShop(EntityId),
+    OpenVending {
+        title: String,
+        items: Vec<(ItemIndex, u16, u32)>,
+    },
+    CancelVendingSetup,
+    CloseVending,
+    ClearQuestNavigation,
+    ClaimAchievementReward(u32),
+    DepositZeny(i32),
+    WithdrawZeny(i32),
+    CloseBank,
+    RequestViewEquipment(EntityId),
+    InviteToParty(String),
+    DropItem(ItemIndex),
+    IncreaseStat(StatType),
+    SaveEquipmentSet(String),
+    EquipEquipmentSet(String),
+    EquipEquipmentSetAt(usize),
+    DeleteEquipmentSet(String),
+    SetItemFavorite {
+        index: ItemIndex,
+        is_favorite: bool,
+    },
+    SetGuildNotice {
+        subject: String,
+        notice: String,
+    },
+    ChangeGuildPosition(GuildPosition),
+    ChangeGuildMemberPosition {
+        account_id: AccountId,
+        character_id: CharacterId,
+        position_id: u32,
+    },
     #[cfg(feature = "debug")]
     ToggleFrustumCulling,
     #[cfg(feature = "debug")]
@@ -95,14 +222,16 @@ pub enum UserEvent {
     #[cfg(feature = "debug")]
     SetMidnight,
     #[cfg(feature = "debug")]
-    OpenThemeViewerWindow,
-    #[cfg(feature = "debug")]
     OpenProfilerWindow,
     #[cfg(feature = "debug")]
     OpenPacketWindow,
     #[cfg(feature = "debug")]
     ClearPacketHistory,
     #[cfg(feature = "debug")]
+    OpenEventTraceWindow,
+    #[cfg(feature = "debug")]
+    ClearEventTrace,
+    #[cfg(feature = "debug")]
     ToggleUseDebugCamera,
     #[cfg(feature = "debug")]
     CameraLookAround(Vector2<f32>),
@@ -174,4 +303,36 @@ pub enum UserEvent {
     ToggleShowPickerBuffer,
     #[cfg(feature = "debug")]
     ToggleShowFontAtlas,
+    #[cfg(feature = "debug")]
+    CaptureFrame,
+}
+
+#[cfg(feature = "debug")]
+impl UserEvent {
+    /// Camera events are emitted every frame while the camera is moving, so
+    /// they are not traced by default.
+    pub fn is_camera_event(&self) -> bool {
+        matches!(
+            self,
+            UserEvent::CameraZoom(..)
+                | UserEvent::CameraRotate(..)
+                | UserEvent::CameraLookAround(..)
+                | UserEvent::CameraMoveForward
+                | UserEvent::CameraMoveBackward
+                | UserEvent::CameraMoveLeft
+                | UserEvent::CameraMoveRight
+                | UserEvent::CameraMoveUp
+                | UserEvent::CameraAccelerate
+                | UserEvent::CameraDecelerate
+        )
+    }
+
+    /// Description of the event for the event trace. Passwords are not
+    /// included.
+    pub fn trace_description(&self) -> String {
+        match self {
+            UserEvent::LogIn { service_id, username, .. } => format!("LogIn {{ service_id: {service_id:?}, username: {username:?} }}"),
+            event => format!("{event:?}"),
+        }
+    }
 }
@@ -1,27 +1,65 @@
 mod event;
 mod key;
 mod mode;
+mod queue;
 
 use std::mem::variant_count;
 use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
 
+use arboard::Clipboard;
 use cgmath::Vector2;
 use procedural::profile;
 use winit::dpi::PhysicalPosition;
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode};
+use winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, VirtualKeyCode};
 
 pub use self::event::UserEvent;
 pub use self::key::Key;
 pub use self::mode::{Grabbed, MouseInputMode};
 #[cfg(feature = "debug")]
+pub use self::queue::TracedEvent;
+pub use self::queue::{EventSource, UserEventQueue};
+#[cfg(feature = "debug")]
+use crate::debug::*;
+#[cfg(feature = "debug")]
 use crate::graphics::RenderSettings;
 use crate::graphics::{PickerRenderTarget, PickerTarget};
-use crate::interface::{ClickAction, ElementCell, Focus, Interface, MouseCursorState, ScreenPosition, ScreenSize, WeakElementCell};
+use crate::interface::{
+    ClickAction, ElementCell, Focus, Interface, MouseCursorState, ResizeGrip, ScreenPosition, ScreenSize, TextEdit, WeakElementCell,
+};
+use crate::inventory::{HOTBAR_ROW_SLOTS, HOTBAR_SLOTS};
 use crate::network::ClientTick;
+use crate::world::StackedTarget;
 
 const MOUSE_SCOLL_MULTIPLIER: f32 = 30.0;
+/// Maximum time between two clicks on the same element for them to count as a
+/// double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 const KEY_COUNT: usize = variant_count::<VirtualKeyCode>();
 
+/// Keys that activate the hotbar slots, the number keys for the first row and
+/// the function keys for the second one.
+const HOTBAR_KEYS: [VirtualKeyCode; HOTBAR_SLOTS] = [
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+    VirtualKeyCode::F1,
+    VirtualKeyCode::F2,
+    VirtualKeyCode::F3,
+    VirtualKeyCode::F4,
+    VirtualKeyCode::F5,
+    VirtualKeyCode::F6,
+    VirtualKeyCode::F7,
+    VirtualKeyCode::F8,
+    VirtualKeyCode::F9,
+];
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct HotbarSlot(pub usize);
 
@@ -113,6 +151,16 @@ pub struct InputSystem {
     keys: [Key; KEY_COUNT],
     mouse_input_mode: MouseInputMode,
     input_buffer: Vec<char>,
+    /// Pre-edit text of the input method that was not sent to the focused
+    /// element yet. `Some(None)` clears the composition.
+    pending_composition: Option<Option<String>>,
+    clipboard: Option<Clipboard>,
+    /// Time of the last key press, click, scroll or mouse movement, used to
+    /// detect when the player is away.
+    last_activity: Instant,
+    /// Element that was last clicked with the left mouse button and when, used
+    /// to detect double clicks.
+    last_left_click: Option<(Instant, WeakElementCell)>,
 }
 
 impl InputSystem {
@@ -131,6 +179,15 @@ impl InputSystem {
 
         let mouse_input_mode = MouseInputMode::None;
         let input_buffer = Vec::new();
+        let pending_composition = None;
+        let clipboard = Clipboard::new()
+            .map_err(|_error| {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to access the clipboard: {_error}");
+            })
+            .ok();
+        let last_activity = Instant::now();
+        let last_left_click = None;
 
         Self {
             previous_mouse_position,
@@ -144,9 +201,29 @@ impl InputSystem {
             keys,
             mouse_input_mode,
             input_buffer,
+            pending_composition,
+            clipboard,
+            last_activity,
+            last_left_click,
         }
     }
 
+    /// Returns `true` if the click completes a double click on the element.
+    fn register_left_click(&mut self, element: &ElementCell) -> bool {
+        let now = Instant::now();
+        let is_double_click = self.last_left_click.take().is_some_and(|(time, previous_element)| {
+            now.duration_since(time) < DOUBLE_CLICK_INTERVAL && std::ptr::addr_eq(previous_element.as_ptr(), Rc::as_ptr(element))
+        });
+
+        // NOTE: A third click starts a new double click instead of completing another
+        // one.
+        if !is_double_click {
+            self.last_left_click = Some((now, Rc::downgrade(element)));
+        }
+
+        is_double_click
+    }
+
     pub fn reset(&mut self) {
         self.left_mouse_button.reset();
         self.right_mouse_button.reset();
@@ -159,6 +236,7 @@ impl InputSystem {
             left: position.x as f32,
             top: position.y as f32,
         };
+        self.last_activity = Instant::now();
     }
 
     pub fn update_mouse_buttons(&mut self, button: MouseButton, state: ElementState) {
@@ -169,6 +247,8 @@ impl InputSystem {
             MouseButton::Right => self.right_mouse_button.set_down(pressed),
             _ignored => {}
         }
+
+        self.last_activity = Instant::now();
     }
 
     pub fn update_mouse_wheel(&mut self, delta: MouseScrollDelta) {
@@ -176,15 +256,39 @@ impl InputSystem {
             MouseScrollDelta::LineDelta(_x, y) => self.new_scroll_position += y * MOUSE_SCOLL_MULTIPLIER,
             MouseScrollDelta::PixelDelta(position) => self.new_scroll_position += position.y as f32,
         }
+
+        self.last_activity = Instant::now();
     }
 
     pub fn update_keyboard(&mut self, virtual_code: VirtualKeyCode, state: ElementState) {
         let pressed = matches!(state, ElementState::Pressed);
         self.keys[virtual_code as usize].set_down(pressed);
+        self.last_activity = Instant::now();
     }
 
     pub fn buffer_character(&mut self, character: char) {
         self.input_buffer.push(character);
+        self.last_activity = Instant::now();
+    }
+
+    /// Time since the last input of the player.
+    pub fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
+    /// Handle the events of input methods, which are used to type characters
+    /// that are not on the keyboard, most notably Chinese, Japanese and
+    /// Korean.
+    pub fn update_ime(&mut self, ime: Ime) {
+        match ime {
+            Ime::Enabled => {}
+            Ime::Preedit(text, _cursor) => self.pending_composition = Some((!text.is_empty()).then_some(text)),
+            Ime::Commit(text) => {
+                self.input_buffer.extend(text.chars());
+                self.pending_composition = Some(None);
+            }
+            Ime::Disabled => self.pending_composition = Some(None),
+        }
     }
 
     pub fn update_delta(&mut self) {
@@ -199,6 +303,19 @@ impl InputSystem {
         self.keys.iter_mut().for_each(|key| key.update());
     }
 
+    pub fn copy_to_clipboard(&mut self, text: String) {
+        if let Some(clipboard) = &mut self.clipboard
+            && let Err(_error) = clipboard.set_text(text)
+        {
+            #[cfg(feature = "debug")]
+            print_debug!("[{RED}error{NONE}] failed to copy to the clipboard: {_error}");
+        }
+    }
+
+    fn paste_from_clipboard(&mut self) -> Option<String> {
+        self.clipboard.as_mut().and_then(|clipboard| clipboard.get_text().ok())
+    }
+
     fn get_key(&self, key_code: VirtualKeyCode) -> &Key {
         &self.keys[key_code as usize]
     }
@@ -207,17 +324,20 @@ impl InputSystem {
     pub fn user_events(
         &mut self,
         interface: &mut Interface,
+        event_queue: &mut UserEventQueue,
         focus_state: &mut FocusState,
         picker_target: &mut PickerRenderTarget,
+        stacked_target: Option<StackedTarget>,
         #[cfg(feature = "debug")] render_settings: &RenderSettings,
         window_size: Vector2<usize>,
         client_tick: ClientTick,
-    ) -> (Vec<UserEvent>, Option<ElementCell>, Option<ElementCell>, Option<PickerTarget>) {
-        let mut events = Vec::new();
+    ) -> (Option<ElementCell>, Option<ElementCell>, Option<PickerTarget>) {
         let mut mouse_target = None;
         let (hovered_element, mut window_index) = interface.hovered_element(self.new_mouse_position, &self.mouse_input_mode);
 
         let shift_down = self.get_key(VirtualKeyCode::LShift).down();
+        let control_down = self.get_key(VirtualKeyCode::LControl).down() || self.get_key(VirtualKeyCode::RControl).down();
+        let alt_down = self.get_key(VirtualKeyCode::LAlt).down() || self.get_key(VirtualKeyCode::RAlt).down();
 
         #[cfg(feature = "debug")]
         let lock_actions = render_settings.use_debug_camera;
@@ -226,6 +346,17 @@ impl InputSystem {
 
         if self.left_mouse_button.pressed() || self.right_mouse_button.pressed() {
             focus_state.remove_focus();
+
+            // NOTE: Clicking anywhere outside of the context menu closes it.
+            if let Some(context_menu_index) = interface.context_menu_index()
+                && window_index != Some(context_menu_index)
+            {
+                interface.close_window(focus_state, context_menu_index);
+                window_index = window_index.map(|index| match index > context_menu_index {
+                    true => index - 1,
+                    false => index,
+                });
+            }
         }
 
         if shift_down {
@@ -239,11 +370,20 @@ impl InputSystem {
 
                 if self.right_mouse_button.pressed() {
                     *window_index = interface.move_window_to_top(*window_index);
-                    self.mouse_input_mode = MouseInputMode::ResizeInterface(*window_index);
+                    self.mouse_input_mode = MouseInputMode::ResizeInterface(*window_index, ResizeGrip::BOTTOM_RIGHT);
                 }
             }
         }
 
+        if let Some(window_index) = &mut window_index
+            && self.mouse_input_mode.is_none()
+            && self.left_mouse_button.pressed()
+            && let Some(resize_grip) = interface.hovered_resize_grip(*window_index, self.new_mouse_position)
+        {
+            *window_index = interface.move_window_to_top(*window_index);
+            self.mouse_input_mode = MouseInputMode::ResizeInterface(*window_index, resize_grip);
+        }
+
         if let Some(index) = window_index
             && self.left_mouse_button.pressed()
         {
@@ -259,11 +399,15 @@ impl InputSystem {
             self.mouse_input_mode = MouseInputMode::ClickInterface;
 
             if let Some(hovered_element) = &hovered_element {
-                let actions = match self.left_mouse_button.pressed() {
+                let mut actions = match self.left_mouse_button.pressed() {
                     true => interface.left_click_element(hovered_element, *window_index),
                     false => interface.right_click_element(hovered_element, *window_index),
                 };
 
+                if self.left_mouse_button.pressed() && self.register_left_click(hovered_element) {
+                    actions.extend(interface.double_click_element(hovered_element, *window_index));
+                }
+
                 for action in actions {
                     match action {
                         ClickAction::ChangeEvent(..) => {}
@@ -280,7 +424,7 @@ impl InputSystem {
                             focus_state.update_focused_element(new_focused_element, *window_index);
                         }
 
-                        ClickAction::Event(event) => events.push(event),
+                        ClickAction::Event(event) => event_queue.push(event, EventSource::Interface),
 
                         ClickAction::MoveInterface => self.mouse_input_mode = MouseInputMode::MoveInterface(*window_index),
 
@@ -288,6 +432,8 @@ impl InputSystem {
                             self.mouse_input_mode = MouseInputMode::DragElement((hovered_element.clone(), *window_index))
                         }
 
+                        ClickAction::SelectTab(active_tab) => interface.set_active_tab(*window_index, active_tab),
+
                         ClickAction::MoveItem(item_source, item) => {
                             self.mouse_input_mode = MouseInputMode::MoveItem(item_source, item);
                             // Needs to re-render because some elements will render differently
@@ -304,7 +450,12 @@ impl InputSystem {
 
                         ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
 
-                        ClickAction::CloseWindow => interface.close_window(focus_state, *window_index),
+                        ClickAction::CloseWindow => {
+                            // NOTE: Locked windows can't be closed by the player.
+                            if !interface.get_window(*window_index).is_locked() {
+                                interface.close_window(focus_state, *window_index);
+                            }
+                        }
 
                         ClickAction::OpenPopup {
                             element,
@@ -313,6 +464,8 @@ impl InputSystem {
                         } => interface.open_popup(element, position_tracker, size_tracker, *window_index),
 
                         ClickAction::ClosePopup => interface.close_popup(*window_index),
+
+                        ClickAction::OpenContextMenu(entries) => interface.open_context_menu(focus_state, self.new_mouse_position, entries),
                     }
                 }
             }
@@ -321,7 +474,7 @@ impl InputSystem {
         if self.left_mouse_button.released() {
             if let MouseInputMode::MoveInterface(identifier) = self.mouse_input_mode {
                 match self.right_mouse_button.down() && !self.right_mouse_button.released() {
-                    true => self.mouse_input_mode = MouseInputMode::ResizeInterface(identifier),
+                    true => self.mouse_input_mode = MouseInputMode::ResizeInterface(identifier, ResizeGrip::BOTTOM_RIGHT),
                     false => self.mouse_input_mode = MouseInputMode::None,
                 }
             } else {
@@ -334,14 +487,14 @@ impl InputSystem {
                     MouseInputMode::MoveItem(item_source, item) => {
                         if let Some(hovered_element) = &hovered_element {
                             if let Some(item_move) = hovered_element.borrow_mut().drop_item(item_source, item) {
-                                events.push(UserEvent::MoveItem(item_move));
+                                event_queue.push(UserEvent::MoveItem(item_move), EventSource::Interface);
                             }
                         }
                     }
                     MouseInputMode::MoveSkill(skill_source, skill) => {
                         if let Some(hovered_element) = &hovered_element {
                             if let Some(skill_move) = hovered_element.borrow_mut().drop_skill(skill_source, skill) {
-                                events.push(UserEvent::MoveSkill(skill_move));
+                                event_queue.push(UserEvent::MoveSkill(skill_move), EventSource::Interface);
                             }
                         }
                     }
@@ -350,8 +503,12 @@ impl InputSystem {
             }
         }
 
+        // NOTE: Releasing the right mouse button without rotating the camera opens the
+        // context menu of the hovered entity.
+        let right_clicked_world = self.right_mouse_button.released() && self.mouse_input_mode.is_none();
+
         if self.right_mouse_button.released() {
-            if let MouseInputMode::ResizeInterface(identifier) = self.mouse_input_mode {
+            if let MouseInputMode::ResizeInterface(identifier, _) = self.mouse_input_mode {
                 match self.left_mouse_button.down() && !self.left_mouse_button.released() {
                     true => self.mouse_input_mode = MouseInputMode::MoveInterface(identifier),
                     false => self.mouse_input_mode = MouseInputMode::None,
@@ -383,13 +540,13 @@ impl InputSystem {
                 }
                 interface.set_mouse_cursor_state(MouseCursorState::Grab, client_tick);
             }
-            MouseInputMode::ResizeInterface(identifier) => {
+            MouseInputMode::ResizeInterface(identifier, resize_grip) => {
                 if self.mouse_delta != ScreenSize::default() {
-                    interface.resize_window(*identifier, self.mouse_delta);
+                    interface.resize_window(*identifier, *resize_grip, self.mouse_delta);
                 }
             }
             MouseInputMode::RotateCamera => {
-                events.push(UserEvent::CameraRotate(self.mouse_delta.width));
+                event_queue.push(UserEvent::CameraRotate(self.mouse_delta.width), EventSource::Mouse);
                 interface.set_mouse_cursor_state(MouseCursorState::RotateCamera, client_tick);
             }
             MouseInputMode::ClickInterface => interface.set_mouse_cursor_state(MouseCursorState::Click, client_tick),
@@ -402,12 +559,41 @@ impl InputSystem {
                 if let Some(element) = &hovered_element {
                     interface.scroll_element(element, window_index, self.scroll_delta);
                 }
+            } else if shift_down && !lock_actions {
+                event_queue.push(UserEvent::CycleTarget, EventSource::Mouse);
             } else if !lock_actions {
-                events.push(UserEvent::CameraZoom(self.scroll_delta));
+                event_queue.push(UserEvent::CameraZoom(self.scroll_delta), EventSource::Mouse);
+            }
+        }
+
+        let page_up_pressed = self.get_key(VirtualKeyCode::PageUp).pressed();
+
+        if page_up_pressed || self.get_key(VirtualKeyCode::PageDown).pressed() {
+            let scroll_target = focus_state
+                .get_focused_element()
+                .or_else(|| hovered_element.clone().zip(window_index));
+
+            if let Some((element, window_index)) = scroll_target {
+                interface.scroll_page_element(&element, window_index, page_up_pressed);
             }
         }
 
         let characters = self.input_buffer.drain(..).collect::<Vec<_>>();
+        let composition = self.pending_composition.take();
+
+        // NOTE: While a confirmation is pending, Enter and Escape resolve it instead of
+        // doing what they usually do.
+        let confirmation_pending = interface.is_confirmation_pending();
+
+        if confirmation_pending {
+            if self.get_key(VirtualKeyCode::Return).pressed() {
+                event_queue.push(UserEvent::ResolveConfirmation(true), EventSource::Keyboard);
+            }
+
+            if self.get_key(VirtualKeyCode::Escape).pressed() {
+                event_queue.push(UserEvent::ResolveConfirmation(false), EventSource::Keyboard);
+            }
+        }
 
         if let Some((focused_element, focused_window)) = &focus_state.get_focused_element() {
             // this will currently not affect the following statements, which is a bit
@@ -422,17 +608,76 @@ impl InputSystem {
                     .focus_next(focused_element.clone(), None, Focus::new(shift_down.into()));
 
                 focus_state.update_focused_element(new_focused_element, *focused_window);
+
+                if let Some((new_focused_element, window_index)) = focus_state.get_focused_element() {
+                    interface.scroll_to_element(&new_focused_element, window_index);
+                }
             }
 
-            if self.get_key(VirtualKeyCode::Return).pressed() {
+            if control_down {
+                if self.get_key(VirtualKeyCode::A).pressed() {
+                    interface.edit_text_element(focused_element, *focused_window, TextEdit::SelectAll);
+                }
+
+                let copy_pressed = self.get_key(VirtualKeyCode::C).pressed();
+                let cut_pressed = self.get_key(VirtualKeyCode::X).pressed();
+
+                if copy_pressed || cut_pressed {
+                    let text = focused_element.borrow().copy_text();
+
+                    if let Some(text) = text {
+                        self.copy_to_clipboard(text);
+
+                        if cut_pressed {
+                            interface.edit_text_element(focused_element, *focused_window, TextEdit::DeleteSelection);
+                        }
+                    }
+                }
+
+                if self.get_key(VirtualKeyCode::V).pressed()
+                    && let Some(text) = self.paste_from_clipboard()
+                {
+                    interface.edit_text_element(focused_element, *focused_window, TextEdit::Insert(text));
+                }
+            }
+
+            if self.get_key(VirtualKeyCode::Left).pressed() {
+                let edit = match shift_down {
+                    true => TextEdit::ExtendSelection,
+                    false => TextEdit::CursorLeft,
+                };
+                interface.edit_text_element(focused_element, *focused_window, edit);
+            }
+
+            if self.get_key(VirtualKeyCode::Right).pressed() {
+                let edit = match shift_down {
+                    true => TextEdit::ShrinkSelection,
+                    false => TextEdit::CursorRight,
+                };
+                interface.edit_text_element(focused_element, *focused_window, edit);
+            }
+
+            if self.get_key(VirtualKeyCode::Up).pressed() {
+                interface.edit_text_element(focused_element, *focused_window, TextEdit::CursorUp);
+            }
+
+            if self.get_key(VirtualKeyCode::Down).pressed() {
+                interface.edit_text_element(focused_element, *focused_window, TextEdit::CursorDown);
+            }
+
+            if !confirmation_pending && self.get_key(VirtualKeyCode::Return).pressed() {
                 let actions = interface.left_click_element(focused_element, *focused_window);
 
                 for action in actions {
                     // TODO: remove and replace with proper event
                     match action {
-                        ClickAction::Event(event) => events.push(event),
+                        ClickAction::Event(event) => event_queue.push(event, EventSource::Interface),
                         ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
-                        ClickAction::CloseWindow => interface.close_window(focus_state, *focused_window),
+                        ClickAction::CloseWindow => {
+                            if !interface.get_window(*focused_window).is_locked() {
+                                interface.close_window(focus_state, *focused_window);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -447,6 +692,10 @@ impl InputSystem {
         }
 
         if let Some((focused_element, focused_window)) = &focus_state.get_focused_element() {
+            if let Some(composition) = composition {
+                interface.input_composition_element(focused_element, *focused_window, composition);
+            }
+
             for character in characters {
                 match character {
                     // ignore since we need to handle tab knowing the state of shift
@@ -472,22 +721,31 @@ impl InputSystem {
 
                                     focus_state.update_focused_element(new_focused_element, *focused_window);
                                 }
-                                ClickAction::Event(event) => events.push(event),
+                                ClickAction::Event(event) => event_queue.push(event, EventSource::Interface),
                                 ClickAction::MoveInterface => self.mouse_input_mode = MouseInputMode::MoveInterface(*focused_window),
                                 ClickAction::DragElement => {
                                     self.mouse_input_mode = MouseInputMode::DragElement((focused_element.clone(), *focused_window))
                                 }
+                                ClickAction::SelectTab(active_tab) => interface.set_active_tab(*focused_window, active_tab),
                                 // TODO: should just move immediately ?
                                 ClickAction::MoveItem(..) => {}
                                 ClickAction::MoveSkill(..) => {}
                                 ClickAction::OpenWindow(prototype_window) => interface.open_window(focus_state, prototype_window.as_ref()),
-                                ClickAction::CloseWindow => interface.close_window(focus_state, *focused_window),
+                                ClickAction::CloseWindow => {
+                                    if !interface.get_window(*focused_window).is_locked() {
+                                        interface.close_window(focus_state, *focused_window);
+                                    }
+                                }
                                 ClickAction::OpenPopup {
                                     element,
                                     position_tracker,
                                     size_tracker,
                                 } => interface.open_popup(element, position_tracker, size_tracker, *focused_window),
                                 ClickAction::ClosePopup => interface.close_popup(*focused_window),
+                                ClickAction::OpenContextMenu(entries) => {
+                                    let (position, _) = interface.get_window(*focused_window).get_area();
+                                    interface.open_context_menu(focus_state, position, entries);
+                                }
                             }
                         }
                     }
@@ -498,76 +756,84 @@ impl InputSystem {
                 interface.first_focused_element(focus_state);
             }
 
-            if self.get_key(VirtualKeyCode::Escape).pressed() {
-                events.push(UserEvent::OpenMenuWindow);
-            }
-
-            if self.get_key(VirtualKeyCode::I).pressed() {
-                events.push(UserEvent::OpenInventoryWindow);
+            // NOTE: Elements that can't be focused, like the chat, can still be copied
+            // from by hovering them.
+            if control_down
+                && self.get_key(VirtualKeyCode::C).pressed()
+                && let Some(text) = hovered_element.as_ref().and_then(|element| element.borrow().copy_text())
+            {
+                self.copy_to_clipboard(text);
             }
 
-            if self.get_key(VirtualKeyCode::H).pressed() && shift_down {
-                events.push(UserEvent::ToggleShowInterface);
+            if !confirmation_pending && self.get_key(VirtualKeyCode::Escape).pressed() {
+                event_queue.push(UserEvent::OpenMenuWindow, EventSource::Keyboard);
             }
 
-            if self.get_key(VirtualKeyCode::J).pressed() {
-                events.push(UserEvent::CastSkill(HotbarSlot(0)));
+            if self.get_key(VirtualKeyCode::I).pressed() {
+                event_queue.push(UserEvent::OpenInventoryWindow, EventSource::Keyboard);
             }
 
-            if self.get_key(VirtualKeyCode::J).released() {
-                events.push(UserEvent::StopSkill(HotbarSlot(0)));
+            if self.get_key(VirtualKeyCode::W).pressed() && alt_down {
+                event_queue.push(UserEvent::OpenCartWindow, EventSource::Keyboard);
             }
 
-            if self.get_key(VirtualKeyCode::L).pressed() {
-                events.push(UserEvent::CastSkill(HotbarSlot(1)));
+            if self.get_key(VirtualKeyCode::U).pressed() && alt_down {
+                event_queue.push(UserEvent::OpenQuestWindow, EventSource::Keyboard);
             }
 
-            if self.get_key(VirtualKeyCode::L).released() {
-                events.push(UserEvent::StopSkill(HotbarSlot(1)));
+            if self.get_key(VirtualKeyCode::H).pressed() && shift_down {
+                event_queue.push(UserEvent::ToggleShowInterface, EventSource::Keyboard);
             }
 
-            if self.get_key(VirtualKeyCode::U).pressed() {
-                events.push(UserEvent::CastSkill(HotbarSlot(2)));
-            }
+            for (slot, key) in HOTBAR_KEYS.into_iter().enumerate() {
+                if self.get_key(key).pressed() {
+                    // NOTE: Holding alt switches the number keys from the hotbar to the equipment
+                    // sets.
+                    match alt_down && slot < HOTBAR_ROW_SLOTS {
+                        true => event_queue.push(UserEvent::EquipEquipmentSetAt(slot), EventSource::Keyboard),
+                        false => event_queue.push(UserEvent::UseHotbarSlot(HotbarSlot(slot)), EventSource::Keyboard),
+                    }
+                }
 
-            if self.get_key(VirtualKeyCode::U).released() {
-                events.push(UserEvent::StopSkill(HotbarSlot(2)));
+                if self.get_key(key).released() {
+                    event_queue.push(UserEvent::ReleaseHotbarSlot(HotbarSlot(slot)), EventSource::Keyboard);
+                }
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::M).pressed() {
-                events.push(UserEvent::OpenMapsWindow);
+                event_queue.push(UserEvent::OpenMapsWindow, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::R).pressed() {
-                events.push(UserEvent::OpenRenderSettingsWindow);
+                event_queue.push(UserEvent::OpenRenderSettingsWindow, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::T).pressed() {
-                events.push(UserEvent::OpenTimeWindow);
+                event_queue.push(UserEvent::OpenTimeWindow, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::P).pressed() {
-                events.push(UserEvent::OpenPacketWindow);
+                event_queue.push(UserEvent::OpenPacketWindow, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::LShift).pressed() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraAccelerate);
+                event_queue.push(UserEvent::CameraAccelerate, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::LShift).released() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraDecelerate);
+                event_queue.push(UserEvent::CameraDecelerate, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::F).pressed() {
-                events.push(UserEvent::ToggleUseDebugCamera);
-                events.push(UserEvent::CameraDecelerate);
+                event_queue.push(UserEvent::ToggleUseDebugCamera, EventSource::Keyboard);
+                event_queue.push(UserEvent::CameraDecelerate, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
@@ -576,35 +842,35 @@ impl InputSystem {
                 && self.mouse_input_mode.is_none()
                 && render_settings.use_debug_camera
             {
-                events.push(UserEvent::CameraLookAround(-Vector2::new(
-                    self.mouse_delta.width,
-                    self.mouse_delta.height,
-                )));
+                event_queue.push(
+                    UserEvent::CameraLookAround(-Vector2::new(self.mouse_delta.width, self.mouse_delta.height)),
+                    EventSource::Mouse,
+                );
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::W).down() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraMoveForward);
+                event_queue.push(UserEvent::CameraMoveForward, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::S).down() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraMoveBackward);
+                event_queue.push(UserEvent::CameraMoveBackward, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::A).down() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraMoveLeft);
+                event_queue.push(UserEvent::CameraMoveLeft, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::D).down() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraMoveRight);
+                event_queue.push(UserEvent::CameraMoveRight, EventSource::Keyboard);
             }
 
             #[cfg(feature = "debug")]
             if self.get_key(VirtualKeyCode::Space).down() && render_settings.use_debug_camera {
-                events.push(UserEvent::CameraMoveUp);
+                event_queue.push(UserEvent::CameraMoveUp, EventSource::Keyboard);
             }
         }
 
@@ -623,16 +889,31 @@ impl InputSystem {
                     let picker_target = PickerTarget::from(pixel);
 
                     if self.left_mouse_button.pressed() {
-                        match picker_target {
-                            PickerTarget::Entity(entity_id) => events.push(UserEvent::RequestPlayerInteract(entity_id)),
-                            PickerTarget::Tile { x, y } => {
+                        // NOTE: Targets on the same tile cover each other, so the target stack
+                        // decides which one is interacted with.
+                        match (stacked_target, &picker_target) {
+                            (_, &PickerTarget::Tile { x, y }) if self.get_key(VirtualKeyCode::LAlt).down() => {
+                                event_queue.push(
+                                    UserEvent::SendPartyPing(Vector2::new(x as usize, y as usize)),
+                                    EventSource::World,
+                                );
+                            }
+                            (Some(StackedTarget::Entity(entity_id)), _) | (None, &PickerTarget::Entity(entity_id)) => {
+                                event_queue.push(UserEvent::RequestPlayerInteract(entity_id), EventSource::World)
+                            }
+                            (Some(StackedTarget::GroundItem(entity_id)), _) => {
+                                event_queue.push(UserEvent::RequestPickUpItem(entity_id), EventSource::World)
+                            }
+                            (None, &PickerTarget::Tile { x, y }) => {
                                 let position = Vector2::new(x as usize, y as usize);
                                 self.mouse_input_mode = MouseInputMode::Walk(position);
 
-                                events.push(UserEvent::RequestPlayerMove(position));
+                                event_queue.push(UserEvent::RequestPlayerMove(position), EventSource::World);
                             }
                             #[cfg(feature = "debug")]
-                            PickerTarget::Marker(marker_identifier) => events.push(UserEvent::OpenMarkerDetails(marker_identifier)),
+                            (None, &PickerTarget::Marker(marker_identifier)) => {
+                                event_queue.push(UserEvent::OpenMarkerDetails(marker_identifier), EventSource::World)
+                            }
                         }
                     } else if self.left_mouse_button.down()
                         && let MouseInputMode::Walk(requested_position) = &mut self.mouse_input_mode
@@ -643,7 +924,18 @@ impl InputSystem {
                         if new_position != *requested_position {
                             *requested_position = new_position;
 
-                            events.push(UserEvent::RequestPlayerMove(new_position));
+                            event_queue.push(UserEvent::RequestPlayerMove(new_position), EventSource::World);
+                        }
+                    }
+
+                    if right_clicked_world {
+                        let entity_id = match (stacked_target, &picker_target) {
+                            (Some(StackedTarget::Entity(entity_id)), _) | (None, &PickerTarget::Entity(entity_id)) => Some(entity_id),
+                            _ => None,
+                        };
+
+                        if let Some(entity_id) = entity_id {
+                            event_queue.push(UserEvent::OpenEntityContextMenu(entity_id), EventSource::World);
                         }
                     }
 
@@ -657,7 +949,10 @@ impl InputSystem {
         // TODO: this will fail if the user hovers over an entity that changes the
         // cursor and then immediately over a different one that doesn't,
         // because main wont set the default cursor
-        if self.mouse_input_mode.is_none() && !matches!(mouse_target, Some(PickerTarget::Entity(_))) {
+        if self.mouse_input_mode.is_none()
+            && !matches!(mouse_target, Some(PickerTarget::Entity(_)))
+            && !matches!(stacked_target, Some(StackedTarget::Entity(_)))
+        {
             interface.set_mouse_cursor_state(MouseCursorState::Default, client_tick);
         }
 
@@ -683,7 +978,7 @@ impl InputSystem {
 
         let focused_element = focus_state.update(&hovered_element, window_index);
 
-        (events, hovered_element, focused_element, mouse_target)
+        (hovered_element, focused_element, mouse_target)
     }
 
     pub fn get_mouse_position(&self) -> ScreenPosition {
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use cgmath::Vector2;
 use vulkano::image::view::ImageView;
 
-use crate::interface::{ElementCell, ItemSource, SkillSource};
+use crate::interface::{ElementCell, ItemSource, ResizeGrip, SkillSource};
 use crate::inventory::{Item, Skill};
 use crate::loaders::{Actions, AnimationState, Sprite};
 
@@ -12,7 +12,7 @@ pub enum MouseInputMode {
     MoveItem(ItemSource, Item),
     MoveSkill(SkillSource, Skill),
     MoveInterface(usize),
-    ResizeInterface(usize),
+    ResizeInterface(usize, ResizeGrip),
     DragElement((ElementCell, usize)),
     ClickInterface,
     RotateCamera,
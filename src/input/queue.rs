@@ -0,0 +1,131 @@
+use std::fmt::{Display, Formatter};
+
+use super::UserEvent;
+#[cfg(feature = "debug")]
+use crate::debug::RingBuffer;
+#[cfg(feature = "debug")]
+use crate::interface::{EventTraceWindow, TrackedState, ValueState};
+
+#[cfg(feature = "debug")]
+const TRACE_SIZE: usize = 256;
+
+/// Where a [`UserEvent`] was emitted from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventSource {
+    /// Keyboard shortcuts that are not handled by the interface.
+    Keyboard,
+    /// Camera controls using the mouse.
+    Mouse,
+    /// Elements of the interface, either by clicking or by dropping items and
+    /// skills on them.
+    Interface,
+    /// Clicking on entities or tiles in the game world.
+    World,
+}
+
+impl Display for EventSource {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventSource::Keyboard => write!(formatter, "keyboard"),
+            EventSource::Mouse => write!(formatter, "mouse"),
+            EventSource::Interface => write!(formatter, "interface"),
+            EventSource::World => write!(formatter, "world"),
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+pub struct TracedEvent {
+    frame: usize,
+    source: EventSource,
+    description: String,
+}
+
+#[cfg(feature = "debug")]
+impl Display for TracedEvent {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "^888888{}^000000 [{}] {}", self.frame, self.source, self.description)
+    }
+}
+
+/// Collects all [`UserEvent`]s emitted during a frame. The events are only
+/// executed once they are taken from the queue, so events emitted while
+/// handling other events are deferred to the next frame.
+#[derive(Default)]
+pub struct UserEventQueue {
+    events: Vec<UserEvent>,
+    #[cfg(feature = "debug")]
+    frame: usize,
+    #[cfg(feature = "debug")]
+    trace: TrackedState<RingBuffer<TracedEvent, TRACE_SIZE>>,
+    #[cfg(feature = "debug")]
+    trace_camera_events: TrackedState<bool>,
+}
+
+impl UserEventQueue {
+    pub fn push(&mut self, event: UserEvent, _source: EventSource) {
+        #[cfg(feature = "debug")]
+        if self.trace_camera_events.get() || !event.is_camera_event() {
+            let traced_event = TracedEvent {
+                frame: self.frame,
+                source: _source,
+                description: event.trace_description(),
+            };
+
+            self.trace.with_mut(|trace| {
+                trace.push(traced_event);
+                ValueState::Mutated(())
+            });
+        }
+
+        self.events.push(event);
+    }
+
+    /// Take all events emitted since the last call. Should only be called once
+    /// per frame.
+    pub fn take_events(&mut self) -> Vec<UserEvent> {
+        #[cfg(feature = "debug")]
+        {
+            self.frame = self.frame.wrapping_add(1);
+        }
+
+        std::mem::take(&mut self.events)
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn clear_trace(&mut self) {
+        self.trace.with_mut(|trace| {
+            trace.clear();
+            ValueState::Mutated(())
+        });
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn trace_window(&self) -> EventTraceWindow<TRACE_SIZE> {
+        EventTraceWindow::new(self.trace.new_remote(), self.trace_camera_events.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EventSource, UserEventQueue};
+    use crate::input::UserEvent;
+
+    #[test]
+    fn events_are_deferred_until_taken() {
+        let mut queue = UserEventQueue::default();
+
+        queue.push(UserEvent::OpenMenuWindow, EventSource::Keyboard);
+        queue.push(UserEvent::CameraZoom(1.0), EventSource::Mouse);
+
+        let events = queue.take_events();
+        queue.push(UserEvent::Exit, EventSource::Interface);
+
+        assert!(matches!(events.as_slice(), [
+            UserEvent::OpenMenuWindow,
+            UserEvent::CameraZoom(_)
+        ]));
+        assert!(matches!(queue.take_events().as_slice(), [UserEvent::Exit]));
+        assert!(queue.take_events().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use std::cell::Cell;
+use std::time::Instant;
+
+use crate::graphics::Color;
+use crate::interface::{ChangeEvent, InterfaceSettings};
+
+/// Curve that maps the linear progress of an [`Animation`] to the progress of
+/// its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => progress,
+            Easing::EaseIn => progress * progress,
+            Easing::EaseOut => 1.0 - (1.0 - progress) * (1.0 - progress),
+            Easing::EaseInOut => match progress < 0.5 {
+                true => 2.0 * progress * progress,
+                false => 1.0 - (-2.0 * progress + 2.0).powi(2) / 2.0,
+            },
+        }
+    }
+}
+
+/// Values that can be animated.
+pub trait Interpolate: Copy {
+    fn interpolate(self, target: Self, factor: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, target: Self, factor: f32) -> Self {
+        self + (target - self) * factor
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, target: Self, factor: f32) -> Self {
+        Color {
+            red: self.red.interpolate(target.red, factor),
+            green: self.green.interpolate(target.green, factor),
+            blue: self.blue.interpolate(target.blue, factor),
+            alpha: self.alpha.interpolate(target.alpha, factor),
+        }
+    }
+}
+
+/// Transition of a value over time. Animations are driven by the per-frame
+/// update of the interface: elements call [`update`](Self::update) to request
+/// renders while the animation is running and read the current value with
+/// [`get`](Self::get) when rendering.
+pub struct Animation<T: Interpolate> {
+    from: T,
+    to: T,
+    /// Duration in seconds.
+    duration: f32,
+    easing: Easing,
+    start_time: Instant,
+    /// Set once the final value has been requested to render.
+    settled: Cell<bool>,
+}
+
+impl<T: Interpolate> Animation<T> {
+    pub fn new(from: T, to: T, duration: f32, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing,
+            start_time: Instant::now(),
+            settled: Cell::new(false),
+        }
+    }
+
+    /// Animation that stays at the given value until it is retargeted.
+    pub fn fixed(value: T, duration: f32, easing: Easing) -> Self {
+        Self {
+            settled: Cell::new(true),
+            ..Self::new(value, value, duration, easing)
+        }
+    }
+
+    fn progress(&self) -> f32 {
+        match self.duration > 0.0 {
+            true => (self.start_time.elapsed().as_secs_f32() / self.duration).min(1.0),
+            false => 1.0,
+        }
+    }
+
+    fn current(&self) -> T {
+        self.from.interpolate(self.to, self.easing.apply(self.progress()))
+    }
+
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Current value of the animation. If animations are disabled, this is
+    /// always the target value.
+    pub fn get(&self, interface_settings: &InterfaceSettings) -> T {
+        match interface_settings.animations {
+            true => self.current(),
+            false => self.to,
+        }
+    }
+
+    /// Animate from the current value to a new target.
+    pub fn retarget(&mut self, to: T) {
+        self.from = self.current();
+        self.to = to;
+        self.start_time = Instant::now();
+        self.settled.set(false);
+    }
+
+    /// Returns the change event while the animation is running, including
+    /// once more after it finished so the final value is rendered.
+    pub fn update(&self, change_event: ChangeEvent) -> Option<ChangeEvent> {
+        if self.settled.get() {
+            return None;
+        }
+
+        if self.is_finished() {
+            self.settled.set(true);
+        }
+
+        Some(change_event)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Easing, Interpolate};
+
+    #[test]
+    fn easing_bounds() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn easing_curves() {
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.25);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn interpolate() {
+        assert_eq!(2.0.interpolate(4.0, 0.5), 3.0);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Seconds without any damage involving the player after which the player is
+/// no longer considered to be in combat.
+const COMBAT_TIMEOUT: f32 = 5.0;
+/// Opacity of faded windows while the player is in combat.
+pub const COMBAT_OPACITY: f32 = 0.25;
+
+/// How the windows that get in the way during fights change while the player
+/// is in combat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombatWindowMode {
+    #[default]
+    Unchanged,
+    Faded,
+    Hidden,
+}
+
+/// Windows that are faded or hidden while the player is in combat.
+#[derive(Serialize, Deserialize)]
+pub struct CombatWindows {
+    pub mode: CombatWindowMode,
+    /// Classes of the affected windows. Not exposed in the interface, but can
+    /// be changed in the settings file.
+    pub window_classes: Vec<String>,
+}
+
+impl Default for CombatWindows {
+    fn default() -> Self {
+        let window_classes = ["inventory", "equipment", "skill_tree"].map(str::to_owned).to_vec();
+
+        Self {
+            mode: CombatWindowMode::default(),
+            window_classes,
+        }
+    }
+}
+
+impl CombatWindows {
+    pub fn affects(&self, window_class: Option<&str>) -> bool {
+        window_class.is_some_and(|window_class| self.window_classes.iter().any(|class| class == window_class))
+    }
+}
+
+/// Keeps track of whether the player is in combat. Every hit dealt or taken
+/// by the player extends the combat.
+#[derive(Default)]
+pub struct CombatTimer {
+    remaining: f32,
+}
+
+impl CombatTimer {
+    pub fn in_combat(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// Returns `true` if this starts the combat.
+    pub fn engage(&mut self) -> bool {
+        let started = !self.in_combat();
+        self.remaining = COMBAT_TIMEOUT;
+        started
+    }
+
+    /// Returns `true` if the combat ended during this update.
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        if !self.in_combat() {
+            return false;
+        }
+
+        self.remaining -= delta_time;
+        !self.in_combat()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CombatTimer, COMBAT_TIMEOUT};
+
+    #[test]
+    fn combat_starts_once() {
+        let mut combat_timer = CombatTimer::default();
+        assert!(combat_timer.engage());
+        assert!(!combat_timer.engage());
+        assert!(combat_timer.in_combat());
+    }
+
+    #[test]
+    fn hits_extend_combat() {
+        let mut combat_timer = CombatTimer::default();
+        combat_timer.engage();
+
+        assert!(!combat_timer.update(COMBAT_TIMEOUT - 1.0));
+        combat_timer.engage();
+        assert!(!combat_timer.update(COMBAT_TIMEOUT - 1.0));
+        assert!(combat_timer.update(1.0));
+        assert!(!combat_timer.in_combat());
+    }
+
+    #[test]
+    fn combat_ends_once() {
+        let mut combat_timer = CombatTimer::default();
+        combat_timer.engage();
+
+        assert!(combat_timer.update(COMBAT_TIMEOUT));
+        assert!(!combat_timer.update(1.0));
+    }
+}
@@ -80,7 +80,7 @@ impl MouseCursor {
                     texture,
                     mouse_position - ScreenSize::uniform(15.0 * interface_settings.scaling.get()),
                     ScreenSize::uniform(30.0 * interface_settings.scaling.get()),
-                    ScreenClip::default(),
+                    ScreenClip::unbounded(),
                     Color::monochrome_u8(255),
                     false,
                 ),
@@ -91,6 +91,7 @@ impl MouseCursor {
                     &animation_state,
                     mouse_position,
                     0,
+                    ScreenClip::unbounded(),
                     Color::monochrome_u8(255),
                     interface_settings,
                 ),
@@ -110,6 +111,7 @@ impl MouseCursor {
             &self.animation_state,
             mouse_position,
             direction,
+            ScreenClip::unbounded(),
             color,
             interface_settings,
         );
@@ -2,6 +2,7 @@ use std::cell::{Cell, RefCell};
 use std::rc::{Rc, Weak};
 use std::sync::Arc;
 
+use bumpalo::Bump;
 use cgmath::Vector2;
 use vulkano::image::view::ImageView;
 
@@ -45,10 +46,24 @@ impl<'a> ElementRenderer<'a> {
             .get_text_dimensions(text, font_size * self.interface_settings.scaling.get(), available_width)
     }
 
+    /// Bump allocator for temporary allocations while rendering. Only valid
+    /// until the end of the current frame.
+    pub fn get_frame_arena(&self) -> &'a Bump {
+        self.renderer.get_frame_arena()
+    }
+
     pub fn set_scroll(&mut self, scroll: f32) {
         self.position.top -= scroll;
     }
 
+    /// Mask everything rendered afterwards to the shape of a background with
+    /// the given corner radius.
+    pub fn clip_corners(&mut self, corner_radius: CornerRadius) {
+        self.screen_clip = self
+            .screen_clip
+            .round_corners(self.position, self.size, corner_radius * self.interface_settings.scaling.get());
+    }
+
     pub fn render_background(&mut self, corner_radius: CornerRadius, color: Color) {
         self.renderer.render_rectangle(
             self.render_target,
@@ -71,11 +86,43 @@ impl<'a> ElementRenderer<'a> {
         );
     }
 
+    /// Distance in pixels from the left edge of the element to the start of a
+    /// text rendered by [`render_text`](Self::render_text). In right-to-left
+    /// layouts the text is aligned to the right edge of the element, so
+    /// cursors and selections inside of the text have to be measured from
+    /// here.
+    pub fn text_start(&self, text: &str, offset: ScreenPosition, font_size: f32) -> f32 {
+        let scaling = self.interface_settings.scaling.get();
+
+        match self.interface_settings.localization.is_right_to_left() {
+            true => {
+                let available_width = self.size.width - offset.left * scaling;
+                let text_width = self.get_text_dimensions(text, font_size, available_width).x;
+                self.size.width - offset.left * scaling - text_width
+            }
+            false => offset.left * scaling,
+        }
+    }
+
+    /// In right-to-left layouts the text is aligned to the right edge of the
+    /// element, with the offset mirrored accordingly.
     pub fn render_text(&mut self, text: &str, offset: ScreenPosition, foreground_color: Color, font_size: f32) -> f32 {
+        let position = ScreenPosition {
+            left: self.text_start(text, offset, font_size),
+            top: offset.top * self.interface_settings.scaling.get(),
+        };
+
+        self.render_text_at(text, position, foreground_color, font_size)
+    }
+
+    /// Render text at a position in pixels relative to the element. Unlike
+    /// [`render_text`](Self::render_text), the position is not mirrored in
+    /// right-to-left layouts.
+    pub fn render_text_at(&mut self, text: &str, position: ScreenPosition, foreground_color: Color, font_size: f32) -> f32 {
         self.renderer.render_text(
             self.render_target,
             text,
-            self.position + offset * self.interface_settings.scaling.get(),
+            self.position + position,
             self.screen_clip,
             foreground_color,
             font_size * self.interface_settings.scaling.get(),
@@ -150,6 +197,7 @@ pub struct ElementState {
     pub self_element: Option<WeakElementCell>,
     pub parent_element: Option<WeakElementCell>,
     pub mouse_position: Cell<ScreenPosition>,
+    pub tooltip_provider: Option<TooltipProvider>,
 }
 
 impl ElementState {
@@ -190,12 +238,7 @@ impl ElementState {
         let position = parent_position + self.cached_position;
         let size = self.cached_size;
 
-        let screen_clip = ScreenClip {
-            left: screen_clip.left.max(position.left),
-            top: screen_clip.top.max(position.top),
-            right: screen_clip.right.min(position.left + self.cached_size.width),
-            bottom: screen_clip.bottom.min(position.top + self.cached_size.height),
-        };
+        let screen_clip = screen_clip.clamp(position, size);
 
         ElementRenderer {
             render_target,
@@ -302,6 +345,12 @@ pub trait Element {
         Vec::new()
     }
 
+    /// Called after [`left_click`](Self::left_click) if the element was
+    /// clicked twice in quick succession.
+    fn double_click(&mut self, _update: &mut bool) -> Vec<ClickAction> {
+        Vec::new()
+    }
+
     fn drag(&mut self, _mouse_delta: ScreenPosition) -> Option<ChangeEvent> {
         None
     }
@@ -310,6 +359,27 @@ pub trait Element {
         Vec::new()
     }
 
+    /// Text that is currently being composed with an input method. It is not
+    /// part of the input until it is committed.
+    fn input_composition(&mut self, _composition: Option<String>) -> Option<ChangeEvent> {
+        None
+    }
+
+    /// Text that is copied to the clipboard.
+    fn copy_text(&self) -> Option<String> {
+        None
+    }
+
+    /// Text that is shown next to the mouse after hovering the element for a
+    /// while. Uses the tooltip provider of the element state by default.
+    fn tooltip(&self, _theme: &InterfaceTheme) -> Option<String> {
+        self.get_state().tooltip_provider.as_ref().and_then(|provider| provider())
+    }
+
+    fn edit_text(&mut self, _edit: TextEdit) -> Vec<ClickAction> {
+        Vec::new()
+    }
+
     fn drop_item(&mut self, _item_source: ItemSource, _item: Item) -> Option<ItemMove> {
         None
     }
@@ -328,6 +398,27 @@ pub trait Element {
             .and_then(|element| (*element).borrow_mut().scroll(delta))
     }
 
+    /// Scroll by the visible height of the closest scrollable element.
+    fn scroll_page(&mut self, upwards: bool) -> Option<ChangeEvent> {
+        self.get_state()
+            .parent_element
+            .as_ref()
+            .and_then(|weak_pointer| weak_pointer.upgrade())
+            .and_then(|element| (*element).borrow_mut().scroll_page(upwards))
+    }
+
+    /// Scroll the closest scrollable element so that an area of this element
+    /// is visible. The area is relative to the content of this element.
+    fn scroll_into_view(&mut self, top: f32, height: f32) -> Option<ChangeEvent> {
+        let top = top + self.get_state().cached_position.top;
+
+        self.get_state()
+            .parent_element
+            .as_ref()
+            .and_then(|weak_pointer| weak_pointer.upgrade())
+            .and_then(|element| (*element).borrow_mut().scroll_into_view(top, height))
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
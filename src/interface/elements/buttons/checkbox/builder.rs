@@ -0,0 +1,74 @@
+use std::cell::Cell;
+
+use procedural::dimension_bound;
+
+use super::Checkbox;
+use crate::interface::builder::{Set, Unset};
+use crate::interface::*;
+
+/// Type state [`Checkbox`] builder. This builder utilizes the type system to
+/// prevent calling the same method multiple times and calling
+/// [`build`](Self::build) before the mandatory methods have been called.
+#[must_use = "`build` needs to be called"]
+pub struct CheckboxBuilder<TEXT, STATE, WIDTH> {
+    text: TEXT,
+    state: STATE,
+    width_bound: DimensionBound,
+    marker: PhantomData<WIDTH>,
+}
+
+impl CheckboxBuilder<Unset, Unset, Unset> {
+    pub fn new() -> Self {
+        Self {
+            text: Unset,
+            state: Unset,
+            width_bound: dimension_bound!(100%),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<STATE, WIDTH> CheckboxBuilder<Unset, STATE, WIDTH> {
+    pub fn with_text<TEXT: AsRef<str> + 'static>(self, text: TEXT) -> CheckboxBuilder<TEXT, STATE, WIDTH> {
+        CheckboxBuilder { text, ..self }
+    }
+}
+
+impl<TEXT, WIDTH> CheckboxBuilder<TEXT, Unset, WIDTH> {
+    pub fn with_state(self, state: TrackedState<bool>) -> CheckboxBuilder<TEXT, TrackedState<bool>, WIDTH> {
+        CheckboxBuilder { state, ..self }
+    }
+}
+
+impl<TEXT, STATE> CheckboxBuilder<TEXT, STATE, Unset> {
+    pub fn with_width_bound(self, width_bound: DimensionBound) -> CheckboxBuilder<TEXT, STATE, Set> {
+        CheckboxBuilder {
+            width_bound,
+            marker: PhantomData,
+            ..self
+        }
+    }
+}
+
+impl<TEXT, WIDTH> CheckboxBuilder<TEXT, TrackedState<bool>, WIDTH>
+where
+    TEXT: AsRef<str> + 'static,
+{
+    /// Take the builder and turn it into a [`Checkbox`].
+    ///
+    /// NOTE: This method is only available if [`with_text`](Self::with_text)
+    /// and [`with_state`](Self::with_state) have been called on the builder.
+    pub fn build(self) -> Checkbox<TEXT> {
+        let Self {
+            text, state, width_bound, ..
+        } = self;
+
+        Checkbox {
+            text,
+            checked: state,
+            width_bound,
+            rendered_version: Cell::new(None),
+            state: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod builder;
+
+use std::cell::Cell;
+
+pub use self::builder::CheckboxBuilder;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+/// Toggles a [`TrackedState<bool>`] when clicked. Unlike the state button, the
+/// checkbox redraws itself if the state is changed from somewhere else.
+pub struct Checkbox<TEXT>
+where
+    TEXT: AsRef<str> + 'static,
+{
+    text: TEXT,
+    checked: TrackedState<bool>,
+    width_bound: DimensionBound,
+    rendered_version: Cell<Option<usize>>,
+    state: ElementState,
+}
+
+impl<TEXT> Element for Checkbox<TEXT>
+where
+    TEXT: AsRef<str> + 'static,
+{
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        let height_bound = DimensionBound::new(Dimension::Absolute(theme.toggle.height.get()), None, None);
+        let size_bound = self.width_bound.add_height(height_bound);
+        self.state.resolve(placement_resolver, &size_bound);
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let rendered_version = self.rendered_version.get()?;
+        (rendered_version != self.checked.get_version()).then_some(ChangeEvent::RENDER_WINDOW)
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        self.checked.toggle();
+        vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)]
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let highlighted = self.is_element_self(hovered_element) || self.is_element_self(focused_element);
+        let checked = self.checked.get();
+        self.rendered_version.set(Some(self.checked.get_version()));
+
+        let (background_color, foreground_color) = match highlighted {
+            true => (
+                theme.toggle.hovered_background_color.get(),
+                theme.toggle.hovered_foreground_color.get(),
+            ),
+            false => (theme.toggle.background_color.get(), theme.toggle.foreground_color.get()),
+        };
+
+        let icon_color = match checked {
+            true => theme.toggle.checked_color.get(),
+            false => foreground_color,
+        };
+
+        renderer.render_background(theme.toggle.corner_radius.get(), background_color);
+        renderer.render_checkbox(
+            theme.toggle.icon_offset.get(),
+            theme.toggle.icon_size.get(),
+            icon_color,
+            checked,
+        );
+        renderer.render_text(
+            interface_settings.localization.get(self.text.as_ref()),
+            theme.toggle.text_offset.get(),
+            foreground_color,
+            theme.toggle.font_size.get(),
+        );
+    }
+}
@@ -124,6 +124,7 @@ where
             foreground_color,
             background_color,
             width_bound,
+            background_animation: RefCell::new(None),
             state: Default::default(),
         }
     }
@@ -1,10 +1,16 @@
 mod builder;
 
+use std::cell::RefCell;
+
 pub use self::builder::ButtonBuilder;
-use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::graphics::{Color, InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
 
+/// Duration of the transition between the normal and the hovered background
+/// color in seconds.
+const HOVER_TRANSITION_DURATION: f32 = 0.1;
+
 pub struct Button<TEXT, EVENT>
 where
     TEXT: AsRef<str> + 'static,
@@ -16,6 +22,9 @@ where
     foreground_color: Option<ColorSelector>,
     background_color: Option<ColorSelector>,
     width_bound: DimensionBound,
+    /// Transition of the background color when the button is hovered. Created
+    /// on the first render, since the color depends on the theme.
+    background_animation: RefCell<Option<Animation<Color>>>,
     state: ElementState,
 }
 
@@ -54,6 +63,13 @@ impl<TEXT: AsRef<str> + 'static, EVENT: ElementEvent> Element for Button<TEXT, E
         }
     }
 
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.background_animation
+            .borrow()
+            .as_ref()
+            .and_then(|animation| animation.update(ChangeEvent::RENDER_WINDOW))
+    }
+
     fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
         match self.is_disabled() {
             true => Vec::new(),
@@ -87,6 +103,18 @@ impl<TEXT: AsRef<str> + 'static, EVENT: ElementEvent> Element for Button<TEXT, E
             false => theme.button.background_color.get(),
         };
 
+        let background_color = {
+            let mut background_animation = self.background_animation.borrow_mut();
+            let background_animation =
+                background_animation.get_or_insert_with(|| Animation::fixed(background_color, HOVER_TRANSITION_DURATION, Easing::EaseOut));
+
+            if background_animation.target() != background_color {
+                background_animation.retarget(background_color);
+            }
+
+            background_animation.get(interface_settings)
+        };
+
         renderer.render_background(theme.button.corner_radius.get(), background_color);
 
         let foreground_color = if disabled {
@@ -99,7 +127,7 @@ impl<TEXT: AsRef<str> + 'static, EVENT: ElementEvent> Element for Button<TEXT, E
         };
 
         renderer.render_text(
-            self.text.as_ref(),
+            interface_settings.localization.get(self.text.as_ref()),
             theme.button.text_offset.get(),
             foreground_color,
             theme.button.font_size.get(),
@@ -9,6 +9,7 @@ use crate::interface::*;
 pub struct DragButtonBuilder<TITLE, WIDTH> {
     title: TITLE,
     width_bound: WIDTH,
+    window_class: Option<String>,
 }
 
 impl DragButtonBuilder<Unset, Unset> {
@@ -16,6 +17,7 @@ impl DragButtonBuilder<Unset, Unset> {
         Self {
             title: Unset,
             width_bound: Unset,
+            window_class: None,
         }
     }
 }
@@ -35,6 +37,14 @@ impl<TITLE> DragButtonBuilder<TITLE, Unset> {
     }
 }
 
+impl<TITLE, WIDTH> DragButtonBuilder<TITLE, WIDTH> {
+    /// Class of the window that is moved by the button. Only windows with a
+    /// class have options that can be changed through the title bar.
+    pub fn with_window_class_option(self, window_class: Option<String>) -> Self {
+        Self { window_class, ..self }
+    }
+}
+
 impl DragButtonBuilder<String, DimensionBound> {
     /// Take the builder and turn it into a [`DragButton`].
     ///
@@ -42,11 +52,16 @@ impl DragButtonBuilder<String, DimensionBound> {
     /// and [`with_width_bound`](Self::with_width_bound) have been called on
     /// the builder.
     pub fn build(self) -> DragButton {
-        let Self { title, width_bound } = self;
+        let Self {
+            title,
+            width_bound,
+            window_class,
+        } = self;
 
         DragButton {
             title,
             width_bound,
+            window_class,
             state: Default::default(),
         }
     }
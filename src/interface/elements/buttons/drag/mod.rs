@@ -8,6 +8,7 @@ use crate::interface::*;
 pub struct DragButton {
     title: String,
     width_bound: DimensionBound,
+    window_class: Option<String>,
     state: ElementState,
 }
 
@@ -42,6 +43,13 @@ impl Element for DragButton {
         vec![ClickAction::MoveInterface]
     }
 
+    fn right_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        self.window_class
+            .clone()
+            .map(|window_class| vec![ClickAction::Event(UserEvent::OpenWindowOptions(window_class))])
+            .unwrap_or_default()
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -68,7 +76,7 @@ impl Element for DragButton {
         }
 
         renderer.render_text(
-            &self.title,
+            interface_settings.localization.get(&self.title),
             theme.window.text_offset.get(),
             theme.window.foreground_color.get(),
             theme.window.font_size.get(),
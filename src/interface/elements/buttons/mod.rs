@@ -1,9 +1,13 @@
+mod checkbox;
 mod close;
 mod default;
 mod drag;
+mod radio;
 mod state;
 
+pub use self::checkbox::CheckboxBuilder;
 pub use self::close::CloseButtonBuilder;
 pub use self::default::ButtonBuilder;
 pub use self::drag::DragButtonBuilder;
+pub use self::radio::RadioGroupBuilder;
 pub use self::state::StateButtonBuilder;
@@ -0,0 +1,79 @@
+use std::cell::Cell;
+
+use procedural::dimension_bound;
+
+use super::RadioGroup;
+use crate::interface::builder::{Set, Unset};
+use crate::interface::*;
+
+/// Type state [`RadioGroup`] builder. This builder utilizes the type system to
+/// prevent calling the same method multiple times and calling
+/// [`build`](Self::build) before the mandatory methods have been called.
+#[must_use = "`build` needs to be called"]
+pub struct RadioGroupBuilder<OPTIONS, SELECTED, WIDTH> {
+    options: OPTIONS,
+    selected: SELECTED,
+    width_bound: DimensionBound,
+    marker: PhantomData<WIDTH>,
+}
+
+impl RadioGroupBuilder<Unset, Unset, Unset> {
+    pub fn new() -> Self {
+        Self {
+            options: Unset,
+            selected: Unset,
+            width_bound: dimension_bound!(100%),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<SELECTED, WIDTH> RadioGroupBuilder<Unset, SELECTED, WIDTH> {
+    pub fn with_options<K, T>(self, options: Vec<(K, T)>) -> RadioGroupBuilder<Vec<(K, T)>, SELECTED, WIDTH> {
+        RadioGroupBuilder { options, ..self }
+    }
+}
+
+impl<OPTIONS, WIDTH> RadioGroupBuilder<OPTIONS, Unset, WIDTH> {
+    pub fn with_selected<T>(self, selected: TrackedState<T>) -> RadioGroupBuilder<OPTIONS, TrackedState<T>, WIDTH> {
+        RadioGroupBuilder { selected, ..self }
+    }
+}
+
+impl<OPTIONS, SELECTED> RadioGroupBuilder<OPTIONS, SELECTED, Unset> {
+    pub fn with_width_bound(self, width_bound: DimensionBound) -> RadioGroupBuilder<OPTIONS, SELECTED, Set> {
+        RadioGroupBuilder {
+            width_bound,
+            marker: PhantomData,
+            ..self
+        }
+    }
+}
+
+impl<K, T, WIDTH> RadioGroupBuilder<Vec<(K, T)>, TrackedState<T>, WIDTH>
+where
+    K: AsRef<str> + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    /// Take the builder and turn it into a [`RadioGroup`].
+    ///
+    /// NOTE: This method is only available if
+    /// [`with_options`](Self::with_options) and
+    /// [`with_selected`](Self::with_selected) have been called on the builder.
+    pub fn build(self) -> RadioGroup<K, T> {
+        let Self {
+            options,
+            selected,
+            width_bound,
+            ..
+        } = self;
+
+        RadioGroup {
+            options,
+            selected,
+            width_bound,
+            rendered: Cell::new(None),
+            state: Default::default(),
+        }
+    }
+}
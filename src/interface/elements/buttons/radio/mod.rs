@@ -0,0 +1,184 @@
+mod builder;
+
+use std::cell::Cell;
+
+pub use self::builder::RadioGroupBuilder;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+/// Shows all options below each other and sets the [`TrackedState`] to the
+/// value of the option that is clicked.
+pub struct RadioGroup<K, T>
+where
+    K: AsRef<str> + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    options: Vec<(K, T)>,
+    selected: TrackedState<T>,
+    width_bound: DimensionBound,
+    /// Version of the state and the hovered option of the last render.
+    rendered: Cell<Option<(usize, Option<usize>)>>,
+    state: ElementState,
+}
+
+impl<K, T> RadioGroup<K, T>
+where
+    K: AsRef<str> + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    fn option_height(&self) -> f32 {
+        self.state.cached_size.height / self.options.len().max(1) as f32
+    }
+
+    fn option_at(&self, position: ScreenPosition) -> Option<usize> {
+        if position.top < 0.0 {
+            return None;
+        }
+
+        let index = (position.top / self.option_height()) as usize;
+        (index < self.options.len()).then_some(index)
+    }
+}
+
+impl<K, T> Element for RadioGroup<K, T>
+where
+    K: AsRef<str> + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        let height = theme.toggle.height.get() * self.options.len() as f32;
+        let size_bound = self
+            .width_bound
+            .add_height(DimensionBound::new(Dimension::Absolute(height), None, None));
+
+        self.state.resolve(placement_resolver, &size_bound);
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        let (rendered_version, rendered_hovered) = self.rendered.get()?;
+        let hovered = rendered_hovered.and_then(|_| self.option_at(self.state.mouse_position.get()));
+
+        (rendered_version != self.selected.get_version() || rendered_hovered != hovered).then_some(ChangeEvent::RENDER_WINDOW)
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        let Some(index) = self.option_at(self.state.mouse_position.get()) else {
+            return Vec::new();
+        };
+
+        let value = self.options[index].1.clone();
+
+        if *self.selected.borrow() == value {
+            return Vec::new();
+        }
+
+        self.selected.set(value);
+        vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)]
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let scaling = interface_settings.scaling.get();
+        let option_height = self.option_height();
+        let hovered_option = self
+            .is_element_self(hovered_element)
+            .then(|| self.option_at(self.state.mouse_position.get()))
+            .flatten();
+
+        self.rendered.set(Some((self.selected.get_version(), hovered_option)));
+
+        let icon_offset = theme.toggle.icon_offset.get();
+        let icon_size = theme.toggle.icon_size.get();
+        let icon_corner_radius = CornerRadius::uniform(icon_size.width / 2.0);
+        let dot_size = icon_size * 0.5;
+        let selected = self.selected.borrow();
+
+        for (index, (text, value)) in self.options.iter().enumerate() {
+            let option_top = index as f32 * option_height;
+            let is_hovered = hovered_option == Some(index);
+
+            let (background_color, foreground_color) = match is_hovered {
+                true => (
+                    theme.toggle.hovered_background_color.get(),
+                    theme.toggle.hovered_foreground_color.get(),
+                ),
+                false => (theme.toggle.background_color.get(), theme.toggle.foreground_color.get()),
+            };
+
+            renderer.render_rectangle(
+                ScreenPosition::only_top(option_top),
+                ScreenSize {
+                    width: self.state.cached_size.width,
+                    height: option_height,
+                },
+                theme.toggle.corner_radius.get(),
+                background_color,
+            );
+
+            let icon_position = ScreenPosition {
+                left: icon_offset.left * scaling,
+                top: option_top + icon_offset.top * scaling,
+            };
+
+            let icon_color = match *selected == *value {
+                true => theme.toggle.checked_color.get(),
+                false => foreground_color,
+            };
+
+            renderer.render_rectangle(icon_position, icon_size * scaling, icon_corner_radius, icon_color);
+
+            if *selected == *value {
+                let dot_position = ScreenPosition {
+                    left: icon_position.left + (icon_size.width - dot_size.width) / 2.0 * scaling,
+                    top: icon_position.top + (icon_size.height - dot_size.height) / 2.0 * scaling,
+                };
+
+                renderer.render_rectangle(
+                    dot_position,
+                    dot_size * scaling,
+                    CornerRadius::uniform(dot_size.width / 2.0),
+                    foreground_color,
+                );
+            }
+
+            renderer.render_text(
+                interface_settings.localization.get(text.as_ref()),
+                ScreenPosition::only_top(option_top / scaling) + theme.toggle.text_offset.get(),
+                foreground_color,
+                theme.toggle.font_size.get(),
+            );
+        }
+    }
+}
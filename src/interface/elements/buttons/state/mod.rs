@@ -92,7 +92,7 @@ where
         );
 
         renderer.render_text(
-            self.text.as_ref(),
+            interface_settings.localization.get(self.text.as_ref()),
             theme.button.icon_text_offset.get(),
             foreground_color,
             theme.button.font_size.get(),
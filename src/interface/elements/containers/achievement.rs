@@ -0,0 +1,187 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::AchievementData;
+
+/// Achievement level of the player followed by the achievements of the
+/// selected category. Category `0` shows all achievements.
+pub struct AchievementContainer {
+    data: Remote<AchievementData>,
+    category: Remote<u32>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl AchievementContainer {
+    pub fn new(data: Remote<AchievementData>, category: Remote<u32>) -> Self {
+        let elements = {
+            let data = data.borrow();
+            let category = *category.borrow();
+            let level = data.level;
+
+            let experience_progress = match level.experience_to_next_level {
+                0 => 1.0,
+                experience_to_next_level => level.experience as f32 / experience_to_next_level as f32,
+            };
+
+            let mut elements: Vec<ElementCell> = vec![
+                Text::default()
+                    .with_text(format!("Level {} ({} points)", level.level, level.total_score))
+                    .with_width(dimension_bound!(50%))
+                    .wrap(),
+                ProgressBar::new(
+                    experience_progress,
+                    format!("{} / {}", level.experience, level.experience_to_next_level),
+                )
+                .wrap(),
+            ];
+
+            let achievements = data
+                .achievements
+                .iter()
+                .filter(|achievement| category == 0 || achievement.info.category == category)
+                .collect::<Vec<_>>();
+
+            if achievements.is_empty() {
+                elements.push(Text::default().with_text("No achievements".to_owned()).wrap());
+            }
+
+            elements.extend(achievements.into_iter().flat_map(|achievement| {
+                let title_color = match achievement.is_completed {
+                    true => Color::rgb_u8(255, 170, 0),
+                    false => Color::rgb_u8(220, 220, 220),
+                };
+                let progress = achievement.progress();
+
+                let mut elements = vec![
+                    Text::default()
+                        .with_text(achievement.info.title.clone())
+                        .with_foreground_color(move |_| title_color)
+                        .wrap(),
+                ];
+
+                if !achievement.info.summary.is_empty() {
+                    elements.push(Text::default().with_text(achievement.info.summary.clone()).wrap());
+                }
+
+                elements.push(ProgressBar::new(progress, format!("{:.0}%", progress * 100.0)).wrap());
+
+                if achievement.can_claim_reward() {
+                    elements.push(
+                        ButtonBuilder::new()
+                            .with_text("Claim reward")
+                            .with_event(UserEvent::ClaimAchievementReward(achievement.achievement_id))
+                            .build()
+                            .wrap(),
+                    );
+                }
+
+                elements
+            }));
+
+            elements
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            data,
+            category,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for AchievementContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // NOTE: Both remotes have to be consumed, so don't short circuit.
+        let data_changed = self.data.consume_changed();
+        let category_changed = self.category.consume_changed();
+
+        if data_changed || category_changed {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone(), self.category.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::inventory::CharacterStatsData;
+
+/// Balance of the bank account next to the zeny the player is carrying.
+pub struct BankBalanceView {
+    balance: Remote<Option<i64>>,
+    stats: Remote<CharacterStatsData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl BankBalanceView {
+    pub fn new(balance: Remote<Option<i64>>, stats: Remote<CharacterStatsData>) -> Self {
+        let elements = {
+            let balance = match *balance.borrow() {
+                Some(balance) => format!("{balance} Zeny"),
+                None => "...".to_owned(),
+            };
+
+            let rows = [("Balance", balance), ("Carried", format!("{} Zeny", stats.borrow().zeny))];
+
+            rows.into_iter()
+                .flat_map(|(label, value)| {
+                    [
+                        Text::default()
+                            .with_text(label.to_string())
+                            .with_width(dimension_bound!(50%))
+                            .wrap(),
+                        Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+                    ]
+                })
+                .collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            balance,
+            stats,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for BankBalanceView {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // NOTE: Both remotes have to be consumed, so don't short circuit.
+        let balance_changed = self.balance.consume_changed();
+        let stats_changed = self.stats.consume_changed();
+
+        if balance_changed || stats_changed {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.balance.clone(), self.stats.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, _mouse_position: ScreenPosition, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        HoverInformation::Missed
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::inventory::Item;
+use crate::network::CartInformation;
+
+/// Items in the cart of the player. Items dragged from the inventory are put
+/// into the cart.
+pub struct CartContainer {
+    items: Remote<Vec<Item>>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl CartContainer {
+    const COLUMNS: usize = 8;
+    const MINIMUM_SLOTS: usize = 40;
+
+    pub fn new(items: Remote<Vec<Item>>) -> Self {
+        let elements = {
+            let items = items.borrow();
+            let slot_count = items.len().max(Self::MINIMUM_SLOTS);
+
+            (0..slot_count)
+                .map(|index| items.get(index).cloned())
+                .map(|item| ItemBox::new(item, ItemSource::Cart, Box::new(|_| false)))
+                .map(ItemBox::wrap)
+                .collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self { items, weak_self, state }
+    }
+}
+
+impl Element for CartContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        let size_bound = &size_bound!(100%, ?);
+        self.state
+            .resolve_grid(placement_resolver, interface_settings, theme, size_bound, Self::COLUMNS);
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.items.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.items.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::MoveItem(ItemSource::Inventory, _) => self.state.state.hovered_element(mouse_position),
+            MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        matches!(item_source, ItemSource::Inventory).then_some(ItemMove {
+            source: item_source,
+            destination: ItemSource::Cart,
+            item,
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+
+        if matches!(mouse_mode, MouseInputMode::MoveItem(ItemSource::Inventory, _)) {
+            match self.is_element_self(hovered_element) {
+                true => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(60, 160, 160, 160)),
+                false => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(160, 160, 60, 160)),
+            }
+        }
+    }
+}
+
+/// Number of items and weight of the cart.
+pub struct CartInformationView {
+    information: Remote<Option<CartInformation>>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl CartInformationView {
+    pub fn new(information: Remote<Option<CartInformation>>) -> Self {
+        let elements = {
+            let information = information.borrow().unwrap_or_default();
+
+            // NOTE: The map server sends the weight in tenths.
+            let rows = [
+                (
+                    "Items",
+                    format!("{} / {}", information.item_count, information.maximum_item_count),
+                ),
+                (
+                    "Weight",
+                    format!("{} / {}", information.weight / 10, information.maximum_weight / 10),
+                ),
+            ];
+
+            rows.into_iter()
+                .flat_map(|(label, value)| {
+                    [
+                        Text::default()
+                            .with_text(label.to_string())
+                            .with_width(dimension_bound!(50%))
+                            .wrap(),
+                        Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+                    ]
+                })
+                .collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            information,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for CartInformationView {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.information.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.information.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, _mouse_position: ScreenPosition, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        HoverInformation::Missed
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
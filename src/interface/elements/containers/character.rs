@@ -12,6 +12,7 @@ use crate::network::CharacterInformation;
 pub struct CharacterPreview {
     characters: Remote<Vec<CharacterInformation>>,
     move_request: Remote<Option<usize>>,
+    selected_slot: Remote<Option<usize>>,
     slot: usize,
     state: ContainerState,
 }
@@ -36,28 +37,56 @@ impl CharacterPreview {
         let character_information = characters.iter().find(|character| character.character_number as usize == slot);
 
         if let Some(character_information) = character_information {
-            return vec![
+            let character_id = character_information.character_id;
+
+            let mut elements = vec![
                 Text::default()
                     .with_text(character_information.name.clone())
+                    .alias_in_streamer_mode()
                     .with_foreground_color(|_| Color::rgb_u8(220, 210, 210))
                     .with_font_size(|_| 18.0)
                     .wrap(),
-                ButtonBuilder::new()
-                    .with_text("Switch")
-                    .with_event(UserEvent::RequestSwitchCharacterSlot(slot))
-                    .with_background_color(|_| Color::rgb_u8(161, 141, 141))
-                    .with_width_bound(dimension_bound!(50%))
-                    .build()
-                    .wrap(),
+            ];
+
+            // NOTE: While a deletion is pending, the character can't be moved to a
+            // different slot.
+            match character_information.deletion_date() {
+                Some(deletion_date) => elements.extend([
+                    Text::default()
+                        .with_text(format!("Deletable after {}", deletion_date.format("%Y-%m-%d %H:%M")))
+                        .with_foreground_color(|_| Color::rgb_u8(220, 100, 100))
+                        .wrap(),
+                    ButtonBuilder::new()
+                        .with_text("Keep")
+                        .with_event(UserEvent::CancelCharacterDeletion(character_id))
+                        .with_background_color(|_| Color::rgb_u8(161, 141, 141))
+                        .with_width_bound(dimension_bound!(50%))
+                        .build()
+                        .wrap(),
+                ]),
+                None => elements.push(
+                    ButtonBuilder::new()
+                        .with_text("Switch")
+                        .with_event(UserEvent::RequestSwitchCharacterSlot(slot))
+                        .with_background_color(|_| Color::rgb_u8(161, 141, 141))
+                        .with_width_bound(dimension_bound!(50%))
+                        .build()
+                        .wrap(),
+                ),
+            }
+
+            elements.push(
                 ButtonBuilder::new()
                     .with_text("Delete")
-                    .with_event(UserEvent::DeleteCharacter(character_information.character_id))
+                    .with_event(UserEvent::RequestCharacterDeletion(character_id))
                     .with_background_color(|theme| theme.close_button.background_color.get())
                     .with_foreground_color(|theme| theme.close_button.foreground_color.get())
                     .with_width_bound(dimension_bound!(50%))
                     .build()
                     .wrap(),
-            ];
+            );
+
+            return elements;
         }
 
         vec![
@@ -68,13 +97,19 @@ impl CharacterPreview {
         ]
     }
 
-    pub fn new(characters: Remote<Vec<CharacterInformation>>, move_request: Remote<Option<usize>>, slot: usize) -> Self {
+    pub fn new(
+        characters: Remote<Vec<CharacterInformation>>,
+        move_request: Remote<Option<usize>>,
+        selected_slot: Remote<Option<usize>>,
+        slot: usize,
+    ) -> Self {
         let elements = Self::get_elements(&characters, &move_request, slot);
         let state = ContainerState::new(elements);
 
         Self {
             characters,
             move_request,
+            selected_slot,
             slot,
             state,
         }
@@ -83,6 +118,10 @@ impl CharacterPreview {
     fn has_character(&self) -> bool {
         self.state.elements.len() > 1 // TODO:
     }
+
+    fn is_selected(&self) -> bool {
+        *self.selected_slot.borrow() == Some(self.slot)
+    }
 }
 
 impl Element for CharacterPreview {
@@ -125,7 +164,12 @@ impl Element for CharacterPreview {
             let weak_self = self.state.state.self_element.take().unwrap();
             let weak_parent = self.state.state.parent_element.clone();
 
-            *self = Self::new(self.characters.clone(), self.move_request.clone(), self.slot);
+            *self = Self::new(
+                self.characters.clone(),
+                self.move_request.clone(),
+                self.selected_slot.clone(),
+                self.slot,
+            );
 
             // important: link back after creating elements, otherwise focus navigation and
             // scrolling would break
@@ -134,6 +178,10 @@ impl Element for CharacterPreview {
             return Some(ChangeEvent::RESOLVE_WINDOW);
         }
 
+        if self.selected_slot.consume_changed() {
+            return Some(ChangeEvent::RENDER_WINDOW);
+        }
+
         None
     }
 
@@ -147,9 +195,12 @@ impl Element for CharacterPreview {
             return vec![ClickAction::Event(event)];
         }
 
-        let event = match self.has_character() {
-            true => UserEvent::SelectCharacter(self.slot),
-            false => UserEvent::OpenCharacterCreationWindow(self.slot),
+        // NOTE: The first click selects the character and shows a preview, the second
+        // one logs in.
+        let event = match (self.has_character(), self.is_selected()) {
+            (true, true) => UserEvent::SelectCharacter(self.slot),
+            (true, false) => UserEvent::PreviewCharacter(self.slot),
+            (false, _) => UserEvent::OpenCharacterCreationWindow(self.slot),
         };
 
         vec![ClickAction::Event(event)]
@@ -181,7 +232,7 @@ impl Element for CharacterPreview {
             .state
             .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
 
-        let background_color = match self.is_element_self(hovered_element) || self.is_element_self(focused_element) {
+        let background_color = match self.is_selected() || self.is_element_self(hovered_element) || self.is_element_self(focused_element) {
             true => theme.button.hovered_background_color.get(),
             false => theme.button.background_color.get(),
         };
@@ -0,0 +1,137 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::{EquipmentSet, HOTBAR_ROW_SLOTS};
+
+pub struct EquipmentSetContainer {
+    sets: Remote<Vec<EquipmentSet>>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl EquipmentSetContainer {
+    pub fn new(sets: Remote<Vec<EquipmentSet>>) -> Self {
+        let elements = sets
+            .borrow()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, set)| {
+                // NOTE: Only the sets in reach of the number keys can be equipped with a
+                // hotkey.
+                let name = match index < HOTBAR_ROW_SLOTS {
+                    true => format!("{} (Alt+{})", set.name, index + 1),
+                    false => set.name.clone(),
+                };
+
+                [
+                    Text::default().with_text(name).with_width(dimension_bound!(50%)).wrap(),
+                    ButtonBuilder::new()
+                        .with_text("Equip")
+                        .with_event(UserEvent::EquipEquipmentSet(set.name.clone()))
+                        .with_width_bound(dimension_bound!(25%))
+                        .build()
+                        .wrap(),
+                    ButtonBuilder::new()
+                        .with_text("Delete")
+                        .with_event(UserEvent::DeleteEquipmentSet(set.name.clone()))
+                        .with_width_bound(dimension_bound!(!))
+                        .build()
+                        .wrap(),
+                ]
+            })
+            .collect();
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self { sets, weak_self, state }
+    }
+}
+
+impl Element for EquipmentSetContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.sets.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.sets.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -1,17 +1,20 @@
+use std::borrow::Cow;
 use std::rc::Weak;
 
 use procedural::size_bound;
 
 use crate::graphics::{InterfaceRenderer, Renderer};
-use crate::input::MouseInputMode;
+use crate::input::{MouseInputMode, UserEvent};
 use crate::interface::{Element, *};
 
 pub struct Expandable {
     display: String,
+    alias_in_streamer_mode: bool,
     expanded: bool,
     open_size_bound: SizeBound,
     closed_size_bound: SizeBound,
     cached_closed_size: ScreenSize,
+    double_click_event: Option<UserEvent>,
     state: ContainerState,
 }
 
@@ -21,13 +24,39 @@ impl Expandable {
 
         Self {
             display,
+            alias_in_streamer_mode: false,
             expanded,
             open_size_bound: size_bound!(100%, ?),
             closed_size_bound: size_bound!(100%, 18),
             cached_closed_size: ScreenSize::default(),
+            double_click_event: None,
             state,
         }
     }
+
+    /// The text is a name that is replaced with an alias while streamer mode
+    /// is enabled.
+    pub fn alias_in_streamer_mode(mut self) -> Self {
+        self.alias_in_streamer_mode = true;
+        self
+    }
+
+    pub fn with_width(mut self, width_bound: DimensionBound) -> Self {
+        for size_bound in [&mut self.open_size_bound, &mut self.closed_size_bound] {
+            size_bound.width = width_bound.size;
+            size_bound.minimum_width = width_bound.minimum_size;
+            size_bound.maximum_width = width_bound.maximum_size;
+        }
+
+        self
+    }
+
+    /// Event that is emitted when the title of the [`Expandable`] is double
+    /// clicked.
+    pub fn with_double_click_event(mut self, event: UserEvent) -> Self {
+        self.double_click_event = Some(event);
+        self
+    }
 }
 
 impl Element for Expandable {
@@ -152,6 +181,10 @@ impl Element for Expandable {
         Vec::new()
     }
 
+    fn double_click(&mut self, _update: &mut bool) -> Vec<ClickAction> {
+        self.double_click_event.clone().map(ClickAction::Event).into_iter().collect()
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -190,8 +223,14 @@ impl Element for Expandable {
             false => theme.expandable.foreground_color.get(),
         };
 
+        let display = interface_settings.localization.get(&self.display);
+        let display = match self.alias_in_streamer_mode {
+            true => interface_settings.streamer_mode.hide_name(display),
+            false => Cow::Borrowed(display),
+        };
+
         renderer.render_text(
-            &self.display,
+            &display,
             theme.expandable.text_offset.get(),
             foreground_color,
             theme.expandable.font_size.get(),
@@ -1,27 +1,27 @@
 use std::cell::UnsafeCell;
 use std::rc::Weak;
 
-use procedural::size_bound;
+use procedural::{dimension_bound, size_bound};
 
 use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
-use crate::network::Friend;
+use crate::network::{Friend, OnlineState};
 
 pub struct FriendView {
-    friends: Remote<Vec<(Friend, UnsafeCell<Option<WeakElementCell>>)>>,
+    friends: Remote<Vec<(Friend, OnlineState, UnsafeCell<Option<WeakElementCell>>)>>,
     state: ContainerState,
 }
 
 impl FriendView {
-    pub fn new(friends: Remote<Vec<(Friend, UnsafeCell<Option<WeakElementCell>>)>>) -> Self {
+    pub fn new(friends: Remote<Vec<(Friend, OnlineState, UnsafeCell<Option<WeakElementCell>>)>>) -> Self {
         let elements = {
             let friends = friends.borrow();
 
             friends
                 .iter()
-                .map(|(friend, linked_element)| {
-                    let element = Self::friend_to_element(friend);
+                .map(|(friend, state, linked_element)| {
+                    let element = Self::friend_to_element(friend, *state);
                     unsafe { *linked_element.get() = Some(Rc::downgrade(&element)) };
                     element
                 })
@@ -34,8 +34,18 @@ impl FriendView {
         }
     }
 
-    fn friend_to_element(friend: &Friend) -> ElementCell {
+    fn friend_to_element(friend: &Friend, state: OnlineState) -> ElementCell {
+        let (state_text, state_color) = match state {
+            OnlineState::Online => ("Online", Color::rgb_u8(100, 220, 100)),
+            OnlineState::Offline => ("Offline", Color::rgb_u8(150, 150, 150)),
+        };
+
         let elements = vec![
+            ButtonBuilder::new()
+                .with_text("whisper")
+                .with_event(UserEvent::OpenWhisperWindow(friend.name.clone()))
+                .build()
+                .wrap(),
             ButtonBuilder::new()
                 .with_text("remove")
                 .with_event(UserEvent::RemoveFriend {
@@ -46,7 +56,21 @@ impl FriendView {
                 .wrap(),
         ];
 
-        Expandable::new(friend.name.clone(), elements, false).wrap()
+        let expandable = Expandable::new(friend.name.clone(), elements, false)
+            .alias_in_streamer_mode()
+            .with_width(dimension_bound!(!))
+            .with_double_click_event(UserEvent::OpenWhisperWindow(friend.name.clone()))
+            .wrap();
+
+        Container::new(vec![
+            Text::default()
+                .with_text(state_text.to_owned())
+                .with_foreground_color(move |_| state_color)
+                .with_width(dimension_bound!(20%))
+                .wrap(),
+            expandable,
+        ])
+        .wrap()
     }
 }
 
@@ -95,13 +119,13 @@ impl Element for FriendView {
                 .borrow()
                 .iter()
                 .enumerate()
-                .for_each(|(index, (friend, linked_element))| {
+                .for_each(|(index, (friend, state, linked_element))| {
                     if let Some(linked_element) = unsafe { &(*linked_element.get()) } {
                         while !std::ptr::addr_eq(linked_element.as_ptr(), Rc::downgrade(&self.state.elements[index]).as_ptr()) {
                             self.state.elements.remove(index);
                         }
                     } else {
-                        let element = Self::friend_to_element(friend);
+                        let element = Self::friend_to_element(friend, *state);
                         unsafe { *linked_element.get() = Some(Rc::downgrade(&element)) };
                         let weak_self = self.state.state.self_element.clone();
 
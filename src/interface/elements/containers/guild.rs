@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+use vulkano::image::view::ImageView;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::network::{GuildInfo, GuildMember, GuildNotice, GuildPosition};
+
+#[derive(Clone, new)]
+pub struct GuildData {
+    pub info: Remote<Option<GuildInfo>>,
+    pub members: Remote<Vec<GuildMember>>,
+    pub positions: Remote<Vec<GuildPosition>>,
+    pub notice: Remote<GuildNotice>,
+    pub emblems: Remote<HashMap<u32, Arc<ImageView>>>,
+}
+
+#[derive(Clone, Copy)]
+pub enum GuildTab {
+    Information,
+    Members,
+    Notice,
+    Positions,
+}
+
+/// Content of a single tab of the guild window. It is rebuilt whenever the
+/// data shown in the tab changes.
+pub struct GuildView {
+    data: GuildData,
+    tab: GuildTab,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl GuildView {
+    pub fn new(data: GuildData, tab: GuildTab) -> Self {
+        let elements = match tab {
+            GuildTab::Information => Self::information_elements(&data),
+            GuildTab::Members => Self::member_elements(&data),
+            GuildTab::Notice => Self::notice_elements(&data),
+            GuildTab::Positions => Self::position_elements(&data),
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            data,
+            tab,
+            weak_self,
+            state,
+        }
+    }
+
+    fn information_elements(data: &GuildData) -> Vec<ElementCell> {
+        let info = data.info.borrow();
+
+        let Some(info) = info.as_ref() else {
+            return vec![Text::default().with_text("You are not in a guild".to_owned()).wrap()];
+        };
+
+        let master_name = data
+            .members
+            .borrow()
+            .iter()
+            .find(|member| member.character_id == info.master_character_id)
+            .map(|member| member.name.clone())
+            .unwrap_or_default();
+
+        let rows = [
+            ("Level", info.level.to_string()),
+            ("Master", master_name),
+            (
+                "Members",
+                format!("{} / {}", info.online_member_count, info.maximum_member_count),
+            ),
+            ("Average level", info.average_level.to_string()),
+            ("Experience", format!("{} / {}", info.experience, info.next_experience)),
+        ];
+
+        let mut elements = vec![
+            GuildEmblem::new(data.emblems.clone(), info.guild_id).wrap(),
+            Text::default()
+                .with_text(info.name.clone())
+                .with_font_size(|_| 16.0)
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ];
+
+        elements.extend(rows.into_iter().flat_map(|(label, value)| {
+            [
+                Text::default()
+                    .with_text(label.to_string())
+                    .with_width(dimension_bound!(50%))
+                    .wrap(),
+                Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+            ]
+        }));
+
+        elements
+    }
+
+    fn member_elements(data: &GuildData) -> Vec<ElementCell> {
+        let guild_id = data.info.borrow().as_ref().map(|info| info.guild_id);
+        let positions = data.positions.borrow();
+
+        data.members
+            .borrow()
+            .iter()
+            .flat_map(|member| {
+                let position_name = positions
+                    .iter()
+                    .find(|position| position.position_id == member.position_id)
+                    .map(|position| position.name.clone())
+                    .unwrap_or_default();
+                let online_state = match member.is_online {
+                    true => "Online",
+                    false => "Offline",
+                };
+
+                let details = [
+                    ("Level", member.level.to_string()),
+                    ("Position", position_name),
+                    ("State", online_state.to_owned()),
+                ];
+
+                let mut elements: Vec<ElementCell> = details
+                    .into_iter()
+                    .flat_map(|(label, value)| {
+                        [
+                            Text::default()
+                                .with_text(label.to_string())
+                                .with_width(dimension_bound!(50%))
+                                .wrap(),
+                            Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+                        ]
+                    })
+                    .collect();
+
+                elements.extend(
+                    positions
+                        .iter()
+                        .filter(|position| position.position_id != member.position_id)
+                        .map(|position| {
+                            ButtonBuilder::new()
+                                .with_text(format!("Make {}", position.name))
+                                .with_event(UserEvent::ChangeGuildMemberPosition {
+                                    account_id: member.account_id,
+                                    character_id: member.character_id,
+                                    position_id: position.position_id,
+                                })
+                                .build()
+                                .wrap()
+                        }),
+                );
+
+                let emblem = guild_id.map(|guild_id| GuildEmblem::new(data.emblems.clone(), guild_id).with_size(18.0).wrap());
+                let expandable = Expandable::new(member.name.clone(), elements, false)
+                    .alias_in_streamer_mode()
+                    .with_width(dimension_bound!(!))
+                    .wrap();
+
+                emblem.into_iter().chain(std::iter::once(expandable))
+            })
+            .collect()
+    }
+
+    fn notice_elements(data: &GuildData) -> Vec<ElementCell> {
+        let notice = data.notice.borrow();
+        let subject = TrackedState::new(notice.subject.clone());
+        let text = TrackedState::new(notice.notice.clone());
+
+        let save_action = {
+            let subject = subject.clone();
+            let text = text.clone();
+
+            Box::new(move || {
+                vec![ClickAction::Event(UserEvent::SetGuildNotice {
+                    subject: subject.get(),
+                    notice: text.get(),
+                })]
+            })
+        };
+
+        vec![
+            InputFieldBuilder::new()
+                .with_state(subject)
+                .with_ghost_text("Subject")
+                .with_enter_action(save_action.clone())
+                .with_length(59)
+                .build()
+                .wrap(),
+            InputFieldBuilder::new()
+                .with_state(text)
+                .with_ghost_text("Notice")
+                .with_enter_action(save_action.clone())
+                .with_length(119)
+                .build()
+                .wrap(),
+            ButtonBuilder::new().with_text("Save").with_event(save_action).build().wrap(),
+        ]
+    }
+
+    fn position_elements(data: &GuildData) -> Vec<ElementCell> {
+        data.positions
+            .borrow()
+            .iter()
+            .flat_map(|position| {
+                let name = TrackedState::new(position.name.clone());
+                let can_invite = TrackedState::new(position.can_invite());
+                let can_expel = TrackedState::new(position.can_expel());
+
+                let save_action = {
+                    let position = position.clone();
+                    let name = name.clone();
+                    let can_invite = can_invite.clone();
+                    let can_expel = can_expel.clone();
+
+                    Box::new(move || {
+                        let position = GuildPosition {
+                            name: name.get(),
+                            ..position.with_permissions(can_invite.get(), can_expel.get())
+                        };
+
+                        vec![ClickAction::Event(UserEvent::ChangeGuildPosition(position))]
+                    })
+                };
+
+                [
+                    InputFieldBuilder::new()
+                        .with_state(name)
+                        .with_ghost_text("Position name")
+                        .with_enter_action(save_action.clone())
+                        .with_length(23)
+                        .with_width_bound(dimension_bound!(40%))
+                        .build()
+                        .wrap(),
+                    CheckboxBuilder::new()
+                        .with_text("Invite")
+                        .with_state(can_invite)
+                        .with_width_bound(dimension_bound!(20%))
+                        .build()
+                        .wrap(),
+                    CheckboxBuilder::new()
+                        .with_text("Expel")
+                        .with_state(can_expel)
+                        .with_width_bound(dimension_bound!(20%))
+                        .build()
+                        .wrap(),
+                    ButtonBuilder::new()
+                        .with_text("Save")
+                        .with_event(save_action)
+                        .with_width_bound(dimension_bound!(!))
+                        .build()
+                        .wrap(),
+                ]
+            })
+            .collect()
+    }
+
+    fn consume_changed(&mut self) -> bool {
+        // NOTE: All relevant remotes have to be consumed, so don't short circuit.
+        match self.tab {
+            GuildTab::Information => {
+                let info_changed = self.data.info.consume_changed();
+                let members_changed = self.data.members.consume_changed();
+                info_changed || members_changed
+            }
+            GuildTab::Members => {
+                let members_changed = self.data.members.consume_changed();
+                let positions_changed = self.data.positions.consume_changed();
+                members_changed || positions_changed
+            }
+            GuildTab::Notice => self.data.notice.consume_changed(),
+            GuildTab::Positions => self.data.positions.consume_changed(),
+        }
+    }
+}
+
+impl Element for GuildView {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone(), self.tab);
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -1,40 +1,48 @@
+use std::collections::HashMap;
+
 use procedural::size_bound;
 
 use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::{HotbarSlot, MouseInputMode};
 use crate::interface::*;
-use crate::inventory::Skill;
+use crate::inventory::{Cooldown, HotbarEntry, HOTBAR_ROW_SLOTS, HOTBAR_SLOTS};
+use crate::network::SkillId;
 
 pub struct HotbarContainer {
-    skills: Remote<[Option<Skill>; 10]>,
+    entries: Remote<[Option<HotbarEntry>; HOTBAR_SLOTS]>,
+    cooldowns: Remote<HashMap<SkillId, Cooldown>>,
     state: ContainerState,
 }
 
 impl HotbarContainer {
-    pub fn new(skills: Remote<[Option<Skill>; 10]>) -> Self {
+    pub fn new(entries: Remote<[Option<HotbarEntry>; HOTBAR_SLOTS]>, cooldowns: Remote<HashMap<SkillId, Cooldown>>) -> Self {
         let elements = {
-            let skills = skills.borrow();
+            let entries = entries.borrow();
 
-            skills
-                .iter()
-                .cloned()
+            // NOTE: Every row is its own container, so the rows don't reflow when
+            // the window is resized.
+            entries
+                .chunks(HOTBAR_ROW_SLOTS)
                 .enumerate()
-                .map(|(slot, skill)| {
-                    let skill_source = SkillSource::Hotbar { slot: HotbarSlot(slot) };
-                    let skill_box = SkillBox::new(
-                        skill,
-                        skill_source,
-                        Box::new(move |mouse_mode| matches!(mouse_mode, MouseInputMode::MoveSkill(source, _) if *source != skill_source)),
-                    );
-
-                    skill_box.wrap()
+                .map(|(row, row_entries)| {
+                    let boxes = row_entries
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .map(|(column, entry)| {
+                            let slot = HotbarSlot(row * HOTBAR_ROW_SLOTS + column);
+                            HotbarBox::new(entry, slot, cooldowns.clone()).wrap()
+                        })
+                        .collect();
+
+                    Container::new(boxes).wrap()
                 })
                 .collect()
         };
 
         let state = ContainerState::new(elements);
 
-        Self { skills, state }
+        Self { entries, cooldowns, state }
     }
 }
 
@@ -75,11 +83,11 @@ impl Element for HotbarContainer {
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        if self.skills.consume_changed() {
+        if self.entries.consume_changed() {
             let weak_self = self.state.state.self_element.take().unwrap();
             let weak_parent = self.state.state.parent_element.take();
 
-            *self = Self::new(self.skills.clone());
+            *self = Self::new(self.entries.clone(), self.cooldowns.clone());
             // important: link back after creating elements, otherwise focus navigation and
             // scrolling would break
             self.link_back(weak_self, weak_parent);
@@ -87,12 +95,19 @@ impl Element for HotbarContainer {
             return Some(ChangeEvent::RESOLVE_WINDOW);
         }
 
-        None
+        // NOTE: The cooldown overlays shrink every frame, so the window has to be
+        // rendered as long as any cooldown is running.
+        let cooldowns_changed = self.cooldowns.consume_changed();
+        let cooldown_active = !self.cooldowns.borrow().is_empty();
+
+        (cooldowns_changed || cooldown_active).then_some(ChangeEvent::RENDER_WINDOW)
     }
 
     fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
         match mouse_mode {
-            MouseInputMode::MoveSkill(..) | MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            MouseInputMode::MoveSkill(..) | MouseInputMode::MoveItem(..) | MouseInputMode::None => {
+                self.state.hovered_element(mouse_position, mouse_mode, false)
+            }
             _ => HoverInformation::Missed,
         }
     }
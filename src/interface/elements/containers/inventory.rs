@@ -3,20 +3,25 @@ use procedural::size_bound;
 use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
-use crate::inventory::Item;
+use crate::inventory::{InventoryFilter, Item};
 
 pub struct InventoryContainer {
     items: Remote<Vec<Item>>,
+    filter: InventoryFilter,
     weak_self: Option<WeakElementCell>, // TODO: maybe remove?
     state: ContainerState,
 }
 
 impl InventoryContainer {
-    pub fn new(items: Remote<Vec<Item>>) -> Self {
+    const COLUMNS: usize = 8;
+    const MINIMUM_SLOTS: usize = 40;
+
+    pub fn new(items: Remote<Vec<Item>>, filter: InventoryFilter) -> Self {
         let elements = {
-            let items = items.borrow();
+            let items = filter.apply(&items.borrow());
+            let slot_count = items.len().max(Self::MINIMUM_SLOTS);
 
-            (0..40)
+            (0..slot_count)
                 .map(|index| items.get(index).cloned())
                 .map(|item| ItemBox::new(item, ItemSource::Inventory, Box::new(|_| false)))
                 .map(ItemBox::wrap)
@@ -26,7 +31,12 @@ impl InventoryContainer {
         let weak_self = None;
         let state = ContainerState::new(elements);
 
-        Self { items, weak_self, state }
+        Self {
+            items,
+            filter,
+            weak_self,
+            state,
+        }
     }
 }
 
@@ -58,21 +68,20 @@ impl Element for InventoryContainer {
 
     fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
         let size_bound = &size_bound!(100%, ?);
-        self.state.resolve(
-            placement_resolver,
-            interface_settings,
-            theme,
-            size_bound,
-            ScreenSize::uniform(3.0),
-        );
+        self.state
+            .resolve_grid(placement_resolver, interface_settings, theme, size_bound, Self::COLUMNS);
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        if self.items.consume_changed() {
+        // NOTE: Both have to be consumed, so don't short circuit.
+        let items_changed = self.items.consume_changed();
+        let filter_changed = self.filter.consume_changed();
+
+        if items_changed || filter_changed {
             let weak_parent = self.state.state.parent_element.take();
             let weak_self = self.weak_self.take().unwrap();
 
-            *self = Self::new(self.items.clone());
+            *self = Self::new(self.items.clone(), self.filter.clone());
             // important: link back after creating elements, otherwise focus navigation and
             // scrolling would break
             self.link_back(weak_self, weak_parent);
@@ -0,0 +1,436 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::{Item, Mailbox, MailboxData, OpenedMail};
+
+/// Mails in the mailbox of the player. Unread mails are highlighted and
+/// clicking a title opens the mail.
+pub struct MailListContainer {
+    data: Remote<MailboxData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl MailListContainer {
+    pub fn new(data: Remote<MailboxData>) -> Self {
+        let elements = {
+            let data = data.borrow();
+
+            let mut elements: Vec<ElementCell> = data
+                .mails
+                .iter()
+                .flat_map(|mail| {
+                    let (state_text, state_color) = match mail.is_read {
+                        true => ("Read", Color::rgb_u8(150, 150, 150)),
+                        false => ("New", Color::rgb_u8(255, 170, 0)),
+                    };
+                    let attachment_text = match (mail.has_zeny, mail.has_items) {
+                        (true, true) => "Zeny, Items",
+                        (true, false) => "Zeny",
+                        (false, true) => "Items",
+                        (false, false) => "",
+                    };
+
+                    [
+                        Text::default()
+                            .with_text(state_text.to_owned())
+                            .with_foreground_color(move |_| state_color)
+                            .with_width(dimension_bound!(12%))
+                            .wrap(),
+                        Text::default()
+                            .with_text(mail.sender_name.clone())
+                            .alias_in_streamer_mode()
+                            .with_width(dimension_bound!(28%))
+                            .wrap(),
+                        ButtonBuilder::new()
+                            .with_text(mail.title.clone())
+                            .with_event(UserEvent::ReadMail(mail.mail_id))
+                            .with_width_bound(dimension_bound!(40%))
+                            .build()
+                            .wrap(),
+                        Text::default()
+                            .with_text(attachment_text.to_owned())
+                            .with_width(dimension_bound!(!))
+                            .wrap(),
+                    ]
+                })
+                .collect();
+
+            if data.mails.is_empty() {
+                elements.push(Text::default().with_text("Your mailbox is empty".to_owned()).wrap());
+            }
+
+            if !data.is_end {
+                elements.push(
+                    ButtonBuilder::new()
+                        .with_text("Older mails")
+                        .with_event(UserEvent::LoadMoreMail)
+                        .build()
+                        .wrap(),
+                );
+            }
+
+            elements
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self { data, weak_self, state }
+    }
+}
+
+impl Element for MailListContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.data.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
+
+/// Sender, title, text and attachments of the mail the player is reading.
+pub struct MailContentContainer {
+    opened_mail: Remote<Option<OpenedMail>>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl MailContentContainer {
+    pub fn new(opened_mail: Remote<Option<OpenedMail>>) -> Self {
+        let elements = {
+            let opened_mail = opened_mail.borrow();
+
+            match opened_mail.as_ref() {
+                Some(mail) => {
+                    let mut elements = vec![
+                        Text::default()
+                            .with_text(mail.header.sender_name.clone())
+                            .with_foreground_color(|_| Color::rgb_u8(255, 170, 0))
+                            .alias_in_streamer_mode()
+                            .wrap(),
+                        Text::default().with_text(mail.header.title.clone()).wrap(),
+                    ];
+
+                    // NOTE: Text elements only span a single line.
+                    elements.extend(mail.text.lines().map(|line| Text::default().with_text(line.to_owned()).wrap()));
+
+                    elements.extend(
+                        mail.items
+                            .iter()
+                            .cloned()
+                            .map(|item| ItemBox::new(Some(item), ItemSource::Mail, Box::new(|_| false)).wrap()),
+                    );
+
+                    if mail.zeny > 0 {
+                        elements.push(Text::default().with_text(format!("{} Zeny", mail.zeny)).wrap());
+                    }
+
+                    elements
+                }
+                None => Vec::new(),
+            }
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            opened_mail,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for MailContentContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.opened_mail.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.opened_mail.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
+
+/// Items attached to the mail the player is writing. Accepts items dragged
+/// from the inventory.
+pub struct MailAttachmentContainer {
+    attachments: Remote<Vec<Item>>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl MailAttachmentContainer {
+    pub fn new(attachments: Remote<Vec<Item>>) -> Self {
+        let elements = {
+            let attachments = attachments.borrow();
+
+            (0..Mailbox::MAXIMUM_ATTACHMENTS)
+                .map(|index| attachments.get(index).cloned())
+                .map(|item| ItemBox::new(item, ItemSource::MailAttachment, Box::new(|_| false)).wrap())
+                .collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            attachments,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for MailAttachmentContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.attachments.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.attachments.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::MoveItem(ItemSource::Inventory, _) => self.state.state.hovered_element(mouse_position),
+            MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        matches!(item_source, ItemSource::Inventory).then_some(ItemMove {
+            source: item_source,
+            destination: ItemSource::MailAttachment,
+            item,
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+
+        if matches!(mouse_mode, MouseInputMode::MoveItem(ItemSource::Inventory, _)) {
+            match self.is_element_self(hovered_element) {
+                true => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(60, 160, 160, 160)),
+                false => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(160, 160, 60, 160)),
+            }
+        }
+    }
+}
@@ -1,15 +1,29 @@
+mod achievement;
+mod bank;
+mod cart;
 mod character;
 mod default;
 mod dialog;
 mod equipment;
+mod equipment_set;
 mod expandable;
 mod friends;
+mod grid;
+mod guild;
 mod hotbar;
 mod inventory;
 #[cfg(feature = "debug")]
 mod packet;
+mod quest;
 mod scroll;
+mod shop;
 mod skill_tree;
+mod statistics;
+mod stats;
+mod tab;
+#[cfg(feature = "debug")]
+mod trace;
+mod trade;
 
 use std::cell::Cell;
 use std::ops::Add;
@@ -17,18 +31,33 @@ use std::rc::Weak;
 
 use derive_new::new;
 
+pub use self::achievement::AchievementContainer;
+pub use self::bank::BankBalanceView;
+pub use self::cart::{CartContainer, CartInformationView};
 pub use self::character::CharacterPreview;
 pub use self::default::Container;
 pub use self::dialog::{DialogContainer, DialogElement};
 pub use self::equipment::EquipmentContainer;
+pub use self::equipment_set::EquipmentSetContainer;
 pub use self::expandable::Expandable;
 pub use self::friends::FriendView;
+pub use self::grid::GridContainer;
+pub use self::guild::{GuildData, GuildTab, GuildView};
 pub use self::hotbar::HotbarContainer;
 pub use self::inventory::InventoryContainer;
+pub use self::mail::{MailAttachmentContainer, MailContentContainer, MailListContainer};
 #[cfg(feature = "debug")]
 pub use self::packet::{PacketEntry, PacketView};
+pub use self::quest::QuestLogContainer;
 pub use self::scroll::ScrollView;
+pub use self::shop::ShopContainer;
 pub use self::skill_tree::SkillTreeContainer;
+pub use self::statistics::SessionStatisticsView;
+pub use self::stats::CharacterStatsView;
+pub use self::tab::TabContainer;
+#[cfg(feature = "debug")]
+pub use self::trace::EventTraceView;
+pub use self::trade::TradeContainer;
 use crate::input::MouseInputMode;
 use crate::interface::*;
 
@@ -90,6 +119,61 @@ impl ContainerState {
         final_height
     }
 
+    /// Resolve the elements as a grid with the given number of columns. All
+    /// cells have the same size, which is set by the theme.
+    pub fn resolve_grid(
+        &mut self,
+        placement_resolver: &mut PlacementResolver,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        size_bound: &SizeBound,
+        columns: usize,
+    ) -> f32 {
+        let scaling = interface_settings.scaling.get();
+        let border = theme.grid.border_size.get();
+        let cell_size = theme.grid.cell_size.get() * scaling;
+        let gaps = theme.grid.gaps.get() * scaling;
+        let columns = columns.max(1);
+
+        let (mut inner_placement_resolver, mut size, position) = placement_resolver.derive(size_bound, ScreenPosition::default(), border);
+        let parent_limits = inner_placement_resolver.get_parent_limits();
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            let offset = ScreenPosition {
+                left: (index % columns) as f32 * (cell_size.width + gaps.width),
+                top: (index / columns) as f32 * (cell_size.height + gaps.height),
+            };
+            let mut cell_placement_resolver = inner_placement_resolver.derive_cell(offset, cell_size);
+
+            element
+                .borrow_mut()
+                .resolve(&mut cell_placement_resolver, interface_settings, theme)
+        });
+
+        let rows = self.elements.len().div_ceil(columns);
+        let content_height = rows as f32 * cell_size.height + rows.saturating_sub(1) as f32 * gaps.height;
+        inner_placement_resolver.register_height(content_height);
+
+        let final_height = inner_placement_resolver.final_height();
+
+        if size_bound.height.is_flexible() {
+            let final_height = size_bound.validated_height(
+                final_height,
+                placement_resolver.get_available().height,
+                placement_resolver.get_available().height,
+                &parent_limits,
+                interface_settings.scaling.get(),
+            );
+            size.height = Some(final_height);
+            placement_resolver.register_height(final_height);
+        }
+
+        self.state.cached_size = size.finalize();
+        self.state.cached_position = position;
+
+        final_height
+    }
+
     fn get_next_element(&self, start_index: usize, focus_mode: FocusMode, wrapped_around: &mut bool) -> Option<ElementCell> {
         if self.elements.is_empty() {
             return None;
@@ -0,0 +1,190 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::{QuestEntry, QuestLogData};
+
+/// Quests of the player grouped into active and completed quests, with the
+/// progress of each objective.
+pub struct QuestLogContainer {
+    data: Remote<QuestLogData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl QuestLogContainer {
+    fn quest_elements(quest: &QuestEntry, is_completed: bool) -> Vec<ElementCell> {
+        let title_color = match (is_completed, quest.is_active) {
+            (true, _) => Color::rgb_u8(150, 150, 150),
+            (false, true) => Color::rgb_u8(255, 170, 0),
+            (false, false) => Color::rgb_u8(180, 140, 80),
+        };
+        let title = match is_completed || quest.is_active {
+            true => quest.title.clone(),
+            false => format!("{} (inactive)", quest.title),
+        };
+
+        let mut elements = vec![Text::default().with_text(title).with_foreground_color(move |_| title_color).wrap()];
+
+        if !quest.summary.is_empty() {
+            elements.push(Text::default().with_text(quest.summary.clone()).wrap());
+        }
+
+        if is_completed {
+            return elements;
+        }
+
+        elements.extend(quest.objectives.iter().flat_map(|objective| {
+            let is_done = objective.current_count >= objective.total_count;
+            let count_color = match is_done {
+                true => Color::rgb_u8(100, 220, 100),
+                false => Color::rgb_u8(220, 220, 220),
+            };
+
+            [
+                Text::default()
+                    .with_text(objective.mob_name.clone())
+                    .with_width(dimension_bound!(70%))
+                    .wrap(),
+                Text::default()
+                    .with_text(format!("{} / {}", objective.current_count, objective.total_count))
+                    .with_foreground_color(move |_| count_color)
+                    .with_width(dimension_bound!(!))
+                    .wrap(),
+            ]
+        }));
+
+        elements
+    }
+
+    pub fn new(data: Remote<QuestLogData>) -> Self {
+        let elements = {
+            let data = data.borrow();
+            let mut elements: Vec<ElementCell> = Vec::new();
+
+            if let Some(navigation) = &data.navigation {
+                elements.extend([
+                    Text::default()
+                        .with_text(format!(
+                            "Target: {} ({}, {})",
+                            navigation.map_name, navigation.position.x, navigation.position.y
+                        ))
+                        .with_width(dimension_bound!(70%))
+                        .wrap(),
+                    ButtonBuilder::new()
+                        .with_text("Clear")
+                        .with_event(UserEvent::ClearQuestNavigation)
+                        .with_width_bound(dimension_bound!(!))
+                        .build()
+                        .wrap(),
+                ]);
+            }
+
+            elements.push(Text::default().with_text("Active quests".to_owned()).wrap());
+            elements.extend(data.active.iter().flat_map(|quest| Self::quest_elements(quest, false)));
+
+            if data.active.is_empty() {
+                elements.push(Text::default().with_text("You have no active quests".to_owned()).wrap());
+            }
+
+            if !data.completed.is_empty() {
+                elements.push(Text::default().with_text("Completed quests".to_owned()).wrap());
+                elements.extend(data.completed.iter().flat_map(|quest| Self::quest_elements(quest, true)));
+            }
+
+            elements
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self { data, weak_self, state }
+    }
+}
+
+impl Element for QuestLogContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.data.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -9,6 +9,8 @@ const SCROLL_SPEED: f32 = 0.8;
 pub struct ScrollView {
     scroll: f32,
     children_height: f32,
+    scrollbar_width: f32,
+    minimum_scrollbar_height: f32,
     state: ContainerState,
     size_bound: SizeBound,
     background_color: Option<ColorSelector>,
@@ -18,12 +20,16 @@ impl ScrollView {
     pub fn new(elements: Vec<ElementCell>, size_bound: SizeBound) -> Self {
         let scroll = 0.0;
         let children_height = 0.0;
+        let scrollbar_width = 0.0;
+        let minimum_scrollbar_height = 0.0;
         let state = ContainerState::new(elements);
         let background_color = None;
 
         Self {
             scroll,
             children_height,
+            scrollbar_width,
+            minimum_scrollbar_height,
             state,
             size_bound,
             background_color,
@@ -35,10 +41,33 @@ impl ScrollView {
         self
     }
 
+    fn maximum_scroll(&self) -> f32 {
+        (self.children_height - self.state.state.cached_size.height).max(0.0)
+    }
+
     fn clamp_scroll(&mut self) {
-        self.scroll = self
-            .scroll
-            .clamp(0.0, (self.children_height - self.state.state.cached_size.height).max(0.0));
+        self.scroll = self.scroll.clamp(0.0, self.maximum_scroll());
+    }
+
+    /// Top and height of the scrollbar, if the content doesn't fit.
+    fn scrollbar(&self) -> Option<(f32, f32)> {
+        let height = self.state.state.cached_size.height;
+        let maximum_scroll = self.maximum_scroll();
+
+        if maximum_scroll <= 0.0 {
+            return None;
+        }
+
+        let bar_height = (height * height / self.children_height)
+            .max(self.minimum_scrollbar_height)
+            .min(height);
+        let bar_top = (height - bar_height) * self.scroll / maximum_scroll;
+
+        Some((bar_top, bar_height))
+    }
+
+    fn is_over_scrollbar(&self, position: ScreenPosition) -> bool {
+        self.scrollbar().is_some() && position.left >= self.state.state.cached_size.width - self.scrollbar_width
     }
 }
 
@@ -75,6 +104,8 @@ impl Element for ScrollView {
             &self.size_bound,
             ScreenSize::default(),
         );
+        self.scrollbar_width = theme.scroll_view.bar_width.get() * interface_settings.scaling.get();
+        self.minimum_scrollbar_height = theme.scroll_view.minimum_bar_height.get() * interface_settings.scaling.get();
         self.clamp_scroll();
     }
 
@@ -83,6 +114,12 @@ impl Element for ScrollView {
     }
 
     fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        if let MouseInputMode::DragElement((element, _)) = mouse_mode
+            && self.is_element_self(Some(&*element.borrow()))
+        {
+            return HoverInformation::Hovered;
+        }
+
         let absolute_position = ScreenPosition::from_size(mouse_position - self.state.state.cached_position);
 
         if absolute_position.left >= 0.0
@@ -90,6 +127,11 @@ impl Element for ScrollView {
             && absolute_position.left <= self.state.state.cached_size.width
             && absolute_position.top <= self.state.state.cached_size.height
         {
+            if mouse_mode.is_none() && self.is_over_scrollbar(absolute_position) {
+                self.state.state.mouse_position.replace(absolute_position);
+                return HoverInformation::Hovered;
+            }
+
             for element in &self.state.elements {
                 match element
                     .borrow()
@@ -109,12 +151,91 @@ impl Element for ScrollView {
         HoverInformation::Missed
     }
 
+    fn left_click(&mut self, force_update: &mut bool) -> Vec<ClickAction> {
+        let mouse_position = self.state.state.mouse_position.get();
+
+        let Some((bar_top, bar_height)) = self.scrollbar() else {
+            return Vec::new();
+        };
+
+        if !self.is_over_scrollbar(mouse_position) {
+            return Vec::new();
+        }
+
+        // NOTE: Clicking the track next to the bar jumps a page in that direction.
+        if mouse_position.top < bar_top || mouse_position.top > bar_top + bar_height {
+            self.scroll_page(mouse_position.top < bar_top);
+            *force_update = true;
+            return Vec::new();
+        }
+
+        vec![ClickAction::DragElement]
+    }
+
+    fn drag(&mut self, mouse_delta: ScreenPosition) -> Option<ChangeEvent> {
+        let (_, bar_height) = self.scrollbar()?;
+        let track_height = self.state.state.cached_size.height - bar_height;
+
+        if track_height <= 0.0 {
+            return None;
+        }
+
+        self.scroll += mouse_delta.top * self.maximum_scroll() / track_height;
+        self.clamp_scroll();
+        Some(ChangeEvent::RENDER_WINDOW)
+    }
+
     fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
         self.scroll -= delta * SCROLL_SPEED;
         self.clamp_scroll();
         Some(ChangeEvent::RENDER_WINDOW)
     }
 
+    fn scroll_page(&mut self, upwards: bool) -> Option<ChangeEvent> {
+        let page_height = self.state.state.cached_size.height;
+
+        match upwards {
+            true => self.scroll -= page_height,
+            false => self.scroll += page_height,
+        }
+
+        self.clamp_scroll();
+        Some(ChangeEvent::RENDER_WINDOW)
+    }
+
+    fn scroll_into_view(&mut self, top: f32, height: f32) -> Option<ChangeEvent> {
+        let visible_height = self.state.state.cached_size.height;
+        let previous_scroll = self.scroll;
+
+        // NOTE: If the area is higher than the view, its top is kept visible.
+        if top + height > self.scroll + visible_height {
+            self.scroll = top + height - visible_height;
+        }
+
+        if top < self.scroll {
+            self.scroll = top;
+        }
+
+        self.clamp_scroll();
+        let change_event = (self.scroll != previous_scroll).then_some(ChangeEvent::RENDER_WINDOW);
+
+        // NOTE: Scroll views can be nested, so the now visible part of the area also
+        // has to be scrolled into view by the outer ones.
+        let parent_top = self.state.state.cached_position.top + top - self.scroll;
+        let parent_change_event = self
+            .state
+            .state
+            .parent_element
+            .as_ref()
+            .and_then(|weak_pointer| weak_pointer.upgrade())
+            .and_then(|element| (*element).borrow_mut().scroll_into_view(parent_top, height.min(visible_height)));
+
+        match (change_event, parent_change_event) {
+            (Some(change_event), Some(parent_change_event)) => Some(change_event | parent_change_event),
+            (change_event, parent_change_event) => change_event.or(parent_change_event),
+        }
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -136,6 +257,7 @@ impl Element for ScrollView {
 
         if let Some(color_selector) = &self.background_color {
             renderer.render_background(theme.button.corner_radius.get(), color_selector(theme));
+            renderer.clip_corners(theme.button.corner_radius.get());
         }
 
         renderer.set_scroll(self.scroll);
@@ -150,5 +272,25 @@ impl Element for ScrollView {
             mouse_mode,
             second_theme,
         );
+
+        if let Some((bar_top, bar_height)) = self.scrollbar() {
+            // NOTE: The scrollbar doesn't move with the content.
+            renderer.set_scroll(-self.scroll);
+
+            let bar_color = match self.is_element_self(hovered_element) {
+                true => theme.scroll_view.hovered_bar_color.get(),
+                false => theme.scroll_view.bar_color.get(),
+            };
+            let bar_position = ScreenPosition {
+                left: self.state.state.cached_size.width - self.scrollbar_width,
+                top: bar_top,
+            };
+            let bar_size = ScreenSize {
+                width: self.scrollbar_width,
+                height: bar_height,
+            };
+
+            renderer.render_rectangle(bar_position, bar_size, theme.scroll_view.bar_corner_radius.get(), bar_color);
+        }
     }
 }
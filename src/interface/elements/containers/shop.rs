@@ -0,0 +1,218 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::{CharacterStatsData, ShopData};
+use crate::network::ShopDealType;
+
+/// Items of an NPC shop with the amount the player wants to buy or sell,
+/// followed by the zeny and weight the player will have afterwards.
+pub struct ShopContainer {
+    data: Remote<ShopData>,
+    stats: Remote<CharacterStatsData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl ShopContainer {
+    pub fn new(data: Remote<ShopData>, stats: Remote<CharacterStatsData>) -> Self {
+        let elements = {
+            let data = data.borrow();
+            let stats = stats.borrow();
+
+            // NOTE: The map server sends the weight in tenths.
+            let weight = stats.weight / 10;
+            let maximum_weight = stats.maximum_weight / 10;
+
+            let mut elements: Vec<ElementCell> = data
+                .entries
+                .iter()
+                .enumerate()
+                .flat_map(|(position, entry)| {
+                    let amount = entry.amount;
+                    let maximum_amount = data.maximum_amount(position, stats.zeny, maximum_weight.saturating_sub(weight));
+
+                    [
+                        ItemBox::new(Some(entry.item.clone()), ItemSource::Shop, Box::new(|_| false)).wrap(),
+                        Text::default()
+                            .with_text(entry.item.info.name.clone())
+                            .with_width(dimension_bound!(35%))
+                            .wrap(),
+                        Text::default()
+                            .with_text(format!("{} z", entry.price))
+                            .with_width(dimension_bound!(20%))
+                            .wrap(),
+                        ButtonBuilder::new()
+                            .with_text("-")
+                            .with_event(UserEvent::SetShopItemAmount {
+                                position,
+                                amount: amount.saturating_sub(1),
+                            })
+                            .with_disabled_selector(move || amount == 0)
+                            .with_width_bound(dimension_bound!(8%))
+                            .build()
+                            .wrap(),
+                        Text::default()
+                            .with_text(amount.to_string())
+                            .with_width(dimension_bound!(10%))
+                            .wrap(),
+                        ButtonBuilder::new()
+                            .with_text("+")
+                            .with_event(UserEvent::SetShopItemAmount {
+                                position,
+                                amount: amount.saturating_add(1),
+                            })
+                            .with_disabled_selector(move || amount >= maximum_amount)
+                            .with_width_bound(dimension_bound!(8%))
+                            .build()
+                            .wrap(),
+                        ButtonBuilder::new()
+                            .with_text("max")
+                            .with_event(UserEvent::SetShopItemAmount {
+                                position,
+                                amount: maximum_amount,
+                            })
+                            .with_disabled_selector(move || amount >= maximum_amount)
+                            .with_width_bound(dimension_bound!(!))
+                            .build()
+                            .wrap(),
+                    ]
+                })
+                .collect();
+
+            let total_price = data.total_price();
+            let total_weight = data.total_weight();
+
+            let (zeny_after, weight_after) = match data.deal_type {
+                ShopDealType::Buy => (stats.zeny as i64 - total_price as i64, weight + total_weight),
+                ShopDealType::Sell => (stats.zeny as i64 + total_price as i64, weight.saturating_sub(total_weight)),
+            };
+
+            let rows = [
+                ("Total", format!("{total_price} Zeny"), false),
+                ("Zeny after", format!("{zeny_after} Zeny"), zeny_after < 0),
+                (
+                    "Weight after",
+                    format!("{weight_after} / {maximum_weight}"),
+                    weight_after > maximum_weight,
+                ),
+            ];
+
+            elements.extend(rows.into_iter().flat_map(|(label, value, is_exceeded)| {
+                let mut value_text = Text::default().with_text(value).with_width(dimension_bound!(!));
+
+                if is_exceeded {
+                    value_text = value_text.with_foreground_color(|_| Color::rgb_u8(220, 100, 100));
+                }
+
+                [
+                    Text::default().with_text(label.to_owned()).with_width(dimension_bound!(50%)).wrap(),
+                    value_text.wrap(),
+                ]
+            }));
+
+            elements
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            data,
+            stats,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for ShopContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        // NOTE: Both remotes have to be consumed, so don't short circuit.
+        let data_changed = self.data.consume_changed();
+        let stats_changed = self.stats.consume_changed();
+
+        if data_changed || stats_changed {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone(), self.stats.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -12,6 +12,8 @@ pub struct SkillTreeContainer {
 }
 
 impl SkillTreeContainer {
+    const COLUMNS: usize = 8;
+
     pub fn new(skills: Remote<Vec<Skill>>) -> Self {
         let elements = {
             let skills = skills.borrow();
@@ -58,13 +60,8 @@ impl Element for SkillTreeContainer {
 
     fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
         let size_bound = &size_bound!(100%, ?);
-        self.state.resolve(
-            placement_resolver,
-            interface_settings,
-            theme,
-            size_bound,
-            ScreenSize::uniform(3.0),
-        );
+        self.state
+            .resolve_grid(placement_resolver, interface_settings, theme, size_bound, Self::COLUMNS);
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
@@ -0,0 +1,140 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::inventory::SessionStatisticsData;
+
+pub struct SessionStatisticsView {
+    statistics: Remote<SessionStatisticsData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl SessionStatisticsView {
+    pub fn new(statistics: Remote<SessionStatisticsData>) -> Self {
+        let elements = {
+            let statistics = statistics.borrow();
+            let seconds = statistics.duration.as_secs();
+
+            let rows = [
+                (
+                    "Duration",
+                    format!("{:02}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60),
+                ),
+                (
+                    "Base EXP",
+                    format!("{} ({}/h)", statistics.base_experience, statistics.base_experience_per_hour()),
+                ),
+                (
+                    "Job EXP",
+                    format!("{} ({}/h)", statistics.job_experience, statistics.job_experience_per_hour()),
+                ),
+                ("Zeny", statistics.zeny.to_string()),
+                ("Items looted", statistics.items_looted.to_string()),
+                ("Damage dealt", statistics.damage_dealt.to_string()),
+                ("Damage taken", statistics.damage_taken.to_string()),
+                ("Deaths", statistics.deaths.to_string()),
+            ];
+
+            rows.into_iter()
+                .flat_map(|(label, value)| {
+                    [
+                        Text::default()
+                            .with_text(label.to_string())
+                            .with_width(dimension_bound!(50%))
+                            .wrap(),
+                        Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+                    ]
+                })
+                .collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            statistics,
+            weak_self,
+            state,
+        }
+    }
+}
+
+impl Element for SessionStatisticsView {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.statistics.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.statistics.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        None
+    }
+
+    fn hovered_element(&self, _mouse_position: ScreenPosition, _mouse_mode: &MouseInputMode) -> HoverInformation {
+        HoverInformation::Missed
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
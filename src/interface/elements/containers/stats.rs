@@ -0,0 +1,174 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, UserEvent};
+use crate::interface::*;
+use crate::inventory::CharacterStatsData;
+use crate::network::StatType;
+
+pub struct CharacterStatsView {
+    stats: Remote<CharacterStatsData>,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl CharacterStatsView {
+    pub fn new(stats: Remote<CharacterStatsData>) -> Self {
+        let elements = {
+            let stats = stats.borrow();
+
+            let base_stats = [
+                ("STR", StatType::Strength),
+                ("AGI", StatType::Agility),
+                ("VIT", StatType::Vitality),
+                ("INT", StatType::Intelligence),
+                ("DEX", StatType::Dexterity),
+                ("LUK", StatType::Luck),
+            ];
+
+            let base_elements = base_stats.into_iter().flat_map(|(label, stat_type)| {
+                let stat = stats.get_stat(stat_type);
+                let can_increase = stats.can_increase(stat_type);
+
+                [
+                    Text::default()
+                        .with_text(label.to_string())
+                        .with_width(dimension_bound!(25%))
+                        .wrap(),
+                    Text::default()
+                        .with_text(format!("{} + {}", stat.base, stat.bonus))
+                        .with_width(dimension_bound!(35%))
+                        .wrap(),
+                    Text::default()
+                        .with_text(stat.cost.to_string())
+                        .with_foreground_color(|_| Color::rgb_u8(150, 150, 150))
+                        .with_width(dimension_bound!(20%))
+                        .wrap(),
+                    ButtonBuilder::new()
+                        .with_text("+")
+                        .with_event(UserEvent::IncreaseStat(stat_type))
+                        .with_disabled_selector(move || !can_increase)
+                        .with_width_bound(dimension_bound!(!))
+                        .build()
+                        .wrap(),
+                ]
+            });
+
+            let derived_stats = [
+                ("Status points", stats.status_points.to_string()),
+                ("ATK", format!("{} + {}", stats.attack, stats.attack_bonus)),
+                ("MATK", format!("{} + {}", stats.magic_attack, stats.magic_attack_bonus)),
+                ("DEF", format!("{} + {}", stats.defense, stats.defense_bonus)),
+                ("MDEF", format!("{} + {}", stats.magic_defense, stats.magic_defense_bonus)),
+                ("HIT", stats.hit.to_string()),
+                ("FLEE", format!("{} + {}", stats.flee, stats.flee_bonus)),
+                ("CRIT", stats.critical.to_string()),
+                ("ASPD", stats.attack_speed().to_string()),
+                ("Weight", format!("{} / {}", stats.weight / 10, stats.maximum_weight / 10)),
+            ];
+
+            let derived_elements = derived_stats.into_iter().flat_map(|(label, value)| {
+                [
+                    Text::default()
+                        .with_text(label.to_string())
+                        .with_width(dimension_bound!(50%))
+                        .wrap(),
+                    Text::default().with_text(value).with_width(dimension_bound!(!)).wrap(),
+                ]
+            });
+
+            base_elements.chain(derived_elements).collect()
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self { stats, weak_self, state }
+    }
+}
+
+impl Element for CharacterStatsView {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.stats.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.stats.clone());
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
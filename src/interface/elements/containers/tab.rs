@@ -0,0 +1,224 @@
+use std::rc::Weak;
+
+use procedural::size_bound;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::{Element, *};
+
+/// Shows a row of tabs above the elements of the selected tab. Only the
+/// elements of the selected tab are resolved and rendered.
+pub struct TabContainer {
+    tab_names: Vec<String>,
+    tabs: Vec<Vec<ElementCell>>,
+    active_tab: usize,
+    tab_height: f32,
+    size_bound: SizeBound,
+    state: ContainerState,
+}
+
+impl TabContainer {
+    /// The active tab is usually taken from
+    /// [`WindowCache::get_active_tab`], so windows open on the tab that was
+    /// selected last.
+    pub fn new(tabs: Vec<(String, Vec<ElementCell>)>, active_tab: usize) -> Self {
+        let (tab_names, tabs): (Vec<_>, Vec<_>) = tabs.into_iter().unzip();
+        let active_tab = active_tab.min(tabs.len().saturating_sub(1));
+        let elements = tabs.get(active_tab).cloned().unwrap_or_default();
+
+        Self {
+            tab_names,
+            tabs,
+            active_tab,
+            tab_height: 0.0,
+            size_bound: size_bound!(100%, ?),
+            state: ContainerState::new(elements),
+        }
+    }
+
+    pub fn with_size(mut self, size_bound: SizeBound) -> Self {
+        self.size_bound = size_bound;
+        self
+    }
+
+    fn tab_width(&self) -> f32 {
+        self.state.state.cached_size.width / self.tabs.len().max(1) as f32
+    }
+
+    fn tab_at(&self, position: ScreenPosition) -> Option<usize> {
+        if position.top < 0.0 || position.top > self.tab_height || position.left < 0.0 {
+            return None;
+        }
+
+        let index = (position.left / self.tab_width()) as usize;
+        (index < self.tabs.len()).then_some(index)
+    }
+}
+
+impl Element for TabContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: Weak<RefCell<dyn Element>>, weak_parent: Option<Weak<RefCell<dyn Element>>>) {
+        // NOTE: Elements of inactive tabs need a link to their parent as well, since
+        // they can be selected later.
+        self.tabs.iter().flatten().for_each(|element| {
+            let weak_element = Rc::downgrade(element);
+            element.borrow_mut().link_back(weak_element, Some(weak_self.clone()));
+        });
+        self.state.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        let scaling = interface_settings.scaling.get();
+        let tab_height = theme.tab.height.get() * scaling;
+        let content_top = tab_height + theme.tab.content_offset.get() * scaling;
+
+        let (mut inner_placement_resolver, mut size, position) =
+            placement_resolver.derive(&self.size_bound, ScreenPosition::only_top(content_top), ScreenSize::default());
+        let parent_limits = inner_placement_resolver.get_parent_limits();
+
+        self.state.elements.iter_mut().for_each(|element| {
+            element
+                .borrow_mut()
+                .resolve(&mut inner_placement_resolver, interface_settings, theme)
+        });
+
+        if self.size_bound.height.is_flexible() {
+            let final_height = inner_placement_resolver.final_height() + content_top;
+            let final_height = self.size_bound.validated_height(
+                final_height,
+                placement_resolver.get_available().height,
+                placement_resolver.get_available().height,
+                &parent_limits,
+                scaling,
+            );
+
+            size.height = Some(final_height);
+            placement_resolver.register_height(final_height);
+        }
+
+        self.tab_height = tab_height;
+        self.state.state.cached_size = size.finalize();
+        self.state.state.cached_position = position;
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        let absolute_position = ScreenPosition::from_size(mouse_position - self.state.state.cached_position);
+
+        if mouse_mode.is_none() && self.tab_at(absolute_position).is_some() {
+            self.state.state.mouse_position.replace(absolute_position);
+            return HoverInformation::Hovered;
+        }
+
+        self.state.hovered_element(mouse_position, mouse_mode, false)
+    }
+
+    fn left_click(&mut self, force_update: &mut bool) -> Vec<ClickAction> {
+        let Some(tab) = self.tab_at(self.state.state.mouse_position.get()) else {
+            return Vec::new();
+        };
+
+        if tab == self.active_tab {
+            return Vec::new();
+        }
+
+        self.active_tab = tab;
+        self.state.elements = self.tabs[tab].clone();
+        self.state.focus_cache.take();
+        *force_update = true;
+
+        vec![ClickAction::SelectTab(tab)]
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let scaling = interface_settings.scaling.get();
+        let tab_width = self.tab_width();
+        let gap = theme.tab.gap.get() * scaling;
+        let hovered_tab = self
+            .is_element_self(hovered_element)
+            .then(|| self.tab_at(self.state.state.mouse_position.get()))
+            .flatten();
+
+        for (index, tab_name) in self.tab_names.iter().enumerate() {
+            let is_active = index == self.active_tab;
+
+            let background_color = if is_active {
+                theme.tab.active_background_color.get()
+            } else if hovered_tab == Some(index) {
+                theme.tab.hovered_background_color.get()
+            } else {
+                theme.tab.background_color.get()
+            };
+
+            let foreground_color = match is_active {
+                true => theme.tab.active_foreground_color.get(),
+                false => theme.tab.foreground_color.get(),
+            };
+
+            let tab_position = ScreenPosition::only_left(index as f32 * tab_width);
+            let tab_size = ScreenSize {
+                width: (tab_width - gap).max(0.0),
+                height: self.tab_height,
+            };
+
+            renderer.render_rectangle(tab_position, tab_size, theme.tab.corner_radius.get(), background_color);
+            renderer.render_text(
+                interface_settings.localization.get(tab_name),
+                ScreenPosition::only_left(tab_position.left / scaling) + theme.tab.text_offset.get(),
+                foreground_color,
+                theme.tab.font_size.get(),
+            );
+        }
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::rc::Weak;
+
+use procedural::size_bound;
+
+use crate::debug::RingBuffer;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{MouseInputMode, TracedEvent};
+use crate::interface::{Element, *};
+
+/// Lists all traced user events. The list is rebuilt every time a new event
+/// is traced.
+pub struct EventTraceView<const N: usize> {
+    events: Remote<RingBuffer<TracedEvent, N>>,
+    state: ContainerState,
+}
+
+impl<const N: usize> EventTraceView<N> {
+    pub fn new(events: Remote<RingBuffer<TracedEvent, N>>) -> Self {
+        let elements = Self::create_elements(&events);
+
+        Self {
+            events,
+            state: ContainerState::new(elements),
+        }
+    }
+
+    fn create_elements(events: &Remote<RingBuffer<TracedEvent, N>>) -> Vec<ElementCell> {
+        events
+            .borrow()
+            .iter()
+            .map(|event| Text::default().with_text(event.to_string()).wrap())
+            .collect()
+    }
+}
+
+impl<const N: usize> Element for EventTraceView<N> {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: Weak<RefCell<dyn Element>>, weak_parent: Option<Weak<RefCell<dyn Element>>>) {
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if !self.events.consume_changed() {
+            return None;
+        }
+
+        let weak_self = self.state.state.self_element.clone();
+        self.state.elements = Self::create_elements(&self.events);
+        self.state.elements.iter().for_each(|element| {
+            element.borrow_mut().link_back(Rc::downgrade(element), weak_self.clone());
+        });
+
+        Some(ChangeEvent::RESOLVE_WINDOW)
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::inventory::{Item, TradeData, TradeOffer};
+
+/// One side of a trade. The offer of the player accepts items dragged from
+/// the inventory.
+pub struct TradeContainer {
+    data: Remote<TradeData>,
+    is_partner: bool,
+    weak_self: Option<WeakElementCell>,
+    state: ContainerState,
+}
+
+impl TradeContainer {
+    pub fn new(data: Remote<TradeData>, is_partner: bool) -> Self {
+        let elements = {
+            let data = data.borrow();
+
+            let (name, offer) = match is_partner {
+                true => (data.partner_name.clone(), &data.partner_offer),
+                false => ("You".to_owned(), &data.own_offer),
+            };
+            let (lock_text, lock_color) = match offer.is_locked {
+                true => ("Locked", Color::rgb_u8(220, 100, 100)),
+                false => ("Open", Color::rgb_u8(150, 150, 150)),
+            };
+
+            let mut name_text = Text::default()
+                .with_text(name)
+                .with_foreground_color(|_| Color::rgb_u8(255, 170, 0))
+                .with_width(dimension_bound!(70%));
+
+            if is_partner {
+                name_text = name_text.alias_in_streamer_mode();
+            }
+
+            let mut elements = vec![
+                name_text.wrap(),
+                Text::default()
+                    .with_text(lock_text.to_owned())
+                    .with_foreground_color(move |_| lock_color)
+                    .with_width(dimension_bound!(!))
+                    .wrap(),
+            ];
+
+            elements.extend(
+                (0..TradeOffer::MAXIMUM_ITEMS)
+                    .map(|index| offer.items.get(index).cloned())
+                    .map(|item| ItemBox::new(item, ItemSource::Trade, Box::new(|_| false)).wrap()),
+            );
+
+            elements.push(
+                Text::default()
+                    .with_text(format!("{} Zeny", offer.zeny))
+                    .with_width(dimension_bound!(100%))
+                    .wrap(),
+            );
+
+            elements
+        };
+
+        let weak_self = None;
+        let state = ContainerState::new(elements);
+
+        Self {
+            data,
+            is_partner,
+            weak_self,
+            state,
+        }
+    }
+
+    fn accepts_items(&self) -> bool {
+        !self.is_partner && !self.data.borrow().own_offer.is_locked
+    }
+}
+
+impl Element for TradeContainer {
+    fn get_state(&self) -> &ElementState {
+        &self.state.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state.state
+    }
+
+    fn link_back(&mut self, weak_self: WeakElementCell, weak_parent: Option<WeakElementCell>) {
+        self.weak_self = Some(weak_self.clone());
+        self.state.link_back(weak_self, weak_parent);
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.state.is_focusable::<false>()
+    }
+
+    fn focus_next(&self, self_cell: ElementCell, caller_cell: Option<ElementCell>, focus: Focus) -> Option<ElementCell> {
+        self.state.focus_next::<false>(self_cell, caller_cell, focus)
+    }
+
+    fn restore_focus(&self, self_cell: ElementCell) -> Option<ElementCell> {
+        self.state.restore_focus(self_cell)
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        self.state.resolve(
+            placement_resolver,
+            interface_settings,
+            theme,
+            &size_bound!(100%, ?),
+            ScreenSize::default(),
+        );
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        if self.data.consume_changed() {
+            let weak_parent = self.state.state.parent_element.take();
+            let weak_self = self.weak_self.take().unwrap();
+
+            *self = Self::new(self.data.clone(), self.is_partner);
+            self.link_back(weak_self, weak_parent);
+
+            return Some(ChangeEvent::RESOLVE_WINDOW);
+        }
+
+        self.state.update()
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::MoveItem(ItemSource::Inventory, _) if self.accepts_items() => self.state.state.hovered_element(mouse_position),
+            MouseInputMode::None => self.state.hovered_element(mouse_position, mouse_mode, false),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        matches!(item_source, ItemSource::Inventory).then_some(ItemMove {
+            source: item_source,
+            destination: ItemSource::Trade,
+            item,
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        self.state.render(
+            &mut renderer,
+            state_provider,
+            interface_settings,
+            theme,
+            hovered_element,
+            focused_element,
+            mouse_mode,
+            second_theme,
+        );
+
+        if matches!(mouse_mode, MouseInputMode::MoveItem(ItemSource::Inventory, _)) && self.accepts_items() {
+            match self.is_element_self(hovered_element) {
+                true => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(60, 160, 160, 160)),
+                false => renderer.render_background(CornerRadius::uniform(5.0), Color::rgba_u8(160, 160, 60, 160)),
+            }
+        }
+    }
+}
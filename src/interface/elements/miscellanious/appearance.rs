@@ -0,0 +1,114 @@
+use cgmath::Vector2;
+use procedural::size_bound;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+use crate::loaders::AnimationState;
+use crate::network::ClientTick;
+use crate::world::CharacterAppearance;
+
+/// Standing character, facing the camera. Nothing is shown while the
+/// appearance isn't loaded.
+pub struct CharacterAppearancePreview {
+    appearance: Remote<Option<CharacterAppearance>>,
+    animation_state: AnimationState,
+    state: ElementState,
+}
+
+impl CharacterAppearancePreview {
+    pub fn new(appearance: Remote<Option<CharacterAppearance>>) -> Self {
+        Self {
+            appearance,
+            animation_state: AnimationState::new(ClientTick(0)),
+            state: ElementState::default(),
+        }
+    }
+}
+
+impl Element for CharacterAppearancePreview {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &InterfaceTheme) {
+        self.state.resolve(placement_resolver, &size_bound!(100%, 120));
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.appearance.consume_changed().then_some(ChangeEvent::RENDER_WINDOW)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        renderer.render_background(theme.label.corner_radius.get(), theme.label.background_color.get());
+
+        let appearance = self.appearance.borrow();
+        let Some(appearance) = appearance.as_ref() else {
+            return;
+        };
+
+        // NOTE: Sprites are positioned relative to the feet of the character.
+        let feet_position = renderer.position
+            + ScreenPosition {
+                left: self.state.cached_size.width / 2.0,
+                top: self.state.cached_size.height * 0.85,
+            };
+
+        // NOTE: The head is moved so that its attach point lines up with the one of the
+        // body.
+        let head_offset = appearance
+            .body_actions
+            .attach_point(&self.animation_state, 0)
+            .zip(appearance.head_actions.attach_point(&self.animation_state, 0))
+            .map(|(body_point, head_point)| body_point - head_point)
+            .unwrap_or(Vector2::new(0.0, 0.0));
+
+        appearance.body_actions.render2(
+            renderer.render_target,
+            renderer.renderer,
+            &appearance.body_sprite,
+            &self.animation_state,
+            feet_position,
+            0,
+            renderer.screen_clip,
+            Color::monochrome_u8(255),
+            interface_settings,
+        );
+
+        appearance.head_actions.render2(
+            renderer.render_target,
+            renderer.renderer,
+            &appearance.head_sprite,
+            &self.animation_state,
+            feet_position
+                + ScreenPosition {
+                    left: head_offset.x,
+                    top: head_offset.y,
+                },
+            0,
+            renderer.screen_clip,
+            Color::monochrome_u8(255),
+            interface_settings,
+        );
+    }
+}
@@ -47,6 +47,7 @@ impl ChatBuilder<Remote<Vec<ChatMessage>>, Rc<RefCell<FontLoader>>> {
             messages,
             font_loader,
             stamp: true,
+            message_bounds: Vec::new(),
             state: Default::default(),
         }
     }
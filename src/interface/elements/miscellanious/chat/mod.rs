@@ -1,5 +1,7 @@
 mod builder;
 
+use std::borrow::Cow;
+
 use procedural::size_bound;
 
 pub use self::builder::ChatBuilder;
@@ -13,9 +15,31 @@ pub struct Chat {
     font_loader: Rc<RefCell<FontLoader>>,
     // TODO: make this Remote
     stamp: bool,
+    /// Bottom edge of every message from the last resolve, used to find the
+    /// message under the mouse.
+    message_bounds: Vec<f32>,
     state: ElementState,
 }
 
+impl Chat {
+    /// Text of a message as it is shown, with the sender hidden in streamer
+    /// mode.
+    fn displayed_text<'a>(message: &'a ChatMessage, stamp: bool, streamer_mode: &StreamerMode) -> Cow<'a, str> {
+        let stamped_text = message.stamped_text(stamp);
+        let text = message.stamped_text(false);
+
+        match streamer_mode.hide_sender(text) {
+            Cow::Owned(hidden_text) => Cow::Owned(format!("{}{hidden_text}", &stamped_text[..stamped_text.len() - text.len()])),
+            Cow::Borrowed(_) => Cow::Borrowed(stamped_text),
+        }
+    }
+
+    fn hovered_message_index(&self) -> Option<usize> {
+        let mouse_top = self.state.mouse_position.get().top;
+        self.message_bounds.iter().position(|&bottom| mouse_top < bottom)
+    }
+}
+
 impl Element for Chat {
     fn get_state(&self) -> &ElementState {
         &self.state
@@ -35,25 +59,65 @@ impl Element for Chat {
         // padding.
         let mut height = 5.0 * interface_settings.scaling.get();
 
+        let mut message_bottom = 0.0;
+        self.message_bounds.clear();
+
         // NOTE: Dividing by the scaling is done to counteract the scaling being applied
         // twice per message. It's not the cleanest solution but it works.
         for message in self.messages.borrow().iter() {
-            height += self
+            let message_height = self
                 .font_loader
-                .borrow()
+                .borrow_mut()
                 .get_text_dimensions(
-                    message.stamped_text(self.stamp),
+                    &Self::displayed_text(message, self.stamp, &interface_settings.streamer_mode),
                     theme.chat.font_size.get() * interface_settings.scaling.get(),
                     placement_resolver.get_available().width,
+                    placement_resolver.get_frame_arena(),
                 )
-                .y
-                / interface_settings.scaling.get();
+                .y;
+
+            message_bottom += message_height;
+            self.message_bounds.push(message_bottom);
+            height += message_height / interface_settings.scaling.get();
         }
 
         size_bound.height = Dimension::Absolute(height);
         self.state.resolve(placement_resolver, &size_bound);
     }
 
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn copy_text(&self) -> Option<String> {
+        let index = self.hovered_message_index()?;
+
+        self.messages
+            .borrow()
+            .get(index)
+            .map(|message| message.stamped_text(self.stamp).to_owned())
+    }
+
+    fn right_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        let Some(sender) = self
+            .hovered_message_index()
+            .and_then(|index| self.messages.borrow().get(index).and_then(ChatMessage::sender))
+        else {
+            return Vec::new();
+        };
+
+        let entries = vec![
+            ContextMenuEntry::new("Whisper".to_owned(), UserEvent::OpenWhisperWindow(sender.clone())),
+            ContextMenuEntry::new("Add friend".to_owned(), UserEvent::AddFriend(sender.clone())),
+            ContextMenuEntry::new("Invite to party".to_owned(), UserEvent::InviteToParty(sender)),
+        ];
+
+        vec![ClickAction::OpenContextMenu(entries)]
+    }
+
     fn update(&mut self) -> Option<ChangeEvent> {
         self.messages.consume_changed().then_some(ChangeEvent::RESOLVE_WINDOW)
     }
@@ -79,10 +143,10 @@ impl Element for Chat {
         let mut offset = 0.0;
 
         for message in self.messages.borrow().iter() {
-            let text = message.stamped_text(self.stamp);
+            let text = Self::displayed_text(message, self.stamp, &interface_settings.streamer_mode);
 
             renderer.render_text(
-                text,
+                &text,
                 ScreenPosition {
                     left: 0.2,
                     top: offset + 0.2,
@@ -94,7 +158,7 @@ impl Element for Chat {
             // NOTE: Dividing by the scaling is done to counteract the scaling being applied
             // twice per message. It's not the cleanest solution but it works.
             offset += renderer.render_text(
-                text,
+                &text,
                 ScreenPosition::only_top(offset),
                 message.color,
                 theme.chat.font_size.get(),
@@ -0,0 +1,66 @@
+use std::cell::Cell;
+use std::time::Instant;
+
+use crate::interface::ChangeEvent;
+
+/// Blinking of the text cursor. The cursor is always visible right after
+/// it was reset, so it doesn't disappear while typing.
+pub struct CursorBlink {
+    reset_time: Instant,
+    /// Blink interval and visibility of the last render, if the cursor was
+    /// rendered at all.
+    rendered: Cell<Option<(f32, bool)>>,
+}
+
+impl CursorBlink {
+    pub fn new() -> Self {
+        Self {
+            reset_time: Instant::now(),
+            rendered: Cell::new(None),
+        }
+    }
+
+    /// An interval of zero disables blinking.
+    fn is_visible_after(elapsed: f32, interval: f32) -> bool {
+        interval <= 0.0 || (elapsed / interval) as u64 % 2 == 0
+    }
+
+    pub fn reset(&mut self) {
+        self.reset_time = Instant::now();
+    }
+
+    /// Check if the cursor should be rendered this frame. Should only be
+    /// called if the element is focused.
+    pub fn is_visible(&self, interval: f32) -> bool {
+        let visible = Self::is_visible_after(self.reset_time.elapsed().as_secs_f32(), interval);
+        self.rendered.set(Some((interval, visible)));
+        visible
+    }
+
+    /// Should be called when rendering the element without a cursor.
+    pub fn hide(&self) {
+        self.rendered.set(None);
+    }
+
+    /// Request a render once the cursor needs to change its visibility.
+    pub fn update(&self) -> Option<ChangeEvent> {
+        let (interval, visible) = self.rendered.get()?;
+        let elapsed = self.reset_time.elapsed().as_secs_f32();
+
+        (Self::is_visible_after(elapsed, interval) != visible).then_some(ChangeEvent::RENDER_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CursorBlink;
+
+    #[test]
+    fn blink_phases() {
+        assert!(CursorBlink::is_visible_after(0.0, 0.5));
+        assert!(CursorBlink::is_visible_after(0.49, 0.5));
+        assert!(!CursorBlink::is_visible_after(0.5, 0.5));
+        assert!(CursorBlink::is_visible_after(1.2, 0.5));
+        assert!(CursorBlink::is_visible_after(0.7, 0.0));
+    }
+}
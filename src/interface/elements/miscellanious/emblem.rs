@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use procedural::size_bound;
+use vulkano::image::view::ImageView;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+/// Emblem of a guild. Nothing is rendered until the map server sent the
+/// emblem.
+pub struct GuildEmblem {
+    emblems: Remote<HashMap<u32, Arc<ImageView>>>,
+    guild_id: u32,
+    size: f32,
+    state: ElementState,
+}
+
+impl GuildEmblem {
+    pub fn new(emblems: Remote<HashMap<u32, Arc<ImageView>>>, guild_id: u32) -> Self {
+        Self {
+            emblems,
+            guild_id,
+            size: 24.0,
+            state: ElementState::default(),
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl Element for GuildEmblem {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &InterfaceTheme) {
+        let mut size_bound = size_bound!(24, 24);
+        size_bound.width = Dimension::Absolute(self.size);
+        size_bound.height = Dimension::Absolute(self.size);
+
+        self.state.resolve(placement_resolver, &size_bound);
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.emblems.consume_changed().then_some(ChangeEvent::RENDER_WINDOW)
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        _theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        if let Some(emblem) = self.emblems.borrow().get(&self.guild_id).cloned() {
+            renderer.render_sprite(
+                emblem,
+                ScreenPosition::default(),
+                ScreenSize::uniform(self.size),
+                Color::monochrome_u8(255),
+            );
+        }
+    }
+}
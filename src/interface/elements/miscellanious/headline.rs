@@ -52,7 +52,7 @@ impl Element for Headline {
             .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
 
         renderer.render_text(
-            &self.display,
+            interface_settings.localization.get(&self.display),
             theme.label.text_offset.get(),
             theme.label.foreground_color.get(),
             theme.label.font_size.get(),
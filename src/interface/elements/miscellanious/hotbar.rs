@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use procedural::size_bound;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::{HotbarSlot, MouseInputMode};
+use crate::interface::*;
+use crate::inventory::{Cooldown, HotbarEntry, Item, Skill};
+use crate::network::SkillId;
+
+/// A single slot of the hotbar. Unlike the [`SkillBox`] and [`ItemBox`] it
+/// accepts both skills and items.
+pub struct HotbarBox {
+    entry: Option<HotbarEntry>,
+    slot: HotbarSlot,
+    cooldowns: Remote<HashMap<SkillId, Cooldown>>,
+    state: ElementState,
+}
+
+impl HotbarBox {
+    pub fn new(entry: Option<HotbarEntry>, slot: HotbarSlot, cooldowns: Remote<HashMap<SkillId, Cooldown>>) -> Self {
+        Self {
+            entry,
+            slot,
+            cooldowns,
+            state: ElementState::default(),
+        }
+    }
+
+    fn is_own_move(&self, mouse_mode: &MouseInputMode) -> bool {
+        match mouse_mode {
+            MouseInputMode::MoveSkill(SkillSource::Hotbar { slot }, _) | MouseInputMode::MoveItem(ItemSource::Hotbar { slot }, _) => {
+                *slot == self.slot
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Element for HotbarBox {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &InterfaceTheme) {
+        self.state.resolve(placement_resolver, &size_bound!(30, 30));
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match self.entry.is_some() || matches!(mouse_mode, MouseInputMode::MoveSkill(..) | MouseInputMode::MoveItem(..)) {
+            true => self.state.hovered_element(mouse_position),
+            false => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        match &self.entry {
+            Some(HotbarEntry::Skill(skill)) => vec![ClickAction::MoveSkill(SkillSource::Hotbar { slot: self.slot }, skill.clone())],
+            Some(HotbarEntry::Item(item)) => vec![ClickAction::MoveItem(ItemSource::Hotbar { slot: self.slot }, item.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    fn right_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        if self.entry.is_some() {
+            let entries = vec![ContextMenuEntry::new("Remove".to_owned(), UserEvent::ClearHotbarSlot(self.slot))];
+            return vec![ClickAction::OpenContextMenu(entries)];
+        }
+
+        Vec::new()
+    }
+
+    fn tooltip(&self, _theme: &InterfaceTheme) -> Option<String> {
+        match self.entry.as_ref()? {
+            HotbarEntry::Skill(skill) => {
+                let name = match skill.info.name.is_empty() {
+                    true => &skill.skill_name,
+                    false => &skill.info.name,
+                };
+                Some(format!("{} (Lv. {})\r{} SP", name, skill.skill_level.0, skill.spell_point_cost))
+            }
+            HotbarEntry::Item(item) => Some(item.info.name.clone()),
+        }
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        Some(ItemMove {
+            source: item_source,
+            destination: ItemSource::Hotbar { slot: self.slot },
+            item,
+        })
+    }
+
+    fn drop_skill(&mut self, skill_source: SkillSource, skill: Skill) -> Option<SkillMove> {
+        let destination = SkillSource::Hotbar { slot: self.slot };
+
+        (skill_source != destination).then_some(SkillMove {
+            source: skill_source,
+            destination,
+            skill,
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let highlight = matches!(mouse_mode, MouseInputMode::MoveSkill(..) | MouseInputMode::MoveItem(..)) && !self.is_own_move(mouse_mode);
+        let background_color = match self.is_element_self(hovered_element) || self.is_element_self(focused_element) {
+            true if highlight => Color::rgba_u8(60, 160, 160, 255),
+            true if matches!(mouse_mode, MouseInputMode::None) => theme.button.hovered_background_color.get(),
+            false if highlight => Color::rgba_u8(160, 160, 60, 255),
+            _ => theme.button.background_color.get(),
+        };
+
+        renderer.render_background(CornerRadius::uniform(5.0), background_color);
+        renderer.clip_corners(CornerRadius::uniform(5.0));
+
+        match &self.entry {
+            Some(HotbarEntry::Skill(skill)) => {
+                skill.actions.render2(
+                    renderer.render_target,
+                    renderer.renderer,
+                    &skill.sprite,
+                    &skill.animation_state,
+                    renderer.position + ScreenPosition::uniform(15.0 * interface_settings.scaling.get()),
+                    0,
+                    renderer.screen_clip,
+                    Color::monochrome_u8(255),
+                    interface_settings,
+                );
+
+                if let Some(cooldown) = self.cooldowns.borrow().get(&skill.skill_id) {
+                    let size = ScreenSize {
+                        width: renderer.size.width,
+                        height: renderer.size.height * cooldown.remaining(),
+                    };
+                    let position = ScreenPosition::only_top(renderer.size.height - size.height);
+
+                    renderer.render_rectangle(position, size, CornerRadius::default(), Color::rgba_u8(0, 0, 0, 170));
+                }
+
+                let skill_level = bumpalo::format!(in renderer.get_frame_arena(), "{}", skill.skill_level.0);
+                renderer.render_text(&skill_level, ScreenPosition::uniform(1.0), Color::monochrome_u8(0), 15.0);
+                renderer.render_text(&skill_level, ScreenPosition::default(), Color::monochrome_u8(255), 15.0);
+
+                let spell_point_cost = bumpalo::format!(in renderer.get_frame_arena(), "{}", skill.spell_point_cost);
+                renderer.render_text(
+                    &spell_point_cost,
+                    ScreenPosition::only_top(22.0),
+                    Color::rgb_u8(120, 180, 255),
+                    8.0,
+                );
+            }
+            Some(HotbarEntry::Item(item)) => {
+                renderer.render_sprite(
+                    item.texture.clone(),
+                    ScreenPosition::default(),
+                    ScreenSize::uniform(30.0),
+                    Color::monochrome_u8(255),
+                );
+            }
+            None => {}
+        }
+    }
+}
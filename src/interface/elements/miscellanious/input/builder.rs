@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use procedural::dimension_bound;
 
-use super::{EnterAction, InputField};
+use super::{CursorBlink, EnterAction, InputField};
 use crate::interface::builder::{Set, Unset};
 use crate::interface::*;
 
@@ -16,6 +16,7 @@ pub struct InputFieldBuilder<STATE, TEXT, ACTION, LENGTH, HIDDEN, WIDTH> {
     enter_action: ACTION,
     length: usize,
     hidden: bool,
+    hidden_in_streamer_mode: bool,
     width_bound: DimensionBound,
     marker: PhantomData<(LENGTH, HIDDEN, WIDTH)>,
 }
@@ -28,6 +29,7 @@ impl InputFieldBuilder<Unset, Unset, Unset, Unset, Unset, Unset> {
             enter_action: Unset,
             length: 0,
             hidden: false,
+            hidden_in_streamer_mode: false,
             width_bound: dimension_bound!(100%),
             marker: PhantomData,
         }
@@ -86,6 +88,16 @@ impl<STATE, TEXT, ACTION, LENGTH, WIDTH> InputFieldBuilder<STATE, TEXT, ACTION,
             ..self
         }
     }
+
+    /// Only show text as `*` characters while streamer mode is enabled. Useful
+    /// for account names and private messages.
+    pub fn hidden_in_streamer_mode(self) -> InputFieldBuilder<STATE, TEXT, ACTION, LENGTH, Set, WIDTH> {
+        InputFieldBuilder {
+            hidden_in_streamer_mode: true,
+            marker: PhantomData,
+            ..self
+        }
+    }
 }
 
 impl<STATE, TEXT, ACTION, LENGTH, HIDDEN> InputFieldBuilder<STATE, TEXT, ACTION, LENGTH, HIDDEN, Unset> {
@@ -115,6 +127,7 @@ where
             enter_action,
             length,
             hidden,
+            hidden_in_streamer_mode,
             width_bound,
             ..
         } = self;
@@ -125,7 +138,11 @@ where
             enter_action,
             length,
             hidden,
+            hidden_in_streamer_mode,
             width_bound,
+            composition: None,
+            selection_start: None,
+            cursor_blink: CursorBlink::new(),
             state: Default::default(),
         }
     }
@@ -3,6 +3,7 @@ mod builder;
 use std::fmt::Display;
 
 pub use self::builder::InputFieldBuilder;
+use super::cursor::CursorBlink;
 use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::state::ValueState;
@@ -11,40 +12,90 @@ use crate::interface::*;
 /// Local type alias to simplify the builder.
 type EnterAction = Box<dyn FnMut() -> Vec<ClickAction>>;
 
+const COMPOSITION_UNDERLINE_HEIGHT: f32 = 1.0;
+
 pub struct InputField<TEXT: Display + 'static> {
     input_state: TrackedState<String>,
     ghost_text: TEXT,
     enter_action: EnterAction,
     length: usize,
     hidden: bool,
+    hidden_in_streamer_mode: bool,
     width_bound: DimensionBound,
+    /// Uncommitted text of the input method.
+    composition: Option<String>,
+    /// Index of the first selected character. The selection always reaches
+    /// to the end of the input.
+    selection_start: Option<usize>,
+    cursor_blink: CursorBlink,
     state: ElementState,
 }
 
 impl<TEXT: Display + 'static> InputField<TEXT> {
+    /// Byte index of the first selected character in the text.
+    fn selection_index(text: &str, selection_start: Option<usize>) -> Option<usize> {
+        selection_start
+            .and_then(|start| text.char_indices().nth(start))
+            .map(|(index, _)| index)
+    }
+
+    /// Remove the selected text. Returns `true` if any text was selected.
+    fn delete_selection(input_state: &mut String, selection_start: &mut Option<usize>) -> bool {
+        match Self::selection_index(input_state, selection_start.take()) {
+            Some(index) => {
+                input_state.truncate(index);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn remove_character(&mut self) -> Vec<ClickAction> {
         self.input_state.with_mut(|input_state| {
-            if input_state.is_empty() {
+            if !Self::delete_selection(input_state, &mut self.selection_start) && input_state.pop().is_none() {
                 return ValueState::Unchanged(Vec::new());
             }
 
-            input_state.pop();
-
             ValueState::Mutated(vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)])
         })
     }
 
-    fn add_character(&mut self, character: char) -> Vec<ClickAction> {
+    fn remove_selection(&mut self) -> Vec<ClickAction> {
         self.input_state.with_mut(|input_state| {
-            if input_state.len() >= self.length {
+            if !Self::delete_selection(input_state, &mut self.selection_start) {
                 return ValueState::Unchanged(Vec::new());
             }
 
-            input_state.push(character);
+            ValueState::Mutated(vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)])
+        })
+    }
+
+    /// Append text, replacing the selection. Control characters are dropped
+    /// and the text is cut off at the maximum length.
+    fn insert_text(&mut self, text: &str) -> Vec<ClickAction> {
+        self.input_state.with_mut(|input_state| {
+            let selection_deleted = Self::delete_selection(input_state, &mut self.selection_start);
+            let available_length = self.length.saturating_sub(input_state.chars().count());
+            let previous_length = input_state.len();
+
+            input_state.extend(text.chars().filter(|character| !character.is_control()).take(available_length));
+
+            if !selection_deleted && input_state.len() == previous_length {
+                return ValueState::Unchanged(Vec::new());
+            }
 
             ValueState::Mutated(vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)])
         })
     }
+
+    fn set_selection_start(&mut self, selection_start: Option<usize>) -> Vec<ClickAction> {
+        if self.selection_start == selection_start {
+            return Vec::new();
+        }
+
+        self.selection_start = selection_start;
+        vec![ClickAction::ChangeEvent(ChangeEvent::RENDER_WINDOW)]
+    }
 }
 
 impl<TEXT: Display + 'static> Element for InputField<TEXT> {
@@ -68,18 +119,63 @@ impl<TEXT: Display + 'static> Element for InputField<TEXT> {
         }
     }
 
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.cursor_blink.update()
+    }
+
     fn left_click(&mut self, _update: &mut bool) -> Vec<ClickAction> {
+        self.cursor_blink.reset();
         vec![ClickAction::FocusElement]
     }
 
     fn input_character(&mut self, character: char) -> Vec<ClickAction> {
+        self.cursor_blink.reset();
+
         match character {
             '\u{8}' | '\u{7f}' => self.remove_character(),
             '\r' => (self.enter_action)(),
-            character => self.add_character(character),
+            character => self.insert_text(character.encode_utf8(&mut [0; 4])),
         }
     }
 
+    fn copy_text(&self) -> Option<String> {
+        // NOTE: Hidden input, like passwords, should never end up in the clipboard.
+        if self.hidden {
+            return None;
+        }
+
+        let input_state = self.input_state.borrow();
+        Self::selection_index(&input_state, self.selection_start).map(|index| input_state[index..].to_owned())
+    }
+
+    fn edit_text(&mut self, edit: TextEdit) -> Vec<ClickAction> {
+        let character_count = self.input_state.borrow().chars().count();
+        self.cursor_blink.reset();
+
+        match edit {
+            TextEdit::SelectAll => self.set_selection_start((character_count > 0).then_some(0)),
+            TextEdit::ExtendSelection => {
+                let selection_start = self.selection_start.unwrap_or(character_count).min(character_count);
+                self.set_selection_start(selection_start.checked_sub(1))
+            }
+            TextEdit::ShrinkSelection => {
+                let selection_start = self.selection_start.map(|start| start + 1).filter(|start| *start < character_count);
+                self.set_selection_start(selection_start)
+            }
+            TextEdit::DeleteSelection => self.remove_selection(),
+            TextEdit::Insert(text) => self.insert_text(&text),
+            // NOTE: The cursor of an input field is always at the end of the text.
+            TextEdit::CursorLeft | TextEdit::CursorRight | TextEdit::CursorUp | TextEdit::CursorDown => Vec::new(),
+        }
+    }
+
+    fn input_composition(&mut self, composition: Option<String>) -> Option<ChangeEvent> {
+        (self.composition != composition).then(|| {
+            self.composition = composition;
+            ChangeEvent::RENDER_WINDOW
+        })
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -103,12 +199,16 @@ impl<TEXT: Display + 'static> Element for InputField<TEXT> {
         let is_focused = self.is_element_self(focused_element);
         let text_offset = theme.input.text_offset.get();
 
-        let text = if input_state.is_empty() && !is_focused {
-            self.ghost_text.to_string()
-        } else if self.hidden {
-            input_state.chars().map(|_| '*').collect()
+        let is_hidden = self.hidden || (self.hidden_in_streamer_mode && interface_settings.streamer_mode.is_enabled());
+
+        let frame_arena = renderer.get_frame_arena();
+        let text: &str = if input_state.is_empty() && !is_focused {
+            let ghost_text = bumpalo::format!(in frame_arena, "{}", self.ghost_text);
+            frame_arena.alloc_str(interface_settings.localization.get(&ghost_text))
+        } else if is_hidden {
+            bumpalo::collections::String::from_iter_in(input_state.chars().map(|_| '*'), frame_arena).into_bump_str()
         } else {
-            input_state.clone()
+            &input_state
         };
 
         let background_color = if is_hovererd {
@@ -127,25 +227,86 @@ impl<TEXT: Display + 'static> Element for InputField<TEXT> {
             theme.input.text_color.get()
         };
 
+        let scaling = interface_settings.scaling.get();
+        let font_size = theme.input.font_size.get();
+
         renderer.render_background(theme.input.corner_radius.get(), background_color);
-        renderer.render_text(&text, text_offset, text_color, theme.input.font_size.get());
 
-        if is_focused {
-            let cursor_offset = (text_offset.left + theme.input.cursor_offset.get()) * interface_settings.scaling.get()
-                + renderer.get_text_dimensions(&text, theme.input.font_size.get(), f32::MAX).x;
+        // NOTE: The composition of a hidden input would show the typed characters, so
+        // it is not rendered.
+        let composition = self.composition.as_deref().filter(|_| is_focused && !is_hidden);
+
+        // NOTE: The composition is shown right after the text, so both are aligned
+        // together. Everything inside of the text is measured from its start.
+        let text_start = match composition {
+            Some(composition) => renderer.text_start(&bumpalo::format!(in frame_arena, "{text}{composition}"), text_offset, font_size),
+            None => renderer.text_start(text, text_offset, font_size),
+        };
 
-            let cursor_position = ScreenPosition::only_left(cursor_offset);
-            let cursor_size = ScreenSize {
-                width: theme.input.cursor_width.get(),
+        if is_focused && let Some(selection_index) = Self::selection_index(text, self.selection_start) {
+            let prefix_width = renderer.get_text_dimensions(&text[..selection_index], font_size, f32::MAX).x;
+            let selection_width = renderer.get_text_dimensions(&text[selection_index..], font_size, f32::MAX).x;
+
+            let selection_position = ScreenPosition::only_left(text_start + prefix_width);
+            let selection_size = ScreenSize {
+                width: selection_width,
                 height: self.state.cached_size.height,
             };
 
             renderer.render_rectangle(
-                cursor_position,
-                cursor_size,
+                selection_position,
+                selection_size,
                 CornerRadius::default(),
-                theme.input.text_color.get(),
+                theme.input.selection_color.get(),
             );
         }
+
+        let text_position = ScreenPosition {
+            left: text_start,
+            top: text_offset.top * scaling,
+        };
+        renderer.render_text_at(text, text_position, text_color, font_size);
+
+        if !is_focused {
+            self.cursor_blink.hide();
+            return;
+        }
+
+        let text_width = renderer.get_text_dimensions(text, font_size, f32::MAX).x;
+        let mut cursor_offset = text_start + theme.input.cursor_offset.get() * scaling + text_width;
+
+        if let Some(composition) = composition {
+            let composition_position = text_position + ScreenPosition::only_left(text_width);
+            let composition_size = renderer.get_text_dimensions(composition, font_size, f32::MAX);
+
+            renderer.render_text_at(composition, composition_position, text_color, font_size);
+
+            // NOTE: The composition is underlined to show that it's not committed yet.
+            let underline_position = composition_position + ScreenPosition::only_top(composition_size.y);
+            let underline_size = ScreenSize {
+                width: composition_size.x,
+                height: COMPOSITION_UNDERLINE_HEIGHT * scaling,
+            };
+            renderer.render_rectangle(underline_position, underline_size, CornerRadius::default(), text_color);
+
+            cursor_offset += composition_size.x;
+        }
+
+        if !self.cursor_blink.is_visible(theme.input.cursor_blink_interval.get()) {
+            return;
+        }
+
+        let cursor_position = ScreenPosition::only_left(cursor_offset);
+        let cursor_size = ScreenSize {
+            width: theme.input.cursor_width.get(),
+            height: self.state.cached_size.height,
+        };
+
+        renderer.render_rectangle(
+            cursor_position,
+            cursor_size,
+            CornerRadius::default(),
+            theme.input.cursor_color.get(),
+        );
     }
 }
@@ -0,0 +1,180 @@
+mod tooltip;
+
+use derive_new::new;
+use procedural::size_bound;
+
+use self::tooltip::ItemTooltip;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::*;
+use crate::inventory::Item;
+
+#[derive(new)]
+pub struct ItemBox {
+    item: Option<Item>,
+    source: ItemSource,
+    highlight: Box<dyn Fn(&MouseInputMode) -> bool>,
+    #[new(default)]
+    state: ElementState,
+}
+
+impl Element for ItemBox {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.item.is_some()
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &InterfaceTheme) {
+        self.state.resolve(placement_resolver, &size_bound!(30, 30));
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match self.item.is_some() || matches!(mouse_mode, MouseInputMode::MoveItem(..)) {
+            true => self.state.hovered_element(mouse_position),
+            false => HoverInformation::Missed,
+        }
+    }
+
+    fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        if let Some(item) = &self.item {
+            return vec![ClickAction::MoveItem(self.source, item.clone())];
+        }
+
+        Vec::new()
+    }
+
+    fn right_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
+        if let Some(item) = &self.item
+            && let ItemSource::Inventory = self.source
+        {
+            // NOTE: Favorite items are protected by a more explicit confirmation, so they
+            // are not dropped by accident.
+            let drop_text = match item.is_favorite {
+                true => "This item is one of your favorites. Do you want to drop it anyway?",
+                false => "Do you really want to drop this item?",
+            };
+            let favorite_entry = match item.is_favorite {
+                true => ContextMenuEntry::new("Remove from favorites".to_owned(), UserEvent::SetItemFavorite {
+                    index: item.index,
+                    is_favorite: false,
+                }),
+                false => ContextMenuEntry::new("Add to favorites".to_owned(), UserEvent::SetItemFavorite {
+                    index: item.index,
+                    is_favorite: true,
+                }),
+            };
+
+            let entries = vec![
+                favorite_entry,
+                ContextMenuEntry::new("Drop".to_owned(), UserEvent::RequestConfirmation {
+                    text: drop_text.to_owned(),
+                    event: Box::new(UserEvent::DropItem(item.index)),
+                }),
+            ];
+            return vec![ClickAction::OpenContextMenu(entries)];
+        }
+
+        Vec::new()
+    }
+
+    fn tooltip(&self, theme: &InterfaceTheme) -> Option<String> {
+        self.item.as_ref().map(|item| ItemTooltip::new(&item.info).compose(theme))
+    }
+
+    fn drop_item(&mut self, item_source: ItemSource, item: Item) -> Option<ItemMove> {
+        Some(ItemMove {
+            source: item_source,
+            destination: self.source,
+            item,
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let highlight = (self.highlight)(mouse_mode);
+        let background_color = match self.is_element_self(hovered_element) || self.is_element_self(focused_element) {
+            true if highlight => Color::rgba_u8(60, 160, 160, 255),
+            true if matches!(mouse_mode, MouseInputMode::None) => theme.button.hovered_background_color.get(),
+            false if highlight => Color::rgba_u8(160, 160, 60, 255),
+            _ => theme.button.background_color.get(),
+        };
+
+        renderer.render_background(CornerRadius::uniform(5.0), background_color);
+        renderer.clip_corners(CornerRadius::uniform(5.0));
+
+        if let Some(item) = &self.item {
+            let scaling = interface_settings.scaling.get();
+
+            renderer.render_sprite(
+                item.texture.clone(),
+                ScreenPosition::default(),
+                ScreenSize::uniform(30.0),
+                Color::monochrome_u8(255),
+            );
+
+            if item.is_broken {
+                renderer.render_background(CornerRadius::default(), Color::rgba_u8(200, 30, 30, 110));
+            }
+
+            if item.refinement_level > 0 {
+                let refinement = bumpalo::format!(in renderer.get_frame_arena(), "+{}", item.refinement_level);
+                renderer.render_text(&refinement, ScreenPosition::default(), Color::rgb_u8(255, 220, 90), 8.0);
+            }
+
+            if item.amount > 1 {
+                let amount = bumpalo::format!(in renderer.get_frame_arena(), "{}", item.amount);
+                let width = renderer.get_text_dimensions(&amount, 8.0, f32::MAX).x / scaling;
+                renderer.render_text(
+                    &amount,
+                    ScreenPosition {
+                        left: 29.0 - width,
+                        top: 21.0,
+                    },
+                    theme.button.foreground_color.get(),
+                    8.0,
+                );
+            }
+
+            // NOTE: One dot per card slot, filled if a card is inserted.
+            for (slot, card) in item.cards.iter().take(item.info.slot_count as usize).enumerate() {
+                let color = match *card != 0 {
+                    true => Color::rgb_u8(255, 220, 90),
+                    false => Color::rgba_u8(255, 255, 255, 90),
+                };
+
+                renderer.render_rectangle(
+                    ScreenPosition {
+                        left: (2.0 + slot as f32 * 5.0) * scaling,
+                        top: 25.0 * scaling,
+                    },
+                    ScreenSize::uniform(3.0 * scaling),
+                    CornerRadius::uniform(1.5),
+                    color,
+                );
+            }
+        }
+    }
+}
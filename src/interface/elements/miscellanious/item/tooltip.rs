@@ -0,0 +1,138 @@
+use crate::graphics::Color;
+use crate::interface::InterfaceTheme;
+use crate::loaders::ItemInfo;
+
+/// Labels of description lines that are moved to the bottom of the tooltip.
+const FOOTER_LABELS: [&str; 3] = ["Weight", "Required Level", "Base Level"];
+/// Longest label that is still considered a property, so sentences that
+/// happen to contain a colon are not split.
+const MAXIMUM_LABEL_LENGTH: usize = 20;
+
+/// Color code understood by the text renderer. `^000000` switches back to the
+/// default color.
+fn color_code(color: Color) -> String {
+    format!(
+        "^{:02X}{:02X}{:02X}",
+        color.red_as_u8(),
+        color.green_as_u8(),
+        color.blue_as_u8()
+    )
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Property<'a> {
+    label: &'a str,
+    value: &'a str,
+}
+
+impl<'a> Property<'a> {
+    /// Parse description lines like `Weight : 40`.
+    fn parse(line: &'a str) -> Option<Self> {
+        let (label, value) = line.split_once(':')?;
+        let (label, value) = (label.trim(), value.trim());
+
+        let is_label = label.len() <= MAXIMUM_LABEL_LENGTH
+            && label.chars().next().is_some_and(char::is_uppercase)
+            && label.chars().all(|character| character.is_alphanumeric() || character == ' ');
+
+        (is_label && !value.is_empty()).then_some(Self { label, value })
+    }
+}
+
+/// Lines that only separate sections in the item database. The tooltip adds
+/// its own spacing between sections.
+fn is_separator(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && line.chars().all(|character| matches!(character, '_' | '-' | '='))
+}
+
+/// Builds the text of the tooltip shown when hovering an item. The
+/// description is split into the prose and the properties of the item, like
+/// attack or weight, which are highlighted using the tooltip theme.
+pub struct ItemTooltip<'a> {
+    info: &'a ItemInfo,
+}
+
+impl<'a> ItemTooltip<'a> {
+    pub fn new(info: &'a ItemInfo) -> Self {
+        Self { info }
+    }
+
+    pub fn compose(&self, theme: &InterfaceTheme) -> String {
+        let title_color = color_code(theme.tooltip.title_color.get());
+        let label_color = color_code(theme.tooltip.label_color.get());
+        let value_color = color_code(theme.tooltip.value_color.get());
+
+        let title = match self.info.slot_count {
+            0 => format!("{title_color}{}^000000", self.info.name),
+            slot_count => format!("{title_color}{} [{slot_count}]^000000", self.info.name),
+        };
+
+        let mut prose = Vec::new();
+        let mut properties = Vec::new();
+        let mut footer = Vec::new();
+
+        for line in self.info.description.iter().filter(|line| !is_separator(line)) {
+            match Property::parse(line) {
+                Some(property) if FOOTER_LABELS.contains(&property.label) => footer.push(property),
+                Some(property) => properties.push(property),
+                None => prose.push(line.as_str()),
+            }
+        }
+
+        // NOTE: Empty lines at the end of the prose would add to the spacing between
+        // the sections.
+        while prose.last().is_some_and(|line| line.trim().is_empty()) {
+            prose.pop();
+        }
+
+        let format_property =
+            |property: &Property| format!("{label_color}{}:^000000 {value_color}{}^000000", property.label, property.value);
+        let mut lines = vec![title];
+
+        lines.extend(prose.into_iter().map(str::to_owned));
+
+        for section in [properties, footer] {
+            if !section.is_empty() {
+                lines.push(String::new());
+                lines.extend(section.iter().map(format_property));
+            }
+        }
+
+        // NOTE: Line breaks are encoded as carriage returns by the text renderer.
+        lines.join("\r")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_separator, Property};
+
+    #[test]
+    fn parse_properties() {
+        assert_eq!(
+            Property::parse("Weight : 40"),
+            Some(Property {
+                label: "Weight",
+                value: "40"
+            })
+        );
+        assert_eq!(
+            Property::parse("Required Level: 12"),
+            Some(Property {
+                label: "Required Level",
+                value: "12"
+            })
+        );
+        assert_eq!(Property::parse("A sturdy knife. Note: it is sharp"), None);
+        assert_eq!(Property::parse("Attack:"), None);
+        assert_eq!(Property::parse("Heals some HP"), None);
+    }
+
+    #[test]
+    fn separators() {
+        assert!(is_separator("________________________"));
+        assert!(!is_separator(""));
+        assert!(!is_separator("Weight : 40"));
+    }
+}
@@ -1,19 +1,30 @@
+mod appearance;
 mod chat;
+mod cursor;
+mod emblem;
 mod headline;
+mod hotbar;
 mod input;
 mod item;
 mod picklist;
+mod progress;
 mod skill;
 mod slider;
 mod static_label;
 mod text;
+mod text_area;
 
+pub use self::appearance::CharacterAppearancePreview;
 pub use self::chat::ChatBuilder;
+pub use self::emblem::GuildEmblem;
 pub use self::headline::Headline;
+pub use self::hotbar::HotbarBox;
 pub use self::input::InputFieldBuilder;
 pub use self::item::ItemBox;
 pub use self::picklist::PickList;
+pub use self::progress::ProgressBar;
 pub use self::skill::SkillBox;
 pub use self::slider::Slider;
 pub use self::static_label::StaticLabel;
 pub use self::text::Text;
+pub use self::text_area::TextAreaBuilder;
@@ -0,0 +1,69 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::interface::{Element, *};
+
+/// Bar that is filled according to the progress, with a label on top. The
+/// progress is clamped to the range from zero to one.
+#[derive(new)]
+pub struct ProgressBar {
+    progress: f32,
+    label: String,
+    #[new(default)]
+    state: ElementState,
+}
+
+impl Element for ProgressBar {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, _interface_settings: &InterfaceSettings, _theme: &InterfaceTheme) {
+        self.state.resolve(placement_resolver, &size_bound!(!, 16));
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        _hovered_element: Option<&dyn Element>,
+        _focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        renderer.render_background(theme.label.corner_radius.get(), theme.label.background_color.get());
+
+        let bar_size = ScreenSize {
+            width: self.state.cached_size.width * self.progress.clamp(0.0, 1.0),
+            height: self.state.cached_size.height,
+        };
+
+        renderer.render_rectangle(
+            ScreenPosition::default(),
+            bar_size,
+            theme.label.corner_radius.get(),
+            Color::rgba_u8(80, 170, 80, 200),
+        );
+
+        renderer.render_text(
+            &self.label,
+            theme.label.text_offset.get(),
+            theme.label.foreground_color.get(),
+            theme.label.font_size.get(),
+        );
+    }
+}
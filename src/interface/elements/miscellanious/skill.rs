@@ -47,6 +47,21 @@ impl Element for SkillBox {
         Vec::new()
     }
 
+    fn tooltip(&self, _theme: &InterfaceTheme) -> Option<String> {
+        let skill = self.skill.as_ref()?;
+        let name = match skill.info.name.is_empty() {
+            true => &skill.skill_name,
+            false => &skill.info.name,
+        };
+        let title = format!("{} (Lv. {})", name, skill.skill_level.0);
+
+        // NOTE: Line breaks are encoded as carriage returns by the text renderer.
+        let lines: Vec<&str> = std::iter::once(title.as_str())
+            .chain(skill.info.description.iter().map(String::as_str))
+            .collect();
+        Some(lines.join("\r"))
+    }
+
     fn drop_skill(&mut self, skill_source: SkillSource, skill: Skill) -> Option<SkillMove> {
         (skill_source != self.source).then_some(SkillMove {
             source: skill_source,
@@ -91,22 +106,15 @@ impl Element for SkillBox {
                 &skill.animation_state,
                 renderer.position + ScreenPosition::uniform(15.0 * interface_settings.scaling.get()),
                 0,
+                renderer.screen_clip,
                 Color::monochrome_u8(255),
                 interface_settings,
             );
 
-            renderer.render_text(
-                &format!("{}", skill.skill_level.0),
-                ScreenPosition::uniform(1.0),
-                Color::monochrome_u8(0),
-                15.0,
-            );
-            renderer.render_text(
-                &format!("{}", skill.skill_level.0),
-                ScreenPosition::default(),
-                Color::monochrome_u8(255),
-                15.0,
-            );
+            let skill_level = bumpalo::format!(in renderer.get_frame_arena(), "{}", skill.skill_level.0);
+
+            renderer.render_text(&skill_level, ScreenPosition::uniform(1.0), Color::monochrome_u8(0), 15.0);
+            renderer.render_text(&skill_level, ScreenPosition::default(), Color::monochrome_u8(255), 15.0);
         }
     }
 }
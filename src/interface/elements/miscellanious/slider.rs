@@ -1,6 +1,5 @@
 use std::cmp::PartialOrd;
 
-use derive_new::new;
 use num::traits::NumOps;
 use num::{clamp, NumCast, Zero};
 
@@ -8,18 +7,63 @@ use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::{Element, *};
 
-#[derive(new)]
 pub struct Slider<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> {
-    reference: &'static T,
+    get_value: Box<dyn Fn() -> T>,
+    set_value: Box<dyn FnMut(T)>,
     minimum_value: T,
     maximum_value: T,
     change_event: Option<ChangeEvent>,
-    #[new(value = "T::zero()")]
     cached_value: T,
-    #[new(default)]
     state: ElementState,
 }
 
+impl<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> Slider<T> {
+    pub fn new(state: TrackedState<T>, minimum_value: T, maximum_value: T, change_event: Option<ChangeEvent>) -> Self {
+        let mut cloned_state = state.clone();
+
+        Self {
+            get_value: Box::new(move || state.get()),
+            set_value: Box::new(move |value| cloned_state.set(value)),
+            minimum_value,
+            maximum_value,
+            change_event,
+            cached_value: T::zero(),
+            state: ElementState::default(),
+        }
+    }
+
+    /// Slider for a single element of an array type, for example the left of a
+    /// [`ScreenPosition`].
+    pub fn array_element<A>(
+        state: TrackedState<A>,
+        index: usize,
+        minimum_value: T,
+        maximum_value: T,
+        change_event: Option<ChangeEvent>,
+    ) -> Self
+    where
+        A: ArrayType<Element = T> + 'static,
+        [(); A::ELEMENT_COUNT]:,
+    {
+        let mut cloned_state = state.clone();
+
+        Self {
+            get_value: Box::new(move || state.borrow().get_inner()[index]),
+            set_value: Box::new(move |value| {
+                cloned_state.with_mut(|array| {
+                    array.set_element(index, value);
+                    ValueState::Mutated(())
+                })
+            }),
+            minimum_value,
+            maximum_value,
+            change_event,
+            cached_value: T::zero(),
+            state: ElementState::default(),
+        }
+    }
+}
+
 impl<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> Element for Slider<T> {
     fn get_state(&self) -> &ElementState {
         &self.state
@@ -34,7 +78,7 @@ impl<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> Element for Slide
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        let current_value = *self.reference;
+        let current_value = (self.get_value)();
 
         if self.cached_value != current_value {
             self.cached_value = current_value;
@@ -65,12 +109,7 @@ impl<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> Element for Slide
             self.maximum_value.to_f32().unwrap(),
         );
 
-        // SAFETY: Obviously this is totally unsafe, but considering this is a debug
-        // tool I think it's acceptable.
-        unsafe {
-            #[allow(invalid_reference_casting)]
-            std::ptr::write(self.reference as *const T as *mut T, T::from(new_value).unwrap());
-        }
+        (self.set_value)(T::from(new_value).unwrap());
         self.change_event
     }
 
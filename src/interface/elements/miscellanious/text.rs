@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+
 use procedural::dimension_bound;
 
 use crate::graphics::{Color, InterfaceRenderer, Renderer};
@@ -9,6 +12,9 @@ pub struct Text<T: AsRef<str> + 'static> {
     foreground_color: Option<ColorSelector>,
     width_bound: Option<DimensionBound>,
     font_size: Option<FontSizeSelector>,
+    alias_in_streamer_mode: bool,
+    /// Full text of the last render if it didn't fit into the element.
+    truncated_text: RefCell<Option<String>>,
     state: ElementState,
 }
 
@@ -28,11 +34,25 @@ impl<T: AsRef<str> + 'static> Text<T> {
         self
     }
 
+    /// The text is a name that is replaced with an alias while streamer mode
+    /// is enabled.
+    pub fn alias_in_streamer_mode(mut self) -> Self {
+        self.alias_in_streamer_mode = true;
+        self
+    }
+
     pub fn with_width(mut self, width_bound: DimensionBound) -> Self {
         self.width_bound = Some(width_bound);
         self
     }
 
+    /// Show a tooltip when hovering the text. Without a provider, a tooltip
+    /// is only shown if the text is truncated.
+    pub fn with_tooltip(mut self, tooltip_provider: impl Fn() -> Option<String> + 'static) -> Self {
+        self.state.tooltip_provider = Some(Box::new(tooltip_provider));
+        self
+    }
+
     fn get_font_size(&self, theme: &InterfaceTheme) -> f32 {
         self.font_size
             .as_ref()
@@ -70,6 +90,22 @@ impl<T: AsRef<str> + 'static> Element for Text<T> {
         false
     }
 
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        let has_tooltip = self.state.tooltip_provider.is_some() || self.truncated_text.borrow().is_some();
+
+        match has_tooltip && matches!(mouse_mode, MouseInputMode::None) {
+            true => self.state.hovered_element(mouse_position),
+            false => HoverInformation::Missed,
+        }
+    }
+
+    fn tooltip(&self, _theme: &InterfaceTheme) -> Option<String> {
+        match &self.state.tooltip_provider {
+            Some(tooltip_provider) => tooltip_provider(),
+            None => self.truncated_text.borrow().clone(),
+        }
+    }
+
     fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -94,12 +130,16 @@ impl<T: AsRef<str> + 'static> Element for Text<T> {
             .map(|closure| closure(theme))
             .unwrap_or(theme.button.foreground_color.get());
 
-        let text = self.text.as_ref().unwrap();
-        renderer.render_text(
-            text.as_ref(),
-            ScreenPosition::default(),
-            foreground_color,
-            self.get_font_size(theme),
-        );
+        let text = interface_settings.localization.get(self.text.as_ref().unwrap().as_ref());
+        let text = match self.alias_in_streamer_mode {
+            true => interface_settings.streamer_mode.hide_name(text),
+            false => Cow::Borrowed(text),
+        };
+        let font_size = self.get_font_size(theme);
+
+        let text_width = renderer.get_text_dimensions(&text, font_size, f32::MAX).x;
+        *self.truncated_text.borrow_mut() = (text_width > self.state.cached_size.width).then(|| text.to_string());
+
+        renderer.render_text(&text, ScreenPosition::default(), foreground_color, font_size);
     }
 }
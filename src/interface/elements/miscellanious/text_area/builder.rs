@@ -0,0 +1,106 @@
+use procedural::dimension_bound;
+
+use super::{CursorBlink, TextArea};
+use crate::interface::builder::{Set, Unset};
+use crate::interface::*;
+
+/// Type state [`TextArea`] builder. This builder utilizes the type system to
+/// prevent calling the same method multiple times and calling
+/// [`build`](Self::build) before the mandatory methods have been called.
+#[must_use = "`build` needs to be called"]
+pub struct TextAreaBuilder<STATE, LENGTH, LINES, WIDTH> {
+    input_state: STATE,
+    length: usize,
+    visible_lines: usize,
+    width_bound: DimensionBound,
+    marker: PhantomData<(LENGTH, LINES, WIDTH)>,
+}
+
+impl TextAreaBuilder<Unset, Unset, Unset, Unset> {
+    pub fn new() -> Self {
+        Self {
+            input_state: Unset,
+            length: 0,
+            visible_lines: 0,
+            width_bound: dimension_bound!(100%),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<LENGTH, LINES, WIDTH> TextAreaBuilder<Unset, LENGTH, LINES, WIDTH> {
+    pub fn with_state(self, state: TrackedState<String>) -> TextAreaBuilder<TrackedState<String>, LENGTH, LINES, WIDTH> {
+        TextAreaBuilder {
+            input_state: state,
+            ..self
+        }
+    }
+}
+
+impl<STATE, LINES, WIDTH> TextAreaBuilder<STATE, Unset, LINES, WIDTH> {
+    /// Set the maximum number of allowed characters, including newlines.
+    pub fn with_length(self, length: usize) -> TextAreaBuilder<STATE, Set, LINES, WIDTH> {
+        TextAreaBuilder {
+            length,
+            marker: PhantomData,
+            ..self
+        }
+    }
+}
+
+impl<STATE, LENGTH, WIDTH> TextAreaBuilder<STATE, LENGTH, Unset, WIDTH> {
+    /// Set the number of lines that are visible at once. Any additional lines
+    /// can be reached by scrolling.
+    pub fn with_visible_lines(self, visible_lines: usize) -> TextAreaBuilder<STATE, LENGTH, Set, WIDTH> {
+        TextAreaBuilder {
+            visible_lines: visible_lines.max(1),
+            marker: PhantomData,
+            ..self
+        }
+    }
+}
+
+impl<STATE, LENGTH, LINES> TextAreaBuilder<STATE, LENGTH, LINES, Unset> {
+    pub fn with_width_bound(self, width_bound: DimensionBound) -> TextAreaBuilder<STATE, LENGTH, LINES, Set> {
+        TextAreaBuilder {
+            width_bound,
+            marker: PhantomData,
+            ..self
+        }
+    }
+}
+
+impl<WIDTH> TextAreaBuilder<TrackedState<String>, Set, Set, WIDTH> {
+    /// Take the builder and turn it into a [`TextArea`].
+    ///
+    /// NOTE: This method is only available if [`with_state`](Self::with_state),
+    /// [`with_length`](Self::with_length),
+    /// and [`with_visible_lines`](Self::with_visible_lines) have been called on
+    /// the builder.
+    pub fn build(self) -> TextArea {
+        let Self {
+            input_state,
+            length,
+            visible_lines,
+            width_bound,
+            ..
+        } = self;
+
+        // NOTE: Start with the cursor at the end of any existing text.
+        let cursor = input_state.borrow().len();
+
+        TextArea {
+            input_state,
+            length,
+            visible_lines,
+            width_bound,
+            cursor,
+            lines: Vec::new(),
+            scroll_offset: 0,
+            follow_cursor: true,
+            composition: None,
+            cursor_blink: CursorBlink::new(),
+            state: Default::default(),
+        }
+    }
+}
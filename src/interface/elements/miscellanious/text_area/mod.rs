@@ -0,0 +1,384 @@
+mod builder;
+mod wrap;
+
+use std::ops::Range;
+
+pub use self::builder::TextAreaBuilder;
+use self::wrap::wrap_lines;
+use super::cursor::CursorBlink;
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::input::MouseInputMode;
+use crate::interface::state::ValueState;
+use crate::interface::*;
+
+const COMPOSITION_UNDERLINE_HEIGHT: f32 = 1.0;
+
+/// Editable text that spans multiple lines. Long lines are wrapped and the
+/// text can be scrolled if it has more lines than are visible at once.
+pub struct TextArea {
+    input_state: TrackedState<String>,
+    length: usize,
+    visible_lines: usize,
+    width_bound: DimensionBound,
+    /// Byte index of the cursor in the text.
+    cursor: usize,
+    /// Byte ranges of the wrapped lines from the last resolve.
+    lines: Vec<Range<usize>>,
+    /// Index of the first visible line.
+    scroll_offset: usize,
+    /// Scroll to the cursor on the next resolve. This is only set when the
+    /// text or the cursor changes, so scrolling with the mouse wheel is not
+    /// undone by the next resolve.
+    follow_cursor: bool,
+    /// Uncommitted text of the input method.
+    composition: Option<String>,
+    cursor_blink: CursorBlink,
+    state: ElementState,
+}
+
+impl TextArea {
+    fn cursor_line(&self) -> usize {
+        self.lines.iter().rposition(|line| line.start <= self.cursor).unwrap_or(0)
+    }
+
+    fn cursor_changed(&mut self) -> Vec<ClickAction> {
+        self.follow_cursor = true;
+        self.cursor_blink.reset();
+        vec![ClickAction::ChangeEvent(ChangeEvent::RESOLVE_WINDOW)]
+    }
+
+    /// Insert text at the cursor. Control characters other than newlines are
+    /// dropped and the text is cut off at the maximum length.
+    fn insert_text(&mut self, text: &str) -> Vec<ClickAction> {
+        let inserted = self.input_state.with_mut(|input_state| {
+            let available_length = self.length.saturating_sub(input_state.chars().count());
+            let text: String = text
+                .chars()
+                .map(|character| match character {
+                    '\r' => '\n',
+                    character => character,
+                })
+                .filter(|character| *character == '\n' || !character.is_control())
+                .take(available_length)
+                .collect();
+
+            if text.is_empty() {
+                return ValueState::Unchanged(false);
+            }
+
+            input_state.insert_str(self.cursor, &text);
+            self.cursor += text.len();
+
+            ValueState::Mutated(true)
+        });
+
+        match inserted {
+            true => self.cursor_changed(),
+            false => Vec::new(),
+        }
+    }
+
+    /// Remove the character before the cursor, or the one after it if
+    /// `forward` is set.
+    fn remove_character(&mut self, forward: bool) -> Vec<ClickAction> {
+        let removed = self.input_state.with_mut(|input_state| {
+            let range = match forward {
+                true => input_state[self.cursor..]
+                    .chars()
+                    .next()
+                    .map(|character| self.cursor..self.cursor + character.len_utf8()),
+                false => input_state[..self.cursor]
+                    .chars()
+                    .next_back()
+                    .map(|character| self.cursor - character.len_utf8()..self.cursor),
+            };
+
+            let Some(range) = range else {
+                return ValueState::Unchanged(false);
+            };
+
+            self.cursor = range.start;
+            input_state.replace_range(range, "");
+
+            ValueState::Mutated(true)
+        });
+
+        match removed {
+            true => self.cursor_changed(),
+            false => Vec::new(),
+        }
+    }
+
+    fn move_cursor_horizontally(&mut self, forward: bool) -> Vec<ClickAction> {
+        let input_state = self.input_state.borrow();
+        let cursor = match forward {
+            true => input_state[self.cursor..]
+                .chars()
+                .next()
+                .map(|character| self.cursor + character.len_utf8()),
+            false => input_state[..self.cursor]
+                .chars()
+                .next_back()
+                .map(|character| self.cursor - character.len_utf8()),
+        };
+        drop(input_state);
+
+        match cursor {
+            Some(cursor) => {
+                self.cursor = cursor;
+                self.cursor_changed()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Move the cursor to the same column of the previous or next line.
+    fn move_cursor_vertically(&mut self, downwards: bool) -> Vec<ClickAction> {
+        let line_index = self.cursor_line();
+        let target_index = match downwards {
+            true => line_index + 1,
+            false => match line_index.checked_sub(1) {
+                Some(target_index) => target_index,
+                None => return Vec::new(),
+            },
+        };
+
+        let (Some(line), Some(target_line)) = (self.lines.get(line_index), self.lines.get(target_index)) else {
+            return Vec::new();
+        };
+
+        let input_state = self.input_state.borrow();
+        let column = input_state[line.start..self.cursor].chars().count();
+        let cursor = input_state[target_line.clone()]
+            .char_indices()
+            .nth(column)
+            .map(|(offset, _)| target_line.start + offset)
+            .unwrap_or(target_line.end);
+        drop(input_state);
+
+        self.cursor = cursor;
+        self.cursor_changed()
+    }
+
+    fn maximum_scroll_offset(&self) -> usize {
+        self.lines.len().saturating_sub(self.visible_lines)
+    }
+}
+
+impl Element for TextArea {
+    fn get_state(&self) -> &ElementState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ElementState {
+        &mut self.state
+    }
+
+    fn resolve(&mut self, placement_resolver: &mut PlacementResolver, interface_settings: &InterfaceSettings, theme: &InterfaceTheme) {
+        let font_size = theme.input.font_size.get();
+        let text_offset = theme.input.text_offset.get();
+        let scaling = interface_settings.scaling.get();
+
+        let height = self.visible_lines as f32 * font_size + text_offset.top * 2.0;
+        let size_bound = self
+            .width_bound
+            .add_height(DimensionBound::new(Dimension::Absolute(height), None, None));
+        self.state.resolve(placement_resolver, &size_bound);
+
+        // NOTE: The text offset is subtracted on both sides, so the text doesn't touch
+        // the right edge either.
+        let available_width = self.state.cached_size.width - text_offset.left * scaling * 2.0;
+        let input_state = self.input_state.borrow();
+
+        // The text might have been changed from outside of the element.
+        self.cursor = self.cursor.min(input_state.len());
+        while !input_state.is_char_boundary(self.cursor) {
+            self.cursor -= 1;
+        }
+
+        self.lines = wrap_lines(&input_state, available_width, |line| {
+            placement_resolver
+                .get_text_dimensions(line, font_size, ScreenPosition::default(), scaling, f32::MAX)
+                .x
+        });
+        drop(input_state);
+
+        if self.follow_cursor {
+            let cursor_line = self.cursor_line();
+            let first_line = (cursor_line + 1).saturating_sub(self.visible_lines);
+            self.scroll_offset = self.scroll_offset.clamp(first_line, cursor_line);
+            self.follow_cursor = false;
+        }
+
+        self.scroll_offset = self.scroll_offset.min(self.maximum_scroll_offset());
+    }
+
+    fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        match mouse_mode {
+            MouseInputMode::None => self.state.hovered_element(mouse_position),
+            _ => HoverInformation::Missed,
+        }
+    }
+
+    fn update(&mut self) -> Option<ChangeEvent> {
+        self.cursor_blink.update()
+    }
+
+    fn left_click(&mut self, _update: &mut bool) -> Vec<ClickAction> {
+        self.cursor_blink.reset();
+        vec![ClickAction::FocusElement]
+    }
+
+    fn scroll(&mut self, delta: f32) -> Option<ChangeEvent> {
+        let scroll_offset = match delta > 0.0 {
+            true => self.scroll_offset.saturating_sub(1),
+            false => (self.scroll_offset + 1).min(self.maximum_scroll_offset()),
+        };
+
+        (scroll_offset != self.scroll_offset).then(|| {
+            self.scroll_offset = scroll_offset;
+            ChangeEvent::RENDER_WINDOW
+        })
+    }
+
+    fn input_character(&mut self, character: char) -> Vec<ClickAction> {
+        match character {
+            '\u{8}' => self.remove_character(false),
+            '\u{7f}' => self.remove_character(true),
+            character => self.insert_text(character.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    fn edit_text(&mut self, edit: TextEdit) -> Vec<ClickAction> {
+        match edit {
+            TextEdit::Insert(text) => self.insert_text(&text),
+            TextEdit::CursorLeft => self.move_cursor_horizontally(false),
+            TextEdit::CursorRight => self.move_cursor_horizontally(true),
+            TextEdit::CursorUp => self.move_cursor_vertically(false),
+            TextEdit::CursorDown => self.move_cursor_vertically(true),
+            TextEdit::SelectAll | TextEdit::ExtendSelection | TextEdit::ShrinkSelection | TextEdit::DeleteSelection => Vec::new(),
+        }
+    }
+
+    fn input_composition(&mut self, composition: Option<String>) -> Option<ChangeEvent> {
+        (self.composition != composition).then(|| {
+            self.composition = composition;
+            ChangeEvent::RENDER_WINDOW
+        })
+    }
+
+    fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        _state_provider: &StateProvider,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        parent_position: ScreenPosition,
+        screen_clip: ScreenClip,
+        hovered_element: Option<&dyn Element>,
+        focused_element: Option<&dyn Element>,
+        _mouse_mode: &MouseInputMode,
+        _second_theme: bool,
+    ) {
+        let mut renderer = self
+            .state
+            .element_renderer(render_target, renderer, interface_settings, parent_position, screen_clip);
+
+        let input_state = self.input_state.borrow();
+        let is_hovererd = self.is_element_self(hovered_element);
+        let is_focused = self.is_element_self(focused_element);
+        let text_offset = theme.input.text_offset.get();
+        let font_size = theme.input.font_size.get();
+        let scaling = interface_settings.scaling.get();
+
+        let background_color = if is_hovererd {
+            theme.input.hovered_background_color.get()
+        } else if is_focused {
+            theme.input.focused_background_color.get()
+        } else {
+            theme.input.background_color.get()
+        };
+
+        let text_color = match is_focused {
+            true => theme.input.focused_text_color.get(),
+            false => theme.input.text_color.get(),
+        };
+
+        renderer.render_background(theme.input.corner_radius.get(), background_color);
+
+        let visible_lines = self.lines.iter().skip(self.scroll_offset).take(self.visible_lines);
+
+        for (row, line) in visible_lines.enumerate() {
+            // NOTE: Lines are wrapped during the resolve, so the text may have changed
+            // since then.
+            if let Some(line_text) = input_state.get(line.clone()) {
+                let line_offset = text_offset + ScreenPosition::only_top(row as f32 * font_size);
+                renderer.render_text(line_text, line_offset, text_color, font_size);
+            }
+        }
+
+        let cursor_line = self.cursor_line();
+
+        if !is_focused || cursor_line < self.scroll_offset || cursor_line >= self.scroll_offset + self.visible_lines {
+            self.cursor_blink.hide();
+            return;
+        }
+
+        let Some(line) = self.lines.get(cursor_line).cloned() else {
+            return;
+        };
+
+        let row = (cursor_line - self.scroll_offset) as f32;
+        let line_top = text_offset.top + row * font_size;
+        let line_offset = text_offset + ScreenPosition::only_top(row * font_size);
+        let prefix = input_state.get(line.start..self.cursor).unwrap_or_default();
+        let line_text = input_state.get(line).unwrap_or_default();
+        let prefix_width = renderer.get_text_dimensions(prefix, font_size, f32::MAX).x;
+
+        // NOTE: The cursor is measured from the start of the rendered line, which is
+        // aligned to the right edge in right-to-left layouts.
+        let line_start_offset = renderer.text_start(line_text, line_offset, font_size);
+        let mut cursor_offset = line_start_offset + theme.input.cursor_offset.get() * scaling + prefix_width;
+
+        if let Some(composition) = &self.composition {
+            let composition_position = ScreenPosition {
+                left: line_start_offset + prefix_width,
+                top: line_top * scaling,
+            };
+            let composition_size = renderer.get_text_dimensions(composition, font_size, f32::MAX);
+
+            renderer.render_text_at(composition, composition_position, text_color, font_size);
+
+            // NOTE: The composition is underlined to show that it's not committed yet.
+            let underline_position = composition_position + ScreenPosition::only_top(composition_size.y);
+            let underline_size = ScreenSize {
+                width: composition_size.x,
+                height: COMPOSITION_UNDERLINE_HEIGHT * scaling,
+            };
+            renderer.render_rectangle(underline_position, underline_size, CornerRadius::default(), text_color);
+
+            cursor_offset += composition_size.x;
+        }
+
+        if !self.cursor_blink.is_visible(theme.input.cursor_blink_interval.get()) {
+            return;
+        }
+
+        let cursor_position = ScreenPosition {
+            left: cursor_offset,
+            top: line_top * scaling,
+        };
+        let cursor_size = ScreenSize {
+            width: theme.input.cursor_width.get(),
+            height: font_size * scaling,
+        };
+
+        renderer.render_rectangle(
+            cursor_position,
+            cursor_size,
+            CornerRadius::default(),
+            theme.input.cursor_color.get(),
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+/// Split text into lines that fit into the available width. Lines are broken
+/// at newlines and between words, words that are wider than a whole line are
+/// broken between characters. Returns the byte range of every line, newline
+/// characters are not part of any line.
+pub(super) fn wrap_lines(text: &str, available_width: f32, mut measure: impl FnMut(&str) -> f32) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut paragraph_start = 0;
+
+    for paragraph in text.split('\n') {
+        let mut line_start = paragraph_start;
+        let mut line_end = paragraph_start;
+        let mut word_start = paragraph_start;
+
+        for word in paragraph.split_inclusive(' ') {
+            let word_end = word_start + word.len();
+
+            if line_end > line_start && measure(text[line_start..word_end].trim_end()) > available_width {
+                lines.push(line_start..line_end);
+                line_start = line_end;
+            }
+
+            // NOTE: A single word that doesn't fit is broken between characters.
+            if measure(text[line_start..word_end].trim_end()) > available_width {
+                for (offset, character) in text[line_end..word_end].char_indices() {
+                    let character_start = line_end + offset;
+                    let character_end = character_start + character.len_utf8();
+
+                    if character_start > line_start && measure(text[line_start..character_end].trim_end()) > available_width {
+                        lines.push(line_start..character_start);
+                        line_start = character_start;
+                    }
+                }
+            }
+
+            line_end = word_end;
+            word_start = word_end;
+        }
+
+        lines.push(line_start..line_end);
+        paragraph_start += paragraph.len() + 1;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::wrap_lines;
+
+    fn wrap(text: &str, available_width: f32) -> Vec<&str> {
+        wrap_lines(text, available_width, |line| line.chars().count() as f32)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn break_between_words() {
+        assert_eq!(wrap("guild meeting at noon", 10.0), ["guild ", "meeting at ", "noon"]);
+    }
+
+    #[test]
+    fn break_at_newlines() {
+        assert_eq!(wrap("first\n\nsecond\n", 10.0), ["first", "", "second", ""]);
+    }
+
+    #[test]
+    fn break_long_words() {
+        assert_eq!(wrap("abcdefghij", 4.0), ["abcd", "efgh", "ij"]);
+    }
+}
@@ -1,28 +1,24 @@
 use crate::graphics::Color;
 use crate::interface::*;
 
+/// Element that edits the value of a [`TrackedState`], so any window showing
+/// it can change the value without holding a reference to its owner.
 pub trait PrototypeMutableElement {
     fn to_mutable_element(&self, display: String, change_event: Option<ChangeEvent>) -> ElementCell;
 }
 
-impl PrototypeMutableElement for Color {
+impl PrototypeMutableElement for TrackedState<Color> {
     fn to_mutable_element(&self, display: String, change_event: Option<ChangeEvent>) -> ElementCell {
-        // SAFETY: This is obviously unsafe, so one needs to make sure that the element
-        // implementing `PrototypeMutableElement` will be valid and pinned while this
-        // element exists. Additionally, it should only be used in a debug
-        // context.
-        let static_self = unsafe { std::mem::transmute::<_, &'static Self>(self) };
-
         let elements = vec![
             StaticLabel::new(display.clone()).wrap(),
-            MutableColorValue::new(display, static_self, change_event).wrap(),
+            MutableColorValue::new(display, self.clone(), change_event).wrap(),
         ];
 
         Container::new(elements).wrap()
     }
 }
 
-impl PrototypeMutableElement for DimensionBound {
+impl PrototypeMutableElement for TrackedState<DimensionBound> {
     fn to_mutable_element(&self, display: String, _change_event: Option<ChangeEvent>) -> ElementCell {
         let elements = vec![StaticLabel::new(display).wrap()];
 
@@ -30,7 +26,7 @@ impl PrototypeMutableElement for DimensionBound {
     }
 }
 
-impl PrototypeMutableElement for SizeBound {
+impl PrototypeMutableElement for TrackedState<SizeBound> {
     fn to_mutable_element(&self, display: String, _change_event: Option<ChangeEvent>) -> ElementCell {
         let elements = vec![StaticLabel::new(display).wrap()];
 
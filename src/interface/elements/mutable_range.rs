@@ -3,10 +3,12 @@ use std::fmt::Display;
 use num::traits::NumOps;
 use num::{NumCast, Zero};
 
+use crate::graphics::ColorRGB;
 use crate::interface::*;
 
+/// Element that edits the value of a [`TrackedState`] within a range.
 pub trait PrototypeMutableRangeElement<T> {
-    fn to_mutable_range_element(&self, display: String, minimum: Self, maximum: Self, change_event: Option<ChangeEvent>) -> ElementCell;
+    fn to_mutable_range_element(&self, display: String, minimum: T, maximum: T, change_event: Option<ChangeEvent>) -> ElementCell;
 }
 
 // workaround for not having negative trait bounds or better specialization
@@ -16,40 +18,29 @@ impl !IsVector for f32 {}
 impl IsVector for ScreenPosition {}
 impl IsVector for ScreenSize {}
 impl IsVector for CornerRadius {}
+impl IsVector for ColorRGB {}
 
-impl PrototypeMutableRangeElement<f32> for f32 {
-    fn to_mutable_range_element(&self, display: String, minimum: Self, maximum: Self, change_event: Option<ChangeEvent>) -> ElementCell {
-        // SAFETY: This is obviously unsafe, so one needs to make sure that the element
-        // implementing `PrototypeMutableRangeElement` will be valid and pinned while
-        // this element exists. Additionally, it should only be used in a debug
-        // context.
-        let static_self = unsafe { std::mem::transmute::<_, &'static Self>(self) };
-
+impl PrototypeMutableRangeElement<f32> for TrackedState<f32> {
+    fn to_mutable_range_element(&self, display: String, minimum: f32, maximum: f32, change_event: Option<ChangeEvent>) -> ElementCell {
         let elements = vec![
             StaticLabel::new(display.clone()).wrap(),
-            MutableNumberValue::new(display, static_self, minimum, maximum, change_event).wrap(),
+            MutableNumberValue::new(display, self.clone(), minimum, maximum, change_event).wrap(),
         ];
 
         Container::new(elements).wrap()
     }
 }
 
-impl<T> PrototypeMutableRangeElement<T> for T
+impl<T> PrototypeMutableRangeElement<T> for TrackedState<T>
 where
     T: ArrayType + ElementDisplay + IsVector + Copy + PartialEq + 'static, // TODO: !f32 or something
     T::Element: Zero + NumOps + NumCast + Copy + PartialOrd + Display + 'static,
     [(); T::ELEMENT_COUNT]:,
 {
-    fn to_mutable_range_element(&self, display: String, minimum: Self, maximum: Self, change_event: Option<ChangeEvent>) -> ElementCell {
-        // SAFETY: This is obviously unsafe, so one needs to make sure that the element
-        // implementing `PrototypeMutableRangeElement` will be valid and pinned while
-        // this element exists. Additionally, it should only be used in a debug
-        // context.
-        let static_self = unsafe { std::mem::transmute::<_, &'static Self>(self) };
-
+    fn to_mutable_range_element(&self, display: String, minimum: T, maximum: T, change_event: Option<ChangeEvent>) -> ElementCell {
         let elements = vec![
             StaticLabel::new(display.clone()).wrap(),
-            MutableArrayValue::new(display, static_self, minimum, maximum, change_event).wrap(),
+            MutableArrayValue::new(display, self.clone(), minimum, maximum, change_event).wrap(),
         ];
 
         Container::new(elements).wrap()
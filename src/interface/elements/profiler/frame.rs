@@ -135,7 +135,8 @@ impl Element for FrameView {
         let mut y_position = 0.0;
         for (name, color) in std::iter::once((ROOT_MEASUREMENT_NAME, Color::monochrome_u8(150))).chain(color_lookup.into_iter()) {
             let statistics = statistics_map.get(name).unwrap();
-            let text = format!("{} {:?} (SD {:.1})", name, statistics.mean, statistics.standard_deviation);
+            let text =
+                bumpalo::format!(in renderer.get_frame_arena(), "{} {:?} (SD {:.1})", name, statistics.mean, statistics.standard_deviation);
 
             let text_position = ScreenPosition {
                 left: 3.0,
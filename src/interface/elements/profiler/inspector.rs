@@ -133,12 +133,13 @@ impl FrameInspectorView {
         );
 
         if alpha > VISIBILITY_THRESHHOLD {
-            let text = format!("{} ({:?})", measurement.name, measurement.end_time - measurement.start_time);
+            let text = bumpalo::format!(in renderer.get_frame_arena(), "{} ({:?})", measurement.name, measurement.end_time - measurement.start_time);
             let screen_clip = ScreenClip {
                 left: renderer.screen_clip.left + x_position,
                 top: renderer.screen_clip.top + y_position,
                 right: renderer.screen_clip.left + x_position + x_size,
                 bottom: renderer.screen_clip.top + y_position + y_size,
+                corner_radius: CornerRadius::default(),
             };
 
             let text_position = renderer.position
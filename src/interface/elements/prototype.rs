@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Display;
 use std::net::Ipv4Addr;
 use std::rc::Rc;
@@ -119,6 +120,11 @@ impl<T, const N: usize> !NoPrototype for [T; N] {}
 impl<T> !NoPrototype for &[T] {}
 impl<T> !NoPrototype for Vec<T> {}
 impl<T> !NoPrototype for Rc<T> {}
+impl<T> !NoPrototype for TrackedState<T> {}
+impl<K, V, S> !NoPrototype for HashMap<K, V, S> {}
+impl<K, V> !NoPrototype for BTreeMap<K, V> {}
+impl<T, S> !NoPrototype for HashSet<T, S> {}
+impl<T> !NoPrototype for BTreeSet<T> {}
 
 impl NoPrototype for &str {}
 impl NoPrototype for String {}
@@ -157,6 +163,12 @@ impl<T: PrototypeElement> PrototypeElement for std::sync::Arc<T> {
     }
 }
 
+impl<T: PrototypeElement> PrototypeElement for TrackedState<T> {
+    fn to_element(&self, display: String) -> ElementCell {
+        self.borrow().to_element(display)
+    }
+}
+
 impl<T: PrototypeElement> PrototypeElement for Option<T> {
     fn to_element(&self, display: String) -> ElementCell {
         if let Some(value) = self {
@@ -205,6 +217,78 @@ impl<T: PrototypeElement> PrototypeElement for Vec<T> {
     }
 }
 
+/// Render the entries of a map sorted by their key, so the order stays the
+/// same between opening the window multiple times.
+fn map_to_element<'a, K, V>(display: String, entries: impl Iterator<Item = (&'a K, &'a V)>) -> ElementCell
+where
+    K: ElementDisplay + 'a,
+    V: PrototypeElement + 'a,
+{
+    let mut entries: Vec<(String, &V)> = entries.map(|(key, value)| (key.display(), value)).collect();
+    entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let elements = entries.into_iter().map(|(key, value)| value.to_element(key)).collect();
+
+    Expandable::new(display, elements, false).wrap()
+}
+
+fn set_to_element<'a, T>(display: String, items: impl Iterator<Item = &'a T>) -> ElementCell
+where
+    T: ElementDisplay + 'a,
+{
+    let mut items: Vec<String> = items.map(ElementDisplay::display).collect();
+    items.sort();
+
+    let elements = items.into_iter().map(|item| StaticLabel::new(item).wrap()).collect();
+
+    Expandable::new(display, elements, false).wrap()
+}
+
+impl<K: ElementDisplay, V: PrototypeElement, S> PrototypeElement for HashMap<K, V, S> {
+    fn to_element(&self, display: String) -> ElementCell {
+        map_to_element(display, self.iter())
+    }
+}
+
+impl<K: ElementDisplay, V: PrototypeElement> PrototypeElement for BTreeMap<K, V> {
+    fn to_element(&self, display: String) -> ElementCell {
+        map_to_element(display, self.iter())
+    }
+}
+
+impl<T: ElementDisplay, S> PrototypeElement for HashSet<T, S> {
+    fn to_element(&self, display: String) -> ElementCell {
+        set_to_element(display, self.iter())
+    }
+}
+
+impl<T: ElementDisplay> PrototypeElement for BTreeSet<T> {
+    fn to_element(&self, display: String) -> ElementCell {
+        set_to_element(display, self.iter())
+    }
+}
+
+macro_rules! implement_tuple {
+    ($($name:ident: $index:tt),+) => {
+        impl<$($name),+> !NoPrototype for ($($name,)+) {}
+
+        impl<$($name: PrototypeElement),+> PrototypeElement for ($($name,)+) {
+            fn to_element(&self, display: String) -> ElementCell {
+                let elements = vec![$(self.$index.to_element(stringify!($index).to_owned())),+];
+
+                Expandable::new(display, elements, false).wrap()
+            }
+        }
+    };
+}
+
+implement_tuple!(A: 0);
+implement_tuple!(A: 0, B: 1);
+implement_tuple!(A: 0, B: 1, C: 2);
+implement_tuple!(A: 0, B: 1, C: 2, D: 3);
+implement_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+implement_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
 impl PrototypeElement for Color {
     fn to_element(&self, display: String) -> ElementCell {
         let elements = vec![StaticLabel::new(display).wrap(), ColorValue::new(*self).wrap()];
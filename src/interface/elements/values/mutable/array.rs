@@ -15,7 +15,7 @@ where
     [(); T::ELEMENT_COUNT]:,
 {
     name: String,
-    reference: &'static T,
+    reference: TrackedState<T>,
     minimum_value: T,
     maximum_value: T,
     change_event: Option<ChangeEvent>,
@@ -30,8 +30,8 @@ where
     T::Element: Zero + NumOps + NumCast + Copy + PartialOrd + Display + 'static,
     [(); T::ELEMENT_COUNT]:,
 {
-    pub fn new(name: String, reference: &'static T, minimum_value: T, maximum_value: T, change_event: Option<ChangeEvent>) -> Self {
-        let cached_inner = *reference;
+    pub fn new(name: String, reference: TrackedState<T>, minimum_value: T, maximum_value: T, change_event: Option<ChangeEvent>) -> Self {
+        let cached_inner = reference.get();
         let cached_values = cached_inner.display();
         let state = ElementState::default();
 
@@ -67,7 +67,7 @@ where
     }
 
     fn update(&mut self) -> Option<ChangeEvent> {
-        let current_value = *self.reference;
+        let current_value = self.reference.get();
 
         if self.cached_inner != current_value {
             self.cached_inner = current_value;
@@ -88,7 +88,7 @@ where
     fn left_click(&mut self, _force_update: &mut bool) -> Vec<ClickAction> {
         let prototype_window = ArrayWindow::new(
             self.name.clone(),
-            self.reference,
+            self.reference.clone(),
             self.minimum_value,
             self.maximum_value,
             self.change_event,
@@ -1,34 +1,42 @@
 use std::marker::PhantomData;
 
-use derive_new::new;
 use serde::{Deserialize, Serialize};
 
-use crate::interface::{ChangeEvent, ElementCell, IntoChangeEvent, PrototypeElement, PrototypeMutableElement};
+use crate::interface::{ChangeEvent, ElementCell, IntoChangeEvent, PrototypeElement, PrototypeMutableElement, TrackedState};
 
-#[derive(Serialize, Deserialize, new)]
+#[derive(Serialize, Deserialize)]
 pub struct Mutable<T, E>
 where
-    T: Copy + PrototypeMutableElement,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableElement,
     E: IntoChangeEvent,
 {
-    data: T,
-    #[new(default)]
+    data: TrackedState<T>,
     _phantom_data: PhantomData<E>,
 }
 
 impl<T, E> Mutable<T, E>
 where
-    T: Copy + PrototypeMutableElement,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableElement,
     E: IntoChangeEvent,
 {
+    pub fn new(data: T) -> Self {
+        Self {
+            data: TrackedState::new(data),
+            _phantom_data: PhantomData,
+        }
+    }
+
     pub fn get(&self) -> T {
-        self.data
+        self.data.get()
     }
 }
 
 impl<T, E> PrototypeElement for Mutable<T, E>
 where
-    T: Copy + PrototypeMutableElement,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableElement,
     E: IntoChangeEvent,
 {
     fn to_element(&self, display: String) -> ElementCell {
@@ -38,7 +46,8 @@ where
 
 impl<T, E> PrototypeMutableElement for Mutable<T, E>
 where
-    T: Copy + PrototypeMutableElement,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableElement,
     E: IntoChangeEvent,
 {
     fn to_mutable_element(&self, display: String, _change_event: Option<ChangeEvent>) -> ElementCell {
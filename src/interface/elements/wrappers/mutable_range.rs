@@ -1,42 +1,52 @@
 use std::marker::PhantomData;
 
-use derive_new::new;
 use serde::{Deserialize, Serialize};
 
 use crate::interface::{
-    ChangeEvent, ElementCell, IntoChangeEvent, PrototypeElement, PrototypeMutableElement, PrototypeMutableRangeElement,
+    ChangeEvent, ElementCell, IntoChangeEvent, PrototypeElement, PrototypeMutableElement, PrototypeMutableRangeElement, TrackedState,
 };
 
 // TODO: rework when const generics are able to do this:
 //pub struct MutableRange<T, const MIN: Vector2<T>, const MAX: Vector2<T>>(pub
 // T);
 
-#[derive(Serialize, Deserialize, new)]
+#[derive(Serialize, Deserialize)]
 pub struct MutableRange<T, E>
 where
-    T: Copy + PrototypeMutableRangeElement<T>,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableRangeElement<T>,
     E: IntoChangeEvent,
 {
-    inner: T,
+    inner: TrackedState<T>,
     minimum: T,
     maximum: T,
-    #[new(default)]
     _phantom_data: PhantomData<E>,
 }
 
 impl<T, E> MutableRange<T, E>
 where
-    T: Copy + PrototypeMutableRangeElement<T>,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableRangeElement<T>,
     E: IntoChangeEvent,
 {
+    pub fn new(inner: T, minimum: T, maximum: T) -> Self {
+        Self {
+            inner: TrackedState::new(inner),
+            minimum,
+            maximum,
+            _phantom_data: PhantomData,
+        }
+    }
+
     pub fn get(&self) -> T {
-        self.inner
+        self.inner.get()
     }
 }
 
 impl<T, E> PrototypeElement for MutableRange<T, E>
 where
-    T: Copy + PrototypeMutableRangeElement<T>,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableRangeElement<T>,
     E: IntoChangeEvent,
 {
     fn to_element(&self, display: String) -> ElementCell {
@@ -47,7 +57,8 @@ where
 
 impl<T, E> PrototypeMutableElement for MutableRange<T, E>
 where
-    T: Copy + PrototypeMutableRangeElement<T>,
+    T: Copy,
+    TrackedState<T>: PrototypeMutableRangeElement<T>,
     E: IntoChangeEvent,
 {
     fn to_mutable_element(&self, display: String, _change_event: Option<ChangeEvent>) -> ElementCell {
@@ -1,6 +1,6 @@
 use super::{ItemSource, SkillSource};
 use crate::input::UserEvent;
-use crate::interface::{ChangeEvent, ElementCell, FocusMode, PrototypeWindow, ScreenPosition, ScreenSize, Tracker};
+use crate::interface::{ChangeEvent, ContextMenuEntry, ElementCell, FocusMode, PrototypeWindow, ScreenPosition, ScreenSize, Tracker};
 use crate::inventory::{Item, Skill};
 
 pub enum ClickAction {
@@ -9,6 +9,8 @@ pub enum ClickAction {
     ChangeEvent(ChangeEvent),
     Event(UserEvent),
     DragElement,
+    /// Remember the selected tab of the window in the window cache.
+    SelectTab(usize),
     MoveItem(ItemSource, Item),
     MoveSkill(SkillSource, Skill),
     MoveInterface,
@@ -20,4 +22,7 @@ pub enum ClickAction {
         size_tracker: Tracker<ScreenSize>,
     },
     ClosePopup,
+    /// Open a [`ContextMenu`](crate::interface::ContextMenu) at the mouse
+    /// position.
+    OpenContextMenu(Vec<ContextMenuEntry>),
 }
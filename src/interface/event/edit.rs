@@ -0,0 +1,19 @@
+/// Editing operations for elements that contain text. The selection always
+/// reaches from its start to the end of the text.
+#[derive(Clone, Debug)]
+pub enum TextEdit {
+    SelectAll,
+    /// Move the start of the selection one character to the left.
+    ExtendSelection,
+    /// Move the start of the selection one character to the right.
+    ShrinkSelection,
+    DeleteSelection,
+    /// Insert text at the cursor, replacing the selection.
+    Insert(String),
+    CursorLeft,
+    CursorRight,
+    /// Move the cursor to the previous line of multi-line text.
+    CursorUp,
+    /// Move the cursor to the next line of multi-line text.
+    CursorDown,
+}
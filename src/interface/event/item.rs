@@ -1,10 +1,23 @@
+use crate::input::HotbarSlot;
 use crate::inventory::Item;
 use crate::network::EquipPosition;
 
 #[derive(Clone, Copy, Debug)]
 pub enum ItemSource {
     Inventory,
-    Equipment { position: EquipPosition },
+    Equipment {
+        position: EquipPosition,
+    },
+    Hotbar {
+        slot: HotbarSlot,
+    },
+    Trade,
+    Shop,
+    Cart,
+    /// Items attached to a received mail.
+    Mail,
+    /// Items attached to the mail the player is writing.
+    MailAttachment,
 }
 
 #[derive(Debug, Clone)]
@@ -13,3 +26,16 @@ pub struct ItemMove {
     pub destination: ItemSource,
     pub item: Item,
 }
+
+impl ItemMove {
+    /// Favorite items need to be confirmed before they leave the inventory for
+    /// a trade, the cart or a mail, so they are not given away by accident.
+    pub fn needs_confirmation(&self) -> bool {
+        self.item.is_favorite
+            && matches!(self.source, ItemSource::Inventory)
+            && matches!(
+                self.destination,
+                ItemSource::Trade | ItemSource::Cart | ItemSource::MailAttachment
+            )
+    }
+}
@@ -1,11 +1,13 @@
 mod action;
 mod change;
+mod edit;
 mod hover;
 mod item;
 mod skill;
 
 pub use self::action::ClickAction;
 pub use self::change::*;
+pub use self::edit::TextEdit;
 pub use self::hover::HoverInformation;
 pub use self::item::{ItemMove, ItemSource};
 pub use self::skill::{SkillMove, SkillSource};
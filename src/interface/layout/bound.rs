@@ -129,6 +129,17 @@ impl SizeBound {
         ScreenSize { width, height }
     }
 
+    /// Windows can be resized horizontally if their width is a range.
+    pub fn is_width_resizable(&self) -> bool {
+        self.minimum_width.is_some() || self.maximum_width.is_some()
+    }
+
+    /// Windows can be resized vertically if their height is a range. Flexible
+    /// heights are recalculated from the content on every resolve.
+    pub fn is_height_resizable(&self) -> bool {
+        !self.height.is_flexible() && (self.minimum_height.is_some() || self.maximum_height.is_some())
+    }
+
     pub fn validated_position(&self, position: ScreenPosition, size: ScreenSize, available: ScreenSize) -> ScreenPosition {
         let half_size = size / 2.0;
         let left = f32::clamp(position.left, -half_size.width, available.width - half_size.width);
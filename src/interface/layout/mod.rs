@@ -1,9 +1,11 @@
 mod bound;
 mod dimension;
+mod popup;
 mod resolver;
 mod size;
 
 pub use self::bound::{DimensionBound, SizeBound};
 pub use self::dimension::Dimension;
+pub use self::popup::PopupAnchor;
 pub use self::resolver::PlacementResolver;
 pub use self::size::{ArrayType, CornerRadius, PartialScreenSize, ScreenClip, ScreenPosition, ScreenSize};
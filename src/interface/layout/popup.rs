@@ -0,0 +1,110 @@
+use super::{ScreenPosition, ScreenSize};
+
+/// Area that a popup is attached to, like the field of a pick list or the
+/// position of the mouse for a context menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PopupAnchor {
+    pub position: ScreenPosition,
+    pub size: ScreenSize,
+}
+
+impl PopupAnchor {
+    pub fn new(position: ScreenPosition, size: ScreenSize) -> Self {
+        Self { position, size }
+    }
+
+    /// Anchor without a size, for popups that open at the mouse.
+    pub fn at(position: ScreenPosition) -> Self {
+        Self {
+            position,
+            size: ScreenSize::default(),
+        }
+    }
+
+    /// Position of a popup of the given size so that it is fully on screen.
+    /// The popup is placed below the anchor and flips above it if there is
+    /// more space there. If it still doesn't fit, it slides along the edges of
+    /// the screen. Popups that are larger than the screen are aligned to the
+    /// top left corner.
+    pub fn place(&self, popup_size: ScreenSize, available_space: ScreenSize) -> ScreenPosition {
+        let space_below = available_space.height - (self.position.top + self.size.height);
+        let space_above = self.position.top;
+
+        let top = match popup_size.height > space_below && space_above > space_below {
+            true => self.position.top - popup_size.height,
+            false => self.position.top + self.size.height,
+        };
+
+        ScreenPosition {
+            left: self.position.left.min(available_space.width - popup_size.width).max(0.0),
+            top: top.min(available_space.height - popup_size.height).max(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PopupAnchor;
+    use crate::interface::{ScreenPosition, ScreenSize};
+
+    const AVAILABLE_SPACE: ScreenSize = ScreenSize {
+        width: 800.0,
+        height: 600.0,
+    };
+    const POPUP_SIZE: ScreenSize = ScreenSize {
+        width: 150.0,
+        height: 200.0,
+    };
+    const FIELD_SIZE: ScreenSize = ScreenSize {
+        width: 100.0,
+        height: 20.0,
+    };
+
+    #[test]
+    fn place_below_anchor() {
+        let anchor = PopupAnchor::new(ScreenPosition { left: 100.0, top: 100.0 }, FIELD_SIZE);
+        assert_eq!(anchor.place(POPUP_SIZE, AVAILABLE_SPACE), ScreenPosition {
+            left: 100.0,
+            top: 120.0
+        });
+    }
+
+    #[test]
+    fn flip_above_anchor() {
+        let anchor = PopupAnchor::new(ScreenPosition { left: 100.0, top: 500.0 }, FIELD_SIZE);
+        assert_eq!(anchor.place(POPUP_SIZE, AVAILABLE_SPACE), ScreenPosition {
+            left: 100.0,
+            top: 300.0
+        });
+    }
+
+    #[test]
+    fn slide_along_edges() {
+        let right_edge = PopupAnchor::at(ScreenPosition { left: 750.0, top: 100.0 });
+        assert_eq!(right_edge.place(POPUP_SIZE, AVAILABLE_SPACE), ScreenPosition {
+            left: 650.0,
+            top: 100.0
+        });
+
+        // NOTE: Neither below nor above has enough space, so the popup slides up.
+        let tall_popup = ScreenSize {
+            width: 150.0,
+            height: 400.0,
+        };
+        let center = PopupAnchor::at(ScreenPosition { left: 100.0, top: 250.0 });
+        assert_eq!(center.place(tall_popup, AVAILABLE_SPACE), ScreenPosition {
+            left: 100.0,
+            top: 200.0
+        });
+    }
+
+    #[test]
+    fn larger_than_screen() {
+        let too_large = ScreenSize {
+            width: 1000.0,
+            height: 700.0,
+        };
+        let anchor = PopupAnchor::at(ScreenPosition { left: 400.0, top: 300.0 });
+        assert_eq!(anchor.place(too_large, AVAILABLE_SPACE), ScreenPosition { left: 0.0, top: 0.0 });
+    }
+}
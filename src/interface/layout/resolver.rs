@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use bumpalo::Bump;
 use cgmath::Vector2;
 
 use super::bound::ParentLimits;
@@ -10,8 +11,9 @@ use crate::loaders::FontLoader;
 const ELEMENT_THRESHHOLD: f32 = 1.0000;
 const REMAINDER_THRESHHOLD: f32 = 0.0001;
 
-pub struct PlacementResolver {
+pub struct PlacementResolver<'a> {
     font_loader: Rc<RefCell<FontLoader>>,
+    frame_arena: &'a Bump,
     available_space: PartialScreenSize,
     parent_limits: ParentLimits,
     base_position: ScreenPosition,
@@ -21,17 +23,22 @@ pub struct PlacementResolver {
     border: ScreenSize,
     gaps: ScreenSize,
     scaling: f32,
+    /// Lines are filled from the right instead of the left, for languages
+    /// that are written from right to left.
+    right_to_left: bool,
 }
 
-impl PlacementResolver {
+impl<'a> PlacementResolver<'a> {
     pub fn new(
         font_loader: Rc<RefCell<FontLoader>>,
+        frame_arena: &'a Bump,
         screen_size: ScreenSize,
         mut window_size: ScreenSize,
         size_bound: &SizeBound,
         border: ScreenSize,
         gaps: ScreenSize,
         scaling: f32,
+        right_to_left: bool,
     ) -> Self {
         window_size -= border * scaling * 2.0;
 
@@ -48,6 +55,7 @@ impl PlacementResolver {
 
         Self {
             font_loader,
+            frame_arena,
             available_space,
             parent_limits,
             base_position,
@@ -57,6 +65,7 @@ impl PlacementResolver {
             border,
             gaps,
             scaling,
+            right_to_left,
         }
     }
 
@@ -83,9 +92,11 @@ impl PlacementResolver {
         let total_height = 0.0;
         let gaps = self.gaps;
         let scaling = self.scaling;
+        let right_to_left = self.right_to_left;
 
         let derived_resolver = Self {
             font_loader,
+            frame_arena: self.frame_arena,
             available_space,
             parent_limits,
             base_position,
@@ -95,11 +106,37 @@ impl PlacementResolver {
             border,
             gaps,
             scaling,
+            right_to_left,
         };
 
         (derived_resolver, size, position)
     }
 
+    /// Create a resolver for an area with a fixed position and size, like a
+    /// cell of a grid. Unlike [`derive`](Self::derive), this doesn't allocate
+    /// any space in this resolver.
+    pub fn derive_cell(&self, offset: ScreenPosition, size: ScreenSize) -> Self {
+        let base_position = ScreenPosition {
+            left: self.horizontal_position(offset.left, size.width),
+            top: self.base_position.top + offset.top,
+        };
+
+        Self {
+            font_loader: self.font_loader.clone(),
+            frame_arena: self.frame_arena,
+            available_space: PartialScreenSize::new(size.width, Some(size.height)),
+            parent_limits: self.parent_limits,
+            base_position,
+            horizontal_accumulator: 0.0,
+            vertical_offset: 0.0,
+            total_height: 0.0,
+            border: ScreenSize::default(),
+            gaps: ScreenSize::default(),
+            scaling: self.scaling,
+            right_to_left: self.right_to_left,
+        }
+    }
+
     pub fn get_text_dimensions(
         &self,
         text: &str,
@@ -108,9 +145,18 @@ impl PlacementResolver {
         scaling: f32,
         available_width: f32,
     ) -> Vector2<f32> {
-        self.font_loader
-            .borrow()
-            .get_text_dimensions(text, font_size * scaling, available_width - text_offset.left * scaling)
+        self.font_loader.borrow_mut().get_text_dimensions(
+            text,
+            font_size * scaling,
+            available_width - text_offset.left * scaling,
+            self.frame_arena,
+        )
+    }
+
+    /// Bump allocator for temporary allocations during the resolution. Only
+    /// valid until the end of the current frame.
+    pub fn get_frame_arena(&self) -> &'a Bump {
+        self.frame_arena
     }
 
     pub fn set_gaps(&mut self, gaps: ScreenSize) {
@@ -171,10 +217,8 @@ impl PlacementResolver {
             }
         }
 
-        let position = ScreenPosition {
-            left: self.base_position.left + self.horizontal_accumulator + gaps_subtract,
-            top: self.base_position.top,
-        };
+        let offset = self.horizontal_accumulator + gaps_subtract;
+        let top = self.base_position.top;
 
         self.horizontal_accumulator += size.width + gaps_add;
 
@@ -190,6 +234,12 @@ impl PlacementResolver {
         }
 
         size.width -= gaps_subtract;
+
+        let position = ScreenPosition {
+            left: self.horizontal_position(offset, size.width),
+            top,
+        };
+
         (size, position)
     }
 
@@ -207,7 +257,10 @@ impl PlacementResolver {
         }
 
         let position = ScreenPosition {
-            left: self.base_position.left + (self.available_space.width - size.width - self.gaps.width * self.scaling),
+            left: self.horizontal_position(
+                self.available_space.width - size.width - self.gaps.width * self.scaling,
+                size.width,
+            ),
             top: self.base_position.top,
         };
 
@@ -220,6 +273,16 @@ impl PlacementResolver {
         (size, position)
     }
 
+    /// Position of an element from its offset to the start of the line. In
+    /// right-to-left layouts the line starts on the right, so the element is
+    /// mirrored.
+    fn horizontal_position(&self, offset: f32, width: f32) -> f32 {
+        match self.right_to_left {
+            true => self.base_position.left + self.available_space.width - offset - width,
+            false => self.base_position.left + offset,
+        }
+    }
+
     pub fn get_parent_limits(&self) -> ParentLimits {
         self.parent_limits
     }
@@ -8,9 +8,11 @@ pub trait ArrayType {
 
     const ELEMENT_COUNT: usize;
 
-    fn get_array_fields(&'static self) -> [(String, &'static Self::Element); Self::ELEMENT_COUNT];
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT];
 
     fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT];
+
+    fn set_element(&mut self, index: usize, value: Self::Element);
 }
 
 macro_rules! implement_ops {
@@ -119,13 +121,21 @@ impl ArrayType for ScreenPosition {
 
     const ELEMENT_COUNT: usize = 2;
 
-    fn get_array_fields(&'static self) -> [(String, &'static Self::Element); Self::ELEMENT_COUNT] {
-        [("left".to_owned(), &self.left), ("top".to_owned(), &self.top)]
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT] {
+        ["left", "top"]
     }
 
     fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT] {
         [self.left, self.top]
     }
+
+    fn set_element(&mut self, index: usize, value: Self::Element) {
+        match index {
+            0 => self.left = value,
+            1 => self.top = value,
+            _ => panic!("index out of bounds"),
+        }
+    }
 }
 
 implement_ops!(ScreenPosition, left, top);
@@ -159,6 +169,10 @@ impl ScreenSize {
     pub fn only_width(width: f32) -> Self {
         Self { width, height: 0.0 }
     }
+
+    pub fn only_height(height: f32) -> Self {
+        Self { width: 0.0, height }
+    }
 }
 
 impl ElementDisplay for ScreenSize {
@@ -176,13 +190,21 @@ impl ArrayType for ScreenSize {
 
     const ELEMENT_COUNT: usize = 2;
 
-    fn get_array_fields(&'static self) -> [(String, &'static Self::Element); Self::ELEMENT_COUNT] {
-        [("width".to_owned(), &self.width), ("height".to_owned(), &self.height)]
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT] {
+        ["width", "height"]
     }
 
     fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT] {
         [self.width, self.height]
     }
+
+    fn set_element(&mut self, index: usize, value: Self::Element) {
+        match index {
+            0 => self.width = value,
+            1 => self.height = value,
+            _ => panic!("index out of bounds"),
+        }
+    }
 }
 
 implement_ops!(ScreenSize, width, height);
@@ -248,6 +270,100 @@ pub struct ScreenClip {
     pub right: f32,
     pub top: f32,
     pub bottom: f32,
+    /// Uses the same units as the corner radius of rectangles, so a clip will
+    /// match the shape of a background rendered with the same radius.
+    pub corner_radius: CornerRadius,
+}
+
+impl ScreenClip {
+    /// A clip that doesn't cut off anything. The interface shaders skip
+    /// clipping entirely for it, since the size of the clip can't be
+    /// represented precisely.
+    pub const fn unbounded() -> Self {
+        Self {
+            left: 0.0,
+            right: f32::MAX,
+            top: 0.0,
+            bottom: f32::MAX,
+            corner_radius: CornerRadius {
+                top_left: 0.0,
+                top_right: 0.0,
+                bottom_left: 0.0,
+                bottom_right: 0.0,
+            },
+        }
+    }
+
+    /// Shrink the clip to the area of an element. Corners of the clip that are
+    /// still inside the new area keep their radius.
+    pub fn clamp(self, position: ScreenPosition, size: ScreenSize) -> Self {
+        let left = self.left.max(position.left);
+        let top = self.top.max(position.top);
+        let right = self.right.min(position.left + size.width);
+        let bottom = self.bottom.min(position.top + size.height);
+
+        let keep_radius = |horizontal: bool, vertical: bool, radius: f32| match horizontal && vertical {
+            true => radius,
+            false => 0.0,
+        };
+
+        let corner_radius = CornerRadius {
+            top_left: keep_radius(left == self.left, top == self.top, self.corner_radius.top_left),
+            top_right: keep_radius(right == self.right, top == self.top, self.corner_radius.top_right),
+            bottom_left: keep_radius(left == self.left, bottom == self.bottom, self.corner_radius.bottom_left),
+            bottom_right: keep_radius(right == self.right, bottom == self.bottom, self.corner_radius.bottom_right),
+        };
+
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+            corner_radius,
+        }
+    }
+
+    /// Round the corners of the clip that line up with the corners of the
+    /// given area. This is used to mask the content of elements that have
+    /// rounded or circular backgrounds.
+    pub fn round_corners(self, position: ScreenPosition, size: ScreenSize, corner_radius: CornerRadius) -> Self {
+        let right = position.left + size.width;
+        let bottom = position.top + size.height;
+
+        let round_corner = |horizontal: bool, vertical: bool, radius: f32, new_radius: f32| match horizontal && vertical {
+            true => radius.max(new_radius),
+            false => radius,
+        };
+
+        let corner_radius = CornerRadius {
+            top_left: round_corner(
+                self.left == position.left,
+                self.top == position.top,
+                self.corner_radius.top_left,
+                corner_radius.top_left,
+            ),
+            top_right: round_corner(
+                self.right == right,
+                self.top == position.top,
+                self.corner_radius.top_right,
+                corner_radius.top_right,
+            ),
+            bottom_left: round_corner(
+                self.left == position.left,
+                self.bottom == bottom,
+                self.corner_radius.bottom_left,
+                corner_radius.bottom_left,
+            ),
+            bottom_right: round_corner(
+                self.right == right,
+                self.bottom == bottom,
+                self.corner_radius.bottom_right,
+                corner_radius.bottom_right,
+            ),
+        };
+
+        Self { corner_radius, ..self }
+    }
 }
 
 impl From<ScreenClip> for [f32; 4] {
@@ -323,18 +439,23 @@ impl ArrayType for CornerRadius {
 
     const ELEMENT_COUNT: usize = 4;
 
-    fn get_array_fields(&'static self) -> [(String, &'static Self::Element); Self::ELEMENT_COUNT] {
-        [
-            ("top left".to_owned(), &self.top_left),
-            ("top right".to_owned(), &self.top_right),
-            ("bottom right".to_owned(), &self.bottom_right),
-            ("bottom left".to_owned(), &self.bottom_left),
-        ]
+    fn get_field_names(&self) -> [&'static str; Self::ELEMENT_COUNT] {
+        ["top left", "top right", "bottom right", "bottom left"]
     }
 
     fn get_inner(&self) -> [Self::Element; Self::ELEMENT_COUNT] {
         [self.top_left, self.top_right, self.bottom_right, self.bottom_left]
     }
+
+    fn set_element(&mut self, index: usize, value: Self::Element) {
+        match index {
+            0 => self.top_left = value,
+            1 => self.top_right = value,
+            2 => self.bottom_right = value,
+            3 => self.bottom_left = value,
+            _ => panic!("index out of bounds"),
+        }
+    }
 }
 
 impl std::ops::Mul<f32> for CornerRadius {
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::loaders::GameFileLoader;
+
+const MESSAGE_TABLE_PATH: &str = "data\\msgstringtable.txt";
+pub const DEFAULT_LANGUAGE: &str = "english";
+/// Languages that are written from right to left. The interface is mirrored
+/// when one of them is selected.
+const RIGHT_TO_LEFT_LANGUAGES: [&str; 4] = ["arabic", "hebrew", "persian", "urdu"];
+
+/// Translation of a single interface string.
+#[derive(Deserialize)]
+enum Translation {
+    /// Use this text.
+    Text(String),
+    /// Use the line with this index from the message table of the client.
+    Message(usize),
+}
+
+/// Lookup for user facing interface strings. Translation files are stored in
+/// `client/translations/<language>.ron` and map the English text to either a
+/// translated text or an entry in the `msgstringtable.txt` of the client.
+#[derive(Default)]
+pub struct Localization {
+    messages: Vec<String>,
+    translations: HashMap<String, Translation>,
+    right_to_left: bool,
+}
+
+impl Localization {
+    pub fn new(game_file_loader: &mut GameFileLoader, language: &str) -> Self {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("load localization for {MAGENTA}{language}{NONE}"));
+
+        let messages = game_file_loader
+            .get(MESSAGE_TABLE_PATH)
+            .map(|data| parse_message_table(&data))
+            .unwrap_or_else(|_error| {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to load message table: {_error}");
+
+                Vec::new()
+            });
+
+        let translations = match language == DEFAULT_LANGUAGE {
+            true => HashMap::new(),
+            false => std::fs::read_to_string(translation_file(language))
+                .ok()
+                .and_then(|data| ron::from_str(&data).ok())
+                .unwrap_or_else(|| {
+                    #[cfg(feature = "debug")]
+                    print_debug!("[{RED}error{NONE}] failed to load translations for {MAGENTA}{language}{NONE}");
+
+                    HashMap::new()
+                }),
+        };
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        let right_to_left = is_right_to_left(language);
+
+        Self {
+            messages,
+            translations,
+            right_to_left,
+        }
+    }
+
+    /// Get the translation of an interface string. Strings without a
+    /// translation are returned unchanged.
+    pub fn get<'a>(&'a self, text: &'a str) -> &'a str {
+        match self.translations.get(text) {
+            Some(Translation::Text(translation)) => translation,
+            Some(Translation::Message(index)) => self.get_message(*index).unwrap_or(text),
+            None => text,
+        }
+    }
+
+    /// Whether the interface should be laid out from right to left.
+    pub fn is_right_to_left(&self) -> bool {
+        self.right_to_left
+    }
+
+    /// Get a line from the message table of the client.
+    pub fn get_message(&self, index: usize) -> Option<&str> {
+        self.messages.get(index).map(String::as_str)
+    }
+}
+
+fn is_right_to_left(language: &str) -> bool {
+    RIGHT_TO_LEFT_LANGUAGES.contains(&language.to_lowercase().as_str())
+}
+
+pub fn translation_file(language: &str) -> String {
+    format!("client/translations/{language}.ron")
+}
+
+/// Every message in the table is terminated by a `#`. Clients for non-Latin
+/// languages ship the table as UTF-8, older clients use a single byte
+/// encoding that we read the same way as the rest of the game files.
+fn parse_message_table(data: &[u8]) -> Vec<String> {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text.trim_start_matches('\u{feff}').to_owned(),
+        Err(_) => data.iter().map(|&byte| byte as char).collect(),
+    };
+
+    text.split('#')
+        .map(|message| message.trim_start_matches(['\r', '\n']).to_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_right_to_left, parse_message_table};
+
+    #[test]
+    fn split_messages() {
+        let messages = parse_message_table(b"Inventory#\r\nEquipment#\r\nSkills#\r\n");
+        assert_eq!(&messages[..3], ["Inventory", "Equipment", "Skills"]);
+    }
+
+    #[test]
+    fn utf8_messages() {
+        let messages = parse_message_table("\u{feff}인벤토리#\n장비#\n".as_bytes());
+        assert_eq!(&messages[..2], ["인벤토리", "장비"]);
+    }
+
+    #[test]
+    fn single_byte_messages() {
+        let messages = parse_message_table(&[0xC0, 0xAF, b'#']);
+        assert_eq!(messages[0], "\u{c0}\u{af}");
+    }
+
+    #[test]
+    fn right_to_left_languages() {
+        assert!(is_right_to_left("arabic"));
+        assert!(is_right_to_left("Hebrew"));
+        assert!(!is_right_to_left("english"));
+    }
+}
@@ -1,9 +1,14 @@
+mod animation;
+mod combat;
 mod event;
 mod layout;
+mod localization;
 mod provider;
 mod settings;
 mod state;
+mod streamer;
 mod theme;
+mod tooltip;
 #[macro_use]
 mod elements;
 pub mod builder;
@@ -13,24 +18,33 @@ mod windows;
 use std::cell::RefCell;
 use std::marker::{ConstParamTy, PhantomData};
 use std::rc::Rc;
+use std::sync::Arc;
 
+use bumpalo::Bump;
 use derive_new::new;
 use option_ext::OptionExt;
 use procedural::profile;
+use vulkano::image::view::ImageView;
 
+pub use self::animation::{Animation, Easing, Interpolate};
+use self::combat::COMBAT_OPACITY;
+pub use self::combat::{CombatTimer, CombatWindowMode, CombatWindows};
 pub use self::cursor::*;
 pub use self::elements::*;
 pub use self::event::*;
 pub use self::layout::*;
+pub use self::localization::Localization;
 pub use self::provider::StateProvider;
 pub use self::settings::InterfaceSettings;
 pub use self::state::{Remote, TrackedState, TrackedStateTake, ValueState};
-pub use self::theme::{GameTheme, InterfaceTheme};
-use self::theme::{Main, Menu, ThemeSelector, Themes};
+pub use self::streamer::StreamerMode;
+pub use self::theme::{GameTheme, InterfaceTheme, SpeechBubbleTheme};
+use self::theme::{Main, Menu, ThemeSelector, ThemeWatcher, Themes};
+use self::tooltip::TooltipManager;
 pub use self::windows::*;
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{Color, DeferredRenderer, InterfaceRenderer, Renderer};
+use crate::graphics::{Color, DeferredRenderer, InterfaceRenderer, Renderer, SpriteRenderer};
 use crate::input::{FocusState, Grabbed, MouseInputMode, UserEvent};
 use crate::loaders::{ActionLoader, FontLoader, GameFileLoader, SpriteLoader};
 use crate::network::{ClientTick, EntityId};
@@ -39,6 +53,26 @@ use crate::network::{ClientTick, EntityId};
 pub type Selector = Box<dyn Fn() -> bool>;
 pub type ColorSelector = Box<dyn Fn(&InterfaceTheme) -> Color>;
 pub type FontSizeSelector = Box<dyn Fn(&InterfaceTheme) -> f32>;
+pub type TooltipProvider = Box<dyn Fn() -> Option<String>>;
+
+/// Restore the opacity from the window cache, or fade or hide the window if
+/// it is affected by the combat.
+fn apply_combat_state(window: &mut Window, combat_windows: &CombatWindows, window_cache: &WindowCache, in_combat: bool) {
+    let affected = in_combat && combat_windows.affects(window.get_window_class());
+    let opacity = window
+        .get_window_class()
+        .map(|window_class| window_cache.get_opacity(window_class))
+        .unwrap_or(1.0);
+
+    window.set_opacity(opacity);
+
+    match affected && combat_windows.mode == CombatWindowMode::Faded {
+        true => window.fade_to(COMBAT_OPACITY),
+        false => window.fade_to(1.0),
+    }
+
+    window.set_hidden(affected && combat_windows.mode == CombatWindowMode::Hidden);
+}
 
 pub trait ElementEvent {
     fn trigger(&mut self) -> Vec<ClickAction>;
@@ -140,10 +174,15 @@ pub struct Interface {
     window_cache: WindowCache,
     interface_settings: InterfaceSettings,
     available_space: ScreenSize,
+    compact_layout: bool,
     themes: Themes,
+    theme_watcher: ThemeWatcher,
     dialog_handle: Option<DialogHandle>,
+    pending_confirmation: Option<UserEvent>,
     mouse_cursor: MouseCursor,
     mouse_cursor_hidden: bool,
+    tooltip_manager: TooltipManager,
+    in_combat: bool,
     post_update: PostUpdate<Self>,
 }
 
@@ -155,16 +194,22 @@ impl Interface {
         available_space: ScreenSize,
     ) -> Self {
         let window_cache = WindowCache::new();
-        let interface_settings = InterfaceSettings::new();
+        let mut interface_settings = InterfaceSettings::new();
+        interface_settings.localization = Localization::new(game_file_loader, interface_settings.language.get_language());
         let themes = Themes {
             theme_selector: ThemeSelector,
             menu: InterfaceTheme::new::<Menu>(interface_settings.menu_theme.get_file()),
             main: InterfaceTheme::new::<Main>(interface_settings.main_theme.get_file()),
             game: GameTheme::new(interface_settings.game_theme.get_file()),
         };
+        let theme_watcher = ThemeWatcher::new(Self::theme_files(&interface_settings));
+        let compact_layout = CompactLayout::is_required(available_space);
         let dialog_handle = None;
+        let pending_confirmation = None;
         let mouse_cursor = MouseCursor::new(game_file_loader, sprite_loader, action_loader);
         let mouse_cursor_hidden = false;
+        let tooltip_manager = TooltipManager::new();
+        let in_combat = false;
         // NOTE: We need to initially clear the interface buffer
         let post_update = PostUpdate::new().with_render();
 
@@ -173,10 +218,15 @@ impl Interface {
             window_cache,
             interface_settings,
             available_space,
+            compact_layout,
             themes,
+            theme_watcher,
             dialog_handle,
+            pending_confirmation,
             mouse_cursor,
             mouse_cursor_hidden,
+            tooltip_manager,
+            in_combat,
             post_update,
         }
     }
@@ -189,28 +239,164 @@ impl Interface {
         }
     }
 
+    pub fn set_language(&mut self, game_file_loader: &mut GameFileLoader, language: String) {
+        self.interface_settings.localization = Localization::new(game_file_loader, &language);
+        self.interface_settings.language.set_language(language);
+        self.post_update.resolve();
+    }
+
+    pub fn set_animations(&mut self, enabled: bool) {
+        self.interface_settings.animations = enabled;
+        self.post_update.render();
+    }
+
+    pub fn get_animations(&self) -> bool {
+        self.interface_settings.animations
+    }
+
+    pub fn get_streamer_mode(&self) -> &StreamerMode {
+        &self.interface_settings.streamer_mode
+    }
+
+    pub fn set_streamer_mode(&mut self, enabled: bool) {
+        self.interface_settings.streamer_mode.set_enabled(enabled);
+        // NOTE: Hidden text can have a different size than the original.
+        self.post_update.resolve();
+    }
+
+    pub fn get_combat_window_mode(&self) -> CombatWindowMode {
+        self.interface_settings.combat_windows.mode
+    }
+
+    pub fn set_combat_window_mode(&mut self, mode: CombatWindowMode) {
+        self.interface_settings.combat_windows.mode = mode;
+        self.apply_combat_state();
+    }
+
+    /// Fade or hide the windows that get in the way during fights, depending on
+    /// the combat window mode.
+    pub fn set_in_combat(&mut self, in_combat: bool) {
+        self.in_combat = in_combat;
+        self.apply_combat_state();
+    }
+
+    fn apply_combat_state(&mut self) {
+        self.windows.iter_mut().for_each(|(window, _)| {
+            apply_combat_state(
+                window,
+                &self.interface_settings.combat_windows,
+                &self.window_cache,
+                self.in_combat,
+            )
+        });
+        self.post_update.render();
+    }
+
     pub fn get_game_theme(&self) -> &GameTheme {
         &self.themes.game
     }
 
+    /// Save the window cache and the interface settings.
     #[profile]
-    pub fn save_theme(&self, kind: ThemeKind) {
+    pub fn save_state(&self) {
+        self.window_cache.save();
+        self.interface_settings.save();
+    }
+
+    fn theme_files(interface_settings: &InterfaceSettings) -> [(ThemeKind, &str); 3] {
+        [
+            (ThemeKind::Menu, interface_settings.menu_theme.get_file()),
+            (ThemeKind::Main, interface_settings.main_theme.get_file()),
+            (ThemeKind::Game, interface_settings.game_theme.get_file()),
+        ]
+    }
+
+    #[profile]
+    pub fn save_theme(&mut self, kind: ThemeKind) {
+        let theme_file = match kind {
+            ThemeKind::Menu => self.interface_settings.menu_theme.get_file(),
+            ThemeKind::Main => self.interface_settings.main_theme.get_file(),
+            ThemeKind::Game => self.interface_settings.game_theme.get_file(),
+        };
+
         match kind {
-            ThemeKind::Menu => self.themes.menu.save(self.interface_settings.menu_theme.get_file()),
-            ThemeKind::Main => self.themes.main.save(self.interface_settings.main_theme.get_file()),
-            ThemeKind::Game => self.themes.game.save(self.interface_settings.game_theme.get_file()),
+            ThemeKind::Menu => self.themes.menu.save(theme_file),
+            ThemeKind::Main => self.themes.main.save(theme_file),
+            ThemeKind::Game => self.themes.game.save(theme_file),
         }
+
+        self.theme_watcher.mark_current(kind, theme_file);
     }
 
     #[profile]
-    pub fn reload_theme(&mut self, kind: ThemeKind) {
+    pub fn reload_theme(&mut self, focus_state: &mut FocusState, kind: ThemeKind) {
+        let theme_file = match kind {
+            ThemeKind::Menu => self.interface_settings.menu_theme.get_file(),
+            ThemeKind::Main => self.interface_settings.main_theme.get_file(),
+            ThemeKind::Game => self.interface_settings.game_theme.get_file(),
+        };
+
         match kind {
-            ThemeKind::Menu => self.themes.menu.reload::<Menu>(self.interface_settings.menu_theme.get_file()),
-            ThemeKind::Main => self.themes.main.reload::<Main>(self.interface_settings.main_theme.get_file()),
-            ThemeKind::Game => self.themes.game.reload(self.interface_settings.game_theme.get_file()),
+            ThemeKind::Menu => self.themes.menu.reload::<Menu>(theme_file),
+            ThemeKind::Main => self.themes.main.reload::<Main>(theme_file),
+            ThemeKind::Game => self.themes.game.reload(theme_file),
         }
 
+        self.theme_watcher.mark_current(kind, theme_file);
         self.post_update.resolve();
+        self.reopen_theme_editor_window(focus_state);
+    }
+
+    /// Reload all themes that were changed outside of the client.
+    pub fn reload_changed_themes(&mut self, focus_state: &mut FocusState, delta_time: f64) {
+        let changed_themes = self
+            .theme_watcher
+            .changed_themes(delta_time, Self::theme_files(&self.interface_settings));
+
+        for kind in changed_themes {
+            #[cfg(feature = "debug")]
+            print_debug!("theme file of kind {MAGENTA}{kind:?}{NONE} changed, reloading");
+
+            let theme_file = match kind {
+                ThemeKind::Menu => self.interface_settings.menu_theme.get_file(),
+                ThemeKind::Main => self.interface_settings.main_theme.get_file(),
+                ThemeKind::Game => self.interface_settings.game_theme.get_file(),
+            };
+
+            let result = match kind {
+                ThemeKind::Menu => self.themes.menu.reload_changed(theme_file),
+                ThemeKind::Main => self.themes.main.reload_changed(theme_file),
+                ThemeKind::Game => self.themes.game.reload_changed(theme_file),
+            };
+
+            // NOTE: The file is marked as current even if it is broken, so we only try
+            // again once it is changed again.
+            self.theme_watcher.mark_current(kind, theme_file);
+
+            match result {
+                Ok(()) => {
+                    self.post_update.resolve();
+                    self.reopen_theme_editor_window(focus_state);
+                }
+                Err(_error) => {
+                    #[cfg(feature = "debug")]
+                    print_debug!(
+                        "[{RED}error{NONE}] failed to reload theme {MAGENTA}{theme_file}{NONE}, keeping the current one: {_error}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// The theme editor is bound to the state of the theme it was opened
+    /// with, so it needs to be reopened after a theme was reloaded.
+    fn reopen_theme_editor_window(&mut self, focus_state: &mut FocusState) {
+        if let Some(window_class) = self.themes.window_class().map(str::to_owned)
+            && self.window_exists(Some(&window_class))
+        {
+            self.close_window_with_class(focus_state, &window_class);
+            self.open_theme_editor_window(focus_state);
+        }
     }
 
     pub fn schedule_render(&mut self) {
@@ -251,7 +437,13 @@ impl Interface {
     }
 
     #[profile("update user interface")]
-    pub fn update(&mut self, font_loader: Rc<RefCell<FontLoader>>, focus_state: &mut FocusState, client_tick: ClientTick) -> (bool, bool) {
+    pub fn update(
+        &mut self,
+        font_loader: Rc<RefCell<FontLoader>>,
+        frame_arena: &Bump,
+        focus_state: &mut FocusState,
+        client_tick: ClientTick,
+    ) -> (bool, bool) {
         self.mouse_cursor.update(client_tick);
 
         for (window, post_update) in &mut self.windows {
@@ -272,15 +464,30 @@ impl Interface {
 
                 profile_block!("resolve window");
 
-                let (_position, previous_size) = window.get_area();
+                let (previous_position, previous_size) = window.get_area();
                 let theme = match window.get_theme_kind() {
                     ThemeKind::Menu => &self.themes.menu,
                     ThemeKind::Main => &self.themes.main,
                     _ => panic!(),
                 };
 
-                let (window_class, new_position, new_size) =
-                    window.resolve(font_loader.clone(), &self.interface_settings, theme, self.available_space);
+                window.resolve(
+                    font_loader.clone(),
+                    frame_arena,
+                    &self.interface_settings,
+                    theme,
+                    self.available_space,
+                );
+
+                // NOTE: Core windows are pinned while the compact layout is active. Their
+                // compact placement is not stored in the window cache, so the normal layout
+                // can be restored later.
+                let pinned = self.compact_layout && CompactLayout::is_core_window(window.get_window_class());
+                if pinned {
+                    CompactLayout::place(window, &self.interface_settings, self.available_space);
+                }
+
+                let (new_position, new_size) = window.get_area();
 
                 // should only ever be the last window
                 if let Some(focused_index) = focus_state.focused_window()
@@ -289,13 +496,13 @@ impl Interface {
                     restore_focus = true;
                 }
 
-                if let Some(window_class) = window_class {
+                if !pinned && let Some(window_class) = window.get_window_class() {
                     self.window_cache.register_window(window_class, new_position, new_size);
                 }
 
-                // NOTE: If the window got smaller, we need to re-render the entire interface.
-                // If it got bigger, we can just draw over the previous frame.
-                match previous_size.width > new_size.width || previous_size.height > new_size.height {
+                // NOTE: If the window got smaller or moved, we need to re-render the entire
+                // interface. If it got bigger, we can just draw over the previous frame.
+                match previous_size.width > new_size.width || previous_size.height > new_size.height || previous_position != new_position {
                     true => self.post_update.render(),
                     false => post_update.render(),
                 }
@@ -325,8 +532,57 @@ impl Interface {
         (render_interface, render_window)
     }
 
+    /// Should be called every frame after handling the user input, so tooltips
+    /// appear once the hovered element has been hovered long enough.
+    pub fn update_tooltip(&mut self, hovered_element: Option<&ElementCell>, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) {
+        let changed = match mouse_mode {
+            MouseInputMode::None => {
+                // NOTE: The tooltip uses the theme of the window that the mouse is over.
+                let theme_kind = self
+                    .windows
+                    .iter()
+                    .rev()
+                    .map(|(window, _)| window)
+                    .find(|window| {
+                        let (position, size) = window.get_area();
+                        let offset = mouse_position - position;
+
+                        offset.width >= 0.0 && offset.height >= 0.0 && offset.width <= size.width && offset.height <= size.height
+                    })
+                    .map(|window| window.get_theme_kind())
+                    .unwrap_or_default();
+                let theme = match theme_kind {
+                    ThemeKind::Menu => &self.themes.menu,
+                    _ => &self.themes.main,
+                };
+
+                self.tooltip_manager.update(
+                    hovered_element,
+                    mouse_position,
+                    theme_kind,
+                    theme,
+                    self.interface_settings.tooltip_delay.get(),
+                )
+            }
+            _ => self.tooltip_manager.hide(),
+        };
+
+        if changed {
+            self.post_update.render();
+        }
+    }
+
     pub fn update_window_size(&mut self, screen_size: ScreenSize) {
+        let compact_layout = CompactLayout::is_required(screen_size);
+
+        if self.compact_layout && !compact_layout {
+            self.windows.iter_mut().for_each(|(window, _)| {
+                CompactLayout::restore(window, &self.window_cache, &self.interface_settings, screen_size);
+            });
+        }
+
         self.available_space = screen_size;
+        self.compact_layout = compact_layout;
         self.post_update.resolve();
     }
 
@@ -353,18 +609,48 @@ impl Interface {
         new_window_index
     }
 
+    fn handle_click_change_events(
+        post_update: &mut PostUpdate<Self>,
+        window_post_update: &mut PostUpdate<PerWindow>,
+        actions: &[ClickAction],
+    ) {
+        actions.iter().for_each(|action| {
+            if let ClickAction::ChangeEvent(change_event) = action {
+                Self::handle_change_event(post_update, window_post_update, *change_event);
+            }
+        });
+    }
+
     #[profile]
     pub fn left_click_element(&mut self, hovered_element: &ElementCell, window_index: usize) -> Vec<ClickAction> {
         let (_, post_update) = &mut self.windows[window_index];
         let mut resolve = false;
 
-        let action = hovered_element.borrow_mut().left_click(&mut resolve); // TODO: add same change_event check as for input character ?
+        let actions = hovered_element.borrow_mut().left_click(&mut resolve);
+
+        if resolve {
+            post_update.resolve();
+        }
+
+        Self::handle_click_change_events(&mut self.post_update, post_update, &actions);
+
+        actions
+    }
+
+    #[profile]
+    pub fn double_click_element(&mut self, hovered_element: &ElementCell, window_index: usize) -> Vec<ClickAction> {
+        let (_, post_update) = &mut self.windows[window_index];
+        let mut resolve = false;
+
+        let actions = hovered_element.borrow_mut().double_click(&mut resolve);
 
         if resolve {
             post_update.resolve();
         }
 
-        action
+        Self::handle_click_change_events(&mut self.post_update, post_update, &actions);
+
+        actions
     }
 
     #[profile]
@@ -372,22 +658,23 @@ impl Interface {
         let (_, post_update) = &mut self.windows[window_index];
         let mut resolve = false;
 
-        let action = hovered_element.borrow_mut().right_click(&mut resolve); // TODO: add same change_event check as for input character ?
+        let actions = hovered_element.borrow_mut().right_click(&mut resolve);
 
         if resolve {
             post_update.resolve();
         }
 
-        action
+        Self::handle_click_change_events(&mut self.post_update, post_update, &actions);
+
+        actions
     }
 
     #[profile]
-    pub fn drag_element(&mut self, element: &ElementCell, _window_index: usize, mouse_delta: ScreenPosition) {
-        //let (_window, post_update) = &mut self.windows[window_index];
+    pub fn drag_element(&mut self, element: &ElementCell, window_index: usize, mouse_delta: ScreenPosition) {
+        let (_, post_update) = &mut self.windows[window_index];
 
         if let Some(change_event) = element.borrow_mut().drag(mouse_delta) {
-            // TODO: Use the window post_update here (?)
-            Self::handle_change_event(&mut self.post_update, &mut PostUpdate::new(), change_event);
+            Self::handle_change_event(&mut self.post_update, post_update, change_event);
         }
     }
 
@@ -400,6 +687,38 @@ impl Interface {
         }
     }
 
+    #[profile]
+    pub fn scroll_page_element(&mut self, element: &ElementCell, window_index: usize, upwards: bool) {
+        let (_, post_update) = &mut self.windows[window_index];
+
+        if let Some(change_event) = element.borrow_mut().scroll_page(upwards) {
+            Self::handle_change_event(&mut self.post_update, post_update, change_event);
+        }
+    }
+
+    /// Scroll all scrollable parents of an element so that it is visible.
+    #[profile]
+    pub fn scroll_to_element(&mut self, element: &ElementCell, window_index: usize) {
+        let (_, post_update) = &mut self.windows[window_index];
+
+        let (parent_element, top, height) = {
+            let element = element.borrow();
+            let state = element.get_state();
+            (
+                state.parent_element.clone(),
+                state.cached_position.top,
+                state.cached_size.height,
+            )
+        };
+
+        if let Some(change_event) = parent_element
+            .and_then(|weak_pointer| weak_pointer.upgrade())
+            .and_then(|element| element.borrow_mut().scroll_into_view(top, height))
+        {
+            Self::handle_change_event(&mut self.post_update, post_update, change_event);
+        }
+    }
+
     #[profile]
     pub fn input_character_element(&mut self, element: &ElementCell, window_index: usize, character: char) -> Vec<ClickAction> {
         let (_, post_update) = &mut self.windows[window_index];
@@ -415,8 +734,42 @@ impl Interface {
         propagated_actions
     }
 
+    pub fn edit_text_element(&mut self, element: &ElementCell, window_index: usize, edit: TextEdit) {
+        let (_, post_update) = &mut self.windows[window_index];
+
+        for action in element.borrow_mut().edit_text(edit) {
+            if let ClickAction::ChangeEvent(change_event) = action {
+                Self::handle_change_event(&mut self.post_update, post_update, change_event);
+            }
+        }
+    }
+
+    pub fn input_composition_element(&mut self, element: &ElementCell, window_index: usize, composition: Option<String>) {
+        let (_, post_update) = &mut self.windows[window_index];
+
+        if let Some(change_event) = element.borrow_mut().input_composition(composition) {
+            Self::handle_change_event(&mut self.post_update, post_update, change_event);
+        }
+    }
+
+    pub fn set_active_tab(&mut self, window_index: usize, active_tab: usize) {
+        if let Some(window_class) = self.windows[window_index].0.get_window_class() {
+            self.window_cache.update_active_tab(window_class, active_tab);
+        }
+    }
+
+    /// Pinned windows can't be moved or resized by the player, either because
+    /// they are placed by the compact layout or because they are locked.
+    fn is_window_pinned(&self, window: &Window) -> bool {
+        window.is_locked() || (self.compact_layout && CompactLayout::is_core_window(window.get_window_class()))
+    }
+
     #[profile]
     pub fn move_window(&mut self, window_index: usize, offset: ScreenPosition) {
+        if self.is_window_pinned(&self.windows[window_index].0) {
+            return;
+        }
+
         if let Some((window_class, position)) = self.windows[window_index].0.offset(self.available_space, offset) {
             self.window_cache.update_position(window_class, position);
         }
@@ -424,8 +777,31 @@ impl Interface {
         self.post_update.render();
     }
 
+    /// Resize grip of the window under the mouse. The grips cover the border
+    /// of the window, so they don't overlap with any elements.
+    pub fn hovered_resize_grip(&self, window_index: usize, mouse_position: ScreenPosition) -> Option<ResizeGrip> {
+        let (window, _) = &self.windows[window_index];
+
+        if self.is_window_pinned(window) {
+            return None;
+        }
+
+        let theme = match window.get_theme_kind() {
+            ThemeKind::Menu => &self.themes.menu,
+            ThemeKind::Main => &self.themes.main,
+            _ => panic!(),
+        };
+        let grip_size = theme.window.border_size.get() * self.interface_settings.scaling.get();
+
+        window.hovered_resize_grip(mouse_position, grip_size)
+    }
+
     #[profile]
-    pub fn resize_window(&mut self, window_index: usize, growth: ScreenSize) {
+    pub fn resize_window(&mut self, window_index: usize, grip: ResizeGrip, mouse_delta: ScreenSize) {
+        if self.is_window_pinned(&self.windows[window_index].0) {
+            return;
+        }
+
         let (window, post_update) = &mut self.windows[window_index];
 
         let theme = match window.get_theme_kind() {
@@ -433,18 +809,20 @@ impl Interface {
             ThemeKind::Main => &self.themes.main,
             _ => panic!(),
         };
-        let (_position, previous_size) = window.get_area();
+        let (previous_position, previous_size) = window.get_area();
 
-        let (window_class, new_size) = window.resize(&self.interface_settings, theme, self.available_space, growth);
+        let (window_class, new_position, new_size) =
+            window.resize(&self.interface_settings, theme, self.available_space, grip, mouse_delta);
 
         if previous_size != new_size {
             if let Some(window_class) = window_class {
                 self.window_cache.update_size(window_class, new_size);
+                self.window_cache.update_position(window_class, new_position);
             }
 
             post_update.resolve();
 
-            if previous_size.width > new_size.width || previous_size.height > new_size.height {
+            if previous_size.width > new_size.width || previous_size.height > new_size.height || previous_position != new_position {
                 self.post_update.render();
             }
         }
@@ -489,6 +867,7 @@ impl Interface {
     ) {
         let hovered_element = hovered_element.map(|element| unsafe { &*element.as_ptr() });
         let focused_element = focused_element.map(|element| unsafe { &*element.as_ptr() });
+        let mut rendered_window = false;
 
         for (window, post_update) in &mut self.windows {
             if post_update.take_render() || self.post_update.needs_render() {
@@ -511,9 +890,23 @@ impl Interface {
                     focused_element,
                     mouse_mode,
                 );
+
+                rendered_window = true;
             }
         }
 
+        // NOTE: Windows are rendered on top of the previous frame, so the tooltip has
+        // to be rendered again whenever any window changed.
+        if rendered_window || self.post_update.needs_render() {
+            let theme = match self.tooltip_manager.get_theme_kind() {
+                ThemeKind::Menu => &self.themes.menu,
+                _ => &self.themes.main,
+            };
+
+            self.tooltip_manager
+                .render(render_target, renderer, &self.interface_settings, theme, self.available_space);
+        }
+
         self.post_update.take_render();
     }
 
@@ -523,6 +916,7 @@ impl Interface {
         render_target: &mut <DeferredRenderer as Renderer>::Target,
         renderer: &DeferredRenderer,
         text: &str,
+        emblem: Option<Arc<ImageView>>,
         mouse_position: ScreenPosition,
     ) {
         let offset = ScreenPosition {
@@ -530,6 +924,19 @@ impl Interface {
             top: 20.0,
         };
 
+        // The guild emblem is placed in front of the name.
+        if let Some(emblem) = emblem {
+            renderer.render_sprite(
+                render_target,
+                emblem,
+                mouse_position + offset - ScreenSize { width: 18.0, height: 2.0 },
+                ScreenSize::uniform(16.0),
+                ScreenClip::unbounded(),
+                Color::monochrome_u8(255),
+                true,
+            );
+        }
+
         renderer.render_text(
             render_target,
             text,
@@ -594,7 +1001,13 @@ impl Interface {
         }
     }
 
-    fn open_new_window(&mut self, focus_state: &mut FocusState, window: Window) {
+    fn open_new_window(&mut self, focus_state: &mut FocusState, mut window: Window) {
+        apply_combat_state(
+            &mut window,
+            &self.interface_settings.combat_windows,
+            &self.window_cache,
+            self.in_combat,
+        );
         self.windows.push((window, PostUpdate::new().with_resolve()));
         focus_state.set_focused_window(self.windows.len() - 1);
     }
@@ -618,13 +1031,94 @@ impl Interface {
         let entry = &mut self.windows[window_index];
         entry.0.open_popup(element, position_tracker, size_tracker);
         entry.1.resolve();
+
+        // NOTE: The popup may extend past the borders of its window, so everything
+        // has to be rendered.
+        self.post_update.render();
     }
 
     #[profile]
     pub fn close_popup(&mut self, window_index: usize) {
         let entry = &mut self.windows[window_index];
         entry.0.close_popup();
-        entry.1.render();
+        self.post_update.render();
+    }
+
+    /// Open a context menu at the given position, replacing any context menu
+    /// that is already open.
+    #[profile]
+    pub fn open_context_menu(&mut self, focus_state: &mut FocusState, position: ScreenPosition, entries: Vec<ContextMenuEntry>) {
+        self.close_context_menu(focus_state);
+
+        if !entries.is_empty() {
+            self.open_window(focus_state, &ContextMenu::new(position, entries));
+        }
+    }
+
+    pub fn close_context_menu(&mut self, focus_state: &mut FocusState) {
+        if self.window_exists(Some(ContextMenu::WINDOW_CLASS)) {
+            self.close_window_with_class(focus_state, ContextMenu::WINDOW_CLASS);
+        }
+    }
+
+    /// Open the context menu with the options of a window class, like its
+    /// opacity.
+    pub fn open_window_options(&mut self, focus_state: &mut FocusState, position: ScreenPosition, window_class: String) {
+        const OPACITY_STEPS: [f32; 4] = [1.0, 0.75, 0.5, 0.25];
+
+        let current_opacity = self.window_cache.get_opacity(&window_class);
+        let locked = self.window_cache.is_locked(&window_class);
+
+        let mut entries: Vec<ContextMenuEntry> = OPACITY_STEPS
+            .into_iter()
+            .filter(|opacity| *opacity != current_opacity)
+            .map(|opacity| {
+                ContextMenuEntry::new(
+                    format!("Opacity {}%", (opacity * 100.0) as usize),
+                    UserEvent::SetWindowOpacity {
+                        window_class: window_class.clone(),
+                        opacity,
+                    },
+                )
+            })
+            .collect();
+
+        let lock_text = match locked {
+            true => "Unlock",
+            false => "Lock",
+        };
+        entries.push(ContextMenuEntry::new(lock_text.to_owned(), UserEvent::SetWindowLocked {
+            window_class,
+            locked: !locked,
+        }));
+
+        self.open_context_menu(focus_state, position, entries);
+    }
+
+    pub fn set_window_opacity(&mut self, window_class: &str, opacity: f32) {
+        self.window_cache.update_opacity(window_class, opacity);
+
+        self.windows
+            .iter_mut()
+            .filter(|(window, _)| window.get_window_class() == Some(window_class))
+            .for_each(|(window, _)| window.set_opacity(opacity));
+
+        self.post_update.render();
+    }
+
+    pub fn set_window_locked(&mut self, window_class: &str, locked: bool) {
+        self.window_cache.update_locked(window_class, locked);
+
+        self.windows
+            .iter_mut()
+            .filter(|(window, _)| window.get_window_class() == Some(window_class))
+            .for_each(|(window, _)| window.set_locked(locked));
+    }
+
+    pub fn context_menu_index(&self) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|(window, _)| window.get_window_class() == Some(ContextMenu::WINDOW_CLASS))
     }
 
     #[profile]
@@ -681,6 +1175,83 @@ impl Interface {
         }
     }
 
+    /// Ask the player to confirm an event before it is executed. A confirmation
+    /// that is still pending is cancelled.
+    pub fn open_confirmation_dialog(&mut self, focus_state: &mut FocusState, text: String, event: UserEvent) {
+        if self.window_exists(Some(ConfirmationDialog::WINDOW_CLASS)) {
+            self.close_window_with_class(focus_state, ConfirmationDialog::WINDOW_CLASS);
+        }
+
+        self.pending_confirmation = Some(event);
+        self.open_window(focus_state, &ConfirmationDialog::new(text));
+    }
+
+    /// Close the confirmation dialog and return the pending event if it was
+    /// confirmed.
+    pub fn resolve_confirmation(&mut self, focus_state: &mut FocusState, confirmed: bool) -> Option<UserEvent> {
+        let event = self.pending_confirmation.take();
+
+        if self.window_exists(Some(ConfirmationDialog::WINDOW_CLASS)) {
+            self.close_window_with_class(focus_state, ConfirmationDialog::WINDOW_CLASS);
+        }
+
+        event.filter(|_| confirmed)
+    }
+
+    pub fn is_confirmation_pending(&self) -> bool {
+        self.pending_confirmation.is_some()
+    }
+
+    pub fn open_layout_profiles_window(&mut self, focus_state: &mut FocusState) {
+        let profile_names = self.window_cache.get_profile_names();
+        self.open_window(focus_state, &LayoutProfilesWindow::new(profile_names));
+    }
+
+    /// Reopen the layout profiles window so it shows the current list of
+    /// profiles.
+    fn refresh_layout_profiles_window(&mut self, focus_state: &mut FocusState) {
+        if self.window_exists(Some(LayoutProfilesWindow::WINDOW_CLASS)) {
+            self.close_window_with_class(focus_state, LayoutProfilesWindow::WINDOW_CLASS);
+            self.open_layout_profiles_window(focus_state);
+        }
+    }
+
+    pub fn save_layout_profile(&mut self, focus_state: &mut FocusState, name: String) {
+        self.window_cache.save_profile(name);
+        self.refresh_layout_profiles_window(focus_state);
+    }
+
+    pub fn delete_layout_profile(&mut self, focus_state: &mut FocusState, name: &str) {
+        self.window_cache.delete_profile(name);
+        self.refresh_layout_profiles_window(focus_state);
+    }
+
+    pub fn export_layout_profile(&self, name: &str) -> Result<String, String> {
+        self.window_cache.export_profile(name)
+    }
+
+    pub fn import_layout_profile(&mut self, focus_state: &mut FocusState, path: &str) -> Result<String, String> {
+        let name = self.window_cache.import_profile(path)?;
+        self.refresh_layout_profiles_window(focus_state);
+        Ok(name)
+    }
+
+    /// Switch to a layout profile and move all open windows accordingly.
+    pub fn load_layout_profile(&mut self, name: &str) -> Result<(), String> {
+        self.window_cache.load_profile(name)?;
+
+        for (window, _) in &mut self.windows {
+            // NOTE: Core windows stay pinned while the compact layout is active. They are
+            // moved to the new layout once it is deactivated.
+            if !(self.compact_layout && CompactLayout::is_core_window(window.get_window_class())) {
+                window.restore_state(&self.window_cache, &self.interface_settings, self.available_space);
+            }
+        }
+
+        self.post_update.resolve();
+        Ok(())
+    }
+
     pub fn handle_result<T>(&mut self, focus_state: &mut FocusState, result: Result<T, String>) {
         if let Err(message) = result {
             self.open_window(focus_state, &ErrorWindow::new(message));
@@ -688,8 +1259,7 @@ impl Interface {
     }
 
     #[profile]
-    #[cfg(feature = "debug")]
-    pub fn open_theme_viewer_window(&mut self, focus_state: &mut FocusState) {
+    pub fn open_theme_editor_window(&mut self, focus_state: &mut FocusState) {
         if !self.window_exists(self.themes.window_class()) {
             let window = self
                 .themes
@@ -704,6 +1274,12 @@ impl Interface {
         let (window, ..) = self.windows.remove(window_index);
         self.post_update.render();
 
+        // NOTE: Closing the confirmation dialog in any other way than resolving it
+        // cancels the pending event.
+        if window.get_window_class() == Some(ConfirmationDialog::WINDOW_CLASS) {
+            self.pending_confirmation = None;
+        }
+
         // drop window in another thread to avoid frame drops when deallocation a large
         // amount of elements
         std::thread::spawn(move || drop(window));
@@ -742,7 +1318,7 @@ impl Interface {
             if self.windows[index]
                 .0
                 .get_window_class()
-                .map(|class| class != "theme_viewer" && class != "profiler" && class != "network") // HACK: don't hardcode
+                .map(|class| class != "theme_editor" && class != "profiler" && class != "network") // HACK: don't hardcode
                 .unwrap_or(true)
             {
                 self.close_window(focus_state, index);
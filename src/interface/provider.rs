@@ -1,5 +1,6 @@
 use derive_new::new;
 
+use crate::audio::AudioSettings;
 use crate::graphics::GraphicsSettings;
 #[cfg(feature = "debug")]
 use crate::graphics::RenderSettings;
@@ -7,6 +8,7 @@ use crate::graphics::RenderSettings;
 #[derive(new)]
 pub struct StateProvider<'t> {
     pub graphics_settings: &'t GraphicsSettings,
+    pub audio_settings: &'t AudioSettings,
     #[cfg(feature = "debug")]
     pub render_settings: &'t RenderSettings,
 }
@@ -5,6 +5,7 @@ use walkdir::WalkDir;
 
 #[cfg(feature = "debug")]
 use crate::debug::*;
+use crate::interface::localization::DEFAULT_LANGUAGE;
 use crate::interface::*;
 
 #[derive(Serialize, Deserialize)]
@@ -55,6 +56,54 @@ impl<const KIND: ThemeKind> PrototypeElement for ThemeSelector<KIND> {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LanguageSelector(pub String);
+
+impl Default for LanguageSelector {
+    fn default() -> Self {
+        Self(DEFAULT_LANGUAGE.to_string())
+    }
+}
+
+impl LanguageSelector {
+    pub fn get_language(&self) -> &str {
+        &self.0
+    }
+
+    pub fn set_language(&mut self, language: String) {
+        self.0 = language;
+    }
+}
+
+impl PrototypeElement for LanguageSelector {
+    fn to_element(&self, display: String) -> ElementCell {
+        let state = TrackedState::new(self.0.clone());
+
+        let translations = WalkDir::new("client/translations/")
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|path| path.path().file_name()?.to_str()?.strip_suffix(".ron").map(str::to_owned));
+        let languages = std::iter::once(DEFAULT_LANGUAGE.to_owned())
+            .chain(translations)
+            .map(|language| (language.clone(), language))
+            .collect();
+
+        let elements = vec![
+            Text::default().with_text(display).with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(languages)
+                .with_selected(state.clone())
+                .with_event(Box::new(move || vec![ClickAction::Event(UserEvent::SetLanguage(state.get()))]))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ];
+
+        Container::new(elements).wrap()
+    }
+}
+
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct InterfaceSettings {
     #[name("Scaling")]
@@ -65,6 +114,34 @@ pub struct InterfaceSettings {
     pub menu_theme: ThemeSelector<{ ThemeKind::Menu }>,
     #[name("Game theme")]
     pub game_theme: ThemeSelector<{ ThemeKind::Game }>,
+    #[name("Language")]
+    #[serde(default)]
+    pub language: LanguageSelector,
+    #[name("Tooltip delay")]
+    #[serde(default = "default_tooltip_delay")]
+    pub tooltip_delay: MutableRange<f32, Render>,
+    #[hidden_element]
+    #[serde(default = "default_animations")]
+    pub animations: bool,
+    #[hidden_element]
+    #[serde(default)]
+    pub streamer_mode: StreamerMode,
+    #[hidden_element]
+    #[serde(default)]
+    pub combat_windows: CombatWindows,
+    #[hidden_element]
+    #[serde(skip)]
+    pub localization: Localization,
+}
+
+/// Time in seconds that an element needs to be hovered before its tooltip is
+/// shown.
+fn default_tooltip_delay() -> MutableRange<f32, Render> {
+    MutableRange::new(0.5, 0.0, 3.0)
+}
+
+fn default_animations() -> bool {
+    true
 }
 
 impl Default for InterfaceSettings {
@@ -73,12 +150,24 @@ impl Default for InterfaceSettings {
         let main_theme = ThemeSelector("client/themes/main.ron".to_string());
         let menu_theme = ThemeSelector("client/themes/menu.ron".to_string());
         let game_theme = ThemeSelector("client/themes/game.ron".to_string());
+        let language = LanguageSelector::default();
+        let tooltip_delay = default_tooltip_delay();
+        let animations = default_animations();
+        let streamer_mode = StreamerMode::default();
+        let combat_windows = CombatWindows::default();
+        let localization = Localization::default();
 
         Self {
             scaling,
             main_theme,
             menu_theme,
             game_theme,
+            language,
+            tooltip_delay,
+            animations,
+            streamer_mode,
+            combat_windows,
+            localization,
         }
     }
 }
@@ -2,6 +2,9 @@ use std::cell::{Ref, RefCell};
 use std::ops::Not;
 use std::rc::Rc;
 
+use ragnarok_bytes::{ByteStream, ConversionResult, FromBytes, ToBytes};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use super::{ClickAction, StateProvider};
 
 /// The state of a value borrowed by [`borrow_mut`](TrackedState::with_mut).
@@ -194,6 +197,46 @@ impl<VALUE> Clone for TrackedState<VALUE> {
     }
 }
 
+// NOTE: Values that can be edited from the interface, like the ones of themes,
+// are stored in a tracked state, so they are serialized as their inner value.
+impl<VALUE> Serialize for TrackedState<VALUE>
+where
+    VALUE: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<'de, VALUE> Deserialize<'de> for TrackedState<VALUE>
+where
+    VALUE: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VALUE::deserialize(deserializer).map(TrackedState::new)
+    }
+}
+
+// NOTE: Map resources with fields that can be edited from debug windows store
+// them in a tracked state, so they are converted as their inner value.
+impl<VALUE> FromBytes for TrackedState<VALUE>
+where
+    VALUE: FromBytes,
+{
+    fn from_bytes<META>(byte_stream: &mut ByteStream<META>) -> ConversionResult<Self> {
+        VALUE::from_bytes(byte_stream).map(TrackedState::new)
+    }
+}
+
+impl<VALUE> ToBytes for TrackedState<VALUE>
+where
+    VALUE: ToBytes,
+{
+    fn to_bytes(&self) -> ConversionResult<Vec<u8>> {
+        self.borrow().to_bytes()
+    }
+}
+
 pub struct Remote<VALUE> {
     tracked_state: TrackedState<VALUE>,
     version: usize,
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+/// Hides sensitive information, like account and character names, while the
+/// game is streamed or recorded.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StreamerMode(bool);
+
+impl StreamerMode {
+    pub fn is_enabled(&self) -> bool {
+        self.0
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.0 = enabled;
+    }
+
+    /// Replace a name with an alias.
+    pub fn hide_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self.0 {
+            true => Cow::Owned(alias(name)),
+            false => Cow::Borrowed(name),
+        }
+    }
+
+    /// Replace the sender of a message in the `Name : message` format used for
+    /// player chat.
+    pub fn hide_sender<'a>(&self, message: &'a str) -> Cow<'a, str> {
+        match message.split_once(" : ") {
+            Some((name, text)) if self.0 && !name.is_empty() && !name.contains('^') => Cow::Owned(format!("{} : {text}", alias(name))),
+            _ => Cow::Borrowed(message),
+        }
+    }
+}
+
+/// The alias is derived from the name, so every player keeps the same alias
+/// and viewers can still follow a conversation.
+fn alias(name: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    format!("Player {:04X}", hasher.finish() as u16)
+}
+
+#[cfg(test)]
+mod test {
+    use super::StreamerMode;
+
+    #[test]
+    fn disabled() {
+        let streamer_mode = StreamerMode(false);
+        assert_eq!(streamer_mode.hide_name("Name"), "Name");
+        assert_eq!(streamer_mode.hide_sender("Name : hello"), "Name : hello");
+    }
+
+    #[test]
+    fn aliases_are_stable() {
+        let streamer_mode = StreamerMode(true);
+        let alias = streamer_mode.hide_name("Name");
+
+        assert_ne!(alias, "Name");
+        assert_eq!(alias, streamer_mode.hide_name("Name"));
+        assert_eq!(streamer_mode.hide_sender("Name : hello"), format!("{alias} : hello"));
+    }
+
+    #[test]
+    fn system_messages_are_unchanged() {
+        let streamer_mode = StreamerMode(true);
+        assert_eq!(
+            streamer_mode.hide_sender("^ff0000Server^000000 : restart"),
+            "^ff0000Server^000000 : restart"
+        );
+    }
+}
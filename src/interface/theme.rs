@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 use procedural::{dimension_bound, size_bound, PrototypeElement, PrototypeWindow};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
@@ -120,6 +122,8 @@ pub struct WindowTheme {
     pub gaps: MutableRange<ScreenSize, Resolve>,
     pub font_size: MutableRange<f32, Render>,
     pub title_height: DimensionBound,
+    pub resize_grip_color: Mutable<Color, Render>,
+    pub resize_grip_size: MutableRange<ScreenSize, Render>,
 }
 
 impl ThemeDefault<Menu> for WindowTheme {
@@ -147,6 +151,8 @@ impl ThemeDefault<Menu> for WindowTheme {
             ),
             font_size: MutableRange::new(20.0, 6.0, 30.0),
             title_height: dimension_bound!(30),
+            resize_grip_color: Mutable::new(Color::rgba_u8(150, 70, 255, 100)),
+            resize_grip_size: MutableRange::new(ScreenSize::uniform(12.0), ScreenSize::default(), ScreenSize::uniform(30.0)),
         }
     }
 }
@@ -176,6 +182,8 @@ impl ThemeDefault<Main> for WindowTheme {
             ),
             font_size: MutableRange::new(14.0, 6.0, 30.0),
             title_height: dimension_bound!(12),
+            resize_grip_color: Mutable::new(Color::monochrome_u8(80)),
+            resize_grip_size: MutableRange::new(ScreenSize::uniform(6.0), ScreenSize::default(), ScreenSize::uniform(30.0)),
         }
     }
 }
@@ -451,6 +459,278 @@ impl ThemeDefault<Main> for SliderTheme {
     }
 }
 
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct ScrollViewTheme {
+    pub bar_color: Mutable<Color, Render>,
+    pub hovered_bar_color: Mutable<Color, Render>,
+    pub bar_corner_radius: MutableRange<CornerRadius, Render>,
+    pub bar_width: MutableRange<f32, Resolve>,
+    pub minimum_bar_height: MutableRange<f32, Resolve>,
+}
+
+impl ThemeDefault<Menu> for ScrollViewTheme {
+    fn default() -> Self {
+        Self {
+            bar_color: Mutable::new(Color::rgba_u8(150, 130, 150, 150)),
+            hovered_bar_color: Mutable::new(Color::rgb_u8(100, 180, 180)),
+            bar_corner_radius: MutableRange::new(CornerRadius::uniform(4.0), CornerRadius::default(), CornerRadius::uniform(30.0)),
+            bar_width: MutableRange::new(5.0, 1.0, 20.0),
+            minimum_bar_height: MutableRange::new(20.0, 5.0, 100.0),
+        }
+    }
+}
+
+impl ThemeDefault<Main> for ScrollViewTheme {
+    fn default() -> Self {
+        Self {
+            bar_color: Mutable::new(Color::rgba_u8(150, 150, 150, 120)),
+            hovered_bar_color: Mutable::new(Color::rgb_u8(200, 200, 200)),
+            bar_corner_radius: MutableRange::new(CornerRadius::uniform(4.0), CornerRadius::default(), CornerRadius::uniform(30.0)),
+            bar_width: MutableRange::new(4.0, 1.0, 20.0),
+            minimum_bar_height: MutableRange::new(15.0, 5.0, 100.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct ToggleTheme {
+    pub background_color: Mutable<Color, Render>,
+    pub hovered_background_color: Mutable<Color, Render>,
+    pub foreground_color: Mutable<Color, Render>,
+    pub hovered_foreground_color: Mutable<Color, Render>,
+    pub checked_color: Mutable<Color, Render>,
+    pub corner_radius: MutableRange<CornerRadius, Render>,
+    pub icon_offset: MutableRange<ScreenPosition, Render>,
+    pub icon_size: MutableRange<ScreenSize, Render>,
+    pub text_offset: MutableRange<ScreenPosition, Render>,
+    pub font_size: MutableRange<f32, Render>,
+    pub height: MutableRange<f32, Resolve>,
+}
+
+impl ThemeDefault<Menu> for ToggleTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::rgba_u8(0, 0, 0, 0)),
+            hovered_background_color: Mutable::new(Color::rgb_u8(70, 60, 80)),
+            foreground_color: Mutable::new(Color::monochrome_u8(200)),
+            hovered_foreground_color: Mutable::new(Color::rgb_u8(220, 170, 215)),
+            checked_color: Mutable::new(Color::rgb_u8(150, 70, 255)),
+            corner_radius: MutableRange::new(
+                CornerRadius::uniform(26.0),
+                CornerRadius::default(),
+                CornerRadius::uniform(30.0),
+            ),
+            icon_offset: MutableRange::new(
+                ScreenPosition { left: 7.0, top: 5.0 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            icon_size: MutableRange::new(ScreenSize::uniform(16.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+            text_offset: MutableRange::new(
+                ScreenPosition { left: 32.0, top: 6.0 },
+                ScreenPosition::default(),
+                ScreenPosition { left: 100.0, top: 20.0 },
+            ),
+            font_size: MutableRange::new(14.0, 6.0, 30.0),
+            height: MutableRange::new(26.0, 10.0, 50.0),
+        }
+    }
+}
+
+impl ThemeDefault<Main> for ToggleTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::rgba_u8(0, 0, 0, 0)),
+            hovered_background_color: Mutable::new(Color::monochrome_u8(80)),
+            foreground_color: Mutable::new(Color::monochrome_u8(200)),
+            hovered_foreground_color: Mutable::new(Color::rgb_u8(200, 170, 200)),
+            checked_color: Mutable::new(Color::rgb_u8(140, 120, 140)),
+            corner_radius: MutableRange::new(CornerRadius::uniform(6.0), CornerRadius::default(), CornerRadius::uniform(30.0)),
+            icon_offset: MutableRange::new(
+                ScreenPosition { left: 4.0, top: 1.5 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            icon_size: MutableRange::new(ScreenSize::uniform(12.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+            text_offset: MutableRange::new(
+                ScreenPosition { left: 22.0, top: 0.0 },
+                ScreenPosition::default(),
+                ScreenPosition { left: 100.0, top: 20.0 },
+            ),
+            font_size: MutableRange::new(14.0, 6.0, 30.0),
+            height: MutableRange::new(15.0, 10.0, 50.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct TooltipTheme {
+    pub background_color: Mutable<Color, Render>,
+    pub foreground_color: Mutable<Color, Render>,
+    pub title_color: Mutable<Color, Render>,
+    pub label_color: Mutable<Color, Render>,
+    pub value_color: Mutable<Color, Render>,
+    pub corner_radius: MutableRange<CornerRadius, Render>,
+    pub text_offset: MutableRange<ScreenPosition, Render>,
+    pub mouse_offset: MutableRange<ScreenPosition, Render>,
+    pub font_size: MutableRange<f32, Render>,
+    pub maximum_width: MutableRange<f32, Render>,
+}
+
+impl ThemeDefault<Menu> for TooltipTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::rgba_u8(30, 25, 35, 230)),
+            foreground_color: Mutable::new(Color::monochrome_u8(220)),
+            title_color: Mutable::new(Color::rgb_u8(220, 170, 215)),
+            label_color: Mutable::new(Color::monochrome_u8(160)),
+            value_color: Mutable::new(Color::rgb_u8(150, 70, 255)),
+            corner_radius: MutableRange::new(CornerRadius::uniform(8.0), CornerRadius::default(), CornerRadius::uniform(30.0)),
+            text_offset: MutableRange::new(
+                ScreenPosition::uniform(6.0),
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            mouse_offset: MutableRange::new(
+                ScreenPosition { left: 12.0, top: 16.0 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(40.0),
+            ),
+            font_size: MutableRange::new(14.0, 6.0, 30.0),
+            maximum_width: MutableRange::new(300.0, 100.0, 800.0),
+        }
+    }
+}
+
+impl ThemeDefault<Main> for TooltipTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::rgba_u8(20, 20, 20, 230)),
+            foreground_color: Mutable::new(Color::monochrome_u8(220)),
+            title_color: Mutable::new(Color::rgb_u8(255, 200, 120)),
+            label_color: Mutable::new(Color::monochrome_u8(150)),
+            value_color: Mutable::new(Color::rgb_u8(120, 180, 255)),
+            corner_radius: MutableRange::new(CornerRadius::uniform(4.0), CornerRadius::default(), CornerRadius::uniform(30.0)),
+            text_offset: MutableRange::new(
+                ScreenPosition::uniform(4.0),
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            mouse_offset: MutableRange::new(
+                ScreenPosition { left: 12.0, top: 16.0 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(40.0),
+            ),
+            font_size: MutableRange::new(12.0, 6.0, 30.0),
+            maximum_width: MutableRange::new(250.0, 100.0, 800.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct TabTheme {
+    pub background_color: Mutable<Color, Render>,
+    pub hovered_background_color: Mutable<Color, Render>,
+    pub active_background_color: Mutable<Color, Render>,
+    pub foreground_color: Mutable<Color, Render>,
+    pub active_foreground_color: Mutable<Color, Render>,
+    pub corner_radius: MutableRange<CornerRadius, Render>,
+    pub text_offset: MutableRange<ScreenPosition, Render>,
+    pub font_size: MutableRange<f32, Render>,
+    pub height: MutableRange<f32, Resolve>,
+    pub gap: MutableRange<f32, Render>,
+    pub content_offset: MutableRange<f32, Resolve>,
+}
+
+impl ThemeDefault<Menu> for TabTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::monochrome_u8(45)),
+            hovered_background_color: Mutable::new(Color::rgb_u8(200, 70, 255)),
+            active_background_color: Mutable::new(Color::rgb_u8(150, 70, 255)),
+            foreground_color: Mutable::new(Color::monochrome_u8(160)),
+            active_foreground_color: Mutable::new(Color::monochrome_u8(220)),
+            corner_radius: MutableRange::new(
+                CornerRadius {
+                    top_left: 10.0,
+                    top_right: 10.0,
+                    bottom_left: 0.0,
+                    bottom_right: 0.0,
+                },
+                CornerRadius::default(),
+                CornerRadius::uniform(30.0),
+            ),
+            text_offset: MutableRange::new(
+                ScreenPosition { left: 8.0, top: 4.0 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            font_size: MutableRange::new(14.0, 6.0, 30.0),
+            height: MutableRange::new(22.0, 10.0, 50.0),
+            gap: MutableRange::new(2.0, 0.0, 10.0),
+            content_offset: MutableRange::new(4.0, 0.0, 20.0),
+        }
+    }
+}
+
+impl ThemeDefault<Main> for TabTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::monochrome_u8(60)),
+            hovered_background_color: Mutable::new(Color::rgb_u8(140, 120, 140)),
+            active_background_color: Mutable::new(Color::monochrome_u8(100)),
+            foreground_color: Mutable::new(Color::monochrome_u8(160)),
+            active_foreground_color: Mutable::new(Color::monochrome_u8(220)),
+            corner_radius: MutableRange::new(
+                CornerRadius {
+                    top_left: 6.0,
+                    top_right: 6.0,
+                    bottom_left: 0.0,
+                    bottom_right: 0.0,
+                },
+                CornerRadius::default(),
+                CornerRadius::uniform(30.0),
+            ),
+            text_offset: MutableRange::new(
+                ScreenPosition { left: 6.0, top: 2.0 },
+                ScreenPosition::default(),
+                ScreenPosition::uniform(20.0),
+            ),
+            font_size: MutableRange::new(12.0, 6.0, 30.0),
+            height: MutableRange::new(16.0, 10.0, 50.0),
+            gap: MutableRange::new(1.0, 0.0, 10.0),
+            content_offset: MutableRange::new(3.0, 0.0, 20.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct GridTheme {
+    pub cell_size: MutableRange<ScreenSize, Resolve>,
+    pub gaps: MutableRange<ScreenSize, Resolve>,
+    pub border_size: MutableRange<ScreenSize, Resolve>,
+}
+
+impl ThemeDefault<Menu> for GridTheme {
+    fn default() -> Self {
+        Self {
+            cell_size: MutableRange::new(ScreenSize::uniform(30.0), ScreenSize::uniform(10.0), ScreenSize::uniform(100.0)),
+            gaps: MutableRange::new(ScreenSize::uniform(3.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+            border_size: MutableRange::new(ScreenSize::uniform(3.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+        }
+    }
+}
+
+impl ThemeDefault<Main> for GridTheme {
+    fn default() -> Self {
+        Self {
+            cell_size: MutableRange::new(ScreenSize::uniform(30.0), ScreenSize::uniform(10.0), ScreenSize::uniform(100.0)),
+            gaps: MutableRange::new(ScreenSize::uniform(3.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+            border_size: MutableRange::new(ScreenSize::uniform(3.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct InputTheme {
     pub background_color: Mutable<Color, Render>,
@@ -464,6 +744,9 @@ pub struct InputTheme {
     pub text_offset: MutableRange<ScreenPosition, Render>,
     pub cursor_offset: MutableRange<f32, Render>,
     pub cursor_width: MutableRange<f32, Render>,
+    pub cursor_color: Mutable<Color, Render>,
+    pub cursor_blink_interval: MutableRange<f32, Render>,
+    pub selection_color: Mutable<Color, Render>,
     pub height_bound: DimensionBound,
 }
 
@@ -489,6 +772,9 @@ impl ThemeDefault<Menu> for InputTheme {
             ),
             cursor_offset: MutableRange::new(2.0, 0.0, 10.0),
             cursor_width: MutableRange::new(3.0, 2.0, 30.0),
+            cursor_color: Mutable::new(Color::monochrome_u8(200)),
+            cursor_blink_interval: MutableRange::new(0.5, 0.0, 2.0),
+            selection_color: Mutable::new(Color::rgba_u8(150, 70, 255, 120)),
             height_bound: dimension_bound!(26),
         }
     }
@@ -512,6 +798,9 @@ impl ThemeDefault<Main> for InputTheme {
             ),
             cursor_offset: MutableRange::new(2.0, 0.0, 10.0),
             cursor_width: MutableRange::new(3.0, 2.0, 30.0),
+            cursor_color: Mutable::new(Color::monochrome_u8(200)),
+            cursor_blink_interval: MutableRange::new(0.5, 0.0, 2.0),
+            selection_color: Mutable::new(Color::rgba_u8(140, 120, 140, 120)),
             height_bound: dimension_bound!(15),
         }
     }
@@ -637,6 +926,8 @@ pub struct StatusBarTheme {
     pub activity_point_height: MutableRange<f32, Render>,
     pub border_size: MutableRange<ScreenSize, Render>,
     pub gap: MutableRange<f32, Render>,
+    pub away_color: Mutable<Color, Nothing>,
+    pub away_font_size: MutableRange<f32, Render>,
 }
 
 impl Default for StatusBarTheme {
@@ -659,6 +950,33 @@ impl Default for StatusBarTheme {
                 ScreenSize::uniform(20.0),
             ),
             gap: MutableRange::new(1.0, 0.0, 10.0),
+            away_color: Mutable::new(Color::rgb_u8(255, 170, 0)),
+            away_font_size: MutableRange::new(12.0, 6.0, 30.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PrototypeElement)]
+pub struct SpeechBubbleTheme {
+    pub background_color: Mutable<Color, Nothing>,
+    pub foreground_color: Mutable<Color, Nothing>,
+    pub font_size: MutableRange<f32, Render>,
+    pub maximum_width: MutableRange<f32, Render>,
+    pub border_size: MutableRange<ScreenSize, Render>,
+    pub offset: MutableRange<f32, Render>,
+    pub display_duration: MutableRange<f32, Nothing>,
+}
+
+impl Default for SpeechBubbleTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Mutable::new(Color::rgba_u8(255, 255, 255, 220)),
+            foreground_color: Mutable::new(Color::monochrome_u8(30)),
+            font_size: MutableRange::new(12.0, 6.0, 30.0),
+            maximum_width: MutableRange::new(180.0, 50.0, 500.0),
+            border_size: MutableRange::new(ScreenSize::uniform(4.0), ScreenSize::default(), ScreenSize::uniform(20.0)),
+            offset: MutableRange::new(10.0, 0.0, 100.0),
+            display_duration: MutableRange::new(5.0, 1.0, 20.0),
         }
     }
 }
@@ -666,12 +984,24 @@ impl Default for StatusBarTheme {
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub struct IndicatorTheme {
     pub walking: Mutable<Color, Render>,
+    pub path: Mutable<Color, Render>,
+    pub ping: Mutable<Color, Render>,
+    pub loot: Mutable<Color, Render>,
+    pub search: Mutable<Color, Render>,
+    pub cast_area: Mutable<Color, Render>,
+    pub cast_target: Mutable<Color, Render>,
 }
 
 impl Default for IndicatorTheme {
     fn default() -> Self {
         Self {
             walking: Mutable::new(Color::rgba_u8(0, 255, 170, 170)),
+            path: Mutable::new(Color::rgba_u8(0, 170, 255, 120)),
+            ping: Mutable::new(Color::rgba_u8(255, 200, 40, 200)),
+            loot: Mutable::new(Color::rgba_u8(255, 120, 220, 255)),
+            search: Mutable::new(Color::rgba_u8(255, 80, 80, 230)),
+            cast_area: Mutable::new(Color::rgba_u8(255, 60, 40, 110)),
+            cast_target: Mutable::new(Color::rgba_u8(255, 160, 40, 90)),
         }
     }
 }
@@ -772,6 +1102,11 @@ pub struct InterfaceTheme {
     pub value: ValueTheme,
     pub close_button: CloseButtonTheme,
     pub slider: SliderTheme,
+    pub scroll_view: ScrollViewTheme,
+    pub tab: TabTheme,
+    pub grid: GridTheme,
+    pub toggle: ToggleTheme,
+    pub tooltip: TooltipTheme,
     pub input: InputTheme,
     pub profiler: ProfilerTheme,
     pub chat: ChatTheme,
@@ -786,6 +1121,11 @@ where
     ValueTheme: ThemeDefault<T>,
     CloseButtonTheme: ThemeDefault<T>,
     SliderTheme: ThemeDefault<T>,
+    ScrollViewTheme: ThemeDefault<T>,
+    TabTheme: ThemeDefault<T>,
+    GridTheme: ThemeDefault<T>,
+    ToggleTheme: ThemeDefault<T>,
+    TooltipTheme: ThemeDefault<T>,
     InputTheme: ThemeDefault<T>,
     ProfilerTheme: ThemeDefault<T>,
     ChatTheme: ThemeDefault<T>,
@@ -799,6 +1139,11 @@ where
             value: ThemeDefault::<T>::default(),
             close_button: ThemeDefault::<T>::default(),
             slider: ThemeDefault::<T>::default(),
+            scroll_view: ThemeDefault::<T>::default(),
+            tab: ThemeDefault::<T>::default(),
+            grid: ThemeDefault::<T>::default(),
+            toggle: ThemeDefault::<T>::default(),
+            tooltip: ThemeDefault::<T>::default(),
             input: ThemeDefault::<T>::default(),
             profiler: ThemeDefault::<T>::default(),
             chat: ThemeDefault::<T>::default(),
@@ -810,13 +1155,14 @@ where
 pub struct GameTheme {
     pub overlay: OverlayTheme,
     pub status_bar: StatusBarTheme,
+    pub speech_bubble: SpeechBubbleTheme,
     pub indicator: IndicatorTheme,
     pub cursor: CursorTheme,
 }
 
 #[derive(PrototypeWindow)]
-#[window_title("Theme Viewer")]
-#[window_class("theme_viewer")]
+#[window_title("Theme Editor")]
+#[window_class("theme_editor")]
 pub struct Themes {
     #[name("Theme selector")]
     pub theme_selector: ThemeSelector,
@@ -855,6 +1201,14 @@ impl InterfaceTheme {
         *self = Self::new::<T>(theme_file);
     }
 
+    /// Reload the theme after the file was changed outside of the client. If
+    /// the file can't be read or parsed, the current theme is kept.
+    pub fn reload_changed(&mut self, theme_file: &str) -> Result<(), String> {
+        let data = std::fs::read_to_string(theme_file).map_err(|error| error.to_string())?;
+        *self = ron::from_str(&data).map_err(|error| error.to_string())?;
+        Ok(())
+    }
+
     pub fn save(&self, theme_file: &str) {
         #[cfg(feature = "debug")]
         print_debug!("saving theme to {}{}{}", MAGENTA, theme_file, NONE);
@@ -885,6 +1239,14 @@ impl GameTheme {
         *self = Self::new(theme_file);
     }
 
+    /// Reload the theme after the file was changed outside of the client. If
+    /// the file can't be read or parsed, the current theme is kept.
+    pub fn reload_changed(&mut self, theme_file: &str) -> Result<(), String> {
+        let data = std::fs::read_to_string(theme_file).map_err(|error| error.to_string())?;
+        *self = ron::from_str(&data).map_err(|error| error.to_string())?;
+        Ok(())
+    }
+
     pub fn save(&self, theme_file: &str) {
         #[cfg(feature = "debug")]
         print_debug!("saving theme to {}{}{}", MAGENTA, theme_file, NONE);
@@ -893,3 +1255,59 @@ impl GameTheme {
         std::fs::write(theme_file, data).expect("unable to write file");
     }
 }
+
+/// Polls the modification time of the theme files, so that changes made in an
+/// external editor are applied while the client is running.
+pub struct ThemeWatcher {
+    modified_times: [(ThemeKind, Option<SystemTime>); 3],
+    poll_timer: f64,
+}
+
+impl ThemeWatcher {
+    const POLL_INTERVAL: f64 = 1.0;
+
+    pub fn new(theme_files: [(ThemeKind, &str); 3]) -> Self {
+        let modified_times = theme_files.map(|(kind, theme_file)| (kind, Self::modified_time(theme_file)));
+        let poll_timer = 0.0;
+
+        Self {
+            modified_times,
+            poll_timer,
+        }
+    }
+
+    fn modified_time(theme_file: &str) -> Option<SystemTime> {
+        std::fs::metadata(theme_file).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    /// Remember the current state of a theme file, so saving or loading it from
+    /// the client doesn't count as a change.
+    pub fn mark_current(&mut self, kind: ThemeKind, theme_file: &str) {
+        if let Some((_, modified_time)) = self.modified_times.iter_mut().find(|(other, _)| *other == kind) {
+            *modified_time = Self::modified_time(theme_file);
+        }
+    }
+
+    /// Returns the kinds of all themes that were changed on disk since they
+    /// were last loaded or saved.
+    pub fn changed_themes(&mut self, delta_time: f64, theme_files: [(ThemeKind, &str); 3]) -> Vec<ThemeKind> {
+        self.poll_timer += delta_time;
+
+        if self.poll_timer < Self::POLL_INTERVAL {
+            return Vec::new();
+        }
+
+        self.poll_timer = 0.0;
+
+        theme_files
+            .into_iter()
+            .filter(|(kind, theme_file)| {
+                self.modified_times
+                    .iter()
+                    .find(|(other, _)| other == kind)
+                    .is_some_and(|(_, modified_time)| *modified_time != Self::modified_time(theme_file))
+            })
+            .map(|(kind, _)| kind)
+            .collect()
+    }
+}
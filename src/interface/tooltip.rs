@@ -0,0 +1,193 @@
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::graphics::{InterfaceRenderer, Renderer};
+use crate::interface::*;
+
+/// Shows the tooltip of the hovered element once it has been hovered for
+/// longer than the tooltip delay.
+pub struct TooltipManager {
+    hovered_element: Option<WeakElementCell>,
+    hover_start: Instant,
+    /// Text of the visible tooltip and the mouse position when it appeared.
+    tooltip: Option<(String, ScreenPosition)>,
+    theme_kind: ThemeKind,
+}
+
+impl TooltipManager {
+    pub fn new() -> Self {
+        Self {
+            hovered_element: None,
+            hover_start: Instant::now(),
+            tooltip: None,
+            theme_kind: ThemeKind::default(),
+        }
+    }
+
+    pub fn get_theme_kind(&self) -> ThemeKind {
+        self.theme_kind
+    }
+
+    fn is_hovered(&self, element: Option<&ElementCell>) -> bool {
+        match (&self.hovered_element, element) {
+            (Some(hovered_element), Some(element)) => std::ptr::addr_eq(hovered_element.as_ptr(), Rc::as_ptr(element)),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the tooltip appeared or disappeared, in which case
+    /// the interface needs to be re-rendered.
+    pub fn update(
+        &mut self,
+        hovered_element: Option<&ElementCell>,
+        mouse_position: ScreenPosition,
+        theme_kind: ThemeKind,
+        theme: &InterfaceTheme,
+        delay: f32,
+    ) -> bool {
+        if !self.is_hovered(hovered_element) {
+            self.hovered_element = hovered_element.map(Rc::downgrade);
+            self.hover_start = Instant::now();
+            return self.tooltip.take().is_some();
+        }
+
+        if self.tooltip.is_some() || self.hover_start.elapsed().as_secs_f32() < delay {
+            return false;
+        }
+
+        // NOTE: The hovered element might be borrowed mutably while it is being
+        // dragged, in which case we just try again next frame.
+        let Some(text) = hovered_element
+            .and_then(|element| element.try_borrow().ok().and_then(|element| element.tooltip(theme)))
+            .filter(|text| !text.is_empty())
+        else {
+            return false;
+        };
+
+        self.tooltip = Some((text, mouse_position));
+        self.theme_kind = theme_kind;
+        true
+    }
+
+    pub fn hide(&mut self) -> bool {
+        self.hovered_element = None;
+        self.tooltip.take().is_some()
+    }
+
+    /// Position of the tooltip so that it is fully visible. If it doesn't fit
+    /// below the mouse, it is placed above it instead.
+    fn position(
+        mouse_position: ScreenPosition,
+        mouse_offset: ScreenPosition,
+        size: ScreenSize,
+        available_space: ScreenSize,
+    ) -> ScreenPosition {
+        // NOTE: The mouse offset is kept both below and above the mouse.
+        let anchor = PopupAnchor::new(
+            ScreenPosition {
+                left: mouse_position.left + mouse_offset.left,
+                top: mouse_position.top - mouse_offset.top,
+            },
+            ScreenSize::only_height(mouse_offset.top * 2.0),
+        );
+
+        anchor.place(size, available_space)
+    }
+
+    pub fn render(
+        &self,
+        render_target: &mut <InterfaceRenderer as Renderer>::Target,
+        renderer: &InterfaceRenderer,
+        interface_settings: &InterfaceSettings,
+        theme: &InterfaceTheme,
+        available_space: ScreenSize,
+    ) {
+        let Some((text, mouse_position)) = &self.tooltip else {
+            return;
+        };
+
+        let scaling = interface_settings.scaling.get();
+        let text_offset = theme.tooltip.text_offset.get() * scaling;
+        let font_size = theme.tooltip.font_size.get() * scaling;
+        let maximum_width = theme.tooltip.maximum_width.get() * scaling;
+
+        let text_dimensions = renderer.get_text_dimensions(text, font_size, maximum_width);
+        let size = ScreenSize {
+            width: text_dimensions.x + text_offset.left * 2.0,
+            height: text_dimensions.y + text_offset.top * 2.0,
+        };
+        let position = Self::position(
+            *mouse_position,
+            theme.tooltip.mouse_offset.get() * scaling,
+            size,
+            available_space,
+        );
+
+        let screen_clip = ScreenClip {
+            left: position.left,
+            top: position.top,
+            right: position.left + size.width,
+            bottom: position.top + size.height,
+            ..Default::default()
+        };
+
+        renderer.render_rectangle(
+            render_target,
+            position,
+            size,
+            screen_clip,
+            theme.tooltip.corner_radius.get() * scaling,
+            theme.tooltip.background_color.get(),
+        );
+
+        renderer.render_text(
+            render_target,
+            text,
+            position + text_offset,
+            screen_clip,
+            theme.tooltip.foreground_color.get(),
+            font_size,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TooltipManager;
+    use crate::interface::{ScreenPosition, ScreenSize};
+
+    const AVAILABLE_SPACE: ScreenSize = ScreenSize {
+        width: 800.0,
+        height: 600.0,
+    };
+    const MOUSE_OFFSET: ScreenPosition = ScreenPosition { left: 10.0, top: 10.0 };
+    const SIZE: ScreenSize = ScreenSize {
+        width: 200.0,
+        height: 100.0,
+    };
+
+    #[test]
+    fn tooltip_stays_on_screen() {
+        let below = TooltipManager::position(ScreenPosition { left: 100.0, top: 100.0 }, MOUSE_OFFSET, SIZE, AVAILABLE_SPACE);
+        assert_eq!(below, ScreenPosition { left: 110.0, top: 110.0 });
+
+        let above = TooltipManager::position(ScreenPosition { left: 100.0, top: 550.0 }, MOUSE_OFFSET, SIZE, AVAILABLE_SPACE);
+        assert_eq!(above, ScreenPosition { left: 110.0, top: 440.0 });
+
+        let right_edge = TooltipManager::position(ScreenPosition { left: 750.0, top: 100.0 }, MOUSE_OFFSET, SIZE, AVAILABLE_SPACE);
+        assert_eq!(right_edge, ScreenPosition { left: 600.0, top: 110.0 });
+
+        let too_large = ScreenSize {
+            width: 1000.0,
+            height: 700.0,
+        };
+        let clamped = TooltipManager::position(
+            ScreenPosition { left: 400.0, top: 300.0 },
+            MOUSE_OFFSET,
+            too_large,
+            AVAILABLE_SPACE,
+        );
+        assert_eq!(clamped, ScreenPosition { left: 0.0, top: 0.0 });
+    }
+}
@@ -221,6 +221,7 @@ impl<'a> PrototypeWindow for LoginWindow<'a> {
                 .with_ghost_text("Username")
                 .with_enter_action(username_action)
                 .with_length(24)
+                .hidden_in_streamer_mode()
                 .build()
                 .wrap(),
             InputFieldBuilder::new()
@@ -147,6 +147,7 @@ impl<TITLE, CLOSABLE, CLASS, BACKGROUND, THEME> WindowBuilder<TITLE, CLOSABLE, C
             let drag_button = DragButtonBuilder::new()
                 .with_title(title)
                 .with_width_bound(width_bound)
+                .with_window_class_option(class.clone())
                 .build()
                 .wrap();
             elements.insert(0, drag_button);
@@ -185,6 +186,12 @@ impl<TITLE, CLOSABLE, CLASS, BACKGROUND, THEME> WindowBuilder<TITLE, CLOSABLE, C
                     .finalize_or(0.0)
             });
 
+        let opacity = class
+            .as_ref()
+            .map(|window_class| window_cache.get_opacity(window_class))
+            .unwrap_or(1.0);
+        let locked = class.as_ref().is_some_and(|window_class| window_cache.is_locked(window_class));
+
         let position = cached_position
             .map(|position| size_bound.validated_position(position, size, available_space))
             .unwrap_or(ScreenPosition::from_size((available_space - size) / 2.0));
@@ -196,9 +203,16 @@ impl<TITLE, CLOSABLE, CLASS, BACKGROUND, THEME> WindowBuilder<TITLE, CLOSABLE, C
             size,
             elements,
             popup_element: None,
+            anchor: None,
+            available_space,
             closable,
             background_color,
+            opacity,
+            locked,
+            hidden: false,
             theme_kind,
+            open_animation: Animation::new(0.0, 1.0, super::WINDOW_OPEN_DURATION, Easing::EaseOut),
+            fade_animation: Animation::fixed(1.0, super::WINDOW_FADE_DURATION, Easing::EaseInOut),
         }
     }
 }
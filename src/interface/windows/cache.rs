@@ -8,20 +8,68 @@ use serde::{Deserialize, Serialize};
 use crate::debug::*;
 use crate::interface::{ScreenPosition, ScreenSize};
 
-#[derive(Serialize, Deserialize, new)]
+#[derive(Clone, Serialize, Deserialize, new)]
 pub struct WindowState {
     pub position: ScreenPosition,
     pub size: ScreenSize,
+    /// Selected tab of the [`TabContainer`](crate::interface::TabContainer)
+    /// in the window.
+    #[new(default)]
+    #[serde(default)]
+    pub active_tab: usize,
+    /// Opacity of the window background, so windows like the chat can be
+    /// kept open during combat without hiding the map.
+    #[new(value = "1.0")]
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Locked windows can't be moved, resized, or closed by the player.
+    #[new(default)]
+    #[serde(default)]
+    pub locked: bool,
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+const LAYOUT_PROFILES_FILE: &str = "client/layout_profiles.ron";
+const LAYOUT_EXPORT_DIRECTORY: &str = "client/layouts";
+
+type Layout = HashMap<String, WindowState>;
+
+/// Turn the name of a profile into a file name that stays inside of
+/// [`LAYOUT_EXPORT_DIRECTORY`]. Characters other than letters, digits, spaces,
+/// dashes and underscores are replaced, which also covers path separators and
+/// names like `..`.
+fn profile_file_name(name: &str) -> String {
+    let file_name: String = name
+        .chars()
+        .map(
+            |character| match character.is_alphanumeric() || matches!(character, ' ' | '-' | '_') {
+                true => character,
+                false => '_',
+            },
+        )
+        .collect();
+
+    match file_name.trim().is_empty() {
+        true => "profile".to_owned(),
+        false => file_name,
+    }
 }
 
 #[derive(Default, Serialize, Deserialize)]
 pub struct WindowCache {
-    entries: HashMap<String, WindowState>,
+    entries: Layout,
+    /// Named snapshots of the window layout, so players can keep different
+    /// layouts for different activities and switch between them.
+    #[serde(skip)]
+    profiles: HashMap<String, Layout>,
 }
 
 impl WindowCache {
     pub fn new() -> Self {
-        Self::load().unwrap_or_else(|| {
+        let mut window_cache = Self::load().unwrap_or_else(|| {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to load window cache from {}filename{}. creating empty cache",
@@ -29,8 +77,15 @@ impl WindowCache {
                 NONE
             );
 
-            Default::default()
-        })
+            Self::default()
+        });
+
+        window_cache.profiles = std::fs::read_to_string(LAYOUT_PROFILES_FILE)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default();
+
+        window_cache
     }
 
     pub fn load() -> Option<Self> {
@@ -40,7 +95,10 @@ impl WindowCache {
         std::fs::read_to_string("client/window_cache.ron")
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
-            .map(|entries| Self { entries })
+            .map(|entries| Self {
+                entries,
+                profiles: HashMap::new(),
+            })
     }
 
     pub fn save(&self) {
@@ -49,6 +107,73 @@ impl WindowCache {
 
         let data = ron::ser::to_string_pretty(&self.entries, PrettyConfig::new()).unwrap();
         std::fs::write("client/window_cache.ron", data).expect("unable to write file");
+
+        let data = ron::ser::to_string_pretty(&self.profiles, PrettyConfig::new()).unwrap();
+        std::fs::write(LAYOUT_PROFILES_FILE, data).expect("unable to write file");
+    }
+
+    pub fn get_profile_names(&self) -> Vec<String> {
+        let mut profile_names: Vec<String> = self.profiles.keys().cloned().collect();
+        profile_names.sort();
+        profile_names
+    }
+
+    /// Save the current layout under the given name, replacing an existing
+    /// profile with the same name.
+    pub fn save_profile(&mut self, name: String) {
+        self.profiles.insert(name, self.entries.clone());
+    }
+
+    /// Make a profile the current layout. Windows that are not part of the
+    /// profile keep their current state.
+    pub fn load_profile(&mut self, name: &str) -> Result<(), String> {
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| format!("Layout profile {name} does not exist"))?;
+
+        profile.iter().for_each(|(window_class, window_state)| {
+            self.entries.insert(window_class.clone(), window_state.clone());
+        });
+
+        Ok(())
+    }
+
+    pub fn delete_profile(&mut self, name: &str) {
+        self.profiles.remove(name);
+    }
+
+    /// Write a profile to its own file, so it can be shared with other
+    /// players. Returns the path of the file.
+    pub fn export_profile(&self, name: &str) -> Result<String, String> {
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| format!("Layout profile {name} does not exist"))?;
+        let path = format!("{LAYOUT_EXPORT_DIRECTORY}/{}.ron", profile_file_name(name));
+
+        let data = ron::ser::to_string_pretty(profile, PrettyConfig::new()).map_err(|error| error.to_string())?;
+        std::fs::create_dir_all(LAYOUT_EXPORT_DIRECTORY).map_err(|error| error.to_string())?;
+        std::fs::write(&path, data).map_err(|error| format!("Failed to export layout profile: {error}"))?;
+
+        Ok(path)
+    }
+
+    /// Read a profile that was exported with
+    /// [`export_profile`](Self::export_profile). The profile is named after the
+    /// file. Returns the name of the profile.
+    pub fn import_profile(&mut self, path: &str) -> Result<String, String> {
+        let name = std::path::Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| format!("Invalid layout file {path}"))?
+            .to_owned();
+
+        let data = std::fs::read_to_string(path).map_err(|error| format!("Failed to read {path}: {error}"))?;
+        let profile: Layout = ron::from_str(&data).map_err(|error| format!("Invalid layout file {path}: {error}"))?;
+
+        self.profiles.insert(name.clone(), profile);
+        Ok(name)
     }
 
     pub fn register_window(&mut self, identifier: &str, position: ScreenPosition, size: ScreenSize) {
@@ -73,6 +198,39 @@ impl WindowCache {
         }
     }
 
+    pub fn update_active_tab(&mut self, identifier: &str, active_tab: usize) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.active_tab = active_tab;
+        }
+    }
+
+    pub fn get_active_tab(&self, identifier: &str) -> usize {
+        self.entries.get(identifier).map(|entry| entry.active_tab).unwrap_or_default()
+    }
+
+    pub fn update_opacity(&mut self, identifier: &str, opacity: f32) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.opacity = opacity;
+        }
+    }
+
+    pub fn get_opacity(&self, identifier: &str) -> f32 {
+        self.entries
+            .get(identifier)
+            .map(|entry| entry.opacity)
+            .unwrap_or_else(default_opacity)
+    }
+
+    pub fn update_locked(&mut self, identifier: &str, locked: bool) {
+        if let Some(entry) = self.entries.get_mut(identifier) {
+            entry.locked = locked;
+        }
+    }
+
+    pub fn is_locked(&self, identifier: &str) -> bool {
+        self.entries.get(identifier).map(|entry| entry.locked).unwrap_or_default()
+    }
+
     pub fn get_window_state(&self, identifier: &str) -> Option<(ScreenPosition, ScreenSize)> {
         self.entries.get(identifier).map(|entry| (entry.position, entry.size))
     }
@@ -83,3 +241,17 @@ impl Drop for WindowCache {
         self.save();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::profile_file_name;
+
+    #[test]
+    fn profile_file_names() {
+        assert_eq!(profile_file_name("Farming 2"), "Farming 2");
+        assert_eq!(profile_file_name("../../settings"), "______settings");
+        assert_eq!(profile_file_name("a\\b/c"), "a_b_c");
+        assert_eq!(profile_file_name(".."), "__");
+        assert_eq!(profile_file_name("  "), "profile");
+    }
+}
@@ -0,0 +1,56 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::interface::*;
+use crate::inventory::AchievementData;
+
+#[derive(new)]
+pub struct AchievementWindow {
+    data: Remote<AchievementData>,
+}
+
+impl AchievementWindow {
+    pub const WINDOW_CLASS: &'static str = "achievements";
+}
+
+impl PrototypeWindow for AchievementWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let category = TrackedState::new(0);
+
+        // NOTE: The categories are the tabs of the achievement window of the official
+        // client.
+        let elements = vec![
+            Text::default().with_text("Category").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("All", 0),
+                    ("General", 1),
+                    ("Adventure", 2),
+                    ("Battle", 3),
+                    ("Tale", 4),
+                    ("Feat", 5),
+                ])
+                .with_selected(category.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            ScrollView::new(
+                vec![AchievementContainer::new(self.data.clone(), category.new_remote()).wrap()],
+                size_bound!(100%, ? < 500),
+            )
+            .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Achievements".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 400 < 550, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
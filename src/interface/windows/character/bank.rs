@@ -0,0 +1,72 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::CharacterStatsData;
+
+/// Lets the player deposit zeny into the bank and withdraw it again.
+#[derive(new)]
+pub struct BankWindow {
+    balance: Remote<Option<i64>>,
+    stats: Remote<CharacterStatsData>,
+}
+
+impl BankWindow {
+    pub const WINDOW_CLASS: &'static str = "bank";
+}
+
+impl PrototypeWindow for BankWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let amount = TrackedState::<String>::default();
+
+        // NOTE: The map server only accepts positive amounts that fit into an i32.
+        let amount_action = |amount: TrackedState<String>, event: fn(i32) -> UserEvent| {
+            Box::new(move || match amount.borrow().trim().parse::<i32>() {
+                Ok(zeny) if zeny > 0 => vec![ClickAction::Event(event(zeny))],
+                _ => Vec::new(),
+            })
+        };
+
+        let elements = vec![
+            BankBalanceView::new(self.balance.clone(), self.stats.clone()).wrap(),
+            InputFieldBuilder::new()
+                .with_state(amount.clone())
+                .with_ghost_text("Amount")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(10)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Deposit")
+                .with_event(amount_action(amount.clone(), UserEvent::DepositZeny))
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Withdraw")
+                .with_event(amount_action(amount, UserEvent::WithdrawZeny))
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Close")
+                .with_event(UserEvent::CloseBank)
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The map server has to be told when the player stops using the
+        // bank, so the window is only closed through the button.
+        WindowBuilder::new()
+            .with_title("Bank".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
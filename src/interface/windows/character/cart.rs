@@ -0,0 +1,37 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::interface::*;
+use crate::inventory::Item;
+use crate::network::CartInformation;
+
+#[derive(new)]
+pub struct CartWindow {
+    items: Remote<Vec<Item>>,
+    information: Remote<Option<CartInformation>>,
+}
+
+impl CartWindow {
+    pub const WINDOW_CLASS: &'static str = "cart";
+}
+
+impl PrototypeWindow for CartWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            CartInformationView::new(self.information.clone()).wrap(),
+            CartContainer::new(self.items.clone()).wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Cart".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 400 < 500, ? < 80%))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
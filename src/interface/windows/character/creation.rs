@@ -3,13 +3,24 @@ use procedural::dimension_bound;
 
 use crate::input::UserEvent;
 use crate::interface::*;
+use crate::network::{CharacterCreationData, CharacterCreationVersion, Sex};
+use crate::world::CharacterAppearance;
 
 const MINIMUM_NAME_LENGTH: usize = 4;
 const MAXIMUM_NAME_LENGTH: usize = 24;
+/// Hair styles and colors that are available on the character servers by
+/// default.
+const MAXIMUM_HAIR_STYLE: u16 = 23;
+const MAXIMUM_HAIR_COLOR: u16 = 8;
+const SUMMONER_JOB: u16 = 4218;
 
 #[derive(new)]
 pub struct CharacterCreationWindow {
     slot: usize,
+    version: CharacterCreationVersion,
+    /// Sex of the account, used if the server doesn't allow choosing one.
+    sex: Sex,
+    appearance: Remote<Option<CharacterAppearance>>,
 }
 
 impl CharacterCreationWindow {
@@ -23,6 +34,11 @@ impl PrototypeWindow for CharacterCreationWindow {
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
         let name = TrackedState::<String>::default();
+        let hair_style = TrackedState::new(1u16);
+        let hair_color = TrackedState::new(0u16);
+        let sex = TrackedState::new(self.sex);
+        let start_job = TrackedState::new(0u16);
+        let stats = [TrackedState::new(5u8), TrackedState::new(5u8), TrackedState::new(5u8)];
 
         let selector = {
             let name = name.clone();
@@ -32,13 +48,52 @@ impl PrototypeWindow for CharacterCreationWindow {
         let action = {
             let slot = self.slot;
             let name = name.clone();
+            let hair_style = hair_style.clone();
+            let hair_color = hair_color.clone();
+            let sex = sex.clone();
+            let start_job = start_job.clone();
+            let stats = stats.clone();
 
-            move || vec![ClickAction::Event(UserEvent::CreateCharacter(slot, name.borrow().clone()))]
+            move || {
+                let character = CharacterCreationData {
+                    name: name.borrow().clone(),
+                    hair_style: hair_style.get(),
+                    hair_color: hair_color.get(),
+                    stats: stats.each_ref().map(TrackedState::get),
+                    start_job: start_job.get(),
+                    sex: sex.get(),
+                };
+
+                vec![ClickAction::Event(UserEvent::CreateCharacter(slot, character))]
+            }
+        };
+
+        // NOTE: The preview is updated every time the looks of the character change.
+        let appearance_event = {
+            let hair_style = hair_style.clone();
+            let hair_color = hair_color.clone();
+            let sex = sex.clone();
+
+            Box::new(move || {
+                vec![ClickAction::Event(UserEvent::UpdateCharacterAppearance {
+                    sex: sex.get(),
+                    hair_style: hair_style.get(),
+                    hair_color: hair_color.get(),
+                })]
+            })
         };
 
         let input_action = Box::new(move || vec![ClickAction::FocusNext(FocusMode::FocusNext)]);
 
-        let elements = vec![
+        let row = |label: &str, element: ElementCell| {
+            [
+                Text::default().with_text(label.to_owned()).with_width(dimension_bound!(40%)).wrap(),
+                element,
+            ]
+        };
+
+        let mut elements = vec![
+            CharacterAppearancePreview::new(self.appearance.clone()).wrap(),
             InputFieldBuilder::new()
                 .with_state(name)
                 .with_ghost_text("Character name")
@@ -46,6 +101,71 @@ impl PrototypeWindow for CharacterCreationWindow {
                 .with_length(MAXIMUM_NAME_LENGTH)
                 .build()
                 .wrap(),
+        ];
+
+        elements.extend(row(
+            "Hair style",
+            PickList::default()
+                .with_options((1..=MAXIMUM_HAIR_STYLE).map(|style| (style.to_string(), style)).collect())
+                .with_selected(hair_style)
+                .with_event(appearance_event.clone())
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ));
+
+        elements.extend(row(
+            "Hair color",
+            PickList::default()
+                .with_options((0..=MAXIMUM_HAIR_COLOR).map(|color| (color.to_string(), color)).collect())
+                .with_selected(hair_color)
+                .with_event(appearance_event.clone())
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ));
+
+        match self.version {
+            CharacterCreationVersion::Stats => {
+                // NOTE: Opposite stats always add up to ten, so only one of each pair is
+                // chosen.
+                let labels = ["STR / INT", "AGI / LUK", "VIT / DEX"];
+
+                for (label, stat) in labels.into_iter().zip(stats) {
+                    elements.extend(row(
+                        label,
+                        PickList::default()
+                            .with_options((1..=9u8).rev().map(|value| (format!("{value} / {}", 10 - value), value)).collect())
+                            .with_selected(stat)
+                            .with_event(Box::new(Vec::new))
+                            .with_width(dimension_bound!(!))
+                            .wrap(),
+                    ));
+                }
+            }
+            CharacterCreationVersion::HairOnly => {}
+            CharacterCreationVersion::StartingJob => {
+                elements.extend(row(
+                    "Sex",
+                    PickList::default()
+                        .with_options(vec![("Male", Sex::Male), ("Female", Sex::Female)])
+                        .with_selected(sex)
+                        .with_event(appearance_event)
+                        .with_width(dimension_bound!(!))
+                        .wrap(),
+                ));
+
+                elements.extend(row(
+                    "Job",
+                    PickList::default()
+                        .with_options(vec![("Novice", 0), ("Summoner", SUMMONER_JOB)])
+                        .with_selected(start_job)
+                        .with_event(Box::new(Vec::new))
+                        .with_width(dimension_bound!(!))
+                        .wrap(),
+                ));
+            }
+        }
+
+        elements.push(
             ButtonBuilder::new()
                 .with_text("done")
                 .with_disabled_selector(selector)
@@ -53,7 +173,7 @@ impl PrototypeWindow for CharacterCreationWindow {
                 .with_width_bound(dimension_bound!(50%))
                 .build()
                 .wrap(),
-        ];
+        );
 
         WindowBuilder::new()
             .with_title("Create Character".to_string())
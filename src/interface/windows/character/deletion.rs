@@ -0,0 +1,110 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::network::{CharacterDeletionVersion, CharacterId};
+
+/// Deletes a character once the player typed its name, together with the
+/// e-mail address of the account or the birthdate of the account owner.
+#[derive(new)]
+pub struct CharacterDeletionWindow {
+    character_id: CharacterId,
+    character_name: String,
+    version: CharacterDeletionVersion,
+}
+
+impl CharacterDeletionWindow {
+    pub const WINDOW_CLASS: &'static str = "character_deletion";
+}
+
+impl PrototypeWindow for CharacterDeletionWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let typed_name = TrackedState::<String>::default();
+        let confirmation = TrackedState::<String>::default();
+
+        let (ghost_text, length) = match self.version {
+            CharacterDeletionVersion::Email => ("E-mail", 40),
+            CharacterDeletionVersion::Timer => ("Birthdate (YYMMDD)", 6),
+        };
+
+        let selector = {
+            let character_name = self.character_name.clone();
+            let typed_name = typed_name.clone();
+            let confirmation = confirmation.clone();
+
+            move || *typed_name.borrow() == character_name && !confirmation.borrow().is_empty()
+        };
+
+        let action = {
+            let character_id = self.character_id;
+            let confirmation = confirmation.clone();
+
+            move || {
+                vec![ClickAction::Event(UserEvent::DeleteCharacter(
+                    character_id,
+                    confirmation.borrow().clone(),
+                ))]
+            }
+        };
+
+        let input_action = Box::new(move || vec![ClickAction::FocusNext(FocusMode::FocusNext)]);
+
+        let character_name = match interface_settings.streamer_mode.is_enabled() {
+            true => "this character",
+            false => &self.character_name,
+        };
+
+        let elements = vec![
+            Text::default()
+                .with_text(format!(
+                    "This will permanently delete {character_name}. Type the name of the character to confirm."
+                ))
+                .wrap(),
+            InputFieldBuilder::new()
+                .with_state(typed_name)
+                .with_ghost_text("Character name")
+                .with_enter_action(input_action.clone())
+                .with_length(24)
+                .build()
+                .wrap(),
+            InputFieldBuilder::new()
+                .with_state(confirmation)
+                .with_ghost_text(ghost_text)
+                .with_enter_action(input_action)
+                .with_length(length)
+                .hidden_in_streamer_mode()
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Cancel")
+                .with_event(UserEvent::CloseCharacterDeletionWindow)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Delete")
+                .with_disabled_selector(selector)
+                .with_event(Box::new(action))
+                .with_background_color(|theme| theme.close_button.background_color.get())
+                .with_foreground_color(|theme| theme.close_button.foreground_color.get())
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window is not closable, so it can only be left through the
+        // buttons.
+        WindowBuilder::new()
+            .with_title("Delete Character".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .with_theme_kind(ThemeKind::Menu)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
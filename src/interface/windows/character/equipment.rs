@@ -1,11 +1,12 @@
-use procedural::size_bound;
+use procedural::{dimension_bound, size_bound};
 
 use crate::interface::*;
-use crate::inventory::Item;
+use crate::inventory::{EquipmentSet, Item};
 
 #[derive(new)]
 pub struct EquipmentWindow {
     items: Remote<Vec<Item>>,
+    sets: Remote<Vec<EquipmentSet>>,
 }
 
 impl EquipmentWindow {
@@ -18,7 +19,38 @@ impl PrototypeWindow for EquipmentWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let elements = vec![EquipmentContainer::new(self.items.clone()).wrap()];
+        let set_name = TrackedState::<String>::default();
+
+        let save_action = {
+            let mut set_name = set_name.clone();
+
+            Box::new(move || {
+                let taken_string = set_name.take();
+
+                (!taken_string.is_empty())
+                    .then_some(vec![ClickAction::Event(UserEvent::SaveEquipmentSet(taken_string))])
+                    .unwrap_or_default()
+            })
+        };
+
+        let elements = vec![
+            EquipmentContainer::new(self.items.clone()).wrap(),
+            InputFieldBuilder::new()
+                .with_state(set_name)
+                .with_ghost_text("Set name")
+                .with_enter_action(save_action.clone())
+                .with_length(24)
+                .with_width_bound(dimension_bound!(75%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Save")
+                .with_event(save_action)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+            EquipmentSetContainer::new(self.sets.clone()).wrap(),
+        ];
 
         WindowBuilder::new()
             .with_title("Equipment".to_string())
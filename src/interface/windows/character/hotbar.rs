@@ -1,11 +1,15 @@
+use std::collections::HashMap;
+
 use procedural::size_bound;
 
 use crate::interface::*;
-use crate::inventory::Skill;
+use crate::inventory::{Cooldown, HotbarEntry, HOTBAR_SLOTS};
+use crate::network::SkillId;
 
 #[derive(new)]
 pub struct HotbarWindow {
-    skills: Remote<[Option<Skill>; 10]>,
+    entries: Remote<[Option<HotbarEntry>; HOTBAR_SLOTS]>,
+    cooldowns: Remote<HashMap<SkillId, Cooldown>>,
 }
 
 impl HotbarWindow {
@@ -18,7 +22,7 @@ impl PrototypeWindow for HotbarWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let elements = vec![HotbarContainer::new(self.skills.clone()).wrap()];
+        let elements = vec![HotbarContainer::new(self.entries.clone(), self.cooldowns.clone()).wrap()];
 
         WindowBuilder::new()
             .with_title("Hotbar".to_string())
@@ -1,24 +1,60 @@
-use procedural::size_bound;
+use procedural::{dimension_bound, size_bound};
 
 use crate::interface::*;
-use crate::inventory::Item;
+use crate::inventory::{InventoryTab, InventoryView, Item, ItemCategory, ItemSorting};
 
 #[derive(new)]
-pub struct InventoryWindow {
+pub struct InventoryWindow<'a> {
     items: Remote<Vec<Item>>,
+    view: &'a InventoryView,
 }
 
-impl InventoryWindow {
+impl<'a> InventoryWindow<'a> {
     pub const WINDOW_CLASS: &'static str = "inventory";
 }
 
-impl PrototypeWindow for InventoryWindow {
+impl<'a> PrototypeWindow for InventoryWindow<'a> {
     fn window_class(&self) -> Option<&str> {
         Self::WINDOW_CLASS.into()
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let elements = vec![InventoryContainer::new(self.items.clone()).wrap()];
+        let tabs = [
+            ("All", InventoryTab::All),
+            ("Usable", InventoryTab::Category(ItemCategory::Usable)),
+            ("Equip", InventoryTab::Category(ItemCategory::Equipment)),
+            ("Etc", InventoryTab::Category(ItemCategory::Etc)),
+            ("Favorites", InventoryTab::Favorites),
+        ]
+        .into_iter()
+        .map(|(name, tab)| {
+            let container = InventoryContainer::new(self.items.clone(), self.view.filter(tab));
+            (name.to_owned(), vec![container.wrap()])
+        })
+        .collect();
+
+        let elements = vec![
+            InputFieldBuilder::new()
+                .with_state(self.view.get_search_state())
+                .with_ghost_text("Search")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(24)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Inventory order", ItemSorting::Inventory),
+                    ("Type", ItemSorting::Type),
+                    ("Name", ItemSorting::Name),
+                    ("Recent", ItemSorting::Recency),
+                ])
+                .with_selected(self.view.get_sorting_state())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            TabContainer::new(tabs, window_cache.get_active_tab(Self::WINDOW_CLASS)).wrap(),
+        ];
 
         WindowBuilder::new()
             .with_title("Inventory".to_string())
@@ -1,15 +1,29 @@
+mod achievement;
+mod bank;
+mod cart;
 mod creation;
+mod deletion;
 mod equipment;
 mod hotbar;
 mod inventory;
 mod overview;
+mod quest;
 mod selection;
 mod skill_tree;
+mod statistics;
+mod stats;
 
+pub use self::achievement::AchievementWindow;
+pub use self::bank::BankWindow;
+pub use self::cart::CartWindow;
 pub use self::creation::CharacterCreationWindow;
+pub use self::deletion::CharacterDeletionWindow;
 pub use self::equipment::EquipmentWindow;
 pub use self::hotbar::HotbarWindow;
 pub use self::inventory::InventoryWindow;
 pub use self::overview::CharacterOverviewWindow;
+pub use self::quest::QuestWindow;
 pub use self::selection::CharacterSelectionWindow;
 pub use self::skill_tree::SkillTreeWindow;
+pub use self::statistics::SessionStatisticsWindow;
+pub use self::stats::StatsWindow;
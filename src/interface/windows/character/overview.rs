@@ -33,16 +33,56 @@ impl PrototypeWindow for CharacterOverviewWindow {
                 .with_event(UserEvent::OpenEquipmentWindow)
                 .build()
                 .wrap(),
+            ButtonBuilder::new()
+                .with_text("Cart")
+                .with_event(UserEvent::OpenCartWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Stats")
+                .with_event(UserEvent::OpenStatsWindow)
+                .build()
+                .wrap(),
             ButtonBuilder::new()
                 .with_text("Skill tree")
                 .with_event(UserEvent::OpenSkillTreeWindow)
                 .build()
                 .wrap(),
+            ButtonBuilder::new()
+                .with_text("Quests")
+                .with_event(UserEvent::OpenQuestWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Achievements")
+                .with_event(UserEvent::OpenAchievementWindow)
+                .build()
+                .wrap(),
             ButtonBuilder::new()
                 .with_text("Friends")
                 .with_event(UserEvent::OpenFriendsWindow)
                 .build()
                 .wrap(),
+            ButtonBuilder::new()
+                .with_text("Guild")
+                .with_event(UserEvent::OpenGuildWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Mail")
+                .with_event(UserEvent::OpenMailbox)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Bank")
+                .with_event(UserEvent::OpenBankWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Statistics")
+                .with_event(UserEvent::OpenSessionStatisticsWindow)
+                .build()
+                .wrap(),
             ButtonBuilder::new()
                 .with_text("Menu")
                 .with_event(UserEvent::OpenMenuWindow)
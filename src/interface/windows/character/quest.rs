@@ -0,0 +1,38 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::interface::*;
+use crate::inventory::QuestLogData;
+
+#[derive(new)]
+pub struct QuestWindow {
+    data: Remote<QuestLogData>,
+}
+
+impl QuestWindow {
+    pub const WINDOW_CLASS: &'static str = "quest_log";
+}
+
+impl PrototypeWindow for QuestWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            ScrollView::new(
+                vec![QuestLogContainer::new(self.data.clone()).wrap()],
+                size_bound!(100%, ? < 500),
+            )
+            .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Quests".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 350 < 500, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -8,6 +8,7 @@ use crate::network::CharacterInformation;
 pub struct CharacterSelectionWindow {
     characters: Remote<Vec<CharacterInformation>>,
     move_request: Remote<Option<usize>>,
+    selected_slot: Remote<Option<usize>>,
     slot_count: usize,
 }
 
@@ -22,7 +23,15 @@ impl PrototypeWindow for CharacterSelectionWindow {
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
         let elements = (0..self.slot_count)
-            .map(|slot| CharacterPreview::new(self.characters.clone(), self.move_request.clone(), slot).wrap())
+            .map(|slot| {
+                CharacterPreview::new(
+                    self.characters.clone(),
+                    self.move_request.clone(),
+                    self.selected_slot.clone(),
+                    slot,
+                )
+                .wrap()
+            })
             .collect();
 
         WindowBuilder::new()
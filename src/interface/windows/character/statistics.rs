@@ -0,0 +1,47 @@
+use derive_new::new;
+use procedural::dimension_bound;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::SessionStatisticsData;
+
+#[derive(new)]
+pub struct SessionStatisticsWindow {
+    statistics: Remote<SessionStatisticsData>,
+}
+
+impl SessionStatisticsWindow {
+    pub const WINDOW_CLASS: &'static str = "session_statistics";
+}
+
+impl PrototypeWindow for SessionStatisticsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            SessionStatisticsView::new(self.statistics.clone()).wrap(),
+            ButtonBuilder::new()
+                .with_text("Reset")
+                .with_event(UserEvent::ResetSessionStatistics)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Export CSV")
+                .with_event(UserEvent::ExportSessionStatistics)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Session Statistics".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(SizeBound::DEFAULT_UNBOUNDED)
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,31 @@
+use derive_new::new;
+
+use crate::interface::*;
+use crate::inventory::CharacterStatsData;
+
+#[derive(new)]
+pub struct StatsWindow {
+    stats: Remote<CharacterStatsData>,
+}
+
+impl StatsWindow {
+    pub const WINDOW_CLASS: &'static str = "stats";
+}
+
+impl PrototypeWindow for StatsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![CharacterStatsView::new(self.stats.clone()).wrap()];
+
+        WindowBuilder::new()
+            .with_title("Stats".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(SizeBound::DEFAULT_UNBOUNDED)
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
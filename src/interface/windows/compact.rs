@@ -0,0 +1,85 @@
+use crate::interface::*;
+
+/// The compact layout is used if the window is smaller than this in either
+/// dimension.
+const COMPACT_THRESHOLD: ScreenSize = ScreenSize {
+    width: 1280.0,
+    height: 720.0,
+};
+const SCREEN_MARGIN: f32 = 5.0;
+
+#[derive(Clone, Copy)]
+enum Anchor {
+    TopLeft,
+    BottomLeft,
+    BottomCenter,
+}
+
+/// Automatic placement of the core game windows on small screens. While the
+/// compact layout is active, these windows are shrunk and pinned to the edges
+/// of the screen, so they don't cover the game.
+pub struct CompactLayout;
+
+impl CompactLayout {
+    const CORE_WINDOWS: [(&'static str, Anchor, ScreenSize); 3] = [
+        (CharacterOverviewWindow::WINDOW_CLASS, Anchor::TopLeft, ScreenSize {
+            width: 180.0,
+            height: 0.0,
+        }),
+        (ChatWindow::WINDOW_CLASS, Anchor::BottomLeft, ScreenSize {
+            width: 300.0,
+            height: 100.0,
+        }),
+        (HotbarWindow::WINDOW_CLASS, Anchor::BottomCenter, ScreenSize {
+            width: 300.0,
+            height: 0.0,
+        }),
+    ];
+
+    pub fn is_required(available_space: ScreenSize) -> bool {
+        available_space.width < COMPACT_THRESHOLD.width || available_space.height < COMPACT_THRESHOLD.height
+    }
+
+    pub fn is_core_window(window_class: Option<&str>) -> bool {
+        window_class.is_some_and(|window_class| Self::CORE_WINDOWS.iter().any(|(class, ..)| *class == window_class))
+    }
+
+    /// Shrink and pin a core window. Other windows are left untouched.
+    pub fn place(window: &mut Window, interface_settings: &InterfaceSettings, available_space: ScreenSize) {
+        let Some((_, anchor, compact_size)) = Self::CORE_WINDOWS
+            .iter()
+            .find(|(class, ..)| window.window_class.as_deref() == Some(*class))
+        else {
+            return;
+        };
+
+        // NOTE: A height of zero means the window keeps its resolved height.
+        window.size.width = compact_size.width;
+        if compact_size.height > 0.0 {
+            window.size.height = compact_size.height;
+        }
+        window.validate_size(interface_settings, available_space);
+
+        let size = window.size;
+        window.position = match anchor {
+            Anchor::TopLeft => ScreenPosition::uniform(SCREEN_MARGIN),
+            Anchor::BottomLeft => ScreenPosition {
+                left: SCREEN_MARGIN,
+                top: available_space.height - size.height - SCREEN_MARGIN,
+            },
+            Anchor::BottomCenter => ScreenPosition {
+                left: (available_space.width - size.width) / 2.0,
+                top: available_space.height - size.height - SCREEN_MARGIN,
+            },
+        };
+        window.validate_position(available_space);
+    }
+
+    /// Move a core window back to where the player left it before the compact
+    /// layout was activated.
+    pub fn restore(window: &mut Window, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) {
+        if Self::is_core_window(window.get_window_class()) {
+            window.restore_state(window_cache, interface_settings, available_space);
+        }
+    }
+}
@@ -4,6 +4,7 @@ mod maps;
 mod packet;
 mod profiler;
 mod time;
+mod trace;
 
 pub use self::commands::CommandsWindow;
 pub use self::inspector::FrameInspectorWindow;
@@ -11,3 +12,4 @@ pub use self::maps::MapsWindow;
 pub use self::packet::PacketWindow;
 pub use self::profiler::ProfilerWindow;
 pub use self::time::TimeWindow;
+pub use self::trace::EventTraceWindow;
@@ -0,0 +1,65 @@
+use procedural::{dimension_bound, size_bound};
+
+use crate::debug::RingBuffer;
+use crate::input::TracedEvent;
+use crate::interface::*;
+
+pub struct EventTraceWindow<const N: usize> {
+    events: Remote<RingBuffer<TracedEvent, N>>,
+    trace_camera_events: TrackedState<bool>,
+}
+
+impl<const N: usize> EventTraceWindow<N> {
+    pub const WINDOW_CLASS: &'static str = "event_trace";
+
+    pub fn new(events: Remote<RingBuffer<TracedEvent, N>>, trace_camera_events: TrackedState<bool>) -> Self {
+        Self {
+            events,
+            trace_camera_events,
+        }
+    }
+}
+
+impl<const N: usize> PrototypeWindow for EventTraceWindow<N> {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let clear_selector = {
+            let events = self.events.clone();
+            move || !events.borrow().is_empty()
+        };
+
+        let clear_action = { move || vec![ClickAction::Event(UserEvent::ClearEventTrace)] };
+
+        let elements = vec![
+            ButtonBuilder::new()
+                .with_text("Clear")
+                .with_disabled_selector(clear_selector)
+                .with_event(Box::new(clear_action))
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Trace camera")
+                .with_state(self.trace_camera_events.clone())
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+            ScrollView::new(
+                vec![EventTraceView::new(self.events.clone()).wrap()],
+                size_bound!(100%, ? < super),
+            )
+            .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Event trace".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 400 < 600, ? < 80%))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,41 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::interface::*;
+
+#[derive(new)]
+pub struct GuildWindow {
+    data: GuildData,
+}
+
+impl GuildWindow {
+    pub const WINDOW_CLASS: &'static str = "guild";
+}
+
+impl PrototypeWindow for GuildWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let tabs = [
+            ("Information", GuildTab::Information),
+            ("Members", GuildTab::Members),
+            ("Notice", GuildTab::Notice),
+            ("Positions", GuildTab::Positions),
+        ]
+        .into_iter()
+        .map(|(name, tab)| (name.to_owned(), vec![GuildView::new(self.data.clone(), tab).wrap()]))
+        .collect();
+
+        let elements = vec![TabContainer::new(tabs, window_cache.get_active_tab(Self::WINDOW_CLASS)).wrap()];
+
+        WindowBuilder::new()
+            .with_title("Guild".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 350 < 500, ? < 80%))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
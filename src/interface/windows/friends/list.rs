@@ -4,11 +4,11 @@ use derive_new::new;
 use procedural::dimension_bound;
 
 use crate::interface::*;
-use crate::network::Friend;
+use crate::network::{Friend, OnlineState};
 
 #[derive(new)]
 pub struct FriendsWindow {
-    friend_list: Remote<Vec<(Friend, UnsafeCell<Option<WeakElementCell>>)>>,
+    friend_list: Remote<Vec<(Friend, OnlineState, UnsafeCell<Option<WeakElementCell>>)>>,
 }
 
 impl FriendsWindow {
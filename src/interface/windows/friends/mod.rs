@@ -1,5 +1,9 @@
+mod guild;
 mod list;
 mod request;
+mod whisper;
 
+pub use self::guild::GuildWindow;
 pub use self::list::FriendsWindow;
 pub use self::request::FriendRequestWindow;
+pub use self::whisper::WhisperWindow;
@@ -1,6 +1,7 @@
 use derive_new::new;
 use procedural::{dimension_bound, size_bound};
 
+use crate::graphics::Color;
 use crate::input::UserEvent;
 use crate::interface::*;
 use crate::network::Friend;
@@ -18,8 +19,11 @@ impl PrototypeWindow for FriendRequestWindow {
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
         let elements = vec![
             Text::default()
-                .with_text(format!("^ffaa00{}^000000 wants to be friends with you", self.friend.name))
+                .with_text(self.friend.name.clone())
+                .with_foreground_color(|_| Color::rgb_u8(255, 170, 0))
+                .alias_in_streamer_mode()
                 .wrap(),
+            Text::default().with_text("wants to be friends with you").wrap(),
             ButtonBuilder::new()
                 .with_text("reject")
                 .with_event(UserEvent::RejectFriendRequest {
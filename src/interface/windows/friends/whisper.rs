@@ -0,0 +1,68 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::interface::*;
+
+#[derive(new)]
+pub struct WhisperWindow {
+    name: String,
+}
+
+impl WhisperWindow {
+    pub const WINDOW_CLASS: &'static str = "whisper";
+}
+
+impl PrototypeWindow for WhisperWindow {
+    fn window_class(&self) -> Option<&str> {
+        WhisperWindow::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let message = TrackedState::<String>::default();
+
+        let send_action = {
+            let mut message = message.clone();
+            let name = self.name.clone();
+
+            Box::new(move || {
+                let taken_string = message.take();
+
+                (!taken_string.is_empty())
+                    .then(|| {
+                        vec![ClickAction::Event(UserEvent::SendWhisper {
+                            name: name.clone(),
+                            message: taken_string,
+                        })]
+                    })
+                    .unwrap_or_default()
+            })
+        };
+
+        let elements = vec![
+            Text::default().with_text(self.name.clone()).alias_in_streamer_mode().wrap(),
+            InputFieldBuilder::new()
+                .with_state(message)
+                .with_ghost_text("Message")
+                .with_enter_action(send_action.clone())
+                .with_length(80)
+                .hidden_in_streamer_mode()
+                .with_width_bound(dimension_bound!(80%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Send")
+                .with_event(send_action)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Whisper".to_string())
+            .closable()
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,51 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::interface::*;
+
+/// Asks the player to confirm a dangerous action before it is executed. The
+/// action itself is held by the [`Interface`] until the dialog is resolved.
+#[derive(new)]
+pub struct ConfirmationDialog {
+    text: String,
+}
+
+impl ConfirmationDialog {
+    pub const WINDOW_CLASS: &'static str = "confirmation";
+}
+
+impl PrototypeWindow for ConfirmationDialog {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            Text::default().with_text(self.text.clone()).wrap(),
+            ButtonBuilder::new()
+                .with_text("Cancel")
+                .with_event(UserEvent::ResolveConfirmation(false))
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Confirm")
+                .with_event(UserEvent::ResolveConfirmation(true))
+                .with_background_color(|theme| theme.close_button.background_color.get())
+                .with_foreground_color(|theme| theme.close_button.foreground_color.get())
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The dialog is not closable, since closing it without resolving it
+        // would leave the pending action behind.
+        WindowBuilder::new()
+            .with_title("Confirm".to_string())
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(250 > 300 < 400, ?))
+            .with_elements(elements)
+            .with_theme_kind(ThemeKind::Menu)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
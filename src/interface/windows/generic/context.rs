@@ -0,0 +1,56 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Single action of a [`ContextMenu`].
+#[derive(Clone, new)]
+pub struct ContextMenuEntry {
+    text: String,
+    event: UserEvent,
+}
+
+/// Popup with a list of actions that is opened next to the mouse. Selecting
+/// an action emits its [`UserEvent`] and closes the menu.
+#[derive(new)]
+pub struct ContextMenu {
+    position: ScreenPosition,
+    entries: Vec<ContextMenuEntry>,
+}
+
+impl ContextMenu {
+    pub const WINDOW_CLASS: &'static str = "context_menu";
+}
+
+impl PrototypeWindow for ContextMenu {
+    fn window_class(&self) -> Option<&str> {
+        ContextMenu::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = self
+            .entries
+            .iter()
+            .cloned()
+            .map(|ContextMenuEntry { text, event }| {
+                ButtonBuilder::new()
+                    .with_text(text)
+                    .with_event(Box::new(move || {
+                        vec![ClickAction::Event(event.clone()), ClickAction::CloseWindow]
+                    }))
+                    .build()
+                    .wrap()
+            })
+            .collect();
+
+        let mut window = WindowBuilder::new()
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(120 > 150 < 250, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space);
+
+        window.place_next_to(PopupAnchor::at(self.position), available_space);
+        window
+    }
+}
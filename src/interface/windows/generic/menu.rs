@@ -15,6 +15,16 @@ impl PrototypeWindow for MenuWindow {
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
         let elements = vec![
+            ButtonBuilder::new()
+                .with_text("Entity search")
+                .with_event(UserEvent::OpenEntitySearchWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("General settings")
+                .with_event(UserEvent::OpenGeneralSettingsWindow)
+                .build()
+                .wrap(),
             ButtonBuilder::new()
                 .with_text("Graphics settings")
                 .with_event(UserEvent::OpenGraphicsSettingsWindow)
@@ -25,6 +35,16 @@ impl PrototypeWindow for MenuWindow {
                 .with_event(UserEvent::OpenAudioSettingsWindow)
                 .build()
                 .wrap(),
+            ButtonBuilder::new()
+                .with_text("Layout profiles")
+                .with_event(UserEvent::OpenLayoutProfilesWindow)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Theme editor")
+                .with_event(UserEvent::OpenThemeEditorWindow)
+                .build()
+                .wrap(),
             #[cfg(feature = "debug")]
             ButtonBuilder::new()
                 .with_text("Render settings")
@@ -62,22 +82,22 @@ impl PrototypeWindow for MenuWindow {
                 .wrap(),
             #[cfg(feature = "debug")]
             ButtonBuilder::new()
-                .with_text("Theme viewer")
-                .with_event(UserEvent::OpenThemeViewerWindow)
+                .with_text("Profiler")
+                .with_event(UserEvent::OpenProfilerWindow)
                 .with_foreground_color(|theme| theme.button.debug_foreground_color.get())
                 .build()
                 .wrap(),
             #[cfg(feature = "debug")]
             ButtonBuilder::new()
-                .with_text("Profiler")
-                .with_event(UserEvent::OpenProfilerWindow)
+                .with_text("Packets")
+                .with_event(UserEvent::OpenPacketWindow)
                 .with_foreground_color(|theme| theme.button.debug_foreground_color.get())
                 .build()
                 .wrap(),
             #[cfg(feature = "debug")]
             ButtonBuilder::new()
-                .with_text("Packets")
-                .with_event(UserEvent::OpenPacketWindow)
+                .with_text("Event trace")
+                .with_event(UserEvent::OpenEventTraceWindow)
                 .with_foreground_color(|theme| theme.button.debug_foreground_color.get())
                 .build()
                 .wrap(),
@@ -1,9 +1,17 @@
 mod chat;
+mod confirmation;
+mod context;
 mod dialog;
 mod error;
 mod menu;
+mod search;
+mod version;
 
 pub use self::chat::ChatWindow;
+pub use self::confirmation::ConfirmationDialog;
+pub use self::context::{ContextMenu, ContextMenuEntry};
 pub use self::dialog::DialogWindow;
 pub use self::error::ErrorWindow;
 pub use self::menu::MenuWindow;
+pub use self::search::EntitySearchWindow;
+pub use self::version::VersionWindow;
@@ -0,0 +1,60 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::world::EntityType;
+
+#[derive(new)]
+pub struct EntitySearchWindow {
+    name: TrackedState<String>,
+    entity_type: TrackedState<Option<EntityType>>,
+}
+
+impl EntitySearchWindow {
+    pub const WINDOW_CLASS: &'static str = "entity_search";
+}
+
+impl PrototypeWindow for EntitySearchWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            InputFieldBuilder::new()
+                .with_state(self.name.clone())
+                .with_ghost_text("Name")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(24)
+                .build()
+                .wrap(),
+            Text::default().with_text("Type").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Any", None),
+                    ("Players", Some(EntityType::Player)),
+                    ("Monsters", Some(EntityType::Monster)),
+                    ("NPCs", Some(EntityType::Npc)),
+                    ("Warps", Some(EntityType::Warp)),
+                ])
+                .with_selected(self.entity_type.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Clear")
+                .with_event(UserEvent::ClearEntitySearch)
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Entity search".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(200 > 250 < 300, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
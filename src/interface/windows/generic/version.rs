@@ -0,0 +1,51 @@
+use derive_new::new;
+use procedural::size_bound;
+
+use crate::interface::*;
+use crate::system::{ReleaseInformation, VERSION};
+
+/// Shown on startup when a newer release of the client is available.
+#[derive(new)]
+pub struct VersionWindow {
+    release: ReleaseInformation,
+}
+
+impl VersionWindow {
+    pub const WINDOW_CLASS: &'static str = "version";
+}
+
+impl PrototypeWindow for VersionWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let download_url = self.release.download_url.clone();
+
+        let elements = vec![
+            Text::default()
+                .with_text(format!(
+                    "Version ^ffaa00{}^000000 is available, you are using version ^ffaa00{VERSION}^000000",
+                    self.release.version
+                ))
+                .wrap(),
+            Headline::new("Changelog".to_string(), Headline::DEFAULT_SIZE).wrap(),
+            Text::default().with_text(self.release.changelog.clone()).wrap(),
+            Headline::new("Download".to_string(), Headline::DEFAULT_SIZE).wrap(),
+            Text::default().with_text(self.release.download_url.clone()).wrap(),
+            ButtonBuilder::new()
+                .with_text("Copy download link")
+                .with_event(UserEvent::CopyToClipboard(download_url))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("New version available".to_string())
+            .closable()
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(300 > 400 < 600, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
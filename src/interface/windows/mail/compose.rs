@@ -0,0 +1,118 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::Item;
+
+/// Maximum number of characters in the title of a mail.
+const TITLE_LENGTH: usize = 40;
+/// Maximum number of characters in the text of a mail.
+const TEXT_LENGTH: usize = 500;
+
+#[derive(new)]
+pub struct MailComposeWindow {
+    attachments: Remote<Vec<Item>>,
+}
+
+impl MailComposeWindow {
+    pub const WINDOW_CLASS: &'static str = "mail_compose";
+}
+
+impl PrototypeWindow for MailComposeWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let receiver_name = TrackedState::<String>::default();
+        let title = TrackedState::<String>::default();
+        let text = TrackedState::<String>::default();
+        let zeny = TrackedState::<String>::default();
+
+        let send_action = {
+            let receiver_name = receiver_name.clone();
+            let title = title.clone();
+            let text = text.clone();
+            let zeny = zeny.clone();
+
+            Box::new(move || {
+                let receiver_name = receiver_name.borrow().trim().to_owned();
+                let title = title.borrow().clone();
+
+                if receiver_name.is_empty() || title.is_empty() {
+                    return Vec::new();
+                }
+
+                // NOTE: An empty zeny field means that no zeny are sent.
+                let zeny_text = zeny.borrow().trim().to_owned();
+                let zeny = match zeny_text.is_empty() {
+                    true => 0,
+                    false => match zeny_text.parse::<u64>() {
+                        Ok(zeny) => zeny,
+                        Err(_) => return Vec::new(),
+                    },
+                };
+
+                vec![ClickAction::Event(UserEvent::SendMail {
+                    receiver_name,
+                    title,
+                    text: text.borrow().clone(),
+                    zeny,
+                })]
+            })
+        };
+
+        let elements = vec![
+            InputFieldBuilder::new()
+                .with_state(receiver_name)
+                .with_ghost_text("Receiver")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(23)
+                .build()
+                .wrap(),
+            InputFieldBuilder::new()
+                .with_state(title)
+                .with_ghost_text("Title")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(TITLE_LENGTH)
+                .build()
+                .wrap(),
+            TextAreaBuilder::new()
+                .with_state(text)
+                .with_length(TEXT_LENGTH)
+                .with_visible_lines(8)
+                .build()
+                .wrap(),
+            MailAttachmentContainer::new(self.attachments.clone()).wrap(),
+            InputFieldBuilder::new()
+                .with_state(zeny)
+                .with_ghost_text("Zeny")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(10)
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Send")
+                .with_event(send_action)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Cancel")
+                .with_event(UserEvent::CancelMailCompose)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window can't be closed directly, since writing the mail has to
+        // be canceled through the map server.
+        WindowBuilder::new()
+            .with_title("Write Mail".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 350 < 500, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,51 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::MailboxData;
+
+#[derive(new)]
+pub struct MailboxWindow {
+    data: Remote<MailboxData>,
+}
+
+impl MailboxWindow {
+    pub const WINDOW_CLASS: &'static str = "mailbox";
+}
+
+impl PrototypeWindow for MailboxWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            ScrollView::new(
+                vec![MailListContainer::new(self.data.clone()).wrap()],
+                size_bound!(100%, ? < 400),
+            )
+            .wrap(),
+            ButtonBuilder::new()
+                .with_text("Refresh")
+                .with_event(UserEvent::OpenMailbox)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Write")
+                .with_event(UserEvent::OpenMailCompose)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Mailbox".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(400 > 400 < 600, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,7 @@
+mod compose;
+mod list;
+mod read;
+
+pub use self::compose::MailComposeWindow;
+pub use self::list::MailboxWindow;
+pub use self::read::MailWindow;
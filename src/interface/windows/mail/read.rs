@@ -0,0 +1,79 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::OpenedMail;
+
+#[derive(new)]
+pub struct MailWindow {
+    opened_mail: Remote<Option<OpenedMail>>,
+}
+
+impl MailWindow {
+    pub const WINDOW_CLASS: &'static str = "mail";
+}
+
+impl PrototypeWindow for MailWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let has_no_zeny = {
+            let opened_mail = self.opened_mail.clone();
+            move || opened_mail.borrow().as_ref().map_or(true, |mail| mail.zeny == 0)
+        };
+
+        let has_no_items = {
+            let opened_mail = self.opened_mail.clone();
+            move || opened_mail.borrow().as_ref().map_or(true, |mail| mail.items.is_empty())
+        };
+
+        let elements = vec![
+            ScrollView::new(
+                vec![MailContentContainer::new(self.opened_mail.clone()).wrap()],
+                size_bound!(100%, ? < 400),
+            )
+            .wrap(),
+            ButtonBuilder::new()
+                .with_text("Take zeny")
+                .with_event(UserEvent::TakeMailZeny)
+                .with_disabled_selector(has_no_zeny)
+                .with_width_bound(dimension_bound!(25%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Take items")
+                .with_event(UserEvent::TakeMailItems)
+                .with_disabled_selector(has_no_items)
+                .with_width_bound(dimension_bound!(25%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Delete")
+                .with_event(UserEvent::RequestConfirmation {
+                    text: "Do you really want to delete this mail?".to_owned(),
+                    event: Box::new(UserEvent::DeleteMail),
+                })
+                .with_width_bound(dimension_bound!(25%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Close")
+                .with_event(UserEvent::CloseMail)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window can't be closed directly, so the opened mail is always
+        // cleared when it disappears.
+        WindowBuilder::new()
+            .with_title("Mail".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(400 > 400 < 600, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -2,32 +2,67 @@ mod account;
 mod builder;
 mod cache;
 mod character;
+mod compact;
 #[cfg(feature = "debug")]
 mod debug;
 mod friends;
 mod generic;
+mod mail;
 mod mutable;
 mod prototype;
 mod settings;
+mod shop;
+mod trade;
+mod vending;
 
+use bumpalo::Bump;
 use procedural::size_bound;
 
 pub use self::account::*;
 pub use self::builder::WindowBuilder;
 pub use self::cache::*;
 pub use self::character::*;
+pub use self::compact::CompactLayout;
 #[cfg(feature = "debug")]
 pub use self::debug::*;
 pub use self::friends::*;
 pub use self::generic::*;
+pub use self::mail::*;
 pub use self::mutable::*;
 pub use self::prototype::PrototypeWindow;
 pub use self::settings::*;
+pub use self::shop::*;
+pub use self::trade::*;
+pub use self::vending::*;
 use crate::graphics::{InterfaceRenderer, Renderer};
 use crate::input::MouseInputMode;
 use crate::interface::*;
 use crate::loaders::FontLoader;
 
+/// Duration of the animation when opening a window in seconds.
+const WINDOW_OPEN_DURATION: f32 = 0.15;
+/// Part of the window that is revealed at the start of the opening animation.
+const WINDOW_OPEN_REVEAL: f32 = 0.9;
+/// Duration of the animation when fading a window in or out in seconds.
+const WINDOW_FADE_DURATION: f32 = 0.3;
+
+/// Edges of a window that follow the mouse while resizing it. There is no
+/// grip on the top edge, since that is where windows are moved by their title.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResizeGrip {
+    pub left: bool,
+    pub right: bool,
+    pub bottom: bool,
+}
+
+impl ResizeGrip {
+    pub const BOTTOM_RIGHT: Self = Self {
+        left: false,
+        right: true,
+        bottom: true,
+    };
+}
+
 pub struct Window {
     window_class: Option<String>,
     position: ScreenPosition,
@@ -35,9 +70,23 @@ pub struct Window {
     size: ScreenSize,
     elements: Vec<ElementCell>,
     popup_element: Option<(ElementCell, Tracker<ScreenPosition>, Tracker<ScreenSize>)>,
+    /// Area that the window is attached to, if it is a popup itself.
+    anchor: Option<PopupAnchor>,
+    /// Space available when the window was last resolved, used for placing the
+    /// popup element.
+    available_space: ScreenSize,
     closable: bool,
     background_color: Option<ColorSelector>,
+    opacity: f32,
+    locked: bool,
+    /// Hidden windows stay open, but are neither rendered nor hovered.
+    hidden: bool,
     theme_kind: ThemeKind,
+    /// Progress of revealing the window after it was opened.
+    open_animation: Animation<f32>,
+    /// Opacity of the entire window, in contrast to `opacity`, which only
+    /// applies to the background.
+    fade_animation: Animation<f32>,
 }
 
 impl Window {
@@ -46,19 +95,48 @@ impl Window {
     }
 
     fn get_background_color(&self, theme: &InterfaceTheme) -> Color {
-        self.background_color
+        let mut background_color = self
+            .background_color
             .as_ref()
             .map(|closure| closure(theme))
-            .unwrap_or(theme.window.background_color.get())
+            .unwrap_or(theme.window.background_color.get());
+
+        background_color.alpha *= self.opacity;
+        background_color
     }
 
     pub fn has_transparency(&self, theme: &InterfaceTheme) -> bool {
         const TRANSPARENCY_THRESHOLD: f32 = 0.999;
         self.get_background_color(theme).alpha < TRANSPARENCY_THRESHOLD
+            || self.fade_animation.target() < TRANSPARENCY_THRESHOLD
+            || !self.fade_animation.is_finished()
     }
 
     pub fn is_closable(&self) -> bool {
-        self.closable
+        self.closable && !self.locked
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    /// Fade the entire window to the given opacity.
+    pub fn fade_to(&mut self, opacity: f32) {
+        if self.fade_animation.target() != opacity {
+            self.fade_animation.retarget(opacity);
+        }
+    }
+
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
     }
 
     pub fn get_theme_kind(&self) -> ThemeKind {
@@ -68,18 +146,21 @@ impl Window {
     pub fn resolve(
         &mut self,
         font_loader: Rc<RefCell<FontLoader>>,
+        frame_arena: &Bump,
         interface_settings: &InterfaceSettings,
         theme: &InterfaceTheme,
         available_space: ScreenSize,
     ) -> (Option<&str>, ScreenPosition, ScreenSize) {
         let mut placement_resolver = PlacementResolver::new(
             font_loader.clone(),
+            frame_arena,
             available_space,
             self.size,
             &self.size_bound,
             theme.window.border_size.get(),
             theme.window.gaps.get(),
             interface_settings.scaling.get(),
+            interface_settings.localization.is_right_to_left(),
         );
 
         self.elements
@@ -102,13 +183,19 @@ impl Window {
             self.validate_size(interface_settings, available_space);
         }
 
-        self.validate_position(available_space);
+        match self.anchor {
+            Some(anchor) => self.position = anchor.place(self.size, available_space),
+            None => self.validate_position(available_space),
+        }
+
+        self.available_space = available_space;
 
         if let Some((popup, _, size_tracker)) = &self.popup_element {
             let size = size_tracker().unwrap(); // FIX: Don't unwrap obviously
 
             let mut placement_resolver = PlacementResolver::new(
                 font_loader,
+                frame_arena,
                 available_space,
                 // TODO: 250 is an arbitrary limitation. This should be replaced with a value based
                 // on some reasoning.
@@ -120,6 +207,7 @@ impl Window {
                 ScreenSize::default(), //theme.window.border_size.get(), // TODO: Popup
                 ScreenSize::default(), //theme.window.gaps.get(), // TODO: Popup
                 interface_settings.scaling.get(),
+                interface_settings.localization.is_right_to_left(),
             );
 
             popup.borrow_mut().resolve(&mut placement_resolver, interface_settings, theme);
@@ -129,9 +217,15 @@ impl Window {
     }
 
     pub fn update(&mut self) -> Option<ChangeEvent> {
+        // NOTE: The window is transparent while it is opening, so the entire interface
+        // needs to be rendered.
+        let open_change_event = self.open_animation.update(ChangeEvent::RENDER);
+        let fade_change_event = self.fade_animation.update(ChangeEvent::RENDER);
+
         self.elements
             .iter_mut()
             .map(|element| element.borrow_mut().update())
+            .chain([open_change_event, fade_change_event])
             .fold(None, |current, other| {
                 current.zip_with(other, ChangeEvent::union).or(current).or(other)
             })
@@ -147,10 +241,14 @@ impl Window {
     }
 
     pub fn hovered_element(&self, mouse_position: ScreenPosition, mouse_mode: &MouseInputMode) -> HoverInformation {
+        if self.hidden {
+            return HoverInformation::Missed;
+        }
+
         let absolute_position = ScreenPosition::from_size(mouse_position - self.position);
 
-        if let Some((popup, position_tracker, _)) = &self.popup_element {
-            let position = position_tracker().unwrap(); // FIX: Don't unwrap obviously
+        if let Some((popup, ..)) = &self.popup_element {
+            let position = self.popup_position().unwrap(); // FIX: Don't unwrap obviously
             let position = ScreenPosition::from_size(mouse_position - position);
 
             match popup.borrow().hovered_element(position, mouse_mode) {
@@ -194,6 +292,7 @@ impl Window {
     }
 
     pub fn offset(&mut self, available_space: ScreenSize, offset: ScreenPosition) -> Option<(&str, ScreenPosition)> {
+        self.anchor = None;
         self.position += offset;
         self.validate_position(available_space);
         self.window_class
@@ -201,20 +300,92 @@ impl Window {
             .map(|window_class| (window_class.as_str(), self.position))
     }
 
+    /// Attach the window to an area, for example the mouse position. The window
+    /// stays fully on screen, even when its size changes.
+    pub fn place_next_to(&mut self, anchor: PopupAnchor, available_space: ScreenSize) {
+        self.anchor = Some(anchor);
+        self.position = anchor.place(self.size, available_space);
+    }
+
+    /// Move and resize the window to the state stored in the window cache, for
+    /// example after switching to a different layout profile.
+    pub fn restore_state(&mut self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) {
+        let Some((position, size)) = self
+            .window_class
+            .as_deref()
+            .and_then(|window_class| window_cache.get_window_state(window_class))
+        else {
+            return;
+        };
+
+        self.position = position;
+        self.size = size;
+        self.validate_size(interface_settings, available_space);
+        self.validate_position(available_space);
+    }
+
     fn validate_position(&mut self, available_space: ScreenSize) {
         self.position = self.size_bound.validated_position(self.position, self.size, available_space);
     }
 
+    /// Windows show a resize grip in their bottom right corner if the player
+    /// can change their size.
+    fn is_resizable(&self) -> bool {
+        !self.locked && (self.size_bound.is_width_resizable() || self.size_bound.is_height_resizable())
+    }
+
+    /// Resize grip under the mouse. Grips are only available on the axes that
+    /// the size bound of the window allows to be resized.
+    pub fn hovered_resize_grip(&self, mouse_position: ScreenPosition, grip_size: ScreenSize) -> Option<ResizeGrip> {
+        let offset = mouse_position - self.position;
+
+        if offset.width < 0.0 || offset.height < 0.0 || offset.width > self.size.width || offset.height > self.size.height {
+            return None;
+        }
+
+        let horizontal = self.size_bound.is_width_resizable();
+        let vertical = self.size_bound.is_height_resizable();
+
+        let grip = ResizeGrip {
+            left: horizontal && offset.width <= grip_size.width,
+            right: horizontal && offset.width >= self.size.width - grip_size.width,
+            bottom: vertical && offset.height >= self.size.height - grip_size.height,
+        };
+
+        (grip != ResizeGrip::default()).then_some(grip)
+    }
+
     pub fn resize(
         &mut self,
         interface_settings: &InterfaceSettings,
         _theme: &InterfaceTheme,
         available_space: ScreenSize,
-        growth: ScreenSize,
-    ) -> (Option<&str>, ScreenSize) {
+        grip: ResizeGrip,
+        mouse_delta: ScreenSize,
+    ) -> (Option<&str>, ScreenPosition, ScreenSize) {
+        let previous_width = self.size.width;
+
+        let growth = ScreenSize {
+            width: match grip {
+                ResizeGrip { left: true, .. } => -mouse_delta.width,
+                ResizeGrip { right: true, .. } => mouse_delta.width,
+                _ => 0.0,
+            },
+            height: match grip.bottom {
+                true => mouse_delta.height,
+                false => 0.0,
+            },
+        };
+
         self.size += growth;
         self.validate_size(interface_settings, available_space);
-        (self.window_class.as_deref(), self.size)
+
+        // NOTE: When dragging the left edge, the right edge has to stay in place.
+        if grip.left {
+            self.position.left -= self.size.width - previous_width;
+        }
+
+        (self.window_class.as_deref(), self.position, self.size)
     }
 
     fn validate_size(&mut self, interface_settings: &InterfaceSettings, available_space: ScreenSize) {
@@ -235,6 +406,15 @@ impl Window {
         self.popup_element = None;
     }
 
+    /// Position of the popup element below or above the element that opened
+    /// it.
+    fn popup_position(&self) -> Option<ScreenPosition> {
+        let (popup, position_tracker, size_tracker) = self.popup_element.as_ref()?;
+        let anchor = PopupAnchor::new(position_tracker()?, size_tracker()?);
+
+        Some(anchor.place(popup.borrow().get_state().cached_size, self.available_space))
+    }
+
     pub fn render(
         &self,
         render_target: &mut <InterfaceRenderer as Renderer>::Target,
@@ -246,13 +426,29 @@ impl Window {
         focused_element: Option<&dyn Element>,
         mouse_mode: &MouseInputMode,
     ) {
+        if self.hidden {
+            return;
+        }
+
+        // NOTE: Windows fade in and are revealed from their center when they are
+        // opened. The content is not scaled, only the clip grows to the full size.
+        let open_progress = self.open_animation.get(interface_settings);
+        let reveal = WINDOW_OPEN_REVEAL.interpolate(1.0, open_progress);
+        let horizontal_inset = self.size.width * (1.0 - reveal) / 2.0;
+        let vertical_inset = self.size.height * (1.0 - reveal) / 2.0;
+
         let screen_clip = ScreenClip {
-            left: self.position.left,
-            top: self.position.top,
-            right: self.position.left + self.size.width,
-            bottom: self.position.top + self.size.height,
+            left: self.position.left + horizontal_inset,
+            top: self.position.top + vertical_inset,
+            right: self.position.left + self.size.width - horizontal_inset,
+            bottom: self.position.top + self.size.height - vertical_inset,
+            corner_radius: theme.window.corner_radius.get(),
         };
 
+        // NOTE: Everything in the window, not only the background, fades with the
+        // window.
+        renderer.set_opacity(open_progress * self.fade_animation.get(interface_settings));
+
         renderer.render_rectangle(
             render_target,
             self.position,
@@ -262,6 +458,26 @@ impl Window {
             self.get_background_color(theme),
         );
 
+        if self.is_resizable() {
+            let grip_size = theme.window.resize_grip_size.get() * interface_settings.scaling.get();
+            let grip_position = ScreenPosition {
+                left: self.position.left + self.size.width - grip_size.width,
+                top: self.position.top + self.size.height - grip_size.height,
+            };
+
+            let grip_color = theme.window.resize_grip_color.get();
+
+            // NOTE: The screen clip rounds the grip off along the corner of the window.
+            renderer.render_rectangle(
+                render_target,
+                grip_position,
+                grip_size,
+                screen_clip,
+                CornerRadius::default(),
+                grip_color,
+            );
+        }
+
         self.elements.iter().for_each(|element| {
             element.borrow().render(
                 render_target,
@@ -278,8 +494,18 @@ impl Window {
             )
         });
 
-        if let Some((popup, position_tracker, _)) = &self.popup_element {
-            let position = position_tracker().unwrap(); // FIX: Don't unwrap obviously
+        if let Some((popup, ..)) = &self.popup_element {
+            let position = self.popup_position().unwrap(); // FIX: Don't unwrap obviously
+            let size = popup.borrow().get_state().cached_size;
+
+            // NOTE: The popup may extend past the borders of the window.
+            let screen_clip = ScreenClip {
+                left: position.left,
+                top: position.top,
+                right: position.left + size.width,
+                bottom: position.top + size.height,
+                ..Default::default()
+            };
 
             popup.borrow().render(
                 render_target,
@@ -295,6 +521,8 @@ impl Window {
                 false,
             );
         };
+
+        renderer.set_opacity(1.0);
     }
 }
 
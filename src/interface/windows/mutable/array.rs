@@ -15,7 +15,7 @@ where
     [(); T::ELEMENT_COUNT]:,
 {
     name: String,
-    reference: &'static T,
+    reference: TrackedState<T>,
     minimum_value: T,
     maximum_value: T,
     change_event: Option<ChangeEvent>,
@@ -33,9 +33,20 @@ where
         let minimum_value = self.minimum_value.get_inner();
         let maximum_value = self.maximum_value.get_inner();
 
-        for (index, (label, pointer)) in self.reference.get_array_fields().into_iter().enumerate() {
-            elements.push(Headline::new(label, Headline::DEFAULT_SIZE).wrap());
-            elements.push(Slider::new(pointer, minimum_value[index], maximum_value[index], self.change_event).wrap());
+        let field_names = self.reference.borrow().get_field_names();
+
+        for (index, label) in field_names.into_iter().enumerate() {
+            elements.push(Headline::new(label.to_owned(), Headline::DEFAULT_SIZE).wrap());
+            elements.push(
+                Slider::array_element(
+                    self.reference.clone(),
+                    index,
+                    minimum_value[index],
+                    maximum_value[index],
+                    self.change_event,
+                )
+                .wrap(),
+            );
         }
 
         WindowBuilder::new()
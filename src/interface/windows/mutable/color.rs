@@ -6,22 +6,23 @@ use crate::interface::*;
 #[derive(new)]
 pub struct ColorWindow {
     name: String,
-    reference: &'static Color,
+    reference: TrackedState<Color>,
     change_event: Option<ChangeEvent>,
 }
 
 impl PrototypeWindow for ColorWindow {
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let rgb_elements = vec![
-            Headline::new("red".to_string(), Headline::DEFAULT_SIZE).wrap(),
-            Slider::new(&self.reference.red, 0.0, 1.0, self.change_event).wrap(),
-            Headline::new("green".to_string(), Headline::DEFAULT_SIZE).wrap(),
-            Slider::new(&self.reference.green, 0.0, 1.0, self.change_event).wrap(),
-            Headline::new("blue".to_string(), Headline::DEFAULT_SIZE).wrap(),
-            Slider::new(&self.reference.blue, 0.0, 1.0, self.change_event).wrap(),
-            Headline::new("alpha".to_string(), Headline::DEFAULT_SIZE).wrap(),
-            Slider::new(&self.reference.alpha, 0.0, 1.0, self.change_event).wrap(),
-        ];
+        let field_names = self.reference.borrow().get_field_names();
+        let rgb_elements = field_names
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, label)| {
+                [
+                    Headline::new(label.to_owned(), Headline::DEFAULT_SIZE).wrap(),
+                    Slider::array_element(self.reference.clone(), index, 0.0, 1.0, self.change_event).wrap(),
+                ]
+            })
+            .collect();
 
         let elements = vec![Expandable::new("rgb".to_string(), rgb_elements, true).wrap()];
 
@@ -9,7 +9,7 @@ use crate::interface::*;
 #[derive(new)]
 pub struct NumberWindow<T: 'static> {
     name: String,
-    reference: &'static T,
+    reference: TrackedState<T>,
     minimum_value: T,
     maximum_value: T,
     change_event: Option<ChangeEvent>,
@@ -19,7 +19,13 @@ impl<T: Zero + NumOps + NumCast + Copy + PartialOrd + 'static> PrototypeWindow f
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
         let elements = vec![
             Headline::new("value".to_string(), Headline::DEFAULT_SIZE).wrap(),
-            Slider::new(self.reference, self.minimum_value, self.maximum_value, self.change_event).wrap(),
+            Slider::new(
+                self.reference.clone(),
+                self.minimum_value,
+                self.maximum_value,
+                self.change_event,
+            )
+            .wrap(),
         ];
 
         WindowBuilder::new()
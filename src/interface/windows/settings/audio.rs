@@ -1,10 +1,20 @@
-use crate::interface::{InterfaceSettings, PrototypeWindow, ScreenSize, SizeBound, Window, WindowBuilder, WindowCache};
+use procedural::dimension_bound;
 
-#[derive(Default)]
-pub struct AudioSettingsWindow;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct AudioSettingsWindow {
+    mute: TrackedState<bool>,
+    background_music_volume: TrackedState<f32>,
+    sound_effect_volume: TrackedState<f32>,
+}
 
 impl AudioSettingsWindow {
     pub const WINDOW_CLASS: &'static str = "audio_settings";
+
+    fn volume_options() -> Vec<(&'static str, f32)> {
+        vec![("Off", 0.0), ("25%", 0.25), ("50%", 0.5), ("75%", 0.75), ("100%", 1.0)]
+    }
 }
 
 impl PrototypeWindow for AudioSettingsWindow {
@@ -13,7 +23,30 @@ impl PrototypeWindow for AudioSettingsWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let elements = vec![];
+        let elements = vec![
+            CheckboxBuilder::new()
+                .with_text("Mute")
+                .with_state(self.mute.clone())
+                .build()
+                .wrap(),
+            Text::default()
+                .with_text("Background music")
+                .with_width(dimension_bound!(50%))
+                .wrap(),
+            PickList::default()
+                .with_options(Self::volume_options())
+                .with_selected(self.background_music_volume.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default().with_text("Sound effects").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(Self::volume_options())
+                .with_selected(self.sound_effect_volume.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ];
 
         WindowBuilder::new()
             .with_title("Audio Settings".to_string())
@@ -0,0 +1,55 @@
+use procedural::dimension_bound;
+
+use crate::interface::*;
+
+#[derive(new)]
+pub struct GeneralSettingsWindow {
+    away_timeout: TrackedState<Option<usize>>,
+    sit_while_away: TrackedState<bool>,
+    check_for_updates: TrackedState<bool>,
+}
+
+impl GeneralSettingsWindow {
+    pub const WINDOW_CLASS: &'static str = "general_settings";
+}
+
+impl PrototypeWindow for GeneralSettingsWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            Text::default().with_text("Away after").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("1 minute", Some(1)),
+                    ("5 minutes", Some(5)),
+                    ("15 minutes", Some(15)),
+                    ("Never", None),
+                ])
+                .with_selected(self.away_timeout.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Sit down while away")
+                .with_state(self.sit_while_away.clone())
+                .build()
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Check for updates on startup")
+                .with_state(self.check_for_updates.clone())
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("General Settings".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(SizeBound::DEFAULT_UNBOUNDED)
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
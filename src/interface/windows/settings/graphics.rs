@@ -1,17 +1,109 @@
 use procedural::dimension_bound;
 
-use crate::graphics::{PresentModeInfo, ShadowDetail};
-use crate::input::UserEvent;
+use crate::graphics::{
+    PresentModeInfo, SamplerCategory, SamplerConfiguration, SamplerSettings, ShadowDetail, SpriteFiltering, TextureAddressMode,
+    TextureFiltering,
+};
 use crate::interface::*;
+use crate::system::build_version;
+use crate::world::{CastPreviewMode, TargetPriority};
 
 #[derive(new)]
 pub struct GraphicsSettingsWindow {
     present_mode_info: PresentModeInfo,
+    frame_limit: TrackedState<bool>,
     shadow_detail: TrackedState<ShadowDetail>,
+    speech_bubble_range: TrackedState<Option<usize>>,
+    entity_render_distance: TrackedState<Option<usize>>,
+    maximum_rendered_entities: TrackedState<Option<usize>>,
+    sprite_filtering: TrackedState<SpriteFiltering>,
+    sampler_configuration: TrackedState<SamplerConfiguration>,
+    show_speech_bubbles: TrackedState<bool>,
+    show_path_preview: TrackedState<bool>,
+    show_quest_markers: TrackedState<bool>,
+    preload_adjacent_maps: TrackedState<bool>,
+    camera_shake: TrackedState<f32>,
+    hit_stop: TrackedState<bool>,
+    cast_previews: TrackedState<CastPreviewMode>,
+    target_priority: TrackedState<TargetPriority>,
+    animations: TrackedState<bool>,
+    streamer_mode: TrackedState<bool>,
+    combat_window_mode: TrackedState<CombatWindowMode>,
 }
 
 impl GraphicsSettingsWindow {
     pub const WINDOW_CLASS: &'static str = "graphics_settings";
+
+    /// Pick lists for the sampler of a single category. Each option gets its
+    /// own state so they can be picked independently, and every change is
+    /// written back to the sampler configuration.
+    fn sampler_element(&self, display: &str, category: SamplerCategory) -> ElementCell {
+        let settings = self.sampler_configuration.borrow().get(category);
+        let filtering = TrackedState::new(settings.filtering);
+        let address_mode = TrackedState::new(settings.address_mode);
+        let anisotropy = TrackedState::new(settings.anisotropy);
+
+        let apply_settings = {
+            let mut sampler_configuration = self.sampler_configuration.clone();
+            let filtering = filtering.clone();
+            let address_mode = address_mode.clone();
+            let anisotropy = anisotropy.clone();
+
+            Box::new(move || {
+                let settings = SamplerSettings {
+                    filtering: filtering.get(),
+                    address_mode: address_mode.get(),
+                    anisotropy: anisotropy.get(),
+                };
+
+                sampler_configuration.with_mut(|configuration| {
+                    *configuration.get_mut(category) = settings;
+                    ValueState::Mutated(())
+                });
+
+                Vec::new()
+            })
+        };
+
+        let elements = vec![
+            Text::default().with_text("Filtering").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Nearest", TextureFiltering::Nearest),
+                    ("Linear", TextureFiltering::Linear),
+                ])
+                .with_selected(filtering)
+                .with_event(apply_settings.clone())
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default().with_text("Address mode").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Clamp", TextureAddressMode::ClampToEdge),
+                    ("Repeat", TextureAddressMode::Repeat),
+                    ("Mirror", TextureAddressMode::MirroredRepeat),
+                ])
+                .with_selected(address_mode)
+                .with_event(apply_settings.clone())
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default().with_text("Anisotropy").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Off", None),
+                    ("2x", Some(2)),
+                    ("4x", Some(4)),
+                    ("8x", Some(8)),
+                    ("16x", Some(16)),
+                ])
+                .with_selected(anisotropy)
+                .with_event(apply_settings)
+                .with_width(dimension_bound!(!))
+                .wrap(),
+        ];
+
+        Expandable::new(display.to_owned(), elements, false).wrap()
+    }
 }
 
 impl PrototypeWindow for GraphicsSettingsWindow {
@@ -20,7 +112,7 @@ impl PrototypeWindow for GraphicsSettingsWindow {
     }
 
     fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
-        let mut elements = vec![
+        let mut rendering_elements = vec![
             Text::default().with_text("Shadow detail").with_width(dimension_bound!(50%)).wrap(),
             PickList::default()
                 .with_options(vec![
@@ -33,23 +125,178 @@ impl PrototypeWindow for GraphicsSettingsWindow {
                 .with_event(Box::new(Vec::new))
                 .with_width(dimension_bound!(!))
                 .wrap(),
-            interface_settings.to_element("Interface settings".to_string()),
+            Text::default().with_text("Sprite filtering").wrap(),
+            RadioGroupBuilder::new()
+                .with_options(vec![
+                    ("Nearest", SpriteFiltering::Nearest),
+                    ("Linear", SpriteFiltering::Linear),
+                    ("Supersampled", SpriteFiltering::Supersampled),
+                ])
+                .with_selected(self.sprite_filtering.clone())
+                .build()
+                .wrap(),
+            self.sampler_element("Ground textures", SamplerCategory::Ground),
+            self.sampler_element("Model textures", SamplerCategory::Model),
+            self.sampler_element("Sprite textures", SamplerCategory::Sprite),
+            self.sampler_element("Interface text", SamplerCategory::Interface),
+            self.sampler_element("Shadows", SamplerCategory::Shadow),
+        ];
+
+        let world_elements = vec![
+            CheckboxBuilder::new()
+                .with_text("Show speech bubbles")
+                .with_state(self.show_speech_bubbles.clone())
+                .build()
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Show path preview")
+                .with_state(self.show_path_preview.clone())
+                .build()
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Show quest markers")
+                .with_state(self.show_quest_markers.clone())
+                .build()
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Preload adjacent maps")
+                .with_state(self.preload_adjacent_maps.clone())
+                .build()
+                .wrap(),
+            Text::default()
+                .with_text("Speech bubble range")
+                .with_width(dimension_bound!(50%))
+                .wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Close", Some(5)),
+                    ("Medium", Some(10)),
+                    ("Far", Some(15)),
+                    ("Unlimited", None),
+                ])
+                .with_selected(self.speech_bubble_range.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default()
+                .with_text("Entity render distance")
+                .with_width(dimension_bound!(50%))
+                .wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Close", Some(10)),
+                    ("Medium", Some(20)),
+                    ("Far", Some(30)),
+                    ("Unlimited", None),
+                ])
+                .with_selected(self.entity_render_distance.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default()
+                .with_text("Maximum entities")
+                .with_width(dimension_bound!(50%))
+                .wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("50", Some(50)),
+                    ("100", Some(100)),
+                    ("200", Some(200)),
+                    ("Unlimited", None),
+                ])
+                .with_selected(self.maximum_rendered_entities.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default().with_text("Camera shake").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![("Off", 0.0), ("Subtle", 0.5), ("Strong", 1.0)])
+                .with_selected(self.camera_shake.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            CheckboxBuilder::new()
+                .with_text("Hit-stop on heavy hits")
+                .with_state(self.hit_stop.clone())
+                .build()
+                .wrap(),
+            Text::default().with_text("Cast previews").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("Off", CastPreviewMode::Off),
+                    ("Monsters", CastPreviewMode::MonstersOnly),
+                    ("Everyone", CastPreviewMode::Everyone),
+                ])
+                .with_selected(self.cast_previews.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
+            Text::default().with_text("Click priority").with_width(dimension_bound!(50%)).wrap(),
+            PickList::default()
+                .with_options(vec![
+                    ("NPCs first", TargetPriority::npcs_first()),
+                    ("Monsters first", TargetPriority::monsters_first()),
+                    ("Items first", TargetPriority::items_first()),
+                ])
+                .with_selected(self.target_priority.clone())
+                .with_event(Box::new(Vec::new))
+                .with_width(dimension_bound!(!))
+                .wrap(),
         ];
 
         // TODO: Instead of not showing this option, disable the checkbox and add a
         // tooltip
         if self.present_mode_info.supports_immediate || self.present_mode_info.supports_mailbox {
-            elements.insert(
+            rendering_elements.insert(
                 0,
-                StateButtonBuilder::new()
+                CheckboxBuilder::new()
                     .with_text("Framerate limit")
-                    .with_selector(|state_provider| state_provider.graphics_settings.frame_limit)
-                    .with_event(UserEvent::ToggleFrameLimit)
+                    .with_state(self.frame_limit.clone())
                     .build()
                     .wrap(),
             );
         }
 
+        let tabs = vec![
+            ("Rendering".to_string(), rendering_elements),
+            ("World".to_string(), world_elements),
+            ("Interface".to_string(), vec![
+                interface_settings.to_element("Interface settings".to_string()),
+                CheckboxBuilder::new()
+                    .with_text("Animations")
+                    .with_state(self.animations.clone())
+                    .build()
+                    .wrap(),
+                CheckboxBuilder::new()
+                    .with_text("Streamer mode")
+                    .with_state(self.streamer_mode.clone())
+                    .build()
+                    .wrap(),
+                Text::default()
+                    .with_text("Windows in combat")
+                    .with_width(dimension_bound!(50%))
+                    .wrap(),
+                PickList::default()
+                    .with_options(vec![
+                        ("Unchanged", CombatWindowMode::Unchanged),
+                        ("Faded", CombatWindowMode::Faded),
+                        ("Hidden", CombatWindowMode::Hidden),
+                    ])
+                    .with_selected(self.combat_window_mode.clone())
+                    .with_event(Box::new(Vec::new))
+                    .with_width(dimension_bound!(!))
+                    .wrap(),
+            ]),
+        ];
+        let elements = vec![
+            TabContainer::new(tabs, window_cache.get_active_tab(Self::WINDOW_CLASS)).wrap(),
+            // NOTE: Shown so players can include the exact build in bug reports.
+            Text::default()
+                .with_text(build_version())
+                .with_foreground_color(|theme| theme.button.disabled_foreground_color.get())
+                .wrap(),
+        ];
+
         WindowBuilder::new()
             .with_title("Graphics Settings".to_string())
             .with_class(Self::WINDOW_CLASS.to_string())
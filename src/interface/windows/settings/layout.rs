@@ -0,0 +1,117 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::interface::*;
+
+/// Window for saving, switching, exporting and importing named interface
+/// layouts.
+#[derive(new)]
+pub struct LayoutProfilesWindow {
+    profile_names: Vec<String>,
+}
+
+impl LayoutProfilesWindow {
+    pub const WINDOW_CLASS: &'static str = "layout_profiles";
+}
+
+impl PrototypeWindow for LayoutProfilesWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let profile_name = TrackedState::<String>::default();
+        let import_path = TrackedState::<String>::default();
+
+        let save_action = {
+            let mut profile_name = profile_name.clone();
+
+            Box::new(move || {
+                let taken_string = profile_name.take();
+
+                (!taken_string.is_empty())
+                    .then_some(vec![ClickAction::Event(UserEvent::SaveLayoutProfile(taken_string))])
+                    .unwrap_or_default()
+            })
+        };
+
+        let import_action = {
+            let mut import_path = import_path.clone();
+
+            Box::new(move || {
+                let taken_string = import_path.take();
+
+                (!taken_string.is_empty())
+                    .then_some(vec![ClickAction::Event(UserEvent::ImportLayoutProfile(taken_string))])
+                    .unwrap_or_default()
+            })
+        };
+
+        let mut elements = vec![
+            InputFieldBuilder::new()
+                .with_state(profile_name)
+                .with_ghost_text("Profile name")
+                .with_enter_action(save_action.clone())
+                .with_length(32)
+                .with_width_bound(dimension_bound!(75%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Save")
+                .with_event(save_action)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+            InputFieldBuilder::new()
+                .with_state(import_path)
+                .with_ghost_text("Path to layout file")
+                .with_enter_action(import_action.clone())
+                .with_length(128)
+                .with_width_bound(dimension_bound!(75%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Import")
+                .with_event(import_action)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        for name in &self.profile_names {
+            elements.push(Text::default().with_text(name.clone()).with_width(dimension_bound!(40%)).wrap());
+            elements.push(
+                ButtonBuilder::new()
+                    .with_text("Load")
+                    .with_event(UserEvent::LoadLayoutProfile(name.clone()))
+                    .with_width_bound(dimension_bound!(20%))
+                    .build()
+                    .wrap(),
+            );
+            elements.push(
+                ButtonBuilder::new()
+                    .with_text("Export")
+                    .with_event(UserEvent::ExportLayoutProfile(name.clone()))
+                    .with_width_bound(dimension_bound!(20%))
+                    .build()
+                    .wrap(),
+            );
+            elements.push(
+                ButtonBuilder::new()
+                    .with_text("Delete")
+                    .with_event(UserEvent::DeleteLayoutProfile(name.clone()))
+                    .with_width_bound(dimension_bound!(!))
+                    .build()
+                    .wrap(),
+            );
+        }
+
+        WindowBuilder::new()
+            .with_title("Layout profiles".to_string())
+            .closable()
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(300 > 350 < 500, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -1,9 +1,13 @@
 mod audio;
+mod general;
 mod graphics;
+mod layout;
 #[cfg(feature = "debug")]
 mod render;
 
 pub use self::audio::AudioSettingsWindow;
+pub use self::general::GeneralSettingsWindow;
 pub use self::graphics::GraphicsSettingsWindow;
+pub use self::layout::LayoutProfilesWindow;
 #[cfg(feature = "debug")]
 pub use self::render::RenderSettingsWindow;
@@ -90,6 +90,11 @@ fn buffers_expandable() -> ElementCell {
         render_state_button!("shadow buffer", UserEvent::ToggleShowShadowBuffer, show_shadow_buffer),
         render_state_button!("picker buffer", UserEvent::ToggleShowPickerBuffer, show_picker_buffer),
         render_state_button!("font atlas", UserEvent::ToggleShowFontAtlas, show_font_atlas),
+        ButtonBuilder::new()
+            .with_text("capture frame")
+            .with_event(UserEvent::CaptureFrame)
+            .build()
+            .wrap(),
     ];
 
     Expandable::new("buffers".to_string(), buttons, true).wrap()
@@ -0,0 +1,52 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::network::{EntityId, ShopDealType};
+
+#[derive(new)]
+pub struct ShopDealWindow {
+    npc_id: EntityId,
+}
+
+impl ShopDealWindow {
+    pub const WINDOW_CLASS: &'static str = "shop_deal";
+}
+
+impl PrototypeWindow for ShopDealWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            ButtonBuilder::new()
+                .with_text("buy")
+                .with_event(UserEvent::SelectShopDealType {
+                    npc_id: self.npc_id,
+                    deal_type: ShopDealType::Buy,
+                })
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("sell")
+                .with_event(UserEvent::SelectShopDealType {
+                    npc_id: self.npc_id,
+                    deal_type: ShopDealType::Sell,
+                })
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Shop".to_string())
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(250 > 250 < 250, ?))
+            .with_elements(elements)
+            .closable()
+            .build(window_cache, interface_settings, available_space)
+    }
+}
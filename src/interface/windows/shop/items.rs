@@ -0,0 +1,70 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::{CharacterStatsData, ShopData};
+use crate::network::ShopDealType;
+
+#[derive(new)]
+pub struct ShopWindow {
+    data: Remote<ShopData>,
+    stats: Remote<CharacterStatsData>,
+}
+
+impl ShopWindow {
+    pub const WINDOW_CLASS: &'static str = "shop";
+}
+
+impl PrototypeWindow for ShopWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let (title, confirm_text) = {
+            let data = self.data.borrow();
+
+            match (data.deal_type, &data.vendor) {
+                (ShopDealType::Buy, Some(vendor)) => (vendor.title.clone(), "buy"),
+                (ShopDealType::Buy, None) => ("Buy".to_owned(), "buy"),
+                (ShopDealType::Sell, _) => ("Sell".to_owned(), "sell"),
+            }
+        };
+
+        let has_no_selection = {
+            let data = self.data.clone();
+            move || !data.borrow().has_selection()
+        };
+
+        let elements = vec![
+            ScrollView::new(
+                vec![ShopContainer::new(self.data.clone(), self.stats.clone()).wrap()],
+                size_bound!(100%, ? < 400),
+            )
+            .wrap(),
+            ButtonBuilder::new()
+                .with_text(confirm_text)
+                .with_event(UserEvent::CompleteShopDeal)
+                .with_disabled_selector(has_no_selection)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("cancel")
+                .with_event(UserEvent::CloseShop)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window can't be closed directly, so the shop is always
+        // cleared when it disappears.
+        WindowBuilder::new()
+            .with_title(title)
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(400 > 400 < 600, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,5 @@
+mod deal;
+mod items;
+
+pub use self::deal::ShopDealWindow;
+pub use self::items::ShopWindow;
@@ -0,0 +1,5 @@
+mod offers;
+mod request;
+
+pub use self::offers::TradeWindow;
+pub use self::request::TradeRequestWindow;
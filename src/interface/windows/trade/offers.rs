@@ -0,0 +1,98 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::TradeData;
+
+#[derive(new)]
+pub struct TradeWindow {
+    data: Remote<TradeData>,
+}
+
+impl TradeWindow {
+    pub const WINDOW_CLASS: &'static str = "trade";
+}
+
+impl PrototypeWindow for TradeWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let zeny = TrackedState::<String>::default();
+
+        let zeny_action = {
+            let mut zeny = zeny.clone();
+
+            Box::new(move || {
+                zeny.take()
+                    .parse::<u32>()
+                    .map(|amount| vec![ClickAction::Event(UserEvent::OfferTradeZeny(amount))])
+                    .unwrap_or_default()
+            })
+        };
+
+        let is_locked = {
+            let data = self.data.clone();
+            move || data.borrow().own_offer.is_locked
+        };
+
+        let cannot_confirm = {
+            let data = self.data.clone();
+            move || !data.borrow().can_confirm()
+        };
+
+        let elements = vec![
+            TradeContainer::new(self.data.clone(), false).wrap(),
+            TradeContainer::new(self.data.clone(), true).wrap(),
+            InputFieldBuilder::new()
+                .with_state(zeny)
+                .with_ghost_text("Zeny")
+                .with_enter_action(zeny_action.clone())
+                .with_length(10)
+                .with_width_bound(dimension_bound!(60%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Add zeny")
+                .with_event(zeny_action)
+                .with_disabled_selector(is_locked.clone())
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Lock")
+                .with_event(UserEvent::LockTrade)
+                .with_disabled_selector(is_locked)
+                .with_width_bound(dimension_bound!(33%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Trade")
+                .with_event(UserEvent::RequestConfirmation {
+                    text: "Do you really want to complete this trade?".to_owned(),
+                    event: Box::new(UserEvent::ConfirmTrade),
+                })
+                .with_disabled_selector(cannot_confirm)
+                .with_width_bound(dimension_bound!(33%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Cancel")
+                .with_event(UserEvent::CancelTrade)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window can't be closed directly, since the trade has to be
+        // canceled through the map server.
+        WindowBuilder::new()
+            .with_title("Trade".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(300 > 300 < 300, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
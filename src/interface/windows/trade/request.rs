@@ -0,0 +1,54 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::graphics::Color;
+use crate::input::UserEvent;
+use crate::interface::*;
+
+#[derive(new)]
+pub struct TradeRequestWindow {
+    name: String,
+    base_level: u16,
+}
+
+impl TradeRequestWindow {
+    pub const WINDOW_CLASS: &'static str = "trade_request";
+}
+
+impl PrototypeWindow for TradeRequestWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            Text::default()
+                .with_text(self.name.clone())
+                .with_foreground_color(|_| Color::rgb_u8(255, 170, 0))
+                .alias_in_streamer_mode()
+                .wrap(),
+            Text::default()
+                .with_text(format!("(Lv. {}) wants to trade with you", self.base_level))
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("reject")
+                .with_event(UserEvent::RejectTradeRequest)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("accept")
+                .with_event(UserEvent::AcceptTradeRequest)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ];
+
+        WindowBuilder::new()
+            .with_title("Trade request".to_string())
+            .with_class(Self::WINDOW_CLASS.to_owned())
+            .with_size_bound(size_bound!(250 > 250 < 250, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
@@ -0,0 +1,5 @@
+mod own;
+mod setup;
+
+pub use self::own::OwnVendingWindow;
+pub use self::setup::VendingSetupWindow;
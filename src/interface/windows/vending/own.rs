@@ -0,0 +1,42 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+
+/// Shown while the player has a vending shop open.
+#[derive(new)]
+pub struct OwnVendingWindow {
+    title: String,
+}
+
+impl OwnVendingWindow {
+    pub const WINDOW_CLASS: &'static str = "own_vending";
+}
+
+impl PrototypeWindow for OwnVendingWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let elements = vec![
+            Text::default().with_text(self.title.clone()).wrap(),
+            ButtonBuilder::new()
+                .with_text("Close shop")
+                .with_event(UserEvent::CloseVending)
+                .with_width_bound(dimension_bound!(100%))
+                .build()
+                .wrap(),
+        ];
+
+        // NOTE: The window can't be closed directly, since the shop has to be
+        // closed through the map server.
+        WindowBuilder::new()
+            .with_title("Vending".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(200 > 250 < 400, ?))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
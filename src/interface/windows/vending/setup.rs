@@ -0,0 +1,135 @@
+use derive_new::new;
+use procedural::{dimension_bound, size_bound};
+
+use crate::input::UserEvent;
+use crate::interface::*;
+use crate::inventory::Item;
+
+/// Maximum number of characters in the title of a vending shop.
+const TITLE_LENGTH: usize = 80;
+/// The map server rejects prices above one billion zeny.
+const MAXIMUM_PRICE: u32 = 1_000_000_000;
+
+/// Lets the player pick the items from the cart to sell and set their prices.
+#[derive(new)]
+pub struct VendingSetupWindow {
+    /// The items in the cart when the vending skill was used.
+    items: Vec<Item>,
+    /// Maximum number of different items in the shop.
+    slot_count: u16,
+}
+
+impl VendingSetupWindow {
+    pub const WINDOW_CLASS: &'static str = "vending_setup";
+}
+
+impl PrototypeWindow for VendingSetupWindow {
+    fn window_class(&self) -> Option<&str> {
+        Self::WINDOW_CLASS.into()
+    }
+
+    fn to_window(&self, window_cache: &WindowCache, interface_settings: &InterfaceSettings, available_space: ScreenSize) -> Window {
+        let title = TrackedState::<String>::default();
+        let rows: Vec<(Item, TrackedState<String>, TrackedState<String>)> = self
+            .items
+            .iter()
+            .map(|item| (item.clone(), TrackedState::default(), TrackedState::default()))
+            .collect();
+
+        let open_action = {
+            let title = title.clone();
+            let rows = rows.clone();
+            let slot_count = self.slot_count as usize;
+
+            Box::new(move || {
+                let title = title.borrow().trim().to_owned();
+
+                // NOTE: Items without a valid price are not put up for sale. An empty
+                // amount field sells the entire stack.
+                let items: Vec<_> = rows
+                    .iter()
+                    .filter_map(|(item, amount, price)| {
+                        let price = price.borrow().trim().parse::<u32>().ok()?;
+                        let amount_text = amount.borrow().trim().to_owned();
+                        let amount = match amount_text.is_empty() {
+                            true => item.amount,
+                            false => amount_text.parse::<u16>().ok()?.min(item.amount),
+                        };
+
+                        (price > 0 && price <= MAXIMUM_PRICE && amount > 0).then_some((item.index, amount, price))
+                    })
+                    .take(slot_count)
+                    .collect();
+
+                if title.is_empty() || items.is_empty() {
+                    return Vec::new();
+                }
+
+                vec![ClickAction::Event(UserEvent::OpenVending { title, items })]
+            })
+        };
+
+        let mut elements = vec![
+            InputFieldBuilder::new()
+                .with_state(title)
+                .with_ghost_text("Shop title")
+                .with_enter_action(Box::new(Vec::new))
+                .with_length(TITLE_LENGTH)
+                .build()
+                .wrap(),
+            Text::default()
+                .with_text(format!("Up to {} items can be sold.", self.slot_count))
+                .wrap(),
+        ];
+
+        elements.extend(rows.into_iter().flat_map(|(item, amount, price)| {
+            let name = item.info.name.clone();
+            let stack_size = item.amount;
+
+            [
+                ItemBox::new(Some(item), ItemSource::Cart, Box::new(|_| false)).wrap(),
+                Text::default().with_text(name).with_width(dimension_bound!(40%)).wrap(),
+                InputFieldBuilder::new()
+                    .with_state(amount)
+                    .with_ghost_text(stack_size.to_string())
+                    .with_enter_action(Box::new(Vec::new))
+                    .with_length(5)
+                    .with_width_bound(dimension_bound!(20%))
+                    .build()
+                    .wrap(),
+                InputFieldBuilder::new()
+                    .with_state(price)
+                    .with_ghost_text("Price")
+                    .with_enter_action(Box::new(Vec::new))
+                    .with_length(10)
+                    .with_width_bound(dimension_bound!(!))
+                    .build()
+                    .wrap(),
+            ]
+        }));
+
+        elements.extend([
+            ButtonBuilder::new()
+                .with_text("Open shop")
+                .with_event(open_action)
+                .with_width_bound(dimension_bound!(50%))
+                .build()
+                .wrap(),
+            ButtonBuilder::new()
+                .with_text("Cancel")
+                .with_event(UserEvent::CancelVendingSetup)
+                .with_width_bound(dimension_bound!(!))
+                .build()
+                .wrap(),
+        ]);
+
+        // NOTE: The window can't be closed directly, so the setup is always
+        // canceled when it disappears.
+        WindowBuilder::new()
+            .with_title("Vending".to_string())
+            .with_class(Self::WINDOW_CLASS.to_string())
+            .with_size_bound(size_bound!(350 > 450 < 600, ? < 80%))
+            .with_elements(elements)
+            .build(window_cache, interface_settings, available_space)
+    }
+}
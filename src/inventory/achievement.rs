@@ -0,0 +1,131 @@
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::loaders::{AchievementInfo, ScriptLoader};
+use crate::network::{AchievementInformation, AchievementLevel};
+
+#[derive(Clone)]
+pub struct AchievementEntry {
+    pub achievement_id: u32,
+    pub info: AchievementInfo,
+    pub is_completed: bool,
+    pub is_rewarded: bool,
+    pub objectives: [u32; 10],
+}
+
+impl AchievementEntry {
+    fn new(script_loader: &ScriptLoader, achievement: AchievementInformation) -> Self {
+        let info = script_loader.get_achievement_info(achievement.achievement_id);
+
+        Self {
+            achievement_id: achievement.achievement_id,
+            info,
+            is_completed: achievement.is_completed,
+            is_rewarded: achievement.is_rewarded,
+            objectives: achievement.objectives,
+        }
+    }
+
+    /// Progress from zero to one. Achievements without countable objectives
+    /// are either done or not.
+    pub fn progress(&self) -> f32 {
+        let required: u32 = self.info.objective_counts.iter().sum();
+
+        if self.is_completed || required == 0 {
+            return match self.is_completed {
+                true => 1.0,
+                false => 0.0,
+            };
+        }
+
+        let done: u32 = self
+            .info
+            .objective_counts
+            .iter()
+            .zip(self.objectives)
+            .map(|(count, progress)| progress.min(*count))
+            .sum();
+
+        done as f32 / required as f32
+    }
+
+    pub fn can_claim_reward(&self) -> bool {
+        self.is_completed && !self.is_rewarded
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct AchievementData {
+    pub level: AchievementLevel,
+    /// Sorted by achievement id.
+    pub achievements: Vec<AchievementEntry>,
+}
+
+/// Achievements the player made progress on and the achievement level.
+#[derive(Default)]
+pub struct Achievements {
+    data: TrackedState<AchievementData>,
+}
+
+impl Achievements {
+    pub fn set_achievements(&mut self, script_loader: &ScriptLoader, level: AchievementLevel, achievements: Vec<AchievementInformation>) {
+        let mut achievements: Vec<_> = achievements
+            .into_iter()
+            .map(|achievement| AchievementEntry::new(script_loader, achievement))
+            .collect();
+
+        achievements.sort_by_key(|achievement| achievement.achievement_id);
+        self.data.set(AchievementData { level, achievements });
+    }
+
+    /// Returns the title of the achievement if it was completed by this
+    /// update.
+    pub fn update_achievement(
+        &mut self,
+        script_loader: &ScriptLoader,
+        level: AchievementLevel,
+        achievement: AchievementInformation,
+    ) -> Option<String> {
+        let entry = AchievementEntry::new(script_loader, achievement);
+
+        self.data.with_mut(|data| {
+            data.level = level;
+
+            let was_completed = match data
+                .achievements
+                .binary_search_by_key(&entry.achievement_id, |achievement| achievement.achievement_id)
+            {
+                Ok(position) => std::mem::replace(&mut data.achievements[position], entry.clone()).is_completed,
+                Err(position) => {
+                    data.achievements.insert(position, entry.clone());
+                    false
+                }
+            };
+
+            let completed_title = (entry.is_completed && !was_completed).then(|| entry.info.title.clone());
+            ValueState::Mutated(completed_title)
+        })
+    }
+
+    pub fn set_rewarded(&mut self, achievement_id: u32) {
+        self.data.with_mut(|data| {
+            match data
+                .achievements
+                .iter_mut()
+                .find(|achievement| achievement.achievement_id == achievement_id)
+            {
+                Some(achievement) => {
+                    achievement.is_rewarded = true;
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            }
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.data.set(AchievementData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<AchievementData> {
+        self.data.new_remote()
+    }
+}
@@ -0,0 +1,33 @@
+use crate::interface::{Remote, TrackedState};
+
+/// The zeny the player keeps in the bank. The balance is shared between all
+/// characters of an account.
+#[derive(Default)]
+pub struct Bank {
+    /// `None` until the map server sent the balance.
+    balance: TrackedState<Option<i64>>,
+    is_open: bool,
+}
+
+impl Bank {
+    pub fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    pub fn set_balance(&mut self, balance: i64) {
+        self.balance.set(Some(balance));
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn clear(&mut self) {
+        self.is_open = false;
+        self.balance.set(None);
+    }
+
+    pub fn get_balance(&self) -> Remote<Option<i64>> {
+        self.balance.new_remote()
+    }
+}
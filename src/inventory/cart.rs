@@ -0,0 +1,60 @@
+use super::{Inventory, Item};
+use crate::interface::{Remote, TrackedState};
+use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
+use crate::network::{CartInformation, InventoryItemData, ItemIndex};
+
+/// The cart of a merchant class character. The items are kept the same way
+/// as the ones in the inventory.
+#[derive(Default)]
+pub struct Cart {
+    items: Inventory,
+    /// `None` while the player doesn't have a cart.
+    information: TrackedState<Option<CartInformation>>,
+}
+
+impl Cart {
+    pub fn fill(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        item_data: Vec<InventoryItemData>,
+    ) {
+        self.items.fill(game_file_loader, texture_loader, script_loader, item_data);
+    }
+
+    pub fn add_item(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        item_data: InventoryItemData,
+    ) {
+        self.items.add_item(game_file_loader, texture_loader, script_loader, item_data);
+    }
+
+    pub fn remove_item(&mut self, index: ItemIndex, amount: u16) {
+        self.items.remove_item(index, amount);
+    }
+
+    pub fn update_information(&mut self, information: CartInformation) {
+        self.information.set(Some(information));
+    }
+
+    pub fn has_cart(&self) -> bool {
+        self.information.borrow().is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.information.set(None);
+    }
+
+    pub fn get_items(&self) -> Remote<Vec<Item>> {
+        self.items.get_items()
+    }
+
+    pub fn get_information(&self) -> Remote<Option<CartInformation>> {
+        self.information.new_remote()
+    }
+}
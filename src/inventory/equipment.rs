@@ -0,0 +1,281 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use super::{Inventory, Item};
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::network::{CharacterId, EquipPosition, ItemId, ItemIndex};
+
+const EQUIPMENT_SETS_DIRECTORY: &str = "client/equipment_sets";
+/// The map server ignores equip requests that arrive too quickly after each
+/// other, so they are sent one by one.
+const EQUIP_REQUEST_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The refinement and cards are stored as well, so duplicates of an item can
+/// be told apart.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct EquipmentSetItem {
+    item_id: ItemId,
+    refinement_level: u8,
+    cards: [u32; 4],
+    equip_position: EquipPosition,
+}
+
+impl EquipmentSetItem {
+    fn from_item(item: &Item, equip_position: EquipPosition) -> Self {
+        Self {
+            item_id: item.item_id,
+            refinement_level: item.refinement_level,
+            cards: item.cards,
+            equip_position,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EquipmentSet {
+    pub name: String,
+    items: Vec<EquipmentSetItem>,
+}
+
+/// Named sets of equipment of the current character, so the player can
+/// switch gear with a single click or hotkey.
+#[derive(Default)]
+pub struct EquipmentSets {
+    character_id: Option<CharacterId>,
+    sets: TrackedState<Vec<EquipmentSet>>,
+    pending_requests: VecDeque<(ItemIndex, EquipPosition)>,
+    last_request: Option<Instant>,
+}
+
+impl EquipmentSets {
+    fn file_path(character_id: CharacterId) -> String {
+        format!("{EQUIPMENT_SETS_DIRECTORY}/{}.ron", character_id.0)
+    }
+
+    pub fn load(&mut self, character_id: CharacterId) {
+        let path = Self::file_path(character_id);
+
+        #[cfg(feature = "debug")]
+        print_debug!("loading equipment sets from {MAGENTA}{path}{NONE}");
+
+        let sets = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default();
+
+        self.character_id = Some(character_id);
+        self.pending_requests.clear();
+        self.sets.set(sets);
+    }
+
+    fn save(&self) {
+        let Some(character_id) = self.character_id else {
+            return;
+        };
+        let path = Self::file_path(character_id);
+
+        #[cfg(feature = "debug")]
+        print_debug!("saving equipment sets to {MAGENTA}{path}{NONE}");
+
+        let data = ron::ser::to_string_pretty(&*self.sets.borrow(), PrettyConfig::new()).unwrap();
+        std::fs::create_dir_all(EQUIPMENT_SETS_DIRECTORY).expect("unable to create directory");
+        std::fs::write(path, data).expect("unable to write file");
+    }
+
+    /// Save the currently equipped items under the given name, replacing an
+    /// existing set with the same name.
+    pub fn save_set(&mut self, name: String, inventory: &Inventory) {
+        let items = inventory
+            .get_equipped_items()
+            .into_iter()
+            .map(|item| EquipmentSetItem::from_item(&item, item.equipped_position))
+            .collect();
+
+        self.sets.with_mut(|sets| {
+            sets.retain(|set| set.name != name);
+            sets.push(EquipmentSet { name, items });
+            sets.sort_by(|first, second| first.name.cmp(&second.name));
+            ValueState::Mutated(())
+        });
+
+        self.save();
+    }
+
+    pub fn delete_set(&mut self, name: &str) {
+        self.sets.with_mut(|sets| {
+            sets.retain(|set| set.name != name);
+            ValueState::Mutated(())
+        });
+
+        self.save();
+    }
+
+    /// Queue the equip requests needed to put on a set. Items that are no
+    /// longer in the inventory are skipped.
+    pub fn equip_set(&mut self, name: &str, inventory: &Inventory) {
+        let sets = self.sets.borrow();
+
+        let Some(set) = sets.iter().find(|set| set.name == name) else {
+            return;
+        };
+
+        let candidates: Vec<(ItemIndex, EquipmentSetItem)> = inventory
+            .items
+            .borrow()
+            .iter()
+            .map(|item| (item.index, EquipmentSetItem::from_item(item, item.equipped_position)))
+            .collect();
+
+        self.pending_requests.clear();
+        self.pending_requests.extend(resolve_requests(&set.items, &candidates));
+    }
+
+    /// Equip a set by its position in the list, used by the hotkeys.
+    pub fn equip_set_at(&mut self, position: usize, inventory: &Inventory) {
+        let name = self.sets.borrow().get(position).map(|set| set.name.clone());
+
+        if let Some(name) = name {
+            self.equip_set(&name, inventory);
+        }
+    }
+
+    /// Returns the next equip request that should be sent to the map server,
+    /// if enough time has passed since the last one.
+    pub fn next_request(&mut self) -> Option<(ItemIndex, EquipPosition)> {
+        if self
+            .last_request
+            .is_some_and(|last_request| last_request.elapsed() < EQUIP_REQUEST_INTERVAL)
+        {
+            return None;
+        }
+
+        let request = self.pending_requests.pop_front()?;
+        self.last_request = Some(Instant::now());
+
+        Some(request)
+    }
+
+    pub fn get_sets(&self) -> Remote<Vec<EquipmentSet>> {
+        self.sets.new_remote()
+    }
+}
+
+/// Pick a different inventory item for every item of the set. Items with the
+/// same refinement and cards are preferred, followed by items that are
+/// already equipped in the right position. Only items that still need to be
+/// equipped result in a request.
+fn resolve_requests(set_items: &[EquipmentSetItem], candidates: &[(ItemIndex, EquipmentSetItem)]) -> Vec<(ItemIndex, EquipPosition)> {
+    let mut used_indices = HashSet::new();
+    let mut requests = Vec::new();
+
+    for set_item in set_items {
+        let best_candidate = candidates
+            .iter()
+            .filter(|(index, candidate)| candidate.item_id == set_item.item_id && !used_indices.contains(index))
+            .min_by_key(|(_, candidate)| {
+                let same_item = candidate.refinement_level == set_item.refinement_level && candidate.cards == set_item.cards;
+                let in_position = candidate.equip_position == set_item.equip_position;
+                (!same_item, !in_position)
+            });
+
+        let Some((index, candidate)) = best_candidate else {
+            continue;
+        };
+
+        used_indices.insert(*index);
+
+        if candidate.equip_position != set_item.equip_position {
+            requests.push((*index, set_item.equip_position));
+        }
+    }
+
+    requests
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use ragnarok_bytes::{ByteStream, FromBytes};
+
+    use super::{resolve_requests, EquipmentSetItem, EquipmentSets, EQUIP_REQUEST_INTERVAL};
+    use crate::network::{EquipPosition, ItemId, ItemIndex};
+
+    fn item_index(index: u16) -> ItemIndex {
+        let bytes = (index + 2).to_le_bytes();
+        let mut byte_stream: ByteStream<()> = ByteStream::without_metadata(&bytes);
+        ItemIndex::from_bytes(&mut byte_stream).unwrap()
+    }
+
+    fn set_item(item_id: u32, refinement_level: u8, equip_position: EquipPosition) -> EquipmentSetItem {
+        EquipmentSetItem {
+            item_id: ItemId(item_id),
+            refinement_level,
+            cards: [0; 4],
+            equip_position,
+        }
+    }
+
+    #[test]
+    fn duplicates_resolve_to_different_items() {
+        let set_items = [
+            set_item(1201, 0, EquipPosition::RightHand),
+            set_item(1201, 0, EquipPosition::LeftHand),
+        ];
+        let candidates = [
+            (item_index(2), set_item(1201, 0, EquipPosition::None)),
+            (item_index(3), set_item(1201, 0, EquipPosition::None)),
+        ];
+
+        assert_eq!(resolve_requests(&set_items, &candidates), vec![
+            (item_index(2), EquipPosition::RightHand),
+            (item_index(3), EquipPosition::LeftHand),
+        ]);
+    }
+
+    #[test]
+    fn prefer_same_refinement() {
+        let set_items = [set_item(1201, 7, EquipPosition::RightHand)];
+        let candidates = [
+            (item_index(2), set_item(1201, 0, EquipPosition::None)),
+            (item_index(3), set_item(1201, 7, EquipPosition::None)),
+        ];
+
+        assert_eq!(resolve_requests(&set_items, &candidates), vec![(
+            item_index(3),
+            EquipPosition::RightHand
+        )]);
+    }
+
+    #[test]
+    fn skip_equipped_and_missing_items() {
+        let set_items = [set_item(1201, 0, EquipPosition::RightHand), set_item(2301, 0, EquipPosition::Armor)];
+        let candidates = [
+            (item_index(2), set_item(1201, 0, EquipPosition::None)),
+            (item_index(3), set_item(1201, 0, EquipPosition::RightHand)),
+        ];
+
+        assert!(resolve_requests(&set_items, &candidates).is_empty());
+    }
+
+    #[test]
+    fn requests_are_spaced_out() {
+        let mut equipment_sets = EquipmentSets::default();
+        equipment_sets
+            .pending_requests
+            .extend([(item_index(2), EquipPosition::RightHand), (item_index(3), EquipPosition::LeftHand)]);
+
+        assert_eq!(equipment_sets.next_request(), Some((item_index(2), EquipPosition::RightHand)));
+        assert_eq!(equipment_sets.next_request(), None);
+
+        equipment_sets.last_request = Some(Instant::now() - EQUIP_REQUEST_INTERVAL);
+
+        assert_eq!(equipment_sets.next_request(), Some((item_index(3), EquipPosition::LeftHand)));
+        assert_eq!(equipment_sets.next_request(), None);
+    }
+}
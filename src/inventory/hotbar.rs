@@ -1,48 +1,238 @@
 use std::cell::Ref;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
-use super::Skill;
+use super::{Inventory, Item, Skill, SkillTree};
 use crate::input::HotbarSlot;
 use crate::interface::{Remote, TrackedState, ValueState};
+use crate::network::{HotkeyData, ItemId, SkillId, SkillLevel};
+
+/// Rows of the hotbar. The first row is bound to the number keys and the
+/// second one to the function keys.
+pub const HOTBAR_ROWS: usize = 2;
+pub const HOTBAR_ROW_SLOTS: usize = 9;
+pub const HOTBAR_SLOTS: usize = HOTBAR_ROWS * HOTBAR_ROW_SLOTS;
+
+#[derive(Clone, Debug)]
+pub enum HotbarEntry {
+    Skill(Skill),
+    Item(Item),
+}
+
+impl HotbarEntry {
+    fn to_hotkey(&self) -> HotkeyData {
+        match self {
+            HotbarEntry::Skill(skill) => HotkeyData::new(1, skill.skill_id.0 as u32, skill.skill_level),
+            HotbarEntry::Item(item) => HotkeyData::new(0, item.item_id.0, SkillLevel(0)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Cooldown {
+    start: Instant,
+    duration: Duration,
+}
+
+impl Cooldown {
+    fn remaining_after(&self, elapsed: Duration) -> f32 {
+        match self.duration.is_zero() {
+            true => 0.0,
+            false => 1.0 - (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0),
+        }
+    }
+
+    /// Fraction of the cooldown that is still remaining, from `1.0` right
+    /// after the skill was used to `0.0` once it can be used again.
+    pub fn remaining(&self) -> f32 {
+        self.remaining_after(self.start.elapsed())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.start.elapsed() < self.duration
+    }
+}
 
 #[derive(Default)]
 pub struct Hotbar {
-    skills: TrackedState<[Option<Skill>; 10]>,
+    entries: TrackedState<[Option<HotbarEntry>; HOTBAR_SLOTS]>,
+    cooldowns: TrackedState<HashMap<SkillId, Cooldown>>,
+    /// Shortcuts as stored on the map server. They are kept around so the
+    /// entries can be resolved again once the skill tree or inventory arrive.
+    hotkeys: Vec<Option<HotkeyData>>,
+    /// Used to notice changes to the inventory, so the items on the hotbar
+    /// never show an outdated amount.
+    inventory_items: Option<Remote<Vec<Item>>>,
 }
 
 impl Hotbar {
-    pub fn set_slot(&mut self, skill: Skill, slot: HotbarSlot) {
-        self.skills.with_mut(|skills| {
-            skills[slot.0] = Some(skill);
+    /// Returns the shortcut that needs to be sent to the map server.
+    pub fn set_slot(&mut self, entry: HotbarEntry, slot: HotbarSlot) -> HotkeyData {
+        let hotkey = entry.to_hotkey();
+        self.store_hotkey(slot, Some(hotkey.clone()));
+
+        self.entries.with_mut(|entries| {
+            entries[slot.0] = Some(entry);
             ValueState::Mutated(())
         });
+
+        hotkey
     }
 
-    pub fn swap_slot(&mut self, source_slot: HotbarSlot, destination_slot: HotbarSlot) {
-        if source_slot != destination_slot {
-            self.skills.with_mut(|skills| {
-                let first = skills[source_slot.0].take();
-                let second = skills[destination_slot.0].take();
+    /// Returns the shortcuts that need to be sent to the map server.
+    pub fn swap_slot(&mut self, source_slot: HotbarSlot, destination_slot: HotbarSlot) -> Vec<(HotbarSlot, HotkeyData)> {
+        if source_slot == destination_slot {
+            return Vec::new();
+        }
 
-                skills[source_slot.0] = second;
-                skills[destination_slot.0] = first;
+        self.entries.with_mut(|entries| {
+            entries.swap(source_slot.0, destination_slot.0);
+            ValueState::Mutated(())
+        });
 
-                ValueState::Mutated(())
-            });
+        [source_slot, destination_slot]
+            .into_iter()
+            .map(|slot| {
+                let hotkey = self.entries.borrow()[slot.0].as_ref().map(HotbarEntry::to_hotkey);
+                self.store_hotkey(slot, hotkey.clone());
+                (slot, hotkey.unwrap_or_else(Self::empty_hotkey))
+            })
+            .collect()
+    }
+
+    /// Returns the shortcut that needs to be sent to the map server.
+    pub fn clear_slot(&mut self, slot: HotbarSlot) -> HotkeyData {
+        self.store_hotkey(slot, None);
+
+        self.entries.with_mut(|entries| {
+            entries[slot.0] = None;
+            ValueState::Mutated(())
+        });
+
+        Self::empty_hotkey()
+    }
+
+    pub fn get_entry_in_slot(&self, slot: HotbarSlot) -> Ref<Option<HotbarEntry>> {
+        Ref::map(self.entries.borrow(), |entries| &entries[slot.0])
+    }
+
+    pub fn get_entries(&self) -> Remote<[Option<HotbarEntry>; HOTBAR_SLOTS]> {
+        self.entries.new_remote()
+    }
+
+    pub fn get_cooldowns(&self) -> Remote<HashMap<SkillId, Cooldown>> {
+        self.cooldowns.new_remote()
+    }
+
+    pub fn start_cooldown(&mut self, skill_id: SkillId, duration: Duration) {
+        let start = Instant::now();
+
+        self.cooldowns.with_mut(|cooldowns| {
+            cooldowns.insert(skill_id, Cooldown { start, duration });
+            ValueState::Mutated(())
+        });
+    }
+
+    /// Should be called every frame, so the overlay disappears as soon as a
+    /// cooldown expires.
+    pub fn update_cooldowns(&mut self) {
+        self.cooldowns.with_mut(|cooldowns| {
+            let previous_length = cooldowns.len();
+            cooldowns.retain(|_, cooldown| cooldown.is_active());
+
+            match cooldowns.len() < previous_length {
+                true => ValueState::Mutated(()),
+                false => ValueState::Unchanged(()),
+            }
+        });
+    }
+
+    /// Should be called every frame, so items are resolved again whenever the
+    /// inventory changes.
+    pub fn update_items(&mut self, inventory: &Inventory) {
+        let inventory_items = self.inventory_items.get_or_insert_with(|| inventory.get_items());
+
+        if inventory_items.consume_changed() {
+            self.resolve_items(inventory);
         }
     }
 
-    /*pub fn clear_slot(&mut self, slot: HotbarSlot) {
-        self.skills.with_mut(|skills, changed| {
-            skills[slot.0] = None;
-            changed();
+    /// Replace the hotbar with the shortcuts stored on the map server.
+    pub fn set_hotkeys(&mut self, hotkeys: Vec<HotkeyData>, skill_tree: &SkillTree, inventory: &Inventory) {
+        self.hotkeys = hotkeys
+            .into_iter()
+            .take(HOTBAR_SLOTS)
+            .map(|hotkey| (hotkey.id != 0).then_some(hotkey))
+            .collect();
+
+        self.resolve(skill_tree, inventory);
+    }
+
+    /// Look up the skills and items of the stored shortcuts. Shortcuts to
+    /// skills or items that the character doesn't have are left empty.
+    pub fn resolve(&mut self, skill_tree: &SkillTree, inventory: &Inventory) {
+        let entries = std::array::from_fn(|index| {
+            let hotkey = self.hotkeys.get(index)?.as_ref()?;
+
+            match hotkey.is_skill != 0 {
+                true => skill_tree.find_skill(SkillId(hotkey.id as u16)).map(HotbarEntry::Skill),
+                false => inventory.find_item(ItemId(hotkey.id)).map(HotbarEntry::Item),
+            }
+        });
+
+        self.entries.set(entries);
+    }
+
+    fn resolve_items(&mut self, inventory: &Inventory) {
+        let hotkeys = &self.hotkeys;
+
+        self.entries.with_mut(|entries| {
+            for (index, entry) in entries.iter_mut().enumerate() {
+                let Some(hotkey) = hotkeys.get(index).and_then(Option::as_ref).filter(|hotkey| hotkey.is_skill == 0) else {
+                    continue;
+                };
+
+                *entry = inventory.find_item(ItemId(hotkey.id)).map(HotbarEntry::Item);
+            }
+
+            ValueState::Mutated(())
         });
-    }*/
+    }
 
-    pub fn get_skill_in_slot(&self, slot: HotbarSlot) -> Ref<Option<Skill>> {
-        Ref::map(self.skills.borrow(), |skills| &skills[slot.0])
+    pub fn clear(&mut self) {
+        self.hotkeys.clear();
+        self.entries.set(Default::default());
+        self.cooldowns.set(HashMap::new());
     }
 
-    pub fn get_skills(&self) -> Remote<[Option<Skill>; 10]> {
-        self.skills.new_remote()
+    fn store_hotkey(&mut self, slot: HotbarSlot, hotkey: Option<HotkeyData>) {
+        if self.hotkeys.len() <= slot.0 {
+            self.hotkeys.resize(slot.0 + 1, None);
+        }
+
+        self.hotkeys[slot.0] = hotkey;
+    }
+
+    fn empty_hotkey() -> HotkeyData {
+        HotkeyData::new(0, 0, SkillLevel(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::Cooldown;
+
+    #[test]
+    fn cooldown_remaining() {
+        let cooldown = Cooldown {
+            start: Instant::now(),
+            duration: Duration::from_secs(4),
+        };
+
+        assert_eq!(cooldown.remaining_after(Duration::ZERO), 1.0);
+        assert_eq!(cooldown.remaining_after(Duration::from_secs(1)), 0.75);
+        assert_eq!(cooldown.remaining_after(Duration::from_secs(5)), 0.0);
     }
 }
@@ -0,0 +1,243 @@
+use super::{Inventory, Item};
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
+use crate::network::{EquipPosition, InventoryItemData, ItemIndex, MailHeader, MailId};
+
+#[derive(Clone, Default)]
+pub struct MailboxData {
+    /// Mails sorted from newest to oldest.
+    pub mails: Vec<MailHeader>,
+    /// There are no older mails on the map server.
+    pub is_end: bool,
+}
+
+#[derive(Clone)]
+pub struct OpenedMail {
+    pub header: MailHeader,
+    pub text: String,
+    pub zeny: u64,
+    pub items: Vec<Item>,
+}
+
+/// A mail that is sent once the map server found the receiver.
+pub struct PendingMail {
+    pub receiver_name: String,
+    pub title: String,
+    pub text: String,
+    pub zeny: u64,
+}
+
+/// The mailbox of the player, the mail the player is reading and the mail the
+/// player is writing.
+#[derive(Default)]
+pub struct Mailbox {
+    data: TrackedState<MailboxData>,
+    /// `Some` while the mail window is open.
+    opened_mail: TrackedState<Option<OpenedMail>>,
+    attachments: TrackedState<Vec<Item>>,
+    pending_mail: Option<PendingMail>,
+    /// Set while the compose window is open.
+    is_composing: bool,
+}
+
+impl Mailbox {
+    /// Maximum number of different items that can be attached to a mail.
+    pub const MAXIMUM_ATTACHMENTS: usize = 5;
+
+    pub fn clear_mails(&mut self) {
+        self.data.set(MailboxData::default());
+    }
+
+    /// Mails that are already known are replaced, since their read state may
+    /// have changed.
+    pub fn add_mails(&mut self, mails: Vec<MailHeader>, is_end: bool) {
+        self.data.with_mut(|data| {
+            for mail in mails {
+                match data.mails.iter_mut().find(|known| known.mail_id == mail.mail_id) {
+                    Some(known) => *known = mail,
+                    None => data.mails.push(mail),
+                }
+            }
+
+            data.mails.sort_by(|first, second| second.mail_id.cmp(&first.mail_id));
+            data.is_end = is_end;
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn oldest_mail_id(&self) -> Option<MailId> {
+        self.data.borrow().mails.last().map(|mail| mail.mail_id)
+    }
+
+    fn update_header(&mut self, mail_id: MailId, update: impl FnOnce(&mut MailHeader)) {
+        self.data
+            .with_mut(|data| match data.mails.iter_mut().find(|mail| mail.mail_id == mail_id) {
+                Some(mail) => {
+                    update(mail);
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            });
+    }
+
+    /// Mails that are not in the mailbox are ignored.
+    pub fn open_mail(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        mail_id: MailId,
+        text: String,
+        zeny: u64,
+        items: Vec<InventoryItemData>,
+    ) -> bool {
+        self.update_header(mail_id, |mail| mail.is_read = true);
+
+        let Some(header) = self.data.borrow().mails.iter().find(|mail| mail.mail_id == mail_id).cloned() else {
+            return false;
+        };
+
+        let items = items
+            .into_iter()
+            .map(|item_data| Item::new(game_file_loader, texture_loader, script_loader, item_data, 0))
+            .collect();
+
+        self.opened_mail.set(Some(OpenedMail { header, text, zeny, items }));
+
+        true
+    }
+
+    pub fn get_opened_mail_id(&self) -> Option<MailId> {
+        self.opened_mail.borrow().as_ref().map(|mail| mail.header.mail_id)
+    }
+
+    /// Returns `true` if a mail was open.
+    pub fn close_mail(&mut self) -> bool {
+        let was_open = self.opened_mail.borrow().is_some();
+        self.opened_mail.set(None);
+        was_open
+    }
+
+    fn update_opened_mail(&mut self, mail_id: MailId, update: impl FnOnce(&mut OpenedMail)) {
+        self.opened_mail.with_mut(|opened_mail| match opened_mail {
+            Some(mail) if mail.header.mail_id == mail_id => {
+                update(mail);
+                ValueState::Mutated(())
+            }
+            _ => ValueState::Unchanged(()),
+        });
+    }
+
+    pub fn take_zeny(&mut self, mail_id: MailId) {
+        self.update_header(mail_id, |mail| mail.has_zeny = false);
+        self.update_opened_mail(mail_id, |mail| {
+            mail.header.has_zeny = false;
+            mail.zeny = 0;
+        });
+    }
+
+    pub fn take_items(&mut self, mail_id: MailId) {
+        self.update_header(mail_id, |mail| mail.has_items = false);
+        self.update_opened_mail(mail_id, |mail| {
+            mail.header.has_items = false;
+            mail.items.clear();
+        });
+    }
+
+    /// Returns `true` if the removed mail was open.
+    pub fn remove_mail(&mut self, mail_id: MailId) -> bool {
+        self.data.with_mut(|data| {
+            data.mails.retain(|mail| mail.mail_id != mail_id);
+            ValueState::Mutated(())
+        });
+
+        match self.get_opened_mail_id() == Some(mail_id) {
+            true => self.close_mail(),
+            false => false,
+        }
+    }
+
+    pub fn start_composing(&mut self) {
+        self.is_composing = true;
+        self.pending_mail = None;
+        self.attachments.set(Vec::new());
+    }
+
+    pub fn is_composing(&self) -> bool {
+        self.is_composing
+    }
+
+    /// Returns `true` if the item should be attached by the map server.
+    /// Favorite items are confirmed by the player before they get here.
+    pub fn can_attach(&self, item: &Item) -> bool {
+        let attachments = self.attachments.borrow();
+
+        self.is_composing
+            && item.equipped_position == EquipPosition::None
+            && (attachments.len() < Self::MAXIMUM_ATTACHMENTS || attachments.iter().any(|attached| attached.index == item.index))
+    }
+
+    /// Set the amount of an inventory item that is attached to the mail. An
+    /// amount of zero removes the attachment.
+    pub fn update_attachment(&mut self, inventory: &Inventory, index: ItemIndex, amount: u16) {
+        self.attachments.with_mut(|attachments| {
+            let position = attachments.iter().position(|item| item.index == index);
+
+            match (position, amount) {
+                (Some(position), 0) => {
+                    attachments.remove(position);
+                }
+                (Some(position), amount) => attachments[position].amount = amount,
+                (None, 0) => return ValueState::Unchanged(()),
+                (None, amount) => {
+                    let Some(mut item) = inventory.get_item(index) else {
+                        return ValueState::Unchanged(());
+                    };
+
+                    item.amount = amount;
+                    attachments.push(item);
+                }
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn set_pending_mail(&mut self, pending_mail: PendingMail) {
+        self.pending_mail = Some(pending_mail);
+    }
+
+    pub fn take_pending_mail(&mut self) -> Option<PendingMail> {
+        self.pending_mail.take()
+    }
+
+    /// Returns `true` if the player was writing a mail.
+    pub fn stop_composing(&mut self) -> bool {
+        let was_composing = self.is_composing;
+
+        self.is_composing = false;
+        self.pending_mail = None;
+        self.attachments.set(Vec::new());
+
+        was_composing
+    }
+
+    pub fn clear(&mut self) {
+        self.stop_composing();
+        self.opened_mail.set(None);
+        self.data.set(MailboxData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<MailboxData> {
+        self.data.new_remote()
+    }
+
+    pub fn get_opened_mail(&self) -> Remote<Option<OpenedMail>> {
+        self.opened_mail.new_remote()
+    }
+
+    pub fn get_attachments(&self) -> Remote<Vec<Item>> {
+        self.attachments.new_remote()
+    }
+}
@@ -1,15 +1,37 @@
+mod achievement;
+mod bank;
+mod cart;
+mod equipment;
 mod hotbar;
+mod mail;
+mod quest;
+mod shop;
 mod skills;
+mod statistics;
+mod stats;
+mod trade;
+mod view;
 
 use std::sync::Arc;
 
 use vulkano::image::view::ImageView;
 
-pub use self::hotbar::Hotbar;
+pub use self::achievement::{AchievementData, AchievementEntry, Achievements};
+pub use self::bank::Bank;
+pub use self::cart::Cart;
+pub use self::equipment::{EquipmentSet, EquipmentSets};
+pub use self::hotbar::{Cooldown, Hotbar, HotbarEntry, HOTBAR_ROWS, HOTBAR_ROW_SLOTS, HOTBAR_SLOTS};
+pub use self::mail::{Mailbox, MailboxData, OpenedMail, PendingMail};
+pub use self::quest::{QuestEntry, QuestLog, QuestLogData, QuestNavigation};
+pub use self::shop::{Shop, ShopData, Vendor};
 pub use self::skills::{Skill, SkillTree};
+pub use self::statistics::{SessionStatistics, SessionStatisticsData};
+pub use self::stats::{CharacterStats, CharacterStatsData};
+pub use self::trade::{Trade, TradeData, TradeOffer};
+pub use self::view::{InventoryFilter, InventoryTab, InventoryView, ItemCategory, ItemSorting};
 use crate::interface::{Remote, TrackedState, ValueState};
-use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
-use crate::network::{EquipPosition, ItemId, ItemIndex};
+use crate::loaders::{GameFileLoader, ItemInfo, ScriptLoader, TextureLoader};
+use crate::network::{EquipPosition, InventoryItemData, ItemId, ItemIndex};
 
 /*enum ItemDetails {
     Regular {
@@ -35,16 +57,56 @@ pub struct Item {
     pub item_id: ItemId,
     pub equip_position: EquipPosition,
     pub equipped_position: EquipPosition,
-    //pub item_type: u8,
+    pub amount: u16,
+    pub refinement_level: u8,
+    pub cards: [u32; 4],
+    pub is_broken: bool,
+    pub is_favorite: bool,
+    pub item_type: u8,
+    /// Increases every time an item is added to the inventory, used to sort
+    /// by the most recently acquired items.
+    pub acquired: usize,
     //pub wear_state: u32,
-    //pub slot: [u32; 4], // card ?
     //pub hire_expiration_date: i32,
     pub texture: Arc<ImageView>,
+    pub info: ItemInfo,
+}
+
+impl Item {
+    fn new(
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        item_data: InventoryItemData,
+        acquired: usize,
+    ) -> Self {
+        let resource_name = script_loader.get_item_resource_from_id(item_data.item_id);
+        let full_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{resource_name}.bmp");
+        let texture = texture_loader.get(&full_path, game_file_loader).unwrap();
+        let info = script_loader.get_item_info(item_data.item_id, true);
+
+        Self {
+            index: item_data.index,
+            item_id: item_data.item_id,
+            equip_position: item_data.equip_position,
+            equipped_position: item_data.equipped_position,
+            amount: item_data.amount,
+            refinement_level: item_data.refinement_level,
+            cards: item_data.cards,
+            is_broken: item_data.is_broken,
+            is_favorite: item_data.is_favorite,
+            item_type: item_data.item_type,
+            acquired,
+            texture,
+            info,
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct Inventory {
     items: TrackedState<Vec<Item>>,
+    acquired_count: usize,
 }
 
 impl Inventory {
@@ -53,21 +115,13 @@ impl Inventory {
         game_file_loader: &mut GameFileLoader,
         texture_loader: &mut TextureLoader,
         script_loader: &ScriptLoader,
-        item_data: Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition)>,
+        item_data: Vec<InventoryItemData>,
     ) {
         let items = item_data
             .into_iter()
             .map(|item_data| {
-                let resource_name = script_loader.get_item_resource_from_id(item_data.1);
-                let full_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{resource_name}.bmp");
-                let texture = texture_loader.get(&full_path, game_file_loader).unwrap();
-                Item {
-                    index: item_data.0,
-                    item_id: item_data.1,
-                    equip_position: item_data.2,
-                    equipped_position: item_data.3,
-                    texture,
-                }
+                self.acquired_count += 1;
+                Item::new(game_file_loader, texture_loader, script_loader, item_data, self.acquired_count)
             })
             .collect();
 
@@ -79,34 +133,47 @@ impl Inventory {
         game_file_loader: &mut GameFileLoader,
         texture_loader: &mut TextureLoader,
         script_loader: &ScriptLoader,
-        item_index: ItemIndex,
-        item_id: ItemId,
-        equip_position: EquipPosition,
-        equipped_position: EquipPosition,
+        item_data: InventoryItemData,
     ) {
+        self.acquired_count += 1;
+        let acquired = self.acquired_count;
+
         self.items.with_mut(|items| {
-            if let Some(_stack) = items.iter_mut().find(|item| item.item_id == item_id) {
-                //stack.amount += item_data.amount;
+            if let Some(stack) = items.iter_mut().find(|item| item.index == item_data.index) {
+                stack.amount += item_data.amount;
+                stack.acquired = acquired;
                 return ValueState::Mutated(());
             }
 
-            let resource_name = script_loader.get_item_resource_from_id(item_id);
-            let full_path = format!("À¯ÀúÀÎÅÍÆäÀÌ½º\\item\\{resource_name}.bmp");
-            let texture = texture_loader.get(&full_path, game_file_loader).unwrap();
-            let item = Item {
-                index: item_index,
-                item_id,
-                equip_position,
-                equipped_position,
-                texture,
+            items.push(Item::new(game_file_loader, texture_loader, script_loader, item_data, acquired));
+
+            ValueState::Mutated(())
+        });
+    }
+
+    /// Remove some or all items of a stack, e.g. after they were used, sold
+    /// or moved to the cart.
+    pub fn remove_item(&mut self, index: ItemIndex, amount: u16) {
+        self.items.with_mut(|items| {
+            let Some(position) = items.iter().position(|item| item.index == index) else {
+                return ValueState::Unchanged(());
             };
 
-            items.push(item);
+            match items[position].amount > amount {
+                true => items[position].amount -= amount,
+                false => {
+                    items.remove(position);
+                }
+            }
 
             ValueState::Mutated(())
         });
     }
 
+    pub fn clear(&mut self) {
+        self.items.set(Vec::new());
+    }
+
     pub fn update_equipped_position(&mut self, index: ItemIndex, equipped_position: EquipPosition) {
         self.items.with_mut(|items| {
             items.iter_mut().find(|item| item.index == index).unwrap().equipped_position = equipped_position;
@@ -114,6 +181,34 @@ impl Inventory {
         });
     }
 
+    pub fn update_favorite(&mut self, index: ItemIndex, is_favorite: bool) {
+        self.items
+            .with_mut(|items| match items.iter_mut().find(|item| item.index == index) {
+                Some(item) => {
+                    item.is_favorite = is_favorite;
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            });
+    }
+
+    pub fn get_equipped_items(&self) -> Vec<Item> {
+        self.items
+            .borrow()
+            .iter()
+            .filter(|item| item.equipped_position != EquipPosition::None)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_item(&self, index: ItemIndex) -> Option<Item> {
+        self.items.borrow().iter().find(|item| item.index == index).cloned()
+    }
+
+    pub fn find_item(&self, item_id: ItemId) -> Option<Item> {
+        self.items.borrow().iter().find(|item| item.item_id == item_id).cloned()
+    }
+
     pub fn get_items(&self) -> Remote<Vec<Item>> {
         self.items.new_remote()
     }
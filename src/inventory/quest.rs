@@ -0,0 +1,134 @@
+use cgmath::Vector2;
+
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::loaders::ScriptLoader;
+use crate::network::{QuestData, QuestObjective};
+
+#[derive(Clone)]
+pub struct QuestEntry {
+    pub quest_id: u32,
+    pub title: String,
+    pub summary: String,
+    pub is_active: bool,
+    pub objectives: Vec<QuestObjective>,
+}
+
+impl QuestEntry {
+    fn new(script_loader: &ScriptLoader, quest: QuestData) -> Self {
+        let info = script_loader.get_quest_info(quest.quest_id);
+
+        Self {
+            quest_id: quest.quest_id,
+            title: info.title,
+            summary: info.summary,
+            is_active: quest.is_active,
+            objectives: quest.objectives,
+        }
+    }
+}
+
+/// Position on a map that the map server pointed the player at.
+#[derive(Clone)]
+pub struct QuestNavigation {
+    pub map_name: String,
+    pub position: Vector2<u16>,
+}
+
+#[derive(Clone, Default)]
+pub struct QuestLogData {
+    /// Quests in the quest log of the player.
+    pub active: Vec<QuestEntry>,
+    /// Quests that were removed from the quest log since the player logged in.
+    pub completed: Vec<QuestEntry>,
+    pub navigation: Option<QuestNavigation>,
+}
+
+/// The quests of the player together with their objectives.
+#[derive(Default)]
+pub struct QuestLog {
+    data: TrackedState<QuestLogData>,
+}
+
+impl QuestLog {
+    pub fn set_quests(&mut self, script_loader: &ScriptLoader, quests: Vec<QuestData>) {
+        self.data.with_mut(|data| {
+            data.active = quests.into_iter().map(|quest| QuestEntry::new(script_loader, quest)).collect();
+            ValueState::Mutated(())
+        });
+    }
+
+    /// Quests that are already in the log are replaced.
+    pub fn add_quest(&mut self, script_loader: &ScriptLoader, quest: QuestData) {
+        let entry = QuestEntry::new(script_loader, quest);
+
+        self.data.with_mut(|data| {
+            data.completed.retain(|completed| completed.quest_id != entry.quest_id);
+
+            match data.active.iter_mut().find(|active| active.quest_id == entry.quest_id) {
+                Some(active) => *active = entry,
+                None => data.active.push(entry),
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn update_objective(&mut self, hunt_identification: u32, current_count: u16, total_count: u16) {
+        self.data.with_mut(|data| {
+            let objective = data
+                .active
+                .iter_mut()
+                .flat_map(|quest| quest.objectives.iter_mut())
+                .find(|objective| objective.hunt_identification == hunt_identification);
+
+            match objective {
+                Some(objective) => {
+                    objective.current_count = current_count;
+                    objective.total_count = total_count;
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            }
+        });
+    }
+
+    /// NOTE: The map server removes quests from the log once they are
+    /// completed, so removed quests are shown as completed.
+    pub fn remove_quest(&mut self, quest_id: u32) {
+        self.data
+            .with_mut(|data| match data.active.iter().position(|quest| quest.quest_id == quest_id) {
+                Some(position) => {
+                    let quest = data.active.remove(position);
+                    data.completed.push(quest);
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            });
+    }
+
+    pub fn set_navigation(&mut self, map_name: String, position: Vector2<u16>) {
+        self.data.with_mut(|data| {
+            data.navigation = Some(QuestNavigation { map_name, position });
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn clear_navigation(&mut self) {
+        self.data.with_mut(|data| {
+            data.navigation = None;
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn get_navigation(&self) -> Option<QuestNavigation> {
+        self.data.borrow().navigation.clone()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.set(QuestLogData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<QuestLogData> {
+        self.data.new_remote()
+    }
+}
@@ -0,0 +1,218 @@
+use super::{Inventory, Item};
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
+use crate::network::{EntityId, InventoryItemData, ItemId, ItemIndex, ShopDealType};
+
+/// The map server rejects purchases of more than this many items of a kind.
+const MAXIMUM_BUY_AMOUNT: u16 = 30000;
+
+/// An item listed in an NPC shop or a vending shop.
+#[derive(Clone)]
+pub struct ShopEntry {
+    pub item: Item,
+    pub price: u32,
+    /// Weight of a single item as shown in the interface.
+    pub weight: u32,
+    /// Amount the player wants to buy or sell.
+    pub amount: u16,
+}
+
+/// The player running a vending shop.
+#[derive(Clone)]
+pub struct Vendor {
+    pub entity_id: EntityId,
+    pub vending_id: u32,
+    pub title: String,
+}
+
+#[derive(Clone)]
+pub struct ShopData {
+    pub deal_type: ShopDealType,
+    pub entries: Vec<ShopEntry>,
+    /// Set if the items are bought from another player instead of an NPC.
+    pub vendor: Option<Vendor>,
+}
+
+impl Default for ShopData {
+    fn default() -> Self {
+        Self {
+            deal_type: ShopDealType::Buy,
+            entries: Vec::new(),
+            vendor: None,
+        }
+    }
+}
+
+impl ShopData {
+    pub fn total_price(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.price as u64 * entry.amount as u64).sum()
+    }
+
+    pub fn total_weight(&self) -> u32 {
+        self.entries.iter().map(|entry| entry.weight * entry.amount as u32).sum()
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.entries.iter().any(|entry| entry.amount > 0)
+    }
+
+    /// Largest amount of an entry the player can select. Purchases are
+    /// limited by the zeny and the weight the player can still carry, sales by
+    /// the amount in the inventory. Vendors only have a limited stock.
+    pub fn maximum_amount(&self, position: usize, zeny: u32, free_weight: u32) -> u16 {
+        let entry = &self.entries[position];
+
+        if self.deal_type == ShopDealType::Sell {
+            return entry.item.amount;
+        }
+
+        let stock = match self.vendor {
+            Some(..) => entry.item.amount,
+            None => MAXIMUM_BUY_AMOUNT,
+        };
+
+        let other_price = self.total_price() - entry.price as u64 * entry.amount as u64;
+        let other_weight = self.total_weight() - entry.weight * entry.amount as u32;
+
+        let affordable = match entry.price {
+            0 => u64::MAX,
+            price => (zeny as u64).saturating_sub(other_price) / price as u64,
+        };
+        let carriable = match entry.weight {
+            0 => u32::MAX,
+            weight => free_weight.saturating_sub(other_weight) / weight,
+        };
+
+        affordable.min(carriable as u64).min(stock as u64) as u16
+    }
+}
+
+/// The NPC shop the player is currently dealing with.
+#[derive(Default)]
+pub struct Shop {
+    data: TrackedState<ShopData>,
+    is_open: bool,
+}
+
+impl Shop {
+    /// Items bought from a vendor have the stock of the vendor as their
+    /// amount.
+    pub fn open_buy_list(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        vendor: Option<Vendor>,
+        items: Vec<(InventoryItemData, u32)>,
+    ) {
+        let entries = items
+            .into_iter()
+            .map(|(item_data, price)| {
+                let item = Item::new(game_file_loader, texture_loader, script_loader, item_data, 0);
+                let weight = item.info.weight.unwrap_or_default();
+
+                ShopEntry {
+                    item,
+                    price,
+                    weight,
+                    amount: 0,
+                }
+            })
+            .collect();
+
+        self.is_open = true;
+        self.data.set(ShopData {
+            deal_type: ShopDealType::Buy,
+            entries,
+            vendor,
+        });
+    }
+
+    /// Items that are no longer in the inventory are skipped. Favorite items
+    /// are skipped as well, so they can't be sold by accident.
+    pub fn open_sell_list(&mut self, inventory: &Inventory, items: Vec<(ItemIndex, u32)>) {
+        let entries = items
+            .into_iter()
+            .filter_map(|(index, price)| {
+                let item = inventory.get_item(index).filter(|item| !item.is_favorite)?;
+                let weight = item.info.weight.unwrap_or_default();
+
+                Some(ShopEntry {
+                    item,
+                    price,
+                    weight,
+                    amount: 0,
+                })
+            })
+            .collect();
+
+        self.is_open = true;
+        self.data.set(ShopData {
+            deal_type: ShopDealType::Sell,
+            entries,
+            vendor: None,
+        });
+    }
+
+    pub fn set_amount(&mut self, position: usize, amount: u16) {
+        self.data.with_mut(|data| {
+            let deal_type = data.deal_type;
+            let is_vending = data.vendor.is_some();
+
+            match data.entries.get_mut(position) {
+                Some(entry) => {
+                    entry.amount = match deal_type {
+                        ShopDealType::Buy if is_vending => amount.min(entry.item.amount),
+                        ShopDealType::Buy => amount.min(MAXIMUM_BUY_AMOUNT),
+                        ShopDealType::Sell => amount.min(entry.item.amount),
+                    };
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            }
+        });
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn get_deal_type(&self) -> ShopDealType {
+        self.data.borrow().deal_type
+    }
+
+    pub fn get_vendor(&self) -> Option<Vendor> {
+        self.data.borrow().vendor.clone()
+    }
+
+    pub fn buy_selection(&self) -> Vec<(ItemId, u16)> {
+        self.data
+            .borrow()
+            .entries
+            .iter()
+            .filter(|entry| entry.amount > 0)
+            .map(|entry| (entry.item.item_id, entry.amount))
+            .collect()
+    }
+
+    /// Also used for purchases from vendors, since their items are identified
+    /// by the index in the cart of the vendor.
+    pub fn sell_selection(&self) -> Vec<(ItemIndex, u16)> {
+        self.data
+            .borrow()
+            .entries
+            .iter()
+            .filter(|entry| entry.amount > 0)
+            .map(|entry| (entry.item.index, entry.amount))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.is_open = false;
+        self.data.set(ShopData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<ShopData> {
+        self.data.new_remote()
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::interface::{Remote, TrackedState};
-use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, Sprite, SpriteLoader};
+use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, ScriptLoader, SkillInfo, Sprite, SpriteLoader};
 use crate::network::{ClientTick, SkillId, SkillInformation, SkillLevel, SkillType};
 
 #[derive(Clone, Debug)]
@@ -9,7 +9,9 @@ pub struct Skill {
     pub skill_id: SkillId,
     pub skill_level: SkillLevel,
     pub skill_type: SkillType,
+    pub spell_point_cost: u16,
     pub skill_name: String,
+    pub info: SkillInfo,
     pub sprite: Arc<Sprite>,
     pub actions: Arc<Actions>,
     pub animation_state: AnimationState,
@@ -26,6 +28,7 @@ impl SkillTree {
         game_file_loader: &mut GameFileLoader,
         sprite_loader: &mut SpriteLoader,
         action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
         skill_data: Vec<SkillInformation>,
     ) {
         let skills = skill_data
@@ -34,12 +37,15 @@ impl SkillTree {
                 let file_path = format!("¾ÆÀÌÅÛ\\{}", skill_data.skill_name);
                 let sprite = sprite_loader.get(&format!("{file_path}.spr"), game_file_loader).unwrap();
                 let actions = action_loader.get(&format!("{file_path}.act"), game_file_loader).unwrap();
+                let info = script_loader.get_skill_info(skill_data.skill_id, &skill_data.skill_name);
 
                 Skill {
                     skill_id: skill_data.skill_id,
                     skill_level: skill_data.skill_level,
                     skill_type: skill_data.skill_type,
+                    spell_point_cost: skill_data.spell_point_cost,
                     skill_name: skill_data.skill_name,
+                    info,
                     sprite,
                     actions,
                     // FIX: give correct client tick
@@ -51,6 +57,10 @@ impl SkillTree {
         self.skills.set(skills);
     }
 
+    pub fn find_skill(&self, skill_id: SkillId) -> Option<Skill> {
+        self.skills.borrow().iter().find(|skill| skill.skill_id == skill_id).cloned()
+    }
+
     pub fn get_skills(&self) -> Remote<Vec<Skill>> {
         self.skills.new_remote()
     }
@@ -0,0 +1,280 @@
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::network::StatusType;
+
+const EXPORT_FILENAME: &str = "client/session_statistics.csv";
+const CSV_HEADER: &str = "timestamp,duration,base_experience,base_experience_per_hour,job_experience,job_experience_per_hour,zeny,\
+                          items_looted,damage_dealt,damage_taken,deaths";
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Default)]
+pub struct SessionStatisticsData {
+    pub duration: Duration,
+    pub base_experience: u64,
+    pub job_experience: u64,
+    pub zeny: i64,
+    pub items_looted: usize,
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub deaths: usize,
+}
+
+impl SessionStatisticsData {
+    fn per_hour(&self, value: u64) -> u64 {
+        let hours = self.duration.as_secs_f64() / 3600.0;
+
+        match hours > 0.0 {
+            true => (value as f64 / hours) as u64,
+            false => 0,
+        }
+    }
+
+    pub fn base_experience_per_hour(&self) -> u64 {
+        self.per_hour(self.base_experience)
+    }
+
+    pub fn job_experience_per_hour(&self) -> u64 {
+        self.per_hour(self.job_experience)
+    }
+
+    fn to_csv_row(&self, timestamp: u64) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            timestamp,
+            self.duration.as_secs(),
+            self.base_experience,
+            self.base_experience_per_hour(),
+            self.job_experience,
+            self.job_experience_per_hour(),
+            self.zeny,
+            self.items_looted,
+            self.damage_dealt,
+            self.damage_taken,
+            self.deaths
+        )
+    }
+}
+
+/// The server only sends the current experience of a level, so the gain has
+/// to be derived from consecutive updates.
+#[derive(Default)]
+struct ExperienceTracker {
+    level: Option<u32>,
+    leveled_up: bool,
+    experience: Option<u64>,
+    next_experience: Option<u64>,
+}
+
+impl ExperienceTracker {
+    fn update_level(&mut self, level: u32) {
+        self.leveled_up |= self.level.is_some_and(|previous| level > previous);
+        self.level = Some(level);
+    }
+
+    fn update_next_experience(&mut self, next_experience: u64) {
+        self.next_experience = Some(next_experience);
+    }
+
+    /// Returns the experience gained since the last update.
+    fn update_experience(&mut self, experience: u64) -> u64 {
+        let gained = match self.experience {
+            // NOTE: The experience is reset on level up, so we gained the remaining
+            // experience of the previous level on top of the new value. The server sends
+            // the experience before the requirement of the new level.
+            Some(previous) if self.leveled_up => self.next_experience.unwrap_or(previous).saturating_sub(previous) + experience,
+            // NOTE: Experience lost on death is not subtracted.
+            Some(previous) => experience.saturating_sub(previous),
+            None => 0,
+        };
+
+        self.leveled_up = false;
+        self.experience = Some(experience);
+
+        gained
+    }
+}
+
+/// Statistics about the current play session, derived from packets received
+/// from the map server.
+#[derive(Default)]
+pub struct SessionStatistics {
+    data: TrackedState<SessionStatisticsData>,
+    base_experience: ExperienceTracker,
+    job_experience: ExperienceTracker,
+    zeny: Option<u32>,
+    health_points: Option<u32>,
+    unpublished_duration: Duration,
+}
+
+impl SessionStatistics {
+    fn modify(&mut self, closure: impl FnOnce(&mut SessionStatisticsData)) {
+        self.data.with_mut(|data| {
+            closure(data);
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn update(&mut self, delta_time: f64) {
+        self.unpublished_duration += Duration::from_secs_f64(delta_time);
+
+        // NOTE: Only publish the session duration once in a while, since every update
+        // causes the statistics window to be resolved again.
+        if self.unpublished_duration >= PUBLISH_INTERVAL {
+            let duration = std::mem::take(&mut self.unpublished_duration);
+            self.modify(|data| data.duration += duration);
+        }
+    }
+
+    pub fn update_status(&mut self, status_type: &StatusType) {
+        match *status_type {
+            StatusType::BaseLevel(level) => self.base_experience.update_level(level),
+            StatusType::JobLevel(level) => self.job_experience.update_level(level),
+            StatusType::NextBaseExperience(value) => self.base_experience.update_next_experience(value),
+            StatusType::NextJobExperience(value) => self.job_experience.update_next_experience(value),
+            StatusType::BaseExperience(value) => {
+                let gained = self.base_experience.update_experience(value);
+
+                if gained > 0 {
+                    self.modify(|data| data.base_experience += gained);
+                }
+            }
+            StatusType::JobExperience(value) => {
+                let gained = self.job_experience.update_experience(value);
+
+                if gained > 0 {
+                    self.modify(|data| data.job_experience += gained);
+                }
+            }
+            StatusType::Zeny(value) => {
+                if let Some(previous) = self.zeny.replace(value)
+                    && previous != value
+                {
+                    self.modify(|data| data.zeny += value as i64 - previous as i64);
+                }
+            }
+            StatusType::HealthPoints(value) => {
+                if let Some(previous) = self.health_points.replace(value)
+                    && previous > 0
+                    && value == 0
+                {
+                    self.modify(|data| data.deaths += 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn add_looted_items(&mut self, amount: usize) {
+        self.modify(|data| data.items_looted += amount);
+    }
+
+    pub fn add_damage_dealt(&mut self, damage_amount: usize) {
+        self.modify(|data| data.damage_dealt += damage_amount as u64);
+    }
+
+    pub fn add_damage_taken(&mut self, damage_amount: usize) {
+        self.modify(|data| data.damage_taken += damage_amount as u64);
+    }
+
+    /// Start a new session. The last known status values are kept, so gains
+    /// are still tracked correctly after the reset.
+    pub fn reset(&mut self) {
+        self.unpublished_duration = Duration::ZERO;
+        self.data.set(SessionStatisticsData::default());
+    }
+
+    /// Start a new session for a different character.
+    pub fn clear(&mut self) {
+        self.base_experience = ExperienceTracker::default();
+        self.job_experience = ExperienceTracker::default();
+        self.zeny = None;
+        self.health_points = None;
+        self.reset();
+    }
+
+    /// Append the current statistics to a CSV file, writing the header if the
+    /// file is new.
+    pub fn export(&self) -> Result<(), String> {
+        #[cfg(feature = "debug")]
+        print_debug!("exporting session statistics to {}{EXPORT_FILENAME}{}", MAGENTA, NONE);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(EXPORT_FILENAME)
+            .map_err(|error| format!("failed to open {EXPORT_FILENAME}: {error}"))?;
+
+        let is_new_file = file.metadata().map(|metadata| metadata.len() == 0).unwrap_or(true);
+        let mut output = String::new();
+
+        if is_new_file {
+            output.push_str(CSV_HEADER);
+            output.push('\n');
+        }
+
+        output.push_str(&self.data.borrow().to_csv_row(timestamp));
+        output.push('\n');
+
+        file.write_all(output.as_bytes())
+            .map_err(|error| format!("failed to write {EXPORT_FILENAME}: {error}"))
+    }
+
+    pub fn get_data(&self) -> Remote<SessionStatisticsData> {
+        self.data.new_remote()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExperienceTracker, SessionStatistics};
+
+    #[test]
+    fn experience_gain() {
+        let mut tracker = ExperienceTracker::default();
+
+        assert_eq!(tracker.update_experience(100), 0);
+        assert_eq!(tracker.update_experience(150), 50);
+    }
+
+    #[test]
+    fn experience_gain_on_level_up() {
+        let mut tracker = ExperienceTracker::default();
+        tracker.update_level(10);
+        tracker.update_next_experience(200);
+        tracker.update_experience(150);
+
+        tracker.update_level(11);
+        assert_eq!(tracker.update_experience(30), 80);
+    }
+
+    #[test]
+    fn experience_loss_on_death() {
+        let mut tracker = ExperienceTracker::default();
+        tracker.update_level(10);
+        tracker.update_experience(150);
+
+        assert_eq!(tracker.update_experience(100), 0);
+        assert_eq!(tracker.update_experience(120), 20);
+    }
+
+    #[test]
+    fn looted_items_count_the_amount() {
+        let mut statistics = SessionStatistics::default();
+        statistics.add_looted_items(3);
+        statistics.add_looted_items(1);
+
+        assert_eq!(statistics.data.borrow().items_looted, 4);
+
+        statistics.reset();
+        assert_eq!(statistics.data.borrow().items_looted, 0);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::network::{StatType, StatusType};
+
+#[derive(Clone, Copy, Default)]
+pub struct Stat {
+    pub base: u32,
+    /// Added by equipment and status effects.
+    pub bonus: u32,
+    /// Status points required to raise the base value by one.
+    pub cost: u8,
+}
+
+impl Stat {
+    fn update(&mut self, base: u32, bonus: u32) {
+        self.base = base;
+        self.bonus = bonus;
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct CharacterStatsData {
+    pub strength: Stat,
+    pub agility: Stat,
+    pub vitality: Stat,
+    pub intelligence: Stat,
+    pub dexterity: Stat,
+    pub luck: Stat,
+    pub status_points: u32,
+    pub attack: u32,
+    pub attack_bonus: u32,
+    pub magic_attack: u32,
+    pub magic_attack_bonus: u32,
+    pub defense: u32,
+    pub defense_bonus: u32,
+    pub magic_defense: u32,
+    pub magic_defense_bonus: u32,
+    pub hit: u32,
+    pub flee: u32,
+    pub flee_bonus: u32,
+    pub critical: u32,
+    /// Delay between two attacks in milliseconds, as sent by the map server.
+    pub attack_delay: u32,
+    pub weight: u32,
+    pub maximum_weight: u32,
+    pub zeny: u32,
+}
+
+impl CharacterStatsData {
+    pub fn get_stat(&self, stat_type: StatType) -> &Stat {
+        match stat_type {
+            StatType::Strength => &self.strength,
+            StatType::Agility => &self.agility,
+            StatType::Vitality => &self.vitality,
+            StatType::Intelligence => &self.intelligence,
+            StatType::Dexterity => &self.dexterity,
+            StatType::Luck => &self.luck,
+        }
+    }
+
+    /// The map server sends a cost of zero once a stat can't be raised any
+    /// further.
+    pub fn can_increase(&self, stat_type: StatType) -> bool {
+        let cost = self.get_stat(stat_type).cost as u32;
+        cost > 0 && cost <= self.status_points
+    }
+
+    /// Attack speed as displayed by the official client.
+    pub fn attack_speed(&self) -> u32 {
+        (2000 - self.attack_delay.min(2000)) / 10
+    }
+}
+
+/// Stats of the player character, kept up to date from the status packets
+/// sent by the map server.
+#[derive(Default)]
+pub struct CharacterStats {
+    data: TrackedState<CharacterStatsData>,
+}
+
+impl CharacterStats {
+    pub fn update_status(&mut self, status_type: &StatusType) {
+        self.data.with_mut(|data| {
+            match *status_type {
+                StatusType::Strength(base, bonus) => data.strength.update(base, bonus),
+                StatusType::Agility(base, bonus) => data.agility.update(base, bonus),
+                StatusType::Vitality(base, bonus) => data.vitality.update(base, bonus),
+                StatusType::Intelligence(base, bonus) => data.intelligence.update(base, bonus),
+                StatusType::Dexterity(base, bonus) => data.dexterity.update(base, bonus),
+                StatusType::Luck(base, bonus) => data.luck.update(base, bonus),
+                StatusType::SpUstr(cost) => data.strength.cost = cost,
+                StatusType::SpUagi(cost) => data.agility.cost = cost,
+                StatusType::SpUvit(cost) => data.vitality.cost = cost,
+                StatusType::SpUint(cost) => data.intelligence.cost = cost,
+                StatusType::SpUdex(cost) => data.dexterity.cost = cost,
+                StatusType::SpUluk(cost) => data.luck.cost = cost,
+                StatusType::StatusPoint(value) => data.status_points = value,
+                StatusType::Attack1(value) => data.attack = value,
+                StatusType::Attack2(value) => data.attack_bonus = value,
+                StatusType::MagicAttack1(value) => data.magic_attack = value,
+                StatusType::MagicAttack2(value) => data.magic_attack_bonus = value,
+                StatusType::Defense1(value) => data.defense = value,
+                StatusType::Defense2(value) => data.defense_bonus = value,
+                StatusType::MagicDefense1(value) => data.magic_defense = value,
+                StatusType::MagicDefense2(value) => data.magic_defense_bonus = value,
+                StatusType::Hit(value) => data.hit = value,
+                StatusType::Flee1(value) => data.flee = value,
+                StatusType::Flee2(value) => data.flee_bonus = value,
+                StatusType::Critical(value) => data.critical = value,
+                StatusType::AttackSpeed(value) => data.attack_delay = value,
+                StatusType::Weight(value) => data.weight = value,
+                StatusType::MaximumWeight(value) => data.maximum_weight = value,
+                StatusType::Zeny(value) => data.zeny = value,
+                _ => return ValueState::Unchanged(()),
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.data.set(CharacterStatsData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<CharacterStatsData> {
+        self.data.new_remote()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CharacterStatsData;
+
+    #[test]
+    fn attack_speed() {
+        let mut data = CharacterStatsData {
+            attack_delay: 500,
+            ..Default::default()
+        };
+        assert_eq!(data.attack_speed(), 150);
+
+        data.attack_delay = 2500;
+        assert_eq!(data.attack_speed(), 0);
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+
+use super::Item;
+use crate::interface::{Remote, TrackedState, ValueState};
+use crate::loaders::{GameFileLoader, ScriptLoader, TextureLoader};
+use crate::network::{EquipPosition, InventoryItemData, ItemIndex};
+
+/// Items and zeny that one side of a trade offers.
+#[derive(Clone, Default)]
+pub struct TradeOffer {
+    pub items: Vec<Item>,
+    pub zeny: u32,
+    pub is_locked: bool,
+}
+
+impl TradeOffer {
+    /// Maximum number of items each side can offer.
+    pub const MAXIMUM_ITEMS: usize = 10;
+}
+
+#[derive(Clone, Default)]
+pub struct TradeData {
+    pub partner_name: String,
+    pub own_offer: TradeOffer,
+    pub partner_offer: TradeOffer,
+    /// The player finalized the trade and is waiting for the partner to do the
+    /// same.
+    pub is_confirmed: bool,
+}
+
+impl TradeData {
+    pub fn can_confirm(&self) -> bool {
+        self.own_offer.is_locked && self.partner_offer.is_locked && !self.is_confirmed
+    }
+}
+
+/// The trade the player is currently in. Items offered by the player only show
+/// up once the map server accepted them. The map server doesn't acknowledge
+/// zeny, so zeny shows up right away and is only taken back on failure.
+#[derive(Default)]
+pub struct Trade {
+    data: TrackedState<TradeData>,
+    /// Name of the player that asked for or was asked for a trade, shown once
+    /// the trade starts.
+    requested_partner: Option<String>,
+    pending_items: VecDeque<Item>,
+    is_active: bool,
+}
+
+impl Trade {
+    pub fn set_partner_name(&mut self, name: String) {
+        self.requested_partner = Some(name);
+    }
+
+    pub fn start(&mut self) {
+        let partner_name = self.requested_partner.take().unwrap_or_default();
+
+        self.pending_items.clear();
+        self.is_active = true;
+        self.data.set(TradeData {
+            partner_name,
+            ..Default::default()
+        });
+    }
+
+    fn can_offer(&self) -> bool {
+        self.is_active && !self.data.borrow().own_offer.is_locked
+    }
+
+    /// Returns `true` if the item should be offered to the map server. Items
+    /// that are equipped or already offered are rejected.
+    pub fn offer_item(&mut self, item: Item) -> bool {
+        if !self.can_offer() || item.equipped_position != EquipPosition::None {
+            return false;
+        }
+
+        let (offered_count, is_offered) = {
+            let data = self.data.borrow();
+            let own_items = &data.own_offer.items;
+
+            (
+                own_items.len() + self.pending_items.len(),
+                own_items
+                    .iter()
+                    .chain(&self.pending_items)
+                    .any(|offered| offered.index == item.index),
+            )
+        };
+
+        if is_offered || offered_count >= TradeOffer::MAXIMUM_ITEMS {
+            return false;
+        }
+
+        self.pending_items.push_back(item);
+
+        true
+    }
+
+    /// Returns `true` if the zeny should be offered to the map server.
+    pub fn offer_zeny(&mut self, amount: u32) -> bool {
+        if !self.can_offer() || amount == 0 {
+            return false;
+        }
+
+        self.data.with_mut(|data| {
+            data.own_offer.zeny = amount;
+            ValueState::Mutated(())
+        });
+
+        true
+    }
+
+    /// Apply the answer of the map server to an offer of the player. The
+    /// index is `None` for zeny.
+    pub fn resolve_offer(&mut self, index: Option<ItemIndex>, success: bool) {
+        match index {
+            Some(index) => {
+                let Some(position) = self.pending_items.iter().position(|item| item.index == index) else {
+                    return;
+                };
+                let item = self.pending_items.remove(position).unwrap();
+
+                if success {
+                    self.data.with_mut(|data| {
+                        data.own_offer.items.push(item);
+                        ValueState::Mutated(())
+                    });
+                }
+            }
+            None => {
+                if !success {
+                    self.data.with_mut(|data| {
+                        data.own_offer.zeny = 0;
+                        ValueState::Mutated(())
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn add_partner_item(
+        &mut self,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        script_loader: &ScriptLoader,
+        item_data: InventoryItemData,
+    ) {
+        let item = Item::new(game_file_loader, texture_loader, script_loader, item_data, 0);
+
+        self.data.with_mut(|data| {
+            data.partner_offer.items.push(item);
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn set_partner_zeny(&mut self, zeny: u32) {
+        self.data.with_mut(|data| {
+            data.partner_offer.zeny = zeny;
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn lock(&mut self, partner: bool) {
+        self.data.with_mut(|data| {
+            match partner {
+                true => data.partner_offer.is_locked = true,
+                false => data.own_offer.is_locked = true,
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    /// Returns `true` if the trade should be finalized.
+    pub fn confirm(&mut self) -> bool {
+        self.data.with_mut(|data| match data.can_confirm() {
+            true => {
+                data.is_confirmed = true;
+                ValueState::Mutated(true)
+            }
+            false => ValueState::Unchanged(false),
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.requested_partner = None;
+        self.pending_items.clear();
+        self.is_active = false;
+        self.data.set(TradeData::default());
+    }
+
+    pub fn get_data(&self) -> Remote<TradeData> {
+        self.data.new_remote()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Trade;
+
+    fn started_trade() -> Trade {
+        let mut trade = Trade::default();
+        trade.set_partner_name("partner".to_owned());
+        trade.start();
+        trade
+    }
+
+    #[test]
+    fn start_uses_requested_partner() {
+        let trade = started_trade();
+
+        assert_eq!(trade.get_data().borrow().partner_name, "partner");
+    }
+
+    #[test]
+    fn zeny_is_offered_immediately() {
+        let mut trade = started_trade();
+
+        assert!(trade.offer_zeny(500));
+        assert_eq!(trade.get_data().borrow().own_offer.zeny, 500);
+
+        trade.resolve_offer(None, true);
+        assert_eq!(trade.get_data().borrow().own_offer.zeny, 500);
+    }
+
+    #[test]
+    fn rejected_zeny_is_taken_back() {
+        let mut trade = started_trade();
+
+        trade.offer_zeny(500);
+        trade.resolve_offer(None, false);
+
+        assert_eq!(trade.get_data().borrow().own_offer.zeny, 0);
+    }
+
+    #[test]
+    fn no_offers_outside_of_trade_or_after_lock() {
+        let mut trade = Trade::default();
+        assert!(!trade.offer_zeny(500));
+
+        let mut trade = started_trade();
+        assert!(!trade.offer_zeny(0));
+
+        trade.lock(false);
+        assert!(!trade.offer_zeny(500));
+        assert_eq!(trade.get_data().borrow().own_offer.zeny, 0);
+    }
+
+    #[test]
+    fn confirm_requires_both_locks() {
+        let mut trade = started_trade();
+        assert!(!trade.confirm());
+
+        trade.lock(false);
+        assert!(!trade.confirm());
+
+        trade.lock(true);
+        assert!(trade.confirm());
+        assert!(!trade.confirm());
+    }
+
+    #[test]
+    fn partner_zeny_and_clear() {
+        let mut trade = started_trade();
+
+        trade.set_partner_zeny(1000);
+        assert_eq!(trade.get_data().borrow().partner_offer.zeny, 1000);
+
+        trade.clear();
+        let data = trade.get_data();
+        assert_eq!(data.borrow().partner_offer.zeny, 0);
+        assert!(data.borrow().partner_name.is_empty());
+        assert!(!trade.offer_zeny(500));
+    }
+}
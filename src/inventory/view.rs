@@ -0,0 +1,260 @@
+use std::cmp::Reverse;
+
+use super::Item;
+use crate::interface::{Remote, TrackedState};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ItemSorting {
+    /// The order of the inventory on the map server.
+    #[default]
+    Inventory,
+    Type,
+    Name,
+    Recency,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ItemCategory {
+    Usable,
+    Equipment,
+    Etc,
+}
+
+impl ItemCategory {
+    pub fn from_item_type(item_type: u8) -> Self {
+        match item_type {
+            // Healing, usable, delayed consumption and cash shop items.
+            0 | 2 | 11 | 18 => ItemCategory::Usable,
+            // Armor, weapons, pet armor, ammunition and shadow gear.
+            4 | 5 | 8 | 10 | 12 => ItemCategory::Equipment,
+            _ => ItemCategory::Etc,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InventoryTab {
+    All,
+    Category(ItemCategory),
+    /// Favorite items are only shown in this tab and the one for all items.
+    Favorites,
+}
+
+impl InventoryTab {
+    fn contains(self, item: &Item) -> bool {
+        match self {
+            InventoryTab::All => true,
+            InventoryTab::Category(category) => !item.is_favorite && ItemCategory::from_item_type(item.item_type) == category,
+            InventoryTab::Favorites => item.is_favorite,
+        }
+    }
+}
+
+/// Sorting and search of the inventory window, shared by all of its tabs.
+/// The inventory itself is never reordered.
+#[derive(Default)]
+pub struct InventoryView {
+    search: TrackedState<String>,
+    sorting: TrackedState<ItemSorting>,
+}
+
+impl InventoryView {
+    pub fn get_search_state(&self) -> TrackedState<String> {
+        self.search.clone()
+    }
+
+    pub fn get_sorting_state(&self) -> TrackedState<ItemSorting> {
+        self.sorting.clone()
+    }
+
+    /// Filter for a single tab of the inventory window.
+    pub fn filter(&self, tab: InventoryTab) -> InventoryFilter {
+        InventoryFilter {
+            tab,
+            search: self.search.new_remote(),
+            sorting: self.sorting.new_remote(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct InventoryFilter {
+    tab: InventoryTab,
+    search: Remote<String>,
+    sorting: Remote<ItemSorting>,
+}
+
+impl InventoryFilter {
+    pub fn consume_changed(&mut self) -> bool {
+        // NOTE: Both have to be consumed, so don't short circuit.
+        let search_changed = self.search.consume_changed();
+        let sorting_changed = self.sorting.consume_changed();
+        search_changed || sorting_changed
+    }
+
+    pub fn apply(&self, items: &[Item]) -> Vec<Item> {
+        let search = self.search.borrow().trim().to_lowercase();
+
+        let mut items: Vec<Item> = items
+            .iter()
+            .filter(|item| self.tab.contains(*item))
+            .filter(|item| search.is_empty() || item.info.name.to_lowercase().contains(&search))
+            .cloned()
+            .collect();
+
+        match *self.sorting.borrow() {
+            ItemSorting::Inventory => {}
+            ItemSorting::Type => items.sort_by_key(|item| ItemCategory::from_item_type(item.item_type)),
+            ItemSorting::Name => items.sort_by_cached_key(|item| item.info.name.to_lowercase()),
+            ItemSorting::Recency => items.sort_by_key(|item| Reverse(item.acquired)),
+        }
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use ragnarok_bytes::{ByteStream, FromBytes};
+    use vulkano::device::{Device, DeviceCreateInfo, QueueCreateInfo};
+    use vulkano::format::Format;
+    use vulkano::image::view::ImageView;
+    use vulkano::image::{Image, ImageCreateInfo, ImageUsage};
+    use vulkano::instance::{Instance, InstanceCreateFlags, InstanceCreateInfo};
+    use vulkano::memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator};
+    use vulkano::VulkanLibrary;
+
+    use super::{InventoryTab, InventoryView, ItemCategory, ItemSorting};
+    use crate::inventory::Item;
+    use crate::loaders::ItemInfo;
+    use crate::network::{EquipPosition, ItemId, ItemIndex};
+
+    /// Items always have a texture, so the tests need a device to create one.
+    fn texture() -> Arc<ImageView> {
+        let library = VulkanLibrary::new().expect("failed to load the vulkan library");
+        let instance = Instance::new(library, InstanceCreateInfo {
+            flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
+            ..Default::default()
+        })
+        .unwrap();
+        let physical_device = instance.enumerate_physical_devices().unwrap().next().expect("no physical device");
+        let (device, _queues) = Device::new(physical_device, DeviceCreateInfo {
+            queue_create_infos: vec![QueueCreateInfo {
+                queue_family_index: 0,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [1, 1, 1],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        ImageView::new_default(image).unwrap()
+    }
+
+    fn item_index(index: u16) -> ItemIndex {
+        let bytes = (index + 2).to_le_bytes();
+        let mut byte_stream: ByteStream<()> = ByteStream::without_metadata(&bytes);
+        ItemIndex::from_bytes(&mut byte_stream).unwrap()
+    }
+
+    fn item(texture: &Arc<ImageView>, index: u16, name: &str, item_type: u8, is_favorite: bool, acquired: usize) -> Item {
+        Item {
+            index: item_index(index),
+            item_id: ItemId(index as u32),
+            equip_position: EquipPosition::None,
+            equipped_position: EquipPosition::None,
+            amount: 1,
+            refinement_level: 0,
+            cards: [0; 4],
+            is_broken: false,
+            is_favorite,
+            item_type,
+            acquired,
+            texture: texture.clone(),
+            info: ItemInfo {
+                name: name.to_owned(),
+                description: Vec::new(),
+                slot_count: 0,
+                weight: None,
+            },
+        }
+    }
+
+    fn items() -> Vec<Item> {
+        let texture = texture();
+
+        vec![
+            item(&texture, 0, "Red Potion", 0, false, 3),
+            item(&texture, 1, "Knife", 5, true, 1),
+            item(&texture, 2, "Jellopy", 3, false, 4),
+            item(&texture, 3, "Shadow Armor", 12, false, 2),
+        ]
+    }
+
+    fn names(items: Vec<Item>) -> Vec<String> {
+        items.into_iter().map(|item| item.info.name).collect()
+    }
+
+    #[test]
+    fn shadow_gear_is_equipment() {
+        assert_eq!(ItemCategory::from_item_type(12), ItemCategory::Equipment);
+    }
+
+    #[test]
+    fn filter_by_tab() {
+        let view = InventoryView::default();
+        let items = items();
+
+        assert_eq!(names(view.filter(InventoryTab::All).apply(&items)), [
+            "Red Potion",
+            "Knife",
+            "Jellopy",
+            "Shadow Armor"
+        ]);
+        // NOTE: Favorites are only shown in their own tab.
+        assert_eq!(
+            names(view.filter(InventoryTab::Category(ItemCategory::Equipment)).apply(&items)),
+            ["Shadow Armor"]
+        );
+        assert_eq!(names(view.filter(InventoryTab::Favorites).apply(&items)), ["Knife"]);
+    }
+
+    #[test]
+    fn filter_by_search() {
+        let view = InventoryView::default();
+        let items = items();
+        view.get_search_state().set(" POTION ".to_owned());
+
+        assert_eq!(names(view.filter(InventoryTab::All).apply(&items)), ["Red Potion"]);
+    }
+
+    #[test]
+    fn sort_items() {
+        let view = InventoryView::default();
+        let items = items();
+        let filter = view.filter(InventoryTab::All);
+
+        view.get_sorting_state().set(ItemSorting::Name);
+        assert_eq!(names(filter.apply(&items)), ["Jellopy", "Knife", "Red Potion", "Shadow Armor"]);
+
+        view.get_sorting_state().set(ItemSorting::Type);
+        assert_eq!(names(filter.apply(&items)), ["Red Potion", "Knife", "Shadow Armor", "Jellopy"]);
+
+        view.get_sorting_state().set(ItemSorting::Recency);
+        assert_eq!(names(filter.apply(&items)), ["Jellopy", "Red Potion", "Shadow Armor", "Knife"]);
+    }
+}
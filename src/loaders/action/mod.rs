@@ -76,14 +76,7 @@ pub struct Actions {
 }
 
 impl Actions {
-    pub fn render(
-        &self,
-        sprite: &Sprite,
-        animation_state: &AnimationState,
-        camera_direction: usize,
-        head_direction: usize,
-    ) -> (Arc<ImageView>, Vector2<f32>, bool) {
-        let direction = (camera_direction + head_direction) % 8;
+    fn current_motion(&self, animation_state: &AnimationState, direction: usize) -> &Motion {
         let aa = animation_state.action * 8 + direction;
         let a = &self.actions[aa % self.actions.len()];
         let delay = self.delays[aa % self.delays.len()];
@@ -100,7 +93,27 @@ impl Actions {
         // TODO: work out how to avoid losing digits when casting timg to an f32. When
         // fixed remove set_start_time in MouseCursor.
 
-        let fs = &a.motions[frame as usize % a.motions.len()];
+        &a.motions[frame as usize % a.motions.len()]
+    }
+
+    /// Position of the first attach point of the current frame. Heads are
+    /// placed so that their attach point lines up with the one of the body.
+    pub fn attach_point(&self, animation_state: &AnimationState, camera_direction: usize) -> Option<Vector2<f32>> {
+        self.current_motion(animation_state, camera_direction % 8)
+            .attach_points
+            .first()
+            .map(|attach_point| attach_point.position.map(|component| component as f32))
+    }
+
+    pub fn render(
+        &self,
+        sprite: &Sprite,
+        animation_state: &AnimationState,
+        camera_direction: usize,
+        head_direction: usize,
+    ) -> (Arc<ImageView>, Vector2<f32>, bool) {
+        let direction = (camera_direction + head_direction) % 8;
+        let fs = self.current_motion(animation_state, direction);
 
         let texture = sprite.textures[fs.sprite_clips[0].sprite_number as usize].clone();
         let texture_size = texture.image().extent().map(|component| component as f32);
@@ -121,29 +134,13 @@ impl Actions {
         animation_state: &AnimationState,
         position: ScreenPosition,
         camera_direction: usize,
+        screen_clip: ScreenClip,
         color: Color,
         interface_settings: &InterfaceSettings,
     ) where
         T: Renderer + SpriteRenderer,
     {
-        let direction = camera_direction % 8;
-        let aa = animation_state.action * 8 + direction;
-        let a = &self.actions[aa % self.actions.len()];
-        let delay = self.delays[aa % self.delays.len()];
-
-        let factor = animation_state
-            .factor
-            .map(|factor| delay * (factor / 5.0))
-            .unwrap_or_else(|| delay * 50.0);
-
-        let frame = animation_state
-            .duration
-            .map(|duration| animation_state.time * a.motions.len() as u32 / duration)
-            .unwrap_or_else(|| (animation_state.time as f32 / factor) as u32);
-        // TODO: work out how to avoid losing digits when casting timg to an f32. When
-        // fixed remove set_start_time in MouseCursor.
-
-        let fs = &a.motions[frame as usize % a.motions.len()];
+        let fs = self.current_motion(animation_state, camera_direction % 8);
 
         for sprite_clip in &fs.sprite_clips {
             // NOTE: `get` instead of a direct index in case a fallback was loaded
@@ -175,13 +172,6 @@ impl Actions {
                 top: final_position.y,
             };
 
-            let screen_clip = ScreenClip {
-                left: 0.0,
-                top: 0.0,
-                right: f32::MAX,
-                bottom: f32::MAX,
-            };
-
             renderer.render_sprite(
                 render_target,
                 texture.clone(),
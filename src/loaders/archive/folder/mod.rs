@@ -28,8 +28,15 @@ impl FolderArchive {
         }
     }
 
-    /// Load the file mapping of a given directory.
+    /// Load the file mapping of a given directory. A directory called `data`
+    /// is treated as the data folder of the client, so its name is kept as
+    /// part of the asset paths, just like in the native archives.
     fn load_mapping(directory: &PathBuf) -> HashMap<String, PathBuf> {
+        let root = match directory.file_name().and_then(|name| name.to_str()) {
+            Some(name) if name.eq_ignore_ascii_case("data") => directory.parent().unwrap_or(directory),
+            _ => directory,
+        };
+
         WalkDir::new(directory)
             .into_iter()
             .filter_map(|entry| entry.ok())
@@ -37,7 +44,7 @@ impl FolderArchive {
             .map(|file| {
                 let asset_path = file
                     .path()
-                    .strip_prefix(directory)
+                    .strip_prefix(root)
                     .unwrap()
                     .to_str()
                     .unwrap()
@@ -96,3 +103,11 @@ impl Writable for FolderArchive {
         fs::write(&full_path, file_data).unwrap_or_else(|_| panic!("error writing to file {}", full_path.display()));
     }
 }
+
+impl FolderArchive {
+    /// Remove a file from the folder. Files that don't exist are ignored.
+    pub fn remove_file(&mut self, file_path: &str) {
+        let full_path = self.folder_path.join(Self::os_specific_path(file_path));
+        let _ = fs::remove_file(full_path);
+    }
+}
@@ -0,0 +1,295 @@
+//! Decryption of GRF entries. Encrypted entries use a modified DES with only
+//! a single round, which is applied either to the first 20 blocks of an entry
+//! or to the first 20 blocks and then periodically to the rest of the entry.
+//! In the latter case some of the remaining blocks are also shuffled.
+
+/// Flag of entries that are files rather than directories.
+pub(super) const FILE_FLAG: u8 = 0x01;
+/// Flag of entries that are encrypted throughout.
+pub(super) const MIXED_ENCRYPTION_FLAG: u8 = 0x02;
+/// Flag of entries that only have their first blocks encrypted.
+pub(super) const HEADER_ENCRYPTION_FLAG: u8 = 0x04;
+
+const BLOCK_SIZE: usize = 8;
+const ENCRYPTED_HEADER_BLOCKS: usize = 20;
+const SHUFFLE_CYCLE: usize = 7;
+
+const MASK: [u8; 8] = [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+
+const INITIAL_PERMUTATION: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4, 62, 54, 46, 38, 30, 22, 14, 6, 64, 56, 48, 40, 32, 24, 16, 8, 57, 49, 41,
+    33, 25, 17, 9, 1, 59, 51, 43, 35, 27, 19, 11, 3, 61, 53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const FINAL_PERMUTATION: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31, 38, 6, 46, 14, 54, 22, 62, 30, 37, 5, 45, 13, 53, 21, 61, 29, 36, 4, 44,
+    12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27, 34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41, 9, 49, 17, 57, 25,
+];
+
+const TRANSPOSITION: [u8; 32] = [
+    16, 7, 20, 21, 29, 12, 28, 17, 1, 15, 23, 26, 5, 18, 31, 10, 2, 8, 24, 14, 32, 27, 3, 9, 19, 13, 30, 6, 22, 11, 4, 25,
+];
+
+/// The eight DES substitution boxes, combined so that every lookup resolves
+/// two boxes at once. The upper nibble holds the result of the even box and
+/// the lower nibble holds the result of the odd box.
+const SUBSTITUTION_BOXES: [[u8; 64]; 4] = [
+    [
+        0xEF, 0x03, 0x41, 0xFD, 0xD8, 0x74, 0x1E, 0x47, 0x26, 0xEF, 0xFB, 0x22, 0xB3, 0xD8, 0x84, 0x1E, 0x39, 0xAC, 0xA7, 0x60, 0x62, 0xC1,
+        0xCD, 0xBA, 0x5C, 0x96, 0x90, 0x59, 0x05, 0x3B, 0x7A, 0x85, 0x40, 0xFD, 0x1E, 0xC8, 0xE7, 0x8A, 0x8B, 0x21, 0xDA, 0x43, 0x64, 0x9F,
+        0x2D, 0x14, 0xB1, 0x72, 0xF5, 0x5B, 0xC8, 0xB6, 0x9C, 0x37, 0x76, 0xEC, 0x39, 0xA0, 0xA3, 0x05, 0x52, 0x6E, 0x0F, 0xD9,
+    ],
+    [
+        0xA7, 0xDD, 0x0D, 0x78, 0x9E, 0x0B, 0xE3, 0x95, 0x60, 0x36, 0x36, 0x4F, 0xF9, 0x60, 0x5A, 0xA3, 0x11, 0x24, 0xD2, 0x87, 0xC8, 0x52,
+        0x75, 0xEC, 0xBB, 0xC1, 0x4C, 0xBA, 0x24, 0xFE, 0x8F, 0x19, 0xDA, 0x13, 0x66, 0xAF, 0x49, 0xD0, 0x90, 0x06, 0x8C, 0x6A, 0xFB, 0x91,
+        0x37, 0x8D, 0x0D, 0x78, 0xBF, 0x49, 0x11, 0xF4, 0x23, 0xE5, 0xCE, 0x3B, 0x55, 0xBC, 0xA2, 0x57, 0xE8, 0x22, 0x74, 0xCE,
+    ],
+    [
+        0x2C, 0xEA, 0xC1, 0xBF, 0x4A, 0x24, 0x1F, 0xC2, 0x79, 0x47, 0xA2, 0x7C, 0xB6, 0xD9, 0x68, 0x15, 0x80, 0x56, 0x5D, 0x01, 0x33, 0xFD,
+        0xF4, 0xAE, 0xDE, 0x30, 0x07, 0x9B, 0xE5, 0x83, 0x9B, 0x68, 0x49, 0xB4, 0x2E, 0x83, 0x1F, 0xC2, 0xB5, 0x7C, 0xA2, 0x19, 0xD8, 0xE5,
+        0x7C, 0x2F, 0x83, 0xDA, 0xF7, 0x6B, 0x90, 0xFE, 0xC4, 0x01, 0x5A, 0x97, 0x61, 0xA6, 0x3D, 0x40, 0x0B, 0x58, 0xE6, 0x3D,
+    ],
+    [
+        0x4D, 0xD1, 0xB2, 0x0F, 0x28, 0xBD, 0xE4, 0x78, 0xF6, 0x4A, 0x0F, 0x93, 0x8B, 0x17, 0xD1, 0xA4, 0x3A, 0xEC, 0xC9, 0x35, 0x93, 0x56,
+        0x7E, 0xCB, 0x55, 0x20, 0xA0, 0xFE, 0x6C, 0x89, 0x17, 0x62, 0x17, 0x62, 0x4B, 0xB1, 0xB4, 0xDE, 0xD1, 0x87, 0xC9, 0x14, 0x3C, 0x4A,
+        0x7E, 0xA8, 0xE2, 0x7D, 0xA0, 0x9F, 0xF6, 0x5C, 0x6A, 0x09, 0x8D, 0xF0, 0x0F, 0xE3, 0x53, 0x25, 0x95, 0x36, 0x28, 0xCB,
+    ],
+];
+
+type Block = [u8; BLOCK_SIZE];
+
+fn permute(block: &Block, table: &[u8; 64]) -> Block {
+    let mut permuted = [0; BLOCK_SIZE];
+
+    for (index, source) in table.iter().enumerate() {
+        let source = (*source - 1) as usize;
+
+        if block[source / 8] & MASK[source % 8] != 0 {
+            permuted[index / 8] |= MASK[index % 8];
+        }
+    }
+
+    permuted
+}
+
+/// Expand the right half of the block into eight groups of six bits.
+fn expand(block: &Block) -> Block {
+    [
+        ((block[7] << 5) | (block[4] >> 3)) & 0x3F,
+        ((block[4] << 1) | (block[5] >> 7)) & 0x3F,
+        ((block[4] << 5) | (block[5] >> 3)) & 0x3F,
+        ((block[5] << 1) | (block[6] >> 7)) & 0x3F,
+        ((block[5] << 5) | (block[6] >> 3)) & 0x3F,
+        ((block[6] << 1) | (block[7] >> 7)) & 0x3F,
+        ((block[6] << 5) | (block[7] >> 3)) & 0x3F,
+        ((block[7] << 1) | (block[4] >> 7)) & 0x3F,
+    ]
+}
+
+fn substitute(block: &Block) -> Block {
+    let mut substituted = [0; BLOCK_SIZE];
+
+    for (index, substitution_box) in SUBSTITUTION_BOXES.iter().enumerate() {
+        substituted[index] =
+            (substitution_box[block[index * 2] as usize] & 0xF0) | (substitution_box[block[index * 2 + 1] as usize] & 0x0F);
+    }
+
+    substituted
+}
+
+/// Transpose the result of the substitution into the right half of the block.
+fn transpose(block: &Block) -> Block {
+    let mut transposed = [0; BLOCK_SIZE];
+
+    for (index, source) in TRANSPOSITION.iter().enumerate() {
+        let source = (*source - 1) as usize;
+
+        if block[source / 8] & MASK[source % 8] != 0 {
+            transposed[index / 8 + 4] |= MASK[index % 8];
+        }
+    }
+
+    transposed
+}
+
+fn decrypt_block(block: &mut Block) {
+    let mut permuted = permute(block, &INITIAL_PERMUTATION);
+    let round = transpose(&substitute(&expand(&permuted)));
+
+    permuted
+        .iter_mut()
+        .zip(&round[4..])
+        .for_each(|(byte, round_byte)| *byte ^= round_byte);
+
+    *block = permute(&permuted, &FINAL_PERMUTATION);
+}
+
+fn substitute_byte(byte: u8) -> u8 {
+    match byte {
+        0x00 => 0x2B,
+        0x2B => 0x00,
+        0x6C => 0x80,
+        0x80 => 0x6C,
+        0x01 => 0x68,
+        0x68 => 0x01,
+        0x48 => 0x77,
+        0x77 => 0x48,
+        0x60 => 0xFF,
+        0xFF => 0x60,
+        0xB9 => 0xC0,
+        0xC0 => 0xB9,
+        0xFE => 0xEB,
+        0xEB => 0xFE,
+        byte => byte,
+    }
+}
+
+fn unshuffle_block(block: &mut Block) {
+    *block = [
+        block[3],
+        block[4],
+        block[6],
+        block[0],
+        block[1],
+        block[2],
+        block[5],
+        substitute_byte(block[7]),
+    ];
+}
+
+fn blocks(data: &mut [u8]) -> impl Iterator<Item = &mut Block> {
+    data.chunks_exact_mut(BLOCK_SIZE).map(|chunk| chunk.try_into().unwrap())
+}
+
+fn decrypt_header(data: &mut [u8]) {
+    blocks(data).take(ENCRYPTED_HEADER_BLOCKS).for_each(decrypt_block);
+}
+
+fn decrypt_mixed(data: &mut [u8], compressed_size: usize) {
+    // NOTE: The gap between encrypted blocks depends on the number of digits of
+    // the compressed size.
+    let digits = compressed_size.max(1).ilog10() as usize + 1;
+    let cycle = match digits {
+        0..=2 => 1,
+        3..=4 => digits + 1,
+        5..=6 => digits + 9,
+        _ => digits + 15,
+    };
+
+    let mut plain_blocks = 0;
+
+    for (index, block) in blocks(data).enumerate() {
+        if index < ENCRYPTED_HEADER_BLOCKS || index % cycle == 0 {
+            decrypt_block(block);
+            continue;
+        }
+
+        // NOTE: Every seventh block that isn't encrypted is shuffled, skipping the
+        // first one.
+        if plain_blocks % SHUFFLE_CYCLE == 0 && plain_blocks != 0 {
+            unshuffle_block(block);
+        }
+
+        plain_blocks += 1;
+    }
+}
+
+/// Decrypt the data of an entry in place. `compressed_size` is the size of the
+/// compressed data without the padding to the block size.
+pub(super) fn decrypt_entry(data: &mut [u8], flags: u8, compressed_size: usize) {
+    if flags & MIXED_ENCRYPTION_FLAG != 0 {
+        decrypt_mixed(data, compressed_size);
+    } else if flags & HEADER_ENCRYPTION_FLAG != 0 {
+        decrypt_header(data);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COMPRESSED_SIZE: usize = 283;
+
+    fn encrypted_data() -> Vec<u8> {
+        (0..288).map(|index: usize| (index * 37 + 11) as u8).collect()
+    }
+
+    fn block(data: &[u8], index: usize) -> &[u8] {
+        &data[index * BLOCK_SIZE..(index + 1) * BLOCK_SIZE]
+    }
+
+    // NOTE: The expected blocks are known answers of a single round of textbook
+    // DES (FIPS 46-3) with an all-zero subkey, which is what the GRF cipher
+    // computes. The reference implementation was checked against the standard
+    // full DES vectors (zero key and zero plaintext give 8CA64DE9C1B123A7, key
+    // 133457799BBCDFF1 and plaintext 0123456789ABCDEF give 85E813540F0AB405).
+    const KNOWN_ANSWERS: [(Block, Block); 4] = [
+        ([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], [
+            0x04, 0x04, 0x01, 0x55, 0x55, 0x01, 0x54, 0x55,
+        ]),
+        ([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08], [
+            0x44, 0x16, 0x02, 0x51, 0x40, 0x06, 0x42, 0x5C,
+        ]),
+        ([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF], [
+            0x51, 0x76, 0x05, 0x76, 0x98, 0xEA, 0xD9, 0xEB,
+        ]),
+        ([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], [
+            0xEA, 0xEE, 0xFF, 0xAA, 0xAB, 0xBB, 0xEA, 0xEA,
+        ]),
+    ];
+
+    #[test]
+    fn decrypt_block_known_answers() {
+        for (input, expected) in KNOWN_ANSWERS {
+            let mut block = input;
+            decrypt_block(&mut block);
+
+            assert_eq!(block, expected);
+        }
+    }
+
+    #[test]
+    fn decrypt_block_is_involution() {
+        for (input, expected) in KNOWN_ANSWERS {
+            let mut block = expected;
+            decrypt_block(&mut block);
+
+            assert_eq!(block, input);
+        }
+    }
+
+    #[test]
+    fn decrypt_header_encrypted_entry() {
+        let mut data = encrypted_data();
+        decrypt_entry(&mut data, FILE_FLAG | HEADER_ENCRYPTION_FLAG, COMPRESSED_SIZE);
+
+        assert_eq!(block(&data, 0), [0x4B, 0x65, 0x14, 0x7B, 0xCF, 0x95, 0xF9, 0x1E]);
+        assert_eq!(block(&data, 20), block(&encrypted_data(), 20));
+    }
+
+    #[test]
+    fn decrypt_mixed_encrypted_entry() {
+        let mut data = encrypted_data();
+        decrypt_entry(&mut data, FILE_FLAG | MIXED_ENCRYPTION_FLAG, COMPRESSED_SIZE);
+
+        // Header block.
+        assert_eq!(block(&data, 0), [0x4B, 0x65, 0x14, 0x7B, 0xCF, 0x95, 0xF9, 0x1E]);
+        // Plain block.
+        assert_eq!(block(&data, 21), block(&encrypted_data(), 21));
+        // Encrypted block, since the size has three digits every fourth block is
+        // encrypted.
+        assert_eq!(block(&data, 24), [0x9B, 0xB1, 0x14, 0x6B, 0x0E, 0xD5, 0xF9, 0xCA]);
+        // Shuffled block, which is the seventh plain block after the header.
+        assert_eq!(block(&data, 30), [0x2A, 0x4F, 0x99, 0xBB, 0xE0, 0x05, 0x74, 0xBE]);
+        // Plain block.
+        assert_eq!(block(&data, 31), block(&encrypted_data(), 31));
+    }
+
+    #[test]
+    fn unencrypted_entry() {
+        let mut data = encrypted_data();
+        decrypt_entry(&mut data, FILE_FLAG, COMPRESSED_SIZE);
+
+        assert_eq!(data, encrypted_data());
+    }
+}
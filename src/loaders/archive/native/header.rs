@@ -13,15 +13,22 @@ pub(super) struct Header {
 }
 
 impl Header {
-    pub fn validate_version(&self) {
-        assert_eq!(self.version, 0x200, "invalid grf version");
+    pub fn validate_version(&self) -> Result<(), String> {
+        match self.version {
+            0x200 => Ok(()),
+            version => Err(format!("invalid grf version {version:#x}")),
+        }
     }
 
     pub fn get_file_table_offset(&self) -> usize {
         self.file_table_offset as usize
     }
 
-    pub fn get_file_count(&self) -> usize {
-        (self.file_count - self.reserved_files) as usize - 7
+    /// Returns `None` if the header is corrupt.
+    pub fn get_file_count(&self) -> Option<usize> {
+        self.file_count
+            .checked_sub(self.reserved_files)?
+            .checked_sub(7)
+            .map(|count| count as usize)
     }
 }
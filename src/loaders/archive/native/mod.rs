@@ -1,6 +1,7 @@
 //! A GRF file containing game assets.
 mod assettable;
 mod builder;
+mod encryption;
 mod filetablerow;
 mod header;
 
@@ -14,6 +15,7 @@ use yazi::{decompress, Format};
 
 use self::assettable::AssetTable;
 pub use self::builder::NativeArchiveBuilder;
+use self::encryption::{decrypt_entry, FILE_FLAG};
 use self::filetablerow::FileTableRow;
 use self::header::Header;
 #[cfg(feature = "debug")]
@@ -33,39 +35,50 @@ pub struct NativeArchive {
 const MAGIC_BYTES: &[u8] = b"Master of Magic\0";
 const UNPACKED_SIZE_OF_MAGIC_STRING: usize = MAGIC_BYTES.len();
 
-impl Archive for NativeArchive {
-    fn from_path(path: &Path) -> Self {
+impl NativeArchive {
+    /// Get the paths of all assets in the archive.
+    pub fn get_file_paths(&self) -> Vec<String> {
+        self.file_table.keys().cloned().collect()
+    }
+
+    /// Like [`Archive::from_path`], but returns an error instead of panicking
+    /// if the archive is missing or corrupt.
+    pub fn try_from_path(path: &Path) -> Result<Self, String> {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load game data from {MAGENTA}{0}{NONE}", path.display()));
-        let mut file = File::open(path).unwrap();
+        let mut file = File::open(path).map_err(|error| error.to_string())?;
 
         let mut magic_number_buffer = [0u8; UNPACKED_SIZE_OF_MAGIC_STRING];
-        file.read_exact(&mut magic_number_buffer).unwrap();
+        file.read_exact(&mut magic_number_buffer).map_err(|error| error.to_string())?;
 
         // Keeping the convenience of using [`loaders::stream::ByteStream`]
         // while being able to read without buffering the entire file.
         let mut file_header_buffer = vec![0; Header::size_in_bytes()];
-        file.read_exact(&mut file_header_buffer).unwrap();
-        let file_header = Header::from_bytes(&mut ByteStream::<()>::without_metadata(&file_header_buffer)).unwrap();
-        file_header.validate_version();
+        file.read_exact(&mut file_header_buffer).map_err(|error| error.to_string())?;
+        let file_header =
+            Header::from_bytes(&mut ByteStream::<()>::without_metadata(&file_header_buffer)).map_err(|error| format!("{error:?}"))?;
+        file_header.validate_version()?;
 
-        let _ = file.seek(SeekFrom::Current(file_header.get_file_table_offset() as i64)).unwrap();
+        file.seek(SeekFrom::Current(file_header.get_file_table_offset() as i64))
+            .map_err(|error| error.to_string())?;
         let mut file_table_buffer = vec![0; AssetTable::size_in_bytes()];
 
-        file.read_exact(&mut file_table_buffer).unwrap();
-        let file_table = AssetTable::from_bytes(&mut ByteStream::<()>::without_metadata(&file_table_buffer)).unwrap();
+        file.read_exact(&mut file_table_buffer).map_err(|error| error.to_string())?;
+        let file_table =
+            AssetTable::from_bytes(&mut ByteStream::<()>::without_metadata(&file_table_buffer)).map_err(|error| format!("{error:?}"))?;
 
         let mut compressed_file_table_buffer = vec![0u8; file_table.get_compressed_size()];
-        file.read_exact(&mut compressed_file_table_buffer).unwrap();
-        let (decompressed, _checksum) = decompress(&compressed_file_table_buffer, Format::Zlib).unwrap();
+        file.read_exact(&mut compressed_file_table_buffer)
+            .map_err(|error| error.to_string())?;
+        let (decompressed, _checksum) = decompress(&compressed_file_table_buffer, Format::Zlib).map_err(|error| format!("{error:?}"))?;
 
-        let file_count = file_header.get_file_count();
+        let file_count = file_header.get_file_count().ok_or("invalid file count in grf header")?;
 
         let mut file_table_byte_stream = ByteStream::<()>::without_metadata(&decompressed);
         let mut assets = HashMap::with_capacity(file_count);
 
         for _index in 0..file_count {
-            let file_information = FileTableRow::from_bytes(&mut file_table_byte_stream).unwrap();
+            let file_information = FileTableRow::from_bytes(&mut file_table_byte_stream).map_err(|error| format!("{error:?}"))?;
             let file_name = file_information.file_name.to_lowercase();
 
             assets.insert(file_name, file_information);
@@ -76,37 +89,57 @@ impl Archive for NativeArchive {
 
         // TODO: only take 64..? bytes so that loaded game archives can be extended
         // aswell
-        Self {
+        Ok(Self {
             file_table: assets,
             os_file_handler: file,
-        }
+        })
     }
 
-    fn get_file_by_path(&mut self, asset_path: &str) -> Option<Vec<u8>> {
-        self.file_table.get(asset_path).and_then(|file_information| {
-            let mut compressed_file_buffer = vec![0u8; file_information.compressed_size_aligned as usize];
+    /// Like [`Archive::get_file_by_path`], but returns an error instead of
+    /// panicking if the asset can't be read.
+    pub fn try_get_file_by_path(&mut self, asset_path: &str) -> Result<Option<Vec<u8>>, String> {
+        let Some(file_information) = self.file_table.get(asset_path) else {
+            return Ok(None);
+        };
 
-            // TODO: Figure out what the GRF_FLAG_MIXCRYPT flag actually means and load the
-            // file correctly
-            if file_information.flags > 1 {
-                return None;
-            }
+        let mut compressed_file_buffer = vec![0u8; file_information.compressed_size_aligned as usize];
+        let compressed_size = file_information.compressed_size as usize;
 
-            let position = file_information.offset as u64 + UNPACKED_SIZE_OF_MAGIC_STRING as u64 + Header::size_in_bytes() as u64;
-            self.os_file_handler.seek(SeekFrom::Start(position)).unwrap();
-            self.os_file_handler.read_exact(&mut compressed_file_buffer).unwrap();
+        if compressed_size > compressed_file_buffer.len() {
+            return Err(format!("invalid compressed size of {asset_path}"));
+        }
 
-            let (uncompressed_file_buffer, _checksum) = decompress(&compressed_file_buffer, Format::Zlib).unwrap();
+        let position = file_information.offset as u64 + UNPACKED_SIZE_OF_MAGIC_STRING as u64 + Header::size_in_bytes() as u64;
+        self.os_file_handler
+            .seek(SeekFrom::Start(position))
+            .map_err(|error| error.to_string())?;
+        self.os_file_handler
+            .read_exact(&mut compressed_file_buffer)
+            .map_err(|error| error.to_string())?;
 
-            Some(uncompressed_file_buffer)
-        })
+        decrypt_entry(&mut compressed_file_buffer, file_information.flags, compressed_size);
+
+        let (uncompressed_file_buffer, _checksum) =
+            decompress(&compressed_file_buffer[..compressed_size], Format::Zlib).map_err(|error| format!("{error:?}"))?;
+
+        Ok(Some(uncompressed_file_buffer))
+    }
+}
+
+impl Archive for NativeArchive {
+    fn from_path(path: &Path) -> Self {
+        Self::try_from_path(path).unwrap()
+    }
+
+    fn get_file_by_path(&mut self, asset_path: &str) -> Option<Vec<u8>> {
+        self.try_get_file_by_path(asset_path).unwrap()
     }
 
     fn get_lua_files(&self, lua_files: &mut Vec<String>) {
         let files = self
             .file_table
             .iter()
-            .filter(|(file_name, row)| file_name.ends_with(".lub") && row.flags == 0x01)
+            .filter(|(file_name, row)| file_name.ends_with(".lub") && row.flags & FILE_FLAG != 0)
             .map(|(file_name, _)| file_name.clone());
 
         lua_files.extend(files);
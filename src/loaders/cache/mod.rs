@@ -0,0 +1,182 @@
+//! Disk cache for converted assets. Parsing game files and building vertex
+//! data is slow, so the results are stored in the cache directory, keyed by
+//! a hash of the archive entries they were created from. Changing the
+//! content of an archive entry automatically results in a new key.
+use std::path::PathBuf;
+
+use bytemuck::Pod;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+const CACHE_DIRECTORY: &str = "client/cache/";
+const MAGIC: [u8; 4] = *b"KCAC";
+/// Bump this whenever the layout of any cached asset or the conversion of
+/// the source data changes.
+const CACHE_VERSION: u32 = 5;
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+const FNV_PRIME: u64 = 0x100000001B3;
+
+/// Stable 64-bit FNV-1a hash over a list of byte slices. The standard library
+/// hasher is not guaranteed to produce the same values across releases, so it
+/// can't be used for keys that are stored on disk.
+pub fn cache_key(parts: &[&[u8]]) -> u64 {
+    parts.iter().fold(FNV_OFFSET_BASIS, |hash, part| {
+        // NOTE: Hash the length as well, so moving bytes between parts results in a
+        // different key.
+        (part.len() as u64)
+            .to_le_bytes()
+            .iter()
+            .chain(part.iter())
+            .fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+    })
+}
+
+fn cache_path(kind: &str, key: u64) -> PathBuf {
+    PathBuf::from(CACHE_DIRECTORY).join(format!("{kind}_{key:016x}.bin"))
+}
+
+/// Load a cached asset. Returns [`None`] if the asset is not cached or the
+/// entry was written by an incompatible version.
+pub fn load_cached(kind: &str, key: u64) -> Option<CacheReader> {
+    let data = std::fs::read(cache_path(kind, key)).ok()?;
+    let mut reader = CacheReader { data, offset: 0 };
+
+    (reader.read_pod::<[u8; 4]>()? == MAGIC && reader.read_u32()? == CACHE_VERSION).then_some(reader)
+}
+
+/// Store a converted asset. Failing to write the cache is not an error, the
+/// asset will simply be converted again on the next load.
+pub fn store_cached(kind: &str, key: u64, writer: CacheWriter) {
+    let path = cache_path(kind, key);
+    let result = std::fs::create_dir_all(CACHE_DIRECTORY).and_then(|_| std::fs::write(&path, writer.data));
+
+    #[cfg(feature = "debug")]
+    if let Err(error) = result {
+        print_debug!(
+            "[{}error{}] failed to write cache entry {}{}{}: {}",
+            RED,
+            NONE,
+            MAGENTA,
+            path.display(),
+            NONE,
+            error
+        );
+    }
+
+    #[cfg(not(feature = "debug"))]
+    let _ = result;
+}
+
+pub struct CacheWriter {
+    data: Vec<u8>,
+}
+
+impl CacheWriter {
+    pub fn new() -> Self {
+        let mut writer = Self { data: Vec::new() };
+
+        writer.write_pod(&MAGIC);
+        writer.write_u32(CACHE_VERSION);
+        writer
+    }
+
+    pub fn write_pod<T: Pod>(&mut self, value: &T) {
+        self.data.extend_from_slice(bytemuck::bytes_of(value));
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_pod(&value);
+    }
+
+    pub fn write_slice<T: Pod>(&mut self, values: &[T]) {
+        self.write_u32(values.len() as u32);
+        self.data.extend_from_slice(bytemuck::cast_slice(values));
+    }
+
+    pub fn write_string(&mut self, value: &str) {
+        self.write_slice(value.as_bytes());
+    }
+}
+
+pub struct CacheReader {
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl CacheReader {
+    fn read_bytes(&mut self, length: usize) -> Option<&[u8]> {
+        let end = self.offset.checked_add(length)?;
+        let bytes = self.data.get(self.offset..end)?;
+
+        self.offset = end;
+        Some(bytes)
+    }
+
+    pub fn read_pod<T: Pod>(&mut self) -> Option<T> {
+        self.read_bytes(std::mem::size_of::<T>()).map(bytemuck::pod_read_unaligned)
+    }
+
+    pub fn read_u32(&mut self) -> Option<u32> {
+        self.read_pod()
+    }
+
+    pub fn read_vec<T: Pod>(&mut self) -> Option<Vec<T>> {
+        let count = self.read_u32()? as usize;
+        let length = count.checked_mul(std::mem::size_of::<T>())?;
+
+        // NOTE: The data is not guaranteed to be aligned, so we can't cast it in place.
+        self.read_bytes(length).map(bytemuck::pod_collect_to_vec)
+    }
+
+    pub fn read_string(&mut self) -> Option<String> {
+        self.read_vec::<u8>().and_then(|bytes| String::from_utf8(bytes).ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cache_key, CacheReader, CacheWriter};
+
+    fn round_trip(writer: CacheWriter) -> CacheReader {
+        let mut reader = CacheReader {
+            data: writer.data,
+            offset: 0,
+        };
+
+        reader.read_pod::<[u8; 4]>().unwrap();
+        reader.read_u32().unwrap();
+        reader
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let mut writer = CacheWriter::new();
+        writer.write_u32(7);
+        writer.write_string("prontera");
+        writer.write_slice(&[[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+        let mut reader = round_trip(writer);
+
+        assert_eq!(reader.read_u32(), Some(7));
+        assert_eq!(reader.read_string().as_deref(), Some("prontera"));
+        assert_eq!(reader.read_vec::<[f32; 3]>(), Some(vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+        assert_eq!(reader.read_u32(), None);
+    }
+
+    #[test]
+    fn truncated_data() {
+        let mut writer = CacheWriter::new();
+        writer.write_slice(&[1u32, 2, 3]);
+        writer.data.pop();
+
+        assert_eq!(round_trip(writer).read_vec::<u32>(), None);
+    }
+
+    #[test]
+    fn key_depends_on_part_boundaries() {
+        assert_eq!(cache_key(&[b"ab", b"c"]), cache_key(&[b"ab", b"c"]));
+        assert_ne!(cache_key(&[b"ab", b"c"]), cache_key(&[b"a", b"bc"]));
+    }
+}
@@ -347,7 +347,7 @@ pub enum EffectCenter {
 }
 
 impl EffectCenter {
-    fn to_position(&self) -> Vector3<f32> {
+    pub fn to_position(&self) -> Vector3<f32> {
         match self {
             EffectCenter::Entity(_, position) | EffectCenter::Position(position) => *position,
         }
@@ -429,9 +429,88 @@ impl EffectBase for EffectWithLight {
     }
 }
 
+/// Light without an effect, used for ground units that have no effect file
+/// and for status effects that are attached to an entity.
+#[derive(new)]
+pub struct LightEffect {
+    center: EffectCenter,
+    light_offset: Vector3<f32>,
+    light_color: Color,
+    light_intensity: f32,
+    #[new(default)]
+    remaining_duration: Option<f32>,
+    #[new(default)]
+    current_light_intensity: f32,
+    #[new(default)]
+    gets_deleted: bool,
+}
+
+impl LightEffect {
+    /// Fade out the light after `duration` seconds.
+    pub fn with_duration(self, duration: f32) -> Self {
+        Self {
+            remaining_duration: Some(duration),
+            ..self
+        }
+    }
+}
+
+impl EffectBase for LightEffect {
+    fn update(&mut self, entities: &[crate::world::Entity], delta_time: f32) -> bool {
+        const FADE_SPEED: f32 = 5.0;
+
+        if let Some(remaining_duration) = &mut self.remaining_duration {
+            *remaining_duration -= delta_time;
+
+            if *remaining_duration <= 0.0 {
+                self.gets_deleted = true;
+            }
+        }
+
+        if let EffectCenter::Entity(entity_id, position) = &mut self.center {
+            match entities.iter().find(|entity| entity.get_entity_id() == *entity_id) {
+                Some(entity) => *position = entity.get_position(),
+                // NOTE: The map server doesn't end the status effects of entities that left
+                // our view, so the light is removed together with the entity.
+                None => self.gets_deleted = true,
+            }
+        }
+
+        let target = match self.gets_deleted {
+            true => 0.0,
+            false => self.light_intensity,
+        };
+
+        self.current_light_intensity += (target - self.current_light_intensity) * (FADE_SPEED * delta_time).min(1.0);
+
+        !self.gets_deleted || self.current_light_intensity > 0.1
+    }
+
+    fn mark_for_deletion(&mut self) {
+        self.gets_deleted = true;
+    }
+
+    fn render(&self, render_target: &mut <DeferredRenderer as Renderer>::Target, renderer: &DeferredRenderer, camera: &dyn Camera) {
+        renderer.point_light(
+            render_target,
+            camera,
+            self.center.to_position() + self.light_offset,
+            self.light_color,
+            self.current_light_intensity,
+        );
+    }
+}
+
+/// What an effect belongs to, so it can be removed once the map server ends it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EffectOwner {
+    Unit(EntityId),
+    Status(EntityId, u16),
+}
+
 #[derive(Default)]
 pub struct EffectHolder {
-    effects: Vec<(Box<dyn EffectBase + Send + Sync>, Option<EntityId>)>,
+    effects: Vec<(Box<dyn EffectBase + Send + Sync>, Option<EffectOwner>)>,
 }
 
 impl EffectHolder {
@@ -439,17 +518,38 @@ impl EffectHolder {
         self.effects.push((effect, None));
     }
 
-    pub fn add_unit(&mut self, effect: Box<dyn EffectBase + Send + Sync>, entity_id: EntityId) {
-        self.effects.push((effect, Some(entity_id)));
+    /// Replace the effect of an owner instead of stacking a second one on top.
+    fn add_owned(&mut self, effect: Box<dyn EffectBase + Send + Sync>, owner: EffectOwner) {
+        self.effects.retain(|(_, effect_owner)| *effect_owner != Some(owner));
+        self.effects.push((effect, Some(owner)));
     }
 
-    pub fn remove_unit(&mut self, removed_entity_id: EntityId) {
+    fn remove_owned(&mut self, owner: EffectOwner) {
         self.effects
             .iter_mut()
-            .filter(|(_, entity_id)| entity_id.is_some_and(|entity_id| entity_id == removed_entity_id))
+            .filter(|(_, effect_owner)| *effect_owner == Some(owner))
             .for_each(|(effect, _)| effect.mark_for_deletion());
     }
 
+    /// Add the effect of a skill unit. The map server sends units again when
+    /// they come back into view, in which case the old effect is replaced.
+    pub fn add_unit(&mut self, effect: Box<dyn EffectBase + Send + Sync>, entity_id: EntityId) {
+        self.add_owned(effect, EffectOwner::Unit(entity_id));
+    }
+
+    pub fn remove_unit(&mut self, entity_id: EntityId) {
+        self.remove_owned(EffectOwner::Unit(entity_id));
+    }
+
+    /// Attach the effect of a status, like a weapon endow, to an entity.
+    pub fn add_status_effect(&mut self, effect: Box<dyn EffectBase + Send + Sync>, entity_id: EntityId, status_index: u16) {
+        self.add_owned(effect, EffectOwner::Status(entity_id, status_index));
+    }
+
+    pub fn remove_status_effect(&mut self, entity_id: EntityId, status_index: u16) {
+        self.remove_owned(EffectOwner::Status(entity_id, status_index));
+    }
+
     pub fn clear(&mut self) {
         self.effects.clear();
     }
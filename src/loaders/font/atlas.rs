@@ -0,0 +1,63 @@
+use cgmath::Vector2;
+
+/// Packs glyphs into rows of the font atlas. Glyphs are never removed
+/// individually, instead the whole atlas is cleared once it is full.
+pub(super) struct AtlasAllocator {
+    size: u32,
+    cursor: Vector2<u32>,
+    row_height: u32,
+}
+
+impl AtlasAllocator {
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            cursor: Vector2::new(0, 0),
+            row_height: 0,
+        }
+    }
+
+    /// Find space for a rectangle of the given size and return the position
+    /// of its top left corner.
+    pub fn allocate(&mut self, size: Vector2<u32>) -> Option<Vector2<u32>> {
+        if self.cursor.x + size.x > self.size {
+            self.cursor = Vector2::new(0, self.cursor.y + self.row_height);
+            self.row_height = 0;
+        }
+
+        if size.x > self.size || self.cursor.y + size.y > self.size {
+            return None;
+        }
+
+        let position = self.cursor;
+        self.cursor.x += size.x;
+        self.row_height = self.row_height.max(size.y);
+
+        Some(position)
+    }
+
+    pub fn clear(&mut self) {
+        self.cursor = Vector2::new(0, 0);
+        self.row_height = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+
+    use super::AtlasAllocator;
+
+    #[test]
+    fn wrap_rows() {
+        let mut allocator = AtlasAllocator::new(10);
+
+        assert_eq!(allocator.allocate(Vector2::new(6, 3)), Some(Vector2::new(0, 0)));
+        assert_eq!(allocator.allocate(Vector2::new(3, 5)), Some(Vector2::new(6, 0)));
+        assert_eq!(allocator.allocate(Vector2::new(4, 2)), Some(Vector2::new(0, 5)));
+        assert_eq!(allocator.allocate(Vector2::new(4, 6)), None);
+
+        allocator.clear();
+        assert_eq!(allocator.allocate(Vector2::new(4, 4)), Some(Vector2::new(0, 0)));
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use cgmath::Vector2;
+
+/// Upper bound for the number of cached layouts. Text that changes every
+/// frame, like timers, would otherwise grow the cache indefinitely, so it is
+/// simply cleared once it gets too big.
+const MAXIMUM_ENTRIES: usize = 4096;
+
+/// Remembers the dimensions of text that was already laid out, so static text
+/// is only laid out again if the content, font size or available width
+/// changes.
+#[derive(Default)]
+pub(super) struct TextLayoutCache {
+    entries: HashMap<(u32, u32), HashMap<String, Vector2<f32>>>,
+    entry_count: usize,
+}
+
+impl TextLayoutCache {
+    fn key(font_size: f32, available_width: f32) -> (u32, u32) {
+        (font_size.to_bits(), available_width.to_bits())
+    }
+
+    pub fn get(&self, text: &str, font_size: f32, available_width: f32) -> Option<Vector2<f32>> {
+        self.entries
+            .get(&Self::key(font_size, available_width))
+            .and_then(|texts| texts.get(text))
+            .copied()
+    }
+
+    pub fn insert(&mut self, text: &str, font_size: f32, available_width: f32, size: Vector2<f32>) {
+        if self.entry_count >= MAXIMUM_ENTRIES {
+            self.entries.clear();
+            self.entry_count = 0;
+        }
+
+        let previous = self
+            .entries
+            .entry(Self::key(font_size, available_width))
+            .or_default()
+            .insert(text.to_owned(), size);
+
+        if previous.is_none() {
+            self.entry_count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+
+    use super::{TextLayoutCache, MAXIMUM_ENTRIES};
+
+    #[test]
+    fn constraints_are_part_of_the_key() {
+        let mut cache = TextLayoutCache::default();
+        cache.insert("Inventory", 14.0, 200.0, Vector2::new(60.0, 14.0));
+
+        assert_eq!(cache.get("Inventory", 14.0, 200.0), Some(Vector2::new(60.0, 14.0)));
+        assert_eq!(cache.get("Inventory", 16.0, 200.0), None);
+        assert_eq!(cache.get("Inventory", 14.0, 100.0), None);
+        assert_eq!(cache.get("Equipment", 14.0, 200.0), None);
+    }
+
+    #[test]
+    fn bounded_size() {
+        let mut cache = TextLayoutCache::default();
+
+        for index in 0..=MAXIMUM_ENTRIES {
+            cache.insert(&index.to_string(), 14.0, 200.0, Vector2::new(0.0, 0.0));
+        }
+
+        assert_eq!(cache.get("0", 14.0, 200.0), None);
+        assert!(cache.get(&MAXIMUM_ENTRIES.to_string(), 14.0, 200.0).is_some());
+    }
+}
@@ -1,7 +1,13 @@
+mod atlas;
+mod cache;
+mod sdf;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
 use cgmath::{Array, Vector2};
-use rusttype::gpu_cache::Cache;
 use rusttype::*;
 use vulkano::buffer::{Buffer, BufferCreateInfo, BufferUsage};
 use vulkano::command_buffer::{
@@ -15,26 +21,76 @@ use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
 use vulkano::sync::future::FenceSignalFuture;
 use vulkano::sync::GpuFuture;
 
+use self::atlas::AtlasAllocator;
+use self::cache::TextLayoutCache;
+use self::sdf::{generate_distance_field, SDF_FONT_SIZE, SDF_SPREAD};
 use super::GameFileLoader;
+#[cfg(feature = "debug")]
+use crate::debug::*;
 use crate::graphics::{Color, CommandBuilder, MemoryAllocator};
 
+const PRIMARY_FONT_PATH: &str = "data\\WenQuanYiMicroHei.ttf";
+/// Additional fonts that are used for characters that are missing from the
+/// primary font, in alphabetical order.
+const FALLBACK_FONT_DIRECTORY: &str = "client/fonts/";
+
+// NOTE: CJK text uses a lot of different glyphs, so the atlas needs to be
+// fairly big to fit all of them.
+const FONT_ATLAS_SIZE: u32 = 2048;
+
+/// Location of a glyph in the font atlas. Offset and size are in pixels at
+/// [`SDF_FONT_SIZE`] and include the padding of the distance field.
+#[derive(Clone, Copy)]
+struct CachedGlyph {
+    texture_position: Vector2<f32>,
+    texture_size: Vector2<f32>,
+    offset: Vector2<f32>,
+    size: Vector2<f32>,
+}
+
+/// Glyphs are stored as signed distance fields, so text stays sharp at any
+/// font size and interface scaling without rasterizing every glyph for every
+/// size it is displayed at.
 pub struct FontLoader {
     memory_allocator: Arc<MemoryAllocator>,
     queue: Arc<Queue>,
     font_atlas: Arc<ImageView>,
-    cache: Box<Cache<'static>>,
+    atlas_allocator: AtlasAllocator,
+    /// Glyphs without an outline, like spaces, are cached as `None`.
+    glyph_cache: HashMap<(usize, GlyphId), Option<CachedGlyph>>,
+    text_layout_cache: TextLayoutCache,
     load_buffer: Option<CommandBuilder>,
-    font: Box<Font<'static>>,
+    fonts: Vec<Font<'static>>,
 }
 
 struct GlyphData {
     glyph: PositionedGlyph<'static>,
+    font_id: usize,
     color: Color,
 }
 
-fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str, default_color: Color) -> (Vec<GlyphData>, Vector2<f32>) {
-    let mut result = Vec::new();
-    let v_metrics = font.v_metrics(scale);
+/// Find the first font in the fallback chain that has a glyph for the
+/// character. Falls back to the primary font, so missing glyphs are still
+/// rendered as the replacement glyph of that font.
+fn select_font(fonts: &[Font<'static>], character: char) -> usize {
+    fonts
+        .iter()
+        .position(|font| font.glyph(character).id() != GlyphId(0))
+        .unwrap_or_default()
+}
+
+fn layout_paragraph<'a>(
+    fonts: &[Font<'static>],
+    scale: Scale,
+    width: f32,
+    text: &str,
+    default_color: Color,
+    frame_arena: &'a Bump,
+) -> (BumpVec<'a, GlyphData>, Vector2<f32>) {
+    let mut result = BumpVec::new_in(frame_arena);
+    // NOTE: The line height is always taken from the primary font, so lines don't
+    // jump around if they contain characters from a fallback font.
+    let v_metrics = fonts[0].v_metrics(scale);
     let advance_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
     let mut caret = point(0.0, v_metrics.ascent);
     let mut last_glyph_id = None;
@@ -78,12 +134,18 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
             }
         }
 
+        let font_id = select_font(fonts, character);
+        let font = &fonts[font_id];
         let base_glyph = font.glyph(character);
-        if let Some(id) = last_glyph_id.take() {
+
+        // NOTE: Kerning is only defined between glyphs of the same font.
+        if let Some((last_font_id, id)) = last_glyph_id.take()
+            && last_font_id == font_id
+        {
             caret.x += font.pair_kerning(scale, id, base_glyph.id());
         }
 
-        last_glyph_id = Some(base_glyph.id());
+        last_glyph_id = Some((font_id, base_glyph.id()));
         let mut glyph = base_glyph.scaled(scale).positioned(caret);
 
         if let Some(bb) = glyph.pixel_bounding_box() {
@@ -95,7 +157,7 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
         }
 
         caret.x += glyph.unpositioned().h_metrics().advance_width;
-        result.push(GlyphData { glyph, color });
+        result.push(GlyphData { glyph, font_id, color });
     }
 
     (result, Vector2::new(caret.x, caret.y))
@@ -103,9 +165,7 @@ fn layout_paragraph(font: &Font<'static>, scale: Scale, width: f32, text: &str,
 
 impl FontLoader {
     pub fn new(memory_allocator: Arc<MemoryAllocator>, queue: Arc<Queue>, game_file_loader: &mut GameFileLoader) -> Self {
-        let cache_size = Vector2::from_value(512);
-        let cache = Cache::builder().dimensions(cache_size.x, cache_size.y).build();
-
+        let cache_size = Vector2::from_value(FONT_ATLAS_SIZE);
         let font_atlas_image = Image::new(
             &*memory_allocator,
             ImageCreateInfo {
@@ -119,12 +179,14 @@ impl FontLoader {
         .unwrap();
         let font_atlas = ImageView::new_default(font_atlas_image.clone()).unwrap();
 
-        let font_path = "data\\WenQuanYiMicroHei.ttf";
-        let data = game_file_loader.get(font_path).unwrap();
+        let data = game_file_loader.get(PRIMARY_FONT_PATH).unwrap();
         let font = Font::try_from_vec(data).unwrap_or_else(|| {
-            panic!("error constructing a font from data at {font_path:?}");
+            panic!("error constructing a font from data at {PRIMARY_FONT_PATH:?}");
         });
 
+        let mut fonts = vec![font];
+        fonts.extend(Self::load_fallback_fonts());
+
         let mut builder = AutoCommandBufferBuilder::primary(
             &*memory_allocator,
             queue.queue_family_index(),
@@ -143,93 +205,216 @@ impl FontLoader {
             memory_allocator,
             queue,
             font_atlas,
-            cache: Box::new(cache),
+            atlas_allocator: AtlasAllocator::new(FONT_ATLAS_SIZE),
+            glyph_cache: HashMap::new(),
+            text_layout_cache: TextLayoutCache::default(),
             load_buffer: builder.into(),
-            font: Box::new(font),
+            fonts,
         }
     }
 
-    pub fn get_text_dimensions(&self, text: &str, font_size: f32, available_width: f32) -> Vector2<f32> {
+    fn load_fallback_fonts() -> Vec<Font<'static>> {
+        let Ok(entries) = std::fs::read_dir(FALLBACK_FONT_DIRECTORY) else {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf"))
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                #[cfg(feature = "debug")]
+                print_debug!("loading fallback font {}{}{}", MAGENTA, path.display(), NONE);
+
+                let font = std::fs::read(&path).ok().and_then(Font::try_from_vec);
+
+                #[cfg(feature = "debug")]
+                if font.is_none() {
+                    print_debug!(
+                        "[{RED}error{NONE}] failed to load fallback font {MAGENTA}{}{NONE}",
+                        path.display()
+                    );
+                }
+
+                font
+            })
+            .collect()
+    }
+
+    /// Layout the text without caching the glyphs. All temporary allocations
+    /// are made in the frame arena. The result is cached, so this is cheap to
+    /// call for text that doesn't change.
+    pub fn get_text_dimensions(&mut self, text: &str, font_size: f32, available_width: f32, frame_arena: &Bump) -> Vector2<f32> {
+        if let Some(size) = self.text_layout_cache.get(text, font_size, available_width) {
+            return size;
+        }
+
         let (_, size) = layout_paragraph(
-            &self.font,
+            &self.fonts,
             Scale::uniform(font_size),
             available_width,
             text,
             Color::monochrome_u8(0),
+            frame_arena,
         );
 
+        self.text_layout_cache.insert(text, font_size, available_width, size);
         size
     }
 
-    pub fn get(
+    /// Layout the text and make sure all glyphs are in the font atlas. The
+    /// returned glyphs are only valid until the frame arena is reset.
+    pub fn get<'a>(
         &mut self,
         text: &str,
         default_color: Color,
         font_size: f32,
         available_width: f32,
-    ) -> (Vec<(Rect<f32>, Rect<i32>, Color)>, f32) {
-        let (glyphs, size) = layout_paragraph(&self.font, Scale::uniform(font_size), available_width, text, default_color);
+        frame_arena: &'a Bump,
+    ) -> (BumpVec<'a, (Rect<f32>, Rect<f32>, Color)>, f32) {
+        let (glyphs, size) = layout_paragraph(
+            &self.fonts,
+            Scale::uniform(font_size),
+            available_width,
+            text,
+            default_color,
+            frame_arena,
+        );
+
+        let scale = font_size / SDF_FONT_SIZE;
+        let mut character_layout = BumpVec::with_capacity_in(glyphs.len(), frame_arena);
 
         for glyph in &glyphs {
-            self.cache.queue_glyph(0, glyph.glyph.clone());
+            let Some(cached_glyph) = self.cache_glyph(glyph.font_id, glyph.glyph.id()) else {
+                continue;
+            };
+
+            let origin = glyph.glyph.position();
+            let position_min = point(
+                origin.x + cached_glyph.offset.x * scale,
+                origin.y + cached_glyph.offset.y * scale,
+            );
+            let position = Rect {
+                min: position_min,
+                max: point(
+                    position_min.x + cached_glyph.size.x * scale,
+                    position_min.y + cached_glyph.size.y * scale,
+                ),
+            };
+
+            let texture_position = point(cached_glyph.texture_position.x, cached_glyph.texture_position.y);
+            let texture_coordinates = Rect {
+                min: texture_position,
+                max: point(
+                    texture_position.x + cached_glyph.texture_size.x,
+                    texture_position.y + cached_glyph.texture_size.y,
+                ),
+            };
+
+            character_layout.push((texture_coordinates, position, glyph.color));
         }
 
-        self.cache
-            .cache_queued(|rect, data| {
-                let builder = self.load_buffer.get_or_insert_with(|| {
-                    AutoCommandBufferBuilder::primary(
-                        &*self.memory_allocator,
-                        self.queue.queue_family_index(),
-                        CommandBufferUsage::OneTimeSubmit,
-                    )
-                    .unwrap()
-                });
-
-                let pixels = data.iter().map(|&value| value as i8);
-                let buffer = Buffer::from_iter(
-                    &*self.memory_allocator,
-                    BufferCreateInfo {
-                        usage: BufferUsage::TRANSFER_SRC,
-                        ..Default::default()
-                    },
-                    AllocationCreateInfo {
-                        memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-                        ..Default::default()
-                    },
-                    pixels,
-                )
-                .unwrap();
-
-                let image = self.font_atlas.image().clone();
-
-                let region = BufferImageCopy {
-                    image_subresource: image.subresource_layers(),
-                    image_extent: [rect.width(), rect.height(), 1],
-                    image_offset: [rect.min.x, rect.min.y, 0],
-                    ..Default::default()
-                };
+        (character_layout, size.y)
+    }
 
-                builder
-                    .copy_buffer_to_image(CopyBufferToImageInfo {
-                        regions: [region].into(),
-                        ..CopyBufferToImageInfo::buffer_image(buffer, image)
-                    })
-                    .unwrap();
-            })
-            .unwrap();
+    fn cache_glyph(&mut self, font_id: usize, glyph_id: GlyphId) -> Option<CachedGlyph> {
+        if let Some(cached_glyph) = self.glyph_cache.get(&(font_id, glyph_id)) {
+            return *cached_glyph;
+        }
+
+        let glyph = self.fonts[font_id]
+            .glyph(glyph_id)
+            .scaled(Scale::uniform(SDF_FONT_SIZE))
+            .positioned(point(0.0, 0.0));
+
+        let Some(bounding_box) = glyph.pixel_bounding_box() else {
+            self.glyph_cache.insert((font_id, glyph_id), None);
+            return None;
+        };
+
+        let width = bounding_box.width() as usize;
+        let height = bounding_box.height() as usize;
+        let mut coverage = vec![0; width * height];
+        glyph.draw(|x, y, value| coverage[y as usize * width + x as usize] = (value * 255.0) as u8);
+
+        let distance_field = generate_distance_field(&coverage, width, height);
+        let padded_size = Vector2::new(width + SDF_SPREAD * 2, height + SDF_SPREAD * 2).map(|value| value as u32);
+
+        let atlas_position = match self.atlas_allocator.allocate(padded_size) {
+            Some(atlas_position) => atlas_position,
+            None => {
+                // NOTE: Glyphs that were already queued for rendering this frame might
+                // be overwritten, but that is only visible for a single frame.
+                #[cfg(feature = "debug")]
+                print_debug!("[{YELLOW}warning{NONE}] font atlas is full, clearing all glyphs");
+
+                self.glyph_cache.clear();
+                self.atlas_allocator.clear();
+                self.atlas_allocator.allocate(padded_size)?
+            }
+        };
+
+        self.upload_glyph(atlas_position, padded_size, distance_field);
+
+        let spread = SDF_SPREAD as f32;
+        let cached_glyph = CachedGlyph {
+            texture_position: atlas_position.map(|value| value as f32 / FONT_ATLAS_SIZE as f32),
+            texture_size: padded_size.map(|value| value as f32 / FONT_ATLAS_SIZE as f32),
+            offset: Vector2::new(bounding_box.min.x as f32 - spread, bounding_box.min.y as f32 - spread),
+            size: padded_size.map(|value| value as f32),
+        };
+
+        self.glyph_cache.insert((font_id, glyph_id), Some(cached_glyph));
+        Some(cached_glyph)
+    }
 
-        (
-            glyphs
-                .into_iter()
-                .filter_map(|glyph| {
-                    self.cache
-                        .rect_for(0, &glyph.glyph)
-                        .unwrap()
-                        .map(|tuple| (tuple.0, tuple.1, glyph.color))
-                })
-                .collect(),
-            size.y,
+    fn upload_glyph(&mut self, position: Vector2<u32>, size: Vector2<u32>, distance_field: Vec<u8>) {
+        let builder = self.load_buffer.get_or_insert_with(|| {
+            AutoCommandBufferBuilder::primary(
+                &*self.memory_allocator,
+                self.queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+        });
+
+        let buffer = Buffer::from_iter(
+            &*self.memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            distance_field,
         )
+        .unwrap();
+
+        let image = self.font_atlas.image().clone();
+
+        let region = BufferImageCopy {
+            image_subresource: image.subresource_layers(),
+            image_extent: [size.x, size.y, 1],
+            image_offset: [position.x, position.y, 0],
+            ..Default::default()
+        };
+
+        builder
+            .copy_buffer_to_image(CopyBufferToImageInfo {
+                regions: [region].into(),
+                ..CopyBufferToImageInfo::buffer_image(buffer, image)
+            })
+            .unwrap();
     }
 
     pub fn submit_load_buffer(&mut self) -> Option<FenceSignalFuture<Box<dyn GpuFuture>>> {
@@ -0,0 +1,76 @@
+/// Size at which glyphs are rasterized before they are converted to distance
+/// fields. Text of every size is rendered from these glyphs, so this needs to
+/// be big enough to keep small details intact.
+pub(super) const SDF_FONT_SIZE: f32 = 32.0;
+/// Largest distance from the outline that can be represented, in pixels of
+/// the rasterized glyph. Every glyph is padded by this amount on each side.
+pub(super) const SDF_SPREAD: usize = 4;
+
+/// Convert the coverage of a rasterized glyph into a signed distance field
+/// that is padded by [`SDF_SPREAD`] pixels on every side. The outline of the
+/// glyph is at a value of 0.5, larger values are inside of the glyph.
+pub(super) fn generate_distance_field(coverage: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let spread = SDF_SPREAD as isize;
+    let padded_width = width as isize + spread * 2;
+    let padded_height = height as isize + spread * 2;
+    let maximum_distance = SDF_SPREAD as f32 + 0.5;
+
+    let is_inside = |x: isize, y: isize| {
+        let (x, y) = (x - spread, y - spread);
+        x >= 0 && y >= 0 && x < width as isize && y < height as isize && coverage[y as usize * width + x as usize] >= 128
+    };
+
+    let mut field = Vec::with_capacity((padded_width * padded_height) as usize);
+
+    for y in 0..padded_height {
+        for x in 0..padded_width {
+            let inside = is_inside(x, y);
+            let mut closest = maximum_distance * maximum_distance;
+
+            for offset_y in -spread..=spread {
+                for offset_x in -spread..=spread {
+                    if is_inside(x + offset_x, y + offset_y) != inside {
+                        closest = closest.min((offset_x * offset_x + offset_y * offset_y) as f32);
+                    }
+                }
+            }
+
+            // NOTE: The outline lies halfway between two pixels of different sides, so
+            // the distance is measured to there instead of to the pixel center.
+            let distance = closest.sqrt() - 0.5;
+            let signed_distance = match inside {
+                true => distance,
+                false => -distance,
+            };
+
+            let value = 0.5 + signed_distance / (SDF_SPREAD as f32 * 2.0);
+            field.push((value.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+
+    field
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate_distance_field, SDF_SPREAD};
+
+    #[test]
+    fn padding() {
+        let field = generate_distance_field(&[255; 6], 3, 2);
+        assert_eq!(field.len(), (3 + SDF_SPREAD * 2) * (2 + SDF_SPREAD * 2));
+        assert_eq!(field[0], 0);
+    }
+
+    #[test]
+    fn outline() {
+        let field = generate_distance_field(&[255, 0], 2, 1);
+        let row = &field[SDF_SPREAD * (2 + SDF_SPREAD * 2)..];
+        let inside = row[SDF_SPREAD];
+        let outside = row[SDF_SPREAD + 1];
+
+        assert!(inside > 128);
+        assert!(outside < 128);
+        assert_eq!(inside as i32 - 128, 127 - outside as i32);
+    }
+}
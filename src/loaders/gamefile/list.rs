@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use procedural::PrototypeElement;
 use serde::{Deserialize, Serialize};
 
@@ -5,8 +7,12 @@ use serde::{Deserialize, Serialize};
 use crate::debug::*;
 
 const FILENAME: &str = "client/game_archives.ron";
-const DEFAULT_FILES: &[&str] = &["data.grf", "rdata.grf", "korangar/"];
+const DATA_INI_FILENAME: &str = "data.ini";
+const DATA_FOLDER: &str = "data/";
+const KORANGAR_FOLDER: &str = "korangar/";
+const DEFAULT_FILES: &[&str] = &["data.grf", "rdata.grf", KORANGAR_FOLDER];
 
+/// List of archives to load, ordered from lowest to highest priority.
 #[derive(Serialize, Deserialize, PrototypeElement)]
 pub(super) struct GameArchiveList {
     pub archives: Vec<String>,
@@ -21,6 +27,90 @@ impl Default for GameArchiveList {
 }
 
 impl GameArchiveList {
+    /// Parse a `data.ini` as shipped by servers. Archives in the `[Data]`
+    /// section with a lower number override the ones with a higher number and
+    /// setting `ReadFolder=1` makes the data folder override all of them.
+    fn from_data_ini(data: &str) -> Option<Self> {
+        let mut in_data_section = false;
+        let mut read_folder = false;
+        let mut entries = Vec::new();
+
+        for line in data.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                in_data_section = section.trim().eq_ignore_ascii_case("data");
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let (key, value) = (key.trim(), value.trim());
+
+            if !in_data_section || value.is_empty() {
+                continue;
+            }
+
+            if key.eq_ignore_ascii_case("readfolder") {
+                read_folder = value == "1";
+            } else if let Ok(priority) = key.parse::<u32>() {
+                entries.push((priority, value.to_string()));
+            }
+        }
+
+        if entries.is_empty() {
+            return None;
+        }
+
+        entries.sort_by(|(first, _), (second, _)| second.cmp(first));
+
+        let mut archives: Vec<String> = entries.into_iter().map(|(_, path)| path).collect();
+
+        if read_folder {
+            archives.push(DATA_FOLDER.to_string());
+        }
+
+        // NOTE: The Korangar folder contains assets that the client relies on, so it
+        // always takes precedence.
+        archives.push(KORANGAR_FOLDER.to_string());
+
+        Some(Self { archives })
+    }
+
+    fn load_data_ini() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading game archive list from {}{DATA_INI_FILENAME}{}", MAGENTA, NONE);
+
+        let mut game_archive_list = std::fs::read_to_string(DATA_INI_FILENAME)
+            .ok()
+            .and_then(|data| Self::from_data_ini(&data))?;
+
+        game_archive_list.archives.retain(|path| {
+            let exists = Path::new(path).exists();
+
+            #[cfg(feature = "debug")]
+            if !exists {
+                print_debug!(
+                    "[{}warning{}] archive {}{path}{} from {}{DATA_INI_FILENAME}{} does not exist; skipping",
+                    YELLOW,
+                    NONE,
+                    MAGENTA,
+                    NONE,
+                    MAGENTA,
+                    NONE
+                );
+            }
+
+            exists
+        });
+
+        Some(game_archive_list)
+    }
+
     pub(super) fn load() -> Self {
         #[cfg(feature = "debug")]
         print_debug!("loading game archive list from {}{FILENAME}{}", MAGENTA, NONE);
@@ -29,13 +119,16 @@ impl GameArchiveList {
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
             .map(|archives| Self { archives })
+            .or_else(Self::load_data_ini)
             .unwrap_or_else(|| {
                 #[cfg(feature = "debug")]
                 print_debug!(
-                    "[{}error{}] failed to load game archive list from {}{FILENAME}{}; trying with default",
+                    "[{}error{}] failed to load game archive list from {}{FILENAME}{} or {}{DATA_INI_FILENAME}{}; trying with default",
                     RED,
                     NONE,
                     MAGENTA,
+                    NONE,
+                    MAGENTA,
                     NONE
                 );
 
@@ -43,3 +136,37 @@ impl GameArchiveList {
             })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::GameArchiveList;
+
+    #[test]
+    fn data_ini_priority() {
+        let data = "[Data]\n0=custom.grf\n2=data.grf\n1=rdata.grf\n";
+        let game_archive_list = GameArchiveList::from_data_ini(data).unwrap();
+
+        assert_eq!(game_archive_list.archives, ["data.grf", "rdata.grf", "custom.grf", "korangar/"]);
+    }
+
+    #[test]
+    fn data_ini_read_folder() {
+        let data = "; server files\n[Data]\nReadFolder=1\n0=data.grf\n";
+        let game_archive_list = GameArchiveList::from_data_ini(data).unwrap();
+
+        assert_eq!(game_archive_list.archives, ["data.grf", "data/", "korangar/"]);
+    }
+
+    #[test]
+    fn data_ini_ignores_other_sections() {
+        let data = "[Patch]\n0=patch.grf\n[Data]\n0=data.grf\n";
+        let game_archive_list = GameArchiveList::from_data_ini(data).unwrap();
+
+        assert_eq!(game_archive_list.archives, ["data.grf", "korangar/"]);
+    }
+
+    #[test]
+    fn data_ini_without_archives() {
+        assert!(GameArchiveList::from_data_ini("[Data]\nReadFolder=1\n").is_none());
+    }
+}
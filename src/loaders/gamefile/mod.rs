@@ -2,12 +2,15 @@
 //! methods to retrieve each of them individually. The archives implement the
 //! [`Archive`] trait.
 mod list;
+mod patch;
 
 use core::panic;
+use std::collections::HashSet;
 use std::path::Path;
 use std::u8;
 
 use self::list::GameArchiveList;
+use self::patch::{PatchState, PATCH_FOLDER};
 use super::archive::folder::FolderArchive;
 use super::archive::native::{NativeArchive, NativeArchiveBuilder};
 use super::archive::{Archive, ArchiveType, Writable};
@@ -35,6 +38,9 @@ pub const FALLBACK_ACTIONS_FILE: &str = "data\\sprite\\npc\\missing.act";
 #[derive(Default)]
 pub struct GameFileLoader {
     archives: Vec<Box<dyn Archive>>,
+    /// Files that were removed by a patch and should not be loaded from any
+    /// of the archives.
+    removed_files: HashSet<String>,
 }
 
 impl GameFileLoader {
@@ -78,6 +84,29 @@ impl GameFileLoader {
         timer.stop();
     }
 
+    /// Download and apply new patches from the patch server and load the
+    /// patched files on top of all other archives.
+    pub fn apply_patches(&mut self) {
+        let mut patch_state = PatchState::load();
+
+        // NOTE: The Lua files are converted only once, so if a patch changed any of
+        // them, we need to convert them again.
+        if patch::apply_patches(&mut patch_state) && Path::new(LUA_GRF_FILE_NAME).exists() {
+            let path = Path::new(LUA_GRF_FILE_NAME);
+            let _ = match path.is_dir() {
+                true => std::fs::remove_dir_all(path),
+                false => std::fs::remove_file(path),
+            };
+        }
+
+        if Path::new(PATCH_FOLDER).exists() {
+            let patch_archive = Self::load_archive_from_path(PATCH_FOLDER);
+            self.add_archive(patch_archive);
+        }
+
+        self.removed_files = patch_state.removed_files.into_iter().collect();
+    }
+
     pub fn load_patched_lua_files(&mut self) {
         if !Path::new(LUA_GRF_FILE_NAME).exists() {
             self.patch_lua_files();
@@ -166,11 +195,14 @@ impl GameFileLoader {
 
     pub fn get(&mut self, path: &str) -> Result<Vec<u8>, String> {
         let lowercase_path = path.to_lowercase();
-        let result = self
-            .archives
-            .iter_mut()
-            .find_map(|archive| archive.get_file_by_path(&lowercase_path))
-            .ok_or(format!("failed to find file {path}"));
+        let result = match self.removed_files.contains(&lowercase_path) {
+            true => None,
+            false => self
+                .archives
+                .iter_mut()
+                .find_map(|archive| archive.get_file_by_path(&lowercase_path)),
+        }
+        .ok_or(format!("failed to find file {path}"));
 
         // TODO: should this be removed in the future or left in for resilience?
         if result.is_err() {
@@ -0,0 +1,311 @@
+//! Downloads and applies Thor and GPF patches from a patch server, so the
+//! client can be kept up to date without running an external patcher first.
+//! Patched files are written to a folder that is loaded on top of all other
+//! game archives.
+mod thor;
+
+use std::io::Read;
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use self::thor::parse_thor_archive;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::loaders::archive::folder::FolderArchive;
+use crate::loaders::archive::native::NativeArchive;
+use crate::loaders::archive::{Archive, Writable};
+
+const SETTINGS_FILENAME: &str = "client/patch_settings.ron";
+const STATE_FILENAME: &str = "client/patch_state.ron";
+const TEMPORARY_GPF_FILENAME: &str = "korangar_patch.gpf";
+pub(super) const PATCH_FOLDER: &str = "patch/";
+
+pub(super) enum PatchEntry {
+    Add { path: String, data: Vec<u8> },
+    Remove { path: String },
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct PatchSettings {
+    /// URL of the patch list. Patches are downloaded from the same location.
+    patch_list_url: Option<String>,
+}
+
+impl PatchSettings {
+    fn load() -> Self {
+        std::fs::read_to_string(SETTINGS_FILENAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Keeps track of which patches have already been applied and which files
+/// were removed by them.
+#[derive(Default, Serialize, Deserialize)]
+pub(super) struct PatchState {
+    last_patch_index: usize,
+    pub removed_files: Vec<String>,
+}
+
+impl PatchState {
+    pub(super) fn load() -> Self {
+        #[cfg(feature = "debug")]
+        print_debug!("loading patch state from {}{STATE_FILENAME}{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(STATE_FILENAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving patch state to {}{STATE_FILENAME}{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, Default::default()).unwrap();
+        std::fs::write(STATE_FILENAME, data).expect("unable to write file");
+    }
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url).call().map_err(|error| error.to_string())?;
+    let mut data = Vec::new();
+
+    response.into_reader().read_to_end(&mut data).map_err(|error| error.to_string())?;
+
+    Ok(data)
+}
+
+/// Parse a patch list where every line has the format `<index> <file name>`.
+/// Lines starting with `//` are comments.
+fn parse_patch_list(patch_list: &str) -> Vec<(usize, String)> {
+    patch_list
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .filter_map(|line| {
+            let (index, file_name) = line.split_once(char::is_whitespace)?;
+            Some((index.parse().ok()?, file_name.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_gpf_archive(data: &[u8]) -> Result<Vec<PatchEntry>, String> {
+    // NOTE: GPF patches are regular GRF files, but the native archive can only be
+    // read from disk.
+    let path = std::env::temp_dir().join(TEMPORARY_GPF_FILENAME);
+    std::fs::write(&path, data).map_err(|error| error.to_string())?;
+
+    let entries = NativeArchive::try_from_path(&path).and_then(|mut archive| {
+        archive
+            .get_file_paths()
+            .into_iter()
+            .filter_map(|path| {
+                archive
+                    .try_get_file_by_path(&path)
+                    .transpose()
+                    .map(|data| data.map(|data| PatchEntry::Add { path, data }))
+            })
+            .collect()
+    });
+
+    let _ = std::fs::remove_file(&path);
+
+    entries
+}
+
+/// Patches come from the network, so their paths may not leave the patch
+/// folder.
+fn is_safe_path(path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let path = Path::new(&path);
+
+    !path.has_root()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(..) | Component::CurDir))
+}
+
+/// Returns `true` if any Lua file was changed.
+fn apply_entries(entries: Vec<PatchEntry>, patch_archive: &mut FolderArchive, patch_state: &mut PatchState) -> bool {
+    let mut lua_files_changed = false;
+
+    for entry in entries {
+        let (PatchEntry::Add { path, .. } | PatchEntry::Remove { path }) = &entry;
+
+        if !is_safe_path(path) {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "[{}error{}] skipping patch entry with unsafe path {}{path}{}",
+                RED,
+                NONE,
+                MAGENTA,
+                NONE
+            );
+
+            continue;
+        }
+
+        match entry {
+            PatchEntry::Add { path, data } => {
+                lua_files_changed |= path.ends_with(".lub");
+                patch_state.removed_files.retain(|removed_file| removed_file != &path);
+                patch_archive.add_file(&path, data);
+            }
+            PatchEntry::Remove { path } => {
+                patch_archive.remove_file(&path);
+
+                if !patch_state.removed_files.contains(&path) {
+                    patch_state.removed_files.push(path);
+                }
+            }
+        }
+    }
+
+    lua_files_changed
+}
+
+/// Download and apply all patches that haven't been applied yet. Returns
+/// `true` if any Lua file was changed.
+pub(super) fn apply_patches(patch_state: &mut PatchState) -> bool {
+    let Some(patch_list_url) = PatchSettings::load().patch_list_url else {
+        return false;
+    };
+
+    #[cfg(feature = "debug")]
+    let timer = Timer::new("apply patches");
+
+    let patch_list = match download(&patch_list_url) {
+        Ok(patch_list) => String::from_utf8_lossy(&patch_list).into_owned(),
+        Err(_error) => {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "[{}error{}] failed to download patch list from {}{patch_list_url}{}: {_error}",
+                RED,
+                NONE,
+                MAGENTA,
+                NONE
+            );
+
+            return false;
+        }
+    };
+
+    let base_url = patch_list_url
+        .rsplit_once('/')
+        .map(|(base_url, _)| base_url)
+        .unwrap_or(&patch_list_url);
+
+    let mut patch_archive = FolderArchive::from_path(Path::new(PATCH_FOLDER));
+    let mut lua_files_changed = false;
+
+    for (index, file_name) in parse_patch_list(&patch_list) {
+        if index <= patch_state.last_patch_index {
+            continue;
+        }
+
+        let entries = download(&format!("{base_url}/{file_name}")).and_then(|data| match file_name.to_lowercase().ends_with(".gpf") {
+            true => parse_gpf_archive(&data),
+            false => parse_thor_archive(&data).map_err(|error| format!("{error:?}")),
+        });
+
+        match entries {
+            Ok(entries) => {
+                #[cfg(feature = "debug")]
+                print_debug!("applying patch {}{file_name}{} with {} entries", MAGENTA, NONE, entries.len());
+
+                lua_files_changed |= apply_entries(entries, &mut patch_archive, patch_state);
+                patch_state.last_patch_index = index;
+                patch_state.save();
+            }
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!(
+                    "[{}error{}] failed to apply patch {}{file_name}{}: {_error}",
+                    RED,
+                    NONE,
+                    MAGENTA,
+                    NONE
+                );
+
+                // NOTE: Patches have to be applied in order, so we can't skip one.
+                break;
+            }
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    timer.stop();
+
+    lua_files_changed
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_entries, is_safe_path, parse_patch_list, PatchEntry, PatchState};
+    use crate::loaders::archive::folder::FolderArchive;
+    use crate::loaders::archive::Archive;
+
+    #[test]
+    fn patch_list() {
+        let patch_list = "// patches\n1 first.thor\n\n2  second.gpf\ninvalid\n";
+
+        assert_eq!(parse_patch_list(patch_list), [
+            (1, "first.thor".to_string()),
+            (2, "second.gpf".to_string())
+        ]);
+    }
+
+    #[test]
+    fn unsafe_paths() {
+        assert!(is_safe_path("data\\sprite\\poring.spr"));
+        assert!(is_safe_path("./data/texture.bmp"));
+        assert!(!is_safe_path("..\\..\\korangar.exe"));
+        assert!(!is_safe_path("data\\..\\..\\secret.txt"));
+        assert!(!is_safe_path("/etc/passwd"));
+        assert!(!is_safe_path("\\windows\\system32\\file.dll"));
+    }
+
+    #[test]
+    fn apply_patch_entries() {
+        let folder = std::env::temp_dir().join("korangar_patch_test");
+        let patch_folder = folder.join("patch");
+        let _ = std::fs::remove_dir_all(&folder);
+        std::fs::create_dir_all(patch_folder.join("data")).unwrap();
+        std::fs::write(folder.join("outside.txt"), b"keep").unwrap();
+        std::fs::write(patch_folder.join("data").join("old.txt"), b"old").unwrap();
+
+        let mut patch_archive = FolderArchive::from_path(&patch_folder);
+        let mut patch_state = PatchState::default();
+
+        let entries = vec![
+            PatchEntry::Add {
+                path: "data\\new.lub".to_owned(),
+                data: b"new".to_vec(),
+            },
+            PatchEntry::Add {
+                path: "..\\escaped.txt".to_owned(),
+                data: b"escaped".to_vec(),
+            },
+            PatchEntry::Remove {
+                path: "data\\old.txt".to_owned(),
+            },
+            PatchEntry::Remove {
+                path: "..\\outside.txt".to_owned(),
+            },
+        ];
+
+        let lua_files_changed = apply_entries(entries, &mut patch_archive, &mut patch_state);
+
+        assert!(lua_files_changed);
+        assert_eq!(std::fs::read(patch_folder.join("data").join("new.lub")).unwrap(), b"new");
+        assert!(!patch_folder.join("data").join("old.txt").exists());
+        assert!(!folder.join("escaped.txt").exists());
+        assert!(folder.join("outside.txt").exists());
+        assert_eq!(patch_state.removed_files, ["data\\old.txt".to_owned()]);
+
+        let _ = std::fs::remove_dir_all(&folder);
+    }
+}
@@ -0,0 +1,101 @@
+//! Parser for Thor patch archives as created by the Thor patcher.
+use ragnarok_bytes::{ByteStream, ConversionError, ConversionResult, FromBytes};
+use yazi::{decompress, Format};
+
+use super::PatchEntry;
+
+const MAGIC_BYTES: &[u8] = b"ASSF (C) 2007 Aeomin DEV";
+const SINGLE_FILE_MODE: i16 = 0x21;
+const MULTIPLE_FILES_MODE: i16 = 0x30;
+const REMOVE_FLAG: u8 = 0x01;
+/// Used by the Thor patcher to verify the client files. It is not an asset,
+/// so we don't merge it.
+const INTEGRITY_FILE_NAME: &str = "data.integrity";
+
+/// Marker type to give conversion errors some context.
+struct ThorArchive;
+
+fn read_path(byte_stream: &mut ByteStream) -> ConversionResult<String> {
+    let length = u8::from_bytes(byte_stream)? as usize;
+    let bytes = byte_stream.slice::<ThorArchive>(length)?;
+
+    // NOTE: Paths are not UTF-8, so we read them byte by byte like the native
+    // archive does.
+    Ok(bytes.iter().map(|byte| *byte as char).collect::<String>().to_lowercase())
+}
+
+fn read_data(data: &[u8], offset: usize, compressed_size: usize) -> ConversionResult<Vec<u8>> {
+    let mut byte_stream = ByteStream::<()>::without_metadata(data);
+    byte_stream.set_offset(offset);
+
+    let compressed = byte_stream.slice::<ThorArchive>(compressed_size)?;
+    let (decompressed, _checksum) =
+        decompress(compressed, Format::Zlib).map_err(|error| ConversionError::from_message(format!("{error:?}")))?;
+
+    Ok(decompressed)
+}
+
+/// Read all entries of a Thor archive. Entries that are flagged for removal
+/// are returned as [`PatchEntry::Remove`].
+pub(super) fn parse_thor_archive(data: &[u8]) -> ConversionResult<Vec<PatchEntry>> {
+    let mut byte_stream = ByteStream::<()>::without_metadata(data);
+
+    if byte_stream.slice::<ThorArchive>(MAGIC_BYTES.len())? != MAGIC_BYTES {
+        return Err(ConversionError::from_message("invalid thor magic bytes"));
+    }
+
+    let _use_grf_merging = u8::from_bytes(&mut byte_stream)?;
+    // NOTE: The file count comes from the archive and can't be trusted, so it is
+    // not used to pre-allocate the entries.
+    let _file_count = u32::from_bytes(&mut byte_stream)?;
+    let mode = i16::from_bytes(&mut byte_stream)?;
+    let target_grf_name_length = u8::from_bytes(&mut byte_stream)? as usize;
+    let _target_grf_name = byte_stream.slice::<ThorArchive>(target_grf_name_length)?;
+
+    let mut entries = Vec::new();
+
+    match mode {
+        SINGLE_FILE_MODE => {
+            // NOTE: Single file archives have one unused byte before the entry and the
+            // data follows directly after it.
+            let _unused = u8::from_bytes(&mut byte_stream)?;
+            let compressed_size = i32::from_bytes(&mut byte_stream)? as usize;
+            let _uncompressed_size = i32::from_bytes(&mut byte_stream)?;
+            let path = read_path(&mut byte_stream)?;
+            let data = read_data(data, byte_stream.get_offset(), compressed_size)?;
+
+            entries.push(PatchEntry::Add { path, data });
+        }
+        MULTIPLE_FILES_MODE => {
+            let file_table_compressed_size = i32::from_bytes(&mut byte_stream)? as usize;
+            let file_table_offset = i32::from_bytes(&mut byte_stream)? as usize;
+            let file_table = read_data(data, file_table_offset, file_table_compressed_size)?;
+
+            let mut file_table_stream = ByteStream::<()>::without_metadata(&file_table);
+
+            while !file_table_stream.is_empty() {
+                let path = read_path(&mut file_table_stream)?;
+                let flags = u8::from_bytes(&mut file_table_stream)?;
+
+                if flags & REMOVE_FLAG != 0 {
+                    entries.push(PatchEntry::Remove { path });
+                    continue;
+                }
+
+                let offset = u32::from_bytes(&mut file_table_stream)? as usize;
+                let compressed_size = i32::from_bytes(&mut file_table_stream)? as usize;
+                let _uncompressed_size = i32::from_bytes(&mut file_table_stream)?;
+
+                if path == INTEGRITY_FILE_NAME {
+                    continue;
+                }
+
+                let data = read_data(data, offset, compressed_size)?;
+                entries.push(PatchEntry::Add { path, data });
+            }
+        }
+        mode => return Err(ConversionError::from_message(format!("unsupported thor mode {mode:#x}"))),
+    }
+
+    Ok(entries)
+}
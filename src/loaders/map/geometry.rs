@@ -0,0 +1,107 @@
+use super::data::{GatData, GroundData};
+use super::vertices::{generate_tile_vertices, ground_water_vertices};
+use crate::graphics::{ModelVertex, NativeModelVertex, TileVertex, WaterVertex};
+use crate::loaders::cache::{load_cached, store_cached, CacheReader, CacheWriter};
+use crate::world::Tile;
+
+const CACHE_KIND: &str = "map";
+
+/// Everything a map needs from its ground and altitude files. Generating the
+/// vertices is the most expensive part of loading a map, so the result is
+/// stored in the asset cache.
+pub struct MapGeometry {
+    pub ground_width: i32,
+    pub ground_height: i32,
+    pub texture_names: Vec<String>,
+    pub map_width: i32,
+    pub map_height: i32,
+    pub tiles: Vec<Tile>,
+    pub ground_vertices: Vec<ModelVertex>,
+    pub water_vertices: Vec<WaterVertex>,
+    pub tile_vertices: Vec<ModelVertex>,
+    pub tile_picker_vertices: Vec<TileVertex>,
+}
+
+impl MapGeometry {
+    pub fn generate(ground_data: GroundData, mut gat_data: GatData, water_level: f32) -> Self {
+        let (tile_vertices, tile_picker_vertices) = generate_tile_vertices(&mut gat_data);
+        let (ground_vertices, water_vertices) = ground_water_vertices(&ground_data, water_level);
+
+        Self {
+            ground_width: ground_data.width,
+            ground_height: ground_data.height,
+            texture_names: ground_data.textures,
+            map_width: gat_data.map_width,
+            map_height: gat_data.map_height,
+            tiles: gat_data.tiles,
+            ground_vertices: NativeModelVertex::to_vertices(ground_vertices),
+            water_vertices,
+            tile_vertices,
+            tile_picker_vertices,
+        }
+    }
+
+    pub fn load_cached(key: u64) -> Option<Self> {
+        load_cached(CACHE_KIND, key).and_then(Self::read)
+    }
+
+    fn read(mut reader: CacheReader) -> Option<Self> {
+        let ground_width = reader.read_pod()?;
+        let ground_height = reader.read_pod()?;
+        let texture_names = (0..reader.read_u32()?).map(|_| reader.read_string()).collect::<Option<_>>()?;
+        let map_width = reader.read_pod()?;
+        let map_height = reader.read_pod()?;
+        let tile_heights = reader.read_vec::<[f32; 4]>()?;
+        let tile_types = reader.read_vec::<u8>()?;
+        let tiles = tile_heights
+            .into_iter()
+            .zip(tile_types)
+            .map(|(heights, type_flags)| Tile::new(heights, type_flags))
+            .collect();
+
+        Some(Self {
+            ground_width,
+            ground_height,
+            texture_names,
+            map_width,
+            map_height,
+            tiles,
+            ground_vertices: reader.read_vec()?,
+            water_vertices: reader.read_vec()?,
+            tile_vertices: reader.read_vec()?,
+            tile_picker_vertices: reader.read_vec()?,
+        })
+    }
+
+    pub fn store_cached(&self, key: u64) {
+        let tile_heights: Vec<[f32; 4]> = self
+            .tiles
+            .iter()
+            .map(|tile| {
+                [
+                    tile.upper_left_height,
+                    tile.upper_right_height,
+                    tile.lower_left_height,
+                    tile.lower_right_height,
+                ]
+            })
+            .collect();
+        let tile_types: Vec<u8> = self.tiles.iter().map(|tile| tile.tile_type.0).collect();
+
+        let mut writer = CacheWriter::new();
+        writer.write_pod(&self.ground_width);
+        writer.write_pod(&self.ground_height);
+        writer.write_u32(self.texture_names.len() as u32);
+        self.texture_names.iter().for_each(|texture_name| writer.write_string(texture_name));
+        writer.write_pod(&self.map_width);
+        writer.write_pod(&self.map_height);
+        writer.write_slice(&tile_heights);
+        writer.write_slice(&tile_types);
+        writer.write_slice(&self.ground_vertices);
+        writer.write_slice(&self.water_vertices);
+        writer.write_slice(&self.tile_vertices);
+        writer.write_slice(&self.tile_picker_vertices);
+
+        store_cached(CACHE_KIND, key, writer);
+    }
+}
@@ -1,9 +1,13 @@
 mod data;
+mod geometry;
+mod overrides;
+mod preload;
 mod resource;
 mod vertices;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::thread::JoinHandle;
 
 use cgmath::Vector3;
 use derive_new::new;
@@ -12,16 +16,26 @@ use ragnarok_bytes::{ByteStream, FromBytes};
 #[cfg(feature = "debug")]
 pub use self::data::MapData;
 use self::data::*;
+use self::geometry::MapGeometry;
+pub use self::overrides::FogSettings;
+use self::overrides::MapOverrides;
+pub use self::preload::MapPreloader;
 pub use self::resource::{LightSettings, WaterSettings};
-use self::vertices::{generate_tile_vertices, ground_water_vertices, load_textures};
+use self::vertices::load_textures;
 use super::version::InternalVersion;
 #[cfg(feature = "debug")]
 use crate::debug::*;
-use crate::graphics::{BufferAllocator, NativeModelVertex};
+use crate::graphics::BufferAllocator;
+#[cfg(feature = "debug")]
+use crate::graphics::{mark_map_evicted, ResidencyScope};
+use crate::loaders::cache::cache_key;
 use crate::loaders::{GameFileLoader, ModelLoader, TextureLoader};
 use crate::world::*;
 
 const MAP_OFFSET: f32 = 5.0;
+/// Maximum number of maps that are kept in memory. Maps hold their vertex
+/// buffers, so keeping every visited map around would grow without bound.
+const MAXIMUM_CACHED_MAPS: usize = 4;
 
 #[cfg(feature = "debug")]
 fn assert_byte_stream_empty<META>(mut byte_stream: ByteStream<META>, file_name: &str) {
@@ -42,6 +56,12 @@ fn assert_byte_stream_empty<META>(mut byte_stream: ByteStream<META>, file_name:
 pub struct MapLoader {
     #[new(default)]
     cache: HashMap<String, Arc<Map>>,
+    /// Names of the cached maps, from least to most recently used.
+    #[new(default)]
+    recently_used: VecDeque<String>,
+    /// Maps whose geometry is being generated in the background.
+    #[new(default)]
+    preloading: HashMap<String, JoinHandle<()>>,
 }
 
 impl MapLoader {
@@ -53,10 +73,82 @@ impl MapLoader {
         model_loader: &mut ModelLoader,
         texture_loader: &mut TextureLoader,
     ) -> Result<Arc<Map>, String> {
-        match self.cache.get(&resource_file) {
-            Some(map) => Ok(map.clone()),
-            None => self.load(resource_file, game_file_loader, buffer_allocator, model_loader, texture_loader),
+        if let Some(map) = self.cache.get(&resource_file) {
+            self.recently_used.retain(|name| *name != resource_file);
+            self.recently_used.push_back(resource_file);
+
+            return Ok(map.clone());
+        }
+
+        // NOTE: Wait for the geometry of a preloading map to be in the disk cache, so
+        // it isn't generated twice.
+        if let Some(handle) = self.preloading.remove(&resource_file) {
+            let _ = handle.join();
+        }
+
+        let map = self.load(
+            resource_file.clone(),
+            game_file_loader,
+            buffer_allocator,
+            model_loader,
+            texture_loader,
+        )?;
+        self.cache.insert(resource_file.clone(), map.clone());
+        self.recently_used.push_back(resource_file);
+
+        while self.recently_used.len() > MAXIMUM_CACHED_MAPS
+            && let Some(least_recently_used) = self.recently_used.pop_front()
+        {
+            self.cache.remove(&least_recently_used);
+
+            #[cfg(feature = "debug")]
+            mark_map_evicted(&least_recently_used);
+        }
+
+        Ok(map)
+    }
+
+    /// Generate the geometry of a map on a worker thread and store it in the
+    /// disk cache, so entering the map later doesn't have to. Only the map
+    /// files are read on the calling thread. Failing to load is not an error
+    /// here, since the map might never be entered.
+    pub fn preload(&mut self, resource_file: String, game_file_loader: &mut GameFileLoader) {
+        self.preloading.retain(|_, handle| !handle.is_finished());
+
+        if self.cache.contains_key(&resource_file) || self.preloading.contains_key(&resource_file) {
+            return;
         }
+
+        #[cfg(feature = "debug")]
+        print_debug!("preloading map {MAGENTA}{resource_file}{NONE}");
+
+        let source = parse_map_data(&resource_file, game_file_loader).and_then(|mut map_data| {
+            MapOverrides::load(&resource_file, game_file_loader).apply(&mut map_data);
+            GeometrySource::load(&map_data, game_file_loader)
+        });
+
+        let source = match source {
+            Ok(source) => source,
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to preload map: {_error}");
+
+                return;
+            }
+        };
+
+        let handle = std::thread::spawn(move || {
+            if MapGeometry::load_cached(source.key).is_some() {
+                return;
+            }
+
+            if let Err(_error) = source.generate() {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to preload map: {_error}");
+            }
+        });
+
+        self.preloading.insert(resource_file, handle);
     }
 
     fn load(
@@ -70,31 +162,33 @@ impl MapLoader {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load map from {}", &resource_file));
 
+        #[cfg(feature = "debug")]
+        let _residency_scope = ResidencyScope::new(&resource_file);
+
         let mut map_data = parse_map_data(&resource_file, game_file_loader)?;
+        let overrides = MapOverrides::load(&resource_file, game_file_loader);
+        overrides.apply(&mut map_data);
 
         #[cfg(feature = "debug")]
         let map_data_clone = map_data.clone();
 
-        let ground_data = parse_ground_data(map_data.ground_file.as_str(), game_file_loader)?;
-        let mut gat_data = parse_gat_data(map_data.gat_file.as_str(), game_file_loader)?;
-
-        let (tile_vertices, tile_picker_vertices) = generate_tile_vertices(&mut gat_data);
-        let water_level = -map_data
-            .water_settings
-            .as_ref()
-            .and_then(|settings| settings.water_level)
-            .unwrap_or_default();
-        let (ground_vertices, water_vertices) = ground_water_vertices(&ground_data, water_level);
+        let source = GeometrySource::load(&map_data, game_file_loader)?;
+        let geometry = match MapGeometry::load_cached(source.key) {
+            Some(geometry) => geometry,
+            None => source.generate()?,
+        };
 
-        let ground_vertices = NativeModelVertex::to_vertices(ground_vertices);
-        let ground_vertex_buffer = buffer_allocator.allocate_vertex_buffer(ground_vertices);
+        let ground_vertex_buffer = buffer_allocator.allocate_vertex_buffer(geometry.ground_vertices);
+        let water_vertices = geometry.water_vertices;
         let water_vertex_buffer = (!water_vertices.is_empty()).then(|| buffer_allocator.allocate_vertex_buffer(water_vertices));
+        let tile_vertices = geometry.tile_vertices;
         let tile_vertex_buffer = (!tile_vertices.is_empty()).then(|| buffer_allocator.allocate_vertex_buffer(tile_vertices));
+        let tile_picker_vertices = geometry.tile_picker_vertices;
         let tile_picker_vertex_buffer =
             (!tile_picker_vertices.is_empty()).then(|| buffer_allocator.allocate_vertex_buffer(tile_picker_vertices));
 
-        let textures = load_textures(&ground_data, texture_loader, game_file_loader);
-        apply_map_offset(&ground_data, &mut map_data.resources);
+        let textures = load_textures(&geometry.texture_names, texture_loader, game_file_loader);
+        apply_map_offset(geometry.ground_width, geometry.ground_height, &mut map_data.resources);
 
         // Loading object models
         let objects: Vec<Object> = map_data
@@ -122,11 +216,13 @@ impl MapLoader {
             .collect();
 
         let map = Arc::new(Map::new(
-            gat_data.map_width as usize,
-            gat_data.map_height as usize,
+            geometry.map_width as usize,
+            geometry.map_height as usize,
             map_data.water_settings,
             map_data.light_settings,
-            gat_data.tiles,
+            overrides.indoor_lighting,
+            overrides.fog,
+            geometry.tiles,
             ground_vertex_buffer,
             water_vertex_buffer,
             textures,
@@ -140,8 +236,6 @@ impl MapLoader {
             map_data_clone,
         ));
 
-        self.cache.insert(resource_file, map.clone());
-
         #[cfg(feature = "debug")]
         timer.stop();
 
@@ -149,12 +243,51 @@ impl MapLoader {
     }
 }
 
-fn apply_map_offset(ground_data: &GroundData, resources: &mut MapResources) {
-    let offset = Vector3::new(
-        ground_data.width as f32 * MAP_OFFSET,
-        0.0,
-        ground_data.height as f32 * MAP_OFFSET,
-    );
+/// Files the geometry of a map is generated from.
+struct GeometrySource {
+    key: u64,
+    ground_file: String,
+    ground_bytes: Vec<u8>,
+    gat_file: String,
+    gat_bytes: Vec<u8>,
+    water_level: f32,
+}
+
+impl GeometrySource {
+    fn load(map_data: &MapData, game_file_loader: &mut GameFileLoader) -> Result<Self, String> {
+        let ground_bytes = game_file_loader.get(&format!("data\\{}", &map_data.ground_file))?;
+        let gat_bytes = game_file_loader.get(&format!("data\\{}", &map_data.gat_file))?;
+        let water_level = -map_data
+            .water_settings
+            .as_ref()
+            .and_then(|settings| settings.water_level)
+            .unwrap_or_default();
+
+        let key = cache_key(&[&ground_bytes, &gat_bytes, &water_level.to_le_bytes()]);
+
+        Ok(Self {
+            key,
+            ground_file: map_data.ground_file.clone(),
+            ground_bytes,
+            gat_file: map_data.gat_file.clone(),
+            gat_bytes,
+            water_level,
+        })
+    }
+
+    /// Generate the geometry and store it in the disk cache.
+    fn generate(&self) -> Result<MapGeometry, String> {
+        let ground_data = parse_ground_data(&self.ground_bytes, &self.ground_file)?;
+        let gat_data = parse_gat_data(&self.gat_bytes, &self.gat_file)?;
+        let geometry = MapGeometry::generate(ground_data, gat_data, self.water_level);
+
+        geometry.store_cached(self.key);
+        Ok(geometry)
+    }
+}
+
+fn apply_map_offset(ground_width: i32, ground_height: i32, resources: &mut MapResources) {
+    let offset = Vector3::new(ground_width as f32 * MAP_OFFSET, 0.0, ground_height as f32 * MAP_OFFSET);
 
     resources.objects.iter_mut().for_each(|object| object.offset(offset));
     resources
@@ -187,9 +320,8 @@ fn parse_map_data(resource_file: &str, game_file_loader: &mut GameFileLoader) ->
     Ok(map_data)
 }
 
-fn parse_ground_data(ground_file: &str, game_file_loader: &mut GameFileLoader) -> Result<GroundData, String> {
-    let bytes = game_file_loader.get(&format!("data\\{}", &ground_file))?;
-    let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(&bytes);
+fn parse_ground_data(bytes: &[u8], ground_file: &str) -> Result<GroundData, String> {
+    let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(bytes);
 
     if <[u8; 4]>::from_bytes(&mut byte_stream).unwrap() != [b'G', b'R', b'G', b'N'] {
         return Err(format!("failed to read magic number from {}", &ground_file));
@@ -203,9 +335,8 @@ fn parse_ground_data(ground_file: &str, game_file_loader: &mut GameFileLoader) -
     Ok(ground_data)
 }
 
-fn parse_gat_data(gat_file: &str, game_file_loader: &mut GameFileLoader) -> Result<GatData, String> {
-    let bytes = game_file_loader.get(&format!("data\\{}", &gat_file))?;
-    let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(&bytes);
+fn parse_gat_data(bytes: &[u8], gat_file: &str) -> Result<GatData, String> {
+    let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(bytes);
 
     if <[u8; 4]>::from_bytes(&mut byte_stream).unwrap() != [b'G', b'R', b'A', b'T'] {
         return Err(format!("failed to read magic number from {}", &gat_file));
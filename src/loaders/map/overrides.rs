@@ -0,0 +1,199 @@
+use serde::Deserialize;
+
+use super::data::MapData;
+use super::resource::{LightSettings, WaterSettings};
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::ColorRGB;
+use crate::loaders::GameFileLoader;
+
+/// Visual adjustments for a single map, so broken maps can be fixed by
+/// shipping a data file instead of changing the client. Every field is
+/// optional and only replaces the value stored in the map files if present.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct MapOverrides {
+    /// Keep the lighting at noon instead of following the day cycle.
+    pub indoor_lighting: bool,
+    pub ambient_color: Option<ColorRGB>,
+    pub diffuse_color: Option<ColorRGB>,
+    pub light_intensity: Option<f32>,
+    pub water_level: Option<f32>,
+    pub disable_weather: bool,
+    /// Maps don't have any fog unless it is set here.
+    pub fog: Option<FogSettings>,
+}
+
+/// Distance fog that is blended over the lit scene.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub struct FogSettings {
+    pub color: ColorRGB,
+    /// Distance from the camera at which the fog starts.
+    pub near: f32,
+    /// Distance from the camera at which the fog reaches its full density.
+    pub far: f32,
+    /// Opacity of the fog at full density.
+    #[serde(default = "default_fog_density")]
+    pub density: f32,
+}
+
+fn default_fog_density() -> f32 {
+    1.0
+}
+
+impl MapOverrides {
+    /// Overrides are looked up next to the map resource file. Maps without an
+    /// override file are common, so a missing file is not an error.
+    pub fn load(resource_file: &str, game_file_loader: &mut GameFileLoader) -> Self {
+        let path = format!("data\\{resource_file}.overrides.ron");
+
+        let Ok(bytes) = game_file_loader.get(&path) else {
+            return Self::default();
+        };
+
+        #[cfg(feature = "debug")]
+        print_debug!("loading map overrides from {MAGENTA}{path}{NONE}");
+
+        String::from_utf8(bytes)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_else(|| {
+                #[cfg(feature = "debug")]
+                print_debug!("[{YELLOW}warning{NONE}] failed to parse map overrides from {MAGENTA}{path}{NONE}");
+
+                Self::default()
+            })
+    }
+
+    pub fn apply(&self, map_data: &mut MapData) {
+        self.apply_lighting(&mut map_data.light_settings);
+        self.apply_water(map_data.water_settings.as_mut());
+
+        if self.disable_weather {
+            map_data
+                .resources
+                .effect_sources
+                .retain(|effect_source| !effect_source.is_weather());
+        }
+    }
+
+    fn apply_lighting(&self, light_settings: &mut LightSettings) {
+        if let Some(ambient_color) = self.ambient_color {
+            light_settings.ambient_color = Some(ambient_color);
+        }
+
+        if let Some(diffuse_color) = self.diffuse_color {
+            light_settings.diffuse_color = Some(diffuse_color);
+        }
+
+        if let Some(light_intensity) = self.light_intensity {
+            light_settings.light_intensity = Some(light_intensity);
+        }
+    }
+
+    fn apply_water(&self, water_settings: Option<&mut WaterSettings>) {
+        if let Some(water_level) = self.water_level
+            && let Some(water_settings) = water_settings
+        {
+            water_settings.water_level = Some(water_level);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FogSettings, MapOverrides};
+    use crate::graphics::ColorRGB;
+    use crate::loaders::{LightSettings, WaterSettings};
+
+    fn light_settings() -> LightSettings {
+        LightSettings {
+            light_longitude: Some(45),
+            light_latitude: Some(45),
+            diffuse_color: Some(ColorRGB {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+            }),
+            ambient_color: Some(ColorRGB {
+                red: 0.3,
+                green: 0.3,
+                blue: 0.3,
+            }),
+            light_intensity: Some(0.5),
+        }
+    }
+
+    fn water_settings() -> WaterSettings {
+        WaterSettings {
+            water_level: Some(-10.0),
+            water_type: None,
+            wave_height: None,
+            wave_speed: None,
+            wave_pitch: None,
+            water_animation_speed: None,
+        }
+    }
+
+    #[test]
+    fn missing_fields_are_not_overridden() {
+        let overrides: MapOverrides = ron::from_str("(indoor_lighting: true)").unwrap();
+
+        assert!(overrides.indoor_lighting);
+        assert!(!overrides.disable_weather);
+        assert!(overrides.fog.is_none());
+
+        let mut light_settings = light_settings();
+        let mut water_settings = water_settings();
+        overrides.apply_lighting(&mut light_settings);
+        overrides.apply_water(Some(&mut water_settings));
+
+        assert_eq!(light_settings.ambient_color, light_settings().ambient_color);
+        assert_eq!(light_settings.light_intensity, Some(0.5));
+        assert_eq!(water_settings.water_level, Some(-10.0));
+    }
+
+    #[test]
+    fn override_lighting_and_water() {
+        let overrides: MapOverrides =
+            ron::from_str("(ambient_color: Some((red: 0.5, green: 0.4, blue: 0.3)), light_intensity: Some(1.0), water_level: Some(4.0))")
+                .unwrap();
+
+        let mut light_settings = light_settings();
+        let mut water_settings = water_settings();
+        overrides.apply_lighting(&mut light_settings);
+        overrides.apply_water(Some(&mut water_settings));
+
+        assert_eq!(
+            light_settings.ambient_color,
+            Some(ColorRGB {
+                red: 0.5,
+                green: 0.4,
+                blue: 0.3,
+            })
+        );
+        assert_eq!(light_settings.diffuse_color, light_settings().diffuse_color);
+        assert_eq!(light_settings.light_intensity, Some(1.0));
+        assert_eq!(water_settings.water_level, Some(4.0));
+    }
+
+    #[test]
+    fn custom_fog() {
+        let overrides: MapOverrides =
+            ron::from_str("(fog: Some((color: (red: 0.8, green: 0.8, blue: 0.9), near: 50.0, far: 300.0)))").unwrap();
+
+        assert_eq!(
+            overrides.fog,
+            Some(FogSettings {
+                color: ColorRGB {
+                    red: 0.8,
+                    green: 0.8,
+                    blue: 0.9,
+                },
+                near: 50.0,
+                far: 300.0,
+                density: 1.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+
+use cgmath::Vector2;
+use ron::ser::PrettyConfig;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+/// The client data doesn't contain the destination of warp portals, so the
+/// client remembers which maps the player walked between and stores them in
+/// this file.
+const MAP_CONNECTIONS_PATH: &str = "client/map_connections.ron";
+/// Distance in tiles from a warp portal at which the player is moved to the
+/// next map.
+const WARP_RANGE: usize = 2;
+/// Frames that take longer than this are not considered idle.
+const IDLE_FRAME_TIME: f64 = 1.0 / 50.0;
+/// Time the client needs to be idle before the next map is loaded.
+const PRELOAD_INTERVAL: f64 = 2.0;
+
+/// Queues the maps connected to the current map and hands them out one at a
+/// time while the client is idle, so walking through a warp portal doesn't
+/// have to wait for the next map to load.
+pub struct MapPreloader {
+    connections: HashMap<String, Vec<String>>,
+    current_map: Option<String>,
+    queue: VecDeque<String>,
+    idle_time: f64,
+}
+
+impl MapPreloader {
+    pub fn new() -> Self {
+        let connections = std::fs::read_to_string(MAP_CONNECTIONS_PATH)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default();
+
+        Self::from_connections(connections)
+    }
+
+    fn from_connections(connections: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            connections,
+            current_map: None,
+            queue: VecDeque::new(),
+            idle_time: 0.0,
+        }
+    }
+
+    fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving map connections to {MAGENTA}{MAP_CONNECTIONS_PATH}{NONE}");
+
+        let data = ron::ser::to_string_pretty(&self.connections, PrettyConfig::new()).unwrap();
+
+        if let Err(_error) = std::fs::write(MAP_CONNECTIONS_PATH, data) {
+            #[cfg(feature = "debug")]
+            print_debug!("[{RED}error{NONE}] failed to save map connections: {_error}");
+        }
+    }
+
+    /// Check if the player is standing on a warp portal. Used to tell warp
+    /// portals apart from teleports when the map changes.
+    pub fn is_in_warp_range(player_position: Vector2<usize>, warp_position: Vector2<usize>) -> bool {
+        player_position.x.abs_diff(warp_position.x) <= WARP_RANGE && player_position.y.abs_diff(warp_position.y) <= WARP_RANGE
+    }
+
+    /// Connect two maps in both directions. Returns `true` if the connection
+    /// is new.
+    fn add_connection(&mut self, first_map: &str, second_map: &str) -> bool {
+        let mut connect = |from: &str, to: &str| {
+            let connected_maps = self.connections.entry(from.to_owned()).or_default();
+            let is_new = !connected_maps.iter().any(|map_name| map_name == to);
+
+            if is_new {
+                connected_maps.push(to.to_owned());
+            }
+
+            is_new
+        };
+
+        // NOTE: Both directions need to be added, so don't short-circuit.
+        connect(first_map, second_map) | connect(second_map, first_map)
+    }
+
+    /// Replace the queue with the maps connected to the map that was just
+    /// entered. If the player entered the map through a warp portal, the two
+    /// maps are remembered as connected.
+    pub fn set_current_map(&mut self, map_name: &str, entered_through_warp: bool) {
+        let previous_map = self.current_map.replace(map_name.to_owned());
+
+        if entered_through_warp
+            && let Some(previous_map) = &previous_map
+            && previous_map != map_name
+            && self.add_connection(previous_map, map_name)
+        {
+            self.save();
+        }
+
+        self.queue = self
+            .connections
+            .get(map_name)
+            .into_iter()
+            .flatten()
+            .filter(|connected_map| previous_map.as_deref() != Some(connected_map.as_str()))
+            .cloned()
+            .collect();
+        self.idle_time = 0.0;
+    }
+
+    /// Get the next map that should be loaded, if the client has been idle
+    /// for long enough.
+    pub fn update(&mut self, delta_time: f64) -> Option<String> {
+        if delta_time > IDLE_FRAME_TIME {
+            self.idle_time = 0.0;
+            return None;
+        }
+
+        self.idle_time += delta_time;
+
+        if self.idle_time < PRELOAD_INTERVAL {
+            return None;
+        }
+
+        self.idle_time = 0.0;
+        self.queue.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use cgmath::Vector2;
+
+    use super::{MapPreloader, PRELOAD_INTERVAL};
+
+    #[test]
+    fn preload_connected_maps_when_idle() {
+        let connections = HashMap::from([("prontera".to_owned(), vec!["prt_fild08".to_owned(), "izlude".to_owned()])]);
+        let mut preloader = MapPreloader::from_connections(connections);
+        preloader.set_current_map("prontera", false);
+
+        assert_eq!(preloader.update(0.01), None);
+        // A slow frame resets the idle time.
+        assert_eq!(preloader.update(1.0), None);
+        assert_eq!(preloader.update(0.01), None);
+
+        let frames = (PRELOAD_INTERVAL / 0.01) as usize;
+        let preloaded: Vec<_> = (0..frames * 3).filter_map(|_| preloader.update(0.01)).collect();
+        assert_eq!(preloaded, ["prt_fild08", "izlude"]);
+    }
+
+    #[test]
+    fn connect_maps_in_both_directions() {
+        let mut preloader = MapPreloader::from_connections(HashMap::new());
+
+        assert!(preloader.add_connection("prontera", "izlude"));
+        assert!(!preloader.add_connection("izlude", "prontera"));
+        assert_eq!(preloader.connections["prontera"], ["izlude"]);
+        assert_eq!(preloader.connections["izlude"], ["prontera"]);
+    }
+
+    #[test]
+    fn skip_the_previous_map() {
+        let connections = HashMap::from([("prt_fild08".to_owned(), vec!["prontera".to_owned(), "izlude".to_owned()])]);
+        let mut preloader = MapPreloader::from_connections(connections);
+        preloader.set_current_map("prontera", false);
+        preloader.set_current_map("prt_fild08", false);
+
+        assert_eq!(preloader.queue, ["izlude"]);
+    }
+
+    #[test]
+    fn warp_range() {
+        assert!(MapPreloader::is_in_warp_range(Vector2::new(150, 30), Vector2::new(151, 32)));
+        assert!(!MapPreloader::is_in_warp_range(Vector2::new(150, 30), Vector2::new(150, 33)));
+    }
+}
@@ -148,12 +148,11 @@ pub fn ground_water_vertices(ground_data: &GroundData, water_level: f32) -> (Vec
 }
 
 pub fn load_textures(
-    ground_data: &GroundData,
+    texture_names: &[String],
     texture_loader: &mut TextureLoader,
     game_file_loader: &mut GameFileLoader,
 ) -> Vec<Arc<ImageView>> {
-    ground_data
-        .textures
+    texture_names
         .iter()
         .map(|texture_name| texture_loader.get(texture_name, game_file_loader).unwrap())
         .collect()
@@ -1,5 +1,6 @@
 mod action;
 mod archive;
+mod cache;
 mod effect;
 mod font;
 mod gamefile;
@@ -17,9 +18,11 @@ pub use self::font::FontLoader;
 pub use self::gamefile::*;
 #[cfg(feature = "debug")]
 pub use self::map::MapData;
-pub use self::map::{LightSettings, MapLoader, WaterSettings};
+pub use self::map::{FogSettings, LightSettings, MapLoader, MapPreloader, WaterSettings};
 pub use self::model::*;
-pub use self::script::ScriptLoader;
+pub use self::script::{AchievementInfo, ItemInfo, QuestInfo, ScriptLoader, SkillInfo};
+#[cfg(test)]
+pub use self::server::Service;
 pub use self::server::{load_client_info, ClientInfo, ServiceId};
 pub use self::sprite::*;
 pub use self::texture::TextureLoader;
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use cgmath::{Matrix4, Vector3};
+use vulkano::image::view::ImageView;
+
+use super::{PositionKeyframeData, RotationKeyframeData, ScaleKeyframeData};
+use crate::graphics::{BufferAllocator, ModelVertex};
+use crate::loaders::cache::{load_cached, store_cached, CacheReader, CacheWriter};
+use crate::world::{BoundingBox, Node};
+
+const CACHE_KIND: &str = "model";
+
+/// Processed node of a model that is not yet uploaded to the GPU.
+pub struct NodeGeometry {
+    pub transform_matrix: Matrix4<f32>,
+    pub vertices: Vec<ModelVertex>,
+    pub texture_indices: Vec<u32>,
+    pub child_nodes: Vec<NodeGeometry>,
+    pub rotation_keyframes: Vec<RotationKeyframeData>,
+    pub scale_keyframes: Vec<ScaleKeyframeData>,
+    pub position_keyframes: Vec<PositionKeyframeData>,
+}
+
+impl NodeGeometry {
+    pub fn into_node(self, buffer_allocator: &mut BufferAllocator, textures: &[Arc<ImageView>]) -> Node {
+        let vertex_buffer = buffer_allocator.allocate_vertex_buffer(self.vertices);
+        let node_textures = self.texture_indices.iter().map(|index| textures[*index as usize].clone()).collect();
+        let child_nodes = self
+            .child_nodes
+            .into_iter()
+            .map(|child_node| child_node.into_node(buffer_allocator, textures))
+            .collect();
+
+        Node::new(
+            self.transform_matrix,
+            vertex_buffer,
+            node_textures,
+            child_nodes,
+            self.rotation_keyframes,
+            self.scale_keyframes,
+            self.position_keyframes,
+        )
+    }
+
+    fn read(reader: &mut CacheReader) -> Option<Self> {
+        let transform_matrix = reader.read_pod::<[[f32; 4]; 4]>()?.into();
+        let vertices = reader.read_vec()?;
+        let texture_indices = reader.read_vec()?;
+        let frames = reader.read_vec::<u32>()?;
+        let rotations = reader.read_vec::<[f32; 4]>()?;
+        let rotation_keyframes = frames
+            .into_iter()
+            .zip(rotations)
+            .map(|(frame, rotation)| RotationKeyframeData {
+                frame,
+                quaternions: rotation.into(),
+            })
+            .collect();
+        let frames = reader.read_vec::<u32>()?;
+        let scales = reader.read_vec::<[f32; 4]>()?;
+        let scale_keyframes = frames
+            .into_iter()
+            .zip(scales)
+            .map(|(frame, [x, y, z, data])| ScaleKeyframeData {
+                frame,
+                scale: Vector3::new(x, y, z),
+                data,
+            })
+            .collect();
+        let frames = reader.read_vec::<u32>()?;
+        let positions = reader.read_vec::<[f32; 4]>()?;
+        let position_keyframes = frames
+            .into_iter()
+            .zip(positions)
+            .map(|(frame, [x, y, z, data])| PositionKeyframeData {
+                frame,
+                position: Vector3::new(x, y, z),
+                data,
+            })
+            .collect();
+        let child_nodes = (0..reader.read_u32()?).map(|_| Self::read(reader)).collect::<Option<_>>()?;
+
+        Some(Self {
+            transform_matrix,
+            vertices,
+            texture_indices,
+            child_nodes,
+            rotation_keyframes,
+            scale_keyframes,
+            position_keyframes,
+        })
+    }
+
+    fn write(&self, writer: &mut CacheWriter) {
+        let transform_matrix: [[f32; 4]; 4] = self.transform_matrix.into();
+        let frames: Vec<u32> = self.rotation_keyframes.iter().map(|keyframe| keyframe.frame).collect();
+        let rotations: Vec<[f32; 4]> = self.rotation_keyframes.iter().map(|keyframe| keyframe.quaternions.into()).collect();
+        let scale_frames: Vec<u32> = self.scale_keyframes.iter().map(|keyframe| keyframe.frame).collect();
+        let scales: Vec<[f32; 4]> = self
+            .scale_keyframes
+            .iter()
+            .map(|keyframe| keyframe.scale.extend(keyframe.data).into())
+            .collect();
+        let position_frames: Vec<u32> = self.position_keyframes.iter().map(|keyframe| keyframe.frame).collect();
+        let positions: Vec<[f32; 4]> = self
+            .position_keyframes
+            .iter()
+            .map(|keyframe| keyframe.position.extend(keyframe.data).into())
+            .collect();
+
+        writer.write_pod(&transform_matrix);
+        writer.write_slice(&self.vertices);
+        writer.write_slice(&self.texture_indices);
+        writer.write_slice(&frames);
+        writer.write_slice(&rotations);
+        writer.write_slice(&scale_frames);
+        writer.write_slice(&scales);
+        writer.write_slice(&position_frames);
+        writer.write_slice(&positions);
+        writer.write_u32(self.child_nodes.len() as u32);
+        self.child_nodes.iter().for_each(|child_node| child_node.write(writer));
+    }
+}
+
+/// Processed model that is not yet uploaded to the GPU. Building the vertices
+/// of all nodes is the most expensive part of loading a model, so the result
+/// is stored in the asset cache.
+pub struct ModelGeometry {
+    pub texture_names: Vec<String>,
+    pub bounding_box: BoundingBox,
+    pub alpha: f32,
+    pub root_nodes: Vec<NodeGeometry>,
+}
+
+impl ModelGeometry {
+    // NOTE: Debug builds always parse the model, since the inspector needs the
+    // model data.
+    #[cfg_attr(feature = "debug", allow(dead_code))]
+    pub fn load_cached(key: u64) -> Option<Self> {
+        let mut reader = load_cached(CACHE_KIND, key)?;
+
+        let texture_names = (0..reader.read_u32()?).map(|_| reader.read_string()).collect::<Option<_>>()?;
+        let smallest: [f32; 3] = reader.read_pod()?;
+        let biggest: [f32; 3] = reader.read_pod()?;
+        let bounding_box = BoundingBox {
+            smallest: Vector3::from(smallest),
+            biggest: Vector3::from(biggest),
+        };
+        let alpha = reader.read_pod()?;
+        let root_nodes = (0..reader.read_u32()?)
+            .map(|_| NodeGeometry::read(&mut reader))
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            texture_names,
+            bounding_box,
+            alpha,
+            root_nodes,
+        })
+    }
+
+    pub fn store_cached(&self, key: u64) {
+        let smallest: [f32; 3] = self.bounding_box.smallest.into();
+        let biggest: [f32; 3] = self.bounding_box.biggest.into();
+
+        let mut writer = CacheWriter::new();
+        writer.write_u32(self.texture_names.len() as u32);
+        self.texture_names.iter().for_each(|texture_name| writer.write_string(texture_name));
+        writer.write_pod(&smallest);
+        writer.write_pod(&biggest);
+        writer.write_pod(&self.alpha);
+        writer.write_u32(self.root_nodes.len() as u32);
+        self.root_nodes.iter().for_each(|root_node| root_node.write(&mut writer));
+
+        store_cached(CACHE_KIND, key, writer);
+    }
+}
@@ -1,26 +1,39 @@
+mod geometry;
+
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use cgmath::{Matrix3, Matrix4, Quaternion, Rad, SquareMatrix, Vector2, Vector3};
+use cgmath::{Array, Matrix3, Matrix4, Quaternion, Rad, SquareMatrix, Vector2, Vector3};
 use derive_new::new;
 use procedural::PrototypeElement;
 use ragnarok_bytes::{ByteStream, ConversionError, ConversionResult, ConversionResultExt, FromBytes, FromBytesExt};
 use ragnarok_procedural::FromBytes;
-use vulkano::image::view::ImageView;
 
+use self::geometry::{ModelGeometry, NodeGeometry};
 use super::version::InternalVersion;
 use super::FALLBACK_MODEL_FILE;
 #[cfg(feature = "debug")]
 use crate::debug::*;
+#[cfg(feature = "debug")]
+use crate::graphics::CacheResidencyScope;
 use crate::graphics::{BufferAllocator, NativeModelVertex};
+use crate::loaders::cache::cache_key;
 use crate::loaders::{GameFileLoader, MajorFirst, TextureLoader, Version};
 use crate::system::multiply_matrix4_and_vector3;
-use crate::world::{BoundingBox, Model, Node};
+use crate::world::{BoundingBox, Model};
 
-#[derive(Debug, FromBytes, PrototypeElement)]
+#[derive(Clone, Debug, FromBytes, PrototypeElement)]
+pub struct ScaleKeyframeData {
+    pub frame: u32,
+    pub scale: Vector3<f32>,
+    pub data: f32,
+}
+
+#[derive(Clone, Debug, FromBytes, PrototypeElement)]
 pub struct PositionKeyframeData {
     pub frame: u32,
     pub position: Vector3<f32>,
+    pub data: f32,
 }
 
 #[derive(Clone, Debug, FromBytes, PrototypeElement)]
@@ -29,15 +42,43 @@ pub struct RotationKeyframeData {
     pub quaternions: Quaternion<f32>,
 }
 
+#[derive(Debug, FromBytes, PrototypeElement)]
+pub struct TextureKeyframeData {
+    pub frame: u32,
+    pub offset: f32,
+}
+
+#[derive(Debug, FromBytes, PrototypeElement)]
+pub struct TextureAnimationData {
+    pub animation_type: u32,
+    pub frame_count: u32,
+    #[repeating(self.frame_count)]
+    pub frames: Vec<TextureKeyframeData>,
+}
+
+#[derive(Debug, FromBytes, PrototypeElement)]
+pub struct NodeTextureAnimationData {
+    pub texture_index: u32,
+    pub animation_count: u32,
+    #[repeating(self.animation_count)]
+    pub animations: Vec<TextureAnimationData>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, FromBytes, PrototypeElement)]
 pub struct FaceData {
+    #[version_equals_or_above(2, 2)]
+    pub length: Option<u32>,
     pub vertex_position_indices: [u16; 3],
     pub texture_coordinate_indices: [u16; 3],
     pub texture_index: u16,
     pub padding: u16,
     pub two_sided: i32,
     pub smooth_group: i32,
+    // NOTE: RSM2 faces store their own size, which leaves room for additional
+    // smoothing groups.
+    #[repeating(self.length.map_or(0, |size| size.saturating_sub(24) / 4))]
+    pub additional_smooth_groups: Vec<i32>,
 }
 
 #[derive(Debug, FromBytes, PrototypeElement)]
@@ -50,17 +91,29 @@ pub struct TextureCoordinateData {
 #[derive(Debug, FromBytes, PrototypeElement)]
 pub struct NodeData {
     pub node_name: ModelString<40>,
-    pub parent_node_name: ModelString<40>, // This is where 2.2 starts failing
-    pub texture_count: u32,
-    #[repeating(self.texture_count)]
+    pub parent_node_name: ModelString<40>,
+    #[version_smaller(2, 3)]
+    pub texture_count: Option<u32>,
+    #[repeating(self.texture_count.unwrap_or_default())]
     pub texture_indices: Vec<u32>,
+    // NOTE: Starting with version 2.3, every node references its textures by name.
+    #[version_equals_or_above(2, 3)]
+    pub texture_name_count: Option<u32>,
+    #[repeating(self.texture_name_count.unwrap_or_default())]
+    pub texture_names: Vec<ModelString<40>>,
     #[hidden_element]
     pub offset_matrix: Matrix3<f32>,
     pub translation1: Vector3<f32>,
-    pub translation2: Vector3<f32>,
-    pub rotation_angle: f32,
-    pub rotation_axis: Vector3<f32>,
-    pub scale: Vector3<f32>,
+    // NOTE: RSM2 nodes are fully described by the offset matrix and their
+    // keyframes.
+    #[version_smaller(2, 2)]
+    pub translation2: Option<Vector3<f32>>,
+    #[version_smaller(2, 2)]
+    pub rotation_angle: Option<f32>,
+    #[version_smaller(2, 2)]
+    pub rotation_axis: Option<Vector3<f32>>,
+    #[version_smaller(2, 2)]
+    pub scale: Option<Vector3<f32>>,
     pub vertex_position_count: u32,
     #[repeating(self.vertex_position_count)]
     pub vertex_positions: Vec<Vector3<f32>>,
@@ -70,13 +123,38 @@ pub struct NodeData {
     pub face_count: u32,
     #[repeating(self.face_count)]
     pub faces: Vec<FaceData>,
-    #[version_equals_or_above(2, 5)] // unsure what vesion this is supposed to be (must be > 1.5)
-    pub position_keyframe_count: Option<u32>,
-    #[repeating(self.position_keyframe_count.unwrap_or_default())]
-    pub position_keyframes: Vec<PositionKeyframeData>,
+    #[version_equals_or_above(1, 6)]
+    pub scale_keyframe_count: Option<u32>,
+    #[repeating(self.scale_keyframe_count.unwrap_or_default())]
+    pub scale_keyframes: Vec<ScaleKeyframeData>,
     pub rotation_keyframe_count: u32,
     #[repeating(self.rotation_keyframe_count)]
     pub rotation_keyframes: Vec<RotationKeyframeData>,
+    #[version_equals_or_above(2, 2)]
+    pub position_keyframe_count: Option<u32>,
+    #[repeating(self.position_keyframe_count.unwrap_or_default())]
+    pub position_keyframes: Vec<PositionKeyframeData>,
+    #[version_equals_or_above(2, 3)]
+    pub texture_animation_count: Option<u32>,
+    #[repeating(self.texture_animation_count.unwrap_or_default())]
+    pub texture_animations: Vec<NodeTextureAnimationData>,
+}
+
+impl NodeData {
+    fn translation2(&self) -> Vector3<f32> {
+        self.translation2.unwrap_or(Vector3::from_value(0.0))
+    }
+
+    fn scale(&self) -> Vector3<f32> {
+        self.scale.unwrap_or(Vector3::from_value(1.0))
+    }
+
+    fn rotation_matrix(&self) -> Matrix4<f32> {
+        match (self.rotation_axis, self.rotation_angle) {
+            (Some(rotation_axis), Some(rotation_angle)) => Matrix4::from_axis_angle(rotation_axis, Rad(rotation_angle)),
+            _ => Matrix4::identity(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -119,15 +197,18 @@ pub struct ModelData {
     #[version_equals_or_above(1, 4)]
     pub alpha: Option<u8>,
     #[version_smaller(2, 2)]
-    pub reserved0: Option<[u8; 16]>,
+    pub reserved: Option<[u8; 16]>,
     #[version_equals_or_above(2, 2)]
-    pub reserved1: Option<[u8; 4]>,
-    pub texture_count: u32,
-    #[repeating(self.texture_count)]
+    pub frames_per_second: Option<f32>,
+    #[version_smaller(2, 3)]
+    pub texture_count: Option<u32>,
+    #[repeating(self.texture_count.unwrap_or_default())]
     pub texture_names: Vec<ModelString<40>>,
+    // NOTE: RSM2 models can have multiple root nodes.
     #[version_equals_or_above(2, 2)]
-    pub skip: Option<u32>,
-    pub root_node_name: ModelString<40>,
+    pub root_node_count: Option<u32>,
+    #[repeating(self.root_node_count.unwrap_or(1))]
+    pub root_node_names: Vec<ModelString<40>>,
     pub node_count: u32,
     #[repeating(self.node_count)]
     pub nodes: Vec<NodeData>,
@@ -179,7 +260,7 @@ impl ModelLoader {
     fn make_vertices(node: &NodeData, main_matrix: &Matrix4<f32>, reverse_order: bool) -> Vec<NativeModelVertex> {
         let mut native_vertices = Vec::new();
 
-        let array: [f32; 3] = node.scale.into();
+        let array: [f32; 3] = node.scale().into();
         let reverse_node_order = array.into_iter().fold(1.0, |a, b| a * b).is_sign_negative();
 
         if reverse_node_order {
@@ -230,10 +311,14 @@ impl ModelLoader {
     fn calculate_matrices(node: &NodeData, parent_matrix: &Matrix4<f32>) -> (Matrix4<f32>, Matrix4<f32>, Matrix4<f32>) {
         let main = Matrix4::from_translation(node.translation1) * Matrix4::from(node.offset_matrix);
 
-        let scale_matrix = Matrix4::from_nonuniform_scale(node.scale.x, node.scale.y, node.scale.z);
-        let rotation_matrix = Matrix4::from_axis_angle(node.rotation_axis, Rad(node.rotation_angle));
-        let translation_matrix = Matrix4::from_translation(node.translation2);
+        let scale = node.scale();
+        let scale_matrix = Matrix4::from_nonuniform_scale(scale.x, scale.y, scale.z);
+        let rotation_matrix = node.rotation_matrix();
+        let translation_matrix = Matrix4::from_translation(node.translation2());
 
+        // NOTE: The transform is relative to the parent node and gets combined with the
+        // animated transforms of the parents when rendering. Rotation keyframes
+        // replace the static rotation of the node.
         let transform = match node.rotation_keyframe_count > 0 {
             true => translation_matrix * scale_matrix,
             false => translation_matrix * rotation_matrix * scale_matrix,
@@ -245,20 +330,18 @@ impl ModelLoader {
     }
 
     fn process_node_mesh(
-        buffer_allocator: &mut BufferAllocator,
         current_node: &NodeData,
         nodes: &Vec<NodeData>,
-        textures: &Vec<Arc<ImageView>>,
         parent_matrix: &Matrix4<f32>,
         main_bounding_box: &mut BoundingBox,
-        root_node_name: &ModelString<40>,
+        root_node_names: &[ModelString<40>],
+        texture_indices: &HashMap<&str, u32>,
+        milliseconds_per_frame: f32,
         reverse_order: bool,
-    ) -> Node {
+    ) -> NodeGeometry {
         let (main_matrix, transform_matrix, box_transform_matrix) = Self::calculate_matrices(current_node, parent_matrix);
         let vertices = NativeModelVertex::to_vertices(Self::make_vertices(current_node, &main_matrix, reverse_order));
 
-        let vertex_buffer = buffer_allocator.allocate_vertex_buffer(vertices);
-
         let box_matrix = box_transform_matrix * main_matrix;
         let bounding_box = BoundingBox::new(
             current_node
@@ -268,7 +351,7 @@ impl ModelLoader {
         );
         main_bounding_box.extend(&bounding_box);
 
-        let final_matrix = match current_node.node_name == *root_node_name {
+        let final_matrix = match root_node_names.contains(&current_node.node_name) {
             true => {
                 Matrix4::from_translation(-Vector3::new(
                     bounding_box.center().x,
@@ -279,38 +362,173 @@ impl ModelLoader {
             false => transform_matrix,
         };
 
-        let node_textures = current_node
-            .texture_indices
-            .iter()
-            .map(|index| *index as usize)
-            .map(|index| textures[index].clone())
-            .collect();
-
         let child_nodes = nodes
             .iter()
             .filter(|node| node.parent_node_name == current_node.node_name)
             .filter(|node| node.parent_node_name != node.node_name)
             .map(|node| {
                 Self::process_node_mesh(
-                    buffer_allocator,
                     node,
                     nodes,
-                    textures,
                     &box_transform_matrix,
                     main_bounding_box,
-                    root_node_name,
+                    root_node_names,
+                    texture_indices,
+                    milliseconds_per_frame,
                     reverse_order,
                 )
             })
             .collect();
 
-        Node::new(
-            final_matrix,
-            vertex_buffer,
-            node_textures,
+        // NOTE: Resolve the texture names of RSM2 nodes to indices into the texture
+        // list of the model.
+        let node_texture_indices = match current_node.texture_name_count.is_some() {
+            true => current_node
+                .texture_names
+                .iter()
+                .map(|texture_name| texture_indices[texture_name.inner.as_str()])
+                .collect(),
+            false => current_node.texture_indices.clone(),
+        };
+
+        // NOTE: Keyframes of RSM2 models are measured in frames rather than
+        // milliseconds, so we convert them to make playback independent of the
+        // format.
+        let to_milliseconds = |frame: u32| (frame as f32 * milliseconds_per_frame) as u32;
+
+        let rotation_keyframes = current_node
+            .rotation_keyframes
+            .iter()
+            .map(|keyframe| RotationKeyframeData {
+                frame: to_milliseconds(keyframe.frame),
+                quaternions: keyframe.quaternions,
+            })
+            .collect();
+
+        let scale_keyframes = current_node
+            .scale_keyframes
+            .iter()
+            .map(|keyframe| ScaleKeyframeData {
+                frame: to_milliseconds(keyframe.frame),
+                ..keyframe.clone()
+            })
+            .collect();
+
+        // NOTE: The translation of the node is already applied to the vertices, so
+        // position keyframes are stored as an offset from it.
+        let position_keyframes = current_node
+            .position_keyframes
+            .iter()
+            .map(|keyframe| PositionKeyframeData {
+                frame: to_milliseconds(keyframe.frame),
+                position: keyframe.position - current_node.translation1,
+                data: keyframe.data,
+            })
+            .collect();
+
+        NodeGeometry {
+            transform_matrix: final_matrix,
+            vertices,
+            texture_indices: node_texture_indices,
             child_nodes,
-            current_node.rotation_keyframes.clone(),
-        )
+            rotation_keyframes,
+            scale_keyframes,
+            position_keyframes,
+        }
+    }
+
+    fn collect_texture_names(model_data: &ModelData) -> Vec<String> {
+        let mut texture_names: Vec<String> = model_data.texture_names.iter().map(|name| name.inner.clone()).collect();
+
+        // NOTE: RSM2 nodes name their textures directly, so we build a shared list
+        // without duplicates.
+        model_data
+            .nodes
+            .iter()
+            .flat_map(|node| node.texture_names.iter())
+            .for_each(|texture_name| {
+                if !texture_names.contains(&texture_name.inner) {
+                    texture_names.push(texture_name.inner.clone());
+                }
+            });
+
+        texture_names
+    }
+
+    fn process_model(model_data: &ModelData, reverse_order: bool) -> Result<ModelGeometry, String> {
+        let root_node_names = &model_data.root_node_names;
+        let texture_names = Self::collect_texture_names(model_data);
+        let texture_indices: HashMap<&str, u32> = texture_names
+            .iter()
+            .enumerate()
+            .map(|(index, texture_name)| (texture_name.as_str(), index as u32))
+            .collect();
+        let milliseconds_per_frame = model_data
+            .frames_per_second
+            .filter(|frames_per_second| *frames_per_second > 0.0)
+            .map_or(1.0, |frames_per_second| 1000.0 / frames_per_second);
+
+        let mut bounding_box = BoundingBox::uninitialized();
+        let root_nodes = root_node_names
+            .iter()
+            .map(|root_node_name| {
+                let root_node = model_data
+                    .nodes
+                    .iter()
+                    .find(|node_data| &node_data.node_name == root_node_name)
+                    .ok_or_else(|| format!("failed to find root node {}", root_node_name.inner))?;
+
+                Ok(Self::process_node_mesh(
+                    root_node,
+                    &model_data.nodes,
+                    &Matrix4::identity(),
+                    &mut bounding_box,
+                    root_node_names,
+                    &texture_indices,
+                    milliseconds_per_frame,
+                    reverse_order,
+                ))
+            })
+            .collect::<Result<_, String>>()?;
+
+        // NOTE: Models before version 1.4 don't store an alpha value and are always
+        // opaque.
+        let alpha = model_data.alpha.map_or(1.0, |alpha| alpha as f32 / 255.0);
+
+        Ok(ModelGeometry {
+            texture_names,
+            bounding_box,
+            alpha,
+            root_nodes,
+        })
+    }
+
+    fn create_model(
+        buffer_allocator: &mut BufferAllocator,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+        geometry: ModelGeometry,
+        #[cfg(feature = "debug")] model_data: ModelData,
+    ) -> Arc<Model> {
+        let textures: Vec<_> = geometry
+            .texture_names
+            .iter()
+            .map(|texture_name| texture_loader.get(texture_name, game_file_loader).unwrap())
+            .collect();
+
+        let root_nodes = geometry
+            .root_nodes
+            .into_iter()
+            .map(|root_node| root_node.into_node(buffer_allocator, &textures))
+            .collect();
+
+        Arc::new(Model::new(
+            root_nodes,
+            geometry.bounding_box,
+            geometry.alpha,
+            #[cfg(feature = "debug")]
+            model_data,
+        ))
     }
 
     fn load(
@@ -324,7 +542,23 @@ impl ModelLoader {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load rsm model from {MAGENTA}{model_file}{NONE}"));
 
+        // NOTE: Models are kept in the cache, so their buffers outlive the map.
+        #[cfg(feature = "debug")]
+        let _cache_scope = CacheResidencyScope::enter();
+
         let bytes = game_file_loader.get(&format!("data\\model\\{model_file}"))?;
+        let key = cache_key(&[&bytes, &[reverse_order as u8]]);
+
+        // NOTE: The model inspector needs the parsed model data, so debug builds always
+        // parse the model.
+        #[cfg(not(feature = "debug"))]
+        if let Some(geometry) = ModelGeometry::load_cached(key) {
+            let model = Self::create_model(buffer_allocator, game_file_loader, texture_loader, geometry);
+            self.cache.insert((model_file.to_string(), reverse_order), model.clone());
+
+            return Ok(model);
+        }
+
         let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(&bytes);
 
         if <[u8; 4]>::from_bytes(&mut byte_stream).unwrap() != [b'G', b'R', b'S', b'M'] {
@@ -350,37 +584,34 @@ impl ModelLoader {
             }
         };
 
-        let textures = model_data
-            .texture_names
-            .iter()
-            .map(|texture_name| texture_loader.get(&texture_name.inner, game_file_loader).unwrap())
-            .collect();
-
-        let root_node_name = &model_data.root_node_name;
+        let geometry = match Self::process_model(&model_data, reverse_order) {
+            Ok(geometry) => geometry,
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                {
+                    print_debug!("Failed to process model {model_file}: {_error}");
+                    print_debug!("Replacing with fallback");
+                }
 
-        let root_node = model_data
-            .nodes
-            .iter()
-            .find(|node_data| &node_data.node_name == root_node_name)
-            .expect("failed to find main node");
+                return self.get(
+                    buffer_allocator,
+                    game_file_loader,
+                    texture_loader,
+                    FALLBACK_MODEL_FILE,
+                    reverse_order,
+                );
+            }
+        };
+        geometry.store_cached(key);
 
-        let mut bounding_box = BoundingBox::uninitialized();
-        let root_node = Self::process_node_mesh(
+        let model = Self::create_model(
             buffer_allocator,
-            root_node,
-            &model_data.nodes,
-            &textures,
-            &Matrix4::identity(),
-            &mut bounding_box,
-            root_node_name,
-            reverse_order,
-        );
-        let model = Arc::new(Model::new(
-            root_node,
-            bounding_box,
+            game_file_loader,
+            texture_loader,
+            geometry,
             #[cfg(feature = "debug")]
             model_data,
-        ));
+        );
 
         self.cache.insert((model_file.to_string(), reverse_order), model.clone());
 
@@ -405,3 +636,117 @@ impl ModelLoader {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ragnarok_bytes::{ByteStream, FromBytes};
+
+    use super::{InternalVersion, ModelData, ModelLoader};
+
+    fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn push_f32s(bytes: &mut Vec<u8>, values: &[f32]) {
+        values.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+    }
+
+    fn push_string(bytes: &mut Vec<u8>, value: &str) {
+        // NOTE: RSM2 strings are prefixed with their length. The loader drops the
+        // last character before the terminator.
+        push_u32(bytes, value.len() as u32 + 2);
+        bytes.extend_from_slice(value.as_bytes());
+        bytes.extend_from_slice(&[b'_', 0]);
+    }
+
+    fn push_node(bytes: &mut Vec<u8>, name: &str, parent_name: &str, texture_name: &str) {
+        push_string(bytes, name);
+        push_string(bytes, parent_name);
+        push_u32(bytes, 1);
+        push_string(bytes, texture_name);
+        // Offset matrix and translation.
+        push_f32s(bytes, &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        push_f32s(bytes, &[0.0, 0.0, 0.0]);
+        // Vertex positions.
+        push_u32(bytes, 3);
+        push_f32s(bytes, &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        // Texture coordinates with their color.
+        push_u32(bytes, 3);
+        for coordinates in [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]] {
+            push_u32(bytes, 0xFFFFFFFF);
+            push_f32s(bytes, &coordinates);
+        }
+        // A single one-sided face with an additional smoothing group.
+        push_u32(bytes, 1);
+        push_u32(bytes, 28);
+        [0u16, 1, 2, 0, 1, 2, 0, 0]
+            .iter()
+            .for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+        push_u32(bytes, 0);
+        push_u32(bytes, 0);
+        push_u32(bytes, 0);
+        // Scale keyframes.
+        push_u32(bytes, 0);
+        // Rotation keyframes.
+        push_u32(bytes, 1);
+        push_u32(bytes, 15);
+        push_f32s(bytes, &[0.0, 0.0, 0.0, 1.0]);
+        // Position and texture animation keyframes.
+        push_u32(bytes, 0);
+        push_u32(bytes, 0);
+    }
+
+    fn rsm2_model(root_node_names: &[&str]) -> Vec<u8> {
+        let mut bytes = vec![2, 3];
+        // Animation length, shade type and alpha.
+        push_u32(&mut bytes, 1000);
+        push_u32(&mut bytes, 0);
+        bytes.push(255);
+        push_f32s(&mut bytes, &[30.0]);
+
+        push_u32(&mut bytes, root_node_names.len() as u32);
+        root_node_names.iter().for_each(|name| push_string(&mut bytes, name));
+
+        push_u32(&mut bytes, 3);
+        push_node(&mut bytes, "first", "", "stone.bmp");
+        push_node(&mut bytes, "second", "", "wood.bmp");
+        push_node(&mut bytes, "child", "first", "stone.bmp");
+
+        bytes
+    }
+
+    fn parse(bytes: &[u8]) -> ModelData {
+        let mut byte_stream: ByteStream<Option<InternalVersion>> = ByteStream::without_metadata(bytes);
+        ModelData::from_bytes(&mut byte_stream).unwrap()
+    }
+
+    #[test]
+    fn load_rsm2_model() {
+        let model_data = parse(&rsm2_model(&["first", "second"]));
+        let geometry = ModelLoader::process_model(&model_data, false).unwrap();
+
+        assert_eq!(model_data.nodes[0].faces[0].additional_smooth_groups, [0]);
+        assert_eq!(geometry.texture_names, ["stone.bmp", "wood.bmp"]);
+        assert_eq!(geometry.root_nodes.len(), 2);
+        assert_eq!(geometry.root_nodes[0].child_nodes.len(), 1);
+        assert_eq!(geometry.root_nodes[1].texture_indices, [1]);
+        assert_eq!(geometry.root_nodes[0].child_nodes[0].texture_indices, [0]);
+        assert_eq!(geometry.root_nodes[0].vertices.len(), 3);
+    }
+
+    #[test]
+    fn rsm2_keyframes_are_converted_to_milliseconds() {
+        let model_data = parse(&rsm2_model(&["first"]));
+        let geometry = ModelLoader::process_model(&model_data, false).unwrap();
+
+        // Frame 15 at 30 frames per second.
+        assert_eq!(geometry.root_nodes[0].rotation_keyframes[0].frame, 500);
+    }
+
+    #[test]
+    fn missing_root_node_is_an_error() {
+        let model_data = parse(&rsm2_model(&["first", "missing"]));
+
+        assert!(ModelLoader::process_model(&model_data, false).is_err());
+    }
+}
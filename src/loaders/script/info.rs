@@ -0,0 +1,118 @@
+use mlua::prelude::*;
+
+/// Display information about an item, taken from the `iteminfo` table of the
+/// client.
+#[derive(Clone, Debug, Default)]
+pub struct ItemInfo {
+    pub name: String,
+    pub description: Vec<String>,
+    pub slot_count: u8,
+    /// Weight of a single item. `None` if the item table doesn't know the
+    /// weight.
+    pub weight: Option<u32>,
+}
+
+/// Display information about a skill, taken from the `skillinfolist` and
+/// `skilldescript` tables of the client.
+#[derive(Clone, Debug, Default)]
+pub struct SkillInfo {
+    pub name: String,
+    pub description: Vec<String>,
+}
+
+/// Display information about a quest, taken from the `QuestInfoList` table of
+/// the client.
+#[derive(Clone, Debug, Default)]
+pub struct QuestInfo {
+    pub title: String,
+    pub summary: String,
+}
+
+/// Display information about an achievement, taken from the
+/// `achievement_tbl` table of the client.
+#[derive(Clone, Debug, Default)]
+pub struct AchievementInfo {
+    pub title: String,
+    pub summary: String,
+    /// Tab of the achievement window that the achievement is listed in.
+    pub category: u32,
+    /// Required count of each objective. Empty if the achievement has no
+    /// countable objectives.
+    pub objective_counts: Vec<u32>,
+}
+
+/// Remove the `^RRGGBB` color codes that the client uses in descriptions.
+pub(super) fn strip_color_codes(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut characters = line.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if character == '^' {
+            let code: String = characters.clone().take(6).collect();
+
+            if code.len() == 6 && code.chars().all(|character| character.is_ascii_hexdigit()) {
+                characters.nth(5);
+                continue;
+            }
+        }
+
+        result.push(character);
+    }
+
+    result
+}
+
+pub(super) fn get_string(table: &LuaTable, key: &str) -> Option<String> {
+    table
+        .get::<_, LuaString>(key)
+        .ok()
+        .map(|string| string.to_string_lossy().into_owned())
+}
+
+/// Read a list of description lines, skipping the first `skip` entries.
+/// The item table has no dedicated weight entry, so the weight is taken from
+/// the `Weight : 40` line of the description when the item is loaded.
+pub(super) fn get_weight(description: &[String]) -> Option<u32> {
+    description.iter().find_map(|line| {
+        let (label, value) = line.split_once(':')?;
+        (label.trim() == "Weight").then(|| value.trim().parse().ok()).flatten()
+    })
+}
+
+pub(super) fn get_lines(table: &LuaTable, skip: usize) -> Vec<String> {
+    table
+        .clone()
+        .sequence_values::<LuaString>()
+        .skip(skip)
+        .filter_map(Result::ok)
+        .map(|line| strip_color_codes(&line.to_string_lossy()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_weight, strip_color_codes};
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(strip_color_codes("Red Potion"), "Red Potion");
+    }
+
+    #[test]
+    fn color_codes() {
+        assert_eq!(strip_color_codes("Heals ^0000FF45^000000 HP"), "Heals 45 HP");
+    }
+
+    #[test]
+    fn incomplete_code() {
+        assert_eq!(strip_color_codes("5^2 and ^00ZZ00"), "5^2 and ^00ZZ00");
+    }
+
+    #[test]
+    fn item_weight() {
+        let description = vec!["A potion made from red herbs.".to_owned(), "Weight : 7".to_owned()];
+
+        assert_eq!(get_weight(&description), Some(7));
+        assert_eq!(get_weight(&[]), None);
+    }
+}
@@ -1,7 +1,23 @@
+mod info;
+
 use mlua::Lua;
 
+pub use self::info::{AchievementInfo, ItemInfo, QuestInfo, SkillInfo};
+#[cfg(feature = "debug")]
+use crate::debug::*;
 use crate::loaders::GameFileLoader;
-use crate::network::ItemId;
+use crate::network::{ItemId, SkillId};
+
+/// Skill tables are optional, since not every client ships them.
+const SKILL_FILES: [&str; 3] = [
+    "data\\luafiles514\\lua files\\skillinfoz\\skillid.lub",
+    "data\\luafiles514\\lua files\\skillinfoz\\skillinfolist.lub",
+    "data\\luafiles514\\lua files\\skillinfoz\\skilldescript.lub",
+];
+
+/// Quest and achievement tables are optional as well. They are usually not
+/// part of an archive but found in the `System` folder of the client.
+const SYSTEM_FILES: [&str; 2] = ["System\\OngoingQuestInfoList_True.lub", "System\\achievement_list.lub"];
 
 pub struct ScriptLoader {
     state: Lua,
@@ -22,6 +38,18 @@ impl ScriptLoader {
 
         state.load(&data).exec().unwrap();
 
+        for path in SKILL_FILES.into_iter().chain(SYSTEM_FILES) {
+            let result = game_file_loader
+                .get(path)
+                .map_err(|error| error.to_string())
+                .and_then(|data| state.load(&data).exec().map_err(|error| error.to_string()));
+
+            if let Err(_error) = result {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] failed to load {MAGENTA}{path}{NONE}: {_error}");
+            }
+        }
+
         let job_id_function = r#"
 function get_job_name_from_id(id)
   for k,v in pairs(JTtbl) do
@@ -69,22 +97,23 @@ end
     }
 
     // TODO: move this to a different class that utilizes the script loader
-    /*pub fn get_item_name_from_id(&self, item_id: ItemId) -> String {
+    pub fn get_item_name_from_id(&self, item_id: ItemId, is_identified: bool) -> String {
         use mlua::prelude::*;
 
         let globals = self.state.globals();
+        let key = match is_identified {
+            true => "identifiedDisplayName",
+            false => "unidentifiedDisplayName",
+        };
 
         globals
             .get::<_, LuaTable>("tbl")
             .unwrap()
             .get::<_, LuaTable>(item_id.0)
-            .unwrap()
-            .get::<_, LuaString>("unidentifiedDisplayName")
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .to_owned()
-    }*/
+            .and_then(|table| table.get::<_, LuaString>(key))
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| format!("Unknown item {}", item_id.0))
+    }
 
     // TODO: move this to a different class that utilizes the script loader
     pub fn get_item_resource_from_id(&self, item_id: ItemId) -> String {
@@ -106,4 +135,129 @@ end
             })
             .unwrap_or_else(|_| "»ç°ú".to_owned())
     }
+
+    /// Get the display name, description and slot count of an item.
+    pub fn get_item_info(&self, item_id: ItemId, is_identified: bool) -> ItemInfo {
+        use mlua::prelude::*;
+
+        let (name_key, description_key) = match is_identified {
+            true => ("identifiedDisplayName", "identifiedDescriptionName"),
+            false => ("unidentifiedDisplayName", "unidentifiedDescriptionName"),
+        };
+
+        let Ok(table) = self
+            .state
+            .globals()
+            .get::<_, LuaTable>("tbl")
+            .and_then(|items| items.get::<_, LuaTable>(item_id.0))
+        else {
+            return ItemInfo {
+                name: format!("Unknown item {}", item_id.0),
+                ..Default::default()
+            };
+        };
+
+        let description = table
+            .get::<_, LuaTable>(description_key)
+            .map(|lines| info::get_lines(&lines, 0))
+            .unwrap_or_default();
+        let weight = info::get_weight(&description);
+
+        ItemInfo {
+            name: info::get_string(&table, name_key).unwrap_or_else(|| format!("Unknown item {}", item_id.0)),
+            description,
+            slot_count: table.get::<_, u8>("slotCount").unwrap_or_default(),
+            weight,
+        }
+    }
+
+    /// Get the display name and description of a skill. Falls back to the
+    /// resource name sent by the server if the skill is not in the client
+    /// tables.
+    pub fn get_skill_info(&self, skill_id: SkillId, skill_name: &str) -> SkillInfo {
+        use mlua::prelude::*;
+
+        let globals = self.state.globals();
+        let name = globals
+            .get::<_, LuaTable>("SKILL_INFO_LIST")
+            .and_then(|skills| skills.get::<_, LuaTable>(skill_id.0))
+            .ok()
+            .and_then(|table| info::get_string(&table, "SkillName"))
+            .unwrap_or_else(|| skill_name.to_owned());
+
+        // NOTE: The first line of a skill description is the name of the skill.
+        let description = globals
+            .get::<_, LuaTable>("SKILL_DESCRIPT")
+            .and_then(|skills| skills.get::<_, LuaTable>(skill_id.0))
+            .map(|lines| info::get_lines(&lines, 1))
+            .unwrap_or_default();
+
+        SkillInfo { name, description }
+    }
+
+    /// Get the title and summary of a quest. Quests that are not in the client
+    /// tables are shown by their id.
+    pub fn get_quest_info(&self, quest_id: u32) -> QuestInfo {
+        use mlua::prelude::*;
+
+        let table = self
+            .state
+            .globals()
+            .get::<_, LuaTable>("QuestInfoList")
+            .and_then(|quests| quests.get::<_, LuaTable>(quest_id))
+            .ok();
+
+        let title = table
+            .as_ref()
+            .and_then(|table| info::get_string(table, "Title"))
+            .unwrap_or_else(|| format!("Quest {quest_id}"));
+        let summary = table
+            .as_ref()
+            .and_then(|table| info::get_string(table, "Summary"))
+            .map(|summary| info::strip_color_codes(&summary))
+            .unwrap_or_default();
+
+        QuestInfo { title, summary }
+    }
+
+    /// Get the title, summary, category and objectives of an achievement.
+    pub fn get_achievement_info(&self, achievement_id: u32) -> AchievementInfo {
+        use mlua::prelude::*;
+
+        let Ok(table) = self
+            .state
+            .globals()
+            .get::<_, LuaTable>("achievement_tbl")
+            .and_then(|achievements| achievements.get::<_, LuaTable>(achievement_id))
+        else {
+            return AchievementInfo {
+                title: format!("Achievement {achievement_id}"),
+                ..Default::default()
+            };
+        };
+
+        let summary = table
+            .get::<_, LuaTable>("content")
+            .ok()
+            .and_then(|content| info::get_string(&content, "summary"))
+            .map(|summary| info::strip_color_codes(&summary))
+            .unwrap_or_default();
+        let objective_counts = table
+            .get::<_, LuaTable>("resource")
+            .map(|resources| {
+                resources
+                    .sequence_values::<LuaTable>()
+                    .filter_map(Result::ok)
+                    .filter_map(|resource| resource.get::<_, u32>("count").ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        AchievementInfo {
+            title: info::get_string(&table, "title").unwrap_or_else(|| format!("Achievement {achievement_id}")),
+            summary,
+            category: table.get::<_, u32>("major").unwrap_or_default(),
+            objective_counts,
+        }
+    }
 }
@@ -93,6 +93,12 @@ pub struct Service {
     /// login_athena.conf.
     pub version: i8,
 
+    /// Packet version of the server, in the same `YYYYMMDD` format as
+    /// **PACKETVER** on the server. Not part of the official format. If
+    /// missing, the newest supported packets are used.
+    #[serde(default, alias = "packetversion")]
+    pub packet_version: Option<u32>,
+
     /// Uses the same value from `ServiceType` enum.
     #[serde(default, alias = "langtype", deserialize_with = "language_type_from_index")]
     pub language_type: Option<ServiceType>,
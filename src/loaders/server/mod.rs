@@ -5,6 +5,8 @@ use serde_xml_rs::de::Deserializer;
 use xml::reader::{EventReader, ParserConfig};
 
 pub use self::client_info::ClientInfo;
+#[cfg(test)]
+pub use self::client_info::Service;
 use super::GameFileLoader;
 #[cfg(feature = "debug")]
 use crate::debug::*;
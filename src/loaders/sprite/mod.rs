@@ -158,7 +158,7 @@ pub struct SpriteLoader {
 }
 
 impl SpriteLoader {
-    fn load(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
+    fn load(&mut self, path: &str, palette_path: Option<&str>, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load sprite from {MAGENTA}{path}{NONE}"));
 
@@ -185,8 +185,17 @@ impl SpriteLoader {
         #[cfg(feature = "debug")]
         let cloned_sprite_data = sprite_data.clone();
 
-        let palette = sprite_data.palette.unwrap(); // unwrap_or_default() as soon as i know what
-        // the default palette is
+        // NOTE: External palettes are used to recolor sprites, for example the hair of
+        // players. If the palette can't be loaded, the one of the sprite is used
+        // instead.
+        let external_palette = palette_path.and_then(|palette_path| {
+            let bytes = game_file_loader.get(&format!("data\\palette\\{palette_path}")).ok()?;
+            let mut byte_stream: ByteStream<()> = ByteStream::without_metadata(&bytes);
+            Palette::from_bytes(&mut byte_stream).ok()
+        });
+
+        // TODO: unwrap_or_default() as soon as i know what the default palette is
+        let palette = external_palette.unwrap_or_else(|| sprite_data.palette.unwrap());
 
         let rgba_images/*: Vec<Arc<ImmutableImage>>*/ = sprite_data
             .rgba_image_data
@@ -260,7 +269,7 @@ impl SpriteLoader {
             sprite_data: cloned_sprite_data,
         });
 
-        self.cache.insert(path.to_string(), sprite.clone());
+        self.cache.insert(Self::cache_key(path, palette_path), sprite.clone());
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -268,10 +277,31 @@ impl SpriteLoader {
         Ok(sprite)
     }
 
+    fn cache_key(path: &str, palette_path: Option<&str>) -> String {
+        match palette_path {
+            Some(palette_path) => format!("{path}#{palette_path}"),
+            None => path.to_owned(),
+        }
+    }
+
     pub fn get(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<Sprite>, String> {
         match self.cache.get(path) {
             Some(sprite) => Ok(sprite.clone()),
-            None => self.load(path, game_file_loader),
+            None => self.load(path, None, game_file_loader),
+        }
+    }
+
+    /// Get a sprite with the colors of the palette at the given path instead
+    /// of its own.
+    pub fn get_with_palette(
+        &mut self,
+        path: &str,
+        palette_path: &str,
+        game_file_loader: &mut GameFileLoader,
+    ) -> Result<Arc<Sprite>, String> {
+        match self.cache.get(&Self::cache_key(path, Some(palette_path))) {
+            Some(sprite) => Ok(sprite.clone()),
+            None => self.load(path, Some(palette_path), game_file_loader),
         }
     }
 
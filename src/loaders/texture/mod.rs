@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use derive_new::new;
 use image::io::Reader as ImageReader;
-use image::{EncodableLayout, ImageFormat, Rgba};
+use image::{EncodableLayout, ImageFormat, Rgba, RgbaImage};
 use vulkano::buffer::{Buffer, BufferCreateInfo, BufferUsage};
 use vulkano::command_buffer::{
     AutoCommandBufferBuilder, CommandBufferUsage, CopyBufferToImageInfo, PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract,
@@ -16,13 +16,19 @@ use vulkano::image::{Image, ImageCreateInfo, ImageUsage};
 use vulkano::memory::allocator::{AllocationCreateInfo, MemoryTypeFilter};
 use vulkano::sync::future::FenceSignalFuture;
 use vulkano::sync::GpuFuture;
+use yazi::{decompress, Format};
 
 use super::{FALLBACK_BMP_FILE, FALLBACK_PNG_FILE, FALLBACK_TGA_FILE};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::graphics::MemoryAllocator;
+#[cfg(feature = "debug")]
+use crate::graphics::{track_texture, CacheResidencyScope};
+use crate::loaders::cache::{cache_key, load_cached, store_cached, CacheReader, CacheWriter};
 use crate::loaders::GameFileLoader;
 
+const CACHE_KIND: &str = "texture";
+
 #[derive(new)]
 pub struct TextureLoader {
     memory_allocator: Arc<MemoryAllocator>,
@@ -46,6 +52,17 @@ impl TextureLoader {
         };
 
         let file_data = game_file_loader.get(&format!("data\\texture\\{path}"))?;
+        let key = cache_key(&[&file_data]);
+
+        if let Some(image_buffer) = load_cached(CACHE_KIND, key).and_then(Self::read_cached) {
+            let texture = self.upload(path, image_buffer);
+
+            #[cfg(feature = "debug")]
+            timer.stop();
+
+            return Ok(texture);
+        }
+
         let reader = ImageReader::with_format(Cursor::new(file_data), image_format);
 
         let mut image_buffer = match reader.decode() {
@@ -69,13 +86,41 @@ impl TextureLoader {
         };
 
         if image_format == ImageFormat::Bmp {
-            // These numbers are taken from https://github.com/Duckwhale/RagnarokFileFormats
-            image_buffer
-                .pixels_mut()
-                .filter(|pixel| pixel.0[0] > 0xF0 && pixel.0[1] < 0x10 && pixel.0[2] > 0x0F)
-                .for_each(|pixel| *pixel = Rgba([0; 4]));
+            Self::remove_magenta(&mut image_buffer);
         }
 
+        let mut writer = CacheWriter::new();
+        writer.write_u32(image_buffer.width());
+        writer.write_u32(image_buffer.height());
+        writer.write_slice(image_buffer.as_raw());
+        store_cached(CACHE_KIND, key, writer);
+
+        let texture = self.upload(path, image_buffer);
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(texture)
+    }
+
+    /// Bitmaps use magenta for transparent pixels.
+    fn remove_magenta(image_buffer: &mut RgbaImage) {
+        // These numbers are taken from https://github.com/Duckwhale/RagnarokFileFormats
+        image_buffer
+            .pixels_mut()
+            .filter(|pixel| pixel.0[0] > 0xF0 && pixel.0[1] < 0x10 && pixel.0[2] > 0x0F)
+            .for_each(|pixel| *pixel = Rgba([0; 4]));
+    }
+
+    fn read_cached(mut reader: CacheReader) -> Option<RgbaImage> {
+        let width = reader.read_u32()?;
+        let height = reader.read_u32()?;
+        let pixels = reader.read_vec::<u8>()?;
+
+        RgbaImage::from_raw(width, height, pixels)
+    }
+
+    fn upload(&mut self, path: &str, image_buffer: RgbaImage) -> Arc<ImageView> {
         let load_buffer = self.load_buffer.get_or_insert_with(|| {
             AutoCommandBufferBuilder::primary(
                 &*self.memory_allocator,
@@ -119,9 +164,12 @@ impl TextureLoader {
         self.cache.insert(path.to_string(), texture.clone());
 
         #[cfg(feature = "debug")]
-        timer.stop();
+        {
+            let _cache_scope = CacheResidencyScope::enter();
+            track_texture(path, &texture);
+        }
 
-        Ok(texture)
+        texture
     }
 
     pub fn get(&mut self, path: &str, game_file_loader: &mut GameFileLoader) -> Result<Arc<ImageView>, String> {
@@ -131,6 +179,25 @@ impl TextureLoader {
         }
     }
 
+    /// Guild emblems are sent by the map server as zlib compressed bitmaps.
+    pub fn load_guild_emblem(&mut self, guild_id: u32, emblem_id: u32, emblem_data: &[u8]) -> Result<Arc<ImageView>, String> {
+        let path = format!("guild_emblem_{guild_id}_{emblem_id}");
+
+        if let Some(texture) = self.cache.get(&path) {
+            return Ok(texture.clone());
+        }
+
+        let (data, _checksum) =
+            decompress(emblem_data, Format::Zlib).map_err(|error| format!("failed to decompress guild emblem: {error:?}"))?;
+        let mut image_buffer = image::load_from_memory(&data)
+            .map_err(|error| format!("failed to decode guild emblem: {error}"))?
+            .to_rgba8();
+
+        Self::remove_magenta(&mut image_buffer);
+
+        Ok(self.upload(&path, image_buffer))
+    }
+
     pub fn submit_load_buffer(&mut self) -> Option<FenceSignalFuture<Box<dyn GpuFuture>>> {
         self.load_buffer.take().map(|buffer| {
             buffer
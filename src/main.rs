@@ -22,6 +22,7 @@
 #[cfg(feature = "debug")]
 #[macro_use]
 mod debug;
+mod audio;
 mod input;
 #[macro_use]
 mod system;
@@ -32,12 +33,14 @@ mod loaders;
 mod network;
 mod world;
 
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::io::Cursor;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use cgmath::{Vector2, Vector3, Zero};
+use cgmath::{EuclideanSpace, Vector2, Vector3, Zero};
 use image::io::Reader as ImageReader;
 use image::{EncodableLayout, ImageFormat};
 use network::SkillType;
@@ -54,21 +57,33 @@ use winit::event::{Event, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Icon, WindowBuilder};
 
+use crate::audio::{AudioEngine, AudioSettings};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::graphics::*;
-use crate::input::{FocusState, InputSystem, UserEvent};
+use crate::input::{EventSource, FocusState, InputSystem, UserEvent, UserEventQueue};
 use crate::interface::*;
-use crate::inventory::{Hotbar, Inventory, SkillTree};
+use crate::inventory::{
+    Achievements, Bank, Cart, CharacterStats, EquipmentSets, Hotbar, HotbarEntry, Inventory, InventoryView, Mailbox, PendingMail, QuestLog,
+    SessionStatistics, Shop, SkillTree, Trade, Vendor,
+};
 use crate::loaders::*;
-use crate::network::{ChatMessage, NetworkEvent, NetworkingSystem, SkillId, UnitId};
-use crate::system::{choose_physical_device, get_device_extensions, get_layers, GameTimer};
+use crate::network::{
+    AccountId, CharacterDeletionVersion, ChatMessage, EntityId, EquipPosition, NetworkEvent, NetworkingSystem, OnlineState, ShopDealType,
+    SkillId, SkillTarget, UnitId,
+};
+use crate::system::{
+    choose_physical_device, get_device_extensions, get_layers, AwaySettings, Benchmark, BenchmarkSettings, GameTimer, IdleState,
+    ShutdownReason, ShutdownSignal, UpdateCheck, UpdateSettings, VERSION,
+};
 use crate::world::*;
 
 const ROLLING_CUTTER_ID: SkillId = SkillId(2036);
 
 fn main() {
     const DEFAULT_MAP: &str = "geffen";
+    // NOTE: This is the tile the start camera is focused on.
+    const CHARACTER_PREVIEW_POSITION: Vector2<usize> = Vector2::new(120, 48);
 
     // We start a frame so that functions trying to start a measurement don't panic.
     #[cfg(feature = "debug")]
@@ -189,6 +204,7 @@ fn main() {
     let mut game_file_loader = GameFileLoader::default();
 
     game_file_loader.load_archives_from_settings();
+    game_file_loader.apply_patches();
     game_file_loader.load_patched_lua_files();
 
     let memory_allocator = Arc::new(MemoryAllocator::new(device.clone()));
@@ -203,6 +219,7 @@ fn main() {
     let mut model_loader = ModelLoader::new();
     let mut texture_loader = TextureLoader::new(memory_allocator.clone(), queue.clone());
     let mut map_loader = MapLoader::new();
+    let mut map_preloader = MapPreloader::new();
     let mut sprite_loader = SpriteLoader::new(memory_allocator.clone(), queue.clone());
     let mut action_loader = ActionLoader::default();
     let mut effect_loader = EffectLoader::default();
@@ -224,6 +241,9 @@ fn main() {
         )
         .expect("failed to load initial map");
 
+    #[cfg(feature = "debug")]
+    report_residency_after_map_change(DEFAULT_MAP);
+
     #[cfg(feature = "debug")]
     timer.stop();
 
@@ -267,7 +287,12 @@ fn main() {
         swapchain_holder.window_size_u32(),
     );
 
-    let shadow_renderer = ShadowRenderer::new(memory_allocator, &mut game_file_loader, &mut texture_loader, queue);
+    let shadow_renderer = ShadowRenderer::new(
+        memory_allocator.clone(),
+        &mut game_file_loader,
+        &mut texture_loader,
+        queue.clone(),
+    );
 
     #[cfg(feature = "debug")]
     timer.stop();
@@ -277,13 +302,49 @@ fn main() {
 
     let mut input_system = InputSystem::new();
     let mut graphics_settings = GraphicsSettings::new();
+    let mut audio_settings = AudioSettings::new();
+    let loot_filter = LootFilter::new();
     #[cfg(feature = "debug")]
     let mut render_settings = RenderSettings::new();
+    #[cfg(feature = "debug")]
+    let mut capture_frame = false;
 
     #[cfg(feature = "debug")]
     timer.stop();
 
+    deferred_renderer.set_sprite_filtering(graphics_settings.sprite_filtering);
+    interface_renderer.set_sprite_filtering(graphics_settings.sprite_filtering);
+    deferred_renderer.set_sampler_configuration(&graphics_settings.sampler_configuration);
+    interface_renderer.set_sampler_configuration(&graphics_settings.sampler_configuration);
+
+    let mut frame_limit = Remote::new(graphics_settings.frame_limit);
     let mut shadow_detail = Remote::new(graphics_settings.shadow_detail);
+    let mut speech_bubble_range = Remote::new(graphics_settings.speech_bubble_range);
+    let mut entity_render_distance = Remote::new(graphics_settings.entity_render_distance);
+    let mut maximum_rendered_entities = Remote::new(graphics_settings.maximum_rendered_entities);
+    let mut sprite_filtering = Remote::new(graphics_settings.sprite_filtering);
+    let mut sampler_configuration = Remote::new(graphics_settings.sampler_configuration);
+    let mut show_speech_bubbles = Remote::new(graphics_settings.show_speech_bubbles);
+    let mut show_path_preview = Remote::new(graphics_settings.show_path_preview);
+    let mut show_quest_markers = Remote::new(graphics_settings.show_quest_markers);
+    let mut preload_adjacent_maps = Remote::new(graphics_settings.preload_adjacent_maps);
+    let mut camera_shake = Remote::new(graphics_settings.camera_shake);
+    let mut hit_stop = Remote::new(graphics_settings.hit_stop);
+    let mut cast_previews = Remote::new(graphics_settings.cast_previews);
+    let mut target_priority = Remote::new(graphics_settings.target_priority.clone());
+
+    let mut away_settings = AwaySettings::new();
+    let mut away_timeout = Remote::new(away_settings.away_timeout);
+    let mut sit_while_away = Remote::new(away_settings.sit_while_away);
+
+    let mut update_settings = UpdateSettings::new();
+    let mut check_for_updates = Remote::new(update_settings.check_for_updates);
+    let mut update_check = update_settings
+        .check_for_updates
+        .then(|| UpdateCheck::start(update_settings.release_url.clone()));
+    let mut mute = Remote::new(audio_settings.mute);
+    let mut background_music_volume = Remote::new(audio_settings.background_music_volume);
+    let mut sound_effect_volume = Remote::new(audio_settings.sound_effect_volume);
 
     #[cfg(feature = "debug")]
     let timer = Timer::new("create render targets");
@@ -308,6 +369,14 @@ fn main() {
         .map(|_| shadow_renderer.create_render_target(shadow_detail.get().into_resolution()))
         .collect::<Vec<<ShadowRenderer as Renderer>::Target>>();
 
+    let mut entity_shadow_targets = swapchain_holder
+        .get_swapchain_images()
+        .into_iter()
+        .map(|_| shadow_renderer.create_render_target(shadow_detail.get().into_resolution()))
+        .collect::<Vec<<ShadowRenderer as Renderer>::Target>>();
+
+    let mut shadow_cache = ShadowCache::new(directional_shadow_targets.len());
+
     #[cfg(feature = "debug")]
     timer.stop();
 
@@ -320,7 +389,11 @@ fn main() {
         &mut action_loader,
         swapchain_holder.window_screen_size(),
     );
+    let mut animations = Remote::new(interface.get_animations());
+    let mut streamer_mode = Remote::new(interface.get_streamer_mode().is_enabled());
+    let mut combat_window_mode = Remote::new(interface.get_combat_window_mode());
     let mut focus_state = FocusState::default();
+    let mut user_event_queue = UserEventQueue::default();
 
     #[cfg(feature = "debug")]
     timer.stop();
@@ -330,6 +403,26 @@ fn main() {
 
     let mut game_timer = GameTimer::new();
 
+    let mut benchmark = BenchmarkSettings::from_arguments(std::env::args().skip(1)).map(|settings| {
+        Benchmark::new(
+            settings,
+            &mut game_file_loader,
+            &mut sprite_loader,
+            &mut action_loader,
+            &script_loader,
+            &map,
+            DEFAULT_MAP.to_string(),
+            CHARACTER_PREVIEW_POSITION,
+            game_timer.get_client_tick(),
+        )
+    });
+
+    // NOTE: The frame limit would cap the measured frame rate, so it is always
+    // disabled while benchmarking.
+    if benchmark.is_some() {
+        swapchain_holder.set_frame_limit(present_mode_info, false);
+    }
+
     #[cfg(feature = "debug")]
     timer.stop();
 
@@ -359,28 +452,72 @@ fn main() {
     #[cfg(feature = "debug")]
     timer.stop();
 
+    #[cfg(feature = "debug")]
+    let timer = Timer::new("initialize audio");
+
+    let mut audio_engine = AudioEngine::new(&mut game_file_loader);
+    audio_engine.set_mute(audio_settings.mute);
+    audio_engine.set_background_music_volume(audio_settings.background_music_volume);
+    audio_engine.set_sound_effect_volume(audio_settings.sound_effect_volume);
+    audio_engine.play_background_music(DEFAULT_MAP, &mut game_file_loader);
+    map.register_sound_sources(&mut audio_engine, &mut game_file_loader);
+
+    #[cfg(feature = "debug")]
+    timer.stop();
+
     let mut particle_holder = ParticleHolder::default();
+    map.register_effect_sources(&mut particle_holder, &mut game_file_loader, &mut texture_loader);
+    let mut ping_holder = PingHolder::default();
+    let mut cast_preview_holder = CastPreviewHolder::default();
+    let mut ground_item_holder = GroundItemHolder::default();
+    let mut entity_search = EntitySearch::default();
+    let inventory_view = InventoryView::default();
+    let mut target_stack = TargetStack::default();
+    let mut character_preview: Option<Entity> = None;
+    let mut character_appearance = TrackedState::<Option<CharacterAppearance>>::default();
+    let mut path_preview: Option<(Vector2<usize>, Vector2<usize>, Vec<Vector2<usize>>)> = None;
     let mut effect_holder = EffectHolder::default();
+    let mut screen_effects = ScreenEffects::default();
     let mut entities = Vec::<Entity>::new();
+    let mut entity_name_cache = EntityNameCache::default();
     let mut player_inventory = Inventory::default();
     let mut player_skill_tree = SkillTree::default();
+    let mut session_statistics = SessionStatistics::default();
+    let mut character_stats = CharacterStats::default();
+    let mut equipment_sets = EquipmentSets::default();
+    let mut trade = Trade::default();
+    let mut shop = Shop::default();
+    let mut is_vending_setup = false;
+    let mut is_vending = false;
+    let mut cart = Cart::default();
+    let mut mailbox = Mailbox::default();
+    let mut quest_log = QuestLog::default();
+    let mut achievements = Achievements::default();
+    let mut bank = Bank::default();
+    let mut combat_timer = CombatTimer::default();
     let mut hotbar = Hotbar::default();
+    let mut sat_down_while_away = false;
 
     let welcome_string = format!(
         "Welcome to ^ffff00★^000000 ^ff8800Korangar^000000 ^ffff00★^000000 version ^ff8800{}^000000!",
-        env!("CARGO_PKG_VERSION")
+        VERSION
     );
     let welcome_message = ChatMessage::new(welcome_string, Color::monochrome_u8(255));
-    let mut chat_messages = TrackedState::new(vec![welcome_message]);
+    let mut chat_history = ChatMessage::load_history();
+    chat_history.push(welcome_message);
+    let mut chat_messages = TrackedState::new(chat_history);
 
     let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+    let mut ime_allowed = false;
+    let shutdown_signal = ShutdownSignal::register();
+    let mut idle_state = IdleState::new();
 
     event_loop.run(move |event, _, control_flow| {
         match event {
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
-            } => control_flow.set_exit(),
+            } => shutdown_signal.request(ShutdownReason::WindowClosed),
             Event::WindowEvent {
                 event: WindowEvent::Resized(_),
                 ..
@@ -391,6 +528,8 @@ fn main() {
                     .downcast_ref::<winit::window::Window>()
                     .unwrap()
                     .inner_size();
+                idle_state.set_minimized(window_size.width == 0 || window_size.height == 0);
+                particle_holder.pause_emitters(idle_state.is_idle());
                 interface.update_window_size(ScreenSize {
                     width: window_size.width as f32,
                     height: window_size.height as f32,
@@ -401,6 +540,9 @@ fn main() {
                 event: WindowEvent::Focused(focused),
                 ..
             } => {
+                idle_state.set_focused(focused);
+                particle_holder.pause_emitters(idle_state.is_idle());
+
                 if !focused {
                     input_system.reset();
                     focus_state.remove_focus();
@@ -438,7 +580,34 @@ fn main() {
                 event: WindowEvent::ReceivedCharacter(character),
                 ..
             } => input_system.buffer_character(character),
+            Event::WindowEvent {
+                event: WindowEvent::Ime(ime),
+                ..
+            } => input_system.update_ime(ime),
             Event::MainEventsCleared => {
+                if let Some(_reason) = shutdown_signal.requested() {
+                    #[cfg(feature = "debug")]
+                    print_debug!("shutting down ({MAGENTA}{_reason}{NONE})");
+
+                    interface.save_state();
+                    graphics_settings.save();
+                    audio_settings.save();
+                    away_settings.save();
+                    update_settings.save();
+
+                    ChatMessage::save_history(&chat_messages.borrow());
+                    networking_system.disconnect();
+
+                    // NOTE: Resources must not be destroyed while the GPU is still using them.
+                    if let Err(_error) = unsafe { device.wait_idle() } {
+                        #[cfg(feature = "debug")]
+                        print_debug!("[{RED}error{NONE}] failed to wait for the device to become idle: {_error}");
+                    }
+
+                    *control_flow = ControlFlow::Exit;
+                    return;
+                }
+
                 #[cfg(feature = "debug")]
                 let _measurement = profiler_start_main_thread();
 
@@ -458,24 +627,45 @@ fn main() {
                 networking_system.keep_alive(delta_time, client_tick);
                 let network_events = networking_system.network_events();
 
-                let (user_events, hovered_element, focused_element, mouse_target) = input_system.user_events(
+                let (hovered_element, focused_element, mouse_target) = input_system.user_events(
                     &mut interface,
+                    &mut user_event_queue,
                     &mut focus_state,
                     &mut picker_targets[swapchain_holder.get_image_number()],
+                    target_stack.selected(),
                     #[cfg(feature = "debug")]
                     &render_settings,
                     swapchain_holder.window_size(),
                     client_tick,
                 );
 
+                // NOTE: Input methods capture key presses, so they are only enabled while an
+                // element is focused. Otherwise they would swallow hotkeys.
+                let ime_should_be_allowed = focus_state.get_focused_element().is_some();
+                if ime_should_be_allowed != ime_allowed {
+                    surface
+                        .object()
+                        .unwrap()
+                        .downcast_ref::<winit::window::Window>()
+                        .unwrap()
+                        .set_ime_allowed(ime_should_be_allowed);
+                    ime_allowed = ime_should_be_allowed;
+                }
+
                 #[cfg(feature = "debug")]
                 let picker_measuremen = start_measurement("update picker target");
 
-                if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
+                target_stack.update(
+                    mouse_target.as_ref(),
+                    &entities,
+                    &ground_item_holder,
+                    &graphics_settings.target_priority,
+                );
+
+                if let Some(StackedTarget::Entity(entity_id)) = target_stack.selected() {
                     if let Some(entity) = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id) {
-                        if entity.are_details_unavailable() {
-                            networking_system.request_entity_details(entity_id);
-                            entity.set_details_requested();
+                        if entity.get_details().is_none() {
+                            entity_name_cache.request(entity_id);
                         }
 
                         match entity.get_entity_type() {
@@ -487,6 +677,20 @@ fn main() {
                     }
                 }
 
+                // NOTE: The entity search matches by name, so we need the details of every
+                // entity while it is active.
+                if entity_search.get_filter().is_some() {
+                    entities
+                        .iter_mut()
+                        .skip(1)
+                        .filter(|entity| entity.get_details().is_none())
+                        .for_each(|entity| entity_name_cache.request(entity.get_entity_id()));
+                }
+
+                for entity_id in entity_name_cache.take_requests(client_tick) {
+                    networking_system.request_entity_details(entity_id);
+                }
+
                 #[cfg(feature = "debug")]
                 picker_measuremen.stop();
 
@@ -501,6 +705,10 @@ fn main() {
                             // to prevent this we remove the old entity.
                             entities.retain(|entity| entity.get_entity_id() != entity_appeared_data.entity_id);
 
+                            if let Some(guild_id) = entity_appeared_data.guild_id {
+                                networking_system.request_guild_emblem(guild_id, Some(entity_appeared_data.emblem_id));
+                            }
+
                             let npc = Npc::new(
                                 &mut game_file_loader,
                                 &mut sprite_loader,
@@ -511,7 +719,12 @@ fn main() {
                                 client_tick,
                             );
 
-                            let npc = Entity::Npc(npc);
+                            let mut npc = Entity::Npc(npc);
+
+                            if let Some(name) = entity_name_cache.get(npc.get_entity_id()) {
+                                npc.set_details(name.clone());
+                            }
+
                             entities.push(npc);
                         }
                         NetworkEvent::RemoveEntity(entity_id) => {
@@ -527,17 +740,28 @@ fn main() {
                             }
                         }
                         NetworkEvent::PlayerMove(position_from, position_to, starting_timestamp) => {
-                            entities[0].move_from_to(&map, position_from, position_to, starting_timestamp);
+                            entities[0].confirm_move(&map, position_from, position_to, starting_timestamp);
 
                             /*#[cfg(feature = "debug")]
                             entities[0].generate_steps_vertex_buffer(device.clone(), &map);*/
                         }
                         NetworkEvent::ChangeMap(map_name, player_position) => {
+                            let player_grid_position = entities[0].get_grid_position();
+                            let entered_through_warp = entities.iter().skip(1).any(|entity| {
+                                entity.get_entity_type() == EntityType::Warp
+                                    && MapPreloader::is_in_warp_range(player_grid_position, entity.get_grid_position())
+                            });
+
                             entities.truncate(1);
+                            entity_name_cache.clear();
+
+                            audio_engine.play_background_music(&map_name, &mut game_file_loader);
+                            map_preloader.set_current_map(&map_name, entered_through_warp);
+                            shadow_cache.invalidate();
 
                             map = map_loader
                                 .get(
-                                    map_name,
+                                    map_name.clone(),
                                     &mut game_file_loader,
                                     &mut buffer_allocator,
                                     &mut model_loader,
@@ -545,11 +769,34 @@ fn main() {
                                 )
                                 .unwrap();
 
+                            #[cfg(feature = "debug")]
+                            report_residency_after_map_change(&map_name);
+
                             entities[0].set_position(&map, player_position, client_tick);
                             player_camera.set_focus_point(entities[0].get_position());
 
                             particle_holder.clear();
                             effect_holder.clear();
+                            screen_effects.clear();
+                            ping_holder.clear();
+                            cast_preview_holder.clear();
+                            ground_item_holder.clear();
+                            path_preview = None;
+                            audio_engine.stop_all_sounds();
+                            map.register_sound_sources(&mut audio_engine, &mut game_file_loader);
+                            map.register_effect_sources(&mut particle_holder, &mut game_file_loader, &mut texture_loader);
+
+                            if let Some(navigation) = quest_log.get_navigation()
+                                && navigation.map_name == map_name
+                            {
+                                particle_holder.set_navigation_marker(
+                                    &mut game_file_loader,
+                                    &mut texture_loader,
+                                    &map,
+                                    navigation.position,
+                                );
+                            }
+
                             networking_system.map_loaded();
                             // TODO: this is just a workaround until i find a better solution to make the
                             // cursor always look correct.
@@ -565,17 +812,86 @@ fn main() {
                         NetworkEvent::ChatMessage(message) => {
                             chat_messages.push(message);
                         }
+                        NetworkEvent::EntityMessage(entity_id, message) => {
+                            let message = interface.get_streamer_mode().hide_sender(&message);
+                            particle_holder.add_speech_bubble(entity_id, &message, &interface.get_game_theme().speech_bubble);
+                        }
+                        NetworkEvent::PartyPing { sender, position } => {
+                            ping_holder.add_ping(&sender, position, interface.get_streamer_mode())
+                        }
+                        NetworkEvent::AddGroundItem {
+                            entity_id,
+                            item_id,
+                            position,
+                            amount,
+                            is_identified,
+                            rarity,
+                            dropped,
+                        } => {
+                            let name = script_loader.get_item_name_from_id(item_id, is_identified);
+                            let highlighted = loot_filter.matches(item_id, rarity);
+
+                            // NOTE: Items that are already on the ground when entering the map are only
+                            // highlighted, so we don't get flooded with notifications.
+                            if highlighted && dropped {
+                                if let Some(sound_effect) = &loot_filter.sound_effect
+                                    && let Ok(sound_effect) = audio_engine.get_sound_effect(sound_effect, &mut game_file_loader)
+                                {
+                                    audio_engine.play_sound_effect(sound_effect, 1.0);
+                                }
+
+                                if loot_filter.show_toast {
+                                    ground_item_holder.add_toast(format!("{name} dropped"));
+                                }
+                            }
+
+                            ground_item_holder.add_item(entity_id, name, amount, position, highlighted);
+                        }
+                        NetworkEvent::RemoveGroundItem(entity_id) => ground_item_holder.remove_item(entity_id),
                         NetworkEvent::UpdateEntityDetails(entity_id, name) => {
                             let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
 
                             if let Some(entity) = entity {
-                                entity.set_details(name);
+                                entity.set_details(name.clone());
                             }
+
+                            entity_name_cache.insert(entity_id, name);
                         }
-                        NetworkEvent::DamageEffect(entity_id, damage_amount) => {
+                        NetworkEvent::DamageEffect {
+                            source_entity_id,
+                            destination_entity_id,
+                            damage_amount,
+                            is_critical,
+                        } => {
+                            let player_entity_id = entities[0].get_entity_id();
+                            let involves_player = source_entity_id == player_entity_id || destination_entity_id == player_entity_id;
+
+                            // NOTE: Hits that take a large part of the players health are usually boss
+                            // skills, which we can't tell apart from other skills otherwise.
+                            let heavy_hit = destination_entity_id == player_entity_id
+                                && damage_amount * 5 >= entities[0].get_maximum_health_points().max(1);
+
+                            if (is_critical && involves_player) || heavy_hit {
+                                screen_effects.add_shake(if heavy_hit { 0.8 } else { 0.4 });
+
+                                if graphics_settings.hit_stop {
+                                    screen_effects.add_hit_stop(0.06);
+                                }
+                            }
+
+                            if involves_player && combat_timer.engage() {
+                                interface.set_in_combat(true);
+                            }
+
+                            if source_entity_id == player_entity_id {
+                                session_statistics.add_damage_dealt(damage_amount);
+                            } else if destination_entity_id == player_entity_id {
+                                session_statistics.add_damage_taken(damage_amount);
+                            }
+
                             let entity = entities
                                 .iter()
-                                .find(|entity| entity.get_entity_id() == entity_id)
+                                .find(|entity| entity.get_entity_id() == destination_entity_id)
                                 .unwrap_or(&entities[0]);
 
                             particle_holder.spawn_particle(Box::new(DamageNumber::new(entity.get_position(), damage_amount.to_string())));
@@ -600,6 +916,8 @@ fn main() {
                                 panic!();
                             };
 
+                            session_statistics.update_status(&status_type);
+                            character_stats.update_status(&status_type);
                             player.update_status(status_type);
                         }
                         NetworkEvent::OpenDialog(text, npc_id) => interface.open_dialog_window(&mut focus_state, text, npc_id),
@@ -612,24 +930,50 @@ fn main() {
                         NetworkEvent::RemoveQuestEffect(entity_id) => particle_holder.remove_quest_icon(entity_id),
                         NetworkEvent::Inventory(item_data) => {
                             player_inventory.fill(&mut game_file_loader, &mut texture_loader, &script_loader, item_data);
+                            hotbar.resolve(&player_skill_tree, &player_inventory);
                         }
-                        NetworkEvent::AddIventoryItem(item_index, item_data, equip_position, equipped_position) => {
-                            player_inventory.add_item(
+                        NetworkEvent::AddIventoryItem(item_data) => {
+                            player_inventory.add_item(&mut game_file_loader, &mut texture_loader, &script_loader, item_data);
+                        }
+                        NetworkEvent::LootedItem { amount } => session_statistics.add_looted_items(amount as usize),
+                        NetworkEvent::RemoveInventoryItem { index, amount } => player_inventory.remove_item(index, amount),
+                        NetworkEvent::Cart(item_data) => cart.fill(&mut game_file_loader, &mut texture_loader, &script_loader, item_data),
+                        NetworkEvent::AddCartItem(item_data) => {
+                            cart.add_item(&mut game_file_loader, &mut texture_loader, &script_loader, item_data)
+                        }
+                        NetworkEvent::RemoveCartItem { index, amount } => cart.remove_item(index, amount),
+                        NetworkEvent::UpdateCartInformation(information) => cart.update_information(information),
+                        NetworkEvent::CartRemoved => cart.clear(),
+                        NetworkEvent::SkillTree(skill_information) => {
+                            player_skill_tree.fill(
                                 &mut game_file_loader,
-                                &mut texture_loader,
+                                &mut sprite_loader,
+                                &mut action_loader,
                                 &script_loader,
-                                item_index,
-                                item_data,
-                                equip_position,
-                                equipped_position,
+                                skill_information,
                             );
+
+                            hotbar.resolve(&player_skill_tree, &player_inventory);
                         }
-                        NetworkEvent::SkillTree(skill_information) => {
-                            player_skill_tree.fill(&mut game_file_loader, &mut sprite_loader, &mut action_loader, skill_information);
-                        }
+                        NetworkEvent::SetHotkeys(hotkeys) => hotbar.set_hotkeys(hotkeys, &player_skill_tree, &player_inventory),
+                        NetworkEvent::SkillCooldown(skill_id, duration) => hotbar.start_cooldown(skill_id, duration),
                         NetworkEvent::UpdateEquippedPosition { index, equipped_position } => {
                             player_inventory.update_equipped_position(index, equipped_position);
                         }
+                        NetworkEvent::UpdateItemFavorite { index, is_favorite } => {
+                            player_inventory.update_favorite(index, is_favorite);
+                        }
+                        NetworkEvent::GuildEmblem {
+                            guild_id,
+                            emblem_id,
+                            emblem_data,
+                        } => match texture_loader.load_guild_emblem(guild_id, emblem_id, &emblem_data) {
+                            Ok(texture) => networking_system.set_guild_emblem(guild_id, emblem_id, texture),
+                            Err(_error) => {
+                                #[cfg(feature = "debug")]
+                                print_debug!("[{RED}error{NONE}] failed to load guild emblem: {_error}");
+                            }
+                        },
                         NetworkEvent::ChangeJob(account_id, job_id) => {
                             let entity = entities.iter_mut().find(|entity| entity.get_entity_id().0 == account_id.0).unwrap();
 
@@ -638,9 +982,17 @@ fn main() {
                         }
                         NetworkEvent::Disconnect => {
                             networking_system.disconnect_from_map_server();
+                            hotbar.clear();
                             entities.clear();
+                            entity_name_cache.clear();
                             particle_holder.clear();
                             effect_holder.clear();
+                            screen_effects.clear();
+                            ping_holder.clear();
+                            ground_item_holder.clear();
+                            audio_engine.stop_all_sounds();
+                            audio_engine.play_background_music(DEFAULT_MAP, &mut game_file_loader);
+                            shadow_cache.invalidate();
 
                             map = map_loader
                                 .get(
@@ -652,6 +1004,11 @@ fn main() {
                                 )
                                 .expect("failed to load initial map");
 
+                            #[cfg(feature = "debug")]
+                            report_residency_after_map_change(DEFAULT_MAP);
+
+                            map.register_sound_sources(&mut audio_engine, &mut game_file_loader);
+                            map.register_effect_sources(&mut particle_holder, &mut game_file_loader, &mut texture_loader);
                             interface.close_all_windows_except(&mut focus_state);
 
                             let character_selection_window = networking_system.character_selection_window();
@@ -661,6 +1018,186 @@ fn main() {
                             directional_shadow_camera.set_focus_point(cgmath::Point3::new(600.0, 0.0, 240.0));
                         }
                         NetworkEvent::FriendRequest(friend) => interface.open_window(&mut focus_state, &FriendRequestWindow::new(friend)),
+                        NetworkEvent::TradeRequest { name, base_level } => {
+                            trade.set_partner_name(name.clone());
+                            interface.open_window(&mut focus_state, &TradeRequestWindow::new(name, base_level));
+                        }
+                        NetworkEvent::TradeStarted => {
+                            trade.start();
+                            interface.open_window(&mut focus_state, &TradeWindow::new(trade.get_data()));
+                        }
+                        NetworkEvent::TradeItemAdded(item_data) => {
+                            trade.add_partner_item(&mut game_file_loader, &mut texture_loader, &script_loader, item_data);
+                        }
+                        NetworkEvent::TradeZenyAdded(amount) => trade.set_partner_zeny(amount),
+                        NetworkEvent::TradeOfferResult { index, success } => trade.resolve_offer(index, success),
+                        NetworkEvent::TradeLocked { partner } => trade.lock(partner),
+                        NetworkEvent::TradeCanceled | NetworkEvent::TradeCompleted => {
+                            trade.clear();
+                            interface.close_window_with_class(&mut focus_state, TradeWindow::WINDOW_CLASS);
+                        }
+                        NetworkEvent::ShopDealType(npc_id) => {
+                            interface.open_window(&mut focus_state, &ShopDealWindow::new(npc_id));
+                        }
+                        NetworkEvent::ShopBuyList(items) => {
+                            shop.open_buy_list(&mut game_file_loader, &mut texture_loader, &script_loader, None, items);
+                            interface.open_window(&mut focus_state, &ShopWindow::new(shop.get_data(), character_stats.get_data()));
+                        }
+                        NetworkEvent::ShopSellList(items) => {
+                            shop.open_sell_list(&player_inventory, items);
+                            interface.open_window(&mut focus_state, &ShopWindow::new(shop.get_data(), character_stats.get_data()));
+                        }
+                        NetworkEvent::ShopDealResult { success } => {
+                            // NOTE: On failure the shop stays open, so the player can change the
+                            // selection.
+                            if success && shop.is_open() {
+                                shop.clear();
+                                interface.close_window_with_class(&mut focus_state, ShopWindow::WINDOW_CLASS);
+                            }
+                        }
+                        NetworkEvent::VendingTitle { entity_id, title } => particle_holder.add_vending_title(entity_id, &title),
+                        NetworkEvent::VendingClosed(entity_id) => {
+                            particle_holder.remove_vending_title(entity_id);
+
+                            let is_player = entities.first().is_some_and(|player| player.get_entity_id() == entity_id);
+
+                            if is_player && is_vending {
+                                is_vending = false;
+                                interface.close_window_with_class(&mut focus_state, OwnVendingWindow::WINDOW_CLASS);
+                            }
+                        }
+                        NetworkEvent::VendingList {
+                            entity_id,
+                            vending_id,
+                            items,
+                        } => {
+                            let title = particle_holder.get_vending_title(entity_id).unwrap_or_else(|| "Vending".to_owned());
+                            let vendor = Vendor {
+                                entity_id,
+                                vending_id,
+                                title,
+                            };
+
+                            shop.open_buy_list(&mut game_file_loader, &mut texture_loader, &script_loader, Some(vendor), items);
+                            interface.open_window(&mut focus_state, &ShopWindow::new(shop.get_data(), character_stats.get_data()));
+                        }
+                        NetworkEvent::OpenVendingSetup(slot_count) => {
+                            let items = cart.get_items().borrow().clone();
+                            is_vending_setup = true;
+                            interface.open_window(&mut focus_state, &VendingSetupWindow::new(items, slot_count));
+                        }
+                        NetworkEvent::VendingOpened { title } => {
+                            // NOTE: The map server doesn't send the title of the own shop.
+                            if let Some(player) = entities.first() {
+                                particle_holder.add_vending_title(player.get_entity_id(), &title);
+                            }
+
+                            if is_vending_setup {
+                                is_vending_setup = false;
+                                interface.close_window_with_class(&mut focus_state, VendingSetupWindow::WINDOW_CLASS);
+                            }
+
+                            is_vending = true;
+                            interface.open_window(&mut focus_state, &OwnVendingWindow::new(title));
+                        }
+                        NetworkEvent::QuestList(quests) => quest_log.set_quests(&script_loader, quests),
+                        NetworkEvent::QuestAdded(quest) => quest_log.add_quest(&script_loader, quest),
+                        NetworkEvent::QuestObjectiveUpdated {
+                            hunt_identification,
+                            current_count,
+                            total_count,
+                        } => quest_log.update_objective(hunt_identification, current_count, total_count),
+                        NetworkEvent::QuestRemoved(quest_id) => quest_log.remove_quest(quest_id),
+                        NetworkEvent::QuestNavigation { map_name, position } => {
+                            match map_name == networking_system.get_map_name() {
+                                true => particle_holder.set_navigation_marker(&mut game_file_loader, &mut texture_loader, &map, position),
+                                false => particle_holder.clear_navigation_marker(),
+                            }
+
+                            quest_log.set_navigation(map_name, position);
+                        }
+                        NetworkEvent::AchievementList { level, achievements: list } => {
+                            achievements.set_achievements(&script_loader, level, list)
+                        }
+                        NetworkEvent::AchievementUpdate { level, achievement } => {
+                            if let Some(title) = achievements.update_achievement(&script_loader, level, achievement) {
+                                ground_item_holder.add_toast(format!("Achievement completed: {title}"));
+                            }
+                        }
+                        NetworkEvent::AchievementRewarded(achievement_id) => achievements.set_rewarded(achievement_id),
+                        NetworkEvent::BankOpened => {
+                            bank.open();
+                            networking_system.request_bank_balance();
+
+                            interface.open_window(
+                                &mut focus_state,
+                                &BankWindow::new(bank.get_balance(), character_stats.get_data()),
+                            );
+                        }
+                        NetworkEvent::BankBalance(balance) => bank.set_balance(balance),
+                        NetworkEvent::MailList { mails, is_end } => mailbox.add_mails(mails, is_end),
+                        NetworkEvent::MailContent {
+                            mail_id,
+                            text,
+                            zeny,
+                            items,
+                        } => {
+                            let is_opened = mailbox.open_mail(
+                                &mut game_file_loader,
+                                &mut texture_loader,
+                                &script_loader,
+                                mail_id,
+                                text,
+                                zeny,
+                                items,
+                            );
+
+                            if is_opened {
+                                interface.open_window(&mut focus_state, &MailWindow::new(mailbox.get_opened_mail()));
+                            }
+                        }
+                        NetworkEvent::MailDeleted(mail_id) => {
+                            if mailbox.remove_mail(mail_id) {
+                                interface.close_window_with_class(&mut focus_state, MailWindow::WINDOW_CLASS);
+                            }
+                        }
+                        NetworkEvent::MailZenyTaken(mail_id) => mailbox.take_zeny(mail_id),
+                        NetworkEvent::MailItemsTaken(mail_id) => mailbox.take_items(mail_id),
+                        NetworkEvent::MailComposeOpened => {
+                            if !mailbox.is_composing() {
+                                mailbox.start_composing();
+                                interface.open_window(&mut focus_state, &MailComposeWindow::new(mailbox.get_attachments()));
+                            }
+                        }
+                        NetworkEvent::UpdateMailAttachment { index, amount } => mailbox.update_attachment(&player_inventory, index, amount),
+                        NetworkEvent::MailReceiver(receiver_id) => {
+                            if let Some(pending_mail) = mailbox.take_pending_mail()
+                                && let Some(receiver_id) = receiver_id
+                            {
+                                let PendingMail {
+                                    receiver_name,
+                                    title,
+                                    text,
+                                    zeny,
+                                } = pending_mail;
+
+                                networking_system.send_mail(receiver_name, receiver_id, title, text, zeny);
+                            }
+                        }
+                        NetworkEvent::MailSent { success } => {
+                            if success && mailbox.stop_composing() {
+                                interface.close_window_with_class(&mut focus_state, MailComposeWindow::WINDOW_CLASS);
+                            }
+                        }
+                        NetworkEvent::FriendOnlineStatus { name, state } => {
+                            let name = interface.get_streamer_mode().hide_name(&name);
+                            let text = match state {
+                                OnlineState::Online => format!("{name} has logged in"),
+                                OnlineState::Offline => format!("{name} has logged out"),
+                            };
+
+                            ground_item_holder.add_toast(text);
+                        }
                         NetworkEvent::VisualEffect(path, entity_id) => {
                             let effect = effect_loader.get(path, &mut game_file_loader, &mut texture_loader).unwrap();
                             let frame_timer = effect.new_frame_timer();
@@ -676,53 +1213,169 @@ fn main() {
                                 false,
                             )));
                         }
-                        NetworkEvent::AddSkillUnit(entity_id, unit_id, position) => match unit_id {
-                            UnitId::Firewall => {
+                        NetworkEvent::AddSkillUnit(entity_id, unit_id, position) => {
+                            if let Some(unit_effect) = UnitEffect::from_unit_id(&unit_id) {
                                 let position = map.get_world_position(position);
-                                let effect = effect_loader
-                                    .get("firewall.str", &mut game_file_loader, &mut texture_loader)
-                                    .unwrap();
-                                let frame_timer = effect.new_frame_timer();
-
-                                effect_holder.add_unit(
-                                    Box::new(EffectWithLight::new(
-                                        effect,
-                                        frame_timer,
+                                let effect = unit_effect.effect_file.and_then(|effect_file| {
+                                    effect_loader
+                                        .get(effect_file, &mut game_file_loader, &mut texture_loader)
+                                        .inspect_err(|_error| {
+                                            #[cfg(feature = "debug")]
+                                            print_debug!("[{RED}error{NONE}] failed to load unit effect: {_error}");
+                                        })
+                                        .ok()
+                                });
+
+                                let unit: Box<dyn EffectBase + Send + Sync> = match effect {
+                                    Some(effect) => {
+                                        let frame_timer = effect.new_frame_timer();
+
+                                        Box::new(EffectWithLight::new(
+                                            effect,
+                                            frame_timer,
+                                            EffectCenter::Position(position),
+                                            Vector3::new(0.0, 0.0, 0.0),
+                                            Vector3::new(0.0, 3.0, 0.0),
+                                            unit_effect.light_color,
+                                            unit_effect.light_intensity,
+                                            true,
+                                        ))
+                                    }
+                                    None => Box::new(LightEffect::new(
                                         EffectCenter::Position(position),
-                                        Vector3::new(0.0, 0.0, 0.0),
                                         Vector3::new(0.0, 3.0, 0.0),
-                                        Color::rgb_u8(255, 30, 0),
-                                        20.0,
-                                        true,
+                                        unit_effect.light_color,
+                                        unit_effect.light_intensity,
                                     )),
-                                    entity_id,
+                                };
+
+                                effect_holder.add_unit(unit, entity_id);
+
+                                if let Some(sound_effect_file) = unit_effect.sound_effect_file
+                                    && let Ok(sound_effect) = audio_engine.get_sound_effect(sound_effect_file, &mut game_file_loader)
+                                {
+                                    audio_engine.play_spatial_sound_effect(sound_effect, 1.0, position, 250.0);
+                                }
+                            }
+                        }
+                        NetworkEvent::RemoveSkillUnit(entity_id) => {
+                            effect_holder.remove_unit(entity_id);
+                        }
+                        NetworkEvent::CastSkill {
+                            caster,
+                            target,
+                            position,
+                            skill_id,
+                            cast_time,
+                        } => {
+                            // NOTE: Instant casts and our own casts don't need a preview.
+                            if cast_time > 0 && entities.first().is_some_and(|player| player.get_entity_id() != caster) {
+                                cast_preview_holder.add_cast(caster, target, position, skill_id, cast_time as f32 / 1000.0);
+                            }
+                        }
+                        NetworkEvent::CastCancelled(entity_id) => cast_preview_holder.remove_cast(entity_id),
+                        NetworkEvent::SkillEffect { caster, target, skill_id } => {
+                            let Some(skill_effect) = SkillEffect::from_skill_id(skill_id) else {
+                                continue;
+                            };
+
+                            let entity_center = |entity_id: EntityId| {
+                                entities
+                                    .iter()
+                                    .find(|entity| entity.get_entity_id() == entity_id)
+                                    .map(|entity| EffectCenter::Entity(entity_id, entity.get_position()))
+                            };
+
+                            // NOTE: Entities that are out of view have no position, so their effects are
+                            // not played.
+                            let center = match (skill_effect.anchor, target) {
+                                (SkillEffectAnchor::Caster, _) => entity_center(caster),
+                                (SkillEffectAnchor::Target, SkillTarget::Entity(entity_id)) => entity_center(entity_id),
+                                (SkillEffectAnchor::Target, SkillTarget::Ground(position)) => {
+                                    Some(EffectCenter::Position(map.get_world_position(position)))
+                                }
+                            };
+
+                            let Some(center) = center else {
+                                continue;
+                            };
+
+                            let position = center.to_position();
+
+                            if let Some(emitter) = skill_effect.emitter {
+                                let texture = emitter
+                                    .texture_file
+                                    .and_then(|texture_file| texture_loader.get(texture_file, &mut game_file_loader).ok());
+
+                                particle_holder.add_emitter(
+                                    ParticleEmitter::new(position, emitter, texture).with_duration(SkillEffect::BURST_DURATION),
                                 );
                             }
-                            UnitId::Pneuma => {
-                                let position = map.get_world_position(position);
-                                let effect = effect_loader
-                                    .get("pneuma1.str", &mut game_file_loader, &mut texture_loader)
-                                    .unwrap();
-                                let frame_timer = effect.new_frame_timer();
 
-                                effect_holder.add_unit(
+                            let effect = skill_effect.effect_file.and_then(|effect_file| {
+                                effect_loader
+                                    .get(effect_file, &mut game_file_loader, &mut texture_loader)
+                                    .inspect_err(|_error| {
+                                        #[cfg(feature = "debug")]
+                                        print_debug!("[{RED}error{NONE}] failed to load skill effect: {_error}");
+                                    })
+                                    .ok()
+                            });
+
+                            let effect: Box<dyn EffectBase + Send + Sync> = match effect {
+                                Some(effect) => {
+                                    let frame_timer = effect.new_frame_timer();
+
                                     Box::new(EffectWithLight::new(
                                         effect,
                                         frame_timer,
-                                        EffectCenter::Position(position),
+                                        center,
                                         Vector3::new(0.0, 0.0, 0.0),
                                         Vector3::new(0.0, 3.0, 0.0),
-                                        Color::rgb_u8(83, 220, 108),
-                                        40.0,
+                                        skill_effect.light_color,
+                                        skill_effect.light_intensity,
                                         false,
-                                    )),
-                                    entity_id,
-                                );
+                                    ))
+                                }
+                                None => Box::new(
+                                    LightEffect::new(
+                                        center,
+                                        Vector3::new(0.0, 3.0, 0.0),
+                                        skill_effect.light_color,
+                                        skill_effect.light_intensity,
+                                    )
+                                    .with_duration(SkillEffect::BURST_DURATION),
+                                ),
+                            };
+
+                            effect_holder.add_effect(effect);
+
+                            if let Some(sound_effect_file) = skill_effect.sound_effect_file
+                                && let Ok(sound_effect) = audio_engine.get_sound_effect(sound_effect_file, &mut game_file_loader)
+                            {
+                                audio_engine.play_spatial_sound_effect(sound_effect, 1.0, position, 250.0);
+                            }
+                        }
+                        NetworkEvent::UpdateStatusEffect {
+                            entity_id,
+                            status_index,
+                            active,
+                        } => {
+                            if let Some(weapon_endow) = WeaponEndow::from_status_index(status_index) {
+                                match active {
+                                    true => effect_holder.add_status_effect(
+                                        Box::new(LightEffect::new(
+                                            EffectCenter::Entity(entity_id, Vector3::new(0.0, 0.0, 0.0)),
+                                            Vector3::new(0.0, 6.0, 0.0),
+                                            weapon_endow.glow_color(),
+                                            15.0,
+                                        )),
+                                        entity_id,
+                                        status_index,
+                                    ),
+                                    false => effect_holder.remove_status_effect(entity_id, status_index),
+                                }
                             }
-                            _ => {}
-                        },
-                        NetworkEvent::RemoveSkillUnit(entity_id) => {
-                            effect_holder.remove_unit(entity_id);
                         }
                     }
                 }
@@ -730,10 +1383,23 @@ fn main() {
                 #[cfg(feature = "debug")]
                 network_event_measuremen.stop();
 
+                // NOTE: While the window is in the background, the rest of the frame is delayed
+                // to reduce the load on the CPU and GPU. Network events are handled above on
+                // every wake up, so the connection stays alive.
+                if let Some(next_frame) = idle_state.delay_frame(Instant::now()) {
+                    *control_flow = ControlFlow::WaitUntil(next_frame);
+                    return;
+                }
+
+                *control_flow = ControlFlow::Poll;
+
                 #[cfg(feature = "debug")]
                 let user_event_measuremen = start_measurement("process user events");
 
-                for event in user_events {
+                // NOTE: All user events of this frame are executed here. Events that are
+                // emitted while handling them will be executed in the next
+                // frame.
+                for event in user_event_queue.take_events() {
                     match event {
                         UserEvent::LogIn {
                             service_id,
@@ -765,17 +1431,9 @@ fn main() {
                             }
                         }
                         UserEvent::LogOut => networking_system.log_out().unwrap(),
-                        UserEvent::Exit => *control_flow = ControlFlow::Exit,
+                        UserEvent::Exit => shutdown_signal.request(ShutdownReason::ExitRequested),
                         UserEvent::CameraZoom(factor) => player_camera.soft_zoom(factor),
                         UserEvent::CameraRotate(factor) => player_camera.soft_rotate(factor),
-                        UserEvent::ToggleFrameLimit => {
-                            graphics_settings.toggle_frame_limit();
-                            swapchain_holder.set_frame_limit(present_mode_info, graphics_settings.frame_limit);
-
-                            // NOTE: For some reason the interface buffer becomes messed up when
-                            // recreating the swapchain, so we need to render it again.
-                            interface.schedule_render();
-                        }
                         UserEvent::ToggleShowInterface => graphics_settings.toggle_show_interface(),
                         UserEvent::OpenMenuWindow => {
                             if !entities.is_empty() {
@@ -784,12 +1442,57 @@ fn main() {
                         }
                         UserEvent::OpenInventoryWindow => {
                             if !entities.is_empty() {
-                                interface.open_window(&mut focus_state, &InventoryWindow::new(player_inventory.get_items()))
+                                interface.open_window(
+                                    &mut focus_state,
+                                    &InventoryWindow::new(player_inventory.get_items(), &inventory_view),
+                                )
                             }
                         }
                         UserEvent::OpenEquipmentWindow => {
                             if !entities.is_empty() {
-                                interface.open_window(&mut focus_state, &EquipmentWindow::new(player_inventory.get_items()))
+                                interface.open_window(
+                                    &mut focus_state,
+                                    &EquipmentWindow::new(player_inventory.get_items(), equipment_sets.get_sets()),
+                                )
+                            }
+                        }
+                        UserEvent::OpenQuestWindow => {
+                            if !entities.is_empty() {
+                                interface.open_window(&mut focus_state, &QuestWindow::new(quest_log.get_data()))
+                            }
+                        }
+                        UserEvent::OpenAchievementWindow => {
+                            if !entities.is_empty() {
+                                interface.open_window(&mut focus_state, &AchievementWindow::new(achievements.get_data()))
+                            }
+                        }
+                        UserEvent::ClaimAchievementReward(achievement_id) => networking_system.request_achievement_reward(achievement_id),
+                        UserEvent::OpenBankWindow => {
+                            if !entities.is_empty() && !bank.is_open() {
+                                networking_system.open_bank();
+                            }
+                        }
+                        UserEvent::DepositZeny(zeny) => networking_system.deposit_zeny(zeny),
+                        UserEvent::WithdrawZeny(zeny) => networking_system.withdraw_zeny(zeny),
+                        UserEvent::CloseBank => {
+                            if bank.is_open() {
+                                bank.clear();
+                                networking_system.close_bank();
+                                interface.close_window_with_class(&mut focus_state, BankWindow::WINDOW_CLASS);
+                            }
+                        }
+                        UserEvent::ClearQuestNavigation => {
+                            quest_log.clear_navigation();
+                            particle_holder.clear_navigation_marker();
+                        }
+                        UserEvent::OpenCartWindow => {
+                            if !entities.is_empty() && cart.has_cart() {
+                                interface.open_window(&mut focus_state, &CartWindow::new(cart.get_items(), cart.get_information()))
+                            }
+                        }
+                        UserEvent::OpenStatsWindow => {
+                            if !entities.is_empty() {
+                                interface.open_window(&mut focus_state, &StatsWindow::new(character_stats.get_data()))
                             }
                         }
                         UserEvent::OpenSkillTreeWindow => {
@@ -799,22 +1502,131 @@ fn main() {
                         }
                         UserEvent::OpenGraphicsSettingsWindow => interface.open_window(
                             &mut focus_state,
-                            &GraphicsSettingsWindow::new(present_mode_info, shadow_detail.clone_state()),
+                            &GraphicsSettingsWindow::new(
+                                present_mode_info,
+                                frame_limit.clone_state(),
+                                shadow_detail.clone_state(),
+                                speech_bubble_range.clone_state(),
+                                entity_render_distance.clone_state(),
+                                maximum_rendered_entities.clone_state(),
+                                sprite_filtering.clone_state(),
+                                sampler_configuration.clone_state(),
+                                show_speech_bubbles.clone_state(),
+                                show_path_preview.clone_state(),
+                                show_quest_markers.clone_state(),
+                                preload_adjacent_maps.clone_state(),
+                                camera_shake.clone_state(),
+                                hit_stop.clone_state(),
+                                cast_previews.clone_state(),
+                                target_priority.clone_state(),
+                                animations.clone_state(),
+                                streamer_mode.clone_state(),
+                                combat_window_mode.clone_state(),
+                            ),
+                        ),
+                        UserEvent::OpenGeneralSettingsWindow => interface.open_window(
+                            &mut focus_state,
+                            &GeneralSettingsWindow::new(
+                                away_timeout.clone_state(),
+                                sit_while_away.clone_state(),
+                                check_for_updates.clone_state(),
+                            ),
                         ),
-                        UserEvent::OpenAudioSettingsWindow => interface.open_window(&mut focus_state, &AudioSettingsWindow::default()),
+                        UserEvent::OpenAudioSettingsWindow => interface.open_window(
+                            &mut focus_state,
+                            &AudioSettingsWindow::new(
+                                mute.clone_state(),
+                                background_music_volume.clone_state(),
+                                sound_effect_volume.clone_state(),
+                            ),
+                        ),
+                        UserEvent::OpenWindowOptions(window_class) => {
+                            interface.open_window_options(&mut focus_state, input_system.get_mouse_position(), window_class)
+                        }
+                        UserEvent::SetWindowOpacity { window_class, opacity } => interface.set_window_opacity(&window_class, opacity),
+                        UserEvent::SetWindowLocked { window_class, locked } => interface.set_window_locked(&window_class, locked),
+                        UserEvent::CopyToClipboard(text) => input_system.copy_to_clipboard(text),
+                        UserEvent::RequestConfirmation { text, event } => {
+                            interface.open_confirmation_dialog(&mut focus_state, text, *event)
+                        }
+                        UserEvent::ResolveConfirmation(confirmed) => {
+                            if let Some(event) = interface.resolve_confirmation(&mut focus_state, confirmed) {
+                                user_event_queue.push(event, EventSource::Interface);
+                            }
+                        }
+                        UserEvent::OpenLayoutProfilesWindow => interface.open_layout_profiles_window(&mut focus_state),
+                        UserEvent::OpenThemeEditorWindow => interface.open_theme_editor_window(&mut focus_state),
+                        UserEvent::SaveLayoutProfile(name) => interface.save_layout_profile(&mut focus_state, name),
+                        UserEvent::LoadLayoutProfile(name) => {
+                            let result = interface.load_layout_profile(&name);
+                            interface.handle_result(&mut focus_state, result);
+                        }
+                        UserEvent::DeleteLayoutProfile(name) => interface.delete_layout_profile(&mut focus_state, &name),
+                        UserEvent::ExportLayoutProfile(name) => {
+                            let result = interface.export_layout_profile(&name);
+                            interface.handle_result(&mut focus_state, result);
+                        }
+                        UserEvent::ImportLayoutProfile(path) => {
+                            let result = interface.import_layout_profile(&mut focus_state, &path);
+                            interface.handle_result(&mut focus_state, result);
+                        }
                         UserEvent::OpenFriendsWindow => interface.open_window(&mut focus_state, &networking_system.friends_window()),
+                        UserEvent::OpenGuildWindow => {
+                            networking_system.request_guild_information();
+                            interface.open_window(&mut focus_state, &networking_system.guild_window());
+                        }
+                        UserEvent::OpenSessionStatisticsWindow => {
+                            if !entities.is_empty() {
+                                interface.open_window(&mut focus_state, &SessionStatisticsWindow::new(session_statistics.get_data()))
+                            }
+                        }
+                        UserEvent::ResetSessionStatistics => session_statistics.reset(),
+                        UserEvent::OpenEntitySearchWindow => interface.open_window(
+                            &mut focus_state,
+                            &EntitySearchWindow::new(entity_search.get_name_state(), entity_search.get_entity_type_state()),
+                        ),
+                        UserEvent::ClearEntitySearch => entity_search.clear(),
+                        UserEvent::ExportSessionStatistics => {
+                            if let Err(message) = session_statistics.export() {
+                                interface.open_window(&mut focus_state, &ErrorWindow::new(message));
+                            }
+                        }
+                        UserEvent::SetLanguage(language) => interface.set_language(&mut game_file_loader, language),
                         UserEvent::SetThemeFile { theme_file, theme_kind } => {
                             interface.set_theme_file(theme_file, theme_kind);
-                            interface.reload_theme(theme_kind);
+                            interface.reload_theme(&mut focus_state, theme_kind);
                         }
                         UserEvent::SaveTheme { theme_kind } => interface.save_theme(theme_kind),
-                        UserEvent::ReloadTheme { theme_kind } => interface.reload_theme(theme_kind),
+                        UserEvent::ReloadTheme { theme_kind } => interface.reload_theme(&mut focus_state, theme_kind),
+                        UserEvent::PreviewCharacter(character_slot) => {
+                            character_preview = networking_system.preview_character(character_slot).map(|character_information| {
+                                let player = Player::new(
+                                    &mut game_file_loader,
+                                    &mut sprite_loader,
+                                    &mut action_loader,
+                                    &script_loader,
+                                    &map,
+                                    AccountId(0),
+                                    character_information,
+                                    CHARACTER_PREVIEW_POSITION,
+                                    client_tick,
+                                );
+
+                                Entity::Player(player)
+                            });
+                        }
                         UserEvent::SelectCharacter(character_slot) => {
                             match networking_system.select_character(character_slot) {
                                 Ok((account_id, character_information, map_name)) => {
+                                    character_preview = None;
+
+                                    audio_engine.play_background_music(&map_name, &mut game_file_loader);
+                                    map_preloader.set_current_map(&map_name, false);
+                                    shadow_cache.invalidate();
+
                                     map = map_loader
                                         .get(
-                                            map_name,
+                                            map_name.clone(),
                                             &mut game_file_loader,
                                             &mut buffer_allocator,
                                             &mut model_loader,
@@ -822,6 +1634,24 @@ fn main() {
                                         )
                                         .unwrap();
 
+                                    #[cfg(feature = "debug")]
+                                    report_residency_after_map_change(&map_name);
+
+                                    map.register_sound_sources(&mut audio_engine, &mut game_file_loader);
+                                    map.register_effect_sources(&mut particle_holder, &mut game_file_loader, &mut texture_loader);
+                                    session_statistics.clear();
+                                    character_stats.clear();
+                                    trade.clear();
+                                    shop.clear();
+                                    cart.clear();
+                                    is_vending_setup = false;
+                                    is_vending = false;
+                                    mailbox.clear();
+                                    quest_log.clear();
+                                    achievements.clear();
+                                    bank.clear();
+                                    equipment_sets.load(character_information.character_id);
+
                                     let player = Player::new(
                                         &mut game_file_loader,
                                         &mut sprite_loader,
@@ -846,7 +1676,10 @@ fn main() {
                                         &mut focus_state,
                                         &ChatWindow::new(chat_messages.new_remote(), font_loader.clone()),
                                     );
-                                    interface.open_window(&mut focus_state, &HotbarWindow::new(hotbar.get_skills()));
+                                    interface.open_window(
+                                        &mut focus_state,
+                                        &HotbarWindow::new(hotbar.get_entries(), hotbar.get_cooldowns()),
+                                    );
 
                                     particle_holder.clear();
                                     networking_system.map_loaded();
@@ -859,16 +1692,92 @@ fn main() {
                             }
                         }
                         UserEvent::OpenCharacterCreationWindow(character_slot) => {
-                            interface.open_window(&mut focus_state, &CharacterCreationWindow::new(character_slot))
-                        }
-                        UserEvent::CreateCharacter(character_slot, name) => {
-                            match networking_system.create_character(character_slot, name) {
-                                Ok(..) => interface.close_window_with_class(&mut focus_state, CharacterCreationWindow::WINDOW_CLASS),
-                                Err(message) => interface.open_window(&mut focus_state, &ErrorWindow::new(message)),
-                            }
-                        }
-                        UserEvent::DeleteCharacter(character_id) => {
-                            interface.handle_result(&mut focus_state, networking_system.delete_character(character_id))
+                            let sex = networking_system.get_account_sex();
+
+                            character_appearance.set(Some(CharacterAppearance::load(
+                                &mut game_file_loader,
+                                &mut sprite_loader,
+                                &mut action_loader,
+                                &script_loader,
+                                sex,
+                                1,
+                                0,
+                            )));
+
+                            interface.open_window(
+                                &mut focus_state,
+                                &CharacterCreationWindow::new(
+                                    character_slot,
+                                    networking_system.get_character_creation_version(),
+                                    sex,
+                                    character_appearance.new_remote(),
+                                ),
+                            )
+                        }
+                        UserEvent::UpdateCharacterAppearance {
+                            sex,
+                            hair_style,
+                            hair_color,
+                        } => character_appearance.set(Some(CharacterAppearance::load(
+                            &mut game_file_loader,
+                            &mut sprite_loader,
+                            &mut action_loader,
+                            &script_loader,
+                            sex,
+                            hair_style,
+                            hair_color,
+                        ))),
+                        // NOTE: If the name is already taken, the window stays open so the player can
+                        // pick a different one.
+                        UserEvent::CreateCharacter(character_slot, character) => {
+                            match networking_system.create_character(character_slot, character) {
+                                Ok(..) => interface.close_window_with_class(&mut focus_state, CharacterCreationWindow::WINDOW_CLASS),
+                                Err(message) => interface.open_window(&mut focus_state, &ErrorWindow::new(message)),
+                            }
+                        }
+                        UserEvent::RequestCharacterDeletion(character_id) => {
+                            let version = networking_system.get_character_deletion_version();
+
+                            // NOTE: Newer servers only delete characters after a waiting period, which
+                            // has to be started first.
+                            match networking_system.get_character(character_id) {
+                                Some(character) if version == CharacterDeletionVersion::Timer && character.deletion_date().is_none() => {
+                                    // NOTE: The name of the character is not shown in streamer mode.
+                                    let character_name = match interface.get_streamer_mode().is_enabled() {
+                                        true => "This character",
+                                        false => character.name.as_str(),
+                                    };
+
+                                    interface.open_confirmation_dialog(
+                                        &mut focus_state,
+                                        format!("{character_name} can be deleted after a waiting period. Do you want to start it?"),
+                                        UserEvent::ReserveCharacterDeletion(character_id),
+                                    )
+                                }
+                                Some(character) => interface.open_window(
+                                    &mut focus_state,
+                                    &CharacterDeletionWindow::new(character_id, character.name, version),
+                                ),
+                                None => {}
+                            }
+                        }
+                        UserEvent::ReserveCharacterDeletion(character_id) => {
+                            interface.handle_result(&mut focus_state, networking_system.reserve_character_deletion(character_id))
+                        }
+                        UserEvent::CancelCharacterDeletion(character_id) => {
+                            interface.handle_result(&mut focus_state, networking_system.cancel_character_deletion(character_id))
+                        }
+                        UserEvent::DeleteCharacter(character_id, confirmation) => {
+                            match networking_system.delete_character(character_id, confirmation) {
+                                Ok(..) => {
+                                    character_preview = None;
+                                    interface.close_window_with_class(&mut focus_state, CharacterDeletionWindow::WINDOW_CLASS);
+                                }
+                                Err(message) => interface.open_window(&mut focus_state, &ErrorWindow::new(message)),
+                            }
+                        }
+                        UserEvent::CloseCharacterDeletionWindow => {
+                            interface.close_window_with_class(&mut focus_state, CharacterDeletionWindow::WINDOW_CLASS)
                         }
                         UserEvent::RequestSwitchCharacterSlot(origin_slot) => networking_system.request_switch_character_slot(origin_slot),
                         UserEvent::CancelSwitchCharacterSlot => networking_system.cancel_switch_character_slot(),
@@ -877,6 +1786,7 @@ fn main() {
                         }
                         UserEvent::RequestPlayerMove(destination) => {
                             if !entities.is_empty() {
+                                entities[0].predict_move_to(&map, destination, client_tick);
                                 networking_system.request_player_move(destination)
                             }
                         }
@@ -886,13 +1796,19 @@ fn main() {
                             if let Some(entity) = entity {
                                 match entity.get_entity_type() {
                                     EntityType::Npc => networking_system.start_dialog(entity_id),
+                                    EntityType::Player if particle_holder.has_vending_title(entity_id) => {
+                                        networking_system.request_vending_items(entity_id)
+                                    }
                                     EntityType::Monster => networking_system.request_player_attack(entity_id),
                                     EntityType::Warp => networking_system.request_player_move(entity.get_grid_position()),
                                     _ => {} // TODO: add other interactions
                                 }
                             }
                         }
+                        UserEvent::RequestPickUpItem(entity_id) => networking_system.request_pick_up_item(entity_id),
+                        UserEvent::CycleTarget => target_stack.cycle(),
                         UserEvent::RequestWarpToMap(map_name, position) => networking_system.request_warp_to_map(map_name, position),
+                        UserEvent::SendPartyPing(position) => networking_system.send_party_ping(position),
                         UserEvent::SendMessage(message) => {
                             networking_system.send_message(message);
                             // TODO: maybe find a better solution for unfocusing the message box if
@@ -911,60 +1827,111 @@ fn main() {
                                 interface.close_dialog_window(&mut focus_state);
                             }
                         }
-                        UserEvent::MoveItem(item_move) => match (item_move.source, item_move.destination) {
-                            (ItemSource::Inventory, ItemSource::Equipment { position }) => {
-                                networking_system.request_item_equip(item_move.item.index, position);
-                            }
-                            (ItemSource::Equipment { .. }, ItemSource::Inventory) => {
-                                networking_system.request_item_unequip(item_move.item.index);
+                        UserEvent::MoveItem(item_move) if item_move.needs_confirmation() => interface.open_confirmation_dialog(
+                            &mut focus_state,
+                            "This item is one of your favorites. Do you want to give it away anyway?".to_owned(),
+                            UserEvent::MoveFavoriteItem(item_move),
+                        ),
+                        UserEvent::MoveItem(item_move) | UserEvent::MoveFavoriteItem(item_move) => {
+                            match (item_move.source, item_move.destination) {
+                                (ItemSource::Inventory, ItemSource::Equipment { position }) => {
+                                    networking_system.request_item_equip(item_move.item.index, position);
+                                }
+                                (ItemSource::Equipment { .. }, ItemSource::Inventory) => {
+                                    networking_system.request_item_unequip(item_move.item.index);
+                                }
+                                (ItemSource::Inventory, ItemSource::Hotbar { slot }) => {
+                                    let hotkey = hotbar.set_slot(HotbarEntry::Item(item_move.item), slot);
+                                    networking_system.set_hotkey(slot.0, hotkey);
+                                }
+                                (ItemSource::Hotbar { slot: source_slot }, ItemSource::Hotbar { slot: destination_slot }) => {
+                                    for (slot, hotkey) in hotbar.swap_slot(source_slot, destination_slot) {
+                                        networking_system.set_hotkey(slot.0, hotkey);
+                                    }
+                                }
+                                (ItemSource::Hotbar { slot }, ItemSource::Inventory) => {
+                                    let hotkey = hotbar.clear_slot(slot);
+                                    networking_system.set_hotkey(slot.0, hotkey);
+                                }
+                                (ItemSource::Inventory, ItemSource::Trade) => {
+                                    let (index, amount) = (item_move.item.index, item_move.item.amount);
+
+                                    if trade.offer_item(item_move.item) {
+                                        networking_system.add_trade_item(index, amount);
+                                    }
+                                }
+                                // NOTE: Equipped items can't be put into the cart.
+                                (ItemSource::Inventory, ItemSource::Cart) if item_move.item.equipped_position == EquipPosition::None => {
+                                    networking_system.move_item_to_cart(item_move.item.index, item_move.item.amount);
+                                }
+                                (ItemSource::Cart, ItemSource::Inventory) => {
+                                    networking_system.move_item_from_cart(item_move.item.index, item_move.item.amount);
+                                }
+                                (ItemSource::Inventory, ItemSource::MailAttachment) if mailbox.can_attach(&item_move.item) => {
+                                    networking_system.attach_mail_item(item_move.item.index, item_move.item.amount);
+                                }
+                                (ItemSource::MailAttachment, ItemSource::Inventory) => {
+                                    networking_system.detach_mail_item(item_move.item.index, item_move.item.amount);
+                                }
+                                _ => {}
                             }
-                            _ => {}
-                        },
+                        }
                         UserEvent::MoveSkill(skill_move) => match (skill_move.source, skill_move.destination) {
                             (SkillSource::SkillTree, SkillSource::Hotbar { slot }) => {
-                                hotbar.set_slot(skill_move.skill, slot);
+                                let hotkey = hotbar.set_slot(HotbarEntry::Skill(skill_move.skill), slot);
+                                networking_system.set_hotkey(slot.0, hotkey);
                             }
                             (SkillSource::Hotbar { slot: source_slot }, SkillSource::Hotbar { slot: destination_slot }) => {
-                                hotbar.swap_slot(source_slot, destination_slot);
+                                for (slot, hotkey) in hotbar.swap_slot(source_slot, destination_slot) {
+                                    networking_system.set_hotkey(slot.0, hotkey);
+                                }
                             }
                             _ => {}
                         },
-                        UserEvent::CastSkill(slot) => {
-                            if let Some(skill) = hotbar.get_skill_in_slot(slot).as_ref() {
-                                match skill.skill_type {
-                                    SkillType::Passive => {}
-                                    SkillType::Attack => {
-                                        if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
-                                            networking_system.cast_skill(skill.skill_id, skill.skill_level, entity_id);
-                                        }
+                        UserEvent::ClearHotbarSlot(slot) => {
+                            let hotkey = hotbar.clear_slot(slot);
+                            networking_system.set_hotkey(slot.0, hotkey);
+                        }
+                        UserEvent::UseHotbarSlot(slot) => match hotbar.get_entry_in_slot(slot).as_ref() {
+                            // NOTE: The index of an item can change while it is on the hotbar, so
+                            // we look up the current one.
+                            Some(HotbarEntry::Item(item)) => {
+                                if let Some(item) = player_inventory.find_item(item.item_id) {
+                                    networking_system.use_item(item.index);
+                                }
+                            }
+                            Some(HotbarEntry::Skill(skill)) => match skill.skill_type {
+                                SkillType::Passive => {}
+                                SkillType::Attack => {
+                                    if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
+                                        networking_system.cast_skill(skill.skill_id, skill.skill_level, entity_id);
                                     }
-                                    SkillType::Ground | SkillType::Trap => {
-                                        if let Some(PickerTarget::Tile { x, y }) = mouse_target {
-                                            networking_system.cast_ground_skill(skill.skill_id, skill.skill_level, Vector2::new(x, y));
-                                        }
+                                }
+                                SkillType::Ground | SkillType::Trap => {
+                                    if let Some(PickerTarget::Tile { x, y }) = mouse_target {
+                                        networking_system.cast_ground_skill(skill.skill_id, skill.skill_level, Vector2::new(x, y));
                                     }
-                                    SkillType::SelfCast => match skill.skill_id == ROLLING_CUTTER_ID {
-                                        true => networking_system.cast_channeling_skill(
-                                            skill.skill_id,
-                                            skill.skill_level,
-                                            entities[0].get_entity_id(),
-                                        ),
-                                        false => {
-                                            networking_system.cast_skill(skill.skill_id, skill.skill_level, entities[0].get_entity_id())
-                                        }
-                                    },
-                                    SkillType::Support => {
-                                        if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
-                                            networking_system.cast_skill(skill.skill_id, skill.skill_level, entity_id);
-                                        } else {
-                                            networking_system.cast_skill(skill.skill_id, skill.skill_level, entities[0].get_entity_id());
-                                        }
+                                }
+                                SkillType::SelfCast => match skill.skill_id == ROLLING_CUTTER_ID {
+                                    true => networking_system.cast_channeling_skill(
+                                        skill.skill_id,
+                                        skill.skill_level,
+                                        entities[0].get_entity_id(),
+                                    ),
+                                    false => networking_system.cast_skill(skill.skill_id, skill.skill_level, entities[0].get_entity_id()),
+                                },
+                                SkillType::Support => {
+                                    if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
+                                        networking_system.cast_skill(skill.skill_id, skill.skill_level, entity_id);
+                                    } else {
+                                        networking_system.cast_skill(skill.skill_id, skill.skill_level, entities[0].get_entity_id());
                                     }
                                 }
-                            }
-                        }
-                        UserEvent::StopSkill(slot) => {
-                            if let Some(skill) = hotbar.get_skill_in_slot(slot).as_ref() {
+                            },
+                            None => {}
+                        },
+                        UserEvent::ReleaseHotbarSlot(slot) => {
+                            if let Some(HotbarEntry::Skill(skill)) = hotbar.get_entry_in_slot(slot).as_ref() {
                                 if skill.skill_id == ROLLING_CUTTER_ID {
                                     networking_system.stop_channeling_skill(skill.skill_id);
                                 }
@@ -984,8 +1951,203 @@ fn main() {
                             networking_system.accept_friend_request(account_id, character_id);
                             interface.close_window_with_class(&mut focus_state, FriendRequestWindow::WINDOW_CLASS);
                         }
+                        UserEvent::OpenEntityContextMenu(entity_id) => {
+                            let entity = entities.iter().skip(1).find(|entity| entity.get_entity_id() == entity_id);
+
+                            if let Some(entity) = entity {
+                                let mut entries = Vec::new();
+
+                                match entity.get_entity_type() {
+                                    EntityType::Player => {
+                                        if particle_holder.has_vending_title(entity_id) {
+                                            entries.push(ContextMenuEntry::new(
+                                                "Open shop".to_owned(),
+                                                UserEvent::OpenVendingShop(entity_id),
+                                            ));
+                                        }
+
+                                        if let Some(name) = entity_name_cache.get(entity_id) {
+                                            entries.push(ContextMenuEntry::new(
+                                                "Whisper".to_owned(),
+                                                UserEvent::OpenWhisperWindow(name.clone()),
+                                            ));
+                                            entries.push(ContextMenuEntry::new(
+                                                "Add friend".to_owned(),
+                                                UserEvent::AddFriend(name.clone()),
+                                            ));
+                                            entries.push(ContextMenuEntry::new(
+                                                "Invite to party".to_owned(),
+                                                UserEvent::InviteToParty(name.clone()),
+                                            ));
+                                        }
+
+                                        entries.push(ContextMenuEntry::new("Trade".to_owned(), UserEvent::RequestTrade(entity_id)));
+                                        entries.push(ContextMenuEntry::new(
+                                            "View equipment".to_owned(),
+                                            UserEvent::RequestViewEquipment(entity_id),
+                                        ));
+                                    }
+                                    EntityType::Npc => entries.push(ContextMenuEntry::new(
+                                        "Talk".to_owned(),
+                                        UserEvent::RequestPlayerInteract(entity_id),
+                                    )),
+                                    EntityType::Monster => entries.push(ContextMenuEntry::new(
+                                        "Attack".to_owned(),
+                                        UserEvent::RequestPlayerInteract(entity_id),
+                                    )),
+                                    EntityType::Warp | EntityType::Hidden => {}
+                                }
+
+                                interface.open_context_menu(&mut focus_state, input_system.get_mouse_position(), entries);
+                            }
+                        }
+                        UserEvent::OpenWhisperWindow(name) => interface.open_window(&mut focus_state, &WhisperWindow::new(name)),
+                        UserEvent::SendWhisper { name, message } => networking_system.send_whisper(name, message),
+                        UserEvent::RequestTrade(entity_id) => {
+                            if let Some(name) = entity_name_cache.get(entity_id) {
+                                trade.set_partner_name(name.clone());
+                            }
+
+                            networking_system.request_trade(entity_id);
+                        }
+                        UserEvent::AcceptTradeRequest => {
+                            networking_system.accept_trade_request();
+                            interface.close_window_with_class(&mut focus_state, TradeRequestWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::RejectTradeRequest => {
+                            networking_system.reject_trade_request();
+                            interface.close_window_with_class(&mut focus_state, TradeRequestWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::OfferTradeZeny(amount) => {
+                            if trade.offer_zeny(amount) {
+                                networking_system.add_trade_zeny(amount);
+                            }
+                        }
+                        UserEvent::LockTrade => networking_system.lock_trade(),
+                        UserEvent::ConfirmTrade => {
+                            if trade.confirm() {
+                                networking_system.confirm_trade();
+                            }
+                        }
+                        UserEvent::CancelTrade => networking_system.cancel_trade(),
+                        UserEvent::SelectShopDealType { npc_id, deal_type } => {
+                            networking_system.select_shop_deal_type(npc_id, deal_type);
+                            interface.close_window_with_class(&mut focus_state, ShopDealWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::SetShopItemAmount { position, amount } => shop.set_amount(position, amount),
+                        UserEvent::CompleteShopDeal => match (shop.get_deal_type(), shop.get_vendor()) {
+                            // NOTE: The map server doesn't confirm purchases from vendors, so the
+                            // shop is closed right away.
+                            (ShopDealType::Buy, Some(vendor)) => {
+                                networking_system.buy_vending_items(vendor.entity_id, vendor.vending_id, shop.sell_selection());
+                                shop.clear();
+                                interface.close_window_with_class(&mut focus_state, ShopWindow::WINDOW_CLASS);
+                            }
+                            (ShopDealType::Buy, None) => networking_system.buy_shop_items(shop.buy_selection()),
+                            (ShopDealType::Sell, _) => networking_system.sell_shop_items(shop.sell_selection()),
+                        },
+                        UserEvent::CloseShop => {
+                            shop.clear();
+                            interface.close_window_with_class(&mut focus_state, ShopWindow::WINDOW_CLASS);
+                        }
+                        UserEvent::OpenVendingShop(entity_id) => networking_system.request_vending_items(entity_id),
+                        UserEvent::OpenVending { title, items } => networking_system.open_vending(title, items),
+                        UserEvent::CancelVendingSetup => {
+                            if is_vending_setup {
+                                is_vending_setup = false;
+                                interface.close_window_with_class(&mut focus_state, VendingSetupWindow::WINDOW_CLASS);
+                            }
+                        }
+                        UserEvent::CloseVending => {
+                            if is_vending {
+                                is_vending = false;
+                                networking_system.close_vending();
+                                interface.close_window_with_class(&mut focus_state, OwnVendingWindow::WINDOW_CLASS);
+
+                                if let Some(player) = entities.first() {
+                                    particle_holder.remove_vending_title(player.get_entity_id());
+                                }
+                            }
+                        }
+                        UserEvent::OpenMailbox => {
+                            if !entities.is_empty() {
+                                mailbox.clear_mails();
+                                networking_system.open_mailbox();
+                                interface.open_window(&mut focus_state, &MailboxWindow::new(mailbox.get_data()));
+                            }
+                        }
+                        UserEvent::LoadMoreMail => {
+                            if let Some(mail_id) = mailbox.oldest_mail_id() {
+                                networking_system.request_next_mail_page(mail_id);
+                            }
+                        }
+                        UserEvent::ReadMail(mail_id) => networking_system.read_mail(mail_id),
+                        UserEvent::CloseMail => {
+                            if mailbox.close_mail() {
+                                interface.close_window_with_class(&mut focus_state, MailWindow::WINDOW_CLASS);
+                            }
+                        }
+                        UserEvent::TakeMailZeny => {
+                            if let Some(mail_id) = mailbox.get_opened_mail_id() {
+                                networking_system.take_mail_zeny(mail_id);
+                            }
+                        }
+                        UserEvent::TakeMailItems => {
+                            if let Some(mail_id) = mailbox.get_opened_mail_id() {
+                                networking_system.take_mail_items(mail_id);
+                            }
+                        }
+                        UserEvent::DeleteMail => {
+                            if let Some(mail_id) = mailbox.get_opened_mail_id() {
+                                networking_system.delete_mail(mail_id);
+                            }
+                        }
+                        UserEvent::OpenMailCompose => {
+                            if !mailbox.is_composing() {
+                                networking_system.open_mail_compose();
+                            }
+                        }
+                        UserEvent::CancelMailCompose => {
+                            if mailbox.stop_composing() {
+                                networking_system.cancel_mail_compose();
+                                interface.close_window_with_class(&mut focus_state, MailComposeWindow::WINDOW_CLASS);
+                            }
+                        }
+                        UserEvent::SendMail {
+                            receiver_name,
+                            title,
+                            text,
+                            zeny,
+                        } => {
+                            networking_system.check_mail_receiver(receiver_name.clone());
+                            mailbox.set_pending_mail(PendingMail {
+                                receiver_name,
+                                title,
+                                text,
+                                zeny,
+                            });
+                        }
+                        UserEvent::RequestViewEquipment(entity_id) => networking_system.request_view_equipment(entity_id),
+                        UserEvent::InviteToParty(name) => networking_system.invite_to_party(name),
+                        UserEvent::DropItem(item_index) => networking_system.drop_item(item_index, 1),
+                        UserEvent::IncreaseStat(stat_type) => networking_system.increase_stat(stat_type),
+                        UserEvent::SaveEquipmentSet(name) => equipment_sets.save_set(name, &player_inventory),
+                        UserEvent::EquipEquipmentSet(name) => equipment_sets.equip_set(&name, &player_inventory),
+                        UserEvent::EquipEquipmentSetAt(position) => equipment_sets.equip_set_at(position, &player_inventory),
+                        UserEvent::DeleteEquipmentSet(name) => equipment_sets.delete_set(&name),
+                        UserEvent::SetItemFavorite { index, is_favorite } => networking_system.set_item_favorite(index, is_favorite),
+                        UserEvent::SetGuildNotice { subject, notice } => networking_system.set_guild_notice(subject, notice),
+                        UserEvent::ChangeGuildPosition(position) => networking_system.change_guild_position(position),
+                        UserEvent::ChangeGuildMemberPosition {
+                            account_id,
+                            character_id,
+                            position_id,
+                        } => networking_system.change_guild_member_position(account_id, character_id, position_id),
                         #[cfg(feature = "debug")]
-                        UserEvent::ToggleFrustumCulling => render_settings.toggle_frustum_culling(),
+                        UserEvent::ToggleFrustumCulling => {
+                            render_settings.toggle_frustum_culling();
+                            shadow_cache.invalidate();
+                        }
                         #[cfg(feature = "debug")]
                         UserEvent::ToggleShowBoundingBoxes => render_settings.toggle_show_bounding_boxes(),
                         #[cfg(feature = "debug")]
@@ -1011,14 +2173,16 @@ fn main() {
                         #[cfg(feature = "debug")]
                         UserEvent::SetMidnight => game_timer.set_day_timer(-std::f32::consts::FRAC_PI_2),
                         #[cfg(feature = "debug")]
-                        UserEvent::OpenThemeViewerWindow => interface.open_theme_viewer_window(&mut focus_state),
-                        #[cfg(feature = "debug")]
                         UserEvent::OpenProfilerWindow => interface.open_window(&mut focus_state, &ProfilerWindow::new()),
                         #[cfg(feature = "debug")]
                         UserEvent::OpenPacketWindow => interface.open_window(&mut focus_state, &networking_system.packet_window()),
                         #[cfg(feature = "debug")]
                         UserEvent::ClearPacketHistory => networking_system.clear_packet_history(),
                         #[cfg(feature = "debug")]
+                        UserEvent::OpenEventTraceWindow => interface.open_window(&mut focus_state, &user_event_queue.trace_window()),
+                        #[cfg(feature = "debug")]
+                        UserEvent::ClearEventTrace => user_event_queue.clear_trace(),
+                        #[cfg(feature = "debug")]
                         UserEvent::ToggleUseDebugCamera => render_settings.toggle_use_debug_camera(),
                         #[cfg(feature = "debug")]
                         UserEvent::CameraLookAround(offset) => debug_camera.look_around(offset),
@@ -1048,9 +2212,15 @@ fn main() {
                             interface.schedule_render();
                         }
                         #[cfg(feature = "debug")]
-                        UserEvent::ToggleShowMap => render_settings.toggle_show_map(),
+                        UserEvent::ToggleShowMap => {
+                            render_settings.toggle_show_map();
+                            shadow_cache.invalidate();
+                        }
                         #[cfg(feature = "debug")]
-                        UserEvent::ToggleShowObjects => render_settings.toggle_show_objects(),
+                        UserEvent::ToggleShowObjects => {
+                            render_settings.toggle_show_objects();
+                            shadow_cache.invalidate();
+                        }
                         #[cfg(feature = "debug")]
                         UserEvent::ToggleShowEntities => render_settings.toggle_show_entities(),
                         #[cfg(feature = "debug")]
@@ -1097,6 +2267,8 @@ fn main() {
                         UserEvent::ToggleShowPickerBuffer => render_settings.toggle_show_picker_buffer(),
                         #[cfg(feature = "debug")]
                         UserEvent::ToggleShowFontAtlas => render_settings.toggle_show_font_atlas(),
+                        #[cfg(feature = "debug")]
+                        UserEvent::CaptureFrame => capture_frame = true,
                     }
                 }
 
@@ -1110,9 +2282,17 @@ fn main() {
                 #[cfg(feature = "debug")]
                 let update_entities_measuremen = start_measurement("update entities");
 
+                // NOTE: The world stands still during a hit-stop, but the camera and the
+                // interface keep updating.
+                let world_delta_time = screen_effects.update(delta_time);
+
                 entities
                     .iter_mut()
-                    .for_each(|entity| entity.update(&map, delta_time as f32, client_tick));
+                    .for_each(|entity| entity.update(&map, world_delta_time as f32, client_tick));
+
+                if let Some(character_preview) = &mut character_preview {
+                    character_preview.update(&map, delta_time as f32, client_tick);
+                }
 
                 #[cfg(feature = "debug")]
                 update_entities_measuremen.stop();
@@ -1127,16 +2307,83 @@ fn main() {
                 let update_cameras_measuremen = start_measurement("update cameras");
 
                 start_camera.update(delta_time);
+                player_camera.set_shake_offset(screen_effects.camera_offset(graphics_settings.camera_shake));
                 player_camera.update(delta_time);
+                let day_timer = map.lighting_day_timer(day_timer);
                 directional_shadow_camera.update(day_timer);
 
                 #[cfg(feature = "debug")]
                 update_cameras_measuremen.stop();
 
-                particle_holder.update(delta_time as f32);
-                effect_holder.update(&entities, delta_time as f32);
+                particle_holder.update(world_delta_time as f32);
+                ping_holder.update(delta_time as f32);
+                cast_preview_holder.update(world_delta_time as f32);
+                hotbar.update_cooldowns();
+                hotbar.update_items(&player_inventory);
+                ground_item_holder.update(delta_time as f32);
+
+                if !entities.is_empty() {
+                    session_statistics.update(delta_time);
+                }
+
+                if let Some((item_index, equip_position)) = equipment_sets.next_request() {
+                    networking_system.request_item_equip(item_index, equip_position);
+                }
+
+                if combat_timer.update(delta_time as f32) {
+                    interface.set_in_combat(false);
+                }
+
+                // NOTE: Keep alive packets are sent regardless of any input, so the connection
+                // stays alive while the player is away.
+                if let Some(Entity::Player(player)) = entities.first_mut() {
+                    let is_away = away_settings
+                        .away_timeout
+                        .is_some_and(|minutes| input_system.idle_time() >= Duration::from_secs(minutes as u64 * 60));
+
+                    if is_away != player.is_away {
+                        player.is_away = is_away;
 
-                let (clear_interface, render_interface) = interface.update(font_loader.clone(), &mut focus_state, client_tick);
+                        let entity_id = player.get_common().entity_id;
+
+                        if is_away && away_settings.sit_while_away {
+                            networking_system.request_sit_down(entity_id);
+                            sat_down_while_away = true;
+                        } else if !is_away && sat_down_while_away {
+                            networking_system.request_stand_up(entity_id);
+                            sat_down_while_away = false;
+                        }
+                    }
+                }
+                effect_holder.update(&entities, world_delta_time as f32);
+
+                if let Some(benchmark) = &mut benchmark
+                    && !benchmark.update(&map, delta_time, client_tick, &mut start_camera, &mut particle_holder)
+                {
+                    benchmark.write_report();
+                    *control_flow = ControlFlow::Exit;
+                }
+
+                if graphics_settings.preload_adjacent_maps
+                    && let Some(map_name) = map_preloader.update(delta_time)
+                {
+                    map_loader.preload(map_name, &mut game_file_loader);
+                }
+
+                interface.reload_changed_themes(&mut focus_state, delta_time);
+
+                interface.update_tooltip(
+                    hovered_element.as_ref(),
+                    input_system.get_mouse_position(),
+                    input_system.get_mouse_mode(),
+                );
+
+                let (clear_interface, render_interface) = interface.update(
+                    font_loader.clone(),
+                    interface_renderer.get_frame_arena(),
+                    &mut focus_state,
+                    client_tick,
+                );
 
                 if swapchain_holder.is_swapchain_invalid() {
                     #[cfg(feature = "debug")]
@@ -1173,6 +2420,15 @@ fn main() {
                     return;
                 }
 
+                if frame_limit.consume_changed() {
+                    graphics_settings.frame_limit = frame_limit.get();
+                    swapchain_holder.set_frame_limit(present_mode_info, graphics_settings.frame_limit && benchmark.is_none());
+
+                    // NOTE: For some reason the interface buffer becomes messed up when
+                    // recreating the swapchain, so we need to render it again.
+                    interface.schedule_render();
+                }
+
                 if shadow_detail.consume_changed() {
                     #[cfg(feature = "debug")]
                     print_debug!("re-creating {}directional shadow targets{}", MAGENTA, NONE);
@@ -1188,6 +2444,121 @@ fn main() {
                         .into_iter()
                         .map(|_| shadow_renderer.create_render_target(new_shadow_detail.into_resolution()))
                         .collect::<Vec<<ShadowRenderer as Renderer>::Target>>();
+
+                    entity_shadow_targets = swapchain_holder
+                        .get_swapchain_images()
+                        .into_iter()
+                        .map(|_| shadow_renderer.create_render_target(new_shadow_detail.into_resolution()))
+                        .collect::<Vec<<ShadowRenderer as Renderer>::Target>>();
+
+                    shadow_cache.invalidate();
+                }
+
+                if speech_bubble_range.consume_changed() {
+                    graphics_settings.speech_bubble_range = speech_bubble_range.get();
+                }
+
+                if entity_render_distance.consume_changed() {
+                    graphics_settings.entity_render_distance = entity_render_distance.get();
+                }
+
+                if maximum_rendered_entities.consume_changed() {
+                    graphics_settings.maximum_rendered_entities = maximum_rendered_entities.get();
+                }
+
+                if sprite_filtering.consume_changed() {
+                    let new_sprite_filtering = sprite_filtering.get();
+                    graphics_settings.sprite_filtering = new_sprite_filtering;
+                    deferred_renderer.set_sprite_filtering(new_sprite_filtering);
+                    interface_renderer.set_sprite_filtering(new_sprite_filtering);
+                }
+
+                if sampler_configuration.consume_changed() {
+                    graphics_settings.sampler_configuration = sampler_configuration.get();
+                    deferred_renderer.set_sampler_configuration(&graphics_settings.sampler_configuration);
+                    interface_renderer.set_sampler_configuration(&graphics_settings.sampler_configuration);
+                }
+
+                if check_for_updates.consume_changed() {
+                    update_settings.check_for_updates = check_for_updates.get();
+                }
+
+                if animations.consume_changed() {
+                    interface.set_animations(animations.get());
+                }
+
+                if streamer_mode.consume_changed() {
+                    interface.set_streamer_mode(streamer_mode.get());
+                }
+
+                if combat_window_mode.consume_changed() {
+                    interface.set_combat_window_mode(combat_window_mode.get());
+                }
+
+                if update_check.as_ref().is_some_and(UpdateCheck::is_finished) {
+                    match update_check.take().unwrap().result() {
+                        Ok(Some(release)) => interface.open_window(&mut focus_state, &VersionWindow::new(release)),
+                        Ok(None) => {}
+                        Err(_error) => {
+                            #[cfg(feature = "debug")]
+                            print_debug!("[{RED}error{NONE}] failed to check for updates: {_error}");
+                        }
+                    }
+                }
+
+                if show_speech_bubbles.consume_changed() {
+                    graphics_settings.show_speech_bubbles = show_speech_bubbles.get();
+                }
+
+                if show_path_preview.consume_changed() {
+                    graphics_settings.show_path_preview = show_path_preview.get();
+                }
+
+                if show_quest_markers.consume_changed() {
+                    graphics_settings.show_quest_markers = show_quest_markers.get();
+                }
+
+                if preload_adjacent_maps.consume_changed() {
+                    graphics_settings.preload_adjacent_maps = preload_adjacent_maps.get();
+                }
+
+                if camera_shake.consume_changed() {
+                    graphics_settings.camera_shake = camera_shake.get();
+                }
+
+                if hit_stop.consume_changed() {
+                    graphics_settings.hit_stop = hit_stop.get();
+                }
+
+                if cast_previews.consume_changed() {
+                    graphics_settings.cast_previews = cast_previews.get();
+                }
+
+                if away_timeout.consume_changed() {
+                    away_settings.away_timeout = away_timeout.get();
+                }
+
+                if sit_while_away.consume_changed() {
+                    away_settings.sit_while_away = sit_while_away.get();
+                }
+
+                if target_priority.consume_changed() {
+                    graphics_settings.target_priority = target_priority.get();
+                }
+
+                if mute.consume_changed() {
+                    audio_settings.mute = mute.get();
+                    audio_engine.set_mute(audio_settings.mute);
+                }
+
+                if background_music_volume.consume_changed() {
+                    audio_settings.background_music_volume = background_music_volume.get();
+                    audio_engine.set_background_music_volume(audio_settings.background_music_volume);
+                }
+
+                if sound_effect_volume.consume_changed() {
+                    audio_settings.sound_effect_volume = sound_effect_volume.get();
+                    audio_engine.set_sound_effect_volume(audio_settings.sound_effect_volume);
                 }
 
                 #[cfg(feature = "debug")]
@@ -1214,6 +2585,14 @@ fn main() {
                     false => &player_camera,
                 };
 
+                let listener_position = match entities.is_empty() {
+                    true => start_camera.get_focus_point().to_vec(),
+                    false => entities[0].get_position(),
+                };
+
+                audio_engine.set_listener(listener_position, current_camera);
+                audio_engine.update(delta_time as f32);
+
                 if let Some(mut fence) = screen_targets[swapchain_holder.get_image_number()].state.try_take_fence() {
                     #[cfg(feature = "debug")]
                     profile_block!("wait for frame in current slot");
@@ -1250,11 +2629,67 @@ fn main() {
                 let prepare_frame_measuremen = start_measurement("prepare frame");
 
                 let walk_indicator_color = interface.get_game_theme().indicator.walking.get();
+                let path_indicator_color = interface.get_game_theme().indicator.path.get();
+                let ping_indicator_color = interface.get_game_theme().indicator.ping.get();
+                let cast_area_color = interface.get_game_theme().indicator.cast_area.get();
+                let cast_target_color = interface.get_game_theme().indicator.cast_target.get();
+                let loot_highlight_color = interface.get_game_theme().indicator.loot.get();
+                let search_indicator_color = interface.get_game_theme().indicator.search.get();
+                let entity_filter = entity_search.get_filter();
                 let image_number = swapchain_holder.get_image_number();
                 let directional_shadow_image = directional_shadow_targets[image_number].image.clone();
+                let entity_shadow_image = entity_shadow_targets[image_number].image.clone();
+                let (view_matrix, projection_matrix) = directional_shadow_camera.view_projection_matrices();
+                let light_matrix = projection_matrix * view_matrix;
+                let idle = idle_state.is_idle();
+                // NOTE: While idle, static shadows are only rendered if there are none yet and
+                // entity shadows are skipped entirely.
+                let update_static_shadows = match idle && shadow_cache.is_cached(image_number) {
+                    true => false,
+                    false => shadow_cache.update(image_number, light_matrix),
+                };
                 let screen_target = &mut screen_targets[image_number];
                 let window_size = swapchain_holder.window_screen_size();
                 let window_size_u32 = swapchain_holder.window_size_u32();
+
+                if let Some(PickerTarget::Tile { x, y }) = mouse_target
+                    && graphics_settings.show_path_preview
+                    && !entities.is_empty()
+                {
+                    let from = entities[0].get_grid_position();
+                    let to = Vector2::new(x as usize, y as usize);
+
+                    // NOTE: Only search for a new path if the player or the hovered tile changed.
+                    if path_preview
+                        .as_ref()
+                        .map_or(true, |(start, destination, _)| *start != from || *destination != to)
+                    {
+                        path_preview = Some((from, to, map.find_path(from, to).unwrap_or_default()));
+                    }
+                } else {
+                    path_preview = None;
+                }
+
+                let preview_entities: Vec<&Entity> = match entities.is_empty() {
+                    true => character_preview
+                        .iter()
+                        .chain(benchmark.iter().flat_map(|benchmark| benchmark.entities()))
+                        .collect(),
+                    false => Vec::new(),
+                };
+                let preview_entities = &preview_entities[..];
+                let target_entity_id = match target_stack.selected() {
+                    Some(StackedTarget::Entity(entity_id)) => Some(entity_id),
+                    _ => None,
+                };
+                let visible_entities = select_visible_entities(
+                    &entities,
+                    graphics_settings.entity_render_distance,
+                    graphics_settings.maximum_rendered_entities,
+                    target_entity_id,
+                    networking_system.get_party_members(),
+                );
+                let visible_entities = &visible_entities[..];
                 let entities = &entities[..];
                 #[cfg(feature = "debug")]
                 let hovered_marker_identifier = match mouse_target {
@@ -1278,7 +2713,7 @@ fn main() {
                         map.render_tiles(picker_target, &picker_renderer, current_camera);
 
                         #[debug_condition(render_settings.show_entities)]
-                        map.render_entities(entities, picker_target, &picker_renderer, current_camera, false);
+                        map.render_entities(visible_entities, picker_target, &picker_renderer, current_camera, false, None);
 
                         #[cfg(feature = "debug")]
                         map.render_markers(
@@ -1297,52 +2732,88 @@ fn main() {
                         #[cfg(feature = "debug")]
                         let _measurement = profiler_start_shadow_thread();
 
-                        let directional_shadow_target = &mut directional_shadow_targets[image_number];
+                        if update_static_shadows {
+                            let directional_shadow_target = &mut directional_shadow_targets[image_number];
 
-                        directional_shadow_target.start();
+                            directional_shadow_target.start();
 
-                        #[debug_condition(render_settings.show_map)]
-                        map.render_ground(
-                            directional_shadow_target,
-                            &shadow_renderer,
-                            &directional_shadow_camera,
-                            animation_timer,
-                        );
+                            #[debug_condition(render_settings.show_map)]
+                            map.render_ground(
+                                directional_shadow_target,
+                                &shadow_renderer,
+                                &directional_shadow_camera,
+                                animation_timer,
+                            );
 
-                        #[debug_condition(render_settings.show_objects)]
-                        map.render_objects(
-                            directional_shadow_target,
-                            &shadow_renderer,
-                            &directional_shadow_camera,
-                            client_tick,
-                            animation_timer,
-                            #[cfg(feature = "debug")]
-                            render_settings.frustum_culling,
-                        );
+                            #[debug_condition(render_settings.show_objects)]
+                            map.render_objects(
+                                directional_shadow_target,
+                                &shadow_renderer,
+                                &directional_shadow_camera,
+                                client_tick,
+                                animation_timer,
+                                ObjectSelection::Static,
+                                #[cfg(feature = "debug")]
+                                render_settings.frustum_culling,
+                            );
 
-                        #[debug_condition(render_settings.show_entities)]
-                        map.render_entities(
-                            entities,
-                            directional_shadow_target,
-                            &shadow_renderer,
-                            &directional_shadow_camera,
-                            true,
-                        );
+                            directional_shadow_target.finish();
+                        }
 
-                        if let Some(PickerTarget::Tile { x, y }) = mouse_target
-                            && !entities.is_empty()
-                        {
-                            #[debug_condition(render_settings.show_indicators)]
-                            map.render_walk_indicator(
-                                directional_shadow_target,
+                        let entity_shadow_target = &mut entity_shadow_targets[image_number];
+
+                        entity_shadow_target.start();
+
+                        // NOTE: The target is still cleared, so no outdated entity shadows remain.
+                        if !idle {
+                            // NOTE: Animated objects are not part of the cached static shadows.
+                            #[debug_condition(render_settings.show_objects)]
+                            map.render_objects(
+                                entity_shadow_target,
                                 &shadow_renderer,
                                 &directional_shadow_camera,
-                                walk_indicator_color,
-                                Vector2::new(x as usize, y as usize),
+                                client_tick,
+                                animation_timer,
+                                ObjectSelection::Animated,
+                                #[cfg(feature = "debug")]
+                                render_settings.frustum_culling,
                             );
+
+                            #[debug_condition(render_settings.show_entities)]
+                            map.render_entities(
+                                visible_entities,
+                                entity_shadow_target,
+                                &shadow_renderer,
+                                &directional_shadow_camera,
+                                true,
+                                None,
+                            );
+
+                            #[debug_condition(render_settings.show_entities)]
+                            map.render_entities(
+                                preview_entities,
+                                entity_shadow_target,
+                                &shadow_renderer,
+                                &directional_shadow_camera,
+                                true,
+                                None,
+                            );
+
+                            if let Some(PickerTarget::Tile { x, y }) = mouse_target
+                                && !entities.is_empty()
+                            {
+                                #[debug_condition(render_settings.show_indicators)]
+                                map.render_walk_indicator(
+                                    entity_shadow_target,
+                                    &shadow_renderer,
+                                    &directional_shadow_camera,
+                                    walk_indicator_color,
+                                    Vector2::new(x as usize, y as usize),
+                                );
+                            }
                         }
 
-                        directional_shadow_target.finish();
+                        entity_shadow_target.finish();
                     });
 
                     scope.spawn(|_| {
@@ -1366,16 +2837,47 @@ fn main() {
                             current_camera,
                             client_tick,
                             animation_timer,
+                            ObjectSelection::All,
                             #[cfg(feature = "debug")]
                             render_settings.frustum_culling,
                         );
 
                         #[debug_condition(render_settings.show_entities)]
-                        map.render_entities(entities, screen_target, &deferred_renderer, current_camera, true);
+                        map.render_entities(
+                            visible_entities,
+                            screen_target,
+                            &deferred_renderer,
+                            current_camera,
+                            true,
+                            entity_filter.as_ref(),
+                        );
+
+                        #[debug_condition(render_settings.show_entities)]
+                        map.render_entities(preview_entities, screen_target, &deferred_renderer, current_camera, true, None);
 
                         #[debug_condition(render_settings.show_water)]
                         map.render_water(screen_target, &deferred_renderer, current_camera, animation_timer);
 
+                        #[debug_condition(render_settings.show_indicators)]
+                        ping_holder.render_indicators(screen_target, &deferred_renderer, current_camera, &map, ping_indicator_color);
+
+                        #[debug_condition(render_settings.show_indicators)]
+                        cast_preview_holder.render_indicators(
+                            screen_target,
+                            &deferred_renderer,
+                            current_camera,
+                            &map,
+                            &entities,
+                            graphics_settings.cast_previews,
+                            cast_area_color,
+                            cast_target_color,
+                        );
+
+                        if let Some((_, _, path)) = &path_preview {
+                            #[debug_condition(render_settings.show_indicators)]
+                            map.render_path_indicator(screen_target, &deferred_renderer, current_camera, path_indicator_color, path);
+                        }
+
                         if let Some(PickerTarget::Tile { x, y }) = mouse_target
                             && !entities.is_empty()
                         {
@@ -1394,15 +2896,13 @@ fn main() {
                         #[debug_condition(render_settings.show_ambient_light && !render_settings.show_buffers())]
                         map.ambient_light(screen_target, &deferred_renderer, day_timer);
 
-                        let (view_matrix, projection_matrix) = directional_shadow_camera.view_projection_matrices();
-                        let light_matrix = projection_matrix * view_matrix;
-
                         #[debug_condition(render_settings.show_directional_light && !render_settings.show_buffers())]
                         map.directional_light(
                             screen_target,
                             &deferred_renderer,
                             current_camera,
                             directional_shadow_image.clone(),
+                            entity_shadow_image,
                             light_matrix,
                             day_timer,
                         );
@@ -1413,6 +2913,21 @@ fn main() {
                         #[debug_condition(render_settings.show_water && !render_settings.show_buffers())]
                         map.water_light(screen_target, &deferred_renderer, current_camera);
 
+                        #[debug_condition(!render_settings.show_buffers())]
+                        map.fog(screen_target, &deferred_renderer, current_camera);
+
+                        #[debug_condition(render_settings.show_objects && !render_settings.show_buffers())]
+                        map.render_transparent_objects(
+                            screen_target,
+                            &deferred_renderer,
+                            current_camera,
+                            client_tick,
+                            animation_timer,
+                            day_timer,
+                            #[cfg(feature = "debug")]
+                            render_settings.frustum_culling,
+                        );
+
                         #[cfg(feature = "debug")]
                         map.render_markers(
                             screen_target,
@@ -1440,6 +2955,39 @@ fn main() {
                         }
 
                         particle_holder.render(screen_target, &deferred_renderer, current_camera, window_size, entities);
+
+                        if graphics_settings.show_quest_markers {
+                            particle_holder.render_navigation_marker(screen_target, &deferred_renderer, current_camera, window_size);
+                        }
+
+                        ping_holder.render_labels(
+                            screen_target,
+                            &deferred_renderer,
+                            current_camera,
+                            window_size,
+                            &map,
+                            ping_indicator_color,
+                        );
+                        ground_item_holder.render_labels(
+                            screen_target,
+                            &deferred_renderer,
+                            current_camera,
+                            window_size,
+                            &map,
+                            loot_highlight_color,
+                        );
+                        ground_item_holder.render_toasts(screen_target, &deferred_renderer, window_size, loot_highlight_color);
+
+                        if let Some(entity_filter) = &entity_filter {
+                            entity_filter.render_arrows(
+                                screen_target,
+                                &deferred_renderer,
+                                current_camera,
+                                window_size,
+                                entities,
+                                search_indicator_color,
+                            );
+                        }
                         effect_holder.render(screen_target, &deferred_renderer, current_camera);
                     });
 
@@ -1451,6 +2999,7 @@ fn main() {
 
                         let state_provider = &StateProvider::new(
                             &graphics_settings,
+                            &audio_settings,
                             #[cfg(feature = "debug")]
                             &render_settings,
                         );
@@ -1486,26 +3035,80 @@ fn main() {
                     );
                 }
 
-                if let Some(PickerTarget::Entity(entity_id)) = mouse_target {
-                    #[cfg(feature = "debug")]
-                    profile_block!("render hovered entity status");
+                particle_holder.render_vending_titles(
+                    screen_target,
+                    &deferred_renderer,
+                    current_camera,
+                    window_size,
+                    entities,
+                    &interface.get_game_theme().speech_bubble,
+                );
+
+                if graphics_settings.show_speech_bubbles {
+                    particle_holder.render_speech_bubbles(
+                        screen_target,
+                        &deferred_renderer,
+                        current_camera,
+                        window_size,
+                        entities,
+                        &interface.get_game_theme().speech_bubble,
+                        graphics_settings.speech_bubble_range,
+                    );
+                }
 
-                    let entity = entities.iter().find(|entity| entity.get_entity_id() == entity_id);
+                let hover_text = match target_stack.selected() {
+                    Some(StackedTarget::Entity(entity_id)) => {
+                        #[cfg(feature = "debug")]
+                        profile_block!("render hovered entity status");
 
-                    if let Some(entity) = entity {
-                        entity.render_status(
-                            screen_target,
-                            &deferred_renderer,
-                            current_camera,
-                            interface.get_game_theme(),
-                            window_size,
-                        );
+                        let entity = entities.iter().find(|entity| entity.get_entity_id() == entity_id);
 
-                        if let Some(name) = &entity.get_details() {
-                            let name = name.split('#').next().unwrap();
-                            interface.render_hover_text(screen_target, &deferred_renderer, name, input_system.get_mouse_position());
-                        }
+                        entity.and_then(|entity| {
+                            entity.render_status(
+                                screen_target,
+                                &deferred_renderer,
+                                current_camera,
+                                interface.get_game_theme(),
+                                window_size,
+                            );
+
+                            // NOTE: The map server doesn't send a guild id for the player entity.
+                            let guild_id = match entities[0].get_entity_id() == entity.get_entity_id() {
+                                true => networking_system.get_guild_id(),
+                                false => entity.get_guild_id(),
+                            };
+                            let emblem = guild_id.and_then(|guild_id| networking_system.get_guild_emblem(guild_id));
+
+                            entity.get_details().map(|name| {
+                                let name = name.split('#').next().unwrap();
+                                let name = match entity.get_entity_type() {
+                                    EntityType::Player => interface.get_streamer_mode().hide_name(name),
+                                    _ => Cow::Borrowed(name),
+                                };
+
+                                (name, emblem)
+                            })
+                        })
                     }
+                    Some(StackedTarget::GroundItem(entity_id)) => {
+                        ground_item_holder.get_label(entity_id).map(|label| (Cow::Borrowed(label), None))
+                    }
+                    None => None,
+                };
+
+                if let Some((hover_text, emblem)) = hover_text {
+                    let hover_text = match target_stack.cycle_position() {
+                        Some((position, count)) => Cow::Owned(format!("{hover_text} ({position}/{count})")),
+                        None => hover_text,
+                    };
+
+                    interface.render_hover_text(
+                        screen_target,
+                        &deferred_renderer,
+                        &hover_text,
+                        emblem,
+                        input_system.get_mouse_position(),
+                    );
                 }
 
                 if !entities.is_empty() {
@@ -1544,16 +3147,59 @@ fn main() {
                     .state
                     .try_take_semaphore()
                     .unwrap_or_else(|| now(device.clone()).boxed());
-                let directional_shadow_future = directional_shadow_targets[image_number].state.take_semaphore();
+                let directional_shadow_future = directional_shadow_targets[image_number]
+                    .state
+                    .try_take_semaphore()
+                    .unwrap_or_else(|| now(device.clone()).boxed());
+                let entity_shadow_future = entity_shadow_targets[image_number].state.take_semaphore();
                 let swapchain_acquire_future = swapchain_holder.take_acquire_future();
 
                 let combined_future = interface_future
                     .join(directional_shadow_future)
+                    .join(entity_shadow_future)
                     .join(swapchain_acquire_future)
                     .boxed();
 
                 screen_target.finish(swapchain_holder.get_swapchain(), combined_future, image_number);
 
+                #[cfg(feature = "debug")]
+                if capture_frame {
+                    capture_frame = false;
+
+                    // NOTE: The render targets can only be copied once the GPU is done with
+                    // the frame. This stalls a single frame, which is fine for a debug tool.
+                    if let RenderTargetState::Fence(_) = screen_target.state
+                        && let Some(mut fence) = screen_target.state.try_take_fence()
+                    {
+                        fence.wait(None).unwrap();
+                        fence.cleanup_finished();
+                    }
+
+                    let mut capture_targets = screen_target.capture_targets();
+                    capture_targets.push(CaptureTarget::new(
+                        "directional_shadow",
+                        directional_shadow_targets[image_number].image.clone(),
+                        CaptureEncoding::Depth,
+                    ));
+                    capture_targets.push(CaptureTarget::new(
+                        "entity_shadow",
+                        entity_shadow_targets[image_number].image.clone(),
+                        CaptureEncoding::Depth,
+                    ));
+                    capture_targets.push(CaptureTarget::new(
+                        "interface",
+                        interface_target.image.clone(),
+                        CaptureEncoding::Color,
+                    ));
+
+                    let result = capture_render_targets(&memory_allocator, queue.clone(), capture_targets);
+                    interface.handle_result(&mut focus_state, result);
+                }
+
+                // NOTE: Nothing allocated in the frame arena outlives the frame, so it can
+                // be reused for the next one.
+                interface_renderer.reset_frame_arena();
+
                 #[cfg(feature = "debug")]
                 finalize_frame_measuremen.stop();
             }
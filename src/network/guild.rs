@@ -0,0 +1,248 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use vulkano::image::view::ImageView;
+
+use super::{AccountId, CharacterId};
+use crate::interface::{Remote, TrackedState, ValueState};
+
+/// Bits of the mode of a [`GuildPosition`].
+const POSITION_MODE_INVITE: u32 = 0x01;
+const POSITION_MODE_EXPEL: u32 = 0x10;
+
+#[derive(Clone, Debug)]
+pub struct GuildInfo {
+    pub guild_id: u32,
+    pub emblem_id: u32,
+    pub name: String,
+    pub level: u32,
+    pub online_member_count: u32,
+    pub maximum_member_count: u32,
+    pub average_level: u32,
+    pub experience: u32,
+    pub next_experience: u32,
+    pub master_character_id: CharacterId,
+}
+
+#[derive(Clone, Debug)]
+pub struct GuildMember {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    /// Newer clients don't get the names with the member list, so this stays
+    /// empty until the map server answers the name request.
+    pub name: String,
+    pub level: u16,
+    pub position_id: u32,
+    pub is_online: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GuildPosition {
+    pub position_id: u32,
+    pub name: String,
+    pub mode: u32,
+    pub pay_rate: u32,
+}
+
+impl GuildPosition {
+    pub fn can_invite(&self) -> bool {
+        self.mode & POSITION_MODE_INVITE != 0
+    }
+
+    pub fn can_expel(&self) -> bool {
+        self.mode & POSITION_MODE_EXPEL != 0
+    }
+
+    pub fn with_permissions(&self, can_invite: bool, can_expel: bool) -> Self {
+        let mode = (self.mode & !(POSITION_MODE_INVITE | POSITION_MODE_EXPEL))
+            | (can_invite as u32 * POSITION_MODE_INVITE)
+            | (can_expel as u32 * POSITION_MODE_EXPEL);
+
+        Self { mode, ..self.clone() }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GuildNotice {
+    pub subject: String,
+    pub notice: String,
+}
+
+/// Everything the client knows about the guild of the player. The values are
+/// only sent by the map server once requested, see
+/// [`request_guild_information`](super::NetworkingSystem::request_guild_information).
+#[derive(Default)]
+pub struct Guild {
+    pub(super) guild_id: Option<u32>,
+    pub(super) info: TrackedState<Option<GuildInfo>>,
+    pub(super) members: TrackedState<Vec<GuildMember>>,
+    pub(super) positions: TrackedState<Vec<GuildPosition>>,
+    pub(super) notice: TrackedState<GuildNotice>,
+    /// Emblems of all guilds that were seen so far, including the ones of
+    /// other players, by guild id.
+    pub(super) emblems: TrackedState<HashMap<u32, Arc<ImageView>>>,
+    /// Emblem id of every emblem in `emblems`, so changed emblems are
+    /// requested again.
+    pub(super) emblem_ids: HashMap<u32, u32>,
+    pub(super) requested_emblems: HashSet<u32>,
+}
+
+impl Guild {
+    pub(super) fn set_member_name(&mut self, character_id: CharacterId, name: String) {
+        self.members.with_mut(
+            |members| match members.iter_mut().find(|member| member.character_id == character_id) {
+                Some(member) => {
+                    member.name = name;
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            },
+        );
+    }
+
+    pub(super) fn set_position_names(&mut self, names: Vec<(u32, String)>) {
+        self.positions.with_mut(|positions| {
+            for (position_id, name) in names {
+                match positions.iter_mut().find(|position| position.position_id == position_id) {
+                    Some(position) => position.name = name,
+                    None => positions.push(GuildPosition {
+                        position_id,
+                        name,
+                        ..Default::default()
+                    }),
+                }
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub(super) fn update_positions(&mut self, updated_positions: Vec<GuildPosition>) {
+        self.positions.with_mut(|positions| {
+            for updated_position in updated_positions {
+                match positions
+                    .iter_mut()
+                    .find(|position| position.position_id == updated_position.position_id)
+                {
+                    Some(position) => {
+                        position.mode = updated_position.mode;
+                        position.pay_rate = updated_position.pay_rate;
+
+                        // NOTE: Position info packets don't contain names.
+                        if !updated_position.name.is_empty() {
+                            position.name = updated_position.name;
+                        }
+                    }
+                    None => positions.push(updated_position),
+                }
+            }
+
+            ValueState::Mutated(())
+        });
+    }
+
+    pub(super) fn update_member_position(&mut self, character_id: CharacterId, position_id: u32) {
+        self.members.with_mut(
+            |members| match members.iter_mut().find(|member| member.character_id == character_id) {
+                Some(member) => {
+                    member.position_id = position_id;
+                    ValueState::Mutated(())
+                }
+                None => ValueState::Unchanged(()),
+            },
+        );
+    }
+
+    /// Returns whether the emblem of a guild should be requested. If the
+    /// emblem id is unknown, any emblem of the guild is considered up to date.
+    pub(super) fn request_emblem(&mut self, guild_id: u32, emblem_id: Option<u32>) -> bool {
+        let is_current = match emblem_id {
+            Some(emblem_id) => self.emblem_ids.get(&guild_id) == Some(&emblem_id),
+            None => self.emblem_ids.contains_key(&guild_id),
+        };
+
+        !is_current && self.requested_emblems.insert(guild_id)
+    }
+
+    pub(super) fn set_emblem(&mut self, guild_id: u32, emblem_id: u32, emblem: Arc<ImageView>) {
+        self.requested_emblems.remove(&guild_id);
+        self.emblem_ids.insert(guild_id, emblem_id);
+        self.emblems.with_mut(|emblems| {
+            emblems.insert(guild_id, emblem);
+            ValueState::Mutated(())
+        });
+    }
+
+    pub fn get_guild_id(&self) -> Option<u32> {
+        self.guild_id
+    }
+
+    pub fn get_info(&self) -> Remote<Option<GuildInfo>> {
+        self.info.new_remote()
+    }
+
+    pub fn get_members(&self) -> Remote<Vec<GuildMember>> {
+        self.members.new_remote()
+    }
+
+    pub fn get_positions(&self) -> Remote<Vec<GuildPosition>> {
+        self.positions.new_remote()
+    }
+
+    pub fn get_notice(&self) -> Remote<GuildNotice> {
+        self.notice.new_remote()
+    }
+
+    pub fn get_emblems(&self) -> Remote<HashMap<u32, Arc<ImageView>>> {
+        self.emblems.new_remote()
+    }
+
+    pub fn get_emblem(&self, guild_id: u32) -> Option<Arc<ImageView>> {
+        self.emblems.borrow().get(&guild_id).cloned()
+    }
+
+    pub fn clear(&mut self) {
+        self.guild_id = None;
+        self.info.set(None);
+        self.members.set(Vec::new());
+        self.positions.set(Vec::new());
+        self.notice.set(GuildNotice::default());
+        // NOTE: Emblems are kept, since they are cached by guild id, but pending
+        // requests will never be answered.
+        self.requested_emblems.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Guild;
+
+    #[test]
+    fn emblems_are_requested_once() {
+        let mut guild = Guild::default();
+
+        assert!(guild.request_emblem(7, Some(1)));
+        assert!(!guild.request_emblem(7, Some(1)));
+        assert!(!guild.request_emblem(7, None));
+        assert!(guild.request_emblem(8, None));
+    }
+
+    #[test]
+    fn changed_emblems_are_requested_again() {
+        let mut guild = Guild::default();
+        guild.emblem_ids.insert(7, 1);
+
+        assert!(!guild.request_emblem(7, Some(1)));
+        assert!(!guild.request_emblem(7, None));
+        assert!(guild.request_emblem(7, Some(2)));
+    }
+
+    #[test]
+    fn clear_forgets_pending_requests() {
+        let mut guild = Guild::default();
+
+        assert!(guild.request_emblem(7, None));
+        guild.clear();
+        assert!(guild.request_emblem(7, None));
+    }
+}
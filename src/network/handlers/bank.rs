@@ -0,0 +1,41 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    BankBalancePacket, BankDepositResultPacket, BankWithdrawResultPacket, ChatMessage, CloseBankResultPacket, NetworkEvent,
+    OpenBankResultPacket,
+};
+
+fn bank_message(message: &str) -> NetworkEvent {
+    let color = Color::rgb_u8(255, 100, 100);
+    NetworkEvent::ChatMessage(ChatMessage::new(message.to_owned(), color))
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: OpenBankResultPacket, events| match packet.result {
+        0 => events.push(NetworkEvent::BankOpened),
+        _ => events.push(bank_message("The bank is not available right now.")),
+    });
+
+    registry.ignore::<CloseBankResultPacket>();
+
+    registry.register(|_, packet: BankBalancePacket, events| {
+        events.push(NetworkEvent::BankBalance(packet.balance));
+    });
+
+    // NOTE: The map server also sends the balance if the transaction failed.
+    registry.register(|_, packet: BankDepositResultPacket, events| {
+        if let Some(message) = packet.message() {
+            events.push(bank_message(message));
+        }
+
+        events.push(NetworkEvent::BankBalance(packet.balance));
+    });
+
+    registry.register(|_, packet: BankWithdrawResultPacket, events| {
+        if let Some(message) = packet.message() {
+            events.push(bank_message(message));
+        }
+
+        events.push(NetworkEvent::BankBalance(packet.balance));
+    });
+}
@@ -0,0 +1,48 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    AddItemToCartResultPacket, CartInformation, CartInformationPacket, CartItemAddedPacket, CartItemRemovedPacket, CartRemovedPacket,
+    ChatMessage, EquipPosition, InventoryItemData, NetworkEvent,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: CartInformationPacket, events| {
+        events.push(NetworkEvent::UpdateCartInformation(CartInformation {
+            item_count: packet.item_count,
+            maximum_item_count: packet.maximum_item_count,
+            weight: packet.weight,
+            maximum_weight: packet.maximum_weight,
+        }));
+    });
+
+    registry.register(|_, packet: CartItemAddedPacket, events| {
+        events.push(NetworkEvent::AddCartItem(InventoryItemData {
+            index: packet.index,
+            item_id: packet.item_id,
+            equip_position: EquipPosition::None,
+            equipped_position: EquipPosition::None,
+            item_type: packet.item_type,
+            amount: packet.amount as u16,
+            refinement_level: packet.refinement_level,
+            cards: packet.cards,
+            is_broken: packet.is_broken != 0,
+            is_favorite: false,
+        }));
+    });
+
+    registry.register(|_, packet: CartItemRemovedPacket, events| {
+        events.push(NetworkEvent::RemoveCartItem {
+            index: packet.index,
+            amount: packet.amount as u16,
+        });
+    });
+
+    registry.register(|_, _: CartRemovedPacket, events| {
+        events.push(NetworkEvent::CartRemoved);
+    });
+
+    registry.register(|_, packet: AddItemToCartResultPacket, events| {
+        let color = Color::rgb_u8(220, 200, 30);
+        events.push(NetworkEvent::ChatMessage(ChatMessage::new(packet.message().to_owned(), color)));
+    });
+}
@@ -0,0 +1,68 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    decode_text, parse_party_ping, Broadcast2MessagePacket, BroadcastMessagePacket, ChatMessage, EntityId, EntityMessagePacket,
+    NetworkEvent, NotifyPartyMessagePacket, OverheadMessagePacket, ServerMessagePacket,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: BroadcastMessagePacket, events| {
+        let color = Color::rgb_u8(220, 200, 30);
+        let chat_message = ChatMessage::new(decode_text(packet.message), color);
+        events.push(NetworkEvent::ChatMessage(chat_message));
+    });
+
+    registry.register(|_, packet: Broadcast2MessagePacket, events| {
+        // NOTE: Drop the alpha channel because it might be 0.
+        let color = Color::rgb_u8(packet.font_color.red, packet.font_color.green, packet.font_color.blue);
+        let chat_message = ChatMessage::new(decode_text(packet.message), color);
+        events.push(NetworkEvent::ChatMessage(chat_message));
+    });
+
+    registry.register(|_, packet: OverheadMessagePacket, events| {
+        let color = Color::monochrome_u8(230);
+        let chat_message = ChatMessage::new(decode_text(packet.message.clone()), color);
+        events.push(NetworkEvent::ChatMessage(chat_message));
+        events.push(NetworkEvent::EntityMessage(packet.entity_id, decode_text(packet.message)));
+    });
+
+    registry.register(|networking_system, packet: ServerMessagePacket, events| {
+        // NOTE: The server echoes our own public chat messages with this packet, so we
+        // use the name prefix to tell them apart from other server messages.
+        if let Some(login_data) = &networking_system.login_data
+            && packet.message.starts_with(&format!("{} : ", networking_system.player_name))
+        {
+            let entity_id = EntityId(login_data.account_id.0);
+            events.push(NetworkEvent::EntityMessage(entity_id, decode_text(packet.message.clone())));
+        }
+
+        let chat_message = ChatMessage::new(decode_text(packet.message), Color::monochrome_u8(255));
+        events.push(NetworkEvent::ChatMessage(chat_message));
+    });
+
+    registry.register(|_, packet: EntityMessagePacket, events| {
+        // NOTE: Drop the alpha channel because it might be 0.
+        let color = Color::rgb_u8(packet.color.red, packet.color.green, packet.color.blue);
+        let chat_message = ChatMessage::new(decode_text(packet.message.clone()), color);
+        events.push(NetworkEvent::ChatMessage(chat_message));
+        events.push(NetworkEvent::EntityMessage(packet.entity_id, decode_text(packet.message)));
+    });
+
+    registry.register(
+        |networking_system, packet: NotifyPartyMessagePacket, events| match parse_party_ping(&packet.message) {
+            Some((sender, map_name, position)) => {
+                if map_name == networking_system.map_name {
+                    events.push(NetworkEvent::PartyPing {
+                        sender: sender.to_owned(),
+                        position,
+                    });
+                }
+            }
+            None => {
+                let color = Color::rgb_u8(255, 200, 200);
+                let chat_message = ChatMessage::new(decode_text(packet.message), color);
+                events.push(NetworkEvent::ChatMessage(chat_message));
+            }
+        },
+    );
+}
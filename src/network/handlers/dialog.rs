@@ -0,0 +1,29 @@
+use super::PacketHandlerRegistry;
+use crate::network::{CloseButtonPacket, DialogMenuPacket, DisplayImagePacket, NetworkEvent, NextButtonPacket, NpcDialogPacket};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: NpcDialogPacket, events| {
+        events.push(NetworkEvent::OpenDialog(packet.text, packet.npc_id));
+    });
+
+    registry.register(|_, _: NextButtonPacket, events| {
+        events.push(NetworkEvent::AddNextButton);
+    });
+
+    registry.register(|_, _: CloseButtonPacket, events| {
+        events.push(NetworkEvent::AddCloseButton);
+    });
+
+    registry.register(|_, packet: DialogMenuPacket, events| {
+        let choices = packet
+            .message
+            .split(':')
+            .map(String::from)
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        events.push(NetworkEvent::AddChoiceButtons(choices));
+    });
+
+    registry.ignore::<DisplayImagePacket>();
+}
@@ -0,0 +1,103 @@
+use super::PacketHandlerRegistry;
+use crate::network::{
+    decode_text, DamagePacket, DisplayEmotionPacket, EntityAppeared2Packet, EntityAppearedPacket, EntityDisappearedPacket, EntityId,
+    EntityMovePacket, EntityStopMovePacket, MovingEntityAppearedPacket, NetworkEvent, PlayerMovePacket, QuestEffect, QuestEffectPacket,
+    RequestEntityDetailsSuccessPacket, RequestPlayerAttackFailedPacket, RequestPlayerDetailsSuccessPacket, SpriteChangePacket,
+    UpdateEntityHealthPointsPacket, VisualEffect, VisualEffectPacket,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: EntityAppearedPacket, events| {
+        events.push(NetworkEvent::AddEntity(packet.into()));
+    });
+
+    registry.register(|_, packet: EntityAppeared2Packet, events| {
+        events.push(NetworkEvent::AddEntity(packet.into()));
+    });
+
+    registry.register(|_, packet: MovingEntityAppearedPacket, events| {
+        events.push(NetworkEvent::AddEntity(packet.into()));
+    });
+
+    registry.register(|_, packet: EntityDisappearedPacket, events| {
+        events.push(NetworkEvent::RemoveEntity(packet.entity_id));
+    });
+
+    registry.register(|_, packet: EntityMovePacket, events| {
+        let (origin, destination) = packet.from_to.to_vectors();
+        events.push(NetworkEvent::EntityMove(
+            packet.entity_id,
+            origin,
+            destination,
+            packet.timestamp,
+        ));
+    });
+
+    registry.register(|_, packet: PlayerMovePacket, events| {
+        let (origin, destination) = packet.from_to.to_vectors();
+        events.push(NetworkEvent::PlayerMove(origin, destination, packet.timestamp));
+    });
+
+    registry.register(|_, packet: SpriteChangePacket, events| {
+        if packet.sprite_type == 0 {
+            events.push(NetworkEvent::ChangeJob(packet.account_id, packet.value));
+        }
+    });
+
+    registry.register(|_, packet: RequestPlayerDetailsSuccessPacket, events| {
+        events.push(NetworkEvent::UpdateEntityDetails(
+            EntityId(packet.character_id.0),
+            decode_text(packet.name),
+        ));
+    });
+
+    registry.register(|_, packet: RequestEntityDetailsSuccessPacket, events| {
+        events.push(NetworkEvent::UpdateEntityDetails(packet.entity_id, decode_text(packet.name)));
+    });
+
+    registry.register(|_, packet: UpdateEntityHealthPointsPacket, events| {
+        events.push(NetworkEvent::UpdateEntityHealth(
+            packet.entity_id,
+            packet.health_points as usize,
+            packet.maximum_health_points as usize,
+        ));
+    });
+
+    registry.register(|_, packet: DamagePacket, events| {
+        events.push(NetworkEvent::DamageEffect {
+            source_entity_id: packet.source_entity_id,
+            destination_entity_id: packet.destination_entity_id,
+            damage_amount: packet.damage_amount as usize,
+            is_critical: packet.is_critical(),
+        });
+    });
+
+    registry.register(|_, packet: VisualEffectPacket, events| {
+        let path = match packet.effect {
+            VisualEffect::BaseLevelUp => "angel.str",
+            VisualEffect::JobLevelUp => "joblvup.str",
+            VisualEffect::RefineFailure => "bs_refinefailed.str",
+            VisualEffect::RefineSuccess => "bs_refinesuccess.str",
+            VisualEffect::GameOver => "help_angel\\help_angel\\help_angel.str",
+            VisualEffect::PharmacySuccess => "p_success.str",
+            VisualEffect::PharmacyFailure => "p_failed.str",
+            VisualEffect::BaseLevelUpSuperNovice => "help_angel\\help_angel\\help_angel.str",
+            VisualEffect::JobLevelUpSuperNovice => "help_angel\\help_angel\\help_angel.str",
+            VisualEffect::BaseLevelUpTaekwon => "help_angel\\help_angel\\help_angel.str",
+        };
+
+        events.push(NetworkEvent::VisualEffect(path, packet.entity_id));
+    });
+
+    registry.register(|_, packet: QuestEffectPacket, events| {
+        let event = match packet.effect {
+            QuestEffect::None => NetworkEvent::RemoveQuestEffect(packet.entity_id),
+            _ => NetworkEvent::AddQuestEffect(packet),
+        };
+        events.push(event);
+    });
+
+    registry.ignore::<DisplayEmotionPacket>();
+    registry.ignore::<EntityStopMovePacket>();
+    registry.ignore::<RequestPlayerAttackFailedPacket>();
+}
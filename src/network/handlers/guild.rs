@@ -0,0 +1,121 @@
+use super::PacketHandlerRegistry;
+use crate::network::{
+    CharacterNamePacket, GuildEmblemPacket, GuildInfo, GuildInformationPacket, GuildMember, GuildMemberListPacket,
+    GuildMemberPositionChangedPacket, GuildNotice, GuildNoticePacket, GuildPosition, GuildPositionChangedPacket, GuildPositionInfoPacket,
+    GuildPositionNamesPacket, NetworkEvent, RequestCharacterNamePacket, UpdateGuildIdPacket,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|networking_system, packet: UpdateGuildIdPacket, _| {
+        networking_system.guild.guild_id = Some(packet.guild_id);
+        networking_system.request_guild_emblem(packet.guild_id, None);
+    });
+
+    registry.register(|networking_system, packet: GuildInformationPacket, _| {
+        networking_system.request_guild_emblem(packet.guild_id, Some(packet.emblem_id));
+
+        networking_system.guild.guild_id = Some(packet.guild_id);
+        networking_system.guild.info.set(Some(GuildInfo {
+            guild_id: packet.guild_id,
+            emblem_id: packet.emblem_id,
+            name: packet.name,
+            level: packet.level,
+            online_member_count: packet.online_member_count,
+            maximum_member_count: packet.maximum_member_count,
+            average_level: packet.average_level,
+            experience: packet.experience,
+            next_experience: packet.next_experience,
+            master_character_id: packet.master_character_id,
+        }));
+    });
+
+    registry.register(|networking_system, packet: GuildMemberListPacket, _| {
+        let members = packet
+            .members
+            .into_iter()
+            .map(|member| GuildMember {
+                account_id: member.account_id,
+                character_id: member.character_id,
+                name: String::new(),
+                level: member.level,
+                position_id: member.position_id,
+                is_online: member.current_state != 0,
+            })
+            .collect::<Vec<_>>();
+
+        // NOTE: The member list doesn't contain names, so they are requested
+        // separately.
+        members
+            .iter()
+            .for_each(|member| networking_system.send_packet_to_map_server(RequestCharacterNamePacket::new(member.character_id)));
+
+        networking_system.guild.members.set(members);
+    });
+
+    registry.register(|networking_system, packet: CharacterNamePacket, _| {
+        networking_system.guild.set_member_name(packet.character_id, packet.name);
+    });
+
+    registry.register(|networking_system, packet: GuildPositionNamesPacket, _| {
+        let names = packet
+            .positions
+            .into_iter()
+            .map(|position| (position.position_id, position.name))
+            .collect();
+
+        networking_system.guild.set_position_names(names);
+    });
+
+    registry.register(|networking_system, packet: GuildPositionInfoPacket, _| {
+        let positions = packet
+            .positions
+            .into_iter()
+            .map(|position| GuildPosition {
+                position_id: position.position_id,
+                name: String::new(),
+                mode: position.mode,
+                pay_rate: position.pay_rate,
+            })
+            .collect();
+
+        networking_system.guild.update_positions(positions);
+    });
+
+    registry.register(|networking_system, packet: GuildPositionChangedPacket, _| {
+        let positions = packet
+            .positions
+            .into_iter()
+            .map(|position| GuildPosition {
+                position_id: position.position_id,
+                name: position.name,
+                mode: position.mode,
+                pay_rate: position.pay_rate,
+            })
+            .collect();
+
+        networking_system.guild.update_positions(positions);
+    });
+
+    registry.register(|networking_system, packet: GuildMemberPositionChangedPacket, _| {
+        packet.members.into_iter().for_each(|member| {
+            networking_system
+                .guild
+                .update_member_position(member.character_id, member.position_id)
+        });
+    });
+
+    registry.register(|networking_system, packet: GuildNoticePacket, _| {
+        networking_system.guild.notice.set(GuildNotice {
+            subject: packet.subject,
+            notice: packet.notice,
+        });
+    });
+
+    registry.register(|_, packet: GuildEmblemPacket, events| {
+        events.push(NetworkEvent::GuildEmblem {
+            guild_id: packet.guild_id,
+            emblem_id: packet.emblem_id,
+            emblem_data: packet.emblem_data,
+        });
+    });
+}
@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use cgmath::Vector2;
+use ragnarok_bytes::{ConversionError, FromBytes};
+
+use super::PacketHandlerRegistry;
+use crate::network::{
+    CriticalWeightUpdatePacket, EquipPosition, EquippableItemListPacket, EquippableSwitchItemListPacket, IncomingPacket, InventoryItemData,
+    InventoyEndPacket, InventoyStartPacket, ItemDisappearedPacket, ItemDroppedPacket, ItemFavoriteStatusPacket, ItemOnGroundPacket,
+    ItemPickupPacket, ItemRarity, NetworkEvent, RegularItemListPacket, RemoveItemFromInventoryPacket, RequestEquipItemStatus,
+    RequestEquipItemStatusPacket, RequestUnequipItemStatus, RequestUnequipItemStatusPacket,
+};
+
+const INVENTORY_TYPE_INVENTORY: u8 = 0;
+const INVENTORY_TYPE_CART: u8 = 1;
+/// Time after a pickup request during which an item added to the inventory is
+/// counted as loot.
+const PICK_UP_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register_raw(InventoyStartPacket::HEADER, |_, byte_stream, events| {
+        let packet = InventoyStartPacket::from_bytes(byte_stream)?;
+        let mut item_data = Vec::new();
+
+        // TODO: it might be better for performance and resilience to instead save a
+        // state in the networking system instaed of buffering *all*
+        // inventory packets if one of them is cut off
+        loop {
+            let header = u16::from_bytes(byte_stream)?;
+
+            match header {
+                InventoyEndPacket::HEADER => {
+                    break;
+                }
+                RegularItemListPacket::HEADER => {
+                    let packet = RegularItemListPacket::from_bytes(byte_stream)?;
+                    for item_information in packet.item_information {
+                        item_data.push(InventoryItemData {
+                            index: item_information.index,
+                            item_id: item_information.item_id,
+                            equip_position: EquipPosition::None,
+                            equipped_position: EquipPosition::None,
+                            item_type: item_information.item_type,
+                            amount: item_information.amount,
+                            refinement_level: 0,
+                            cards: item_information.slot,
+                            is_broken: false,
+                            is_favorite: item_information.fags & 0b10 != 0,
+                        });
+                    }
+                }
+                EquippableItemListPacket::HEADER => {
+                    let packet = EquippableItemListPacket::from_bytes(byte_stream)?;
+                    for item_information in packet.item_information {
+                        item_data.push(InventoryItemData {
+                            index: item_information.index,
+                            item_id: item_information.item_id,
+                            equip_position: item_information.equip_position,
+                            equipped_position: item_information.equipped_position,
+                            item_type: item_information.item_type,
+                            amount: 1,
+                            refinement_level: item_information.refinement_level,
+                            cards: item_information.slot,
+                            is_broken: item_information.fags & 0b10 != 0,
+                            is_favorite: item_information.fags & 0b100 != 0,
+                        });
+                    }
+                }
+                _ => return Err(ConversionError::from_message("expected inventory packet")),
+            }
+        }
+
+        let _ = InventoyEndPacket::from_bytes(byte_stream)?;
+
+        // NOTE: The same packets are used to send the items in the cart and the
+        // storage.
+        match packet.inventory_type {
+            INVENTORY_TYPE_INVENTORY => events.push(NetworkEvent::Inventory(item_data)),
+            INVENTORY_TYPE_CART => events.push(NetworkEvent::Cart(item_data)),
+            _ => {}
+        }
+
+        Ok(())
+    });
+
+    registry.register(|networking_system, packet: ItemPickupPacket, events| {
+        // NOTE: The same packet is used for every item added to the inventory, for
+        // example when buying or trading, so only the answer to a pickup request is
+        // counted as loot.
+        let is_loot = networking_system
+            .pick_up_requested
+            .take()
+            .is_some_and(|requested| requested.elapsed() < PICK_UP_TIMEOUT);
+
+        // NOTE: Any other result means that the item was not added, for example
+        // because the player is overweight.
+        if packet.result != 0 {
+            return;
+        }
+
+        if is_loot {
+            events.push(NetworkEvent::LootedItem { amount: packet.count });
+        }
+
+        events.push(NetworkEvent::AddIventoryItem(InventoryItemData {
+            index: packet.index,
+            item_id: packet.item_id,
+            equip_position: packet.equip_position,
+            equipped_position: EquipPosition::None,
+            item_type: packet.item_type,
+            amount: packet.count,
+            refinement_level: packet.refinement_level,
+            cards: packet.cards,
+            is_broken: packet.is_broken != 0,
+            is_favorite: packet.favorite != 0,
+        }));
+    });
+
+    registry.register(|_, packet: RemoveItemFromInventoryPacket, events| {
+        events.push(NetworkEvent::RemoveInventoryItem {
+            index: packet.index,
+            amount: packet.amount,
+        });
+    });
+
+    registry.register(|_, packet: ItemDroppedPacket, events| {
+        events.push(NetworkEvent::AddGroundItem {
+            entity_id: packet.entity_id,
+            item_id: packet.item_id,
+            position: Vector2::new(packet.position_x as usize, packet.position_y as usize),
+            amount: packet.count,
+            is_identified: packet.is_identified != 0,
+            rarity: ItemRarity::from_drop_effect(packet.show_drop_effect, packet.drop_effect_mode),
+            dropped: true,
+        });
+    });
+
+    registry.register(|_, packet: ItemOnGroundPacket, events| {
+        events.push(NetworkEvent::AddGroundItem {
+            entity_id: packet.entity_id,
+            item_id: packet.item_id,
+            position: Vector2::new(packet.position_x as usize, packet.position_y as usize),
+            amount: packet.count,
+            is_identified: packet.is_identified != 0,
+            rarity: ItemRarity::Common,
+            dropped: false,
+        });
+    });
+
+    registry.register(|_, packet: ItemDisappearedPacket, events| {
+        events.push(NetworkEvent::RemoveGroundItem(packet.entity_id));
+    });
+
+    registry.register(|_, packet: RequestEquipItemStatusPacket, events| {
+        if let RequestEquipItemStatus::Success = packet.result {
+            events.push(NetworkEvent::UpdateEquippedPosition {
+                index: packet.inventory_index,
+                equipped_position: packet.equipped_position,
+            });
+        }
+    });
+
+    registry.register(|_, packet: RequestUnequipItemStatusPacket, events| {
+        if let RequestUnequipItemStatus::Success = packet.result {
+            events.push(NetworkEvent::UpdateEquippedPosition {
+                index: packet.inventory_index,
+                equipped_position: EquipPosition::None,
+            });
+        }
+    });
+
+    registry.register(|_, packet: ItemFavoriteStatusPacket, events| {
+        events.push(NetworkEvent::UpdateItemFavorite {
+            index: packet.item_index,
+            is_favorite: packet.is_favorite != 0,
+        });
+    });
+
+    registry.ignore::<EquippableSwitchItemListPacket>();
+    registry.ignore::<CriticalWeightUpdatePacket>();
+}
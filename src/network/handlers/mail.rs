@@ -0,0 +1,145 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    decode_text, AttachMailItemResultPacket, ChatMessage, CheckMailReceiverResultPacket, DeleteMailResultPacket,
+    DetachMailItemResultPacket, EquipPosition, InventoryItemData, ItemIndex, MailContentPacket, MailHeader, MailId, MailListPacket,
+    MailListPacket2, NetworkEvent, NewMailStatusPacket, OpenMailComposeResultPacket, SendMailResult, SendMailResultPacket,
+    TakeMailItemsResult, TakeMailItemsResultPacket, TakeMailZenyResultPacket, MAIL_FLAG_ITEM, MAIL_FLAG_ZENY,
+};
+
+fn mail_message(message: &str) -> NetworkEvent {
+    let color = Color::rgb_u8(220, 200, 30);
+    NetworkEvent::ChatMessage(ChatMessage::new(message.to_owned(), color))
+}
+
+fn mail_header(mail_id: MailId, sender_name: String, title: String, is_read: u8, flags: u8) -> MailHeader {
+    MailHeader {
+        mail_id,
+        sender_name: decode_text(sender_name),
+        title: decode_text(title),
+        is_read: is_read != 0,
+        has_zeny: flags & MAIL_FLAG_ZENY != 0,
+        has_items: flags & MAIL_FLAG_ITEM != 0,
+    }
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: NewMailStatusPacket, events| {
+        if packet.new_available != 0 {
+            events.push(mail_message("You have unread mail."));
+        }
+    });
+
+    registry.register(|_, packet: MailListPacket, events| {
+        let mails = packet
+            .mails
+            .into_iter()
+            .map(|mail| mail_header(mail.mail_id, mail.sender_name, mail.title, mail.is_read, mail.flags))
+            .collect();
+
+        events.push(NetworkEvent::MailList {
+            mails,
+            is_end: packet.is_end != 0,
+        });
+    });
+
+    registry.register(|_, packet: MailListPacket2, events| {
+        let mails = packet
+            .mails
+            .into_iter()
+            .map(|mail| mail_header(mail.mail_id, mail.sender_name, mail.title, mail.is_read, mail.flags))
+            .collect();
+
+        events.push(NetworkEvent::MailList {
+            mails,
+            is_end: packet.is_end != 0,
+        });
+    });
+
+    registry.register(|_, packet: MailContentPacket, events| {
+        let items = packet
+            .items
+            .into_iter()
+            .map(|item| {
+                // NOTE: Attached items are not in the inventory, so they don't have an index.
+                InventoryItemData {
+                    index: ItemIndex(0),
+                    item_id: item.item_id,
+                    equip_position: item.equip_position,
+                    equipped_position: EquipPosition::None,
+                    item_type: item.item_type,
+                    amount: item.amount,
+                    refinement_level: item.refinement_level,
+                    cards: item.cards,
+                    is_broken: item.is_broken != 0,
+                    is_favorite: false,
+                }
+            })
+            .collect();
+
+        events.push(NetworkEvent::MailContent {
+            mail_id: packet.mail_id,
+            text: decode_text(packet.text),
+            zeny: packet.zeny,
+            items,
+        });
+    });
+
+    registry.register(|_, packet: DeleteMailResultPacket, events| {
+        events.push(NetworkEvent::MailDeleted(packet.mail_id));
+    });
+
+    registry.register(|_, packet: TakeMailZenyResultPacket, events| match packet.result {
+        0 => events.push(NetworkEvent::MailZenyTaken(packet.mail_id)),
+        _ => events.push(mail_message("Failed to take the zeny out of the mail.")),
+    });
+
+    registry.register(|_, packet: TakeMailItemsResultPacket, events| {
+        if let Some(message) = packet.message() {
+            events.push(mail_message(message));
+        }
+
+        if packet.result == TakeMailItemsResult::Success {
+            events.push(NetworkEvent::MailItemsTaken(packet.mail_id));
+        }
+    });
+
+    registry.register(|_, packet: OpenMailComposeResultPacket, events| match packet.success {
+        0 => events.push(mail_message("You can't write a mail right now.")),
+        _ => events.push(NetworkEvent::MailComposeOpened),
+    });
+
+    registry.register(|_, packet: AttachMailItemResultPacket, events| match packet.message() {
+        Some(message) => events.push(mail_message(message)),
+        None => events.push(NetworkEvent::UpdateMailAttachment {
+            index: packet.index,
+            amount: packet.amount,
+        }),
+    });
+
+    registry.register(|_, packet: DetachMailItemResultPacket, events| {
+        if packet.result == 0 {
+            events.push(NetworkEvent::UpdateMailAttachment {
+                index: packet.index,
+                amount: packet.amount,
+            });
+        }
+    });
+
+    registry.register(|_, packet: CheckMailReceiverResultPacket, events| {
+        let character_id = (packet.character_id.0 != 0).then_some(packet.character_id);
+
+        if character_id.is_none() {
+            events.push(mail_message("There is no character with that name."));
+        }
+
+        events.push(NetworkEvent::MailReceiver(character_id));
+    });
+
+    registry.register(|_, packet: SendMailResultPacket, events| {
+        events.push(mail_message(packet.message()));
+        events.push(NetworkEvent::MailSent {
+            success: packet.result == SendMailResult::Success,
+        });
+    });
+}
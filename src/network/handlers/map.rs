@@ -0,0 +1,67 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    ChangeMapCellPacket, ChangeMapPacket, ChatMessage, DisconnectResponsePacket, DisconnectResponseStatus, DisplaySpecialEffectPacket,
+    MapServerLoginSuccessPacket, MapTypePacket, MarkMinimapPositionPacket, NavigateToMonsterPacket, NetworkEvent, Packet180b, Packet8302,
+    RestartResponsePacket, RestartResponseStatus, ServerTickPacket, StateChangePacket, UpdateConfigurationPacket, UpdateShowEquipPacket,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: MapServerLoginSuccessPacket, events| {
+        events.push(NetworkEvent::UpdateClientTick(packet.client_tick));
+        events.push(NetworkEvent::SetPlayerPosition(packet.position.to_vector()));
+    });
+
+    registry.register(|networking_system, packet: ChangeMapPacket, events| {
+        networking_system.map_name = packet.map_name.replace(".gat", "");
+        // NOTE: The map server sends the positions of the party members on the new map
+        // once it is loaded.
+        networking_system.party_members.clear();
+        events.push(NetworkEvent::ChangeMap(
+            networking_system.map_name.clone(),
+            packet.position.map(|component| component as usize),
+        ));
+    });
+
+    registry.register(|_, packet: ServerTickPacket, events| {
+        events.push(NetworkEvent::UpdateClientTick(packet.client_tick));
+    });
+
+    registry.register(|_, packet: RestartResponsePacket, events| match packet.result {
+        RestartResponseStatus::Ok => events.push(NetworkEvent::Disconnect),
+        RestartResponseStatus::Nothing => {
+            let color = Color::rgb_u8(255, 100, 100);
+            let chat_message = ChatMessage::new("Failed to log out.".to_string(), color);
+            events.push(NetworkEvent::ChatMessage(chat_message));
+        }
+    });
+
+    registry.register(|_, packet: DisconnectResponsePacket, events| match packet.result {
+        DisconnectResponseStatus::Ok => events.push(NetworkEvent::Disconnect),
+        DisconnectResponseStatus::Wait10Seconds => {
+            let color = Color::rgb_u8(255, 100, 100);
+            let chat_message = ChatMessage::new("Please wait 10 seconds before trying to log out.".to_string(), color);
+            events.push(NetworkEvent::ChatMessage(chat_message));
+        }
+    });
+
+    registry.register(|_, packet: NavigateToMonsterPacket, events| {
+        // NOTE: Navigation to an entity doesn't come with a position.
+        if packet.target_type != 3 {
+            events.push(NetworkEvent::QuestNavigation {
+                map_name: packet.map_name.replace(".gat", ""),
+                position: packet.target_position,
+            });
+        }
+    });
+
+    registry.ignore::<MapTypePacket>();
+    registry.ignore::<ChangeMapCellPacket>();
+    registry.ignore::<MarkMinimapPositionPacket>();
+    registry.ignore::<DisplaySpecialEffectPacket>();
+    registry.ignore::<StateChangePacket>();
+    registry.ignore::<UpdateShowEquipPacket>();
+    registry.ignore::<UpdateConfigurationPacket>();
+    registry.ignore::<Packet8302>();
+    registry.ignore::<Packet180b>();
+}
@@ -0,0 +1,95 @@
+mod bank;
+mod cart;
+mod chat;
+mod dialog;
+mod entity;
+mod guild;
+mod inventory;
+mod mail;
+mod map;
+mod quest;
+mod shop;
+mod skill;
+mod social;
+mod status;
+mod trade;
+mod vending;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use ragnarok_bytes::{ByteStream, ConversionResult};
+
+use super::{IncomingPacket, NetworkEvent, NetworkMetadata, NetworkingSystem};
+
+type PacketHandler = Rc<dyn Fn(&mut NetworkingSystem, &mut ByteStream<NetworkMetadata>, &mut Vec<NetworkEvent>) -> ConversionResult<()>>;
+
+/// Maps packet headers to the functions that handle them. Every subsystem
+/// registers the packets it cares about in its own module, so adding a new
+/// packet doesn't require touching the dispatch in the [`NetworkingSystem`].
+pub struct PacketHandlerRegistry {
+    handlers: HashMap<u16, PacketHandler>,
+}
+
+impl PacketHandlerRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self { handlers: HashMap::new() };
+
+        bank::register(&mut registry);
+        cart::register(&mut registry);
+        chat::register(&mut registry);
+        dialog::register(&mut registry);
+        entity::register(&mut registry);
+        guild::register(&mut registry);
+        inventory::register(&mut registry);
+        mail::register(&mut registry);
+        map::register(&mut registry);
+        quest::register(&mut registry);
+        skill::register(&mut registry);
+        shop::register(&mut registry);
+        social::register(&mut registry);
+        status::register(&mut registry);
+        trade::register(&mut registry);
+        vending::register(&mut registry);
+
+        registry
+    }
+
+    /// Register a handler that receives the parsed packet and may emit any
+    /// number of [`NetworkEvent`]s.
+    pub fn register<P>(&mut self, handler: impl Fn(&mut NetworkingSystem, P, &mut Vec<NetworkEvent>) + 'static)
+    where
+        P: IncomingPacket + 'static,
+    {
+        self.register_raw(P::HEADER, move |networking_system, byte_stream, events| {
+            let packet = P::from_bytes(byte_stream)?;
+            handler(networking_system, packet, events);
+            Ok(())
+        });
+    }
+
+    /// Parse the packet but don't act on it. This is used for packets that
+    /// the client doesn't support yet, so they still show up in the packet
+    /// history and don't stop the dispatch.
+    pub fn ignore<P>(&mut self)
+    where
+        P: IncomingPacket + 'static,
+    {
+        self.register(|_, _: P, _| {});
+    }
+
+    /// Register a handler that reads directly from the byte stream. Only
+    /// needed for packets that are followed by a sequence of other packets.
+    pub fn register_raw(
+        &mut self,
+        header: u16,
+        handler: impl Fn(&mut NetworkingSystem, &mut ByteStream<NetworkMetadata>, &mut Vec<NetworkEvent>) -> ConversionResult<()> + 'static,
+    ) {
+        let previous = self.handlers.insert(header, Rc::new(handler));
+        assert!(previous.is_none(), "packet header {header:#06X} is registered twice");
+    }
+
+    pub(super) fn get(&self, header: u16) -> Option<PacketHandler> {
+        self.handlers.get(&header).cloned()
+    }
+}
@@ -0,0 +1,114 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    decode_text, AchievementData, AchievementInformation, AchievementLevel, AchievementListPacket, AchievementRewardResultPacket,
+    AchievementUpdatePacket, ChatMessage, HuntingObjective, HuntingQuestNotificationPacket, HuntingQuestUpdateObjectivePacket,
+    NetworkEvent, QuestData, QuestListPacket, QuestNotificationPacket1, QuestObjective, QuestRemovedPacket,
+};
+
+fn achievement_information(achievement: AchievementData) -> AchievementInformation {
+    AchievementInformation {
+        achievement_id: achievement.acheivement_id,
+        is_completed: achievement.is_completed != 0,
+        objectives: achievement.objectives,
+        is_rewarded: achievement.got_rewarded != 0,
+    }
+}
+
+fn objective_updated(objective: HuntingObjective) -> NetworkEvent {
+    NetworkEvent::QuestObjectiveUpdated {
+        hunt_identification: objective.hunt_identification,
+        current_count: objective.current_count,
+        total_count: objective.total_count,
+    }
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: QuestListPacket, events| {
+        let quests = packet
+            .quests
+            .into_iter()
+            .map(|quest| QuestData {
+                quest_id: quest.quest_id,
+                is_active: quest.active != 0,
+                objectives: quest
+                    .objective_details
+                    .into_iter()
+                    .map(|objective| QuestObjective {
+                        hunt_identification: objective.hunt_identification,
+                        mob_name: decode_text(objective.mob_name),
+                        current_count: objective.kill_count,
+                        total_count: objective.total_count,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        events.push(NetworkEvent::QuestList(quests));
+    });
+
+    registry.register(|_, packet: QuestNotificationPacket1, events| {
+        let objectives = packet
+            .objective_details
+            .into_iter()
+            .take(packet.objective_count as usize)
+            .map(|objective| QuestObjective {
+                hunt_identification: objective.hunt_identification,
+                mob_name: decode_text(objective.mob_name),
+                current_count: 0,
+                total_count: objective.mob_count,
+            })
+            .collect();
+
+        events.push(NetworkEvent::QuestAdded(QuestData {
+            quest_id: packet.quest_id,
+            is_active: packet.active != 0,
+            objectives,
+        }));
+    });
+
+    registry.register(|_, packet: HuntingQuestNotificationPacket, events| {
+        events.extend(packet.objective_details.into_iter().map(objective_updated));
+    });
+
+    registry.register(|_, packet: HuntingQuestUpdateObjectivePacket, events| {
+        events.extend(packet.objective_details.into_iter().map(objective_updated));
+    });
+
+    registry.register(|_, packet: QuestRemovedPacket, events| {
+        events.push(NetworkEvent::QuestRemoved(packet.quest_id));
+    });
+
+    registry.register(|_, packet: AchievementListPacket, events| {
+        let level = AchievementLevel {
+            level: packet.level,
+            experience: packet.acheivement_experience,
+            experience_to_next_level: packet.acheivement_experience_to_next_level,
+            total_score: packet.total_score,
+        };
+        let achievements = packet.acheivement_data.into_iter().map(achievement_information).collect();
+
+        events.push(NetworkEvent::AchievementList { level, achievements });
+    });
+
+    registry.register(|_, packet: AchievementUpdatePacket, events| {
+        let level = AchievementLevel {
+            level: packet.level,
+            experience: packet.acheivement_experience,
+            experience_to_next_level: packet.acheivement_experience_to_next_level,
+            total_score: packet.total_score,
+        };
+        let achievement = achievement_information(packet.acheivement_data);
+
+        events.push(NetworkEvent::AchievementUpdate { level, achievement });
+    });
+
+    registry.register(|_, packet: AchievementRewardResultPacket, events| match packet.result {
+        0 => {
+            let color = Color::rgb_u8(255, 100, 100);
+            let message = ChatMessage::new("Failed to claim the achievement reward.".to_owned(), color);
+            events.push(NetworkEvent::ChatMessage(message));
+        }
+        _ => events.push(NetworkEvent::AchievementRewarded(packet.achievement_id)),
+    });
+}
@@ -0,0 +1,69 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    ChatMessage, EquipPosition, InventoryItemData, ItemIndex, NetworkEvent, ShopBuyItemListPacket, ShopBuyResult, ShopBuyResultPacket,
+    ShopDealTypePacket, ShopSellItemListPacket, ShopSellResultPacket,
+};
+
+fn shop_message(message: &str) -> NetworkEvent {
+    let color = Color::rgb_u8(220, 200, 30);
+    NetworkEvent::ChatMessage(ChatMessage::new(message.to_owned(), color))
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: ShopDealTypePacket, events| {
+        events.push(NetworkEvent::ShopDealType(packet.npc_id));
+    });
+
+    registry.register(|_, packet: ShopBuyItemListPacket, events| {
+        let items = packet
+            .items
+            .into_iter()
+            .map(|item| {
+                // NOTE: Shop items are not in the inventory, so they don't have an index.
+                let item_data = InventoryItemData {
+                    index: ItemIndex(0),
+                    item_id: item.item_id,
+                    equip_position: EquipPosition::None,
+                    equipped_position: EquipPosition::None,
+                    item_type: item.item_type,
+                    amount: 1,
+                    refinement_level: 0,
+                    cards: [0; 4],
+                    is_broken: false,
+                    is_favorite: false,
+                };
+
+                (item_data, item.discount_price)
+            })
+            .collect();
+
+        events.push(NetworkEvent::ShopBuyList(items));
+    });
+
+    registry.register(|_, packet: ShopSellItemListPacket, events| {
+        let items = packet.items.into_iter().map(|item| (item.index, item.overcharge_price)).collect();
+
+        events.push(NetworkEvent::ShopSellList(items));
+    });
+
+    registry.register(|_, packet: ShopBuyResultPacket, events| {
+        let success = packet.result() == ShopBuyResult::Success;
+
+        if let Some(message) = packet.message() {
+            events.push(shop_message(message));
+        }
+
+        events.push(NetworkEvent::ShopDealResult { success });
+    });
+
+    registry.register(|_, packet: ShopSellResultPacket, events| {
+        let success = packet.result == 0;
+
+        if !success {
+            events.push(shop_message("Item sale failed."));
+        }
+
+        events.push(NetworkEvent::ShopDealResult { success });
+    });
+}
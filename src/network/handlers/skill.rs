@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use super::PacketHandlerRegistry;
+use crate::network::{
+    CastCancelledPacket, DisplayPlayerHealEffect, DisplaySkillCooldownPacket, DisplaySkillEffectAndDamagePacket,
+    DisplaySkillEffectNoDamagePacket, NetworkEvent, NotifyGroundSkillPacket, NotifySkillUnitPacket, SkillTarget, SkillUnitDisappearPacket,
+    ToUseSkillSuccessPacket, UpdateHotkeysPacket, UpdateSkillTreePacket, UseSkillSuccessPacket,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: UpdateSkillTreePacket, events| {
+        events.push(NetworkEvent::SkillTree(packet.skill_information));
+    });
+
+    registry.register(|_, packet: DisplaySkillEffectNoDamagePacket, events| {
+        events.push(NetworkEvent::HealEffect(
+            packet.destination_entity_id,
+            packet.heal_amount as usize,
+        ));
+
+        events.push(NetworkEvent::SkillEffect {
+            caster: packet.source_entity_id,
+            target: SkillTarget::Entity(packet.destination_entity_id),
+            skill_id: packet.skill_id,
+        });
+    });
+
+    registry.register(|_, packet: DisplaySkillEffectAndDamagePacket, events| {
+        events.push(NetworkEvent::SkillEffect {
+            caster: packet.source_entity_id,
+            target: SkillTarget::Entity(packet.destination_entity_id),
+            skill_id: packet.skill_id,
+        });
+    });
+
+    registry.register(|_, packet: NotifyGroundSkillPacket, events| {
+        events.push(NetworkEvent::SkillEffect {
+            caster: packet.entity_id,
+            target: SkillTarget::Ground(packet.position.map(|component| component as usize)),
+            skill_id: packet.skill_id,
+        });
+    });
+
+    registry.register(|_, packet: NotifySkillUnitPacket, events| {
+        // NOTE: Units like traps of other players are sent to us but are not
+        // supposed to be shown.
+        if packet.visible != 0 {
+            events.push(NetworkEvent::AddSkillUnit(
+                packet.entity_id,
+                packet.unit_id,
+                packet.position.map(|component| component as usize),
+            ));
+        }
+    });
+
+    registry.register(|_, packet: SkillUnitDisappearPacket, events| {
+        events.push(NetworkEvent::RemoveSkillUnit(packet.entity_id));
+    });
+
+    registry.register(|_, packet: UseSkillSuccessPacket, events| {
+        // NOTE: Ground skills are sent without a destination entity.
+        let target = (packet.destination_entity.0 != 0).then_some(packet.destination_entity);
+
+        events.push(NetworkEvent::CastSkill {
+            caster: packet.source_entity,
+            target,
+            position: packet.position.map(|component| component as usize),
+            skill_id: packet.skill_id,
+            cast_time: packet.delay_time,
+        });
+    });
+
+    registry.register(|_, packet: CastCancelledPacket, events| {
+        events.push(NetworkEvent::CastCancelled(packet.entity_id));
+    });
+
+    registry.register(|_, packet: UpdateHotkeysPacket, events| {
+        events.push(NetworkEvent::SetHotkeys(packet.hotkeys.into()));
+    });
+
+    registry.register(|_, packet: DisplaySkillCooldownPacket, events| {
+        events.push(NetworkEvent::SkillCooldown(
+            packet.skill_id,
+            Duration::from_millis(packet.duration as u64),
+        ));
+    });
+
+    registry.ignore::<DisplayPlayerHealEffect>();
+    registry.ignore::<ToUseSkillSuccessPacket>();
+}
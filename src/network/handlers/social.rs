@@ -0,0 +1,106 @@
+use std::cell::UnsafeCell;
+use std::time::{Duration, Instant};
+
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::interface::ValueState;
+use crate::network::{
+    ChatMessage, ClanInfoPacket, ClanOnlineCountPacket, FriendListPacket, FriendOnlineStatusPacket, FriendRequestPacket,
+    FriendRequestResult, FriendRequestResultPacket, NetworkEvent, NotifyFriendRemovedPacket, NotifyPartyMemberPositionPacket, OnlineState,
+    PartyInvitePacket, PartyMemberLeftPacket, UpdatePartyInvitationStatePacket,
+};
+
+/// Time after receiving the friend list during which status changes of friends
+/// are applied without notifying the player.
+const FRIEND_LIST_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|networking_system, packet: FriendListPacket, _| {
+        networking_system.friend_list_received = Some(Instant::now());
+
+        networking_system.friend_list.with_mut(|friends| {
+            // NOTE: The map server sends the state of every friend that is online right
+            // after the list.
+            *friends = packet
+                .friends
+                .into_iter()
+                .map(|friend| (friend, OnlineState::Offline, UnsafeCell::new(None)))
+                .collect();
+            ValueState::Mutated(())
+        });
+    });
+
+    registry.register(|_, packet: FriendRequestPacket, events| {
+        events.push(NetworkEvent::FriendRequest(packet.friend));
+    });
+
+    registry.register(|networking_system, packet: FriendRequestResultPacket, events| {
+        if packet.result == FriendRequestResult::Accepted {
+            networking_system
+                .friend_list
+                .push((packet.friend.clone(), OnlineState::Online, UnsafeCell::new(None)));
+        }
+
+        let color = Color::rgb_u8(220, 200, 30);
+        let chat_message = ChatMessage::new(packet.into_message(), color);
+        events.push(NetworkEvent::ChatMessage(chat_message));
+    });
+
+    registry.register(|networking_system, packet: NotifyFriendRemovedPacket, _| {
+        networking_system.friend_list.with_mut(|friends| {
+            friends.retain(|(friend, ..)| !(friend.account_id == packet.account_id && friend.character_id == packet.character_id));
+            ValueState::Mutated(())
+        });
+    });
+
+    registry.register(|networking_system, packet: FriendOnlineStatusPacket, events| {
+        let name = networking_system.friend_list.with_mut(|friends| {
+            let friend = friends
+                .iter_mut()
+                .find(|(friend, ..)| friend.account_id == packet.account_id && friend.character_id == packet.character_id);
+
+            match friend {
+                Some((friend, state, linked_element)) if *state != packet.state => {
+                    *state = packet.state;
+                    // NOTE: Unlinking the element makes the friend view build a new one.
+                    *linked_element = UnsafeCell::new(None);
+                    ValueState::Mutated(Some(friend.name.clone()))
+                }
+                _ => ValueState::Unchanged(None),
+            }
+        });
+
+        let is_settled = networking_system
+            .friend_list_received
+            .is_none_or(|received| received.elapsed() >= FRIEND_LIST_SETTLE_TIME);
+
+        if let Some(name) = name
+            && is_settled
+        {
+            events.push(NetworkEvent::FriendOnlineStatus { name, state: packet.state });
+        }
+    });
+
+    registry.register(|networking_system, packet: NotifyPartyMemberPositionPacket, _| {
+        networking_system.party_members.insert(packet.account_id);
+    });
+
+    registry.register(|networking_system, packet: PartyMemberLeftPacket, _| {
+        let is_player = networking_system
+            .login_data
+            .as_ref()
+            .is_some_and(|login_data| login_data.account_id == packet.account_id);
+
+        match is_player {
+            true => networking_system.party_members.clear(),
+            false => {
+                networking_system.party_members.remove(&packet.account_id);
+            }
+        }
+    });
+
+    registry.ignore::<PartyInvitePacket>();
+    registry.ignore::<UpdatePartyInvitationStatePacket>();
+    registry.ignore::<ClanInfoPacket>();
+    registry.ignore::<ClanOnlineCountPacket>();
+}
@@ -0,0 +1,45 @@
+use super::PacketHandlerRegistry;
+use crate::network::{
+    DisplayGainedExperiencePacket, InitialStatusPacket, NetworkEvent, ReputationPacket, RequestStatUpResponsePacket, StatusChangePacket,
+    StatusChangeSequencePacket, UpdateAttackRangePacket, UpdateStatusPacket, UpdateStatusPacket1, UpdateStatusPacket2, UpdateStatusPacket3,
+};
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: UpdateStatusPacket, events| {
+        events.push(NetworkEvent::UpdateStatus(packet.status_type));
+    });
+
+    registry.register(|_, packet: UpdateStatusPacket1, events| {
+        events.push(NetworkEvent::UpdateStatus(packet.status_type));
+    });
+
+    registry.register(|_, packet: UpdateStatusPacket2, events| {
+        events.push(NetworkEvent::UpdateStatus(packet.status_type));
+    });
+
+    registry.register(|_, packet: UpdateStatusPacket3, events| {
+        events.push(NetworkEvent::UpdateStatus(packet.status_type));
+    });
+
+    registry.register(|_, packet: StatusChangePacket, events| {
+        events.push(NetworkEvent::UpdateStatusEffect {
+            entity_id: packet.entity_id,
+            status_index: packet.index,
+            active: packet.state != 0,
+        });
+    });
+
+    registry.register(|_, packet: StatusChangeSequencePacket, events| {
+        events.push(NetworkEvent::UpdateStatusEffect {
+            entity_id: packet.entity_id,
+            status_index: packet.index,
+            active: packet.state != 0,
+        });
+    });
+
+    registry.ignore::<InitialStatusPacket>();
+    registry.ignore::<RequestStatUpResponsePacket>();
+    registry.ignore::<UpdateAttackRangePacket>();
+    registry.ignore::<DisplayGainedExperiencePacket>();
+    registry.ignore::<ReputationPacket>();
+}
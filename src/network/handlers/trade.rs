@@ -0,0 +1,83 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    AddTradeItemResultPacket, ChatMessage, EquipPosition, InventoryItemData, ItemIndex, NetworkEvent, TradeCanceledPacket,
+    TradeCompletedPacket, TradeItemAddedPacket, TradeLockedPacket, TradeRequestPacket, TradeRequestResult, TradeRequestResultPacket,
+};
+
+fn trade_message(message: &str) -> NetworkEvent {
+    let color = Color::rgb_u8(220, 200, 30);
+    NetworkEvent::ChatMessage(ChatMessage::new(message.to_owned(), color))
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: TradeRequestPacket, events| {
+        events.push(NetworkEvent::TradeRequest {
+            name: packet.name,
+            base_level: packet.base_level,
+        });
+    });
+
+    registry.register(|_, packet: TradeRequestResultPacket, events| {
+        if packet.result == TradeRequestResult::Accepted {
+            events.push(NetworkEvent::TradeStarted);
+        }
+
+        if let Some(message) = packet.message() {
+            events.push(trade_message(message));
+        }
+    });
+
+    registry.register(|_, packet: TradeItemAddedPacket, events| {
+        // NOTE: Zeny is sent as an item with an id of zero.
+        if packet.item_id.0 == 0 {
+            events.push(NetworkEvent::TradeZenyAdded(packet.amount));
+            return;
+        }
+
+        // NOTE: Items of the trade partner are not part of the inventory of the
+        // player, so they don't have a meaningful index.
+        events.push(NetworkEvent::TradeItemAdded(InventoryItemData {
+            index: ItemIndex(0),
+            item_id: packet.item_id,
+            equip_position: EquipPosition::None,
+            equipped_position: EquipPosition::None,
+            item_type: packet.item_type,
+            amount: packet.amount as u16,
+            refinement_level: packet.refinement_level,
+            cards: packet.cards,
+            is_broken: packet.is_broken != 0,
+            is_favorite: false,
+        }));
+    });
+
+    registry.register(|_, packet: AddTradeItemResultPacket, events| {
+        let index = packet.index.checked_sub(2).map(ItemIndex);
+        let success = packet.result == 0;
+
+        if !success {
+            events.push(trade_message("You can't add this item to the trade."));
+        }
+
+        events.push(NetworkEvent::TradeOfferResult { index, success });
+    });
+
+    registry.register(|_, packet: TradeLockedPacket, events| {
+        events.push(NetworkEvent::TradeLocked { partner: packet.who != 0 });
+    });
+
+    registry.register(|_, _: TradeCanceledPacket, events| {
+        events.push(trade_message("The deal has been canceled."));
+        events.push(NetworkEvent::TradeCanceled);
+    });
+
+    registry.register(|_, packet: TradeCompletedPacket, events| {
+        let message = match packet.result {
+            0 => "The deal has been completed.",
+            _ => "The deal has failed.",
+        };
+
+        events.push(trade_message(message));
+        events.push(NetworkEvent::TradeCompleted);
+    });
+}
@@ -0,0 +1,80 @@
+use super::PacketHandlerRegistry;
+use crate::graphics::Color;
+use crate::network::{
+    decode_text, ChatMessage, EquipPosition, InventoryItemData, NetworkEvent, OpenVendingResultPacket, OpenVendingSetupPacket,
+    OwnVendingItemListPacket, VendingClosedPacket, VendingItemListPacket, VendingItemSoldPacket, VendingPurchaseFailedPacket,
+    VendingTitlePacket,
+};
+
+fn vending_message(message: &str) -> NetworkEvent {
+    let color = Color::rgb_u8(220, 200, 30);
+    NetworkEvent::ChatMessage(ChatMessage::new(message.to_owned(), color))
+}
+
+pub fn register(registry: &mut PacketHandlerRegistry) {
+    registry.register(|_, packet: VendingTitlePacket, events| {
+        events.push(NetworkEvent::VendingTitle {
+            entity_id: packet.entity_id,
+            title: decode_text(packet.title),
+        });
+    });
+
+    registry.register(|_, packet: VendingClosedPacket, events| {
+        events.push(NetworkEvent::VendingClosed(packet.entity_id));
+    });
+
+    registry.register(|_, packet: VendingItemListPacket, events| {
+        let items = packet
+            .items
+            .into_iter()
+            .map(|item| {
+                let item_data = InventoryItemData {
+                    index: item.index,
+                    item_id: item.item_id,
+                    equip_position: item.equip_position,
+                    equipped_position: EquipPosition::None,
+                    item_type: item.item_type,
+                    amount: item.amount,
+                    refinement_level: item.refinement_level,
+                    cards: item.cards,
+                    is_broken: item.is_broken != 0,
+                    is_favorite: false,
+                };
+
+                (item_data, item.price)
+            })
+            .collect();
+
+        events.push(NetworkEvent::VendingList {
+            entity_id: packet.entity_id,
+            vending_id: packet.vending_id,
+            items,
+        });
+    });
+
+    registry.register(|_, packet: VendingPurchaseFailedPacket, events| {
+        events.push(vending_message(packet.message()));
+    });
+
+    registry.register(|_, packet: OpenVendingSetupPacket, events| {
+        events.push(NetworkEvent::OpenVendingSetup(packet.slot_count));
+    });
+
+    registry.register(|networking_system, packet: OpenVendingResultPacket, events| {
+        let title = networking_system.vending_title.take();
+
+        match (packet.result, title) {
+            (0, Some(title)) => events.push(NetworkEvent::VendingOpened { title }),
+            _ => events.push(vending_message("Failed to open the shop.")),
+        }
+    });
+
+    // NOTE: The items of the shop are the ones the player selected, so they
+    // don't have to be shown again.
+    registry.ignore::<OwnVendingItemListPacket>();
+
+    registry.register(|_, packet: VendingItemSoldPacket, events| {
+        let message = format!("{} item(s) were sold in your shop.", packet.amount);
+        events.push(vending_message(&message));
+    });
+}
@@ -0,0 +1,498 @@
+//! Scripted in-process server for end-to-end tests of the networking system.
+//! A script is a sequence of packets the client is expected to send and
+//! packets the server sends back, executed in order on a separate thread.
+
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::{NetworkEvent, NetworkingSystem, OutgoingPacket, PacketVersion};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+enum MockStep {
+    Expect(Vec<u8>),
+    Send(Vec<u8>),
+}
+
+/// Raw packet sent by the [`MockServer`]. Incoming packets can't be
+/// serialized, so they are assembled field by field.
+pub struct MockPacket {
+    header: u16,
+    variable_length: bool,
+    data: Vec<u8>,
+}
+
+impl MockPacket {
+    pub fn fixed(header: u16) -> Self {
+        Self {
+            header,
+            variable_length: false,
+            data: Vec::new(),
+        }
+    }
+
+    /// Packet that starts with its own length, *including* the header.
+    pub fn variable(header: u16) -> Self {
+        Self {
+            header,
+            variable_length: true,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn u8(mut self, value: u8) -> Self {
+        self.data.push(value);
+        self
+    }
+
+    pub fn u16(mut self, value: u16) -> Self {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn u32(mut self, value: u32) -> Self {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    /// Null padded string of a fixed length.
+    pub fn string(mut self, value: &str, length: usize) -> Self {
+        assert!(value.len() < length, "string does not fit into {length} bytes");
+
+        self.data.extend_from_slice(value.as_bytes());
+        self.data.resize(self.data.len() + length - value.len(), 0);
+        self
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        let mut bytes = self.header.to_le_bytes().to_vec();
+
+        if self.variable_length {
+            let packet_length = (self.data.len() + 4) as u16;
+            bytes.extend_from_slice(&packet_length.to_le_bytes());
+        }
+
+        bytes.extend(self.data);
+        bytes
+    }
+}
+
+#[derive(Default)]
+pub struct MockScript {
+    steps: Vec<MockStep>,
+}
+
+impl MockScript {
+    /// Wait for the client to send exactly this packet.
+    pub fn expect(mut self, packet: impl OutgoingPacket) -> Self {
+        self.steps
+            .push(MockStep::Expect(packet.to_bytes(PacketVersion::default()).unwrap()));
+        self
+    }
+
+    pub fn send(mut self, packet: MockPacket) -> Self {
+        self.steps.push(MockStep::Send(packet.into_bytes()));
+        self
+    }
+}
+
+pub struct MockServer {
+    address: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start a server that accepts a single connection and runs the script on
+    /// it. Assertion failures are reported by [`MockServer::finish`].
+    pub fn start(script: MockScript) -> Self {
+        // NOTE: Service ports in the client info are signed 16 bit integers, so we
+        // can't let the operating system pick the port.
+        let listener = (20000..i16::MAX as u16)
+            .find_map(|port| TcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok())
+            .expect("no free port for the mock server");
+        let address = listener.local_addr().unwrap();
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("client did not connect");
+            stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+
+            for step in script.steps {
+                match step {
+                    MockStep::Expect(expected) => {
+                        let mut received = vec![0; expected.len()];
+                        stream.read_exact(&mut received).expect("client did not send the expected packet");
+                        assert_eq!(received, expected, "client sent an unexpected packet");
+                    }
+                    MockStep::Send(bytes) => stream.write_all(&bytes).unwrap(),
+                }
+            }
+
+            // NOTE: Keep the connection open until the client disconnects, otherwise
+            // the client would keep reading an empty stream.
+            let _ = stream.read(&mut [0; 1]);
+        });
+
+        Self { address, handle }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.address.port()
+    }
+
+    /// Connect the map server stream of a fresh networking system to this
+    /// server.
+    pub fn connect_map_server(&self) -> NetworkingSystem {
+        let map_stream = TcpStream::connect(self.address).unwrap();
+        map_stream.set_nonblocking(true).unwrap();
+
+        let mut networking_system = NetworkingSystem::new();
+        networking_system.map_stream = Some(map_stream);
+        networking_system
+    }
+
+    /// Wait for the script to complete and propagate any failed assertions.
+    /// The client needs to be dropped before calling this.
+    pub fn finish(self) {
+        if let Err(panic) = self.handle.join() {
+            std::panic::resume_unwind(panic);
+        }
+    }
+}
+
+/// Poll the map server until at least one event arrives.
+pub fn wait_for_events(networking_system: &mut NetworkingSystem) -> Vec<NetworkEvent> {
+    let start = Instant::now();
+
+    loop {
+        let events = networking_system.network_events();
+
+        if !events.is_empty() || start.elapsed() > TIMEOUT {
+            return events;
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+    use ragnarok_bytes::ByteStream;
+
+    use super::{wait_for_events, MockPacket, MockScript, MockServer};
+    use crate::loaders::{ClientInfo, Service};
+    use crate::network::*;
+
+    fn regular_item(packet: MockPacket, index: u16, item_id: u32, amount: u16) -> MockPacket {
+        packet
+            .u16(index + 2)
+            .u32(item_id)
+            .u8(0) // item type
+            .u16(amount)
+            .u32(0) // wear state
+            .bytes(&[0; 16]) // cards
+            .u32(0) // hire expiration date
+            .u8(1) // flags
+    }
+
+    #[test]
+    fn login() {
+        let script = MockScript::default()
+            .expect(LoginServerLoginPacket::new("user".to_owned(), "password".to_owned()))
+            .send(
+                MockPacket::variable(LoginServerLoginSuccessPacket::HEADER)
+                    .u32(11) // login id 1
+                    .u32(2000000) // account id
+                    .u32(22) // login id 2
+                    .u32(0) // ip address
+                    .bytes(&[0; 24]) // name
+                    .u16(0) // unknown
+                    .u8(1) // sex
+                    .bytes(&[0; 17]) // auth token
+                    .bytes(&[127, 0, 0, 1])
+                    .u16(6121)
+                    .string("Mock", 20)
+                    .u16(0) // user count
+                    .u16(0) // server type
+                    .u16(0) // display new
+                    .bytes(&[0; 128]),
+            );
+        let server = MockServer::start(script);
+
+        let service = Service {
+            address: "127.0.0.1".to_owned(),
+            port: server.port() as i16,
+            ..Default::default()
+        };
+        let service_id = service.service_id();
+        let client_info = ClientInfo {
+            services: vec![service],
+            ..Default::default()
+        };
+
+        let mut networking_system = NetworkingSystem::new();
+        let character_servers = networking_system
+            .log_in(&client_info, service_id, "user".to_owned(), "password".to_owned())
+            .unwrap();
+
+        assert_eq!(character_servers.len(), 1);
+        assert_eq!(character_servers[0].server_name, "Mock");
+        assert_eq!(character_servers[0].server_port, 6121);
+
+        let login_data = networking_system.login_data.as_ref().unwrap();
+        assert_eq!(login_data.account_id, AccountId(2000000));
+        assert_eq!(login_data.login_id1, 11);
+        assert_eq!(login_data.login_id2, 22);
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    #[test]
+    fn login_compact() {
+        let script = MockScript::default()
+            .expect(LoginServerLoginPacket::new("user".to_owned(), "password".to_owned()))
+            .send(
+                MockPacket::variable(CompactLoginServerLoginSuccessPacket::HEADER)
+                    .u32(11) // login id 1
+                    .u32(2000000) // account id
+                    .u32(22) // login id 2
+                    .u32(0) // ip address
+                    .bytes(&[0; 24]) // name
+                    .u16(0) // unknown
+                    .u8(1) // sex
+                    .bytes(&[127, 0, 0, 1])
+                    .u16(6121)
+                    .string("Mock", 20)
+                    .u16(0) // user count
+                    .u16(0) // server type
+                    .u16(0), // display new
+            );
+        let server = MockServer::start(script);
+
+        let service = Service {
+            address: "127.0.0.1".to_owned(),
+            port: server.port() as i16,
+            packet_version: Some(20151104),
+            ..Default::default()
+        };
+        let service_id = service.service_id();
+        let client_info = ClientInfo {
+            services: vec![service],
+            ..Default::default()
+        };
+
+        let mut networking_system = NetworkingSystem::new();
+        let character_servers = networking_system
+            .log_in(&client_info, service_id, "user".to_owned(), "password".to_owned())
+            .unwrap();
+
+        assert_eq!(character_servers.len(), 1);
+        assert_eq!(character_servers[0].server_name, "Mock");
+        assert_eq!(character_servers[0].server_port, 6121);
+        assert_eq!(networking_system.login_data.as_ref().unwrap().account_id, AccountId(2000000));
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    fn compact_character_list(packet_version: u32) -> Vec<CharacterInformation> {
+        let packet = MockPacket::variable(CompactRequestCharacterListSuccessPacket::HEADER)
+            .u32(150000) // character id
+            .u32(1234) // experience
+            .u32(0) // money
+            .u32(56) // job experience
+            .bytes(&[0; 26]) // job level to job point
+            .u32(400) // health points
+            .u32(500) // maximum health points
+            .u16(40) // spell points
+            .u16(50) // maximum spell points
+            .bytes(&[0; 6]) // movement speed, job and head
+            .bytes(&[0; 18]) // weapon to body palette
+            .string("Poring", 24)
+            .bytes(&[1; 6]) // stats
+            .u8(2) // character number
+            .bytes(&[0; 3]) // hair color and changed
+            .string("prontera.gat", 16)
+            .bytes(&[0; 16]); // deletion date to name change count
+        let packet = match packet_version >= 20141016 {
+            true => packet.u8(0), // sex
+            false => packet,
+        };
+
+        let bytes = packet.into_bytes();
+        let mut byte_stream = ByteStream::with_metadata(&bytes[2..], NetworkMetadata::new(PacketVersion(packet_version)));
+
+        CompactRequestCharacterListSuccessPacket::from_bytes(&mut byte_stream)
+            .unwrap()
+            .character_information
+    }
+
+    #[test]
+    fn compact_character_list_without_sex() {
+        let characters = compact_character_list(20140101);
+
+        assert_eq!(characters.len(), 1);
+        assert_eq!(characters[0].character_id, CharacterId(150000));
+        assert_eq!(characters[0].experience, 1234);
+        assert_eq!(characters[0].job_experience, 56);
+        assert_eq!(characters[0].maximum_health_points, 500);
+        assert_eq!(characters[0].maximum_spell_points, 50);
+        assert_eq!(characters[0].name, "Poring");
+        assert_eq!(characters[0].character_number, 2);
+        assert_eq!(characters[0].map_name, "prontera.gat");
+        assert_eq!(characters[0].sex, Sex::Server);
+    }
+
+    #[test]
+    fn compact_character_list_with_sex() {
+        let characters = compact_character_list(20141016);
+
+        assert_eq!(characters.len(), 1);
+        assert_eq!(characters[0].name, "Poring");
+        assert_eq!(characters[0].map_name, "prontera.gat");
+        assert_eq!(characters[0].sex, Sex::Female);
+    }
+
+    #[test]
+    fn map_change() {
+        let script = MockScript::default().send(
+            MockPacket::fixed(ChangeMapPacket::HEADER)
+                .string("prontera.gat", 16)
+                .u16(150)
+                .u16(180),
+        );
+        let server = MockServer::start(script);
+        let mut networking_system = server.connect_map_server();
+
+        let events = wait_for_events(&mut networking_system);
+
+        assert!(matches!(
+            events.as_slice(),
+            [NetworkEvent::ChangeMap(map_name, position)] if map_name == "prontera" && *position == Vector2::new(150, 180)
+        ));
+        assert_eq!(networking_system.map_name, "prontera");
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    #[test]
+    fn skip_unknown_packet() {
+        let script = MockScript::default()
+            .send(MockPacket::fixed(0x00D8).u32(7)) // destroy chat room
+            .send(MockPacket::fixed(ChangeMapPacket::HEADER).string("geffen.gat", 16).u16(120).u16(60));
+        let server = MockServer::start(script);
+        let mut networking_system = server.connect_map_server();
+
+        let events = wait_for_events(&mut networking_system);
+
+        assert!(matches!(events.as_slice(), [NetworkEvent::ChangeMap(map_name, _)] if map_name == "geffen"));
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    #[test]
+    fn combat() {
+        let script = MockScript::default()
+            .expect(RequestActionPacket::new(EntityId(110), Action::Attack))
+            .send(
+                MockPacket::fixed(DamagePacket::HEADER)
+                    .u32(2000000) // source entity
+                    .u32(110) // destination entity
+                    .u32(0) // client tick
+                    .u32(0) // source movement speed
+                    .u32(0) // destination movement speed
+                    .u32(42) // damage
+                    .u8(0) // is special damage
+                    .u16(1) // amount of hits
+                    .u8(0) // damage type
+                    .u32(0), // second damage
+            );
+        let server = MockServer::start(script);
+        let mut networking_system = server.connect_map_server();
+
+        networking_system.request_player_attack(EntityId(110));
+        let events = wait_for_events(&mut networking_system);
+
+        assert!(matches!(events.as_slice(), [NetworkEvent::DamageEffect {
+            source_entity_id: EntityId(2000000),
+            destination_entity_id: EntityId(110),
+            damage_amount: 42,
+            is_critical: false,
+        }]));
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    #[test]
+    fn inventory() {
+        let item_list = MockPacket::variable(RegularItemListPacket::HEADER).u8(0);
+        let item_list = regular_item(item_list, 0, 501, 5);
+        let item_list = regular_item(item_list, 1, 502, 2);
+
+        let script = MockScript::default()
+            .send(MockPacket::variable(InventoyStartPacket::HEADER).u8(0).string("inventory", 10))
+            .send(item_list)
+            .send(MockPacket::fixed(InventoyEndPacket::HEADER).u8(0).u8(0));
+        let server = MockServer::start(script);
+        let mut networking_system = server.connect_map_server();
+
+        let events = wait_for_events(&mut networking_system);
+
+        let [NetworkEvent::Inventory(items)] = events.as_slice() else {
+            panic!("expected a single inventory event");
+        };
+        let item_ids: Vec<ItemId> = items.iter().map(|item| item.item_id).collect();
+        assert_eq!(item_ids, [ItemId(501), ItemId(502)]);
+        let amounts: Vec<u16> = items.iter().map(|item| item.amount).collect();
+        assert_eq!(amounts, [5, 2]);
+
+        drop(networking_system);
+        server.finish();
+    }
+
+    #[test]
+    fn mailbox() {
+        let script = MockScript::default()
+            .expect(OpenMailboxPacket2::new(MailboxType::Character, MailId(0)))
+            .send(
+                MockPacket::variable(MailListPacket2::HEADER)
+                    .u8(1) // is end
+                    .u8(0) // mailbox type
+                    .bytes(&7u64.to_le_bytes())
+                    .u8(0) // is read
+                    .u8(MAIL_FLAG_ZENY)
+                    .string("Kafra", 24)
+                    .u32(0) // expiration time
+                    .u16(5)
+                    .bytes(b"Hello"),
+            );
+        let server = MockServer::start(script);
+        let mut networking_system = server.connect_map_server();
+
+        networking_system.open_mailbox();
+        let events = wait_for_events(&mut networking_system);
+
+        let [NetworkEvent::MailList { mails, is_end: true }] = events.as_slice() else {
+            panic!("expected a single mail list event");
+        };
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].mail_id, MailId(7));
+        assert_eq!(mails[0].sender_name, "Kafra");
+        assert_eq!(mails[0].title, "Hello");
+        assert!(mails[0].has_zeny && !mails[0].has_items && !mails[0].is_read);
+
+        drop(networking_system);
+        server.finish();
+    }
+}
@@ -1,18 +1,32 @@
+mod guild;
+mod handlers;
 mod login;
+#[cfg(test)]
+mod mock;
+mod protocol;
+
 use std::cell::UnsafeCell;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::io::prelude::*;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use cgmath::Vector2;
-use chrono::Local;
+use chrono::{DateTime, Local};
 use derive_new::new;
 use procedural::{profile, PrototypeElement};
-use ragnarok_bytes::{ByteStream, ConversionError, ConversionResult, ConversionResultExt, FromBytes, ToBytes};
+use ragnarok_bytes::{ByteStream, ConversionError, ConversionResult, ConversionResultExt, FixedByteSize, FromBytes, FromBytesExt, ToBytes};
 use ragnarok_procedural::{ByteConvertable, FixedByteSize, FromBytes, IncomingPacket, OutgoingPacket};
+use serde::{Deserialize, Serialize};
+use vulkano::image::view::ImageView;
 
+pub use self::guild::{Guild, GuildInfo, GuildMember, GuildNotice, GuildPosition};
+use self::handlers::PacketHandlerRegistry;
 pub use self::login::LoginSettings;
+pub use self::protocol::PacketVersion;
+use self::protocol::{load_packet_definitions, PacketDefinition, PacketLengthTable};
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::graphics::{Color, ColorBGRA, ColorRGBA};
@@ -21,10 +35,11 @@ use crate::interface::PacketEntry;
 #[cfg(feature = "debug")]
 use crate::interface::PacketWindow;
 use crate::interface::{
-    CharacterSelectionWindow, ElementCell, ElementWrap, Expandable, FriendsWindow, PrototypeElement, TrackedState, TrackedStateTake,
-    ValueState, WeakElementCell,
+    CharacterSelectionWindow, ElementCell, ElementWrap, Expandable, FriendsWindow, GuildData, GuildWindow, PrototypeElement, TrackedState,
+    TrackedStateTake, ValueState, WeakElementCell,
 };
 use crate::loaders::{ClientInfo, ServiceId};
+use crate::system::Scheduler;
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
 pub struct ClientTick(pub u32);
@@ -65,13 +80,54 @@ impl ToBytes for ItemIndex {
     }
 }
 
-#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PrototypeElement, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PrototypeElement, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ItemId(pub u32);
 
-#[cfg(feature = "debug")]
-type NetworkMetadata = Vec<PacketEntry>;
-#[cfg(not(feature = "debug"))]
-type NetworkMetadata = ();
+#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PrototypeElement, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MailId(pub u64);
+
+/// Pillar of light that the server can display on dropped items to mark
+/// valuable drops, ordered from least to most valuable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ItemRarity {
+    Common,
+    White,
+    Blue,
+    Yellow,
+    Purple,
+    Orange,
+}
+
+impl ItemRarity {
+    fn from_drop_effect(show_drop_effect: u8, drop_effect_mode: u16) -> Self {
+        match (show_drop_effect, drop_effect_mode) {
+            (0, _) => Self::Common,
+            (_, 0) => Self::White,
+            (_, 1) => Self::Blue,
+            (_, 2) => Self::Yellow,
+            (_, 3) => Self::Purple,
+            _ => Self::Orange,
+        }
+    }
+}
+
+/// Passed along while reading packets, so packets can adjust their layout to
+/// the packet version of the server.
+pub struct NetworkMetadata {
+    pub packet_version: PacketVersion,
+    #[cfg(feature = "debug")]
+    pub packets: Vec<PacketEntry>,
+}
+
+impl NetworkMetadata {
+    pub fn new(packet_version: PacketVersion) -> Self {
+        Self {
+            packet_version,
+            #[cfg(feature = "debug")]
+            packets: Vec::new(),
+        }
+    }
+}
 
 /// Extension trait for for [`ByteStream`] for working with network packets.
 #[cfg(feature = "debug")]
@@ -83,13 +139,14 @@ trait ByteStreamNetworkExt {
 }
 
 #[cfg(feature = "debug")]
-impl<'a> ByteStreamNetworkExt for ByteStream<'a, Vec<PacketEntry>> {
+impl<'a> ByteStreamNetworkExt for ByteStream<'a, NetworkMetadata> {
     fn incoming_packet<T>(&mut self, packet: &T)
     where
         T: IncomingPacket + Clone + 'static,
     {
         self.get_metadata_mut::<T, NetworkMetadata>()
             .expect("wrong metadata")
+            .packets
             .push(PacketEntry::new_incoming(packet, std::any::type_name::<T>(), T::IS_PING));
     }
 }
@@ -114,7 +171,8 @@ pub trait IncomingPacket: PrototypeElement + Clone {
 pub trait OutgoingPacket: PrototypeElement + Clone {
     const IS_PING: bool;
 
-    fn to_bytes(&self) -> ConversionResult<Vec<u8>>;
+    /// Fields that don't exist in the packet version are skipped.
+    fn to_bytes(&self, packet_version: PacketVersion) -> ConversionResult<Vec<u8>>;
 }
 
 trait IncomingPacketExt: IncomingPacket {
@@ -136,6 +194,13 @@ where
     }
 }
 
+/// What a skill was used on.
+#[derive(Clone, Copy, Debug)]
+pub enum SkillTarget {
+    Entity(EntityId),
+    Ground(Vector2<usize>),
+}
+
 /// An event triggered by the map server.
 pub enum NetworkEvent {
     /// Add an entity to the list of entities that the client is aware of.
@@ -155,11 +220,24 @@ pub enum NetworkEvent {
     UpdateClientTick(ClientTick),
     /// New chat message for the client.
     ChatMessage(ChatMessage),
+    /// Public chat message spoken by an entity. Displayed in a speech bubble
+    /// above the entity.
+    EntityMessage(EntityId, String),
+    /// A party member marked a position on the current map.
+    PartyPing {
+        sender: String,
+        position: Vector2<usize>,
+    },
     /// Update entity details. Mostly received when the client sends
     /// [RequestDetailsPacket] after the player hovered an entity.
     UpdateEntityDetails(EntityId, String),
     UpdateEntityHealth(EntityId, usize, usize),
-    DamageEffect(EntityId, usize),
+    DamageEffect {
+        source_entity_id: EntityId,
+        destination_entity_id: EntityId,
+        damage_amount: usize,
+        is_critical: bool,
+    },
     HealEffect(EntityId, usize),
     UpdateStatus(StatusType),
     OpenDialog(String, EntityId),
@@ -168,22 +246,306 @@ pub enum NetworkEvent {
     AddChoiceButtons(Vec<String>),
     AddQuestEffect(QuestEffectPacket),
     RemoveQuestEffect(EntityId),
-    Inventory(Vec<(ItemIndex, ItemId, EquipPosition, EquipPosition)>),
-    AddIventoryItem(ItemIndex, ItemId, EquipPosition, EquipPosition),
+    /// An item appeared on the ground. `dropped` is set if the item was just
+    /// dropped, e.g. by a monster, rather than coming into view.
+    AddGroundItem {
+        entity_id: EntityId,
+        item_id: ItemId,
+        position: Vector2<usize>,
+        amount: u16,
+        is_identified: bool,
+        rarity: ItemRarity,
+        dropped: bool,
+    },
+    RemoveGroundItem(EntityId),
+    Inventory(Vec<InventoryItemData>),
+    AddIventoryItem(InventoryItemData),
+    /// The player picked up an item from the ground.
+    LootedItem {
+        amount: u16,
+    },
+    RemoveInventoryItem {
+        index: ItemIndex,
+        amount: u16,
+    },
+    Cart(Vec<InventoryItemData>),
+    AddCartItem(InventoryItemData),
+    RemoveCartItem {
+        index: ItemIndex,
+        amount: u16,
+    },
+    UpdateCartInformation(CartInformation),
+    CartRemoved,
     SkillTree(Vec<SkillInformation>),
+    /// The shortcuts stored on the map server for the current character.
+    SetHotkeys(Vec<HotkeyData>),
+    SkillCooldown(SkillId, Duration),
     UpdateEquippedPosition {
         index: ItemIndex,
         equipped_position: EquipPosition,
     },
+    UpdateItemFavorite {
+        index: ItemIndex,
+        is_favorite: bool,
+    },
     ChangeJob(AccountId, u32),
     SetPlayerPosition(Vector2<usize>),
     Disconnect,
     FriendRequest(Friend),
+    /// Another player asked the player for a trade.
+    TradeRequest {
+        name: String,
+        base_level: u16,
+    },
+    TradeStarted,
+    /// The trade partner offered an item.
+    TradeItemAdded(InventoryItemData),
+    /// The trade partner offered zeny.
+    TradeZenyAdded(u32),
+    /// The map server answered an offer of the player. The index is `None`
+    /// for zeny.
+    TradeOfferResult {
+        index: Option<ItemIndex>,
+        success: bool,
+    },
+    TradeLocked {
+        partner: bool,
+    },
+    TradeCanceled,
+    TradeCompleted,
+    /// The player talked to an NPC shop and has to choose between buying and
+    /// selling.
+    ShopDealType(EntityId),
+    /// Items an NPC shop sells, together with their price.
+    ShopBuyList(Vec<(InventoryItemData, u32)>),
+    /// Items of the player that an NPC shop buys, together with their price.
+    ShopSellList(Vec<(ItemIndex, u32)>),
+    /// The map server answered a purchase or sale of the player.
+    ShopDealResult {
+        success: bool,
+    },
+    /// A page of the mailbox. More mails can be requested if `is_end` is not
+    /// set.
+    MailList {
+        mails: Vec<MailHeader>,
+        is_end: bool,
+    },
+    MailContent {
+        mail_id: MailId,
+        text: String,
+        zeny: u64,
+        items: Vec<InventoryItemData>,
+    },
+    MailDeleted(MailId),
+    MailZenyTaken(MailId),
+    MailItemsTaken(MailId),
+    /// The map server allows the player to write a mail.
+    MailComposeOpened,
+    /// The total amount of an item that is attached to the mail changed.
+    UpdateMailAttachment {
+        index: ItemIndex,
+        amount: u16,
+    },
+    /// The character that the mail will be sent to. [`None`] if there is no
+    /// character with that name.
+    MailReceiver(Option<CharacterId>),
+    MailSent {
+        success: bool,
+    },
+    /// A player opened a vending shop with the given title.
+    VendingTitle {
+        entity_id: EntityId,
+        title: String,
+    },
+    VendingClosed(EntityId),
+    /// Items of a vending shop, together with their price. The item index is
+    /// the index in the cart of the vendor and the amount is the stock.
+    VendingList {
+        entity_id: EntityId,
+        vending_id: u32,
+        items: Vec<(InventoryItemData, u32)>,
+    },
+    /// The player may now set up a vending shop with up to this many items.
+    OpenVendingSetup(u16),
+    /// The shop of the player was opened.
+    VendingOpened {
+        title: String,
+    },
+    /// All quests in the quest log of the player.
+    QuestList(Vec<QuestData>),
+    QuestAdded(QuestData),
+    /// The player made progress on an objective of a quest.
+    QuestObjectiveUpdated {
+        hunt_identification: u32,
+        current_count: u16,
+        total_count: u16,
+    },
+    QuestRemoved(u32),
+    /// The map server points the player at a position, usually the target of
+    /// a quest.
+    QuestNavigation {
+        map_name: String,
+        position: Vector2<u16>,
+    },
+    /// All achievements the player made progress on.
+    AchievementList {
+        level: AchievementLevel,
+        achievements: Vec<AchievementInformation>,
+    },
+    AchievementUpdate {
+        level: AchievementLevel,
+        achievement: AchievementInformation,
+    },
+    AchievementRewarded(u32),
+    /// The map server allows the player to use the bank.
+    BankOpened,
+    BankBalance(i64),
+    /// A friend of the player logged in or out.
+    FriendOnlineStatus {
+        name: String,
+        state: OnlineState,
+    },
+    /// The map server sent the emblem of a guild, which still has to be
+    /// decoded.
+    GuildEmblem {
+        guild_id: u32,
+        emblem_id: u32,
+        emblem_data: Vec<u8>,
+    },
     VisualEffect(&'static str, EntityId),
     AddSkillUnit(EntityId, UnitId, Vector2<usize>),
     RemoveSkillUnit(EntityId),
+    /// An entity started casting a skill. `target` is [`None`] for ground
+    /// skills, which are cast on `position` instead.
+    CastSkill {
+        caster: EntityId,
+        target: Option<EntityId>,
+        position: Vector2<usize>,
+        skill_id: SkillId,
+        cast_time: u32,
+    },
+    CastCancelled(EntityId),
+    /// A skill took effect, so its visual and sound effect should be played.
+    SkillEffect {
+        caster: EntityId,
+        target: SkillTarget,
+        skill_id: SkillId,
+    },
+    /// A status effect, like a weapon endow, started or ended on an entity.
+    /// The index is the status icon id used by the map server.
+    UpdateStatusEffect {
+        entity_id: EntityId,
+        status_index: u16,
+        active: bool,
+    },
+}
+
+/// State of a single item in the inventory of the player.
+#[derive(Clone, Debug)]
+pub struct InventoryItemData {
+    pub index: ItemIndex,
+    pub item_id: ItemId,
+    pub equip_position: EquipPosition,
+    pub equipped_position: EquipPosition,
+    pub item_type: u8,
+    pub amount: u16,
+    pub refinement_level: u8,
+    /// Item ids of the cards in each slot, `0` for empty slots.
+    pub cards: [u32; 4],
+    pub is_broken: bool,
+    pub is_favorite: bool,
+}
+
+/// Number of items and weight of the cart of the player.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CartInformation {
+    pub item_count: u16,
+    pub maximum_item_count: u16,
+    pub weight: u32,
+    pub maximum_weight: u32,
+}
+
+/// A mail as shown in the mailbox, without its text and attachments.
+#[derive(Clone, Debug)]
+pub struct MailHeader {
+    pub mail_id: MailId,
+    pub sender_name: String,
+    pub title: String,
+    pub is_read: bool,
+    pub has_zeny: bool,
+    pub has_items: bool,
+}
+
+/// Achievement level of the player. Completing achievements grants
+/// achievement experience.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AchievementLevel {
+    pub level: u16,
+    pub experience: u32,
+    pub experience_to_next_level: u32,
+    pub total_score: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct AchievementInformation {
+    pub achievement_id: u32,
+    pub is_completed: bool,
+    /// Progress of each objective. Unused objectives are zero.
+    pub objectives: [u32; 10],
+    pub is_rewarded: bool,
+}
+
+/// A monster the player has to hunt for a quest.
+#[derive(Clone, Debug)]
+pub struct QuestObjective {
+    /// Used by the map server to identify the objective in updates.
+    pub hunt_identification: u32,
+    pub mob_name: String,
+    pub current_count: u16,
+    pub total_count: u16,
+}
+
+#[derive(Clone, Debug)]
+pub struct QuestData {
+    pub quest_id: u32,
+    /// Inactive quests are paused and don't progress.
+    pub is_active: bool,
+    pub objectives: Vec<QuestObjective>,
+}
+
+/// Strings in packets are read one character per byte. Servers that use
+/// UTF-8 send multibyte characters (for example Korean or Japanese names), so
+/// we reinterpret the text as UTF-8 if that is valid and leave it unchanged
+/// otherwise.
+fn decode_text(text: String) -> String {
+    if text.is_ascii() || text.chars().any(|character| character as u32 > 0xFF) {
+        return text;
+    }
+
+    let bytes: Vec<u8> = text.chars().map(|character| character as u8).collect();
+    String::from_utf8(bytes).unwrap_or(text)
+}
+
+const PARTY_PING_PREFIX: &str = "[ping] ";
+
+/// Extract the sender, map name and position from a party message created by
+/// [`NetworkingSystem::send_party_ping`].
+fn parse_party_ping(message: &str) -> Option<(&str, &str, Vector2<usize>)> {
+    let (sender, text) = message.split_once(" : ")?;
+    let mut arguments = text.strip_prefix(PARTY_PING_PREFIX)?.split(' ');
+
+    let map_name = arguments.next()?;
+    let x = arguments.next()?.parse().ok()?;
+    let y = arguments.next()?.parse().ok()?;
+
+    arguments.next().is_none().then_some((sender, map_name, Vector2::new(x, y)))
 }
 
+const CHAT_HISTORY_FILE: &str = "client/chat_history.ron";
+/// Only the latest messages are saved, so the file doesn't grow forever.
+const MAXIMUM_SAVED_CHAT_MESSAGES: usize = 100;
+
+#[derive(Serialize, Deserialize)]
 pub struct ChatMessage {
     pub text: String,
     pub color: Color,
@@ -205,6 +567,33 @@ impl ChatMessage {
         let start = self.offset * !stamp as usize;
         &self.text[start..]
     }
+
+    /// Name of the player that sent the message, if the message is in the
+    /// `Name : message` format used for player chat.
+    pub fn sender(&self) -> Option<String> {
+        let (name, _) = self.stamped_text(false).split_once(" : ")?;
+
+        (!name.is_empty() && name.len() <= 24 && !name.contains('^')).then(|| name.to_owned())
+    }
+
+    pub fn load_history() -> Vec<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading chat history from {}filename{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(CHAT_HISTORY_FILE)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_history(messages: &[Self]) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving chat history to {}filename{}", MAGENTA, NONE);
+
+        let start = messages.len().saturating_sub(MAXIMUM_SAVED_CHAT_MESSAGES);
+        let data = ron::ser::to_string_pretty(&messages[start..], ron::ser::PrettyConfig::new()).unwrap();
+        std::fs::write(CHAT_HISTORY_FILE, data).expect("unable to write file");
+    }
 }
 
 #[derive(Copy, Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, PartialEq)]
@@ -257,6 +646,46 @@ struct LoginServerLoginSuccessPacket {
     pub character_server_information: Vec<CharacterServerInformation>,
 }
 
+/// Version of [`LoginServerLoginSuccessPacket`] sent by servers older than
+/// 2017-03-15. It has no authentication token and the character server
+/// information isn't padded.
+#[allow(dead_code)]
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0069)]
+struct CompactLoginServerLoginSuccessPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub login_id1: u32,
+    pub account_id: AccountId,
+    pub login_id2: u32,
+    /// Deprecated and always 0 on rAthena
+    pub ip_address: u32,
+    /// Deprecated and always 0 on rAthena
+    pub name: [u8; 24],
+    /// Always 0 on rAthena
+    pub unknown: u16,
+    pub sex: Sex,
+    #[repeating_remaining]
+    pub character_server_information: Vec<CompactCharacterServerInformation>,
+}
+
+impl From<CompactLoginServerLoginSuccessPacket> for LoginServerLoginSuccessPacket {
+    fn from(packet: CompactLoginServerLoginSuccessPacket) -> Self {
+        Self {
+            packet_length: packet.packet_length,
+            login_id1: packet.login_id1,
+            account_id: packet.account_id,
+            login_id2: packet.login_id2,
+            ip_address: packet.ip_address,
+            name: packet.name,
+            unknown: packet.unknown,
+            sex: packet.sex,
+            auth_token: [0; 17],
+            character_server_information: packet.character_server_information.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 /// Sent by the character server as a response to [CharacterServerLoginPacket]
 /// succeeding. Provides basic information about the number of available
 /// character slots.
@@ -441,11 +870,35 @@ struct CharacterSelectionSuccessPacket {
     pub unknown: [u8; 128],
 }
 
+/// Version of [`CharacterSelectionSuccessPacket`] sent by servers older than
+/// 2017-03-15.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0071)]
+struct CompactCharacterSelectionSuccessPacket {
+    pub character_id: CharacterId,
+    #[length_hint(16)]
+    pub map_name: String,
+    pub map_server_ip: Ipv4Addr,
+    pub map_server_port: u16,
+}
+
+impl From<CompactCharacterSelectionSuccessPacket> for CharacterSelectionSuccessPacket {
+    fn from(packet: CompactCharacterSelectionSuccessPacket) -> Self {
+        Self {
+            character_id: packet.character_id,
+            map_name: packet.map_name,
+            map_server_ip: packet.map_server_ip,
+            map_server_port: packet.map_server_port,
+            unknown: [0; 128],
+        }
+    }
+}
+
 #[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
 pub enum CharacterCreationFailedReason {
     CharacterNameAlreadyUsed,
     NotOldEnough,
-    #[numeric_value(3)]
+    SymbolsNotAllowed,
     NotAllowedToUseSlot,
     #[numeric_value(255)]
     CharacterCerationFailed,
@@ -480,6 +933,33 @@ pub struct CharacterServerInformation {
     pub unknown: [u8; 128],
 }
 
+/// Version of [`CharacterServerInformation`] sent by servers older than
+/// 2017-03-15.
+#[derive(Clone, Debug, FromBytes, FixedByteSize, PrototypeElement)]
+pub struct CompactCharacterServerInformation {
+    pub server_ip: Ipv4Addr,
+    pub server_port: u16,
+    #[length_hint(20)]
+    pub server_name: String,
+    pub user_count: u16,
+    pub server_type: u16,
+    pub display_new: u16,
+}
+
+impl From<CompactCharacterServerInformation> for CharacterServerInformation {
+    fn from(information: CompactCharacterServerInformation) -> Self {
+        Self {
+            server_ip: information.server_ip,
+            server_port: information.server_port,
+            server_name: information.server_name,
+            user_count: information.user_count,
+            server_type: information.server_type,
+            display_new: information.display_new,
+            unknown: [0; 128],
+        }
+    }
+}
+
 /// Sent by the client to the character server after after successfully logging
 /// into the login server.
 /// Attempts to log into the character server using the provided information.
@@ -533,7 +1013,73 @@ struct CreateCharacterPacket {
     pub sex: Sex,
 }
 
-#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+/// Version of [`CreateCharacterPacket`] used by servers older than
+/// 2015-10-01. The starting job and the sex can't be chosen.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0970)]
+struct CreateCharacterWithoutJobPacket {
+    #[length_hint(24)]
+    pub name: String,
+    pub slot: u8,
+    pub hair_color: u16,
+    pub hair_style: u16,
+}
+
+/// Version of [`CreateCharacterPacket`] used by servers older than
+/// 2012-03-07. The player distributes the starting stats.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0067)]
+struct CreateCharacterWithStatsPacket {
+    #[length_hint(24)]
+    pub name: String,
+    pub strength: u8,
+    pub agility: u8,
+    pub vitality: u8,
+    pub intelligence: u8,
+    pub dexterity: u8,
+    pub luck: u8,
+    pub slot: u8,
+    pub hair_color: u16,
+    pub hair_style: u16,
+}
+
+/// Which options the character server accepts when creating a character.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CharacterCreationVersion {
+    /// Starting stats are chosen by the player.
+    Stats,
+    /// Only the name and the hair can be chosen.
+    HairOnly,
+    /// The starting job and the sex are chosen by the player.
+    #[default]
+    StartingJob,
+}
+
+impl CharacterCreationVersion {
+    pub fn from_packet_version(packet_version: PacketVersion) -> Self {
+        match packet_version {
+            packet_version if packet_version.before(20120307) => Self::Stats,
+            packet_version if packet_version.before(20151001) => Self::HairOnly,
+            _ => Self::StartingJob,
+        }
+    }
+}
+
+/// Everything the player chose for a new character. Fields that the server
+/// doesn't accept are ignored when the character is created.
+#[derive(Clone, Debug)]
+pub struct CharacterCreationData {
+    pub name: String,
+    pub hair_style: u16,
+    pub hair_color: u16,
+    /// Strength, agility and vitality. Intelligence, luck and dexterity get
+    /// the remaining points, so that each pair adds up to ten.
+    pub stats: [u8; 3],
+    pub start_job: u16,
+    pub sex: Sex,
+}
+
+#[derive(Clone, Debug, PrototypeElement)]
 pub struct CharacterInformation {
     pub character_id: CharacterId,
     pub experience: i64,
@@ -576,6 +1122,8 @@ pub struct CharacterInformation {
     pub b_is_changed_char: i16,
     #[length_hint(16)]
     pub map_name: String,
+    /// Sent as the seconds until the character can be deleted, but stored as
+    /// a Unix timestamp once received. Zero if no deletion is pending.
     pub deletion_reverse_date: i32,
     pub robe_palette: i32,
     pub character_slot_change_count: i32,
@@ -583,6 +1131,215 @@ pub struct CharacterInformation {
     pub sex: Sex,
 }
 
+// NOTE: The layout changed with a lot of packet versions, so the character
+// information is read by hand instead of making most of its fields optional.
+impl FromBytes for CharacterInformation {
+    fn from_bytes<META>(byte_stream: &mut ByteStream<META>) -> ConversionResult<Self> {
+        let packet_version = byte_stream.get_metadata::<Self, NetworkMetadata>()?.packet_version;
+
+        let character_id = CharacterId::from_bytes(byte_stream).trace::<Self>()?;
+        let experience = match packet_version.since(20170830) {
+            true => i64::from_bytes(byte_stream),
+            false => i32::from_bytes(byte_stream).map(i64::from),
+        }
+        .trace::<Self>()?;
+        let money = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let job_experience = match packet_version.since(20170830) {
+            true => i64::from_bytes(byte_stream),
+            false => i32::from_bytes(byte_stream).map(i64::from),
+        }
+        .trace::<Self>()?;
+        let jop_level = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let body_state = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let health_state = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let effect_state = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let virtue = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let honor = i32::from_bytes(byte_stream).trace::<Self>()?;
+        let jobpoint = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let (health_points, maximum_health_points, spell_points, maximum_spell_points) = match packet_version {
+            packet_version if packet_version.since(20211103) => (
+                i64::from_bytes(byte_stream).trace::<Self>()?,
+                i64::from_bytes(byte_stream).trace::<Self>()?,
+                i64::from_bytes(byte_stream).trace::<Self>()?,
+                i64::from_bytes(byte_stream).trace::<Self>()?,
+            ),
+            packet_version if packet_version.since(20081217) => (
+                i32::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i32::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+            ),
+            _ => (
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+                i16::from_bytes(byte_stream).trace::<Self>()?.into(),
+            ),
+        };
+        let movement_speed = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let job = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let head = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let body = match packet_version.since(20141022) {
+            true => i16::from_bytes(byte_stream).trace::<Self>()?,
+            false => 0,
+        };
+        let weapon = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let level = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let sp_point = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let accessory = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let shield = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let accessory2 = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let accessory3 = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let head_palette = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let body_palette = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let name = String::from_n_bytes(byte_stream, 24).trace::<Self>()?;
+        let strength = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let agility = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let vit = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let intelligence = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let dexterity = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let luck = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let character_number = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let hair_color = u8::from_bytes(byte_stream).trace::<Self>()?;
+        let b_is_changed_char = i16::from_bytes(byte_stream).trace::<Self>()?;
+        let (map_name, deletion_reverse_date) = match packet_version.since(20100803) {
+            true => (
+                String::from_n_bytes(byte_stream, 16).trace::<Self>()?,
+                i32::from_bytes(byte_stream).trace::<Self>()?,
+            ),
+            false => (String::new(), 0),
+        };
+        let robe_palette = match packet_version.since(20110111) {
+            true => i32::from_bytes(byte_stream).trace::<Self>()?,
+            false => 0,
+        };
+        let character_slot_change_count = match packet_version.since(20110928) {
+            true => i32::from_bytes(byte_stream).trace::<Self>()?,
+            false => 0,
+        };
+        let character_name_change_count = match packet_version.since(20111025) {
+            true => i32::from_bytes(byte_stream).trace::<Self>()?,
+            false => 0,
+        };
+        // NOTE: Characters of older servers always have the sex of the account, which
+        // is set once the character information is received.
+        let sex = match packet_version.since(20141016) {
+            true => Sex::from_bytes(byte_stream).trace::<Self>()?,
+            false => Sex::Server,
+        };
+
+        Ok(Self {
+            character_id,
+            experience,
+            money,
+            job_experience,
+            jop_level,
+            body_state,
+            health_state,
+            effect_state,
+            virtue,
+            honor,
+            jobpoint,
+            health_points,
+            maximum_health_points,
+            spell_points,
+            maximum_spell_points,
+            movement_speed,
+            job,
+            head,
+            body,
+            weapon,
+            level,
+            sp_point,
+            accessory,
+            shield,
+            accessory2,
+            accessory3,
+            head_palette,
+            body_palette,
+            name,
+            strength,
+            agility,
+            vit,
+            intelligence,
+            dexterity,
+            luck,
+            character_number,
+            hair_color,
+            b_is_changed_char,
+            map_name,
+            deletion_reverse_date,
+            robe_palette,
+            character_slot_change_count,
+            character_name_change_count,
+            sex,
+        })
+    }
+}
+
+impl CharacterInformation {
+    /// Size of the character information in the packet version of the
+    /// server, used to get the number of characters in a list.
+    fn size_in_bytes<META>(byte_stream: &ByteStream<META>) -> ConversionResult<usize> {
+        let packet_version = byte_stream.get_metadata::<Self, NetworkMetadata>()?.packet_version;
+
+        let points_size = match packet_version {
+            packet_version if packet_version.since(20211103) => 32,
+            packet_version if packet_version.since(20081217) => 12,
+            _ => 8,
+        };
+        let optional_sizes = [
+            (20170830, 8),
+            (20141022, 2),
+            (20100803, 20),
+            (20110111, 4),
+            (20110928, 4),
+            (20111025, 4),
+            (20141016, 1),
+        ];
+        let optional_size: usize = optional_sizes
+            .into_iter()
+            .filter(|(since, _)| packet_version.since(*since))
+            .map(|(_, size)| size)
+            .sum();
+
+        Ok(100 + points_size + optional_size)
+    }
+
+    /// Characters of servers older than 2014-10-16 don't have a sex of their
+    /// own, so they use the one of the account.
+    fn with_account_sex(mut self, sex: Sex) -> Self {
+        if self.sex == Sex::Server {
+            self.sex = sex;
+        }
+
+        self
+    }
+
+    /// Convert the remaining time of a pending deletion to a point in time,
+    /// so it stays correct while the character list is shown.
+    fn with_deletion_timestamp(mut self) -> Self {
+        if self.deletion_reverse_date > 0 {
+            self.deletion_reverse_date = deletion_timestamp(self.deletion_reverse_date);
+        }
+
+        self
+    }
+
+    /// Time after which the character can be deleted, if a deletion is
+    /// pending.
+    pub fn deletion_date(&self) -> Option<DateTime<Local>> {
+        (self.deletion_reverse_date > 0)
+            .then(|| DateTime::from_timestamp(self.deletion_reverse_date as i64, 0))
+            .flatten()
+            .map(|date| date.with_timezone(&Local))
+    }
+}
+
+fn deletion_timestamp(remaining_seconds: i32) -> i32 {
+    (Local::now().timestamp() as i32).saturating_add(remaining_seconds)
+}
+
 /// Sent by the character server as a response to [CreateCharacterPacket]
 /// succeeding. Provides all character information of the newly created
 /// character.
@@ -592,6 +1349,14 @@ struct CreateCharacterSuccessPacket {
     pub character_information: CharacterInformation,
 }
 
+/// Version of [`CreateCharacterSuccessPacket`] sent by servers older than
+/// 2021-11-03.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x006D)]
+struct CompactCreateCharacterSuccessPacket {
+    pub character_information: CharacterInformation,
+}
+
 /// Sent by the client to the character server.
 /// Requests a list of every character associated with the account.
 #[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
@@ -603,9 +1368,18 @@ struct RequestCharacterListPacket {}
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x0B72)]
 struct RequestCharacterListSuccessPacket {
-    #[packet_length]
     pub packet_length: u16,
-    #[repeating_remaining]
+    #[repeating((self.packet_length as usize - 4) / CharacterInformation::size_in_bytes(byte_stream)?)]
+    pub character_information: Vec<CharacterInformation>,
+}
+
+/// Version of [`RequestCharacterListSuccessPacket`] sent by servers older than
+/// 2021-11-03.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x099D)]
+struct CompactRequestCharacterListSuccessPacket {
+    pub packet_length: u16,
+    #[repeating((self.packet_length as usize - 4) / CharacterInformation::size_in_bytes(byte_stream)?)]
     pub character_information: Vec<CharacterInformation>,
 }
 
@@ -696,29 +1470,127 @@ struct CharacterDeletionFailedPacket {
 #[header(0x006F)]
 struct CharacterDeletionSuccessPacket {}
 
-/// Sent by the client to the character server when the user selects a
-/// character. Attempts to select the character in the specified slot.
+/// Sent by the client to the character server to start the waiting period
+/// after which a character can be deleted. Used instead of
+/// [DeleteCharacterPacket] by newer servers.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0066)]
-struct SelectCharacterPacket {
-    pub selected_slot: u8,
+#[header(0x0827)]
+struct ReserveCharacterDeletionPacket {
+    pub character_id: CharacterId,
 }
 
-/// Sent by the map server to the client when there is a new chat message from
-/// the server. Provides the message to be displayed in the chat window.
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, PartialEq, Eq)]
+#[numeric_type(u32)]
+enum ReserveCharacterDeletionResult {
+    Error,
+    Success,
+    DatabaseError,
+    InGuild,
+    InParty,
+}
+
+/// Sent by the character server as a response to
+/// [ReserveCharacterDeletionPacket].
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x008E)]
-struct ServerMessagePacket {
-    pub packet_length: u16,
-    #[length_hint(self.packet_length - 4)]
-    pub message: String,
+#[header(0x0828)]
+struct ReserveCharacterDeletionResultPacket {
+    pub character_id: CharacterId,
+    pub result: ReserveCharacterDeletionResult,
+    /// Seconds until the character can be deleted.
+    pub remaining_seconds: i32,
 }
 
-/// Sent by the client to the map server when the user hovers over an entity.
-/// Attempts to fetch additional information about the entity, such as the
-/// display name.
+/// Sent by the client to the character server to delete a character after
+/// the waiting period is over.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0368)]
+#[header(0x0829)]
+struct DeleteReservedCharacterPacket {
+    pub character_id: CharacterId,
+    /// Birthdate of the account owner in the format `YYMMDD`.
+    #[length_hint(6)]
+    pub birthdate: String,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, PartialEq, Eq)]
+#[numeric_type(u32)]
+enum DeleteReservedCharacterResult {
+    Error,
+    Success,
+    NotAllowed,
+    DatabaseError,
+    TooEarly,
+    BirthdateMismatch,
+}
+
+/// Sent by the character server as a response to
+/// [DeleteReservedCharacterPacket].
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x082A)]
+struct DeleteReservedCharacterResultPacket {
+    pub character_id: CharacterId,
+    pub result: DeleteReservedCharacterResult,
+}
+
+/// Sent by the client to the character server to stop a pending deletion.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x082B)]
+struct CancelCharacterDeletionPacket {
+    pub character_id: CharacterId,
+}
+
+/// Sent by the character server as a response to
+/// [CancelCharacterDeletionPacket]. A result of one means success.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x082C)]
+struct CancelCharacterDeletionResultPacket {
+    pub character_id: CharacterId,
+    pub result: u32,
+}
+
+/// How the character server expects characters to be deleted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CharacterDeletionVersion {
+    /// Characters are deleted right away after entering the e-mail address of
+    /// the account.
+    Email,
+    /// Characters are deleted after a waiting period, by entering the
+    /// birthdate of the account owner.
+    #[default]
+    Timer,
+}
+
+impl CharacterDeletionVersion {
+    pub fn from_packet_version(packet_version: PacketVersion) -> Self {
+        match packet_version.before(20100803) {
+            true => Self::Email,
+            false => Self::Timer,
+        }
+    }
+}
+
+/// Sent by the client to the character server when the user selects a
+/// character. Attempts to select the character in the specified slot.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0066)]
+struct SelectCharacterPacket {
+    pub selected_slot: u8,
+}
+
+/// Sent by the map server to the client when there is a new chat message from
+/// the server. Provides the message to be displayed in the chat window.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x008E)]
+struct ServerMessagePacket {
+    pub packet_length: u16,
+    #[length_hint(self.packet_length - 4)]
+    pub message: String,
+}
+
+/// Sent by the client to the map server when the user hovers over an entity.
+/// Attempts to fetch additional information about the entity, such as the
+/// display name.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0368)]
 struct RequestDetailsPacket {
     pub entity_id: EntityId,
 }
@@ -753,12 +1625,6 @@ struct RequestEntityDetailsSuccessPacket {
     pub title: String,
 }
 
-#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x09E7)]
-struct NewMailStatusPacket {
-    pub new_available: u8,
-}
-
 #[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
 struct AchievementData {
     pub acheivement_id: u32,
@@ -792,6 +1658,21 @@ struct AchievementListPacket {
     pub acheivement_data: Vec<AchievementData>,
 }
 
+/// Sent by the client to the map server to claim the reward of a completed
+/// achievement.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0A25)]
+struct RequestAchievementRewardPacket {
+    pub achievement_id: u32,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A26)]
+struct AchievementRewardResultPacket {
+    pub result: u16,
+    pub achievement_id: u32,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x0ADE)]
 struct CriticalWeightUpdatePacket {
@@ -839,7 +1720,7 @@ struct RegularItemInformation {
     pub wear_state: u32,
     pub slot: [u32; 4], // card ?
     pub hire_expiration_date: i32,
-    pub fags: u8, // bit 1 - is_identified; bit 2 - place_in_etc_tab;
+    pub fags: u8, // bit 1 - is_identified; bit 2 - is_favorite;
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
@@ -867,7 +1748,7 @@ struct EquippableItemInformation {
     pub option_data: [ItemOptions; 5], // fix count
     pub refinement_level: u8,
     pub enchantment_level: u8,
-    pub fags: u8, // bit 1 - is_identified; bit 2 - is_damaged; bit 3 - place_in_etc_tab
+    pub fags: u8, // bit 1 - is_identified; bit 2 - is_damaged; bit 3 - is_favorite
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
@@ -1127,7 +2008,7 @@ struct UpdateStatusPacket {
 #[header(0x0196)]
 struct StatusChangeSequencePacket {
     pub index: u16,
-    pub id: u32,
+    pub entity_id: EntityId,
     pub state: u8,
 }
 
@@ -1188,6 +2069,42 @@ struct UpdateStatusPacket3 {
     pub status_type: StatusType,
 }
 
+/// The base stats that can be raised with status points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, FixedByteSize, PrototypeElement)]
+#[numeric_type(u16)]
+pub enum StatType {
+    #[numeric_value(13)]
+    Strength,
+    #[numeric_value(14)]
+    Agility,
+    #[numeric_value(15)]
+    Vitality,
+    #[numeric_value(16)]
+    Intelligence,
+    #[numeric_value(17)]
+    Dexterity,
+    #[numeric_value(18)]
+    Luck,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00BB)]
+struct RequestStatUpPacket {
+    pub stat_type: StatType,
+    pub amount: u8,
+}
+
+/// Sent by the map server as a response to [RequestStatUpPacket].
+/// This packet is ignored by Korangar since the new values and the remaining
+/// status points are sent again using the UpdateStatusPackets.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00BC)]
+struct RequestStatUpResponsePacket {
+    pub stat_type: StatType,
+    pub result: u8,
+    pub value: u8,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x013A)]
 struct UpdateAttackRangePacket {
@@ -1225,6 +2142,12 @@ struct RequestActionPacket {
     pub action: Action,
 }
 
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0362)]
+struct RequestPickUpItemPacket {
+    pub entity_id: EntityId,
+}
+
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
 #[header(0x00F3)]
 struct GlobalMessagePacket {
@@ -1232,6 +2155,45 @@ struct GlobalMessagePacket {
     pub message: String,
 }
 
+/// Sent by the client to the map server when the player writes in party chat.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0108)]
+struct PartyMessagePacket {
+    pub packet_length: u16,
+    pub message: String,
+}
+
+/// Sent by the map server to the client when a party member writes in party
+/// chat.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0109)]
+struct NotifyPartyMessagePacket {
+    pub packet_length: u16,
+    pub account_id: AccountId,
+    #[length_hint(self.packet_length - 8)]
+    pub message: String,
+}
+
+/// Sent by the map server to the client when a party member on the same map
+/// moves or when the player enters a map with other party members on it.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0107)]
+struct NotifyPartyMemberPositionPacket {
+    pub account_id: AccountId,
+    pub position: Vector2<u16>,
+}
+
+/// Sent by the map server to the client when a member leaves the party or is
+/// expelled from it.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0105)]
+struct PartyMemberLeftPacket {
+    pub account_id: AccountId,
+    #[length_hint(24)]
+    pub name: String,
+    pub result: u8,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x0139)]
 struct RequestPlayerAttackFailedPacket {
@@ -1269,6 +2231,13 @@ struct DamagePacket {
     pub damage_amount2: u32,
 }
 
+impl DamagePacket {
+    /// Critical hits and critical multi-hits.
+    fn is_critical(&self) -> bool {
+        matches!(self.damage_type, 10 | 13)
+    }
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x007F)]
 #[ping]
@@ -1359,7 +2328,8 @@ struct MovingEntityAppearedPacket {
     pub maximum_health_points: i32,
     pub health_points: i32,
     pub is_boss: u8,
-    pub body: u16,
+    #[packet_version_since(20150513)]
+    pub body: Option<u16>,
     #[length_hint(24)]
     pub name: String,
 }
@@ -1400,7 +2370,8 @@ struct EntityAppearedPacket {
     pub maximum_health_points: i32,
     pub health_points: i32,
     pub is_boss: u8,
-    pub body: u16,
+    #[packet_version_since(20150513)]
+    pub body: Option<u16>,
     #[length_hint(24)]
     pub name: String,
 }
@@ -1442,7 +2413,8 @@ struct EntityAppeared2Packet {
     pub maximum_health_points: i32,
     pub health_points: i32,
     pub is_boss: u8,
-    pub body: u16,
+    #[packet_version_since(20150513)]
+    pub body: Option<u16>,
     #[length_hint(24)]
     pub name: String,
 }
@@ -1457,6 +2429,8 @@ pub struct EntityData {
     pub maximum_health_points: i32,
     pub head_direction: usize,
     pub sex: Sex,
+    pub guild_id: Option<u32>,
+    pub emblem_id: u32,
 }
 
 impl EntityData {
@@ -1471,6 +2445,8 @@ impl EntityData {
             maximum_health_points: character_information.maximum_health_points as i32,
             head_direction: 0, // TODO: get correct rotation
             sex: character_information.sex,
+            guild_id: None,
+            emblem_id: 0,
         }
     }
 }
@@ -1487,6 +2463,8 @@ impl From<EntityAppearedPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            guild_id: (packet.guild_id != 0).then_some(packet.guild_id),
+            emblem_id: packet.emblem_version as u32,
         }
     }
 }
@@ -1503,6 +2481,8 @@ impl From<EntityAppeared2Packet> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            guild_id: (packet.guild_id != 0).then_some(packet.guild_id),
+            emblem_id: packet.emblem_version as u32,
         }
     }
 }
@@ -1521,6 +2501,8 @@ impl From<MovingEntityAppearedPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            guild_id: (packet.guild_id != 0).then_some(packet.guild_id),
+            emblem_id: packet.emblem_version as u32,
         }
     }
 }
@@ -1563,10 +2545,12 @@ struct UpdateSkillTreePacket {
     pub skill_information: Vec<SkillInformation>,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-struct HotkeyData {
+/// A single shortcut as stored by the map server. The id is a skill id or an
+/// item id depending on `is_skill`.
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, new)]
+pub struct HotkeyData {
     pub is_skill: u8,
-    pub skill_id: u32,
+    pub id: u32,
     pub quantity_or_skill_level: SkillLevel,
 }
 
@@ -1578,6 +2562,16 @@ struct UpdateHotkeysPacket {
     pub hotkeys: [HotkeyData; 38],
 }
 
+/// Sent by the client to the map server to store a shortcut, so it persists
+/// across sessions and clients.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0B21)]
+struct SetHotkeyPacket {
+    pub tab: u16,
+    pub index: u16,
+    pub hotkey: HotkeyData,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x02C9)]
 struct UpdatePartyInvitationStatePacket {
@@ -1659,7 +2653,8 @@ struct DisplaySpecialEffectPacket {
 #[header(0x043D)]
 struct DisplaySkillCooldownPacket {
     pub skill_id: SkillId,
-    pub until: ClientTick,
+    /// Remaining cooldown in milliseconds.
+    pub duration: u32,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
@@ -1739,10 +2734,12 @@ struct QuestNotificationPacket1 {
     pub objective_details: [ObjectiveDetails1; 3],
 }
 
+/// The hunt identification is the same as in [`QuestDetails`], the map
+/// server sends it twice.
 #[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
 struct HuntingObjective {
-    pub quest_id: u32,
-    pub mob_id: u32,
+    pub hunt_identification: u32,
+    pub hunt_identification2: u32,
     pub total_count: u16,
     pub current_count: u16,
 }
@@ -1903,6 +2900,43 @@ struct ItemPickupPacket {
     pub enchantment_level: u8,
 }
 
+/// Sent when an item is dropped on the ground, e.g. by a monster.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0ADD)]
+struct ItemDroppedPacket {
+    pub entity_id: EntityId,
+    pub item_id: ItemId,
+    pub item_type: u16,
+    pub is_identified: u8,
+    pub position_x: u16,
+    pub position_y: u16,
+    pub sub_x: u8,
+    pub sub_y: u8,
+    pub count: u16,
+    pub show_drop_effect: u8,
+    pub drop_effect_mode: u16,
+}
+
+/// Sent for items that already lie on the ground when they come into view.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x009D)]
+struct ItemOnGroundPacket {
+    pub entity_id: EntityId,
+    pub item_id: ItemId,
+    pub is_identified: u8,
+    pub position_x: u16,
+    pub position_y: u16,
+    pub count: u16,
+    pub sub_x: u8,
+    pub sub_y: u8,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00A1)]
+struct ItemDisappearedPacket {
+    pub entity_id: EntityId,
+}
+
 #[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
 #[numeric_type(u16)]
 enum RemoveItemReason {
@@ -1920,111 +2954,559 @@ enum RemoveItemReason {
 #[header(0x07FA)]
 struct RemoveItemFromInventoryPacket {
     pub remove_reason: RemoveItemReason,
-    pub index: u16,
+    pub index: ItemIndex,
     pub amount: u16,
 }
 
-// TODO: improve names
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-#[numeric_type(u16)]
-pub enum QuestEffect {
-    Quest,
-    Quest2,
-    Job,
-    Job2,
-    Event,
-    Event2,
-    ClickMe,
-    DailyQuest,
-    Event3,
-    JobQuest,
-    JumpingPoring,
-    #[numeric_value(9999)]
-    None,
+/// Sent by the map server whenever the content of the cart changes.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0121)]
+struct CartInformationPacket {
+    pub item_count: u16,
+    pub maximum_item_count: u16,
+    pub weight: u32,
+    pub maximum_weight: u32,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-#[numeric_type(u16)]
-pub enum QuestColor {
-    Yellow,
-    Orange,
-    Green,
-    Purple,
+/// Sent by the map server when an item was put into the cart.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0B45)]
+struct CartItemAddedPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
 }
 
+/// Sent by the map server when an item was taken out of the cart.
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x0446)]
-pub struct QuestEffectPacket {
-    pub entity_id: EntityId,
-    pub position: Vector2<u16>,
-    pub effect: QuestEffect,
-    pub color: QuestColor,
+#[header(0x0125)]
+struct CartItemRemovedPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
+}
+
+/// Sent by the client to the map server to put an item from the inventory
+/// into the cart.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0126)]
+struct MoveItemToCartPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
+}
+
+/// Sent by the client to the map server to take an item out of the cart and
+/// put it into the inventory.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0127)]
+struct MoveItemFromCartPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
 }
 
+/// Sent by the map server when the player no longer has a cart.
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x00B4)]
-struct NpcDialogPacket {
-    pub packet_length: u16,
-    pub npc_id: EntityId,
-    #[length_hint(self.packet_length - 8)]
-    pub text: String,
+#[header(0x012B)]
+struct CartRemovedPacket {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum AddItemToCartResult {
+    Overweight,
+    TooManyItems,
 }
 
-#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
-#[header(0x007D)]
-struct MapLoadedPacket {}
+/// Sent by the map server if an item could not be put into the cart.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x012C)]
+struct AddItemToCartResultPacket {
+    pub result: AddItemToCartResult,
+}
 
-#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0187)]
-#[ping]
-struct CharacterServerKeepalivePacket {
-    /// rAthena never reads this value, so just set it to 0.
-    #[new(value = "AccountId(0)")]
-    pub account_id: AccountId,
+impl AddItemToCartResultPacket {
+    pub fn message(&self) -> &'static str {
+        // Messages taken from the official client
+        match self.result {
+            AddItemToCartResult::Overweight => "You can't put this item into your cart because it is too heavy.",
+            AddItemToCartResult::TooManyItems => "You can't put any more items into your cart.",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum MailboxType {
+    Character,
+    Account,
+    Returned,
 }
 
+/// Sent by the client to the map server to open the mailbox. The map server
+/// answers with the newest mails.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0090)]
-struct StartDialogPacket {
-    pub npc_id: EntityId,
-    #[new(value = "1")]
-    pub dialog_type: u8,
+#[header(0x09E8)]
+struct OpenMailboxPacket {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
 }
 
+/// Sent by the client to the map server to get the mails that are older than
+/// the given mail.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x00B9)]
-struct NextDialogPacket {
-    pub npc_id: EntityId,
+#[header(0x09EE)]
+struct RequestNextMailPagePacket {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+}
+
+/// Packet version that introduced [`OpenMailboxPacket2`],
+/// [`RequestNextMailPagePacket2`] and [`MailListPacket2`].
+const RODEX_MAIL_LIST_VERSION: u32 = 20170419;
+
+/// Mail flags of a [`MailListEntry`].
+const MAIL_FLAG_ZENY: u8 = 0x02;
+const MAIL_FLAG_ITEM: u8 = 0x04;
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct MailListEntry {
+    pub mail_id: MailId,
+    pub is_read: u8,
+    pub flags: u8,
+    #[length_hint(24)]
+    pub sender_name: String,
+    pub received_time: u32,
+    pub expiration_time: u32,
+    pub title_length: u16,
+    #[length_hint(self.title_length)]
+    pub title: String,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09F0)]
+struct MailListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub mailbox_type: MailboxType,
+    pub mail_count: u8,
+    pub is_end: u8,
+    #[repeating(self.mail_count)]
+    pub mails: Vec<MailListEntry>,
 }
 
+/// Replaces [`OpenMailboxPacket`] since packet version 20170419.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0146)]
-struct CloseDialogPacket {
-    pub npc_id: EntityId,
+#[header(0x0AC0)]
+struct OpenMailboxPacket2 {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+    /// Unused
+    #[new(default)]
+    pub unknown: [u8; 15],
 }
 
+/// Replaces [`RequestNextMailPagePacket`] since packet version 20170419.
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x00B8)]
-struct ChooseDialogOptionPacket {
-    pub npc_id: EntityId,
-    pub option: i8,
+#[header(0x0AC1)]
+struct RequestNextMailPagePacket2 {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+    /// Unused
+    #[new(default)]
+    pub unknown: [u8; 15],
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, FixedByteSize, PrototypeElement)]
-#[numeric_type(u32)]
-pub enum EquipPosition {
-    #[numeric_value(0)]
-    None,
-    #[numeric_value(1)]
-    HeadLower,
-    #[numeric_value(512)]
-    HeadMiddle,
-    #[numeric_value(256)]
-    HeadTop,
-    #[numeric_value(2)]
-    RightHand,
-    #[numeric_value(32)]
-    LeftHand,
+/// Entries of a [`MailListPacket2`] carry their own mailbox type and no
+/// longer have a received time.
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct MailListEntry2 {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+    pub is_read: u8,
+    pub flags: u8,
+    #[length_hint(24)]
+    pub sender_name: String,
+    pub expiration_time: u32,
+    pub title_length: u16,
+    #[length_hint(self.title_length)]
+    pub title: String,
+}
+
+/// Replaces [`MailListPacket`] since packet version 20170419. The mails fill
+/// the rest of the packet, so there is no mail count.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0AC2)]
+struct MailListPacket2 {
+    #[packet_length]
+    pub packet_length: u16,
+    pub is_end: u8,
+    #[length_hint(self.packet_length - 5)]
+    pub mails: Vec<MailListEntry2>,
+}
+
+/// Sent by the map server when the player logs in or receives a mail while
+/// online.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09E7)]
+struct NewMailStatusPacket {
+    pub new_available: u8,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09EA)]
+struct ReadMailPacket {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct MailItemInformation {
+    pub amount: u16,
+    pub item_id: ItemId,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub equip_position: EquipPosition,
+    pub item_type: u8,
+    pub view_sprite: u16,
+    pub bind_on_equip_type: u16,
+    pub option_data: [ItemOptions; 5], // fix count
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09EB)]
+struct MailContentPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+    pub text_length: u16,
+    pub zeny: u64,
+    pub item_count: u8,
+    #[length_hint(self.text_length)]
+    pub text: String,
+    #[repeating(self.item_count)]
+    pub items: Vec<MailItemInformation>,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09F5)]
+struct DeleteMailPacket {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09F6)]
+struct DeleteMailResultPacket {
+    pub mailbox_type: MailboxType,
+    pub mail_id: MailId,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09F1)]
+struct TakeMailZenyPacket {
+    pub mail_id: MailId,
+    pub mailbox_type: MailboxType,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09F2)]
+struct TakeMailZenyResultPacket {
+    pub mail_id: MailId,
+    pub mailbox_type: MailboxType,
+    pub result: u8,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09F3)]
+struct TakeMailItemsPacket {
+    pub mail_id: MailId,
+    pub mailbox_type: MailboxType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum TakeMailItemsResult {
+    Success,
+    Failed,
+    Overweight,
+    TooManyItems,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09F4)]
+struct TakeMailItemsResultPacket {
+    pub mail_id: MailId,
+    pub mailbox_type: MailboxType,
+    pub result: TakeMailItemsResult,
+}
+
+impl TakeMailItemsResultPacket {
+    pub fn message(&self) -> Option<&'static str> {
+        match self.result {
+            TakeMailItemsResult::Success => None,
+            TakeMailItemsResult::Failed => Some("Failed to take the items out of the mail."),
+            TakeMailItemsResult::Overweight => Some("You can't take the items because they are too heavy."),
+            TakeMailItemsResult::TooManyItems => Some("You can't take the items because your inventory is full."),
+        }
+    }
+}
+
+/// Sent by the client to the map server to start writing a mail. Items can
+/// only be attached after the map server accepted this.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0A08)]
+struct OpenMailComposePacket {
+    #[length_hint(24)]
+    pub receiver_name: String,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A12)]
+struct OpenMailComposeResultPacket {
+    #[length_hint(24)]
+    pub receiver_name: String,
+    pub success: u8,
+}
+
+/// Sent by the client to the map server to stop writing a mail. Attached
+/// items and zeny stay in the inventory.
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x0A03)]
+struct CancelMailComposePacket {}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0A04)]
+struct AttachMailItemPacket {
+    pub index: ItemIndex,
+    pub amount: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum AttachMailItemResult {
+    Success,
+    Overweight,
+    Failed,
+    TooManyItems,
+    InvalidItem,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A05)]
+struct AttachMailItemResultPacket {
+    pub result: AttachMailItemResult,
+    pub index: ItemIndex,
+    /// Total amount of the item that is attached to the mail.
+    pub amount: u16,
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub weight: u16,
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+impl AttachMailItemResultPacket {
+    pub fn message(&self) -> Option<&'static str> {
+        match self.result {
+            AttachMailItemResult::Success => None,
+            AttachMailItemResult::Overweight => Some("The attached items are too heavy."),
+            AttachMailItemResult::Failed => Some("Failed to attach the item."),
+            AttachMailItemResult::TooManyItems => Some("You can't attach any more items."),
+            AttachMailItemResult::InvalidItem => Some("This item can't be sent by mail."),
+        }
+    }
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0A06)]
+struct DetachMailItemPacket {
+    pub index: ItemIndex,
+    pub amount: u16,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A07)]
+struct DetachMailItemResultPacket {
+    pub result: u8,
+    pub index: ItemIndex,
+    /// Amount of the item that is still attached to the mail.
+    pub amount: u16,
+    pub weight: u16,
+}
+
+/// Sent by the client to the map server to look up the character a mail is
+/// sent to. Mails can only be sent once the receiver is known.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0A13)]
+struct CheckMailReceiverPacket {
+    #[length_hint(24)]
+    pub receiver_name: String,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A14)]
+struct CheckMailReceiverResultPacket {
+    /// Zero if there is no character with the name.
+    pub character_id: CharacterId,
+    pub job: u16,
+    pub base_level: u16,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09EC)]
+struct SendMailPacket {
+    pub packet_length: u16,
+    #[length_hint(24)]
+    pub receiver_name: String,
+    #[length_hint(24)]
+    pub sender_name: String,
+    pub zeny: u64,
+    pub title_length: u16,
+    pub text_length: u16,
+    pub receiver_id: CharacterId,
+    #[length_hint(self.title_length)]
+    pub title: String,
+    #[length_hint(self.text_length)]
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum SendMailResult {
+    Success,
+    Failed,
+    InsufficientZeny,
+    Overweight,
+    TooManyMails,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09ED)]
+struct SendMailResultPacket {
+    pub result: SendMailResult,
+}
+
+impl SendMailResultPacket {
+    pub fn message(&self) -> &'static str {
+        match self.result {
+            SendMailResult::Success => "The mail was sent.",
+            SendMailResult::Failed => "Failed to send the mail.",
+            SendMailResult::InsufficientZeny => "You don't have enough zeny to send this mail.",
+            SendMailResult::Overweight => "The attached items are too heavy.",
+            SendMailResult::TooManyMails => "You can't send any more mails today.",
+        }
+    }
+}
+
+// TODO: improve names
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+#[numeric_type(u16)]
+pub enum QuestEffect {
+    Quest,
+    Quest2,
+    Job,
+    Job2,
+    Event,
+    Event2,
+    ClickMe,
+    DailyQuest,
+    Event3,
+    JobQuest,
+    JumpingPoring,
+    #[numeric_value(9999)]
+    None,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+#[numeric_type(u16)]
+pub enum QuestColor {
+    Yellow,
+    Orange,
+    Green,
+    Purple,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0446)]
+pub struct QuestEffectPacket {
+    pub entity_id: EntityId,
+    pub position: Vector2<u16>,
+    pub effect: QuestEffect,
+    pub color: QuestColor,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00B4)]
+struct NpcDialogPacket {
+    pub packet_length: u16,
+    pub npc_id: EntityId,
+    #[length_hint(self.packet_length - 8)]
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x007D)]
+struct MapLoadedPacket {}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0187)]
+#[ping]
+struct CharacterServerKeepalivePacket {
+    /// rAthena never reads this value, so just set it to 0.
+    #[new(value = "AccountId(0)")]
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0090)]
+struct StartDialogPacket {
+    pub npc_id: EntityId,
+    #[new(value = "1")]
+    pub dialog_type: u8,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00B9)]
+struct NextDialogPacket {
+    pub npc_id: EntityId,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0146)]
+struct CloseDialogPacket {
+    pub npc_id: EntityId,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00B8)]
+struct ChooseDialogOptionPacket {
+    pub npc_id: EntityId,
+    pub option: i8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, FixedByteSize, PrototypeElement, Serialize, Deserialize)]
+#[numeric_type(u32)]
+pub enum EquipPosition {
+    #[numeric_value(0)]
+    None,
+    #[numeric_value(1)]
+    HeadLower,
+    #[numeric_value(512)]
+    HeadMiddle,
+    #[numeric_value(256)]
+    HeadTop,
+    #[numeric_value(2)]
+    RightHand,
+    #[numeric_value(32)]
+    LeftHand,
     #[numeric_value(16)]
     Armor,
     #[numeric_value(64)]
@@ -2104,15 +3586,49 @@ struct RequestEquipItemPacket {
     pub equip_position: EquipPosition,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-enum RequestEquipItemStatus {
-    Success,
-    Failed,
-    FailedDueToLevelRequirement,
+/// Sent by the client to the map server to drop an item from the inventory on
+/// the ground.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0363)]
+struct DropItemPacket {
+    pub item_index: ItemIndex,
+    pub amount: u16,
 }
 
-#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x0999)]
+/// Sent by the client to the map server to move an item to or from the
+/// favorites tab of the inventory.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0907)]
+struct SetItemFavoritePacket {
+    pub item_index: ItemIndex,
+    pub is_favorite: u8,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0908)]
+struct ItemFavoriteStatusPacket {
+    pub item_index: ItemIndex,
+    pub is_favorite: u8,
+}
+
+/// Sent by the client to the map server to use a consumable item from the
+/// inventory.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0439)]
+struct UseItemPacket {
+    pub item_index: ItemIndex,
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum RequestEquipItemStatus {
+    Success,
+    Failed,
+    FailedDueToLevelRequirement,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0999)]
 struct RequestEquipItemStatusPacket {
     pub inventory_index: ItemIndex,
     pub equipped_position: EquipPosition,
@@ -2175,6 +3691,15 @@ enum DisconnectResponseStatus {
     Wait10Seconds,
 }
 
+/// Sent when quitting the game, so the character is removed from the map
+/// right away instead of after the connection times out.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x018A)]
+struct RequestDisconnectPacket {
+    /// Always 0 in the official client.
+    pub disconnect_type: u16,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x018B)]
 struct DisconnectResponsePacket {
@@ -2225,6 +3750,12 @@ struct UseSkillSuccessPacket {
     pub disposable: u8,
 }
 
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x01B9)]
+struct CastCancelledPacket {
+    pub entity_id: EntityId,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x0110)]
 struct ToUseSkillSuccessPacket {
@@ -2480,6 +4011,606 @@ struct RemoveFriendPacket {
     pub character_id: CharacterId,
 }
 
+/// Sent by the client to the map server when the player whispers to another
+/// player.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0096)]
+struct WhisperMessagePacket {
+    pub packet_length: u16,
+    #[length_hint(24)]
+    pub name: String,
+    pub message: String,
+}
+
+/// Sent by the client to the map server to ask another player for a trade.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00E4)]
+struct RequestTradePacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the map server when another player asks the player for a trade.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x01F4)]
+struct TradeRequestPacket {
+    #[length_hint(24)]
+    pub name: String,
+    pub character_id: CharacterId,
+    pub base_level: u16,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+enum TradeRequestResponse {
+    #[numeric_value(3)]
+    Accept,
+    #[numeric_value(4)]
+    Reject,
+}
+
+/// Sent by the client to the map server to answer a trade request.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00E6)]
+struct TradeRequestResponsePacket {
+    pub response: TradeRequestResponse,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+enum TradeRequestResult {
+    TooFarAway,
+    CharacterDoesNotExist,
+    Failed,
+    Accepted,
+    Canceled,
+    Busy,
+}
+
+/// Sent by the map server when a trade request of either player was answered.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x01F5)]
+struct TradeRequestResultPacket {
+    pub result: TradeRequestResult,
+    pub character_id: CharacterId,
+    pub base_level: u16,
+}
+
+impl TradeRequestResultPacket {
+    pub fn message(&self) -> Option<&'static str> {
+        // Messages taken from the official client
+        match self.result {
+            TradeRequestResult::TooFarAway => Some("You are too far away from the person to trade."),
+            TradeRequestResult::CharacterDoesNotExist => Some("This character is not currently online or does not exist."),
+            TradeRequestResult::Failed => Some("The person is in another deal."),
+            TradeRequestResult::Accepted => None,
+            TradeRequestResult::Canceled => Some("The deal has been rejected."),
+            TradeRequestResult::Busy => Some("The person is in another deal."),
+        }
+    }
+}
+
+/// Sent by the client to the map server to offer an item in the current
+/// trade.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00E8)]
+struct AddTradeItemPacket {
+    pub index: ItemIndex,
+    pub amount: u32,
+}
+
+/// Sent by the client to the map server to offer zeny in the current trade.
+/// Uses the same header as [`AddTradeItemPacket`], but the index is always
+/// zero.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00E8)]
+struct AddTradeZenyPacket {
+    #[new(value = "0")]
+    pub index: u16,
+    pub amount: u32,
+}
+
+/// Sent by the map server when the trade partner offered an item. Zeny is
+/// offered with an item id of zero.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0B42)]
+struct TradeItemAddedPacket {
+    pub item_id: ItemId,
+    pub item_type: u8,
+    pub amount: u32,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+/// Sent by the map server in response to [`AddTradeItemPacket`] and
+/// [`AddTradeZenyPacket`].
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00EA)]
+struct AddTradeItemResultPacket {
+    /// Inventory index of the item offset by two, or zero for zeny. Not an
+    /// [`ItemIndex`] since zero would underflow.
+    pub index: u16,
+    pub result: u8,
+}
+
+/// Sent by the client to the map server to lock the offer of the player.
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x00EB)]
+struct LockTradePacket {}
+
+/// Sent by the map server when one of the players locked their offer.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00EC)]
+struct TradeLockedPacket {
+    /// `0` if the player locked their offer, `1` for the trade partner.
+    pub who: u8,
+}
+
+/// Sent by the client to the map server to cancel the current trade.
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x00ED)]
+struct CancelTradePacket {}
+
+/// Sent by the map server when the trade was canceled by either player.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00EE)]
+struct TradeCanceledPacket {}
+
+/// Sent by the client to the map server to finalize the trade after both
+/// offers are locked.
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x00EF)]
+struct ConfirmTradePacket {}
+
+/// Sent by the map server once the trade was finalized.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00F0)]
+struct TradeCompletedPacket {
+    /// `0` if the items were exchanged.
+    pub result: u8,
+}
+
+/// Sent by the map server when the player talks to an NPC that runs a shop,
+/// so the player can choose between buying and selling.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00C4)]
+struct ShopDealTypePacket {
+    pub npc_id: EntityId,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+pub enum ShopDealType {
+    Buy,
+    Sell,
+}
+
+/// Sent by the client to the map server to answer [`ShopDealTypePacket`].
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00C5)]
+struct SelectShopDealTypePacket {
+    pub npc_id: EntityId,
+    pub deal_type: ShopDealType,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct ShopBuyItemData {
+    pub price: u32,
+    /// Price after the discount skill of the player is applied.
+    pub discount_price: u32,
+    pub item_type: u8,
+    pub item_id: ItemId,
+}
+
+/// Sent by the map server with the items an NPC shop sells.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00C6)]
+struct ShopBuyItemListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    #[repeating_remaining]
+    pub items: Vec<ShopBuyItemData>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct ShopSellItemData {
+    pub index: ItemIndex,
+    pub price: u32,
+    /// Price after the overcharge skill of the player is applied.
+    pub overcharge_price: u32,
+}
+
+/// Sent by the map server with the items of the player that an NPC shop is
+/// willing to buy.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00C7)]
+struct ShopSellItemListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    #[repeating_remaining]
+    pub items: Vec<ShopSellItemData>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct BuyShopItemData {
+    pub amount: u16,
+    pub item_id: ItemId,
+}
+
+/// Sent by the client to the map server to buy items from the NPC shop that
+/// is currently open.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00C8)]
+struct BuyShopItemsPacket {
+    pub packet_length: u16,
+    pub items: Vec<BuyShopItemData>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct SellShopItemData {
+    pub index: ItemIndex,
+    pub amount: u16,
+}
+
+/// Sent by the client to the map server to sell items to the NPC shop that is
+/// currently open.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x00C9)]
+struct SellShopItemsPacket {
+    pub packet_length: u16,
+    pub items: Vec<SellShopItemData>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ShopBuyResult {
+    Success,
+    InsufficientZeny,
+    Overweight,
+    TooManyItems,
+    OutOfStock,
+    /// The player is in a trade.
+    Trading,
+    /// Any other failure. Servers use a lot of different codes for failed
+    /// purchases, most of which are never shown to the player.
+    Failed(u8),
+}
+
+impl From<u8> for ShopBuyResult {
+    fn from(result: u8) -> Self {
+        match result {
+            0 => Self::Success,
+            1 => Self::InsufficientZeny,
+            2 => Self::Overweight,
+            3 => Self::TooManyItems,
+            4 => Self::OutOfStock,
+            5 => Self::Trading,
+            other => Self::Failed(other),
+        }
+    }
+}
+
+/// Sent by the map server in response to [`BuyShopItemsPacket`].
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00CA)]
+struct ShopBuyResultPacket {
+    /// See [`ShopBuyResult`].
+    pub result: u8,
+}
+
+impl ShopBuyResultPacket {
+    pub fn result(&self) -> ShopBuyResult {
+        ShopBuyResult::from(self.result)
+    }
+
+    pub fn message(&self) -> Option<&'static str> {
+        // Messages are mostly taken from the official client
+        match self.result() {
+            ShopBuyResult::Success => None,
+            ShopBuyResult::InsufficientZeny => Some("You do not have enough zeny."),
+            ShopBuyResult::Overweight => Some("You are over your weight limit."),
+            ShopBuyResult::TooManyItems => Some("You cannot carry more items because you have too many kinds of items."),
+            ShopBuyResult::OutOfStock => Some("The item is out of stock."),
+            ShopBuyResult::Trading => Some("You cannot buy items while trading."),
+            ShopBuyResult::Failed(_) => Some("Item purchase failed."),
+        }
+    }
+}
+
+/// Sent by the map server in response to [`SellShopItemsPacket`].
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x00CB)]
+struct ShopSellResultPacket {
+    /// `0` if the items were sold.
+    pub result: u8,
+}
+
+/// Sent by the map server when a player with an open vending shop comes into
+/// view.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0131)]
+struct VendingTitlePacket {
+    pub entity_id: EntityId,
+    #[length_hint(80)]
+    pub title: String,
+}
+
+/// Sent by the map server when a player closes their vending shop.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0132)]
+struct VendingClosedPacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the client to the map server to look at the items of a vending
+/// shop.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0130)]
+struct RequestVendingItemsPacket {
+    pub entity_id: EntityId,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct VendingItemInformation {
+    pub price: u32,
+    pub amount: u16,
+    /// Index of the item in the cart of the vendor.
+    pub index: ItemIndex,
+    pub item_type: u8,
+    pub item_id: ItemId,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub equip_position: EquipPosition,
+    pub view_sprite: u16,
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0800)]
+struct VendingItemListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub entity_id: EntityId,
+    pub vending_id: u32,
+    #[repeating_remaining]
+    pub items: Vec<VendingItemInformation>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct BuyVendingItemData {
+    pub amount: u16,
+    pub index: ItemIndex,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0801)]
+struct BuyVendingItemsPacket {
+    pub packet_length: u16,
+    pub entity_id: EntityId,
+    pub vending_id: u32,
+    pub items: Vec<BuyVendingItemData>,
+}
+
+/// Sent by the map server if an item of a vending shop could not be bought.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0135)]
+struct VendingPurchaseFailedPacket {
+    pub index: ItemIndex,
+    pub amount: u16,
+    pub result: u8,
+}
+
+impl VendingPurchaseFailedPacket {
+    pub fn message(&self) -> &'static str {
+        match self.result {
+            1 => "You do not have enough zeny.",
+            2 => "You are over your weight limit.",
+            4 => "The vendor doesn't have that many items left.",
+            _ => "Item purchase failed.",
+        }
+    }
+}
+
+/// Sent by the map server when the player used the vending skill. The player
+/// may now open a shop with up to `slot_count` different items from the cart.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x012D)]
+struct OpenVendingSetupPacket {
+    pub slot_count: u16,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct VendingSetupItemData {
+    /// Index of the item in the cart.
+    pub index: ItemIndex,
+    pub amount: u16,
+    pub price: u32,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x01B2)]
+struct OpenVendingPacket {
+    pub packet_length: u16,
+    #[length_hint(80)]
+    pub title: String,
+    pub open: u8,
+    pub items: Vec<VendingSetupItemData>,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A28)]
+struct OpenVendingResultPacket {
+    /// `0` if the shop was opened.
+    pub result: u8,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct OwnVendingItemInformation {
+    pub price: u32,
+    pub index: ItemIndex,
+    pub amount: u16,
+    pub item_type: u8,
+    pub item_id: ItemId,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub cards: [u32; 4],
+    pub option_data: [ItemOptions; 5], // fix count
+    pub refinement_level: u8,
+    pub enchantment_level: u8,
+}
+
+/// Sent by the map server once the shop of the player is open.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0136)]
+struct OwnVendingItemListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub entity_id: EntityId,
+    #[repeating_remaining]
+    pub items: Vec<OwnVendingItemInformation>,
+}
+
+/// Sent by the map server when another player bought from the shop of the
+/// player.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0137)]
+struct VendingItemSoldPacket {
+    /// Index of the item in the cart.
+    pub index: ItemIndex,
+    pub amount: u16,
+}
+
+#[derive(Clone, Debug, Default, OutgoingPacket, PrototypeElement)]
+#[header(0x012E)]
+struct CloseVendingPacket {}
+
+/// Sent by the client to the map server to start using the bank.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09B6)]
+struct OpenBankPacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09B7)]
+struct OpenBankResultPacket {
+    pub result: u16,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09B8)]
+struct CloseBankPacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09B9)]
+struct CloseBankResultPacket {
+    pub result: u16,
+}
+
+/// Sent by the client to the map server to get the balance of the bank
+/// account.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09AB)]
+struct RequestBankBalancePacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09A6)]
+struct BankBalancePacket {
+    pub balance: i64,
+    pub result: u16,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09A7)]
+struct BankDepositPacket {
+    pub account_id: AccountId,
+    pub zeny: i32,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, PartialEq, Eq)]
+#[numeric_type(u16)]
+enum BankDepositResult {
+    Success,
+    Error,
+    NotEnoughZeny,
+    BalanceLimitReached,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09A8)]
+struct BankDepositResultPacket {
+    pub result: BankDepositResult,
+    pub balance: i64,
+    pub zeny: i32,
+}
+
+impl BankDepositResultPacket {
+    pub fn message(&self) -> Option<&'static str> {
+        match self.result {
+            BankDepositResult::Success => None,
+            BankDepositResult::Error => Some("The deposit failed."),
+            BankDepositResult::NotEnoughZeny => Some("You don't have enough zeny."),
+            BankDepositResult::BalanceLimitReached => Some("Your bank account can't hold that much zeny."),
+        }
+    }
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x09A9)]
+struct BankWithdrawPacket {
+    pub account_id: AccountId,
+    pub zeny: i32,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, PartialEq, Eq)]
+#[numeric_type(u16)]
+enum BankWithdrawResult {
+    Success,
+    NotEnoughBalance,
+    Error,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x09AA)]
+struct BankWithdrawResultPacket {
+    pub result: BankWithdrawResult,
+    pub balance: i64,
+    pub zeny: i32,
+}
+
+impl BankWithdrawResultPacket {
+    pub fn message(&self) -> Option<&'static str> {
+        match self.result {
+            BankWithdrawResult::Success => None,
+            BankWithdrawResult::NotEnoughBalance => Some("Your bank balance is too low."),
+            BankWithdrawResult::Error => Some("The withdrawal failed."),
+        }
+    }
+}
+
+/// Sent by the client to the map server to look at the equipment of another
+/// player. Only succeeds if the other player allows it.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x02D6)]
+struct RequestViewEquipmentPacket {
+    pub entity_id: EntityId,
+}
+
+/// Sent by the client to the map server to invite a player to the party of
+/// the player.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x02C4)]
+struct PartyInviteByNamePacket {
+    #[length_hint(24)]
+    pub name: String,
+}
+
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
 #[header(0x020A)]
 struct NotifyFriendRemovedPacket {
@@ -2496,8 +4627,8 @@ struct FriendListPacket {
     pub friends: Vec<Friend>,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-enum OnlineState {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+pub enum OnlineState {
     Online,
     Offline,
 }
@@ -2526,102 +4657,320 @@ enum FriendRequestResponse {
 }
 
 #[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
-#[header(0x0208)]
-struct FriendRequestResponsePacket {
-    pub account_id: AccountId,
+#[header(0x0208)]
+struct FriendRequestResponsePacket {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    pub response: FriendRequestResponse,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
+#[numeric_type(u16)]
+enum FriendRequestResult {
+    Accepted,
+    Rejected,
+    OwnFriendListFull,
+    OtherFriendListFull,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0209)]
+struct FriendRequestResultPacket {
+    pub result: FriendRequestResult,
+    pub friend: Friend,
+}
+
+impl FriendRequestResultPacket {
+    pub fn into_message(self) -> String {
+        // Messages taken from rAthena
+        match self.result {
+            FriendRequestResult::Accepted => format!("You have become friends with {}.", self.friend.name),
+            FriendRequestResult::Rejected => format!("{} does not want to be friends with you.", self.friend.name),
+            FriendRequestResult::OwnFriendListFull => "Your Friend List is full.".to_owned(),
+            FriendRequestResult::OtherFriendListFull => format!("{}'s Friend List is full.", self.friend.name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x02C6)]
+struct PartyInvitePacket {
+    pub party_id: PartyId,
+    #[length_hint(24)]
+    pub party_name: String,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement, FixedByteSize)]
+struct ReputationEntry {
+    pub reputation_type: u64,
+    pub points: i64,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0B8D)]
+struct ReputationPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub success: u8,
+    #[repeating_remaining]
+    pub entries: Vec<ReputationEntry>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct Aliance {
+    #[length_hint(24)]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
+struct Antagonist {
+    #[length_hint(24)]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x098A)]
+struct ClanInfoPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub clan_id: u32,
+    #[length_hint(24)]
+    pub clan_name: String,
+    #[length_hint(24)]
+    pub clan_master: String,
+    #[length_hint(16)]
+    pub clan_map: String,
+    pub aliance_count: u8,
+    pub antagonist_count: u8,
+    #[repeating(self.aliance_count)]
+    pub aliances: Vec<Aliance>,
+    #[repeating(self.antagonist_count)]
+    pub antagonists: Vec<Antagonist>,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0988)]
+struct ClanOnlineCountPacket {
+    pub online_members: u16,
+    pub maximum_members: u16,
+}
+
+/// Sent by the map server to the client when loading onto a map if the
+/// player is a member of a guild.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x016C)]
+struct UpdateGuildIdPacket {
+    pub guild_id: u32,
+    pub emblem_id: u32,
+    pub mode: u32,
+    pub is_master: u8,
+    pub inter_server_id: u32,
+    #[length_hint(24)]
+    pub guild_name: String,
+}
+
+#[derive(Clone, Copy, Debug, ByteConvertable, PrototypeElement)]
+#[numeric_type(u32)]
+enum GuildMenu {
+    Information,
+    MemberList,
+    Positions,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x014F)]
+struct RequestGuildMenuPacket {
+    pub menu: GuildMenu,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0A84)]
+struct GuildInformationPacket {
+    pub guild_id: u32,
+    pub level: u32,
+    pub online_member_count: u32,
+    pub maximum_member_count: u32,
+    pub average_level: u32,
+    pub experience: u32,
+    pub next_experience: u32,
+    pub tax_points: u32,
+    pub tendency_left_right: u32,
+    pub tendency_down_up: u32,
+    pub emblem_id: u32,
+    #[length_hint(24)]
+    pub name: String,
+    #[length_hint(16)]
+    pub territory: String,
+    pub zeny: u32,
+    pub master_character_id: CharacterId,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct GuildMemberData {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    pub head: u16,
+    pub head_palette: u16,
+    pub sex: u16,
+    pub job: u16,
+    pub level: u16,
+    pub contributed_experience: u32,
+    pub current_state: u32,
+    pub position_id: u32,
+    pub last_login: u32,
+}
+
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0AA5)]
+struct GuildMemberListPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    #[repeating_remaining]
+    pub members: Vec<GuildMemberData>,
+}
+
+/// Sent by the client to the map server to get the name of a character that
+/// is not necessarily on the same map, e.g. a guild member.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0369)]
+struct RequestCharacterNamePacket {
     pub character_id: CharacterId,
-    pub response: FriendRequestResponse,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, ByteConvertable, PrototypeElement)]
-#[numeric_type(u16)]
-enum FriendRequestResult {
-    Accepted,
-    Rejected,
-    OwnFriendListFull,
-    OtherFriendListFull,
+/// Sent by the map server to the client as a response to
+/// [RequestCharacterNamePacket].
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0194)]
+struct CharacterNamePacket {
+    pub character_id: CharacterId,
+    #[length_hint(24)]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct GuildPositionNameData {
+    pub position_id: u32,
+    #[length_hint(24)]
+    pub name: String,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x0209)]
-struct FriendRequestResultPacket {
-    pub result: FriendRequestResult,
-    pub friend: Friend,
+#[header(0x0166)]
+struct GuildPositionNamesPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    #[repeating_remaining]
+    pub positions: Vec<GuildPositionNameData>,
 }
 
-impl FriendRequestResultPacket {
-    pub fn into_message(self) -> String {
-        // Messages taken from rAthena
-        match self.result {
-            FriendRequestResult::Accepted => format!("You have become friends with {}.", self.friend.name),
-            FriendRequestResult::Rejected => format!("{} does not want to be friends with you.", self.friend.name),
-            FriendRequestResult::OwnFriendListFull => "Your Friend List is full.".to_owned(),
-            FriendRequestResult::OtherFriendListFull => format!("{}'s Friend List is full.", self.friend.name),
-        }
-    }
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement)]
+struct GuildPositionInfoData {
+    pub position_id: u32,
+    pub mode: u32,
+    pub ranking: u32,
+    pub pay_rate: u32,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x02C6)]
-struct PartyInvitePacket {
-    pub party_id: PartyId,
+#[header(0x0160)]
+struct GuildPositionInfoPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    #[repeating_remaining]
+    pub positions: Vec<GuildPositionInfoData>,
+}
+
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct GuildPositionData {
+    pub position_id: u32,
+    pub mode: u32,
+    pub ranking: u32,
+    pub pay_rate: u32,
     #[length_hint(24)]
-    pub party_name: String,
+    pub name: String,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement, FixedByteSize)]
-struct ReputationEntry {
-    pub reputation_type: u64,
-    pub points: i64,
+/// Sent by the client to the map server to change the name and permissions
+/// of a guild position. The map server accepts multiple positions, but the
+/// client only ever changes one at a time.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0161)]
+struct ChangeGuildPositionPacket {
+    pub packet_length: u16,
+    pub position: GuildPositionData,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x0B8D)]
-struct ReputationPacket {
+#[header(0x0174)]
+struct GuildPositionChangedPacket {
     #[packet_length]
     pub packet_length: u16,
-    pub success: u8,
     #[repeating_remaining]
-    pub entries: Vec<ReputationEntry>,
+    pub positions: Vec<GuildPositionData>,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-struct Aliance {
-    #[length_hint(24)]
-    pub name: String,
+#[derive(Clone, Debug, ByteConvertable, FixedByteSize, PrototypeElement, new)]
+struct GuildMemberPositionData {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    pub position_id: u32,
 }
 
-#[derive(Clone, Debug, ByteConvertable, PrototypeElement)]
-struct Antagonist {
-    #[length_hint(24)]
-    pub name: String,
+/// Sent by the client to the map server to move guild members to a different
+/// position. Like [ChangeGuildPositionPacket], the client only sends a single
+/// member at a time.
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0155)]
+struct ChangeGuildMemberPositionPacket {
+    pub packet_length: u16,
+    pub member: GuildMemberPositionData,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x098A)]
-struct ClanInfoPacket {
+#[header(0x0156)]
+struct GuildMemberPositionChangedPacket {
     #[packet_length]
     pub packet_length: u16,
-    pub clan_id: u32,
-    #[length_hint(24)]
-    pub clan_name: String,
-    #[length_hint(24)]
-    pub clan_master: String,
-    #[length_hint(16)]
-    pub clan_map: String,
-    pub aliance_count: u8,
-    pub antagonist_count: u8,
-    #[repeating(self.aliance_count)]
-    pub aliances: Vec<Aliance>,
-    #[repeating(self.antagonist_count)]
-    pub antagonists: Vec<Antagonist>,
+    #[repeating_remaining]
+    pub members: Vec<GuildMemberPositionData>,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
-#[header(0x0988)]
-struct ClanOnlineCountPacket {
-    pub online_members: u16,
-    pub maximum_members: u16,
+#[header(0x016F)]
+struct GuildNoticePacket {
+    #[length_hint(60)]
+    pub subject: String,
+    #[length_hint(120)]
+    pub notice: String,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x016E)]
+struct SetGuildNoticePacket {
+    pub guild_id: u32,
+    #[length_hint(60)]
+    pub subject: String,
+    #[length_hint(120)]
+    pub notice: String,
+}
+
+#[derive(Clone, Debug, OutgoingPacket, PrototypeElement, new)]
+#[header(0x0151)]
+struct RequestGuildEmblemPacket {
+    pub guild_id: u32,
+}
+
+/// Sent by the map server as a response to [RequestGuildEmblemPacket]. The
+/// emblem is a zlib compressed bitmap.
+#[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
+#[header(0x0152)]
+struct GuildEmblemPacket {
+    #[packet_length]
+    pub packet_length: u16,
+    pub guild_id: u32,
+    pub emblem_id: u32,
+    #[length_hint(self.packet_length - 12)]
+    pub emblem_data: Vec<u8>,
 }
 
 #[derive(Clone, Debug, IncomingPacket, PrototypeElement)]
@@ -2636,6 +4985,8 @@ struct ChangeMapCellPacket {
 #[derive(Clone, new)]
 struct UnknownPacket {
     bytes: Vec<u8>,
+    /// Name from the packet database, if the packet is in there.
+    name: Option<String>,
 }
 
 impl IncomingPacket for UnknownPacket {
@@ -2658,7 +5009,14 @@ impl PrototypeElement for UnknownPacket {
                 let header = format!("0x{:0>4x}", signature);
                 let data = &self.bytes[byte_stream.get_offset()..];
 
-                vec![header.to_element("header".to_owned()), data.to_element("data".to_owned())]
+                let mut elements = vec![header.to_element("header".to_owned())];
+
+                if let Some(name) = &self.name {
+                    elements.push(name.to_element("name".to_owned()));
+                }
+
+                elements.push(data.to_element("data".to_owned()));
+                elements
             }
             false => {
                 vec![self.bytes.to_element("data".to_owned())]
@@ -2669,23 +5027,20 @@ impl PrototypeElement for UnknownPacket {
     }
 }
 
-#[derive(new)]
-struct NetworkTimer {
-    period: Duration,
-    #[new(default)]
-    accumulator: Duration,
+#[derive(Clone, Copy)]
+enum KeepAlive {
+    LoginServer,
+    CharacterServer,
+    MapServer,
 }
 
-impl NetworkTimer {
-    pub fn update(&mut self, elapsed_time: f64) -> bool {
-        self.accumulator += Duration::from_secs_f64(elapsed_time);
-        let reset = self.accumulator > self.period;
-
-        if reset {
-            self.accumulator -= self.period;
+impl KeepAlive {
+    fn period(self) -> Duration {
+        match self {
+            KeepAlive::LoginServer => Duration::from_secs(58),
+            KeepAlive::CharacterServer => Duration::from_secs(10),
+            KeepAlive::MapServer => Duration::from_secs(4),
         }
-
-        reset
     }
 }
 
@@ -2707,7 +5062,7 @@ struct LoginData {
 //         slot_count: usize,
 //     },
 //     Playing {
-//         friend_list: TrackedState<Vec<(Friend,
+//         friend_list: TrackedState<Vec<(Friend, OnlineState,
 // UnsafeCell<Option<WeakElementCell>>)>>,         player_name: String,
 //     },
 // }
@@ -2718,17 +5073,36 @@ pub struct NetworkingSystem {
     map_stream: Option<TcpStream>,
     // TODO: Make this a heapless Vec or something
     map_stream_buffer: Vec<u8>,
-    login_keep_alive_timer: NetworkTimer,
-    character_keep_alive_timer: NetworkTimer,
-    map_keep_alive_timer: NetworkTimer,
+    keep_alive_scheduler: Scheduler<KeepAlive>,
 
     // TODO: Move to GameState
     login_data: Option<LoginData>,
     characters: TrackedState<Vec<CharacterInformation>>,
     move_request: TrackedState<Option<usize>>,
-    friend_list: TrackedState<Vec<(Friend, UnsafeCell<Option<WeakElementCell>>)>>,
+    selected_slot: TrackedState<Option<usize>>,
+    friend_list: TrackedState<Vec<(Friend, OnlineState, UnsafeCell<Option<WeakElementCell>>)>>,
+    /// Time at which the friend list was received. The map server sends the
+    /// state of every online friend right after the list, which should not be
+    /// announced as logins.
+    friend_list_received: Option<Instant>,
+    /// Time at which the player last asked to pick up an item.
+    pick_up_requested: Option<Instant>,
+    /// Party members on the current map.
+    party_members: HashSet<AccountId>,
+    guild: Guild,
+    /// Title of the vending shop the player is opening.
+    vending_title: Option<String>,
+    character_creation_version: CharacterCreationVersion,
+    character_deletion_version: CharacterDeletionVersion,
+    packet_version: PacketVersion,
+    /// Built-in packet definitions and the ones from the packet database of
+    /// the user.
+    packet_definitions: Vec<PacketDefinition>,
+    packet_length_table: PacketLengthTable,
     slot_count: usize,
     player_name: String,
+    map_name: String,
+    packet_handlers: PacketHandlerRegistry,
     #[cfg(feature = "debug")]
     update_packets: TrackedState<bool>,
     #[cfg(feature = "debug")]
@@ -2744,12 +5118,26 @@ impl NetworkingSystem {
         let login_data = None;
         let characters = TrackedState::default();
         let move_request = TrackedState::default();
+        let selected_slot = TrackedState::default();
         let friend_list = TrackedState::default();
+        let friend_list_received = None;
+        let pick_up_requested = None;
+        let party_members = HashSet::new();
+        let guild = Guild::default();
+        let vending_title = None;
+        let character_creation_version = CharacterCreationVersion::default();
+        let character_deletion_version = CharacterDeletionVersion::default();
+        let packet_version = PacketVersion::default();
+        let packet_definitions = load_packet_definitions();
+        let packet_length_table = PacketLengthTable::new(&packet_definitions, packet_version);
         let slot_count = 0;
-        let login_keep_alive_timer = NetworkTimer::new(Duration::from_secs(58));
-        let character_keep_alive_timer = NetworkTimer::new(Duration::from_secs(10));
-        let map_keep_alive_timer = NetworkTimer::new(Duration::from_secs(4));
+        let mut keep_alive_scheduler = Scheduler::new();
+        [KeepAlive::LoginServer, KeepAlive::CharacterServer, KeepAlive::MapServer]
+            .into_iter()
+            .for_each(|keep_alive| keep_alive_scheduler.schedule(keep_alive.period(), keep_alive));
         let player_name = String::new();
+        let map_name = String::new();
+        let packet_handlers = PacketHandlerRegistry::new();
         #[cfg(feature = "debug")]
         let update_packets = TrackedState::new(true);
         #[cfg(feature = "debug")]
@@ -2764,11 +5152,22 @@ impl NetworkingSystem {
             map_stream_buffer,
             characters,
             move_request,
+            selected_slot,
             friend_list,
-            login_keep_alive_timer,
-            character_keep_alive_timer,
-            map_keep_alive_timer,
+            friend_list_received,
+            pick_up_requested,
+            party_members,
+            guild,
+            vending_title,
+            character_creation_version,
+            character_deletion_version,
+            packet_version,
+            packet_definitions,
+            packet_length_table,
+            keep_alive_scheduler,
             player_name,
+            map_name,
+            packet_handlers,
             #[cfg(feature = "debug")]
             update_packets,
             #[cfg(feature = "debug")]
@@ -2792,15 +5191,19 @@ impl NetworkingSystem {
             .find(|service| service.service_id() == service_id)
             .unwrap();
         let service_address = format!("{}:{}", service.address, service.port);
+        self.packet_version = service.packet_version.map(PacketVersion).unwrap_or_default();
+        self.packet_length_table = PacketLengthTable::new(&self.packet_definitions, self.packet_version);
+        self.character_creation_version = CharacterCreationVersion::from_packet_version(self.packet_version);
+        self.character_deletion_version = CharacterDeletionVersion::from_packet_version(self.packet_version);
 
         let login_stream = TcpStream::connect(service_address).map_err(|_| "failed to connect to login server".to_owned())?;
         login_stream.set_read_timeout(Duration::from_secs(1).into()).unwrap();
         self.login_stream = Some(login_stream);
 
-        self.send_packet_to_login_server(LoginServerLoginPacket::new(username.clone(), password.clone()));
+        self.send_packet_to_login_server(LoginServerLoginPacket::new(username.clone(), password.clone()))?;
 
         let response = self.get_data_from_login_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let header = u16::from_bytes(&mut byte_stream).unwrap();
         let login_server_login_success_packet = match header {
@@ -2827,6 +5230,9 @@ impl NetworkingSystem {
                 }
             }
             LoginServerLoginSuccessPacket::HEADER => LoginServerLoginSuccessPacket::from_bytes(&mut byte_stream).unwrap(),
+            CompactLoginServerLoginSuccessPacket::HEADER => {
+                CompactLoginServerLoginSuccessPacket::from_bytes(&mut byte_stream).unwrap().into()
+            }
             _ => panic!(),
         };
 
@@ -2869,14 +5275,15 @@ impl NetworkingSystem {
             login_data.sex,
         );
 
+        let packet_bytes = self.packet_bytes(&character_server_login_packet)?;
         let character_stream = self.character_stream.as_mut().ok_or("no character server connection")?;
         character_stream
-            .write_all(&character_server_login_packet.to_bytes().unwrap())
+            .write_all(&packet_bytes)
             .map_err(|_| "failed to send packet to character server")?;
 
         let response = self.get_data_from_character_server();
 
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
         let account_id = AccountId::from_bytes(&mut byte_stream).unwrap();
 
         assert_eq!(account_id, login_data.account_id);
@@ -2885,7 +5292,7 @@ impl NetworkingSystem {
         self.update_packet_history(byte_stream.into_metadata());
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let header = u16::from_bytes(&mut byte_stream).unwrap();
         let character_server_login_success_packet = match header {
@@ -2901,16 +5308,36 @@ impl NetworkingSystem {
             _ => panic!(),
         };
 
-        self.send_packet_to_character_server(RequestCharacterListPacket::default());
+        self.send_packet_to_character_server(RequestCharacterListPacket::default())?;
 
         #[cfg(feature = "debug")]
         self.update_packet_history(byte_stream.into_metadata());
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
+
+        let header = u16::from_bytes(&mut byte_stream).unwrap();
+        let character_information = match header {
+            RequestCharacterListSuccessPacket::HEADER => {
+                RequestCharacterListSuccessPacket::from_bytes(&mut byte_stream)
+                    .unwrap()
+                    .character_information
+            }
+            CompactRequestCharacterListSuccessPacket::HEADER => {
+                CompactRequestCharacterListSuccessPacket::from_bytes(&mut byte_stream)
+                    .unwrap()
+                    .character_information
+            }
+            _ => panic!(),
+        };
 
-        let request_character_list_success_packet = RequestCharacterListSuccessPacket::take_from_bytes(&mut byte_stream).unwrap();
-        self.characters.set(request_character_list_success_packet.character_information);
+        self.characters.set(
+            character_information
+                .into_iter()
+                .map(|character_information| character_information.with_account_sex(login_data.sex))
+                .map(CharacterInformation::with_deletion_timestamp)
+                .collect(),
+        );
 
         #[cfg(feature = "debug")]
         self.update_packet_history(byte_stream.into_metadata());
@@ -2924,13 +5351,62 @@ impl NetworkingSystem {
     }
 
     pub fn character_selection_window(&self) -> CharacterSelectionWindow {
-        CharacterSelectionWindow::new(self.characters.new_remote(), self.move_request.new_remote(), self.slot_count)
+        CharacterSelectionWindow::new(
+            self.characters.new_remote(),
+            self.move_request.new_remote(),
+            self.selected_slot.new_remote(),
+            self.slot_count,
+        )
+    }
+
+    pub fn get_character_creation_version(&self) -> CharacterCreationVersion {
+        self.character_creation_version
+    }
+
+    pub fn get_character_deletion_version(&self) -> CharacterDeletionVersion {
+        self.character_deletion_version
+    }
+
+    pub fn get_character(&self, character_id: CharacterId) -> Option<CharacterInformation> {
+        self.characters
+            .borrow()
+            .iter()
+            .find(|character| character.character_id == character_id)
+            .cloned()
+    }
+
+    pub fn get_account_sex(&self) -> Sex {
+        self.login_data.as_ref().unwrap().sex
+    }
+
+    /// Mark the character in the given slot as selected and return its
+    /// information, so it can be previewed on the character selection screen.
+    pub fn preview_character(&mut self, slot: usize) -> Option<CharacterInformation> {
+        let character_information = self
+            .characters
+            .borrow()
+            .iter()
+            .find(|character| character.character_number as usize == slot)
+            .cloned();
+
+        self.selected_slot.set(character_information.as_ref().map(|_| slot));
+        character_information
     }
 
     pub fn friends_window(&self) -> FriendsWindow {
         FriendsWindow::new(self.friend_list.new_remote())
     }
 
+    pub fn guild_window(&self) -> GuildWindow {
+        GuildWindow::new(GuildData::new(
+            self.guild.get_info(),
+            self.guild.get_members(),
+            self.guild.get_positions(),
+            self.guild.get_notice(),
+            self.guild.get_emblems(),
+        ))
+    }
+
     pub fn log_out(&mut self) -> Result<(), String> {
         #[cfg(feature = "debug")]
         let timer = Timer::new("log out");
@@ -2943,11 +5419,20 @@ impl NetworkingSystem {
         Ok(())
     }
 
+    /// Create a byte stream for reading packets in the packet version of the
+    /// server.
+    fn byte_stream<'a>(&self, data: &'a [u8]) -> ByteStream<'a, NetworkMetadata> {
+        ByteStream::with_metadata(data, NetworkMetadata::new(self.packet_version))
+    }
+
     #[cfg(feature = "debug")]
-    fn update_packet_history(&mut self, mut packets: Vec<PacketEntry>) {
+    fn update_packet_history(&mut self, mut metadata: NetworkMetadata) {
         if self.update_packets.get() {
             self.packet_history.with_mut(|buffer| {
-                packets.drain(..).for_each(|packet| buffer.push((packet, UnsafeCell::new(None))));
+                metadata
+                    .packets
+                    .drain(..)
+                    .for_each(|packet| buffer.push((packet, UnsafeCell::new(None))));
                 ValueState::Mutated(())
             });
         }
@@ -2969,35 +5454,53 @@ impl NetworkingSystem {
         }
     }
 
-    fn send_packet_to_login_server<T>(&mut self, packet: T)
+    /// Convert a packet for the packet version of the server. Fails if the
+    /// packet is missing a field the packet version requires.
+    fn packet_bytes<T>(&self, packet: &T) -> Result<Vec<u8>, String>
+    where
+        T: OutgoingPacket,
+    {
+        packet.to_bytes(self.packet_version).map_err(|error| {
+            format!(
+                "failed to convert packet for packet version {}: {error:?}",
+                self.packet_version.0
+            )
+        })
+    }
+
+    fn send_packet_to_login_server<T>(&mut self, packet: T) -> Result<(), String>
     where
         T: OutgoingPacket + 'static,
     {
         #[cfg(feature = "debug")]
         self.new_outgoing(&packet);
 
-        let packet_bytes = packet.to_bytes().unwrap();
+        let packet_bytes = self.packet_bytes(&packet)?;
         let login_stream = self.login_stream.as_mut().expect("no login server connection");
 
         login_stream
             .write_all(&packet_bytes)
-            .expect("failed to send packet to login server");
+            .map_err(|_| "failed to send packet to login server".to_owned())
     }
 
-    fn send_packet_to_character_server<T>(&mut self, packet: T)
+    fn send_packet_to_character_server<T>(&mut self, packet: T) -> Result<(), String>
     where
         T: OutgoingPacket + 'static,
     {
         #[cfg(feature = "debug")]
         self.new_outgoing(&packet);
 
-        let packet_bytes = packet.to_bytes().unwrap();
+        let packet_bytes = self.packet_bytes(&packet)?;
         let character_stream = self.character_stream.as_mut().expect("no character server connection");
+
         character_stream
             .write_all(&packet_bytes)
-            .expect("failed to send packet to character server");
+            .map_err(|_| "failed to send packet to character server".to_owned())
     }
 
+    /// Packets that can't be converted for the packet version of the server
+    /// are dropped, since requests to the map server don't wait for a
+    /// response.
     fn send_packet_to_map_server<T>(&mut self, packet: T)
     where
         T: OutgoingPacket + 'static,
@@ -3005,7 +5508,16 @@ impl NetworkingSystem {
         #[cfg(feature = "debug")]
         self.new_outgoing(&packet);
 
-        let packet_bytes = packet.to_bytes().unwrap();
+        let packet_bytes = match self.packet_bytes(&packet) {
+            Ok(packet_bytes) => packet_bytes,
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{RED}error{NONE}] {_error}");
+
+                return;
+            }
+        };
+
         let map_stream = self.map_stream.as_mut().expect("no map server connection");
         map_stream.write_all(&packet_bytes).expect("failed to send packet to map server");
     }
@@ -3044,20 +5556,31 @@ impl NetworkingSystem {
     }
 
     pub fn keep_alive(&mut self, delta_time: f64, client_tick: ClientTick) {
-        if self.login_keep_alive_timer.update(delta_time) && self.login_stream.is_some() {
-            self.send_packet_to_login_server(LoginServerKeepalivePacket::default());
-        }
-
-        if self.character_keep_alive_timer.update(delta_time) && self.character_stream.is_some() {
-            self.send_packet_to_character_server(CharacterServerKeepalivePacket::new());
-        }
+        for keep_alive in self.keep_alive_scheduler.update(delta_time) {
+            match keep_alive {
+                KeepAlive::LoginServer if self.login_stream.is_some() => {
+                    if let Err(_error) = self.send_packet_to_login_server(LoginServerKeepalivePacket::default()) {
+                        #[cfg(feature = "debug")]
+                        print_debug!("[{YELLOW}warning{NONE}] {_error}");
+                    }
+                }
+                KeepAlive::CharacterServer if self.character_stream.is_some() => {
+                    if let Err(_error) = self.send_packet_to_character_server(CharacterServerKeepalivePacket::new()) {
+                        #[cfg(feature = "debug")]
+                        print_debug!("[{YELLOW}warning{NONE}] {_error}");
+                    }
+                }
+                KeepAlive::MapServer if self.map_stream.is_some() => {
+                    self.send_packet_to_map_server(RequestServerTickPacket::new(client_tick));
+                }
+                _ => {}
+            }
 
-        if self.map_keep_alive_timer.update(delta_time) && self.map_stream.is_some() {
-            self.send_packet_to_map_server(RequestServerTickPacket::new(client_tick));
+            self.keep_alive_scheduler.schedule(keep_alive.period(), keep_alive);
         }
     }
 
-    pub fn create_character(&mut self, slot: usize, name: String) -> Result<(), String> {
+    pub fn create_character(&mut self, slot: usize, character: CharacterCreationData) -> Result<(), String> {
         #[cfg(feature = "debug")]
         let timer = Timer::new("create character");
 
@@ -3065,46 +5588,82 @@ impl NetworkingSystem {
         print_debug!(
             "character with name {}{}{} in slot {}{}{}",
             MAGENTA,
-            name,
+            character.name,
             NONE,
             MAGENTA,
             slot,
             NONE
         );
 
-        let hair_color = 0;
-        let hair_style = 0;
-        let start_job = 0;
-        let sex = Sex::Male;
-
-        self.send_packet_to_character_server(CreateCharacterPacket::new(
-            name, slot as u8, hair_color, hair_style, start_job, sex,
-        ));
+        let CharacterCreationData {
+            name,
+            hair_style,
+            hair_color,
+            stats: [strength, agility, vitality],
+            start_job,
+            sex,
+        } = character;
+
+        match self.character_creation_version {
+            CharacterCreationVersion::Stats => self.send_packet_to_character_server(CreateCharacterWithStatsPacket::new(
+                name,
+                strength,
+                agility,
+                vitality,
+                10 - strength,
+                10 - vitality,
+                10 - agility,
+                slot as u8,
+                hair_color,
+                hair_style,
+            )),
+            CharacterCreationVersion::HairOnly => {
+                self.send_packet_to_character_server(CreateCharacterWithoutJobPacket::new(name, slot as u8, hair_color, hair_style))
+            }
+            CharacterCreationVersion::StartingJob => self.send_packet_to_character_server(CreateCharacterPacket::new(
+                name, slot as u8, hair_color, hair_style, start_job, sex,
+            )),
+        }?;
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let header = u16::from_bytes(&mut byte_stream).unwrap();
-        let create_character_success_packet = match header {
+        let character_information = match header {
             CharacterCreationFailedPacket::HEADER => {
                 let packet = CharacterCreationFailedPacket::from_bytes(&mut byte_stream).unwrap();
                 match packet.reason {
-                    CharacterCreationFailedReason::CharacterNameAlreadyUsed => return Err("character name is already used".to_string()),
+                    CharacterCreationFailedReason::CharacterNameAlreadyUsed => {
+                        return Err("character name is already used, please choose another one".to_string());
+                    }
                     CharacterCreationFailedReason::NotOldEnough => return Err("you are not old enough to create a character".to_string()),
+                    CharacterCreationFailedReason::SymbolsNotAllowed => {
+                        return Err("symbols are not allowed in character names".to_string());
+                    }
                     CharacterCreationFailedReason::NotAllowedToUseSlot => {
                         return Err("you are not allowed to use that character slot".to_string());
                     }
                     CharacterCreationFailedReason::CharacterCerationFailed => return Err("character creation failed".to_string()),
                 }
             }
-            CreateCharacterSuccessPacket::HEADER => CreateCharacterSuccessPacket::from_bytes(&mut byte_stream).unwrap(),
+            CreateCharacterSuccessPacket::HEADER => {
+                CreateCharacterSuccessPacket::from_bytes(&mut byte_stream)
+                    .unwrap()
+                    .character_information
+            }
+            CompactCreateCharacterSuccessPacket::HEADER => {
+                CompactCreateCharacterSuccessPacket::from_bytes(&mut byte_stream)
+                    .unwrap()
+                    .character_information
+            }
             _ => panic!(),
         };
 
         #[cfg(feature = "debug")]
         self.update_packet_history(byte_stream.into_metadata());
 
-        self.characters.push(create_character_success_packet.character_information);
+        let account_sex = self.get_account_sex();
+        self.characters.push(character_information.with_account_sex(account_sex));
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -3112,27 +5671,97 @@ impl NetworkingSystem {
         Ok(())
     }
 
-    pub fn delete_character(&mut self, character_id: CharacterId) -> Result<(), String> {
+    /// Start the waiting period after which the character can be deleted.
+    /// Only used if the [`CharacterDeletionVersion`] is `Timer`.
+    pub fn reserve_character_deletion(&mut self, character_id: CharacterId) -> Result<(), String> {
         #[cfg(feature = "debug")]
-        let timer = Timer::new("delete character");
+        let timer = Timer::new("reserve character deletion");
 
-        let email = "a@a.com".to_string();
+        self.send_packet_to_character_server(ReserveCharacterDeletionPacket::new(character_id))?;
+
+        let response = self.get_data_from_character_server();
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
+
+        let packet = ReserveCharacterDeletionResultPacket::take_from_bytes(&mut byte_stream).unwrap();
+        match packet.result {
+            ReserveCharacterDeletionResult::Success => {}
+            ReserveCharacterDeletionResult::Error => return Err("character deletion failed".to_string()),
+            ReserveCharacterDeletionResult::DatabaseError => return Err("a database error occurred".to_string()),
+            ReserveCharacterDeletionResult::InGuild => return Err("you have to leave your guild to delete this character".to_string()),
+            ReserveCharacterDeletionResult::InParty => return Err("you have to leave your party to delete this character".to_string()),
+        }
 
         #[cfg(feature = "debug")]
-        print_debug!(
-            "character with id {}{}{} and email {}{}{}",
-            MAGENTA,
-            character_id.0,
-            NONE,
-            MAGENTA,
-            email,
-            NONE
-        );
+        self.update_packet_history(byte_stream.into_metadata());
+
+        self.characters.with_mut(|characters| {
+            characters
+                .iter_mut()
+                .filter(|character| character.character_id == packet.character_id)
+                .for_each(|character| character.deletion_reverse_date = deletion_timestamp(packet.remaining_seconds.max(1)));
+
+            ValueState::Mutated(())
+        });
 
-        self.send_packet_to_character_server(DeleteCharacterPacket::new(character_id, email));
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(())
+    }
+
+    pub fn cancel_character_deletion(&mut self, character_id: CharacterId) -> Result<(), String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new("cancel character deletion");
+
+        self.send_packet_to_character_server(CancelCharacterDeletionPacket::new(character_id))?;
+
+        let response = self.get_data_from_character_server();
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
+
+        let packet = CancelCharacterDeletionResultPacket::take_from_bytes(&mut byte_stream).unwrap();
+        if packet.result != 1 {
+            return Err("failed to cancel the character deletion".to_string());
+        }
+
+        #[cfg(feature = "debug")]
+        self.update_packet_history(byte_stream.into_metadata());
+
+        self.characters.with_mut(|characters| {
+            characters
+                .iter_mut()
+                .filter(|character| character.character_id == packet.character_id)
+                .for_each(|character| character.deletion_reverse_date = 0);
+
+            ValueState::Mutated(())
+        });
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(())
+    }
+
+    /// The confirmation is either the e-mail address of the account or the
+    /// birthdate of the account owner, depending on the
+    /// [`CharacterDeletionVersion`].
+    pub fn delete_character(&mut self, character_id: CharacterId, confirmation: String) -> Result<(), String> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new("delete character");
+
+        #[cfg(feature = "debug")]
+        print_debug!("character with id {}{}{}", MAGENTA, character_id.0, NONE);
+
+        match self.character_deletion_version {
+            CharacterDeletionVersion::Email => {
+                self.send_packet_to_character_server(DeleteCharacterPacket::new(character_id, confirmation))?;
+            }
+            CharacterDeletionVersion::Timer => {
+                self.send_packet_to_character_server(DeleteReservedCharacterPacket::new(character_id, confirmation))?;
+            }
+        }
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let header = u16::from_bytes(&mut byte_stream).unwrap();
         match header {
@@ -3147,6 +5776,17 @@ impl NetworkingSystem {
             CharacterDeletionSuccessPacket::HEADER => {
                 let _ = CharacterDeletionSuccessPacket::from_bytes(&mut byte_stream).unwrap();
             }
+            DeleteReservedCharacterResultPacket::HEADER => {
+                let packet = DeleteReservedCharacterResultPacket::from_bytes(&mut byte_stream).unwrap();
+                match packet.result {
+                    DeleteReservedCharacterResult::Success => {}
+                    DeleteReservedCharacterResult::Error => return Err("character deletion failed".to_string()),
+                    DeleteReservedCharacterResult::NotAllowed => return Err("you are not allowed to delete this character".to_string()),
+                    DeleteReservedCharacterResult::DatabaseError => return Err("a database error occurred".to_string()),
+                    DeleteReservedCharacterResult::TooEarly => return Err("the character can not be deleted yet".to_string()),
+                    DeleteReservedCharacterResult::BirthdateMismatch => return Err("the birthdate does not match".to_string()),
+                }
+            }
             _ => panic!(),
         }
 
@@ -3154,6 +5794,7 @@ impl NetworkingSystem {
         self.update_packet_history(byte_stream.into_metadata());
 
         self.characters.retain(|character| character.character_id != character_id);
+        self.selected_slot.set(None);
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -3168,10 +5809,10 @@ impl NetworkingSystem {
         #[cfg(feature = "debug")]
         print_debug!("character in slot {}{}{}", MAGENTA, slot, NONE,);
 
-        self.send_packet_to_character_server(SelectCharacterPacket::new(slot as u8));
+        self.send_packet_to_character_server(SelectCharacterPacket::new(slot as u8))?;
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let header = u16::from_bytes(&mut byte_stream).unwrap();
         let character_selection_success_packet = match header {
@@ -3194,6 +5835,9 @@ impl NetworkingSystem {
                 return Err("Map server currently unavailable".to_string());
             }
             CharacterSelectionSuccessPacket::HEADER => CharacterSelectionSuccessPacket::from_bytes(&mut byte_stream).unwrap(),
+            CompactCharacterSelectionSuccessPacket::HEADER => {
+                CompactCharacterSelectionSuccessPacket::from_bytes(&mut byte_stream).unwrap().into()
+            }
             _ => panic!(),
         };
 
@@ -3241,20 +5885,50 @@ impl NetworkingSystem {
             .unwrap();
 
         self.player_name = character_information.name.clone();
+        self.selected_slot.set(None);
+        self.map_name = character_selection_success_packet.map_name.replace(".gat", "");
 
         #[cfg(feature = "debug")]
         timer.stop();
 
-        Ok((
-            account_id,
-            character_information,
-            character_selection_success_packet.map_name.replace(".gat", ""),
-        ))
+        Ok((account_id, character_information, self.map_name.clone()))
+    }
+
+    /// Tell the map server that the player is quitting and close all
+    /// connections. Errors are ignored, since the server might already be
+    /// gone.
+    pub fn disconnect(&mut self) {
+        if self.map_stream.is_some() {
+            let packet = RequestDisconnectPacket::new(0);
+
+            #[cfg(feature = "debug")]
+            self.new_outgoing(&packet);
+
+            let result = self.packet_bytes(&packet).and_then(|packet_bytes| {
+                self.map_stream
+                    .as_mut()
+                    .unwrap()
+                    .write_all(&packet_bytes)
+                    .map_err(|error| error.to_string())
+            });
+
+            if let Err(_error) = result {
+                #[cfg(feature = "debug")]
+                print_debug!("[{YELLOW}warning{NONE}] failed to send disconnect packet: {_error}");
+            }
+        }
+
+        self.login_stream = None;
+        self.character_stream = None;
+        self.map_stream = None;
     }
 
     pub fn disconnect_from_map_server(&mut self) {
         // Dropping the TcpStream will also close the connection.
         self.map_stream = None;
+        self.guild.clear();
+        self.party_members.clear();
+        self.vending_title = None;
     }
 
     pub fn request_switch_character_slot(&mut self, origin_slot: usize) {
@@ -3282,10 +5956,10 @@ impl NetworkingSystem {
             NONE
         );
 
-        self.send_packet_to_character_server(SwitchCharacterSlotPacket::new(origin_slot as u16, destination_slot as u16));
+        self.send_packet_to_character_server(SwitchCharacterSlotPacket::new(origin_slot as u16, destination_slot as u16))?;
 
         let response = self.get_data_from_character_server();
-        let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&response);
+        let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&response);
 
         let switch_character_slot_response_packet = SwitchCharacterSlotResponsePacket::take_from_bytes(&mut byte_stream).unwrap();
 
@@ -3295,11 +5969,13 @@ impl NetworkingSystem {
                 let _packet_006b = Packet6b00::take_from_bytes(&mut byte_stream).unwrap();
 
                 let character_count = self.characters.len();
+                let account_sex = self.get_account_sex();
                 self.characters.clear();
 
                 for _index in 0..character_count {
                     let character_information = CharacterInformation::from_bytes(&mut byte_stream).unwrap();
-                    self.characters.push(character_information);
+                    self.characters
+                        .push(character_information.with_account_sex(account_sex).with_deletion_timestamp());
                 }
 
                 // packet_length and packet 0x09a0 are left unread because we
@@ -3342,15 +6018,50 @@ impl NetworkingSystem {
         self.send_packet_to_map_server(RequestActionPacket::new(entity_id, Action::Attack));
     }
 
+    pub fn request_pick_up_item(&mut self, entity_id: EntityId) {
+        self.pick_up_requested = Some(Instant::now());
+        self.send_packet_to_map_server(RequestPickUpItemPacket::new(entity_id));
+    }
+
+    pub fn request_sit_down(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestActionPacket::new(entity_id, Action::SitDown));
+    }
+
+    pub fn request_stand_up(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestActionPacket::new(entity_id, Action::StandUp));
+    }
+
     pub fn send_message(&mut self, message: String) {
         let complete_message = format!("{} : {}", self.player_name, message);
 
-        self.send_packet_to_map_server(GlobalMessagePacket::new(
+        self.send_packet_to_map_server(GlobalMessagePacket::new(
+            complete_message.bytes().len() as u16 + 5,
+            complete_message,
+        ));
+    }
+
+    pub fn send_party_message(&mut self, message: String) {
+        let complete_message = format!("{} : {}", self.player_name, message);
+
+        self.send_packet_to_map_server(PartyMessagePacket::new(
             complete_message.bytes().len() as u16 + 5,
             complete_message,
         ));
     }
 
+    /// Mark a position on the current map for all party members. The position
+    /// is encoded as a regular party message, so clients that don't support
+    /// pings will simply display it in the chat.
+    pub fn send_party_ping(&mut self, position: Vector2<usize>) {
+        // NOTE: The ground can be clicked on the login screen as well.
+        if self.map_stream.is_none() {
+            return;
+        }
+
+        let message = format!("{}{} {} {}", PARTY_PING_PREFIX, self.map_name, position.x, position.y);
+        self.send_party_message(message);
+    }
+
     pub fn start_dialog(&mut self, npc_id: EntityId) {
         self.send_packet_to_map_server(StartDialogPacket::new(npc_id));
     }
@@ -3375,6 +6086,27 @@ impl NetworkingSystem {
         self.send_packet_to_map_server(RequestUnequipItemPacket::new(item_index));
     }
 
+    pub fn increase_stat(&mut self, stat_type: StatType) {
+        self.send_packet_to_map_server(RequestStatUpPacket::new(stat_type, 1));
+    }
+
+    pub fn drop_item(&mut self, item_index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(DropItemPacket::new(item_index, amount));
+    }
+
+    pub fn set_item_favorite(&mut self, item_index: ItemIndex, is_favorite: bool) {
+        self.send_packet_to_map_server(SetItemFavoritePacket::new(item_index, is_favorite as u8));
+    }
+
+    pub fn use_item(&mut self, item_index: ItemIndex) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(UseItemPacket::new(item_index, account_id));
+    }
+
+    pub fn set_hotkey(&mut self, index: usize, hotkey: HotkeyData) {
+        self.send_packet_to_map_server(SetHotkeyPacket::new(0, index as u16, hotkey));
+    }
+
     pub fn cast_skill(&mut self, skill_id: SkillId, skill_level: SkillLevel, entity_id: EntityId) {
         self.send_packet_to_map_server(UseSkillAtIdPacket::new(skill_level, skill_id, entity_id));
     }
@@ -3402,6 +6134,66 @@ impl NetworkingSystem {
         self.send_packet_to_map_server(AddFriendPacket::new(name));
     }
 
+    /// Ask the map server for everything shown in the guild window. The
+    /// notice is sent by the map server on its own.
+    pub fn request_guild_information(&mut self) {
+        if self.guild.get_guild_id().is_none() {
+            return;
+        }
+
+        [GuildMenu::Information, GuildMenu::MemberList, GuildMenu::Positions]
+            .into_iter()
+            .for_each(|menu| self.send_packet_to_map_server(RequestGuildMenuPacket::new(menu)));
+    }
+
+    pub fn set_guild_notice(&mut self, subject: String, notice: String) {
+        if let Some(guild_id) = self.guild.get_guild_id() {
+            self.send_packet_to_map_server(SetGuildNoticePacket::new(guild_id, subject, notice));
+        }
+    }
+
+    pub fn change_guild_position(&mut self, position: GuildPosition) {
+        let position = GuildPositionData::new(
+            position.position_id,
+            position.mode,
+            position.position_id,
+            position.pay_rate,
+            position.name,
+        );
+        self.send_packet_to_map_server(ChangeGuildPositionPacket::new(44, position));
+    }
+
+    pub fn change_guild_member_position(&mut self, account_id: AccountId, character_id: CharacterId, position_id: u32) {
+        let member = GuildMemberPositionData::new(account_id, character_id, position_id);
+        self.send_packet_to_map_server(ChangeGuildMemberPositionPacket::new(16, member));
+    }
+
+    pub fn set_guild_emblem(&mut self, guild_id: u32, emblem_id: u32, emblem: Arc<ImageView>) {
+        self.guild.set_emblem(guild_id, emblem_id, emblem);
+    }
+
+    /// Request the emblem of a guild, unless it is already known or
+    /// requested. This is used for the guilds of other players too.
+    pub fn request_guild_emblem(&mut self, guild_id: u32, emblem_id: Option<u32>) {
+        if self.guild.request_emblem(guild_id, emblem_id) {
+            self.send_packet_to_map_server(RequestGuildEmblemPacket::new(guild_id));
+        }
+    }
+
+    pub fn get_guild_id(&self) -> Option<u32> {
+        self.guild.get_guild_id()
+    }
+
+    pub fn get_guild_emblem(&self, guild_id: u32) -> Option<Arc<ImageView>> {
+        self.guild.get_emblem(guild_id)
+    }
+
+    /// Party members on the current map, which are rendered before other
+    /// entities.
+    pub fn get_party_members(&self) -> &HashSet<AccountId> {
+        &self.party_members
+    }
+
     pub fn remove_friend(&mut self, account_id: AccountId, character_id: CharacterId) {
         self.send_packet_to_map_server(RemoveFriendPacket::new(account_id, character_id));
     }
@@ -3422,12 +6214,230 @@ impl NetworkingSystem {
         ));
     }
 
+    pub fn send_whisper(&mut self, name: String, message: String) {
+        // NOTE: Header, packet length, the fixed size name and the null terminator of
+        // the message.
+        let packet_length = message.bytes().len() as u16 + 29;
+        self.send_packet_to_map_server(WhisperMessagePacket::new(packet_length, name, message));
+    }
+
+    pub fn request_trade(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestTradePacket::new(entity_id));
+    }
+
+    pub fn accept_trade_request(&mut self) {
+        self.send_packet_to_map_server(TradeRequestResponsePacket::new(TradeRequestResponse::Accept));
+    }
+
+    pub fn reject_trade_request(&mut self) {
+        self.send_packet_to_map_server(TradeRequestResponsePacket::new(TradeRequestResponse::Reject));
+    }
+
+    pub fn add_trade_item(&mut self, index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(AddTradeItemPacket::new(index, amount as u32));
+    }
+
+    pub fn add_trade_zeny(&mut self, amount: u32) {
+        self.send_packet_to_map_server(AddTradeZenyPacket::new(amount));
+    }
+
+    pub fn lock_trade(&mut self) {
+        self.send_packet_to_map_server(LockTradePacket::default());
+    }
+
+    pub fn cancel_trade(&mut self) {
+        self.send_packet_to_map_server(CancelTradePacket::default());
+    }
+
+    pub fn confirm_trade(&mut self) {
+        self.send_packet_to_map_server(ConfirmTradePacket::default());
+    }
+
+    pub fn select_shop_deal_type(&mut self, npc_id: EntityId, deal_type: ShopDealType) {
+        self.send_packet_to_map_server(SelectShopDealTypePacket::new(npc_id, deal_type));
+    }
+
+    pub fn buy_shop_items(&mut self, items: Vec<(ItemId, u16)>) {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|(item_id, amount)| BuyShopItemData::new(amount, item_id))
+            .collect();
+
+        // NOTE: Header and packet length.
+        let packet_length = (items.len() * BuyShopItemData::size_in_bytes() + 4) as u16;
+        self.send_packet_to_map_server(BuyShopItemsPacket::new(packet_length, items));
+    }
+
+    pub fn sell_shop_items(&mut self, items: Vec<(ItemIndex, u16)>) {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|(index, amount)| SellShopItemData::new(index, amount))
+            .collect();
+
+        // NOTE: Header and packet length.
+        let packet_length = (items.len() * SellShopItemData::size_in_bytes() + 4) as u16;
+        self.send_packet_to_map_server(SellShopItemsPacket::new(packet_length, items));
+    }
+
+    pub fn move_item_to_cart(&mut self, index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(MoveItemToCartPacket::new(index, amount as u32));
+    }
+
+    pub fn move_item_from_cart(&mut self, index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(MoveItemFromCartPacket::new(index, amount as u32));
+    }
+
+    pub fn open_mailbox(&mut self) {
+        match self.packet_version.before(RODEX_MAIL_LIST_VERSION) {
+            true => self.send_packet_to_map_server(OpenMailboxPacket::new(MailboxType::Character, MailId(0))),
+            false => self.send_packet_to_map_server(OpenMailboxPacket2::new(MailboxType::Character, MailId(0))),
+        }
+    }
+
+    pub fn request_next_mail_page(&mut self, oldest_mail_id: MailId) {
+        match self.packet_version.before(RODEX_MAIL_LIST_VERSION) {
+            true => self.send_packet_to_map_server(RequestNextMailPagePacket::new(MailboxType::Character, oldest_mail_id)),
+            false => self.send_packet_to_map_server(RequestNextMailPagePacket2::new(MailboxType::Character, oldest_mail_id)),
+        }
+    }
+
+    pub fn read_mail(&mut self, mail_id: MailId) {
+        self.send_packet_to_map_server(ReadMailPacket::new(MailboxType::Character, mail_id));
+    }
+
+    pub fn delete_mail(&mut self, mail_id: MailId) {
+        self.send_packet_to_map_server(DeleteMailPacket::new(MailboxType::Character, mail_id));
+    }
+
+    pub fn take_mail_zeny(&mut self, mail_id: MailId) {
+        self.send_packet_to_map_server(TakeMailZenyPacket::new(mail_id, MailboxType::Character));
+    }
+
+    pub fn take_mail_items(&mut self, mail_id: MailId) {
+        self.send_packet_to_map_server(TakeMailItemsPacket::new(mail_id, MailboxType::Character));
+    }
+
+    pub fn open_mail_compose(&mut self) {
+        self.send_packet_to_map_server(OpenMailComposePacket::new(String::new()));
+    }
+
+    pub fn cancel_mail_compose(&mut self) {
+        self.send_packet_to_map_server(CancelMailComposePacket::default());
+    }
+
+    pub fn attach_mail_item(&mut self, index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(AttachMailItemPacket::new(index, amount));
+    }
+
+    pub fn detach_mail_item(&mut self, index: ItemIndex, amount: u16) {
+        self.send_packet_to_map_server(DetachMailItemPacket::new(index, amount));
+    }
+
+    pub fn check_mail_receiver(&mut self, receiver_name: String) {
+        self.send_packet_to_map_server(CheckMailReceiverPacket::new(receiver_name));
+    }
+
+    pub fn send_mail(&mut self, receiver_name: String, receiver_id: CharacterId, title: String, text: String, zeny: u64) {
+        // NOTE: The lengths include the null terminators.
+        let title_length = title.len() as u16 + 1;
+        let text_length = text.len() as u16 + 1;
+        // NOTE: Header, packet length, both names, zeny, both lengths and the receiver
+        // id.
+        let packet_length = 2 + 2 + 24 + 24 + 8 + 2 + 2 + 4 + title_length + text_length;
+
+        self.send_packet_to_map_server(SendMailPacket::new(
+            packet_length,
+            receiver_name,
+            self.player_name.clone(),
+            zeny,
+            title_length,
+            text_length,
+            receiver_id,
+            title,
+            text,
+        ));
+    }
+
+    pub fn request_achievement_reward(&mut self, achievement_id: u32) {
+        self.send_packet_to_map_server(RequestAchievementRewardPacket::new(achievement_id));
+    }
+
+    pub fn get_map_name(&self) -> &str {
+        &self.map_name
+    }
+
+    pub fn request_vending_items(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestVendingItemsPacket::new(entity_id));
+    }
+
+    pub fn buy_vending_items(&mut self, entity_id: EntityId, vending_id: u32, items: Vec<(ItemIndex, u16)>) {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|(index, amount)| BuyVendingItemData::new(amount, index))
+            .collect();
+
+        // NOTE: Header, packet length, entity id and vending id.
+        let packet_length = (items.len() * BuyVendingItemData::size_in_bytes() + 12) as u16;
+        self.send_packet_to_map_server(BuyVendingItemsPacket::new(packet_length, entity_id, vending_id, items));
+    }
+
+    /// Open a vending shop with items from the cart. The title is shown once
+    /// the map server accepted the shop.
+    pub fn open_vending(&mut self, title: String, items: Vec<(ItemIndex, u16, u32)>) {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|(index, amount, price)| VendingSetupItemData::new(index, amount, price))
+            .collect();
+
+        // NOTE: Header, packet length, title and open flag.
+        let packet_length = (items.len() * VendingSetupItemData::size_in_bytes() + 85) as u16;
+        self.vending_title = Some(title.clone());
+        self.send_packet_to_map_server(OpenVendingPacket::new(packet_length, title, 1, items));
+    }
+
+    pub fn close_vending(&mut self) {
+        self.send_packet_to_map_server(CloseVendingPacket::default());
+    }
+
+    pub fn open_bank(&mut self) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(OpenBankPacket::new(account_id));
+    }
+
+    pub fn close_bank(&mut self) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(CloseBankPacket::new(account_id));
+    }
+
+    pub fn request_bank_balance(&mut self) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(RequestBankBalancePacket::new(account_id));
+    }
+
+    pub fn deposit_zeny(&mut self, zeny: i32) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(BankDepositPacket::new(account_id, zeny));
+    }
+
+    pub fn withdraw_zeny(&mut self, zeny: i32) {
+        let account_id = self.login_data.as_ref().unwrap().account_id;
+        self.send_packet_to_map_server(BankWithdrawPacket::new(account_id, zeny));
+    }
+
+    pub fn request_view_equipment(&mut self, entity_id: EntityId) {
+        self.send_packet_to_map_server(RequestViewEquipmentPacket::new(entity_id));
+    }
+
+    pub fn invite_to_party(&mut self, name: String) {
+        self.send_packet_to_map_server(PartyInviteByNamePacket::new(name));
+    }
+
     #[profile]
     pub fn network_events(&mut self) -> Vec<NetworkEvent> {
         let mut events = Vec::new();
 
         while let Some(data) = self.try_get_data_from_map_server() {
-            let mut byte_stream: ByteStream<NetworkMetadata> = ByteStream::without_metadata(&data);
+            let mut byte_stream: ByteStream<NetworkMetadata> = self.byte_stream(&data);
 
             while !byte_stream.is_empty() {
                 let saved_offset = byte_stream.get_offset();
@@ -3442,16 +6452,42 @@ impl NetworkingSystem {
                 match self.handle_packet(&mut byte_stream, header, &mut events) {
                     Ok(true) => {}
                     // Unknown packet
-                    Ok(false) => {
-                        #[cfg(feature = "debug")]
-                        {
+                    Ok(false) => match self.packet_length_table.packet_length(header, &mut byte_stream) {
+                        // Known length, so we can skip it and continue with the next packet
+                        Ok(Some(packet_length)) if packet_length >= 2 => {
+                            byte_stream.set_offset(saved_offset);
+
+                            let Ok(_bytes) = byte_stream.slice::<UnknownPacket>(packet_length).map(<[u8]>::to_vec) else {
+                                // Cut-off packet
+                                byte_stream.set_offset(saved_offset);
+                                self.map_stream_buffer = byte_stream.remaining_bytes();
+                                break;
+                            };
+
+                            #[cfg(feature = "debug")]
+                            {
+                                let name = self.packet_length_table.packet_name(header).map(str::to_owned);
+                                byte_stream.incoming_packet(&UnknownPacket::new(_bytes, name));
+                            }
+                        }
+                        // Packet is cut-off at the length
+                        Err(error) if error.is_byte_stream_too_short() => {
                             byte_stream.set_offset(saved_offset);
-                            let packet = UnknownPacket::new(byte_stream.remaining_bytes());
-                            byte_stream.incoming_packet(&packet);
+                            self.map_stream_buffer = byte_stream.remaining_bytes();
+                            break;
                         }
+                        // Unknown length, so the rest of the data can't be read
+                        _ => {
+                            #[cfg(feature = "debug")]
+                            {
+                                byte_stream.set_offset(saved_offset);
+                                let packet = UnknownPacket::new(byte_stream.remaining_bytes(), None);
+                                byte_stream.incoming_packet(&packet);
+                            }
 
-                        break;
-                    }
+                            break;
+                        }
+                    },
                     // Cut-off packet
                     Err(error) if error.is_byte_stream_too_short() => {
                         byte_stream.set_offset(saved_offset);
@@ -3476,456 +6512,11 @@ impl NetworkingSystem {
         header: u16,
         events: &mut Vec<NetworkEvent>,
     ) -> ConversionResult<bool> {
-        match header {
-            BroadcastMessagePacket::HEADER => {
-                let packet = BroadcastMessagePacket::from_bytes(byte_stream)?;
-                let color = Color::rgb_u8(220, 200, 30);
-                let chat_message = ChatMessage::new(packet.message, color);
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            Broadcast2MessagePacket::HEADER => {
-                let packet = Broadcast2MessagePacket::from_bytes(byte_stream)?;
-                // NOTE: Drop the alpha channel because it might be 0.
-                let color = Color::rgb_u8(packet.font_color.red, packet.font_color.green, packet.font_color.blue);
-                let chat_message = ChatMessage::new(packet.message, color);
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            OverheadMessagePacket::HEADER => {
-                let packet = OverheadMessagePacket::from_bytes(byte_stream)?;
-                let color = Color::monochrome_u8(230);
-                let chat_message = ChatMessage::new(packet.message, color);
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            ServerMessagePacket::HEADER => {
-                let packet = ServerMessagePacket::from_bytes(byte_stream)?;
-                let chat_message = ChatMessage::new(packet.message, Color::monochrome_u8(255));
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            EntityMessagePacket::HEADER => {
-                let packet = EntityMessagePacket::from_bytes(byte_stream)?;
-                // NOTE: Drop the alpha channel because it might be 0.
-                let color = Color::rgb_u8(packet.color.red, packet.color.green, packet.color.blue);
-                let chat_message = ChatMessage::new(packet.message, color);
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            DisplayEmotionPacket::HEADER => {
-                let _packet = DisplayEmotionPacket::from_bytes(byte_stream)?;
-            }
-            EntityMovePacket::HEADER => {
-                let packet = EntityMovePacket::from_bytes(byte_stream)?;
-                let (origin, destination) = packet.from_to.to_vectors();
-                events.push(NetworkEvent::EntityMove(
-                    packet.entity_id,
-                    origin,
-                    destination,
-                    packet.timestamp,
-                ));
-            }
-            EntityStopMovePacket::HEADER => {
-                let _packet = EntityStopMovePacket::from_bytes(byte_stream)?;
-            }
-            PlayerMovePacket::HEADER => {
-                let packet = PlayerMovePacket::from_bytes(byte_stream)?;
-                let (origin, destination) = packet.from_to.to_vectors();
-                events.push(NetworkEvent::PlayerMove(origin, destination, packet.timestamp));
-            }
-            ChangeMapPacket::HEADER => {
-                let packet = ChangeMapPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::ChangeMap(
-                    packet.map_name.replace(".gat", ""),
-                    packet.position.map(|component| component as usize),
-                ));
-            }
-            EntityAppearedPacket::HEADER => {
-                let packet = EntityAppearedPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddEntity(packet.into()));
-            }
-            EntityAppeared2Packet::HEADER => {
-                let packet = EntityAppeared2Packet::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddEntity(packet.into()));
-            }
-            MovingEntityAppearedPacket::HEADER => {
-                let packet = MovingEntityAppearedPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddEntity(packet.into()));
-            }
-            EntityDisappearedPacket::HEADER => {
-                let packet = EntityDisappearedPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::RemoveEntity(packet.entity_id));
-            }
-            UpdateStatusPacket::HEADER => {
-                let packet = UpdateStatusPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateStatus(packet.status_type));
-            }
-            UpdateStatusPacket1::HEADER => {
-                let packet = UpdateStatusPacket1::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateStatus(packet.status_type));
-            }
-            UpdateStatusPacket2::HEADER => {
-                let packet = UpdateStatusPacket2::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateStatus(packet.status_type));
-            }
-            UpdateStatusPacket3::HEADER => {
-                let packet = UpdateStatusPacket3::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateStatus(packet.status_type));
-            }
-            UpdateAttackRangePacket::HEADER => {
-                let _packet = UpdateAttackRangePacket::from_bytes(byte_stream)?;
-            }
-            NewMailStatusPacket::HEADER => {
-                let _packet = NewMailStatusPacket::from_bytes(byte_stream)?;
-            }
-            AchievementUpdatePacket::HEADER => {
-                let _packet = AchievementUpdatePacket::from_bytes(byte_stream)?;
-            }
-            AchievementListPacket::HEADER => {
-                let _packet = AchievementListPacket::from_bytes(byte_stream)?;
-            }
-            CriticalWeightUpdatePacket::HEADER => {
-                let _packet = CriticalWeightUpdatePacket::from_bytes(byte_stream)?;
-            }
-            SpriteChangePacket::HEADER => {
-                let packet = SpriteChangePacket::from_bytes(byte_stream)?;
-                if packet.sprite_type == 0 {
-                    events.push(NetworkEvent::ChangeJob(packet.account_id, packet.value));
-                }
-            }
-            InventoyStartPacket::HEADER => {
-                let _packet = InventoyStartPacket::from_bytes(byte_stream)?;
-                let mut item_data = Vec::new();
-
-                // TODO: it might be better for performance and resilience to instead save a
-                // state in the networking system instaed of buffering *all*
-                // inventory packets if one of them is cut off
-                loop {
-                    let header = u16::from_bytes(byte_stream)?;
-
-                    match header {
-                        InventoyEndPacket::HEADER => {
-                            break;
-                        }
-                        RegularItemListPacket::HEADER => {
-                            let packet = RegularItemListPacket::from_bytes(byte_stream)?;
-                            for item_information in packet.item_information {
-                                item_data.push((
-                                    item_information.index,
-                                    item_information.item_id,
-                                    EquipPosition::None,
-                                    EquipPosition::None,
-                                )); // TODO: Don't add that data here, only equippable items need this data.
-                            }
-                        }
-                        EquippableItemListPacket::HEADER => {
-                            let packet = EquippableItemListPacket::from_bytes(byte_stream)?;
-                            for item_information in packet.item_information {
-                                item_data.push((
-                                    item_information.index,
-                                    item_information.item_id,
-                                    item_information.equip_position,
-                                    item_information.equipped_position,
-                                ));
-                            }
-                        }
-                        _ => return Err(ConversionError::from_message("expected inventory packet")),
-                    }
-                }
-
-                let _ = InventoyEndPacket::from_bytes(byte_stream)?;
-
-                events.push(NetworkEvent::Inventory(item_data));
-            }
-            EquippableSwitchItemListPacket::HEADER => {
-                let _packet = EquippableSwitchItemListPacket::from_bytes(byte_stream)?;
-            }
-            MapTypePacket::HEADER => {
-                let _packet = MapTypePacket::from_bytes(byte_stream)?;
-            }
-            UpdateSkillTreePacket::HEADER => {
-                let packet = UpdateSkillTreePacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::SkillTree(packet.skill_information));
-            }
-            UpdateHotkeysPacket::HEADER => {
-                let _packet = UpdateHotkeysPacket::from_bytes(byte_stream)?;
-            }
-            InitialStatusPacket::HEADER => {
-                let _packet = InitialStatusPacket::from_bytes(byte_stream)?;
-            }
-            UpdatePartyInvitationStatePacket::HEADER => {
-                let _packet = UpdatePartyInvitationStatePacket::from_bytes(byte_stream)?;
-            }
-            UpdateShowEquipPacket::HEADER => {
-                let _packet = UpdateShowEquipPacket::from_bytes(byte_stream)?;
-            }
-            UpdateConfigurationPacket::HEADER => {
-                let _packet = UpdateConfigurationPacket::from_bytes(byte_stream)?;
-            }
-            NavigateToMonsterPacket::HEADER => {
-                let _packet = NavigateToMonsterPacket::from_bytes(byte_stream)?;
-            }
-            MarkMinimapPositionPacket::HEADER => {
-                let _packet = MarkMinimapPositionPacket::from_bytes(byte_stream)?;
-            }
-            NextButtonPacket::HEADER => {
-                let _packet = NextButtonPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddNextButton);
-            }
-            CloseButtonPacket::HEADER => {
-                let _packet = CloseButtonPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddCloseButton);
-            }
-            DialogMenuPacket::HEADER => {
-                let packet = DialogMenuPacket::from_bytes(byte_stream)?;
-                let choices = packet
-                    .message
-                    .split(':')
-                    .map(String::from)
-                    .filter(|text| !text.is_empty())
-                    .collect();
-
-                events.push(NetworkEvent::AddChoiceButtons(choices));
-            }
-            DisplaySpecialEffectPacket::HEADER => {
-                let _packet = DisplaySpecialEffectPacket::from_bytes(byte_stream)?;
-            }
-            DisplaySkillCooldownPacket::HEADER => {
-                let _packet = DisplaySkillCooldownPacket::from_bytes(byte_stream)?;
-            }
-            DisplaySkillEffectAndDamagePacket::HEADER => {
-                let _packet = DisplaySkillEffectAndDamagePacket::from_bytes(byte_stream)?;
-            }
-            DisplaySkillEffectNoDamagePacket::HEADER => {
-                let packet = DisplaySkillEffectNoDamagePacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::HealEffect(
-                    packet.destination_entity_id,
-                    packet.heal_amount as usize,
-                ));
-
-                //events.push(NetworkEvent::VisualEffect());
-            }
-            DisplayPlayerHealEffect::HEADER => {
-                let _packet = DisplayPlayerHealEffect::from_bytes(byte_stream)?;
-            }
-            StatusChangePacket::HEADER => {
-                let _packet = StatusChangePacket::from_bytes(byte_stream)?;
-            }
-            QuestNotificationPacket1::HEADER => {
-                let _packet = QuestNotificationPacket1::from_bytes(byte_stream)?;
-            }
-            HuntingQuestNotificationPacket::HEADER => {
-                let _packet = HuntingQuestNotificationPacket::from_bytes(byte_stream)?;
-            }
-            HuntingQuestUpdateObjectivePacket::HEADER => {
-                let _packet = HuntingQuestUpdateObjectivePacket::from_bytes(byte_stream)?;
-            }
-            QuestRemovedPacket::HEADER => {
-                let _packet = QuestRemovedPacket::from_bytes(byte_stream)?;
-            }
-            QuestListPacket::HEADER => {
-                let _packet = QuestListPacket::from_bytes(byte_stream)?;
-            }
-            VisualEffectPacket::HEADER => {
-                let packet = VisualEffectPacket::from_bytes(byte_stream)?;
-                let path = match packet.effect {
-                    VisualEffect::BaseLevelUp => "angel.str",
-                    VisualEffect::JobLevelUp => "joblvup.str",
-                    VisualEffect::RefineFailure => "bs_refinefailed.str",
-                    VisualEffect::RefineSuccess => "bs_refinesuccess.str",
-                    VisualEffect::GameOver => "help_angel\\help_angel\\help_angel.str",
-                    VisualEffect::PharmacySuccess => "p_success.str",
-                    VisualEffect::PharmacyFailure => "p_failed.str",
-                    VisualEffect::BaseLevelUpSuperNovice => "help_angel\\help_angel\\help_angel.str",
-                    VisualEffect::JobLevelUpSuperNovice => "help_angel\\help_angel\\help_angel.str",
-                    VisualEffect::BaseLevelUpTaekwon => "help_angel\\help_angel\\help_angel.str",
-                };
-
-                events.push(NetworkEvent::VisualEffect(path, packet.entity_id));
-            }
-            DisplayGainedExperiencePacket::HEADER => {
-                let _packet = DisplayGainedExperiencePacket::from_bytes(byte_stream)?;
-            }
-            DisplayImagePacket::HEADER => {
-                let _packet = DisplayImagePacket::from_bytes(byte_stream)?;
-            }
-            StateChangePacket::HEADER => {
-                let _packet = StateChangePacket::from_bytes(byte_stream)?;
-            }
-
-            QuestEffectPacket::HEADER => {
-                let packet = QuestEffectPacket::from_bytes(byte_stream)?;
-                let event = match packet.effect {
-                    QuestEffect::None => NetworkEvent::RemoveQuestEffect(packet.entity_id),
-                    _ => NetworkEvent::AddQuestEffect(packet),
-                };
-                events.push(event);
-            }
-            ItemPickupPacket::HEADER => {
-                let packet = ItemPickupPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddIventoryItem(
-                    packet.index,
-                    packet.item_id,
-                    packet.equip_position,
-                    EquipPosition::None,
-                ));
-            }
-            RemoveItemFromInventoryPacket::HEADER => {
-                let _packet = RemoveItemFromInventoryPacket::from_bytes(byte_stream)?;
-            }
-            ServerTickPacket::HEADER => {
-                let packet = ServerTickPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateClientTick(packet.client_tick));
-            }
-            RequestPlayerDetailsSuccessPacket::HEADER => {
-                let packet = RequestPlayerDetailsSuccessPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateEntityDetails(EntityId(packet.character_id.0), packet.name));
-            }
-            RequestEntityDetailsSuccessPacket::HEADER => {
-                let packet = RequestEntityDetailsSuccessPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateEntityDetails(packet.entity_id, packet.name));
-            }
-            UpdateEntityHealthPointsPacket::HEADER => {
-                let packet = UpdateEntityHealthPointsPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateEntityHealth(
-                    packet.entity_id,
-                    packet.health_points as usize,
-                    packet.maximum_health_points as usize,
-                ));
-            }
-            RequestPlayerAttackFailedPacket::HEADER => {
-                let _packet = RequestPlayerAttackFailedPacket::from_bytes(byte_stream)?;
-            }
-            DamagePacket::HEADER => {
-                let packet = DamagePacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::DamageEffect(
-                    packet.destination_entity_id,
-                    packet.damage_amount as usize,
-                ));
-            }
-            NpcDialogPacket::HEADER => {
-                let packet = NpcDialogPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::OpenDialog(packet.text, packet.npc_id));
-            }
-            RequestEquipItemStatusPacket::HEADER => {
-                let packet = RequestEquipItemStatusPacket::from_bytes(byte_stream)?;
-                if let RequestEquipItemStatus::Success = packet.result {
-                    events.push(NetworkEvent::UpdateEquippedPosition {
-                        index: packet.inventory_index,
-                        equipped_position: packet.equipped_position,
-                    });
-                }
-            }
-            RequestUnequipItemStatusPacket::HEADER => {
-                let packet = RequestUnequipItemStatusPacket::from_bytes(byte_stream)?;
-                if let RequestUnequipItemStatus::Success = packet.result {
-                    events.push(NetworkEvent::UpdateEquippedPosition {
-                        index: packet.inventory_index,
-                        equipped_position: EquipPosition::None,
-                    });
-                }
-            }
-            Packet8302::HEADER => {
-                let _packet = Packet8302::from_bytes(byte_stream)?;
-            }
-            Packet180b::HEADER => {
-                let _packet = Packet180b::from_bytes(byte_stream)?;
-            }
-            MapServerLoginSuccessPacket::HEADER => {
-                let packet = MapServerLoginSuccessPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::UpdateClientTick(packet.client_tick));
-                events.push(NetworkEvent::SetPlayerPosition(packet.position.to_vector()));
-            }
-            RestartResponsePacket::HEADER => {
-                let packet = RestartResponsePacket::from_bytes(byte_stream)?;
-                match packet.result {
-                    RestartResponseStatus::Ok => events.push(NetworkEvent::Disconnect),
-                    RestartResponseStatus::Nothing => {
-                        let color = Color::rgb_u8(255, 100, 100);
-                        let chat_message = ChatMessage::new("Failed to log out.".to_string(), color);
-                        events.push(NetworkEvent::ChatMessage(chat_message));
-                    }
-                }
-            }
-            DisconnectResponsePacket::HEADER => {
-                let packet = DisconnectResponsePacket::from_bytes(byte_stream)?;
-                match packet.result {
-                    DisconnectResponseStatus::Ok => events.push(NetworkEvent::Disconnect),
-                    DisconnectResponseStatus::Wait10Seconds => {
-                        let color = Color::rgb_u8(255, 100, 100);
-                        let chat_message = ChatMessage::new("Please wait 10 seconds before trying to log out.".to_string(), color);
-                        events.push(NetworkEvent::ChatMessage(chat_message));
-                    }
-                }
-            }
-            UseSkillSuccessPacket::HEADER => {
-                let _packet = UseSkillSuccessPacket::from_bytes(byte_stream)?;
-            }
-            ToUseSkillSuccessPacket::HEADER => {
-                let _packet = ToUseSkillSuccessPacket::from_bytes(byte_stream)?;
-            }
-            NotifySkillUnitPacket::HEADER => {
-                let packet = NotifySkillUnitPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::AddSkillUnit(
-                    packet.entity_id,
-                    packet.unit_id,
-                    packet.position.map(|component| component as usize),
-                ));
-            }
-            SkillUnitDisappearPacket::HEADER => {
-                let packet = SkillUnitDisappearPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::RemoveSkillUnit(packet.entity_id));
-            }
-            NotifyGroundSkillPacket::HEADER => {
-                let _packet = NotifyGroundSkillPacket::from_bytes(byte_stream)?;
-            }
-            FriendListPacket::HEADER => {
-                let packet = FriendListPacket::from_bytes(byte_stream)?;
-                self.friend_list.with_mut(|friends| {
-                    *friends = packet.friends.into_iter().map(|friend| (friend, UnsafeCell::new(None))).collect();
-                    ValueState::Mutated(())
-                });
-            }
-            FriendOnlineStatusPacket::HEADER => {
-                let _packet = FriendOnlineStatusPacket::from_bytes(byte_stream)?;
-            }
-            FriendRequestPacket::HEADER => {
-                let packet = FriendRequestPacket::from_bytes(byte_stream)?;
-                events.push(NetworkEvent::FriendRequest(packet.friend));
-            }
-            FriendRequestResultPacket::HEADER => {
-                let packet = FriendRequestResultPacket::from_bytes(byte_stream)?;
-                if packet.result == FriendRequestResult::Accepted {
-                    self.friend_list.push((packet.friend.clone(), UnsafeCell::new(None)));
-                }
+        let Some(handler) = self.packet_handlers.get(header) else {
+            return Ok(false);
+        };
 
-                let color = Color::rgb_u8(220, 200, 30);
-                let chat_message = ChatMessage::new(packet.into_message(), color);
-                events.push(NetworkEvent::ChatMessage(chat_message));
-            }
-            NotifyFriendRemovedPacket::HEADER => {
-                let packet = NotifyFriendRemovedPacket::from_bytes(byte_stream)?;
-                self.friend_list.with_mut(|friends| {
-                    friends.retain(|(friend, _)| !(friend.account_id == packet.account_id && friend.character_id == packet.character_id));
-                    ValueState::Mutated(())
-                });
-            }
-            PartyInvitePacket::HEADER => {
-                let _packet = PartyInvitePacket::from_bytes(byte_stream)?;
-            }
-            StatusChangeSequencePacket::HEADER => {
-                let _packet = StatusChangeSequencePacket::from_bytes(byte_stream)?;
-            }
-            ReputationPacket::HEADER => {
-                let _packet = ReputationPacket::from_bytes(byte_stream)?;
-            }
-            ClanInfoPacket::HEADER => {
-                let _packet = ClanInfoPacket::from_bytes(byte_stream)?;
-            }
-            ClanOnlineCountPacket::HEADER => {
-                let _packet = ClanOnlineCountPacket::from_bytes(byte_stream)?;
-            }
-            ChangeMapCellPacket::HEADER => {
-                let _packet = ChangeMapCellPacket::from_bytes(byte_stream)?;
-            }
-            _ => return Ok(false),
-        }
+        handler(self, byte_stream, events)?;
 
         Ok(true)
     }
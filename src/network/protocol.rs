@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use ragnarok_bytes::{ByteStream, ConversionResult, FromBytes};
+
+use super::NetworkMetadata;
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+/// Packet lengths supplied by the user, in the format of the `packet_db.txt`
+/// shipped with server emulators.
+const PACKET_DATABASE_FILE: &str = "client/packet_db.txt";
+
+/// Date of the client the server was built for, e.g. `20220406`. Packets
+/// change their layout between versions, so the client needs to know which
+/// version the server speaks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PacketVersion(pub u32);
+
+impl PacketVersion {
+    /// Version the client is developed against. Used if the client info does
+    /// not specify a version for the service.
+    pub const LATEST: Self = Self(20220406);
+
+    pub fn before(&self, packet_version: u32) -> bool {
+        self.0 < packet_version
+    }
+
+    pub fn since(&self, packet_version: u32) -> bool {
+        self.0 >= packet_version
+    }
+}
+
+impl Default for PacketVersion {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketLength {
+    /// Length of the packet in bytes, *including* the header.
+    Fixed(u16),
+    /// The length of the packet follows the header.
+    Variable,
+}
+
+/// Lengths of packets the client doesn't handle yet, indexed by header and
+/// the packet version they were introduced in. This only covers packets that
+/// servers commonly send without being asked, the packet database of the
+/// user is used for everything else.
+const PACKET_LENGTHS: &[(u16, u32, PacketLength)] = &[
+    (0x00D7, 0, PacketLength::Variable),
+    (0x00D8, 0, PacketLength::Fixed(6)),
+    (0x0191, 0, PacketLength::Fixed(86)),
+    (0x0284, 0, PacketLength::Fixed(14)),
+    (0x02B9, 0, PacketLength::Fixed(191)),
+    (0x07D9, 20090617, PacketLength::Fixed(268)),
+    (0x0A00, 20141022, PacketLength::Fixed(269)),
+    (0x0B1B, 20190213, PacketLength::Fixed(2)),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketDefinition {
+    pub header: u16,
+    /// First packet version the definition applies to.
+    pub since: u32,
+    pub length: PacketLength,
+    pub name: Option<String>,
+}
+
+/// Get the built-in packet definitions followed by the ones from the packet
+/// database, so definitions of the user take precedence.
+pub fn load_packet_definitions() -> Vec<PacketDefinition> {
+    let mut definitions: Vec<PacketDefinition> = PACKET_LENGTHS
+        .iter()
+        .map(|(header, since, length)| PacketDefinition {
+            header: *header,
+            since: *since,
+            length: *length,
+            name: None,
+        })
+        .collect();
+
+    if let Ok(data) = std::fs::read_to_string(PACKET_DATABASE_FILE) {
+        #[cfg(feature = "debug")]
+        print_debug!("loading packet database from {}{PACKET_DATABASE_FILE}{}", MAGENTA, NONE);
+
+        definitions.extend(parse_packet_database(&data));
+    }
+
+    definitions
+}
+
+/// Lines look like `0x0072,19,wanttoconnection,2:6:10:14:18`, where the name
+/// and the field offsets are optional and a length of `-1` marks a packet of
+/// variable length. Definitions after a line like `packet_ver: 20120410` only
+/// apply to that packet version and above. Invalid lines are skipped.
+///
+/// Emulators number their packet versions instead, e.g. `packet_ver: 30`, and
+/// put the date of the client in a comment above, like
+/// `//2012-04-10aRagexeRE`. For those the date of the last such comment is
+/// used as the packet version.
+fn parse_packet_database(data: &str) -> Vec<PacketDefinition> {
+    let mut since = 0;
+    let mut client_date = None;
+    let mut definitions = Vec::new();
+
+    for (_line_number, line) in data.lines().enumerate() {
+        let (line, comment) = line.split_once("//").unwrap_or((line, ""));
+        let line = line.trim();
+
+        if let Some(date) = parse_client_date(comment) {
+            client_date = Some(date);
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':').filter(|(key, _)| !key.contains(',')) {
+            // NOTE: Other keys, like the packet keys used for obfuscation, don't matter
+            // for reading packets.
+            if key.trim() == "packet_ver" {
+                let packet_version = value.trim().parse::<u32>().ok().and_then(|packet_version| match packet_version {
+                    packet_version if packet_version >= MINIMUM_DATE => Some(packet_version),
+                    _ => client_date,
+                });
+
+                match packet_version {
+                    Some(packet_version) => since = packet_version,
+                    None => {
+                        #[cfg(feature = "debug")]
+                        print_debug!("[{YELLOW}warning{NONE}] invalid packet version in line {}", _line_number + 1);
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        let mut columns = line.split(',').map(str::trim);
+        let header = columns
+            .next()
+            .and_then(|header| header.strip_prefix("0x").or(header.strip_prefix("0X")))
+            .and_then(|header| u16::from_str_radix(header, 16).ok());
+        let length = columns.next().and_then(|length| match length {
+            "-1" => Some(PacketLength::Variable),
+            length => length.parse::<u16>().ok().filter(|length| *length >= 2).map(PacketLength::Fixed),
+        });
+        let name = columns.next().filter(|name| !name.is_empty()).map(str::to_owned);
+
+        let (Some(header), Some(length)) = (header, length) else {
+            #[cfg(feature = "debug")]
+            print_debug!("[{YELLOW}warning{NONE}] invalid packet definition in line {}", _line_number + 1);
+
+            continue;
+        };
+
+        definitions.push(PacketDefinition {
+            header,
+            since,
+            length,
+            name,
+        });
+    }
+
+    definitions
+}
+
+/// Packet versions below this are numbered by the emulator instead of being
+/// the date of the client.
+const MINIMUM_DATE: u32 = 10000000;
+
+/// Get the date from a comment like `2012-04-10aRagexeRE` as a packet version.
+fn parse_client_date(comment: &str) -> Option<u32> {
+    let date = comment.trim().get(..10)?;
+    let mut parts = date.split('-');
+    let (Some(year), Some(month), Some(day), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return None;
+    };
+
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+
+    format!("{year}{month}{day}").parse().ok()
+}
+
+/// Used to skip packets without a handler, so the packets following them in
+/// the stream can still be read.
+pub struct PacketLengthTable {
+    definitions: HashMap<u16, PacketDefinition>,
+}
+
+impl PacketLengthTable {
+    /// Later definitions replace earlier ones.
+    pub fn new(definitions: &[PacketDefinition], packet_version: PacketVersion) -> Self {
+        let definitions = definitions
+            .iter()
+            .filter(|definition| packet_version.since(definition.since))
+            .map(|definition| (definition.header, definition.clone()))
+            .collect();
+
+        Self { definitions }
+    }
+
+    /// Get the length of the packet in bytes, *including* the header. The byte
+    /// stream is expected to be right after the header and is moved past the
+    /// length for packets of variable length.
+    pub fn packet_length(&self, header: u16, byte_stream: &mut ByteStream<NetworkMetadata>) -> ConversionResult<Option<usize>> {
+        match self.definitions.get(&header).map(|definition| definition.length) {
+            Some(PacketLength::Fixed(packet_length)) => Ok(Some(packet_length as usize)),
+            Some(PacketLength::Variable) => u16::from_bytes(byte_stream).map(|packet_length| Some(packet_length as usize)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn packet_name(&self, header: u16) -> Option<&str> {
+        self.definitions.get(&header)?.name.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_bytes::ByteStream;
+
+    use super::{parse_client_date, parse_packet_database, PacketDefinition, PacketLength, PacketLengthTable, PacketVersion};
+    use crate::network::NetworkMetadata;
+
+    #[test]
+    fn packet_database() {
+        let data = "
+            // 2012-04-10aRagexeRE
+            packet_ver: 20120410
+            packet_keys: 0x01581359,0x452D6FFA,0x6AFB6E2E
+            0x0072,19,wanttoconnection,2:6:10:14:18
+            0x09CB,-1 // custom packet
+            0x0A00,invalid
+
+            packet_ver: 20150513
+            0x0072,22
+        ";
+
+        let definitions = parse_packet_database(data);
+
+        assert_eq!(definitions, vec![
+            PacketDefinition {
+                header: 0x0072,
+                since: 20120410,
+                length: PacketLength::Fixed(19),
+                name: Some("wanttoconnection".to_owned()),
+            },
+            PacketDefinition {
+                header: 0x09CB,
+                since: 20120410,
+                length: PacketLength::Variable,
+                name: None,
+            },
+            PacketDefinition {
+                header: 0x0072,
+                since: 20150513,
+                length: PacketLength::Fixed(22),
+                name: None,
+            },
+        ]);
+
+        let old_table = PacketLengthTable::new(&definitions, PacketVersion(20100101));
+        let table = PacketLengthTable::new(&definitions, PacketVersion(20130101));
+        let new_table = PacketLengthTable::new(&definitions, PacketVersion(20160101));
+
+        assert!(old_table.definitions.is_empty());
+        assert_eq!(table.definitions[&0x0072].length, PacketLength::Fixed(19));
+        assert_eq!(table.packet_name(0x0072), Some("wanttoconnection"));
+        assert_eq!(new_table.definitions[&0x0072].length, PacketLength::Fixed(22));
+    }
+
+    #[test]
+    fn client_date() {
+        assert_eq!(parse_client_date("2012-04-10aRagexeRE"), Some(20120410));
+        assert_eq!(parse_client_date(" 2008-09-10aSakexe"), Some(20080910));
+        assert_eq!(parse_client_date("Added new packets"), None);
+        assert_eq!(parse_client_date("2012-4-10aRagexeRE"), None);
+    }
+
+    #[test]
+    fn emulator_packet_database() {
+        // Excerpt in the format of the packet database of eAthena and rAthena, which
+        // number their packet versions.
+        let data = "
+            //2008-09-10aSakexe
+            packet_ver: 22
+            0x0072,22,wanttoconnection,3:9:13:17:21
+            0x0085,8,walktoxy,5
+            0x00d7,-1
+
+            //2012-04-10aRagexeRE
+            packet_ver: 30
+            0x01fd,15,repairitem,2
+            0x0085,5,walktoxy,2
+        ";
+
+        let definitions = parse_packet_database(data);
+
+        assert_eq!(definitions[0].since, 20080910);
+        assert_eq!(definitions[3].since, 20120410);
+
+        let table = PacketLengthTable::new(&definitions, PacketVersion(20100101));
+        let new_table = PacketLengthTable::new(&definitions, PacketVersion(20120410));
+
+        let data = [0x20, 0x00];
+        let mut byte_stream = ByteStream::with_metadata(&data, NetworkMetadata::new(PacketVersion(20100101)));
+
+        assert_eq!(table.packet_length(0x0085, &mut byte_stream).unwrap(), Some(8));
+        assert_eq!(table.packet_length(0x01FD, &mut byte_stream).unwrap(), None);
+        assert_eq!(table.packet_length(0x00D7, &mut byte_stream).unwrap(), Some(32));
+        assert_eq!(byte_stream.get_offset(), 2);
+
+        assert_eq!(new_table.packet_length(0x0085, &mut byte_stream).unwrap(), Some(5));
+        assert_eq!(new_table.packet_length(0x01FD, &mut byte_stream).unwrap(), Some(15));
+        assert_eq!(new_table.packet_name(0x01FD), Some("repairitem"));
+    }
+
+    #[test]
+    fn emulator_version_without_date() {
+        let definitions = parse_packet_database("packet_ver: 22\n0x0072,22");
+
+        assert_eq!(definitions[0].since, 0);
+    }
+}
@@ -0,0 +1,58 @@
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+const SETTINGS_FILENAME: &str = "client/away_settings.ron";
+
+#[derive(Serialize, Deserialize)]
+pub struct AwaySettings {
+    /// Minutes without input after which the player is shown as away.
+    pub away_timeout: Option<usize>,
+    /// Sit down while the player is away.
+    pub sit_while_away: bool,
+}
+
+impl Default for AwaySettings {
+    fn default() -> Self {
+        Self {
+            away_timeout: Some(5),
+            sit_while_away: false,
+        }
+    }
+}
+
+impl AwaySettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load away settings from {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading away settings from {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(SETTINGS_FILENAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving away settings to {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(SETTINGS_FILENAME, data).expect("unable to write file");
+    }
+}
+
+impl Drop for AwaySettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
@@ -0,0 +1,325 @@
+use cgmath::{Point3, Vector2};
+use chrono::Local;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::{DamageNumber, ParticleHolder, StartCamera};
+use crate::loaders::{ActionLoader, GameFileLoader, ScriptLoader, SpriteLoader};
+use crate::network::{ClientTick, EntityData, EntityId, Sex};
+use crate::world::{Entity, Map, Npc};
+
+const BENCHMARK_ARGUMENT: &str = "--benchmark";
+const DEFAULT_DURATION: f64 = 30.0;
+const REPORT_DIRECTORY: &str = "client/benchmarks";
+/// Frames during this time are not recorded, so loading textures and
+/// compiling pipelines doesn't skew the results.
+const WARMUP_TIME: f64 = 3.0;
+/// The random number generator is seeded, so every run uses the same entity
+/// movement and effects.
+const RANDOM_SEED: u64 = 0x6B6F72616E676172;
+const ENTITY_COUNT: usize = 60;
+const SPAWN_RADIUS: isize = 12;
+const MOVEMENT_INTERVAL: f64 = 2.5;
+const EFFECT_INTERVAL: f64 = 0.1;
+/// Radius of the camera path in world units.
+const CAMERA_PATH_RADIUS: f32 = 40.0;
+/// Time in seconds for one full circle of the camera path.
+const CAMERA_PATH_PERIOD: f64 = 20.0;
+/// Synthetic entities use ids that are very unlikely to collide with ones
+/// sent by the server.
+const FIRST_ENTITY_ID: u32 = 0xB000_0000;
+const MONSTER_JOBS: [u16; 5] = [1002, 1031, 1049, 1063, 1113];
+
+/// Settings parsed from the command line. The benchmark is started with
+/// `--benchmark`, optionally followed by the duration in seconds.
+#[derive(Debug, PartialEq)]
+pub struct BenchmarkSettings {
+    pub duration: f64,
+}
+
+impl BenchmarkSettings {
+    pub fn from_arguments(mut arguments: impl Iterator<Item = String>) -> Option<Self> {
+        arguments.position(|argument| argument == BENCHMARK_ARGUMENT)?;
+
+        let duration = arguments
+            .next()
+            .and_then(|argument| argument.parse::<f64>().ok())
+            .filter(|duration| *duration > 0.0)
+            .unwrap_or(DEFAULT_DURATION);
+
+        Some(Self { duration })
+    }
+}
+
+/// Frame time statistics in milliseconds.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FrameStatistics {
+    pub frame_count: usize,
+    pub average_frame_time: f64,
+    pub minimum_frame_time: f64,
+    pub maximum_frame_time: f64,
+    pub percentile_50: f64,
+    pub percentile_95: f64,
+    pub percentile_99: f64,
+    pub average_frames_per_second: f64,
+}
+
+impl FrameStatistics {
+    /// Calculate the statistics from frame times in seconds.
+    pub fn from_frame_times(frame_times: &[f64]) -> Option<Self> {
+        if frame_times.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = frame_times.iter().map(|frame_time| frame_time * 1000.0).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let percentile = |percentile: f64| {
+            let index = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+            sorted[index.clamp(1, sorted.len()) - 1]
+        };
+
+        let average_frame_time = sorted.iter().sum::<f64>() / sorted.len() as f64;
+
+        Some(Self {
+            frame_count: sorted.len(),
+            average_frame_time,
+            minimum_frame_time: sorted[0],
+            maximum_frame_time: sorted[sorted.len() - 1],
+            percentile_50: percentile(50.0),
+            percentile_95: percentile(95.0),
+            percentile_99: percentile(99.0),
+            average_frames_per_second: 1000.0 / average_frame_time,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport<'a> {
+    map: &'a str,
+    timestamp: String,
+    duration: f64,
+    entity_count: usize,
+    #[serde(flatten)]
+    statistics: FrameStatistics,
+}
+
+/// Runs a scripted camera path over the current map with synthetic entities
+/// and effects, while recording the frame times.
+pub struct Benchmark {
+    settings: BenchmarkSettings,
+    map_name: String,
+    center: Vector2<usize>,
+    entities: Vec<Entity>,
+    random: StdRng,
+    elapsed_time: f64,
+    movement_timer: f64,
+    effect_timer: f64,
+    frame_times: Vec<f64>,
+}
+
+impl Benchmark {
+    pub fn new(
+        settings: BenchmarkSettings,
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        map: &Map,
+        map_name: String,
+        center: Vector2<usize>,
+        client_tick: ClientTick,
+    ) -> Self {
+        let mut random = StdRng::seed_from_u64(RANDOM_SEED);
+
+        let entities = (0..ENTITY_COUNT)
+            .filter_map(|index| {
+                let position = Self::random_walkable_tile(map, center, &mut random)?;
+                let entity_data = EntityData {
+                    entity_id: EntityId(FIRST_ENTITY_ID + index as u32),
+                    movement_speed: 150,
+                    job: MONSTER_JOBS[index % MONSTER_JOBS.len()],
+                    position,
+                    destination: None,
+                    health_points: 100,
+                    maximum_health_points: 100,
+                    head_direction: 0,
+                    sex: Sex::Male,
+                    guild_id: None,
+                    emblem_id: 0,
+                };
+
+                Some(Entity::Npc(Npc::new(
+                    game_file_loader,
+                    sprite_loader,
+                    action_loader,
+                    script_loader,
+                    map,
+                    entity_data,
+                    client_tick,
+                )))
+            })
+            .collect();
+
+        #[cfg(feature = "debug")]
+        print_debug!(
+            "starting benchmark on {MAGENTA}{map_name}{NONE} for {MAGENTA}{}{NONE} seconds",
+            settings.duration
+        );
+
+        Self {
+            settings,
+            map_name,
+            center,
+            entities,
+            random,
+            elapsed_time: 0.0,
+            movement_timer: 0.0,
+            effect_timer: 0.0,
+            frame_times: Vec::new(),
+        }
+    }
+
+    fn random_walkable_tile(map: &Map, center: Vector2<usize>, random: &mut StdRng) -> Option<Vector2<usize>> {
+        // NOTE: Give up after a few attempts, so a map with very few walkable tiles
+        // doesn't stall the startup.
+        (0..16).find_map(|_| {
+            let x = center.x.checked_add_signed(random.gen_range(-SPAWN_RADIUS..=SPAWN_RADIUS))?;
+            let y = center.y.checked_add_signed(random.gen_range(-SPAWN_RADIUS..=SPAWN_RADIUS))?;
+            let position = Vector2::new(x, y);
+
+            (map.x_in_bounds(x) && map.y_in_bounds(y) && map.get_tile(position).is_walkable()).then_some(position)
+        })
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Advance the benchmark by one frame. Returns `false` once the benchmark
+    /// is finished.
+    pub fn update(
+        &mut self,
+        map: &Map,
+        delta_time: f64,
+        client_tick: ClientTick,
+        start_camera: &mut StartCamera,
+        particle_holder: &mut ParticleHolder,
+    ) -> bool {
+        self.elapsed_time += delta_time;
+
+        if self.elapsed_time > WARMUP_TIME {
+            self.frame_times.push(delta_time);
+        }
+
+        let angle = (self.elapsed_time / CAMERA_PATH_PERIOD * std::f64::consts::TAU) as f32;
+        let center = map.get_world_position(self.center);
+        start_camera.set_focus_point(Point3::new(
+            center.x + angle.cos() * CAMERA_PATH_RADIUS,
+            center.y,
+            center.z + angle.sin() * CAMERA_PATH_RADIUS,
+        ));
+
+        self.movement_timer += delta_time;
+
+        if self.movement_timer > MOVEMENT_INTERVAL {
+            self.movement_timer = 0.0;
+
+            for entity in &mut self.entities {
+                let position = entity.get_grid_position();
+
+                if let Some(destination) = Self::random_walkable_tile(map, self.center, &mut self.random) {
+                    entity.move_from_to(map, position, destination, client_tick);
+                }
+            }
+        }
+
+        self.effect_timer += delta_time;
+
+        while self.effect_timer > EFFECT_INTERVAL && !self.entities.is_empty() {
+            self.effect_timer -= EFFECT_INTERVAL;
+
+            let entity = &self.entities[self.random.gen_range(0..self.entities.len())];
+            let damage = self.random.gen_range(1..10000);
+            particle_holder.spawn_particle(Box::new(DamageNumber::new(entity.get_position(), damage.to_string())));
+        }
+
+        self.entities
+            .iter_mut()
+            .for_each(|entity| entity.update(map, delta_time as f32, client_tick));
+
+        self.elapsed_time < self.settings.duration + WARMUP_TIME
+    }
+
+    /// Write the frame time statistics to a JSON file in the report directory.
+    pub fn write_report(&self) {
+        let Some(statistics) = FrameStatistics::from_frame_times(&self.frame_times) else {
+            eprintln!("benchmark finished without recording any frames");
+            return;
+        };
+
+        let now = Local::now();
+        let report = BenchmarkReport {
+            map: &self.map_name,
+            timestamp: now.to_rfc3339(),
+            duration: self.settings.duration,
+            entity_count: self.entities.len(),
+            statistics,
+        };
+
+        let path = format!("{REPORT_DIRECTORY}/benchmark_{}.json", now.format("%Y-%m-%d_%H-%M-%S"));
+        let data = serde_json::to_string_pretty(&report).unwrap();
+        let result = std::fs::create_dir_all(REPORT_DIRECTORY).and_then(|_| std::fs::write(&path, data));
+
+        match result {
+            Ok(()) => println!(
+                "benchmark finished: {:.2} fps average, {:.2} ms 99th percentile, report written to {path}",
+                report.statistics.average_frames_per_second, report.statistics.percentile_99
+            ),
+            Err(error) => eprintln!("benchmark finished, but the report could not be written to {path}: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BenchmarkSettings, FrameStatistics, DEFAULT_DURATION};
+
+    #[test]
+    fn parse_arguments() {
+        let parse = |arguments: &[&str]| BenchmarkSettings::from_arguments(arguments.iter().map(|argument| argument.to_string()));
+
+        assert_eq!(parse(&[]), None);
+        assert_eq!(
+            parse(&["--benchmark"]),
+            Some(BenchmarkSettings {
+                duration: DEFAULT_DURATION
+            })
+        );
+        assert_eq!(parse(&["--benchmark", "10"]), Some(BenchmarkSettings { duration: 10.0 }));
+        assert_eq!(
+            parse(&["--benchmark", "-5"]),
+            Some(BenchmarkSettings {
+                duration: DEFAULT_DURATION
+            })
+        );
+    }
+
+    #[test]
+    fn frame_statistics() {
+        let frame_times: Vec<f64> = (1..=100).map(|milliseconds| milliseconds as f64 / 1000.0).collect();
+        let statistics = FrameStatistics::from_frame_times(&frame_times).unwrap();
+
+        assert_eq!(statistics.frame_count, 100);
+        assert!((statistics.minimum_frame_time - 1.0).abs() < 1e-9);
+        assert!((statistics.maximum_frame_time - 100.0).abs() < 1e-9);
+        assert!((statistics.percentile_50 - 50.0).abs() < 1e-9);
+        assert!((statistics.percentile_99 - 99.0).abs() < 1e-9);
+        assert!((statistics.average_frame_time - 50.5).abs() < 1e-9);
+
+        assert_eq!(FrameStatistics::from_frame_times(&[]), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+/// Time between two frames while the window is in the background.
+const IDLE_FRAME_TIME: Duration = Duration::from_millis(100);
+
+/// Keeps track of whether the window is in the background, so the client can
+/// reduce its work while the player is tabbed out or the window is minimized.
+pub struct IdleState {
+    focused: bool,
+    minimized: bool,
+    last_frame: Instant,
+}
+
+impl IdleState {
+    pub fn new() -> Self {
+        Self {
+            focused: true,
+            minimized: false,
+            last_frame: Instant::now(),
+        }
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.minimized = minimized;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        !self.focused || self.minimized
+    }
+
+    /// Returns the time at which the next frame should start if it is too
+    /// early to start it now. Frames are never delayed while the window is
+    /// focused.
+    pub fn delay_frame(&mut self, now: Instant) -> Option<Instant> {
+        let next_frame = self.last_frame + IDLE_FRAME_TIME;
+
+        if self.is_idle() && now < next_frame {
+            return Some(next_frame);
+        }
+
+        self.last_frame = now;
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{IdleState, IDLE_FRAME_TIME};
+
+    #[test]
+    fn focused_frames_are_not_delayed() {
+        let mut idle_state = IdleState::new();
+        let now = Instant::now();
+
+        assert!(idle_state.delay_frame(now).is_none());
+        assert!(idle_state.delay_frame(now + Duration::from_millis(1)).is_none());
+    }
+
+    #[test]
+    fn idle_frames_are_delayed() {
+        let mut idle_state = IdleState::new();
+        let now = Instant::now();
+
+        assert!(idle_state.delay_frame(now).is_none());
+
+        idle_state.set_focused(false);
+        assert_eq!(
+            idle_state.delay_frame(now + Duration::from_millis(1)),
+            Some(now + IDLE_FRAME_TIME)
+        );
+        assert!(idle_state.delay_frame(now + IDLE_FRAME_TIME).is_none());
+
+        idle_state.set_focused(true);
+        assert!(idle_state.delay_frame(now + IDLE_FRAME_TIME + Duration::from_millis(1)).is_none());
+    }
+}
@@ -1,6 +1,18 @@
+mod away;
+mod benchmark;
+mod idle;
+mod scheduler;
+mod shutdown;
 mod timer;
+mod version;
 #[macro_use]
 mod vulkan;
 
+pub use self::away::AwaySettings;
+pub use self::benchmark::{Benchmark, BenchmarkSettings};
+pub use self::idle::IdleState;
+pub use self::scheduler::Scheduler;
+pub use self::shutdown::{ShutdownReason, ShutdownSignal};
 pub use self::timer::GameTimer;
+pub use self::version::{build_version, ReleaseInformation, UpdateCheck, UpdateSettings, VERSION};
 pub use self::vulkan::*;
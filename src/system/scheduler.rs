@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+const SLOT_COUNT: usize = 256;
+const TICK_DURATION: Duration = Duration::from_millis(10);
+
+struct ScheduledEvent<E> {
+    rounds: usize,
+    event: E,
+}
+
+/// Timer wheel that hands out events once their delay has passed. Every slot
+/// of the wheel covers one tick, so scheduling and updating are constant time
+/// regardless of how many events are pending. Events that are further in the
+/// future than one turn of the wheel wait for the required number of rounds.
+pub struct Scheduler<E> {
+    slots: Vec<Vec<ScheduledEvent<E>>>,
+    current_slot: usize,
+    accumulator: Duration,
+}
+
+impl<E> Scheduler<E> {
+    pub fn new() -> Self {
+        Self {
+            slots: (0..SLOT_COUNT).map(|_| Vec::new()).collect(),
+            current_slot: 0,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Schedule an event to be returned by [`update`](Self::update) after
+    /// `delay` has passed. Events are never returned before their delay, but
+    /// may be returned up to one tick later.
+    pub fn schedule(&mut self, delay: Duration, event: E) {
+        let ticks = (delay.as_nanos().div_ceil(TICK_DURATION.as_nanos()) as usize).max(1);
+        let slot = (self.current_slot + ticks) % SLOT_COUNT;
+        let rounds = (ticks - 1) / SLOT_COUNT;
+
+        self.slots[slot].push(ScheduledEvent { rounds, event });
+    }
+
+    /// Advance the timer wheel and return all events that are due, in the
+    /// order they became due.
+    pub fn update(&mut self, delta_time: f64) -> Vec<E> {
+        let mut due_events = Vec::new();
+
+        self.accumulator += Duration::from_secs_f64(delta_time);
+
+        while self.accumulator >= TICK_DURATION {
+            self.accumulator -= TICK_DURATION;
+            self.current_slot = (self.current_slot + 1) % SLOT_COUNT;
+
+            let slot = &mut self.slots[self.current_slot];
+            let mut index = 0;
+
+            while index < slot.len() {
+                match slot[index].rounds {
+                    0 => due_events.push(slot.remove(index).event),
+                    _ => {
+                        slot[index].rounds -= 1;
+                        index += 1;
+                    }
+                }
+            }
+        }
+
+        due_events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn event_is_due_after_delay() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Duration::from_millis(100), 'a');
+
+        assert!(scheduler.update(0.05).is_empty());
+        assert_eq!(scheduler.update(0.06), ['a']);
+        assert!(scheduler.update(1.0).is_empty());
+    }
+
+    #[test]
+    fn events_are_due_in_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Duration::from_millis(300), 'c');
+        scheduler.schedule(Duration::from_millis(100), 'a');
+        scheduler.schedule(Duration::from_millis(200), 'b');
+
+        assert_eq!(scheduler.update(0.5), ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn event_longer_than_one_round() {
+        let delay = TICK_DURATION * (SLOT_COUNT as u32 * 5 / 2);
+
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(delay, 'a');
+
+        assert!(scheduler.update((delay - TICK_DURATION * 2).as_secs_f64()).is_empty());
+        assert_eq!(scheduler.update((TICK_DURATION * 3).as_secs_f64()), ['a']);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+/// Value of the shared flag while no shutdown is requested.
+const NOT_REQUESTED: u8 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ShutdownReason {
+    WindowClosed = 1,
+    ExitRequested = 2,
+    Terminated = 3,
+}
+
+impl ShutdownReason {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::WindowClosed),
+            2 => Some(Self::ExitRequested),
+            3 => Some(Self::Terminated),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ShutdownReason {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownReason::WindowClosed => write!(formatter, "window closed"),
+            ShutdownReason::ExitRequested => write!(formatter, "exit requested"),
+            ShutdownReason::Terminated => write!(formatter, "terminated"),
+        }
+    }
+}
+
+/// Collects shutdown requests from the window, the interface and termination
+/// signals, so the main loop can run the shutdown sequence at a defined point
+/// in the frame instead of exiting immediately.
+///
+/// Only the first request is kept, later ones are ignored.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    requested: Arc<AtomicU8>,
+}
+
+impl ShutdownSignal {
+    /// Create a new signal and install a handler for `SIGINT` and `SIGTERM`
+    /// (or their equivalent on Windows).
+    pub fn register() -> Self {
+        let signal = Self {
+            requested: Arc::new(AtomicU8::new(NOT_REQUESTED)),
+        };
+
+        let handler_signal = signal.clone();
+        if let Err(_error) = ctrlc::set_handler(move || handler_signal.request(ShutdownReason::Terminated)) {
+            #[cfg(feature = "debug")]
+            print_debug!("[{RED}error{NONE}] failed to install termination handler: {_error}");
+        }
+
+        signal
+    }
+
+    pub fn request(&self, reason: ShutdownReason) {
+        let _ = self
+            .requested
+            .compare_exchange(NOT_REQUESTED, reason as u8, Ordering::AcqRel, Ordering::Acquire);
+    }
+
+    pub fn requested(&self) -> Option<ShutdownReason> {
+        ShutdownReason::from_u8(self.requested.load(Ordering::Acquire))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::AtomicU8;
+    use std::sync::Arc;
+
+    use super::{ShutdownReason, ShutdownSignal, NOT_REQUESTED};
+
+    #[test]
+    fn first_request_is_kept() {
+        let signal = ShutdownSignal {
+            requested: Arc::new(AtomicU8::new(NOT_REQUESTED)),
+        };
+
+        assert_eq!(signal.requested(), None);
+
+        signal.request(ShutdownReason::WindowClosed);
+        signal.clone().request(ShutdownReason::Terminated);
+
+        assert_eq!(signal.requested(), Some(ShutdownReason::WindowClosed));
+    }
+}
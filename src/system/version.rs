@@ -0,0 +1,159 @@
+use std::thread::JoinHandle;
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const COMMIT_HASH: &str = env!("KORANGAR_COMMIT_HASH");
+const SETTINGS_FILENAME: &str = "client/update_settings.ron";
+const DEFAULT_RELEASE_URL: &str = "https://api.github.com/repos/vE5li/korangar/releases/latest";
+
+/// Version and commit of this build, for showing in the interface and for
+/// bug reports.
+pub fn build_version() -> String {
+    format!("Korangar {VERSION} ({COMMIT_HASH})")
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateSettings {
+    /// Checking for updates sends a request to the release server, so it is
+    /// opt-in.
+    pub check_for_updates: bool,
+    /// URL of the metadata of the latest release, in the format of the GitHub
+    /// releases API.
+    pub release_url: String,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            check_for_updates: false,
+            release_url: DEFAULT_RELEASE_URL.to_owned(),
+        }
+    }
+}
+
+impl UpdateSettings {
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load update settings from {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading update settings from {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(SETTINGS_FILENAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving update settings to {}{SETTINGS_FILENAME}{}", MAGENTA, NONE);
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+        std::fs::write(SETTINGS_FILENAME, data).expect("unable to write file");
+    }
+}
+
+impl Drop for UpdateSettings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct ReleaseInformation {
+    #[serde(rename = "tag_name")]
+    pub version: String,
+    #[serde(rename = "body", default)]
+    pub changelog: String,
+    #[serde(rename = "html_url")]
+    pub download_url: String,
+}
+
+/// Check for a newer release in the background, so a slow release server
+/// doesn't delay the start of the client.
+pub struct UpdateCheck {
+    handle: JoinHandle<Result<Option<ReleaseInformation>, String>>,
+}
+
+impl UpdateCheck {
+    pub fn start(release_url: String) -> Self {
+        let handle = std::thread::spawn(move || {
+            let response = ureq::get(&release_url)
+                .call()
+                .map_err(|error| error.to_string())?
+                .into_string()
+                .map_err(|error| error.to_string())?;
+            let release: ReleaseInformation = serde_json::from_str(&response).map_err(|error| error.to_string())?;
+
+            Ok(is_newer_version(&release.version, VERSION).then_some(release))
+        });
+
+        Self { handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Returns the latest release if it is newer than this build. Blocks until
+    /// the check is finished.
+    pub fn result(self) -> Result<Option<ReleaseInformation>, String> {
+        self.handle
+            .join()
+            .map_err(|_| "update check panicked".to_owned())
+            .and_then(|result| result)
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['.', '-'])
+        .take(3)
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Compare two versions in the format `major.minor.patch`, optionally
+/// prefixed by a `v`. Versions that can't be parsed are never newer.
+fn is_newer_version(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_newer_version;
+
+    #[test]
+    fn newer_version() {
+        assert!(is_newer_version("v0.2.0", "0.1.0"));
+        assert!(is_newer_version("0.1.10", "0.1.9"));
+        assert!(is_newer_version("1.0.0", "0.9.9"));
+    }
+
+    #[test]
+    fn same_or_older_version() {
+        assert!(!is_newer_version("v0.1.0", "0.1.0"));
+        assert!(!is_newer_version("0.0.9", "0.1.0"));
+    }
+
+    #[test]
+    fn invalid_version() {
+        assert!(!is_newer_version("nightly", "0.1.0"));
+    }
+}
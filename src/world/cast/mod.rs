@@ -0,0 +1,187 @@
+use cgmath::Vector2;
+use procedural::profile;
+use serde::{Deserialize, Serialize};
+
+use crate::graphics::{Camera, Color, IndicatorRenderer, Renderer};
+use crate::network::{EntityId, SkillId};
+use crate::world::{Entity, EntityType, Map};
+
+/// Which casts are previewed on the ground. Servers with PvP may consider
+/// seeing where other players are aiming an unfair advantage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CastPreviewMode {
+    Off,
+    #[default]
+    MonstersOnly,
+    Everyone,
+}
+
+/// Number of tiles that a ground skill reaches in every direction from the
+/// target cell.
+fn area_radius(skill_id: SkillId) -> Option<usize> {
+    match skill_id.0 {
+        12 => Some(0),  // MG_SAFETYWALL
+        21 => Some(2),  // MG_THUNDERSTORM
+        25 => Some(1),  // AL_PNEUMA
+        70 => Some(2),  // PR_SANCTUARY
+        79 => Some(3),  // PR_MAGNUS
+        80 => Some(1),  // WZ_FIREPILLAR
+        83 => Some(3),  // WZ_METEOR
+        85 => Some(5),  // WZ_VERMILION
+        89 => Some(4),  // WZ_STORMGUST
+        91 => Some(2),  // WZ_HEAVENDRIVE
+        92 => Some(2),  // WZ_QUAGMIRE
+        110 => Some(2), // BS_HAMMERFALL
+        _ => None,
+    }
+}
+
+enum CastTarget {
+    Area { center: Vector2<usize>, radius: usize },
+    Entity(EntityId),
+}
+
+struct CastPreview {
+    caster: EntityId,
+    target: CastTarget,
+    remaining: f32,
+}
+
+/// Ground markers for skills that other entities are currently casting, so
+/// players have a chance to get out of the way.
+#[derive(Default)]
+pub struct CastPreviewHolder {
+    casts: Vec<CastPreview>,
+}
+
+impl CastPreviewHolder {
+    /// Add a cast. Ground skills mark their area of effect, targeted skills
+    /// draw a line from the caster to the target.
+    pub fn add_cast(&mut self, caster: EntityId, target: Option<EntityId>, position: Vector2<usize>, skill_id: SkillId, cast_time: f32) {
+        let target = match target {
+            Some(entity_id) => CastTarget::Entity(entity_id),
+            None => match area_radius(skill_id) {
+                Some(radius) => CastTarget::Area { center: position, radius },
+                None => return,
+            },
+        };
+
+        // NOTE: An entity can only cast one skill at a time.
+        self.remove_cast(caster);
+        self.casts.push(CastPreview {
+            caster,
+            target,
+            remaining: cast_time,
+        });
+    }
+
+    pub fn remove_cast(&mut self, caster: EntityId) {
+        self.casts.retain(|cast| cast.caster != caster);
+    }
+
+    pub fn clear(&mut self) {
+        self.casts.clear();
+    }
+
+    #[profile("update cast previews")]
+    pub fn update(&mut self, delta_time: f32) {
+        self.casts.retain_mut(|cast| {
+            cast.remaining -= delta_time;
+            cast.remaining > 0.0
+        });
+    }
+
+    #[profile("render cast previews")]
+    pub fn render_indicators<T>(
+        &self,
+        render_target: &mut T::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        map: &Map,
+        entities: &[Entity],
+        mode: CastPreviewMode,
+        area_color: Color,
+        target_color: Color,
+    ) where
+        T: Renderer + IndicatorRenderer,
+    {
+        let find_entity = |entity_id: EntityId| entities.iter().find(|entity| entity.get_entity_id() == entity_id);
+
+        for cast in &self.casts {
+            let Some(caster) = find_entity(cast.caster) else {
+                continue;
+            };
+
+            let is_visible = match mode {
+                CastPreviewMode::Off => false,
+                CastPreviewMode::MonstersOnly => caster.get_entity_type() == EntityType::Monster,
+                CastPreviewMode::Everyone => true,
+            };
+
+            if !is_visible {
+                continue;
+            }
+
+            match cast.target {
+                CastTarget::Area { center, radius } => {
+                    area_tiles(center, radius)
+                        .filter(|tile| map.x_in_bounds(tile.x) && map.y_in_bounds(tile.y))
+                        .for_each(|tile| map.render_walk_indicator(render_target, renderer, camera, area_color, tile));
+                }
+                CastTarget::Entity(target) => {
+                    if let Some(target) = find_entity(target) {
+                        line_tiles(caster.get_grid_position(), target.get_grid_position())
+                            .into_iter()
+                            .for_each(|tile| map.render_walk_indicator(render_target, renderer, camera, target_color, tile));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn area_tiles(center: Vector2<usize>, radius: usize) -> impl Iterator<Item = Vector2<usize>> {
+    let x_range = center.x.saturating_sub(radius)..=center.x + radius;
+    let y_range = center.y.saturating_sub(radius)..=center.y + radius;
+
+    x_range.flat_map(move |x| y_range.clone().map(move |y| Vector2::new(x, y)))
+}
+
+/// Tiles between two cells, excluding the cells themselves since the caster
+/// and the target are standing on them.
+fn line_tiles(from: Vector2<usize>, to: Vector2<usize>) -> Vec<Vector2<usize>> {
+    let delta_x = to.x as isize - from.x as isize;
+    let delta_y = to.y as isize - from.y as isize;
+    let steps = delta_x.abs().max(delta_y.abs());
+
+    (1..steps)
+        .map(|step| {
+            let x = from.x as isize + delta_x * step / steps;
+            let y = from.y as isize + delta_y * step / steps;
+            Vector2::new(x as usize, y as usize)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+
+    use super::{area_tiles, line_tiles};
+
+    #[test]
+    fn area_is_clamped_at_the_map_edge() {
+        assert_eq!(area_tiles(Vector2::new(5, 5), 1).count(), 9);
+        assert_eq!(area_tiles(Vector2::new(0, 0), 2).count(), 9);
+    }
+
+    #[test]
+    fn line_skips_the_end_points() {
+        assert_eq!(line_tiles(Vector2::new(0, 0), Vector2::new(3, 0)), vec![
+            Vector2::new(1, 0),
+            Vector2::new(2, 0)
+        ]);
+        assert_eq!(line_tiles(Vector2::new(2, 2), Vector2::new(0, 0)), vec![Vector2::new(1, 1)]);
+        assert!(line_tiles(Vector2::new(2, 2), Vector2::new(2, 3)).is_empty());
+    }
+}
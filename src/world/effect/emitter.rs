@@ -0,0 +1,191 @@
+use std::ops::Range;
+
+use cgmath::Vector3;
+
+use super::lookup::EffectId;
+use crate::graphics::Color;
+
+/// Texture of soft, round particles like smoke or glowing lights.
+pub(super) const SOFT_PARTICLE_TEXTURE: &str = "particle.png";
+
+/// Describes the particles that an effect source of a map emits.
+#[derive(Clone, Debug)]
+pub struct EmitterDescription {
+    /// Number of particles spawned per second.
+    pub spawn_rate: f32,
+    /// Lifetime of a single particle in seconds.
+    pub lifetime: Range<f32>,
+    /// Particles spawn at a random offset within this distance of the
+    /// effect source on every axis.
+    pub spread: Vector3<f32>,
+    pub minimum_velocity: Vector3<f32>,
+    pub maximum_velocity: Vector3<f32>,
+    /// Acceleration along the y axis. Negative values pull the particles
+    /// down.
+    pub gravity: f32,
+    /// Color at the start and the end of the lifetime of a particle.
+    pub colors: [Color; 2],
+    /// Size in pixels at the start and the end of the lifetime of a particle.
+    pub sizes: [f32; 2],
+    /// Particles without a texture are rendered as plain rectangles.
+    pub texture_file: Option<&'static str>,
+}
+
+impl EmitterDescription {
+    pub(super) fn new(spawn_rate: f32, lifetime: Range<f32>, colors: [Color; 2], sizes: [f32; 2]) -> Self {
+        Self {
+            spawn_rate,
+            lifetime,
+            spread: Vector3::new(0.0, 0.0, 0.0),
+            minimum_velocity: Vector3::new(0.0, 0.0, 0.0),
+            maximum_velocity: Vector3::new(0.0, 0.0, 0.0),
+            gravity: 0.0,
+            colors,
+            sizes,
+            texture_file: None,
+        }
+    }
+
+    pub(super) fn with_spread(self, spread: Vector3<f32>) -> Self {
+        Self { spread, ..self }
+    }
+
+    pub(super) fn with_velocity(self, minimum_velocity: Vector3<f32>, maximum_velocity: Vector3<f32>) -> Self {
+        Self {
+            minimum_velocity,
+            maximum_velocity,
+            ..self
+        }
+    }
+
+    pub(super) fn with_gravity(self, gravity: f32) -> Self {
+        Self { gravity, ..self }
+    }
+
+    pub(super) fn with_texture(self, texture_file: &'static str) -> Self {
+        Self {
+            texture_file: Some(texture_file),
+            ..self
+        }
+    }
+
+    pub(super) fn flame(color: Color, fade_color: Color) -> Self {
+        Self::new(12.0, 0.4..0.8, [color, fade_color], [6.0, 1.5])
+            .with_spread(Vector3::new(1.0, 0.5, 1.0))
+            .with_velocity(Vector3::new(-1.5, 6.0, -1.5), Vector3::new(1.5, 10.0, 1.5))
+            .with_texture(SOFT_PARTICLE_TEXTURE)
+    }
+
+    fn waterfall(width: f32) -> Self {
+        Self::new(
+            6.0 * width,
+            0.8..1.2,
+            [Color::rgba(0.8, 0.9, 1.0, 0.7), Color::rgba(0.6, 0.8, 1.0, 0.0)],
+            [5.0, 10.0],
+        )
+        .with_spread(Vector3::new(width, 1.0, width))
+        .with_velocity(Vector3::new(-1.0, -4.0, -1.0), Vector3::new(1.0, 0.0, 1.0))
+        .with_gravity(-30.0)
+        .with_texture(SOFT_PARTICLE_TEXTURE)
+    }
+
+    pub(super) fn glow(color: Color) -> Self {
+        Self::new(3.0, 1.5..3.0, [color, color.multiply_alpha(0.0)], [4.0, 8.0])
+            .with_spread(Vector3::new(4.0, 2.0, 4.0))
+            .with_velocity(Vector3::new(-0.5, 0.5, -0.5), Vector3::new(0.5, 2.0, 0.5))
+            .with_texture(SOFT_PARTICLE_TEXTURE)
+    }
+
+    /// Returns [`None`] for effect types that are unknown or don't emit
+    /// particles.
+    pub fn from_effect_type(effect_type: u32) -> Option<Self> {
+        let description = match EffectId::from_effect_type(effect_type)? {
+            EffectId::Torch | EffectId::ChookgiFire | EffectId::TetraFire | EffectId::KoZenkaiFire => {
+                Self::flame(Color::rgb_u8(255, 190, 60), Color::rgba_u8(255, 40, 0, 0))
+            }
+            EffectId::TorchRed => Self::flame(Color::rgb_u8(255, 80, 40), Color::rgba_u8(160, 0, 0, 0)),
+            EffectId::TorchGreen => Self::flame(Color::rgb_u8(140, 255, 90), Color::rgba_u8(0, 120, 20, 0)),
+            EffectId::TorchPurple => Self::flame(Color::rgb_u8(220, 120, 255), Color::rgba_u8(90, 0, 160, 0)),
+            EffectId::Smoke | EffectId::WaterSmoke => Self::new(
+                2.0,
+                3.0..5.0,
+                [Color::rgba_u8(90, 90, 90, 150), Color::rgba_u8(150, 150, 150, 0)],
+                [8.0, 30.0],
+            )
+            .with_spread(Vector3::new(1.0, 0.0, 1.0))
+            .with_velocity(Vector3::new(-1.0, 3.0, -1.0), Vector3::new(1.0, 5.0, 1.0))
+            .with_texture(SOFT_PARTICLE_TEXTURE),
+            EffectId::BottomFogwall => Self::new(
+                1.0,
+                4.0..6.0,
+                [Color::rgba_u8(220, 220, 230, 60), Color::rgba_u8(220, 220, 230, 0)],
+                [40.0, 70.0],
+            )
+            .with_spread(Vector3::new(15.0, 1.0, 15.0))
+            .with_velocity(Vector3::new(-1.0, 0.0, -1.0), Vector3::new(1.0, 0.5, 1.0))
+            .with_texture(SOFT_PARTICLE_TEXTURE),
+            EffectId::Firefly => Self::new(1.5, 3.0..5.0, [Color::rgb_u8(210, 255, 90), Color::rgba_u8(120, 255, 60, 0)], [
+                3.0, 2.0,
+            ])
+            .with_spread(Vector3::new(10.0, 3.0, 10.0))
+            .with_velocity(Vector3::new(-2.0, -1.0, -2.0), Vector3::new(2.0, 1.0, 2.0))
+            .with_texture(SOFT_PARTICLE_TEXTURE),
+            EffectId::Snow | EffectId::GiftOfSnow => {
+                Self::new(15.0, 4.0..6.0, [Color::monochrome_u8(255), Color::rgba_u8(255, 255, 255, 0)], [
+                    3.0, 3.0,
+                ])
+                .with_spread(Vector3::new(40.0, 5.0, 40.0))
+                .with_velocity(Vector3::new(-2.0, -8.0, -2.0), Vector3::new(2.0, -5.0, 2.0))
+            }
+            EffectId::Flowerleaf => Self::new(
+                3.0,
+                4.0..6.0,
+                [Color::rgb_u8(255, 170, 200), Color::rgba_u8(255, 120, 170, 0)],
+                [4.0, 4.0],
+            )
+            .with_spread(Vector3::new(15.0, 2.0, 15.0))
+            .with_velocity(Vector3::new(-3.0, -4.0, -3.0), Vector3::new(3.0, -2.0, 3.0)),
+            EffectId::Dust | EffectId::Duststorm => Self::new(
+                4.0,
+                2.0..4.0,
+                [Color::rgba_u8(170, 140, 100, 120), Color::rgba_u8(170, 140, 100, 0)],
+                [6.0, 16.0],
+            )
+            .with_spread(Vector3::new(10.0, 1.0, 10.0))
+            .with_velocity(Vector3::new(-4.0, 0.0, -4.0), Vector3::new(4.0, 1.5, 4.0))
+            .with_texture(SOFT_PARTICLE_TEXTURE),
+            EffectId::Waterfall | EffectId::Waterfall90 | EffectId::WaterfallT2 | EffectId::WaterfallT2_90 | EffectId::WaterfallAni => {
+                Self::waterfall(8.0)
+            }
+            EffectId::WaterfallSmall | EffectId::WaterfallSmall90 | EffectId::WaterfallSmallT2 | EffectId::WaterfallSmallT2_90 => {
+                Self::waterfall(4.0)
+            }
+            EffectId::Forestlight | EffectId::MapGreenlight => Self::glow(Color::rgba_u8(160, 255, 130, 180)),
+            EffectId::Circlelight | EffectId::Linklight => Self::glow(Color::rgba_u8(200, 220, 255, 180)),
+            EffectId::Soullight => Self::glow(Color::rgba_u8(130, 160, 255, 180)),
+            _ => return None,
+        };
+
+        Some(description)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EmitterDescription;
+
+    #[test]
+    fn torches_differ_from_snow() {
+        let torch = EmitterDescription::from_effect_type(47).unwrap();
+        let snow = EmitterDescription::from_effect_type(162).unwrap();
+
+        assert!(torch.maximum_velocity.y > 0.0);
+        assert!(snow.maximum_velocity.y < 0.0);
+        assert_ne!(torch.colors, snow.colors);
+    }
+
+    #[test]
+    fn unknown_effect_type() {
+        assert!(EmitterDescription::from_effect_type(u32::MAX).is_none());
+    }
+}
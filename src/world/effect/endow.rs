@@ -0,0 +1,39 @@
+use crate::graphics::Color;
+
+/// Element that a weapon is endowed with, for example by a Sage or by
+/// consuming a converter. Endowed weapons glow in the color of their element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponEndow {
+    Fire,
+    Water,
+    Wind,
+    Earth,
+    Holy,
+    Poison,
+}
+
+impl WeaponEndow {
+    /// Map the status icon id sent by the map server to the endow it displays.
+    pub fn from_status_index(status_index: u16) -> Option<Self> {
+        match status_index {
+            6 => Some(Self::Poison),
+            17 => Some(Self::Holy),
+            90 => Some(Self::Fire),
+            91 => Some(Self::Water),
+            92 => Some(Self::Wind),
+            93 => Some(Self::Earth),
+            _ => None,
+        }
+    }
+
+    pub fn glow_color(self) -> Color {
+        match self {
+            Self::Fire => Color::rgb_u8(255, 70, 20),
+            Self::Water => Color::rgb_u8(40, 120, 255),
+            Self::Wind => Color::rgb_u8(120, 255, 120),
+            Self::Earth => Color::rgb_u8(180, 130, 60),
+            Self::Holy => Color::rgb_u8(255, 250, 200),
+            Self::Poison => Color::rgb_u8(160, 60, 200),
+        }
+    }
+}
@@ -1,14 +1,35 @@
+mod emitter;
+mod endow;
 mod lookup;
+mod skill;
+mod unit;
 
 use cgmath::Vector3;
 use procedural::{PrototypeElement, PrototypeWindow};
+use ragnarok_bytes::{ByteStream, FromBytes};
 use ragnarok_procedural::ByteConvertable;
 
+pub use self::emitter::EmitterDescription;
+pub use self::endow::WeaponEndow;
+use self::lookup::EffectId;
+pub use self::skill::{SkillEffect, SkillEffectAnchor};
+pub use self::unit::UnitEffect;
+use crate::graphics::ParticleEmitter;
 #[cfg(feature = "debug")]
 use crate::graphics::{Camera, MarkerRenderer, Renderer};
+use crate::loaders::{GameFileLoader, TextureLoader};
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
+impl EffectId {
+    /// Returns [`None`] for effect types that are not known to the client.
+    fn from_effect_type(effect_type: u32) -> Option<Self> {
+        let bytes = effect_type.to_le_bytes();
+        let mut byte_stream = ByteStream::<()>::without_metadata(&bytes);
+        Self::from_bytes(&mut byte_stream).ok()
+    }
+}
+
 #[derive(Clone, ByteConvertable, PrototypeElement, PrototypeWindow)]
 #[window_title("Effect Source")]
 pub struct EffectSource {
@@ -28,6 +49,24 @@ impl EffectSource {
         self.position += offset;
     }
 
+    /// Weather effects can be disabled for a map through its overrides.
+    pub fn is_weather(&self) -> bool {
+        matches!(
+            EffectId::from_effect_type(self.effect_type),
+            Some(EffectId::Snow | EffectId::GiftOfSnow | EffectId::Flowerleaf | EffectId::Duststorm)
+        )
+    }
+
+    /// Returns [`None`] if the effect type doesn't emit particles.
+    pub fn to_emitter(&self, game_file_loader: &mut GameFileLoader, texture_loader: &mut TextureLoader) -> Option<ParticleEmitter> {
+        let description = EmitterDescription::from_effect_type(self.effect_type)?;
+        let texture = description
+            .texture_file
+            .and_then(|texture_file| texture_loader.get(texture_file, game_file_loader).ok());
+
+        Some(ParticleEmitter::new(self.position, description, texture))
+    }
+
     #[cfg(feature = "debug")]
     pub fn render_marker<T>(
         &self,
@@ -0,0 +1,129 @@
+use cgmath::Vector3;
+
+use super::emitter::{EmitterDescription, SOFT_PARTICLE_TEXTURE};
+use crate::graphics::Color;
+use crate::network::SkillId;
+
+/// Where the effect of a skill is played.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkillEffectAnchor {
+    Caster,
+    /// The target entity, or the target cell for ground skills.
+    Target,
+}
+
+/// Effect that is played once when a skill takes effect. Skills without an
+/// effect file use a short burst of particles instead.
+pub struct SkillEffect {
+    pub anchor: SkillEffectAnchor,
+    pub effect_file: Option<&'static str>,
+    pub emitter: Option<EmitterDescription>,
+    pub light_color: Color,
+    pub light_intensity: f32,
+    pub sound_effect_file: Option<&'static str>,
+}
+
+impl SkillEffect {
+    /// Time in seconds that the particle burst of a skill keeps emitting.
+    pub const BURST_DURATION: f32 = 0.3;
+
+    fn new(anchor: SkillEffectAnchor, light_color: Color, light_intensity: f32) -> Self {
+        Self {
+            anchor,
+            effect_file: None,
+            emitter: None,
+            light_color,
+            light_intensity,
+            sound_effect_file: None,
+        }
+    }
+
+    fn with_effect_file(self, effect_file: &'static str) -> Self {
+        Self {
+            effect_file: Some(effect_file),
+            ..self
+        }
+    }
+
+    fn with_emitter(self, emitter: EmitterDescription) -> Self {
+        Self {
+            emitter: Some(emitter),
+            ..self
+        }
+    }
+
+    fn with_sound_effect(self, sound_effect_file: &'static str) -> Self {
+        Self {
+            sound_effect_file: Some(sound_effect_file),
+            ..self
+        }
+    }
+
+    /// Particles bursting out in every direction, for hits and explosions.
+    fn burst(color: Color, fade_color: Color, speed: f32) -> EmitterDescription {
+        EmitterDescription::new(80.0, 0.3..0.6, [color, fade_color], [6.0, 2.0])
+            .with_spread(Vector3::new(1.0, 1.0, 1.0))
+            .with_velocity(Vector3::new(-speed, 0.0, -speed), Vector3::new(speed, speed, speed))
+            .with_gravity(-speed)
+            .with_texture(SOFT_PARTICLE_TEXTURE)
+    }
+
+    /// Particles slowly rising around the target, for heals and buffs.
+    fn aura(color: Color) -> EmitterDescription {
+        EmitterDescription::new(40.0, 0.8..1.2, [color, color.multiply_alpha(0.0)], [5.0, 3.0])
+            .with_spread(Vector3::new(3.0, 0.5, 3.0))
+            .with_velocity(Vector3::new(-0.5, 6.0, -0.5), Vector3::new(0.5, 12.0, 0.5))
+            .with_texture(SOFT_PARTICLE_TEXTURE)
+    }
+
+    /// Returns [`None`] for skills that don't have an effect.
+    pub fn from_skill_id(skill_id: SkillId) -> Option<Self> {
+        use SkillEffectAnchor::{Caster, Target};
+
+        let skill_effect = match skill_id.0 {
+            // SM_BASH
+            5 => Self::new(Target, Color::rgb_u8(255, 200, 150), 10.0).with_emitter(Self::burst(
+                Color::rgb_u8(255, 230, 180),
+                Color::rgba_u8(255, 120, 40, 0),
+                20.0,
+            )),
+            // SM_MAGNUM
+            7 => Self::new(Caster, Color::rgb_u8(255, 80, 20), 40.0)
+                .with_emitter(Self::burst(Color::rgb_u8(255, 170, 40), Color::rgba_u8(255, 30, 0, 0), 40.0))
+                .with_sound_effect("effect\\ef_magnumbreak.wav"),
+            // MG_SOULSTRIKE
+            13 => Self::new(Target, Color::rgb_u8(200, 160, 255), 15.0).with_emitter(Self::burst(
+                Color::rgb_u8(230, 210, 255),
+                Color::rgba_u8(140, 80, 255, 0),
+                20.0,
+            )),
+            // MG_COLDBOLT
+            14 => Self::new(Target, Color::rgb_u8(120, 180, 255), 20.0)
+                .with_emitter(Self::burst(Color::rgb_u8(220, 240, 255), Color::rgba_u8(80, 150, 255, 0), 25.0))
+                .with_sound_effect("effect\\ef_icearrow.wav"),
+            // MG_FIREBOLT
+            19 => Self::new(Target, Color::rgb_u8(255, 100, 20), 20.0)
+                .with_emitter(Self::burst(Color::rgb_u8(255, 190, 60), Color::rgba_u8(255, 40, 0, 0), 25.0))
+                .with_sound_effect("effect\\ef_firearrow.wav"),
+            // MG_LIGHTNINGBOLT
+            20 => Self::new(Target, Color::rgb_u8(255, 255, 160), 25.0)
+                .with_emitter(Self::burst(Color::rgb_u8(255, 255, 220), Color::rgba_u8(255, 240, 80, 0), 30.0))
+                .with_sound_effect("effect\\ef_lightbolt.wav"),
+            // AL_HEAL
+            28 => Self::new(Target, Color::rgb_u8(150, 255, 150), 20.0)
+                .with_emitter(Self::aura(Color::rgba_u8(170, 255, 170, 220)))
+                .with_sound_effect("_heal_effect.wav"),
+            // AL_INCAGI
+            29 => Self::new(Target, Color::rgb_u8(150, 220, 255), 15.0).with_emitter(Self::aura(Color::rgba_u8(170, 230, 255, 220))),
+            // AL_BLESSING
+            34 => Self::new(Target, Color::rgb_u8(255, 240, 170), 15.0).with_emitter(Self::aura(Color::rgba_u8(255, 245, 190, 220))),
+            // WZ_VERMILION
+            85 => Self::new(Target, Color::rgb_u8(255, 255, 200), 40.0).with_effect_file("lord.str"),
+            // WZ_STORMGUST
+            89 => Self::new(Target, Color::rgb_u8(160, 200, 255), 40.0).with_effect_file("stormgust.str"),
+            _ => return None,
+        };
+
+        Some(skill_effect)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::graphics::Color;
+use crate::network::UnitId;
+
+/// Ground effect shown for a skill unit, like a fire wall or a trap. Units
+/// stay on the ground until the map server removes them, so their effects
+/// are always repeated.
+pub struct UnitEffect {
+    /// Effect that is played on the cell of the unit. Units without an effect
+    /// are only marked by their light.
+    pub effect_file: Option<&'static str>,
+    pub light_color: Color,
+    pub light_intensity: f32,
+    pub sound_effect_file: Option<&'static str>,
+}
+
+impl UnitEffect {
+    fn new(effect_file: Option<&'static str>, light_color: Color, light_intensity: f32) -> Self {
+        Self {
+            effect_file,
+            light_color,
+            light_intensity,
+            sound_effect_file: None,
+        }
+    }
+
+    fn with_sound_effect(self, sound_effect_file: &'static str) -> Self {
+        Self {
+            sound_effect_file: Some(sound_effect_file),
+            ..self
+        }
+    }
+
+    /// Returns [`None`] for units that are not visible to the player, like
+    /// hidden traps or dummy units.
+    pub fn from_unit_id(unit_id: &UnitId) -> Option<Self> {
+        let unit_effect = match unit_id {
+            UnitId::Firewall => {
+                Self::new(Some("firewall.str"), Color::rgb_u8(255, 30, 0), 20.0).with_sound_effect("effect\\ef_firewall.wav")
+            }
+            UnitId::Pneuma => Self::new(Some("pneuma1.str"), Color::rgb_u8(83, 220, 108), 40.0),
+            UnitId::Safetywall => Self::new(Some("safetywall.str"), Color::rgb_u8(255, 120, 160), 25.0),
+            UnitId::WarpActive => Self::new(Some("warp.str"), Color::rgb_u8(80, 140, 255), 40.0),
+            UnitId::WarpWaiting => Self::new(None, Color::rgb_u8(80, 140, 255), 15.0),
+            UnitId::Sanctuary => Self::new(Some("sanctuary.str"), Color::rgb_u8(255, 255, 200), 20.0),
+            UnitId::Magnus => Self::new(Some("magnus.str"), Color::rgb_u8(255, 255, 220), 25.0),
+            UnitId::Quagmire => Self::new(Some("quagmire.str"), Color::rgb_u8(120, 90, 40), 10.0),
+            UnitId::Landprotector => Self::new(Some("landprotector.str"), Color::rgb_u8(200, 220, 255), 15.0),
+            UnitId::Volcano => Self::new(Some("volcano.str"), Color::rgb_u8(255, 80, 20), 20.0),
+            UnitId::Deluge => Self::new(Some("deluge.str"), Color::rgb_u8(60, 120, 255), 20.0),
+            UnitId::Violentgale => Self::new(Some("violentgale.str"), Color::rgb_u8(120, 255, 160), 20.0),
+            UnitId::Trap
+            | UnitId::UsedTraps
+            | UnitId::Blastmine
+            | UnitId::Skidtrap
+            | UnitId::Anklesnare
+            | UnitId::Landmine
+            | UnitId::Shockwave
+            | UnitId::Sandman
+            | UnitId::Flasher
+            | UnitId::Freezingtrap
+            | UnitId::Claymoretrap
+            | UnitId::Talkiebox => Self::new(None, Color::rgb_u8(255, 200, 120), 8.0),
+            _ => return None,
+        };
+
+        Some(unit_effect)
+    }
+}
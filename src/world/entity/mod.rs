@@ -1,3 +1,6 @@
+mod names;
+mod visibility;
+
 use std::sync::Arc;
 
 use cgmath::{Array, Vector2, Vector3, VectorSpace};
@@ -5,9 +8,11 @@ use derive_new::new;
 use procedural::{profile, PrototypeElement, PrototypeWindow};
 use vulkano::buffer::Subbuffer;
 
+pub use self::names::EntityNameCache;
+pub use self::visibility::select_visible_entities;
 #[cfg(feature = "debug")]
 use crate::graphics::MarkerRenderer;
-use crate::graphics::{Camera, DeferredRenderer, EntityRenderer, ModelVertex, Renderer};
+use crate::graphics::{Camera, Color, DeferredRenderer, EntityRenderer, ModelVertex, Renderer};
 use crate::interface::{GameTheme, InterfaceSettings, PrototypeWindow, ScreenPosition, ScreenSize, Window, WindowCache};
 use crate::loaders::{ActionLoader, Actions, AnimationState, GameFileLoader, ScriptLoader, Sprite, SpriteLoader};
 use crate::network::{AccountId, CharacterInformation, ClientTick, EntityData, EntityId, Sex, StatusType};
@@ -18,14 +23,13 @@ use crate::world::MarkerIdentifier;
 pub enum ResourceState<T> {
     Available(T),
     Unavailable,
-    Requested,
 }
 
 impl<T> ResourceState<T> {
     pub fn as_option(&self) -> Option<&T> {
         match self {
             ResourceState::Available(value) => Some(value),
-            _requested_or_unavailable => None,
+            ResourceState::Unavailable => None,
         }
     }
 }
@@ -59,6 +63,7 @@ pub struct Common {
     pub movement_speed: usize,
     pub head_direction: usize,
     pub sex: Sex,
+    pub guild_id: Option<u32>,
 
     #[hidden_element]
     pub entity_type: EntityType,
@@ -256,6 +261,65 @@ fn get_sprite_and_actions(
     )
 }
 
+/// Body and head of a player character with a specific hair style and color.
+/// Used to preview new characters.
+#[derive(Clone)]
+pub struct CharacterAppearance {
+    pub body_sprite: Arc<Sprite>,
+    pub body_actions: Arc<Actions>,
+    pub head_sprite: Arc<Sprite>,
+    pub head_actions: Arc<Actions>,
+}
+
+impl CharacterAppearance {
+    pub fn load(
+        game_file_loader: &mut GameFileLoader,
+        sprite_loader: &mut SpriteLoader,
+        action_loader: &mut ActionLoader,
+        script_loader: &ScriptLoader,
+        sex: Sex,
+        hair_style: u16,
+        hair_color: u16,
+    ) -> Self {
+        // NOTE: New characters always start out as novices.
+        let (body_sprite, body_actions) = get_sprite_and_actions(
+            game_file_loader,
+            sprite_loader,
+            action_loader,
+            script_loader,
+            EntityType::Player,
+            0,
+            sex,
+        );
+
+        let sex_sprite_path = match sex == Sex::Female {
+            true => "¿©",
+            false => "³²",
+        };
+
+        let file_path = format!("ÀÎ°£Á·\\¸Ó¸®Åë\\{sex_sprite_path}\\{hair_style}_{sex_sprite_path}");
+
+        // NOTE: Hair color 0 is the color of the sprite itself.
+        let head_sprite = match hair_color {
+            0 => sprite_loader.get(&format!("{file_path}.spr"), game_file_loader),
+            hair_color => sprite_loader.get_with_palette(
+                &format!("{file_path}.spr"),
+                &format!("¸Ó¸®\\¸Ó¸®{hair_style}_{sex_sprite_path}_{hair_color}.pal"),
+                game_file_loader,
+            ),
+        }
+        .unwrap();
+        let head_actions = action_loader.get(&format!("{file_path}.act"), game_file_loader).unwrap();
+
+        Self {
+            body_sprite,
+            body_actions,
+            head_sprite,
+            head_actions,
+        }
+    }
+}
+
 impl Common {
     pub fn new(
         game_file_loader: &mut GameFileLoader,
@@ -276,6 +340,7 @@ impl Common {
         let health_points = entity_data.health_points as usize;
         let maximum_health_points = entity_data.maximum_health_points as usize;
         let sex = entity_data.sex;
+        let guild_id = entity_data.guild_id;
 
         let active_movement = None;
 
@@ -308,6 +373,7 @@ impl Common {
             job_id,
             head_direction,
             sex,
+            guild_id,
             active_movement,
             entity_type,
             movement_speed,
@@ -399,137 +465,96 @@ impl Common {
         self.animation_state.update(client_tick);
     }
 
-    pub fn move_from_to(&mut self, map: &Map, from: Vector2<usize>, to: Vector2<usize>, starting_timestamp: ClientTick) {
-        use pathfinding::prelude::astar;
+    fn generate_steps(&self, path: Vec<Vector2<usize>>, starting_timestamp: u32) -> Vec<(Vector2<usize>, u32)> {
+        let mut last_timestamp = starting_timestamp;
+        let mut last_position: Option<Vector2<usize>> = None;
 
-        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-        struct Pos(usize, usize);
+        path.into_iter()
+            .map(|position| {
+                if let Some(previous_position) = last_position {
+                    const DIAGONAL_MULTIPLIER: f32 = 1.4;
 
-        impl Pos {
-            fn successors(&self, map: &Map) -> Vec<Pos> {
-                let &Pos(x, y) = self;
-                let mut successors = Vec::new();
+                    let speed = match previous_position.x == position.x || previous_position.y == position.y {
+                        // true means we are moving orthogonally
+                        true => self.movement_speed as u32,
+                        // false means we are moving diagonally
+                        false => (self.movement_speed as f32 * DIAGONAL_MULTIPLIER) as u32,
+                    };
 
-                if map.x_in_bounds(x + 1) {
-                    successors.push(Pos(x + 1, y));
-                }
+                    let arrival_position = position;
+                    let arrival_timestamp = last_timestamp + speed;
 
-                if x > 0 {
-                    successors.push(Pos(x - 1, y));
-                }
-
-                if map.y_in_bounds(y + 1) {
-                    successors.push(Pos(x, y + 1));
-                }
-
-                if y > 0 {
-                    successors.push(Pos(x, y - 1));
-                }
-
-                if map.x_in_bounds(x + 1)
-                    && map.y_in_bounds(y + 1)
-                    && map.get_tile(Vector2::new(x + 1, y)).is_walkable()
-                    && map.get_tile(Vector2::new(x, y + 1)).is_walkable()
-                {
-                    successors.push(Pos(x + 1, y + 1));
-                }
+                    last_timestamp = arrival_timestamp;
+                    last_position = Some(arrival_position);
 
-                if x > 0
-                    && map.y_in_bounds(y + 1)
-                    && map.get_tile(Vector2::new(x - 1, y)).is_walkable()
-                    && map.get_tile(Vector2::new(x, y + 1)).is_walkable()
-                {
-                    successors.push(Pos(x - 1, y + 1));
+                    (arrival_position, arrival_timestamp)
+                } else {
+                    last_position = Some(position);
+                    (position, last_timestamp)
                 }
+            })
+            .collect()
+    }
 
-                if map.x_in_bounds(x + 1)
-                    && y > 0
-                    && map.get_tile(Vector2::new(x + 1, y)).is_walkable()
-                    && map.get_tile(Vector2::new(x, y - 1)).is_walkable()
-                {
-                    successors.push(Pos(x + 1, y - 1));
-                }
+    fn start_movement(&mut self, steps: Vec<(Vector2<usize>, u32)>, starting_timestamp: ClientTick) {
+        self.active_movement = Movement::new(steps, starting_timestamp.0).into();
 
-                if x > 0
-                    && y > 0
-                    && map.get_tile(Vector2::new(x - 1, y)).is_walkable()
-                    && map.get_tile(Vector2::new(x, y - 1)).is_walkable()
-                {
-                    successors.push(Pos(x - 1, y - 1));
-                }
-
-                let successors = successors
-                    .drain(..)
-                    .filter(|Pos(x, y)| map.get_tile(Vector2::new(*x, *y)).is_walkable())
-                    .collect::<Vec<Pos>>();
+        if self.animation_state.action != 1 {
+            self.animation_state.walk(self.movement_speed, starting_timestamp);
+        }
+    }
 
-                successors
-            }
+    pub fn move_from_to(&mut self, map: &Map, from: Vector2<usize>, to: Vector2<usize>, starting_timestamp: ClientTick) {
+        if let Some(path) = map.find_path(from, to) {
+            let steps = self.generate_steps(path, starting_timestamp.0);
 
-            fn convert_to_vector(self) -> Vector2<usize> {
-                Vector2::new(self.0, self.1)
+            // If there is only a single step the player is already on the correct tile.
+            if steps.len() > 1 {
+                self.start_movement(steps, starting_timestamp);
             }
         }
+    }
 
-        let result = astar(
-            &Pos(from.x, from.y),
-            |position| position.successors(map).into_iter().map(|position| (position, 0)),
-            |position| -> usize {
-                // Values taken from rAthena.
-                const MOVE_COST: usize = 10;
-                const DIAGONAL_MOVE_COST: usize = 14;
-
-                let distance_x = usize::abs_diff(position.0, to.x);
-                let distance_y = usize::abs_diff(position.1, to.y);
-
-                let straight_moves = usize::abs_diff(distance_x, distance_y);
-                let diagonal_moves = usize::min(distance_x, distance_y);
+    /// Start walking towards the destination without waiting for the server.
+    /// The predicted movement is reconciled in [`Self::confirm_move`] once
+    /// the server sends the actual path.
+    pub fn predict_move_to(&mut self, map: &Map, destination: Vector2<usize>, client_tick: ClientTick) {
+        // NOTE: If the entity is currently walking, we keep the step it is on and
+        // continue from the tile it is walking towards, so the movement stays
+        // smooth.
+        let current_step = self.active_movement.as_ref().and_then(|movement| {
+            let next_index = movement
+                .steps
+                .iter()
+                .position(|(_, arrival_timestamp)| *arrival_timestamp > client_tick.0)?;
+
+            (next_index > 0).then(|| (movement.steps[next_index - 1], movement.steps[next_index]))
+        });
+
+        let Some((previous_step, next_step)) = current_step else {
+            self.move_from_to(map, self.grid_position, destination, client_tick);
+            return;
+        };
 
-                DIAGONAL_MOVE_COST * diagonal_moves + MOVE_COST * straight_moves
-            },
-            |position| *position == Pos(to.x, to.y),
-        )
-        .map(|x| x.0);
-
-        if let Some(path) = result {
-            let mut last_timestamp = starting_timestamp.0;
-            let mut last_position: Option<Vector2<usize>> = None;
-
-            let steps: Vec<(Vector2<usize>, u32)> = path
-                .into_iter()
-                .map(|pos| {
-                    if let Some(position) = last_position {
-                        const DIAGONAL_MULTIPLIER: f32 = 1.4;
-
-                        let speed = match position.x == pos.0 || position.y == pos.1 {
-                            // true means we are moving orthogonally
-                            true => self.movement_speed as u32,
-                            // false means we are moving diagonally
-                            false => (self.movement_speed as f32 * DIAGONAL_MULTIPLIER) as u32,
-                        };
-
-                        let arrival_position = pos.convert_to_vector();
-                        let arrival_timestamp = last_timestamp + speed;
-
-                        last_timestamp = arrival_timestamp;
-                        last_position = Some(arrival_position);
-
-                        (arrival_position, arrival_timestamp)
-                    } else {
-                        last_position = Some(from);
-                        (from, last_timestamp)
-                    }
-                })
-                .collect();
+        if let Some(path) = map.find_path(next_step.0, destination) {
+            let mut steps = vec![previous_step];
+            steps.extend(self.generate_steps(path, next_step.1));
 
-            // If there is only a single step the player is already on the correct tile.
-            if steps.len() > 1 {
-                self.active_movement = Movement::new(steps, starting_timestamp.0).into();
+            self.start_movement(steps, ClientTick(previous_step.1));
+        }
+    }
 
-                if self.animation_state.action != 1 {
-                    self.animation_state.walk(self.movement_speed, starting_timestamp);
-                }
-            }
+    /// Apply a movement sent by the server. If the predicted movement already
+    /// leads to the same destination through the same tile, it is kept to
+    /// avoid visible corrections.
+    pub fn confirm_move(&mut self, map: &Map, from: Vector2<usize>, to: Vector2<usize>, starting_timestamp: ClientTick) {
+        let matches_prediction = self.active_movement.as_ref().is_some_and(|movement| {
+            movement.steps.last().is_some_and(|(position, _)| *position == to)
+                && movement.steps.iter().any(|(position, _)| *position == from)
+        });
+
+        if !matches_prediction {
+            self.move_from_to(map, from, to, starting_timestamp);
         }
     }
 
@@ -713,7 +738,7 @@ impl Common {
         active_movement.steps_vertex_buffer = Some(vertex_buffer);
     }*/
 
-    pub fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera)
+    pub fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, color: Color)
     where
         T: Renderer + EntityRenderer,
     {
@@ -733,6 +758,7 @@ impl Common {
             Vector2::new(0, 0),
             mirror,
             self.entity_id,
+            color,
         );
     }
 
@@ -758,6 +784,8 @@ pub struct Player {
     pub activity_points: usize,
     pub maximum_spell_points: usize,
     pub maximum_activity_points: usize,
+    /// Set when the player didn't give any input for a while.
+    pub is_away: bool,
 }
 
 impl Player {
@@ -792,6 +820,7 @@ impl Player {
             activity_points,
             maximum_spell_points,
             maximum_activity_points,
+            is_away: false,
         }
     }
 
@@ -897,6 +926,23 @@ impl Player {
             self.maximum_activity_points as f32,
             self.activity_points as f32,
         );
+
+        if self.is_away {
+            let font_size = theme.status_bar.away_font_size.get();
+            // NOTE: Characters of the font are roughly half as wide as they are high.
+            let text_position = ScreenPosition {
+                left: background_position.left + background_size.width / 2.0 - font_size * 0.75,
+                top: background_position.top - font_size - gap,
+            };
+
+            renderer.render_text(
+                render_target,
+                "AFK",
+                text_position,
+                theme.status_bar.away_color.get(),
+                font_size,
+            );
+        }
     }
 }
 
@@ -1016,11 +1062,8 @@ impl Entity {
         self.get_common().entity_type
     }
 
-    pub fn are_details_unavailable(&self) -> bool {
-        match &self.get_common().details {
-            ResourceState::Unavailable => true,
-            _requested_or_available => false,
-        }
+    pub fn get_maximum_health_points(&self) -> usize {
+        self.get_common().maximum_health_points
     }
 
     pub fn set_job(&mut self, job_id: usize) {
@@ -1038,10 +1081,6 @@ impl Entity {
             .reload_sprite(game_file_loader, sprite_loader, action_loader, script_loader);
     }
 
-    pub fn set_details_requested(&mut self) {
-        self.get_common_mut().details = ResourceState::Requested;
-    }
-
     pub fn set_details(&mut self, details: String) {
         self.get_common_mut().details = ResourceState::Available(details);
     }
@@ -1050,6 +1089,10 @@ impl Entity {
         self.get_common().details.as_option()
     }
 
+    pub fn get_guild_id(&self) -> Option<u32> {
+        self.get_common().guild_id
+    }
+
     pub fn get_grid_position(&self) -> Vector2<usize> {
         self.get_common().grid_position
     }
@@ -1076,16 +1119,24 @@ impl Entity {
         self.get_common_mut().move_from_to(map, from, to, starting_timestamp);
     }
 
+    pub fn predict_move_to(&mut self, map: &Map, destination: Vector2<usize>, client_tick: ClientTick) {
+        self.get_common_mut().predict_move_to(map, destination, client_tick);
+    }
+
+    pub fn confirm_move(&mut self, map: &Map, from: Vector2<usize>, to: Vector2<usize>, starting_timestamp: ClientTick) {
+        self.get_common_mut().confirm_move(map, from, to, starting_timestamp);
+    }
+
     /*#[cfg(feature = "debug")]
     pub fn generate_steps_vertex_buffer(&mut self, device: Arc<Device>, map: &Map) {
         self.get_common_mut().generate_steps_vertex_buffer(device, map);
     }*/
 
-    pub fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera)
+    pub fn render<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, color: Color)
     where
         T: Renderer + EntityRenderer,
     {
-        self.get_common().render(render_target, renderer, camera);
+        self.get_common().render(render_target, renderer, camera, color);
     }
 
     #[cfg(feature = "debug")]
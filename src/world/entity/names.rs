@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::network::{ClientTick, EntityId};
+
+/// Time in milliseconds after which an unanswered request may be sent again.
+const REQUEST_TIMEOUT: u32 = 3000;
+/// Maximum number of requests sent per frame, so starting an entity search on a
+/// crowded map doesn't flood the map server.
+const MAXIMUM_REQUESTS_PER_FRAME: usize = 16;
+
+/// Names of entities that were received from the map server. Entities leaving
+/// and re-entering the screen keep their entity id, so their name only needs
+/// to be requested once.
+#[derive(Default)]
+pub struct EntityNameCache {
+    names: HashMap<EntityId, String>,
+    /// Client tick at which the request for the entity was sent.
+    requested: HashMap<EntityId, ClientTick>,
+    queued: Vec<EntityId>,
+}
+
+impl EntityNameCache {
+    pub fn get(&self, entity_id: EntityId) -> Option<&String> {
+        self.names.get(&entity_id)
+    }
+
+    /// Queue a request for the name of an entity, unless the name is already
+    /// known or a request is pending. Calling this every frame is fine.
+    pub fn request(&mut self, entity_id: EntityId) {
+        if self.names.contains_key(&entity_id) || self.requested.contains_key(&entity_id) || self.queued.contains(&entity_id) {
+            return;
+        }
+
+        self.queued.push(entity_id);
+    }
+
+    /// Take the requests that should be sent this frame. Requests that didn't
+    /// get a response in time are forgotten, so they can be queued again.
+    pub fn take_requests(&mut self, client_tick: ClientTick) -> Vec<EntityId> {
+        self.requested
+            .retain(|_, request_tick| client_tick.0.wrapping_sub(request_tick.0) < REQUEST_TIMEOUT);
+
+        let count = self.queued.len().min(MAXIMUM_REQUESTS_PER_FRAME);
+        let requests: Vec<EntityId> = self.queued.drain(..count).collect();
+
+        requests.iter().for_each(|entity_id| {
+            self.requested.insert(*entity_id, client_tick);
+        });
+
+        requests
+    }
+
+    pub fn insert(&mut self, entity_id: EntityId, name: String) {
+        self.requested.remove(&entity_id);
+        self.names.insert(entity_id, name);
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.requested.clear();
+        self.queued.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EntityNameCache, MAXIMUM_REQUESTS_PER_FRAME, REQUEST_TIMEOUT};
+    use crate::network::{ClientTick, EntityId};
+
+    #[test]
+    fn requests_are_deduplicated_and_batched() {
+        let mut name_cache = EntityNameCache::default();
+
+        (0..MAXIMUM_REQUESTS_PER_FRAME as u32 + 2).for_each(|id| name_cache.request(EntityId(id)));
+        name_cache.request(EntityId(0));
+
+        assert_eq!(name_cache.take_requests(ClientTick(0)).len(), MAXIMUM_REQUESTS_PER_FRAME);
+        assert_eq!(name_cache.take_requests(ClientTick(10)).len(), 2);
+
+        name_cache.request(EntityId(0));
+        name_cache.insert(EntityId(1), "Poring".to_owned());
+        name_cache.request(EntityId(1));
+        assert!(name_cache.take_requests(ClientTick(20)).is_empty());
+        assert_eq!(name_cache.get(EntityId(1)).map(String::as_str), Some("Poring"));
+
+        // The request for the first entity was sent at tick 0 and expires here.
+        name_cache.request(EntityId(0));
+        assert!(name_cache.take_requests(ClientTick(REQUEST_TIMEOUT)).is_empty());
+
+        name_cache.request(EntityId(0));
+        assert_eq!(name_cache.take_requests(ClientTick(REQUEST_TIMEOUT + 10)), vec![EntityId(0)]);
+    }
+}
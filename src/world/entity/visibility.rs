@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use super::Entity;
+use crate::network::{AccountId, EntityId};
+
+/// Select the entities that should be rendered this frame. The player is
+/// always rendered, followed by the targeted entity, the party members and
+/// then the closest entities until `maximum_entities` is reached. Entities
+/// that are more than `render_distance` tiles away from the player are never
+/// rendered.
+pub fn select_visible_entities<'a>(
+    entities: &'a [Entity],
+    render_distance: Option<usize>,
+    maximum_entities: Option<usize>,
+    target_entity_id: Option<EntityId>,
+    party_members: &HashSet<AccountId>,
+) -> Vec<&'a Entity> {
+    let Some((player, other_entities)) = entities.split_first() else {
+        return Vec::new();
+    };
+
+    let player_position = player.get_grid_position();
+    let mut candidates: Vec<(usize, usize, &Entity)> = other_entities
+        .iter()
+        .map(|entity| {
+            let position = entity.get_grid_position();
+            let distance = position.x.abs_diff(player_position.x).max(position.y.abs_diff(player_position.y));
+            let entity_id = entity.get_entity_id();
+
+            // NOTE: The entity id of a player is their account id.
+            let priority = match target_entity_id == Some(entity_id) {
+                true => 0,
+                false if party_members.contains(&AccountId(entity_id.0)) => 1,
+                false => 2,
+            };
+
+            (priority, distance, entity)
+        })
+        .filter(|(_, distance, _)| render_distance.map_or(true, |render_distance| *distance <= render_distance))
+        .collect();
+
+    if let Some(maximum_entities) = maximum_entities {
+        candidates.sort_by_key(|(priority, distance, _)| (*priority, *distance));
+        candidates.truncate(maximum_entities.saturating_sub(1));
+    }
+
+    std::iter::once(player)
+        .chain(candidates.into_iter().map(|(_, _, entity)| entity))
+        .collect()
+}
@@ -3,6 +3,7 @@ use procedural::{PrototypeElement, PrototypeWindow};
 use ragnarok_procedural::ByteConvertable;
 
 use crate::graphics::*;
+use crate::interface::TrackedState;
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
@@ -12,22 +13,36 @@ pub struct LightSource {
     #[length_hint(80)]
     pub name: String,
     pub position: Vector3<f32>,
-    pub color: ColorRGB,
-    pub range: f32,
+    #[mutable(ColorRGB::monochrome(0.0), ColorRGB::monochrome(1.0))]
+    pub color: TrackedState<ColorRGB>,
+    #[mutable(0.0, 500.0)]
+    pub range: TrackedState<f32>,
 }
 
+// Needed so that the map can be rendered in another thread. The color and range
+// are only read by the deferred thread while rendering, the light source window
+// only accesses them on the main thread outside of the render scope.
+unsafe impl Send for LightSource {}
+unsafe impl Sync for LightSource {}
+
 impl LightSource {
     pub fn offset(&mut self, offset: Vector3<f32>) {
         self.position += offset;
     }
 
+    /// Distance at which the light no longer has a visible effect. This
+    /// matches the size of the area that the point light renderer draws.
+    pub fn effective_range(&self) -> f32 {
+        (10.0 * (self.range.get() / 0.05).ln()).max(0.0)
+    }
+
     pub fn render_light(
         &self,
         render_target: &mut <DeferredRenderer as Renderer>::Target,
         renderer: &DeferredRenderer,
         camera: &dyn Camera,
     ) {
-        renderer.point_light(render_target, camera, self.position, self.color.to_owned().into(), self.range);
+        renderer.point_light(render_target, camera, self.position, self.color.get().into(), self.range.get());
     }
 
     #[cfg(feature = "debug")]
@@ -0,0 +1,237 @@
+use cgmath::{Vector2, Vector3};
+use procedural::profile;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use crate::debug::*;
+use crate::graphics::{Camera, Color, DeferredRenderer, Renderer};
+use crate::interface::{ScreenPosition, ScreenSize};
+use crate::network::{EntityId, ItemId, ItemRarity};
+use crate::world::Map;
+
+const FILTER_FILENAME: &str = "client/loot_filter.ron";
+const LABEL_FONT_SIZE: f32 = 12.0;
+const TOAST_FONT_SIZE: f32 = 16.0;
+
+/// Items that should stand out when they are dropped.
+#[derive(Serialize, Deserialize)]
+pub struct LootFilter {
+    /// Items that always match the filter.
+    pub item_ids: Vec<u32>,
+    /// Items with at least this rarity match the filter.
+    pub minimum_rarity: Option<ItemRarity>,
+    /// Sound effect played when a matching item is dropped.
+    pub sound_effect: Option<String>,
+    /// Show a notification when a matching item is dropped.
+    pub show_toast: bool,
+}
+
+impl Default for LootFilter {
+    fn default() -> Self {
+        Self {
+            item_ids: Vec::new(),
+            minimum_rarity: Some(ItemRarity::Blue),
+            sound_effect: Some("_heal_effect.wav".to_string()),
+            show_toast: true,
+        }
+    }
+}
+
+impl LootFilter {
+    pub fn new() -> Self {
+        #[cfg(feature = "debug")]
+        print_debug!("loading loot filter from {}{FILTER_FILENAME}{}", MAGENTA, NONE);
+
+        std::fs::read_to_string(FILTER_FILENAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_else(|| {
+                #[cfg(feature = "debug")]
+                print_debug!("failed to load loot filter from {}{FILTER_FILENAME}{}", MAGENTA, NONE);
+
+                Default::default()
+            })
+    }
+
+    pub fn matches(&self, item_id: ItemId, rarity: ItemRarity) -> bool {
+        self.item_ids.contains(&item_id.0) || self.minimum_rarity.is_some_and(|minimum_rarity| rarity >= minimum_rarity)
+    }
+}
+
+struct GroundItem {
+    entity_id: EntityId,
+    label: String,
+    position: Vector2<usize>,
+    highlighted: bool,
+}
+
+struct Toast {
+    text: String,
+    timer: f32,
+}
+
+impl Toast {
+    const DURATION: f32 = 4.0;
+    const FADE_DURATION: f32 = 1.0;
+
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.timer -= delta_time;
+        self.timer > 0.0
+    }
+
+    fn get_color(&self, color: Color) -> Color {
+        color.multiply_alpha((self.timer / Self::FADE_DURATION).min(1.0))
+    }
+}
+
+/// Items lying on the ground and notifications about valuable drops.
+#[derive(Default)]
+pub struct GroundItemHolder {
+    items: Vec<GroundItem>,
+    toasts: Vec<Toast>,
+}
+
+impl GroundItemHolder {
+    pub fn add_item(&mut self, entity_id: EntityId, name: String, amount: u16, position: Vector2<usize>, highlighted: bool) {
+        let label = match amount > 1 {
+            true => format!("{name} x{amount}"),
+            false => name,
+        };
+
+        self.items.retain(|item| item.entity_id != entity_id);
+        self.items.push(GroundItem {
+            entity_id,
+            label,
+            position,
+            highlighted,
+        });
+    }
+
+    pub fn remove_item(&mut self, entity_id: EntityId) {
+        self.items.retain(|item| item.entity_id != entity_id);
+    }
+
+    pub fn items_at(&self, position: Vector2<usize>) -> impl Iterator<Item = EntityId> + '_ {
+        self.items
+            .iter()
+            .filter(move |item| item.position == position)
+            .map(|item| item.entity_id)
+    }
+
+    pub fn get_label(&self, entity_id: EntityId) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|item| item.entity_id == entity_id)
+            .map(|item| item.label.as_str())
+    }
+
+    pub fn add_toast(&mut self, text: String) {
+        self.toasts.push(Toast {
+            text,
+            timer: Toast::DURATION,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.toasts.clear();
+    }
+
+    #[profile("update ground items")]
+    pub fn update(&mut self, delta_time: f32) {
+        self.toasts.retain_mut(|toast| toast.update(delta_time));
+    }
+
+    fn render_outlined_text(
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        text: &str,
+        position: ScreenPosition,
+        color: Color,
+        font_size: f32,
+    ) {
+        renderer.render_text(
+            render_target,
+            text,
+            position + ScreenPosition::uniform(1.0),
+            Color::monochrome_u8(0).multiply_alpha(color.alpha),
+            font_size,
+        );
+        renderer.render_text(render_target, text, position, color, font_size);
+    }
+
+    #[profile("render ground item labels")]
+    pub fn render_labels(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        map: &Map,
+        highlight_color: Color,
+    ) {
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+
+        // NOTE: Render highlighted labels last so they are drawn on top.
+        let items = self
+            .items
+            .iter()
+            .filter(|item| !item.highlighted)
+            .chain(self.items.iter().filter(|item| item.highlighted))
+            .filter(|item| map.x_in_bounds(item.position.x) && map.y_in_bounds(item.position.y));
+
+        for item in items {
+            let position = map.get_world_position(item.position) + Vector3::new(0.0, 2.0, 0.0);
+            let clip_space_position = (projection_matrix * view_matrix) * position.extend(1.0);
+
+            // NOTE: Skip items behind the camera.
+            if clip_space_position.w <= 0.0 {
+                continue;
+            }
+
+            let screen_position = Vector2::new(
+                clip_space_position.x / clip_space_position.w + 1.0,
+                clip_space_position.y / clip_space_position.w + 1.0,
+            ) / 2.0;
+            let final_position = ScreenPosition {
+                left: screen_position.x * window_size.width - item.label.len() as f32 * LABEL_FONT_SIZE / 4.0,
+                top: screen_position.y * window_size.height - LABEL_FONT_SIZE,
+            };
+
+            let color = match item.highlighted {
+                true => highlight_color,
+                false => Color::monochrome_u8(230),
+            };
+
+            Self::render_outlined_text(render_target, renderer, &item.label, final_position, color, LABEL_FONT_SIZE);
+        }
+    }
+
+    #[profile("render loot toasts")]
+    pub fn render_toasts(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        window_size: ScreenSize,
+        highlight_color: Color,
+    ) {
+        const TOP_OFFSET: f32 = 60.0;
+        const SPACING: f32 = 4.0;
+
+        self.toasts.iter().rev().enumerate().for_each(|(index, toast)| {
+            let position = ScreenPosition {
+                left: window_size.width / 2.0 - toast.text.len() as f32 * TOAST_FONT_SIZE / 4.0,
+                top: TOP_OFFSET + index as f32 * (TOAST_FONT_SIZE + SPACING),
+            };
+
+            Self::render_outlined_text(
+                render_target,
+                renderer,
+                &toast.text,
+                position,
+                toast.get_color(highlight_color),
+                TOAST_FONT_SIZE,
+            );
+        });
+    }
+}
@@ -12,6 +12,7 @@ use vulkano::buffer::Subbuffer;
 use vulkano::image::view::ImageView;
 
 pub use self::tile::Tile;
+use crate::audio::AudioEngine;
 #[cfg(feature = "debug")]
 use crate::debug::*;
 use crate::graphics::*;
@@ -19,10 +20,35 @@ use crate::graphics::*;
 use crate::interface::PrototypeWindow;
 #[cfg(feature = "debug")]
 use crate::loaders::MapData;
-use crate::loaders::{LightSettings, WaterSettings};
+use crate::loaders::{FogSettings, GameFileLoader, LightSettings, TextureLoader, WaterSettings};
 use crate::network::ClientTick;
 use crate::world::*;
 
+/// Objects to render in a pass. The shadow map of static objects is cached,
+/// so animated objects are rendered separately.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ObjectSelection {
+    All,
+    Static,
+    Animated,
+}
+
+impl ObjectSelection {
+    fn includes(self, object: &Object) -> bool {
+        match self {
+            ObjectSelection::All => true,
+            ObjectSelection::Static => !object.model.is_animated(),
+            ObjectSelection::Animated => object.model.is_animated(),
+        }
+    }
+}
+
+/// Maximum number of point lights of the map that are rendered each frame.
+/// If more lights are visible, the ones closest to the camera are preferred.
+const MAXIMUM_POINT_LIGHTS: usize = 64;
+/// Lights that don't reach within this distance of the camera are skipped.
+const MAXIMUM_LIGHT_DISTANCE: f32 = 1000.0;
+
 // MOVE
 fn get_value(day_timer: f32, offset: f32, p: f32) -> f32 {
     let sin = (day_timer + offset).sin();
@@ -78,6 +104,21 @@ pub fn get_light_direction(day_timer: f32) -> Vector3<f32> {
     }
 }
 
+/// Axis aligned bounding box of an object in world space.
+fn world_bounding_box(object: &Object) -> BoundingBox {
+    let oriented_bounding_box = OrientedBox::default().transform(object.get_bounding_box_matrix());
+    BoundingBox::new(oriented_bounding_box.corners)
+}
+
+fn is_culled(frustum: &Frustum<f32>, bounding_box: &BoundingBox) -> bool {
+    let collision_bounding_box = Aabb3 {
+        min: Point3::from_vec(bounding_box.smallest),
+        max: Point3::from_vec(bounding_box.biggest),
+    };
+
+    matches!(frustum.contains(&collision_bounding_box), Relation::Out)
+}
+
 #[cfg(feature = "debug")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum MarkerIdentifier {
@@ -100,6 +141,8 @@ pub struct Map {
     height: usize,
     water_settings: Option<WaterSettings>,
     light_settings: LightSettings,
+    indoor_lighting: bool,
+    fog_settings: Option<FogSettings>,
     tiles: Vec<Tile>,
     ground_vertex_buffer: Subbuffer<[ModelVertex]>,
     water_vertex_buffer: Option<Subbuffer<[WaterVertex]>>,
@@ -123,6 +166,14 @@ impl Map {
         y <= self.height
     }
 
+    /// Indoor maps are always lit as if it was noon.
+    pub fn lighting_day_timer(&self, day_timer: f32) -> f32 {
+        match self.indoor_lighting {
+            true => std::f32::consts::FRAC_PI_2,
+            false => day_timer,
+        }
+    }
+
     pub fn get_world_position(&self, position: Vector2<usize>) -> Vector3<f32> {
         let height = self.get_tile(position).average_height();
         Vector3::new(position.x as f32 * 5.0 + 2.5, height, position.y as f32 * 5.0 + 2.5)
@@ -133,6 +184,17 @@ impl Map {
         &self.tiles[position.x + position.y * self.width]
     }
 
+    /// Find a walkable path between two tiles. The returned path includes both
+    /// the starting and the destination tile.
+    #[profile]
+    pub fn find_path(&self, from: Vector2<usize>, to: Vector2<usize>) -> Option<Vec<Vector2<usize>>> {
+        find_path(
+            |position| position.x < self.width && position.y < self.height && self.get_tile(position).is_walkable(),
+            from,
+            to,
+        )
+    }
+
     #[profile]
     pub fn render_ground<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, time: f32)
     where
@@ -143,6 +205,7 @@ impl Map {
             camera,
             self.ground_vertex_buffer.clone(),
             &self.ground_textures,
+            SamplerCategory::Ground,
             Matrix4::identity(),
             time,
         );
@@ -156,15 +219,21 @@ impl Map {
         camera: &dyn Camera,
         client_tick: ClientTick,
         time: f32,
+        selection: ObjectSelection,
         #[cfg(feature = "debug")] frustum_culling: bool,
     ) where
         T: Renderer + GeometryRenderer,
     {
         let (view_matrix, projection_matrix) = camera.view_projection_matrices();
         let frustum = Frustum::from_matrix4(projection_matrix * view_matrix).unwrap();
-        let standard_box = OrientedBox::default();
 
-        for object in &self.objects {
+        // NOTE: Transparent objects are rendered in a separate pass after the lighting
+        // and don't cast shadows.
+        for object in self
+            .objects
+            .iter()
+            .filter(|object| !object.model.is_transparent() && selection.includes(object))
+        {
             #[cfg(feature = "debug")]
             if !frustum_culling {
                 object.render_geometry(render_target, renderer, camera, client_tick, time);
@@ -174,14 +243,7 @@ impl Map {
             #[cfg(feature = "debug")]
             let culling_measurement = start_measurement("frustum culling");
 
-            let bounding_box_matrix = object.get_bounding_box_matrix();
-            let oriented_bounding_box = standard_box.transform(bounding_box_matrix);
-            let bounding_box = BoundingBox::new(oriented_bounding_box.corners);
-            let collision_bounding_box = Aabb3 {
-                min: Point3::from_vec(bounding_box.smallest),
-                max: Point3::from_vec(bounding_box.biggest),
-            };
-            let culled = matches!(frustum.contains(&collision_bounding_box), Relation::Out);
+            let culled = is_culled(&frustum, &world_bounding_box(object));
 
             #[cfg(feature = "debug")]
             culling_measurement.stop();
@@ -192,21 +254,90 @@ impl Map {
         }
     }
 
+    /// Render objects with transparent models over the lit scene. The objects
+    /// don't write any depth, so they are sorted back to front to blend
+    /// correctly with each other.
+    #[profile]
+    pub fn render_transparent_objects(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        client_tick: ClientTick,
+        time: f32,
+        day_timer: f32,
+        #[cfg(feature = "debug")] frustum_culling: bool,
+    ) {
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let frustum = Frustum::from_matrix4(projection_matrix * view_matrix).unwrap();
+
+        let mut visible_objects: Vec<(f32, &Object)> = self
+            .objects
+            .iter()
+            .filter(|object| object.model.is_transparent())
+            .filter_map(|object| {
+                let bounding_box = world_bounding_box(object);
+
+                #[cfg(feature = "debug")]
+                let culled = frustum_culling && is_culled(&frustum, &bounding_box);
+
+                #[cfg(not(feature = "debug"))]
+                let culled = is_culled(&frustum, &bounding_box);
+
+                (!culled).then(|| (camera.distance_to(bounding_box.center()), object))
+            })
+            .collect();
+
+        if visible_objects.is_empty() {
+            return;
+        }
+
+        visible_objects.sort_by(|(distance, _), (other_distance, _)| other_distance.total_cmp(distance));
+
+        let (light_color, intensity) = get_directional_light_color_intensity(
+            self.light_settings.diffuse_color.to_owned().unwrap().into(),
+            self.light_settings.light_intensity.unwrap(),
+            day_timer,
+        );
+        let lighting = TransparentLighting {
+            ambient_color: get_ambient_light_color(self.light_settings.ambient_color.to_owned().unwrap().into(), day_timer),
+            light_direction: get_light_direction(day_timer),
+            light_color: Color::rgb(
+                light_color.red * intensity,
+                light_color.green * intensity,
+                light_color.blue * intensity,
+            ),
+        };
+
+        visible_objects
+            .into_iter()
+            .for_each(|(_, object)| object.render_transparent_geometry(render_target, renderer, camera, client_tick, &lighting, time));
+    }
+
     #[profile]
     pub fn render_entities<T>(
         &self,
-        entities: &[Entity],
+        entities: &[&Entity],
         render_target: &mut T::Target,
         renderer: &T,
         camera: &dyn Camera,
         include_self: bool,
+        entity_filter: Option<&EntityFilter>,
     ) where
         T: Renderer + EntityRenderer,
     {
         entities
             .iter()
+            .enumerate()
             .skip(!include_self as usize)
-            .for_each(|entity| entity.render(render_target, renderer, camera));
+            .for_each(|(index, entity)| {
+                // NOTE: The player is never dimmed.
+                let color = entity_filter
+                    .filter(|_| index != 0)
+                    .map_or(Color::monochrome_u8(255), |entity_filter| entity_filter.get_color(entity));
+
+                entity.render(render_target, renderer, camera, color);
+            });
     }
 
     #[cfg(feature = "debug")]
@@ -279,6 +410,21 @@ impl Map {
         }
     }
 
+    #[profile]
+    pub fn render_path_indicator<T>(
+        &self,
+        render_target: &mut <T>::Target,
+        renderer: &T,
+        camera: &dyn Camera,
+        color: Color,
+        path: &[Vector2<usize>],
+    ) where
+        T: Renderer + IndicatorRenderer,
+    {
+        path.iter()
+            .for_each(|position| self.render_walk_indicator(render_target, renderer, camera, color, *position));
+    }
+
     #[profile]
     pub fn render_water(
         &self,
@@ -305,6 +451,7 @@ impl Map {
         renderer: &DeferredRenderer,
         camera: &dyn Camera,
         light_image: Arc<ImageView>,
+        entity_light_image: Arc<ImageView>,
         light_matrix: Matrix4<f32>,
         day_timer: f32,
     ) {
@@ -319,6 +466,7 @@ impl Map {
             render_target,
             camera,
             light_image,
+            entity_light_image,
             light_matrix,
             light_direction,
             directional_color,
@@ -333,9 +481,41 @@ impl Map {
         renderer: &DeferredRenderer,
         camera: &dyn Camera,
     ) {
-        self.light_sources
+        #[cfg(feature = "debug")]
+        let culling_measurement = start_measurement("light culling");
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let frustum = Frustum::from_matrix4(projection_matrix * view_matrix).unwrap();
+
+        let mut visible_lights: Vec<(f32, &LightSource)> = self
+            .light_sources
             .iter()
-            .for_each(|light_source| light_source.render_light(render_target, renderer, camera));
+            .filter_map(|light_source| {
+                let range = light_source.effective_range();
+                let extent = Vector3::from_value(range);
+                let bounding_box = Aabb3 {
+                    min: Point3::from_vec(light_source.position - extent),
+                    max: Point3::from_vec(light_source.position + extent),
+                };
+
+                if matches!(frustum.contains(&bounding_box), Relation::Out) {
+                    return None;
+                }
+
+                let distance = camera.distance_to(light_source.position) - range;
+                (distance < MAXIMUM_LIGHT_DISTANCE).then_some((distance, light_source))
+            })
+            .collect();
+
+        visible_lights.sort_unstable_by(|(first, _), (second, _)| first.total_cmp(second));
+
+        #[cfg(feature = "debug")]
+        culling_measurement.stop();
+
+        visible_lights
+            .into_iter()
+            .take(MAXIMUM_POINT_LIGHTS)
+            .for_each(|(_, light_source)| light_source.render_light(render_target, renderer, camera));
     }
 
     #[profile]
@@ -354,6 +534,37 @@ impl Map {
         renderer.water_light(render_target, camera, water_level);
     }
 
+    #[profile]
+    pub fn fog(&self, render_target: &mut <DeferredRenderer as Renderer>::Target, renderer: &DeferredRenderer, camera: &dyn Camera) {
+        if let Some(fog_settings) = &self.fog_settings {
+            renderer.fog(render_target, camera, fog_settings);
+        }
+    }
+
+    /// Replace the ambient sounds of the audio engine with the sound sources
+    /// of this map.
+    pub fn register_sound_sources(&self, audio_engine: &mut AudioEngine, game_file_loader: &mut GameFileLoader) {
+        audio_engine.clear_ambient_sounds();
+        self.sound_sources
+            .iter()
+            .for_each(|sound_source| sound_source.register(audio_engine, game_file_loader));
+    }
+
+    pub fn register_effect_sources(
+        &self,
+        particle_holder: &mut ParticleHolder,
+        game_file_loader: &mut GameFileLoader,
+        texture_loader: &mut TextureLoader,
+    ) {
+        let emitters = self
+            .effect_sources
+            .iter()
+            .filter_map(|effect_source| effect_source.to_emitter(game_file_loader, texture_loader))
+            .collect();
+
+        particle_holder.set_emitters(emitters);
+    }
+
     #[cfg(feature = "debug")]
     pub fn to_prototype_window(&self) -> &dyn PrototypeWindow {
         &self.map_data
@@ -486,3 +697,125 @@ impl Map {
         }
     }
 }
+
+// Values taken from rAthena.
+const MOVE_COST: usize = 10;
+const DIAGONAL_MOVE_COST: usize = 14;
+
+/// A* search over the tile grid. Diagonal steps are only allowed if both
+/// tiles next to the step are walkable, so paths never cut corners.
+fn find_path(is_walkable: impl Fn(Vector2<usize>) -> bool, from: Vector2<usize>, to: Vector2<usize>) -> Option<Vec<Vector2<usize>>> {
+    use pathfinding::prelude::astar;
+
+    const DIRECTIONS: [(isize, isize); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, 1), (1, -1), (-1, -1)];
+
+    let offset = |position: Vector2<usize>, x: isize, y: isize| {
+        Some(Vector2::new(
+            position.x.checked_add_signed(x)?,
+            position.y.checked_add_signed(y)?,
+        ))
+    };
+
+    let successors = |position: &Vector2<usize>| {
+        let position = *position;
+
+        DIRECTIONS
+            .iter()
+            .filter_map(|&(x, y)| {
+                let next = offset(position, x, y).filter(|next| is_walkable(*next))?;
+
+                match x != 0 && y != 0 {
+                    true => {
+                        let corners_walkable =
+                            offset(position, x, 0).is_some_and(&is_walkable) && offset(position, 0, y).is_some_and(&is_walkable);
+                        corners_walkable.then_some((next, DIAGONAL_MOVE_COST))
+                    }
+                    false => Some((next, MOVE_COST)),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let heuristic = |position: &Vector2<usize>| {
+        let distance_x = position.x.abs_diff(to.x);
+        let distance_y = position.y.abs_diff(to.y);
+
+        let straight_moves = distance_x.abs_diff(distance_y);
+        let diagonal_moves = distance_x.min(distance_y);
+
+        DIAGONAL_MOVE_COST * diagonal_moves + MOVE_COST * straight_moves
+    };
+
+    astar(&from, successors, heuristic, |position| *position == to).map(|(path, _cost)| path)
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+
+    use super::{find_path, DIAGONAL_MOVE_COST, MOVE_COST};
+
+    /// Build a walkability check from rows of `.` (walkable) and `#` (blocked).
+    fn grid(rows: &'static [&'static str]) -> impl Fn(Vector2<usize>) -> bool {
+        move |position| {
+            rows.get(position.y)
+                .and_then(|row| row.as_bytes().get(position.x))
+                .is_some_and(|tile| *tile == b'.')
+        }
+    }
+
+    fn path_cost(path: &[Vector2<usize>]) -> usize {
+        path.windows(2)
+            .map(|step| match step[0].x != step[1].x && step[0].y != step[1].y {
+                true => DIAGONAL_MOVE_COST,
+                false => MOVE_COST,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn straight_path() {
+        let path = find_path(grid(&["....."]), Vector2::new(0, 0), Vector2::new(4, 0)).unwrap();
+
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.first(), Some(&Vector2::new(0, 0)));
+        assert_eq!(path.last(), Some(&Vector2::new(4, 0)));
+    }
+
+    #[test]
+    fn shortest_path_around_wall() {
+        let rows = &[
+            "..........", //
+            "..######..",
+            "..........",
+        ];
+
+        // NOTE: Start and destination are both closer to the left end of the wall, so
+        // going around it takes 7 steps instead of 11 around the right end.
+        let path = find_path(grid(rows), Vector2::new(3, 2), Vector2::new(4, 0)).unwrap();
+
+        assert_eq!(path_cost(&path), 7 * MOVE_COST);
+    }
+
+    #[test]
+    fn no_corner_cutting() {
+        let rows = &[
+            ".#", //
+            "..",
+        ];
+
+        let path = find_path(grid(rows), Vector2::new(0, 0), Vector2::new(1, 1)).unwrap();
+
+        assert_eq!(path, [Vector2::new(0, 0), Vector2::new(0, 1), Vector2::new(1, 1)]);
+    }
+
+    #[test]
+    fn unreachable() {
+        let rows = &[
+            ".#.", //
+            ".#.",
+        ];
+
+        assert_eq!(find_path(grid(rows), Vector2::new(0, 0), Vector2::new(2, 0)), None);
+    }
+}
@@ -52,6 +52,21 @@ pub struct Tile {
 }
 
 impl Tile {
+    /// Create a tile from heights and type flags that were already processed,
+    /// e.g. when loading a map from the asset cache.
+    pub fn new(heights: [f32; 4], type_flags: u8) -> Self {
+        let [upper_left_height, upper_right_height, lower_left_height, lower_right_height] = heights;
+
+        Self {
+            upper_left_height,
+            upper_right_height,
+            lower_left_height,
+            lower_right_height,
+            tile_type: TileType(type_flags),
+            _skip: [0; 3],
+        }
+    }
+
     pub fn is_walkable(&self) -> bool {
         self.tile_type.is_walkable()
     }
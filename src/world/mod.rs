@@ -1,15 +1,25 @@
+mod cast;
 mod effect;
 mod entity;
 mod light;
+mod loot;
 mod map;
 mod model;
 mod object;
+mod ping;
+mod search;
 mod sound;
+mod targeting;
 
+pub use self::cast::*;
 pub use self::effect::*;
 pub use self::entity::*;
 pub use self::light::*;
+pub use self::loot::*;
 pub use self::map::*;
 pub use self::model::*;
 pub use self::object::*;
+pub use self::ping::*;
+pub use self::search::*;
 pub use self::sound::*;
+pub use self::targeting::*;
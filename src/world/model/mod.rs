@@ -7,22 +7,34 @@ use derive_new::new;
 use procedural::PrototypeElement;
 
 pub use self::node::{BoundingBox, Node, OrientedBox};
-use crate::graphics::{Camera, GeometryRenderer, Renderer, Transform};
 #[cfg(feature = "debug")]
-use crate::graphics::{Color, DeferredRenderer};
+use crate::graphics::Color;
+use crate::graphics::{Camera, DeferredRenderer, GeometryRenderer, Renderer, Transform, TransparentLighting};
 #[cfg(feature = "debug")]
 use crate::loaders::ModelData;
 use crate::network::ClientTick;
 
 #[derive(PrototypeElement, new)]
 pub struct Model {
-    pub root_node: Node,
+    // NOTE: RSM2 models can have multiple root nodes.
+    pub root_nodes: Vec<Node>,
     pub bounding_box: BoundingBox,
+    /// Opacity of the whole model. Models that aren't fully opaque are
+    /// blended over the lit scene instead of being part of the G-buffer.
+    pub alpha: f32,
     #[cfg(feature = "debug")]
     pub model_data: ModelData,
 }
 
 impl Model {
+    pub fn is_transparent(&self) -> bool {
+        self.alpha < 1.0
+    }
+
+    pub fn is_animated(&self) -> bool {
+        self.root_nodes.iter().any(Node::has_animation)
+    }
+
     pub fn render_geometry<T>(
         &self,
         render_target: &mut T::Target,
@@ -34,8 +46,33 @@ impl Model {
     ) where
         T: Renderer + GeometryRenderer,
     {
-        self.root_node
-            .render_geometry(render_target, renderer, camera, root_transform, client_tick, time);
+        self.root_nodes
+            .iter()
+            .for_each(|root_node| root_node.render_geometry(render_target, renderer, camera, root_transform, client_tick, time));
+    }
+
+    pub fn render_transparent_geometry(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        root_transform: &Transform,
+        client_tick: ClientTick,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        self.root_nodes.iter().for_each(|root_node| {
+            root_node.render_transparent_geometry(
+                render_target,
+                renderer,
+                camera,
+                root_transform,
+                client_tick,
+                self.alpha,
+                lighting,
+                time,
+            )
+        });
     }
 
     #[cfg(feature = "debug")]
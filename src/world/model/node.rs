@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
-use cgmath::{Array, Matrix4, SquareMatrix, Vector3, Vector4};
+use cgmath::{Array, Matrix4, SquareMatrix, Vector3, Vector4, VectorSpace};
 use derive_new::new;
 use procedural::{profile, PrototypeElement};
 use vulkano::buffer::Subbuffer;
 use vulkano::image::view::ImageView;
 
-use crate::graphics::{Camera, GeometryRenderer, ModelVertex, Renderer, Transform};
-use crate::loaders::RotationKeyframeData;
+use crate::graphics::{Camera, DeferredRenderer, GeometryRenderer, ModelVertex, Renderer, SamplerCategory, Transform, TransparentLighting};
+use crate::loaders::{PositionKeyframeData, RotationKeyframeData, ScaleKeyframeData};
 use crate::network::ClientTick;
 use crate::system::multiply_matrix4_and_vector3;
 
@@ -112,37 +112,74 @@ pub struct Node {
     pub textures: Vec<Arc<ImageView>>,
     pub child_nodes: Vec<Node>,
     pub rotation_keyframes: Vec<RotationKeyframeData>,
+    pub scale_keyframes: Vec<ScaleKeyframeData>,
+    pub position_keyframes: Vec<PositionKeyframeData>,
 }
 
-impl Node {
-    fn animaton_matrix(&self, client_tick: ClientTick) -> Matrix4<f32> {
-        let last_step = self.rotation_keyframes.last().unwrap();
-        let animation_tick = client_tick.0 % last_step.frame;
+/// Find the keyframes surrounding the current animation tick and how far the
+/// animation has progressed between them. Keyframes are expected to be sorted
+/// and non-empty.
+fn surrounding_keyframes<T>(keyframes: &[T], frame: impl Fn(&T) -> u32, client_tick: ClientTick) -> (&T, &T, f32) {
+    let first_keyframe = &keyframes[0];
+    let last_frame = frame(keyframes.last().unwrap());
 
-        let mut last_keyframe_index = 0;
-        while self.rotation_keyframes[last_keyframe_index + 1].frame < animation_tick {
-            last_keyframe_index += 1;
-        }
+    if keyframes.len() == 1 || last_frame == 0 {
+        return (first_keyframe, first_keyframe, 0.0);
+    }
 
-        let last_step = &self.rotation_keyframes[last_keyframe_index];
-        let next_step = &self.rotation_keyframes[(last_keyframe_index + 1) % self.rotation_keyframes.len()];
+    let animation_tick = client_tick.0 % last_frame;
+    let next_index = keyframes
+        .iter()
+        .position(|keyframe| frame(keyframe) > animation_tick)
+        .unwrap_or(keyframes.len() - 1);
+    let last_keyframe = &keyframes[next_index.saturating_sub(1)];
+    let next_keyframe = &keyframes[next_index];
 
-        let total = next_step.frame - last_step.frame;
-        let offset = animation_tick - last_step.frame;
+    let total = frame(next_keyframe).saturating_sub(frame(last_keyframe));
+    let offset = animation_tick.saturating_sub(frame(last_keyframe));
 
-        let animation_elapsed = (1.0 / total as f32) * offset as f32;
-        let current_rotation = last_step.quaternions.nlerp(next_step.quaternions, animation_elapsed);
+    let animation_elapsed = match total {
+        0 => 0.0,
+        total => (offset as f32 / total as f32).min(1.0),
+    };
 
-        current_rotation.into()
-    }
+    (last_keyframe, next_keyframe, animation_elapsed)
+}
 
-    #[profile]
-    pub fn world_matrix(&self, transform: &Transform, client_tick: ClientTick) -> Matrix4<f32> {
-        let animation_rotation_matrix = match self.rotation_keyframes.is_empty() {
+impl Node {
+    fn animation_matrix(&self, client_tick: ClientTick) -> Matrix4<f32> {
+        let position_matrix = match self.position_keyframes.is_empty() {
+            true => Matrix4::identity(),
+            false => {
+                let (last_step, next_step, elapsed) =
+                    surrounding_keyframes(&self.position_keyframes, |keyframe| keyframe.frame, client_tick);
+                Matrix4::from_translation(last_step.position.lerp(next_step.position, elapsed))
+            }
+        };
+
+        let rotation_matrix = match self.rotation_keyframes.is_empty() {
             true => Matrix4::identity(),
-            false => self.animaton_matrix(client_tick),
+            false => {
+                let (last_step, next_step, elapsed) =
+                    surrounding_keyframes(&self.rotation_keyframes, |keyframe| keyframe.frame, client_tick);
+                last_step.quaternions.nlerp(next_step.quaternions, elapsed).into()
+            }
         };
 
+        let scale_matrix = match self.scale_keyframes.is_empty() {
+            true => Matrix4::identity(),
+            false => {
+                let (last_step, next_step, elapsed) = surrounding_keyframes(&self.scale_keyframes, |keyframe| keyframe.frame, client_tick);
+                let scale = last_step.scale.lerp(next_step.scale, elapsed);
+                Matrix4::from_nonuniform_scale(scale.x, scale.y, scale.z)
+            }
+        };
+
+        position_matrix * rotation_matrix * scale_matrix
+    }
+
+    /// Transform of the whole model in the world, shared by all of its nodes.
+    fn model_matrix(transform: &Transform) -> Matrix4<f32> {
         let rotation_matrix = Matrix4::from_angle_z(-transform.rotation.z)
             * Matrix4::from_angle_x(-transform.rotation.x)
             * Matrix4::from_angle_y(transform.rotation.y);
@@ -156,8 +193,39 @@ impl Node {
                 Vector4::new(0.0, 0.0, 1.0, 0.0),
                 Vector4::new(0.0, 0.0, 0.0, 1.0),
             )
-            * self.transform_matrix
-            * animation_rotation_matrix
+    }
+
+    fn is_animated(&self) -> bool {
+        !self.rotation_keyframes.is_empty() || !self.scale_keyframes.is_empty() || !self.position_keyframes.is_empty()
+    }
+
+    /// Returns `true` if the node or any of its children is animated.
+    pub fn has_animation(&self) -> bool {
+        self.is_animated() || self.child_nodes.iter().any(Node::has_animation)
+    }
+
+    /// World matrix of the node at the current animation tick. Child nodes are
+    /// placed relative to their parent, so they follow its animation (e.g. the
+    /// blades of a rotating windmill).
+    #[profile]
+    pub fn world_matrix(&self, parent_matrix: &Matrix4<f32>, client_tick: ClientTick) -> Matrix4<f32> {
+        let animation_matrix = match self.is_animated() {
+            true => self.animation_matrix(client_tick),
+            false => Matrix4::identity(),
+        };
+
+        parent_matrix * self.transform_matrix * animation_matrix
+    }
+
+    /// Call `function` with every node of the tree and its current world
+    /// matrix, parents before their children.
+    fn for_each_node(&self, parent_matrix: &Matrix4<f32>, client_tick: ClientTick, function: &mut impl FnMut(&Node, Matrix4<f32>)) {
+        let world_matrix = self.world_matrix(parent_matrix, client_tick);
+        function(self, world_matrix);
+
+        self.child_nodes
+            .iter()
+            .for_each(|node| node.for_each_node(&world_matrix, client_tick, function));
     }
 
     #[profile("render node geometry")]
@@ -172,17 +240,46 @@ impl Node {
     ) where
         T: Renderer + GeometryRenderer,
     {
-        renderer.render_geometry(
-            render_target,
-            camera,
-            self.vertex_buffer.clone(),
-            &self.textures,
-            self.world_matrix(transform, client_tick),
-            time,
-        );
+        let model_matrix = Self::model_matrix(transform);
 
-        self.child_nodes
-            .iter()
-            .for_each(|node| node.render_geometry(render_target, renderer, camera, transform, client_tick, time));
+        self.for_each_node(&model_matrix, client_tick, &mut |node, world_matrix| {
+            renderer.render_geometry(
+                render_target,
+                camera,
+                node.vertex_buffer.clone(),
+                &node.textures,
+                SamplerCategory::Model,
+                world_matrix,
+                time,
+            );
+        });
+    }
+
+    #[profile("render transparent node geometry")]
+    pub fn render_transparent_geometry(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        transform: &Transform,
+        client_tick: ClientTick,
+        alpha: f32,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        let model_matrix = Self::model_matrix(transform);
+
+        self.for_each_node(&model_matrix, client_tick, &mut |node, world_matrix| {
+            renderer.render_transparent_geometry(
+                render_target,
+                camera,
+                node.vertex_buffer.clone(),
+                &node.textures,
+                world_matrix,
+                alpha,
+                lighting,
+                time,
+            );
+        });
     }
 }
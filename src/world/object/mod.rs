@@ -25,6 +25,19 @@ impl Object {
             .render_geometry(render_target, renderer, camera, &self.transform, client_tick, time);
     }
 
+    pub fn render_transparent_geometry(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        client_tick: ClientTick,
+        lighting: &TransparentLighting,
+        time: f32,
+    ) {
+        self.model
+            .render_transparent_geometry(render_target, renderer, camera, &self.transform, client_tick, lighting, time);
+    }
+
     //#[profile]
     pub fn get_bounding_box_matrix(&self) -> Matrix4<f32> {
         self.model.get_bounding_box_matrix(&self.transform)
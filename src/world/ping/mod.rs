@@ -0,0 +1,111 @@
+use cgmath::{Vector2, Vector3};
+use procedural::profile;
+
+use crate::graphics::{Camera, Color, DeferredRenderer, IndicatorRenderer, Renderer};
+use crate::interface::{ScreenPosition, ScreenSize, StreamerMode};
+use crate::world::Map;
+
+pub struct Ping {
+    sender: String,
+    position: Vector2<usize>,
+    timer: f32,
+}
+
+impl Ping {
+    const DURATION: f32 = 8.0;
+    const FADE_DURATION: f32 = 1.0;
+
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.timer -= delta_time;
+        self.timer > 0.0
+    }
+
+    fn get_color(&self, color: Color) -> Color {
+        // Pulse while the ping is active and fade out during the last second.
+        let pulse = 0.75 + (self.timer * 6.0).sin() * 0.25;
+        let fade = (self.timer / Self::FADE_DURATION).min(1.0);
+        color.multiply_alpha(pulse * fade)
+    }
+}
+
+/// Ground markers placed by party members.
+#[derive(Default)]
+pub struct PingHolder {
+    pings: Vec<Ping>,
+}
+
+impl PingHolder {
+    pub fn add_ping(&mut self, sender: &str, position: Vector2<usize>, streamer_mode: &StreamerMode) {
+        let sender = streamer_mode.hide_name(sender).into_owned();
+
+        // NOTE: Only keep the latest ping of every party member.
+        self.pings.retain(|ping| ping.sender != sender);
+        self.pings.push(Ping {
+            sender,
+            position,
+            timer: Ping::DURATION,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.pings.clear();
+    }
+
+    #[profile("update pings")]
+    pub fn update(&mut self, delta_time: f32) {
+        self.pings.retain_mut(|ping| ping.update(delta_time));
+    }
+
+    #[profile("render ping indicators")]
+    pub fn render_indicators<T>(&self, render_target: &mut T::Target, renderer: &T, camera: &dyn Camera, map: &Map, color: Color)
+    where
+        T: Renderer + IndicatorRenderer,
+    {
+        self.pings.iter().for_each(|ping| {
+            if map.x_in_bounds(ping.position.x) && map.y_in_bounds(ping.position.y) {
+                map.render_walk_indicator(render_target, renderer, camera, ping.get_color(color), ping.position);
+            }
+        });
+    }
+
+    #[profile("render ping labels")]
+    pub fn render_labels(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        map: &Map,
+        color: Color,
+    ) {
+        const FONT_SIZE: f32 = 12.0;
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+
+        self.pings
+            .iter()
+            .filter(|ping| map.x_in_bounds(ping.position.x) && map.y_in_bounds(ping.position.y))
+            .for_each(|ping| {
+                let position = map.get_world_position(ping.position) + Vector3::new(0.0, 5.0, 0.0);
+                let clip_space_position = (projection_matrix * view_matrix) * position.extend(1.0);
+                let screen_position = Vector2::new(
+                    clip_space_position.x / clip_space_position.w + 1.0,
+                    clip_space_position.y / clip_space_position.w + 1.0,
+                );
+                let screen_position = screen_position / 2.0;
+                let final_position = ScreenPosition {
+                    left: screen_position.x * window_size.width - ping.sender.len() as f32 * FONT_SIZE / 4.0,
+                    top: screen_position.y * window_size.height - FONT_SIZE,
+                };
+
+                renderer.render_text(
+                    render_target,
+                    &ping.sender,
+                    final_position + ScreenPosition::uniform(1.0),
+                    Color::monochrome_u8(0),
+                    FONT_SIZE,
+                );
+                renderer.render_text(render_target, &ping.sender, final_position, ping.get_color(color), FONT_SIZE);
+            });
+    }
+}
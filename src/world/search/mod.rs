@@ -0,0 +1,156 @@
+use cgmath::{InnerSpace, Vector2, Vector3};
+use procedural::profile;
+
+use crate::graphics::{Camera, Color, DeferredRenderer, Renderer};
+use crate::interface::{ScreenPosition, ScreenSize, TrackedState};
+use crate::world::{Entity, EntityType};
+
+/// Temporary filter that highlights entities by name and type, so specific
+/// monsters or NPCs are easy to find on crowded maps.
+#[derive(Default)]
+pub struct EntitySearch {
+    name: TrackedState<String>,
+    entity_type: TrackedState<Option<EntityType>>,
+}
+
+impl EntitySearch {
+    pub fn get_name_state(&self) -> TrackedState<String> {
+        self.name.clone()
+    }
+
+    pub fn get_entity_type_state(&self) -> TrackedState<Option<EntityType>> {
+        self.entity_type.clone()
+    }
+
+    pub fn clear(&mut self) {
+        self.name.set(String::new());
+        self.entity_type.set(None);
+    }
+
+    /// Snapshot of the current search that can be shared with the render
+    /// threads. Returns [`None`] if the search is empty.
+    pub fn get_filter(&self) -> Option<EntityFilter> {
+        let name = self.name.borrow().trim().to_lowercase();
+        let entity_type = self.entity_type.get();
+
+        (!name.is_empty() || entity_type.is_some()).then_some(EntityFilter { name, entity_type })
+    }
+}
+
+pub struct EntityFilter {
+    name: String,
+    entity_type: Option<EntityType>,
+}
+
+impl EntityFilter {
+    const DIMMED_BRIGHTNESS: u8 = 70;
+
+    pub fn matches(&self, entity: &Entity) -> bool {
+        let type_matches = self.entity_type.map_or(true, |entity_type| entity_type == entity.get_entity_type());
+        // NOTE: Entities without details can't match a name yet. The details are
+        // requested while a search is active.
+        let name_matches = self.name.is_empty()
+            || entity
+                .get_details()
+                .is_some_and(|details| details.to_lowercase().contains(&self.name));
+
+        type_matches && name_matches
+    }
+
+    pub fn get_color(&self, entity: &Entity) -> Color {
+        match self.matches(entity) {
+            true => Color::monochrome_u8(255),
+            false => Color::monochrome_u8(Self::DIMMED_BRIGHTNESS),
+        }
+    }
+
+    /// Render an arrow at the edge of the screen for every matching entity that
+    /// is currently off-screen.
+    #[profile("render entity search arrows")]
+    pub fn render_arrows(
+        &self,
+        render_target: &mut <DeferredRenderer as Renderer>::Target,
+        renderer: &DeferredRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        entities: &[Entity],
+        color: Color,
+    ) {
+        const FONT_SIZE: f32 = 12.0;
+        const EDGE_MARGIN: f32 = 0.9;
+        const DOT_SIZE: f32 = 4.0;
+        const DOT_SPACING: f32 = 5.0;
+        const BARB_ANGLE: f32 = 0.6;
+
+        let (view_matrix, projection_matrix) = camera.view_projection_matrices();
+        let to_screen = |position: Vector2<f32>| ScreenPosition {
+            left: (position.x + 1.0) / 2.0 * window_size.width,
+            top: (position.y + 1.0) / 2.0 * window_size.height,
+        };
+        let render_dot = |render_target: &mut <DeferredRenderer as Renderer>::Target, position: ScreenPosition| {
+            let offset = ScreenPosition::uniform(DOT_SIZE / 2.0);
+            renderer.render_rectangle(render_target, position - offset, ScreenSize::uniform(DOT_SIZE), color);
+        };
+
+        // NOTE: The first entity is always the player.
+        for entity in entities.iter().skip(1).filter(|entity| self.matches(entity)) {
+            let position = entity.get_position() + Vector3::new(0.0, 5.0, 0.0);
+            let clip_space_position = (projection_matrix * view_matrix) * position.extend(1.0);
+            // NOTE: Dividing by the absolute value keeps points behind the camera from
+            // being mirrored, so the arrow still points in the right direction.
+            let device_position = Vector2::new(clip_space_position.x, clip_space_position.y) / clip_space_position.w.abs();
+
+            let on_screen = clip_space_position.w > 0.0 && device_position.x.abs() <= 1.0 && device_position.y.abs() <= 1.0;
+            if on_screen {
+                continue;
+            }
+
+            let largest_component = device_position.x.abs().max(device_position.y.abs());
+            if largest_component <= f32::EPSILON {
+                continue;
+            }
+
+            let tip = device_position / largest_component * EDGE_MARGIN;
+            let tip_position = to_screen(tip);
+            let direction = Vector2::new(
+                tip_position.left - window_size.width / 2.0,
+                tip_position.top - window_size.height / 2.0,
+            )
+            .normalize();
+
+            let rotate = |angle: f32| {
+                let (sine, cosine) = angle.sin_cos();
+                Vector2::new(
+                    direction.x * cosine - direction.y * sine,
+                    direction.x * sine + direction.y * cosine,
+                )
+            };
+
+            for line_direction in [direction, rotate(BARB_ANGLE), rotate(-BARB_ANGLE)] {
+                for step in 0..4 {
+                    let offset = line_direction * (step as f32 * DOT_SPACING);
+                    render_dot(render_target, ScreenPosition {
+                        left: tip_position.left - offset.x,
+                        top: tip_position.top - offset.y,
+                    });
+                }
+            }
+
+            if let Some(name) = entity.get_details() {
+                let label_position = ScreenPosition {
+                    left: tip_position.left - direction.x * 30.0 - name.len() as f32 * FONT_SIZE / 4.0,
+                    top: tip_position.top - direction.y * 30.0 - FONT_SIZE / 2.0,
+                };
+
+                renderer.render_text(
+                    render_target,
+                    name,
+                    label_position + ScreenPosition::uniform(1.0),
+                    Color::monochrome_u8(0),
+                    FONT_SIZE,
+                );
+                renderer.render_text(render_target, name, label_position, color, FONT_SIZE);
+            }
+        }
+    }
+}
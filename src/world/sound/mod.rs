@@ -2,8 +2,12 @@ use cgmath::Vector3;
 use procedural::{PrototypeElement, PrototypeWindow};
 use ragnarok_procedural::ByteConvertable;
 
+use crate::audio::AudioEngine;
+#[cfg(feature = "debug")]
+use crate::debug::*;
 #[cfg(feature = "debug")]
 use crate::graphics::{Camera, MarkerRenderer, Renderer};
+use crate::loaders::GameFileLoader;
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 
@@ -28,6 +32,20 @@ impl SoundSource {
         self.position += offset;
     }
 
+    pub fn register(&self, audio_engine: &mut AudioEngine, game_file_loader: &mut GameFileLoader) {
+        match audio_engine.get_sound_effect(&self.sound_file, game_file_loader) {
+            Ok(key) => audio_engine.add_ambient_sound(key, self.volume, self.position, self.range, self.cycle),
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!(
+                    "[{RED}error{NONE}] failed to load sound {MAGENTA}{}{NONE} of sound source {MAGENTA}{}{NONE}: {_error}",
+                    self.sound_file,
+                    self.name
+                );
+            }
+        }
+    }
+
     #[cfg(feature = "debug")]
     pub fn render_marker<T>(
         &self,
@@ -0,0 +1,262 @@
+use cgmath::Vector2;
+use serde::{Deserialize, Serialize};
+
+use crate::graphics::PickerTarget;
+use crate::network::EntityId;
+use crate::world::{Entity, EntityType, GroundItemHolder};
+
+/// Kind of a target that can be clicked in the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetKind {
+    Npc,
+    Monster,
+    Player,
+    Warp,
+    GroundItem,
+}
+
+impl TargetKind {
+    fn from_entity_type(entity_type: EntityType) -> Option<Self> {
+        match entity_type {
+            EntityType::Npc => Some(Self::Npc),
+            EntityType::Monster => Some(Self::Monster),
+            EntityType::Player => Some(Self::Player),
+            EntityType::Warp => Some(Self::Warp),
+            EntityType::Hidden => None,
+        }
+    }
+
+    /// Players and warps don't cover the tile they are standing on, so clicking
+    /// the tile should still walk there instead of interacting with them.
+    fn covers_tile(self) -> bool {
+        !matches!(self, Self::Player | Self::Warp)
+    }
+}
+
+/// Order in which overlapping targets are picked. Kinds that are not in the
+/// list come last.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TargetPriority(Vec<TargetKind>);
+
+impl TargetPriority {
+    pub fn npcs_first() -> Self {
+        use TargetKind::*;
+        Self(vec![Npc, Monster, GroundItem, Warp, Player])
+    }
+
+    pub fn monsters_first() -> Self {
+        use TargetKind::*;
+        Self(vec![Monster, Npc, GroundItem, Warp, Player])
+    }
+
+    pub fn items_first() -> Self {
+        use TargetKind::*;
+        Self(vec![GroundItem, Monster, Npc, Warp, Player])
+    }
+
+    fn rank(&self, kind: TargetKind) -> usize {
+        self.0.iter().position(|other| *other == kind).unwrap_or(self.0.len())
+    }
+}
+
+impl Default for TargetPriority {
+    fn default() -> Self {
+        Self::npcs_first()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StackedTarget {
+    Entity(EntityId),
+    GroundItem(EntityId),
+}
+
+/// All targets on the tile under the cursor, sorted by priority. Entities
+/// standing on the same tile cover each other, so only the topmost one is in
+/// the picker buffer. The stack makes the others reachable.
+#[derive(Default)]
+pub struct TargetStack {
+    targets: Vec<StackedTarget>,
+    selected: usize,
+}
+
+impl TargetStack {
+    pub fn update(
+        &mut self,
+        mouse_target: Option<&PickerTarget>,
+        entities: &[Entity],
+        ground_item_holder: &GroundItemHolder,
+        priority: &TargetPriority,
+    ) {
+        let position = match mouse_target {
+            Some(PickerTarget::Tile { x, y }) => Some(Vector2::new(*x as usize, *y as usize)),
+            Some(PickerTarget::Entity(entity_id)) => entities
+                .iter()
+                .find(|entity| entity.get_entity_id() == *entity_id)
+                .map(|entity| entity.get_grid_position()),
+            #[cfg(feature = "debug")]
+            Some(PickerTarget::Marker(_)) => None,
+            None => None,
+        };
+
+        let mut candidates: Vec<(TargetKind, StackedTarget)> = Vec::new();
+
+        if let Some(position) = position {
+            // NOTE: The first entity is the player, which is never a target.
+            let entities = entities
+                .iter()
+                .skip(1)
+                .filter(|entity| entity.get_grid_position() == position)
+                .filter_map(|entity| {
+                    TargetKind::from_entity_type(entity.get_entity_type()).map(|kind| (kind, StackedTarget::Entity(entity.get_entity_id())))
+                });
+            let ground_items = ground_item_holder
+                .items_at(position)
+                .map(|entity_id| (TargetKind::GroundItem, StackedTarget::GroundItem(entity_id)));
+
+            candidates.extend(entities.chain(ground_items));
+        }
+
+        let tile_picked = matches!(mouse_target, Some(PickerTarget::Tile { .. }));
+        self.set_targets(stack_targets(candidates, tile_picked, priority));
+    }
+
+    fn set_targets(&mut self, targets: Vec<StackedTarget>) {
+        // NOTE: Keep the cycled target while the same stack is hovered.
+        if targets != self.targets {
+            self.targets = targets;
+            self.selected = 0;
+        }
+    }
+
+    /// Select the next target in the stack, wrapping around at the end.
+    pub fn cycle(&mut self) {
+        if !self.targets.is_empty() {
+            self.selected = (self.selected + 1) % self.targets.len();
+        }
+    }
+
+    pub fn selected(&self) -> Option<StackedTarget> {
+        self.targets.get(self.selected).copied()
+    }
+
+    /// Position of the selected target and the size of the stack, if there is
+    /// more than one target to cycle through.
+    pub fn cycle_position(&self) -> Option<(usize, usize)> {
+        (self.targets.len() > 1).then_some((self.selected + 1, self.targets.len()))
+    }
+}
+
+/// Sort the targets on a tile by priority. If the tile itself was picked,
+/// targets that don't cover the tile are left out, so the click walks there.
+fn stack_targets(
+    candidates: impl IntoIterator<Item = (TargetKind, StackedTarget)>,
+    tile_picked: bool,
+    priority: &TargetPriority,
+) -> Vec<StackedTarget> {
+    let mut targets: Vec<(TargetKind, StackedTarget)> = candidates
+        .into_iter()
+        .filter(|(kind, _)| !tile_picked || kind.covers_tile())
+        .collect();
+
+    // NOTE: The sort is stable, so targets of the same kind keep their order.
+    targets.sort_by_key(|(kind, _)| priority.rank(*kind));
+    targets.into_iter().map(|(_, target)| target).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{stack_targets, StackedTarget, TargetKind, TargetPriority, TargetStack};
+    use crate::network::EntityId;
+
+    const NPC: StackedTarget = StackedTarget::Entity(EntityId(1));
+    const MONSTER: StackedTarget = StackedTarget::Entity(EntityId(2));
+    const PLAYER: StackedTarget = StackedTarget::Entity(EntityId(3));
+    const ITEM: StackedTarget = StackedTarget::GroundItem(EntityId(4));
+
+    fn candidates() -> Vec<(TargetKind, StackedTarget)> {
+        vec![
+            (TargetKind::Player, PLAYER),
+            (TargetKind::Npc, NPC),
+            (TargetKind::GroundItem, ITEM),
+            (TargetKind::Monster, MONSTER),
+        ]
+    }
+
+    #[test]
+    fn rank_by_priority() {
+        let priority = TargetPriority::items_first();
+
+        assert!(priority.rank(TargetKind::GroundItem) < priority.rank(TargetKind::Monster));
+        assert!(priority.rank(TargetKind::Monster) < priority.rank(TargetKind::Npc));
+    }
+
+    #[test]
+    fn missing_kinds_come_last() {
+        let priority = TargetPriority(vec![TargetKind::Monster]);
+
+        assert_eq!(priority.rank(TargetKind::Monster), 0);
+        assert_eq!(priority.rank(TargetKind::Npc), 1);
+    }
+
+    #[test]
+    fn stack_by_priority() {
+        let targets = stack_targets(candidates(), false, &TargetPriority::monsters_first());
+
+        assert_eq!(targets, vec![MONSTER, NPC, ITEM, PLAYER]);
+    }
+
+    #[test]
+    fn picked_tile_skips_players() {
+        let targets = stack_targets(candidates(), true, &TargetPriority::npcs_first());
+
+        assert_eq!(targets, vec![NPC, MONSTER, ITEM]);
+    }
+
+    #[test]
+    fn picked_tile_with_only_players_walks() {
+        let targets = stack_targets(vec![(TargetKind::Player, PLAYER)], true, &TargetPriority::npcs_first());
+
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn cycle_wraps_around() {
+        let mut target_stack = TargetStack::default();
+        target_stack.set_targets(vec![NPC, MONSTER]);
+
+        assert_eq!(target_stack.selected(), Some(NPC));
+        assert_eq!(target_stack.cycle_position(), Some((1, 2)));
+
+        target_stack.cycle();
+        assert_eq!(target_stack.selected(), Some(MONSTER));
+        assert_eq!(target_stack.cycle_position(), Some((2, 2)));
+
+        target_stack.cycle();
+        assert_eq!(target_stack.selected(), Some(NPC));
+    }
+
+    #[test]
+    fn cycle_empty_stack() {
+        let mut target_stack = TargetStack::default();
+        target_stack.cycle();
+
+        assert_eq!(target_stack.selected(), None);
+        assert_eq!(target_stack.cycle_position(), None);
+    }
+
+    #[test]
+    fn same_stack_keeps_selection() {
+        let mut target_stack = TargetStack::default();
+        target_stack.set_targets(vec![NPC, MONSTER]);
+        target_stack.cycle();
+
+        target_stack.set_targets(vec![NPC, MONSTER]);
+        assert_eq!(target_stack.selected(), Some(MONSTER));
+
+        target_stack.set_targets(vec![MONSTER, ITEM]);
+        assert_eq!(target_stack.selected(), Some(MONSTER));
+        assert_eq!(target_stack.cycle_position(), Some((1, 2)));
+    }
+}